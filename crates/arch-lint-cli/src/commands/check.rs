@@ -1,91 +1,389 @@
 //! Check command implementation.
 
 use anyhow::{Context, Result};
-use arch_lint_core::{Analyzer, Config};
-use arch_lint_rules::{
-    recommended_rules, HandlerComplexity, NoErrorSwallowing, NoSilentResultDrop, NoSyncIo,
-    NoUnwrapExpect, RequireThiserror, RequireTracing, TracingEnvInit,
-};
-use std::path::Path;
+use arch_lint_core::declarative::{self, DeclarativeRules};
+use arch_lint_core::{Analyzer, Config, ProjectRule, ProjectRuleBox, Rule, RuleRegistry};
+use arch_lint_rules::{preset_rules, project_rules, rule_registry};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-use crate::OutputFormat;
+use super::gate::Gate;
+use super::output::{self, Layout, Snippets};
+use crate::{ColumnBase, FailOn, OutputFormat};
+
+/// Options for the check command, as parsed from CLI flags.
+#[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CheckOptions {
+    /// Output format.
+    pub format: OutputFormat,
+    /// First column number in JSON output (`--column-base`).
+    pub column_base: ColumnBase,
+    /// Comma-separated rule names or codes to run instead of the preset.
+    pub rules_filter: Option<String>,
+    /// Comma-separated rule names or codes to remove from the active set.
+    pub skip_rules: Option<String>,
+    /// Additional exclude patterns.
+    pub exclude: Vec<String>,
+    /// Additional cfg options assumed enabled (`--cfg`).
+    pub cfg: Vec<String>,
+    /// Whether to use the incremental cache.
+    pub use_cache: bool,
+    /// Severity threshold override (`--fail-on`).
+    pub fail_on: Option<FailOn>,
+    /// Maximum tolerated warnings (`--max-warnings`).
+    pub max_warnings: Option<usize>,
+    /// Treat warnings as errors for the exit code (`--deny-warnings`).
+    pub deny_warnings: bool,
+    /// Sorting and grouping of text output.
+    pub layout: Layout,
+    /// Report absolute paths instead of root-relative ones.
+    pub absolute_paths: bool,
+    /// Show source lines under text-format violations.
+    pub snippets: bool,
+    /// Print per-rule violation counts after the report (`--stats`).
+    pub stats: bool,
+    /// Print where analysis time went (`--timings`).
+    pub timings: bool,
+}
 
 /// Runs the check command.
+///
+/// Returns `true` if the result fails the configured gate.
 pub fn run(
-    path: &Path,
-    format: OutputFormat,
-    rules_filter: Option<String>,
-    exclude: Vec<String>,
+    paths: &[PathBuf],
+    options: &CheckOptions,
     source: &crate::config_resolver::ConfigSource,
-) -> Result<()> {
-    let config = match source {
-        crate::config_resolver::ConfigSource::Default => Config::default(),
+) -> Result<bool> {
+    let config = load_config(source)?;
+    let gate = resolve_gate(options, &config)?;
+
+    let declarative = load_declarative(source)?;
+    let analyzer = build_analyzer(paths, config, declarative, options)?;
+    let (format, layout) = (options.format, options.layout);
+    let mut snippets = options
+        .snippets
+        .then(|| Snippets::new(vec![analyzer.root().to_path_buf()]));
+
+    tracing::info!("Analyzing {:?} with {} rules", paths, analyzer.rule_count());
+
+    let (summary, rule_counts) = if matches!(format, OutputFormat::Text) && layout.is_streamable() {
+        // Print as files finish instead of after the whole tree.
+        let mut rule_counts = BTreeMap::new();
+        let summary = analyzer
+            .analyze_streaming(|violation| {
+                output::print_violation(violation, snippets.as_mut());
+                if options.stats {
+                    *rule_counts.entry(violation.rule.clone()).or_insert(0) += 1;
+                }
+            })
+            .context("Analysis failed")?;
+        output::print_text_summary(&summary);
+        (summary, rule_counts)
+    } else {
+        let result = analyzer.analyze().context("Analysis failed")?;
+        output::print(
+            &result,
+            format,
+            options.column_base,
+            layout,
+            snippets.as_mut(),
+        )?;
+        (result.summary(), result.counts_by_rule())
+    };
+
+    if options.stats {
+        output::print_stats(&rule_counts, format);
+    }
+    if let Some(timings) = analyzer.timings() {
+        output::print_timings(&timings);
+    }
+    gate.report_breaches(&summary);
+    Ok(gate.fails(&summary))
+}
+
+/// Reads newline-separated paths from `source` (`-` for stdin), keeping
+/// those that exist as files. Blank lines and deleted files (as in a list
+/// of staged changes) are dropped.
+pub fn read_file_list(source: &Path) -> Result<Vec<PathBuf>> {
+    let content = if source == Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).context("Failed to read file list from stdin")?
+    } else {
+        std::fs::read_to_string(source)
+            .with_context(|| format!("Failed to read file list: {}", source.display()))?
+    };
+    Ok(content
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .collect())
+}
+
+/// Resolves the gate from the CLI flags and the config's `fail_on`,
+/// `deny_warnings`, and `[gate]` settings.
+pub fn resolve_gate(options: &CheckOptions, config: &Config) -> Result<Gate> {
+    Ok(Gate::resolve(
+        options.fail_on,
+        config.fail_on.as_deref(),
+        config.gate,
+        options.max_warnings,
+    )?
+    .deny_warnings(options.deny_warnings || config.deny_warnings))
+}
+
+/// Loads the Rust-engine config, falling back to defaults without a file.
+pub fn load_config(source: &crate::config_resolver::ConfigSource) -> Result<Config> {
+    match source {
+        crate::config_resolver::ConfigSource::Default => Ok(Config::default()),
+        crate::config_resolver::ConfigSource::CargoMetadata(manifest) => {
+            let content = arch_lint_core::cargo_metadata_config(manifest).with_context(|| {
+                format!("No [package.metadata.arch-lint] in {}", manifest.display())
+            })?;
+            Config::parse(&content).with_context(|| {
+                format!(
+                    "Failed to load config from Cargo metadata: {}",
+                    manifest.display()
+                )
+            })
+        }
         other => {
             // Invariant: non-Default variants always have a path
             let p = other.path().context("resolved config has no path")?;
             if source.is_global() {
                 tracing::info!("Using global config: {}", p.display());
             }
-            Config::from_file(p)
-                .with_context(|| format!("Failed to load config: {}", p.display()))?
+            Config::from_file(p).with_context(|| format!("Failed to load config: {}", p.display()))
         }
-    };
-
-    // Build analyzer
-    let mut builder = Analyzer::builder().root(path).config(config);
-
-    // Add exclude patterns
-    for pattern in exclude {
-        builder = builder.exclude(pattern);
     }
+}
 
-    // Add rules based on filter
-    let rules_to_add = if let Some(filter) = rules_filter {
-        let rule_names: Vec<&str> = filter.split(',').map(str::trim).collect();
-        filter_rules(&rule_names)
-    } else {
-        recommended_rules()
+/// Loads the config's declarative rules (`[[restrict-use]]`,
+/// `[[crate-layers]]`, ...) with the loader `check!()` uses; none without a
+/// config file.
+pub fn load_declarative(source: &crate::config_resolver::ConfigSource) -> Result<DeclarativeRules> {
+    let Some((content, format)) = source.read()? else {
+        return Ok(DeclarativeRules::default());
     };
+    let loaded = match source.path() {
+        Some(path) => declarative::load_file(path, &content, format),
+        None => declarative::load_all(&content, format),
+    };
+    loaded.context("Invalid declarative rules in config")
+}
 
-    for rule in rules_to_add {
+/// Builds the Rust analyzer for `paths` from `config`, the config's
+/// declarative rules and the CLI options.
+///
+/// Declarative rules run alongside the preset (or `--rules`) selection,
+/// as in `check!()`; `[rules.<name>] enabled = false` disables them.
+pub fn build_analyzer(
+    paths: &[PathBuf],
+    config: Config,
+    declarative: DeclarativeRules,
+    options: &CheckOptions,
+) -> Result<Analyzer> {
+    let preset = resolve_preset(config.preset.as_deref(), &config)?;
+    let mut config = config;
+    config.analyzer.cfg.extend(options.cfg.iter().cloned());
+    let mut builder = Analyzer::builder()
+        .roots(paths)
+        .config(config)
+        .cache(options.use_cache)
+        .absolute_paths(options.absolute_paths)
+        .timings(options.timings)
+        .excludes(&options.exclude)
+        .registry(select_rules(
+            preset.rules,
+            options.rules_filter.as_deref(),
+            options.skip_rules.as_deref(),
+        ));
+    for rule in select_project_rules(
+        options.rules_filter.as_deref(),
+        options.skip_rules.as_deref(),
+    ) {
+        builder = builder.project_rule_box(rule);
+    }
+    for rule in declarative.rules {
         builder = builder.rule_box(rule);
     }
+    for rule in declarative.project_rules {
+        builder = builder.project_rule_box(rule);
+    }
 
-    let analyzer = builder.build().context("Failed to build analyzer")?;
+    builder.build().context("Failed to build analyzer")
+}
 
-    tracing::info!("Analyzing {:?} with {} rules", path, analyzer.rule_count());
+/// The preset a run starts from.
+pub struct ActivePreset {
+    /// Preset name: built-in, or a custom `[presets.<name>]` table.
+    pub name: String,
+    /// The preset's rules.
+    pub rules: RuleRegistry,
+}
 
-    let result = analyzer.analyze().context("Analysis failed")?;
+/// Looks up the preset named in the config, defaulting to recommended.
+pub fn resolve_preset(name: Option<&str>, config: &Config) -> Result<ActivePreset> {
+    let name = name.unwrap_or("recommended");
+    let rules = preset_rules(name, config)?;
+    Ok(ActivePreset {
+        name: name.to_string(),
+        rules: rules.into(),
+    })
+}
 
-    // Output results
-    super::output::print(&result, format)?;
+/// Resolves the active rule set: `--rules` (or the preset's rules) selects
+/// the base set, then `--skip-rules` removes from it.
+///
+/// Names and codes that match no known rule are warned about and otherwise
+/// ignored.
+pub fn select_rules(
+    preset: RuleRegistry,
+    filter: Option<&str>,
+    skip: Option<&str>,
+) -> RuleRegistry {
+    let mut rules = match filter {
+        Some(filter) => {
+            let names = split_names(filter);
+            warn_unknown(&names, "--rules");
+            let mut rules = rule_registry();
+            rules.retain(|rule| matches_any(rule, &names));
+            rules
+        }
+        None => preset,
+    };
+    if let Some(skip) = skip {
+        let names = split_names(skip);
+        warn_unknown(&names, "--skip-rules");
+        rules.retain(|rule| !matches_any(rule, &names));
+    }
+    rules
+}
 
-    // Exit with error code if there are errors
-    if result.has_errors() {
-        std::process::exit(1);
+/// Resolves the built-in project rules the same way: all of them unless
+/// `--rules` is given, minus `--skip-rules`.
+///
+/// Unknown names are already warned about by [`select_rules`].
+pub fn select_project_rules(filter: Option<&str>, skip: Option<&str>) -> Vec<ProjectRuleBox> {
+    let mut rules = project_rules();
+    if let Some(filter) = filter {
+        let names = split_names(filter);
+        rules.retain(|rule| matches_any_project(rule.as_ref(), &names));
+    }
+    if let Some(skip) = skip {
+        let names = split_names(skip);
+        rules.retain(|rule| !matches_any_project(rule.as_ref(), &names));
     }
+    rules
+}
 
-    Ok(())
+/// Splits a comma-separated list of rule names or codes.
+pub fn split_names(list: &str) -> Vec<&str> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect()
 }
 
-fn filter_rules(names: &[&str]) -> Vec<arch_lint_core::RuleBox> {
-    let mut rules: Vec<arch_lint_core::RuleBox> = Vec::new();
+fn matches_any(rule: &dyn Rule, names: &[&str]) -> bool {
+    names.contains(&rule.name()) || names.contains(&rule.code())
+}
+
+fn matches_any_project(rule: &dyn ProjectRule, names: &[&str]) -> bool {
+    names.contains(&rule.name()) || names.contains(&rule.code())
+}
 
+fn warn_unknown(names: &[&str], flag: &str) {
+    let known = rule_registry();
+    let project = project_rules();
     for name in names {
-        match *name {
-            "no-unwrap-expect" | "AL001" => rules.push(Box::new(NoUnwrapExpect::new())),
-            "no-sync-io" | "AL002" => rules.push(Box::new(NoSyncIo::new())),
-            "no-error-swallowing" | "AL003" => rules.push(Box::new(NoErrorSwallowing::new())),
-            "handler-complexity" | "AL004" => rules.push(Box::new(HandlerComplexity::new())),
-            "require-thiserror" | "AL005" => rules.push(Box::new(RequireThiserror::new())),
-            "require-tracing" | "AL006" => rules.push(Box::new(RequireTracing::new())),
-            "tracing-env-init" | "AL007" => rules.push(Box::new(TracingEnvInit::new())),
-            "no-silent-result-drop" | "AL013" => {
-                rules.push(Box::new(NoSilentResultDrop::new()));
-            }
-            _ => tracing::warn!("Unknown rule: {}", name),
+        let is_project = project
+            .iter()
+            .any(|rule| rule.name() == *name || rule.code() == *name);
+        if !known.contains(name) && !is_project {
+            tracing::warn!("Unknown rule in {}: {}", flag, name);
         }
     }
+}
 
-    rules
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_lint_rules::Preset;
+
+    fn codes(rules: &RuleRegistry) -> Vec<&'static str> {
+        rules.codes().collect()
+    }
+
+    #[test]
+    fn skip_removes_from_preset_by_name_and_code() {
+        let rules = select_rules(
+            Preset::Recommended.registry(),
+            None,
+            Some("no-unwrap-expect, AL005"),
+        );
+        let codes = codes(&rules);
+        assert!(!codes.contains(&"AL001"));
+        assert!(!codes.contains(&"AL005"));
+        assert_eq!(codes.len(), Preset::Recommended.rules().len() - 2);
+    }
+
+    #[test]
+    fn preset_selects_base_set() {
+        let rules = select_rules(Preset::Strict.registry(), None, None);
+        assert!(codes(&rules).contains(&"AL004"));
+        assert_eq!(
+            codes(&select_rules(Preset::Minimal.registry(), None, None)),
+            vec!["AL001"]
+        );
+    }
+
+    #[test]
+    fn skip_applies_after_rules_filter() {
+        let rules = select_rules(
+            Preset::Recommended.registry(),
+            Some("AL001,AL002,AL003"),
+            Some("no-sync-io"),
+        );
+        assert_eq!(codes(&rules), vec!["AL001", "AL003"]);
+    }
+
+    #[test]
+    fn unknown_skip_name_is_ignored() {
+        let rules = select_rules(
+            Preset::Recommended.registry(),
+            Some("AL001"),
+            Some("not-a-rule"),
+        );
+        assert_eq!(codes(&rules), vec!["AL001"]);
+    }
+
+    #[test]
+    fn rules_filter_accepts_every_known_rule() {
+        let rules = select_rules(
+            Preset::Recommended.registry(),
+            Some("async-fn-without-await, AL012, not-a-rule"),
+            None,
+        );
+        assert_eq!(codes(&rules), vec!["AL012", "AL028"]);
+    }
+
+    fn project_codes(rules: &[ProjectRuleBox]) -> Vec<&'static str> {
+        rules.iter().map(|rule| rule.code()).collect()
+    }
+
+    #[test]
+    fn project_rules_run_by_default_and_follow_filters() {
+        assert_eq!(
+            project_codes(&select_project_rules(None, None)),
+            vec!["AL040"]
+        );
+        assert!(select_project_rules(None, Some("orphan-modules")).is_empty());
+        assert!(select_project_rules(Some("AL001"), None).is_empty());
+        assert_eq!(
+            project_codes(&select_project_rules(Some("AL001, AL040"), None)),
+            vec!["AL040"]
+        );
+    }
 }
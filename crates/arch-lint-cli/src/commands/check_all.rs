@@ -0,0 +1,55 @@
+//! Combined check command.
+//!
+//! Runs the syn engine over Rust sources and the tree-sitter engine over
+//! Kotlin sources, then reports both as a single [`LintResult`]. Codes stay
+//! distinct (`AL*` vs `LAYER*`/`PATTERN*`/`NAMING*`), so the merged result
+//! needs no further namespacing.
+
+use anyhow::{Context, Result};
+
+use super::check::{self, CheckOptions};
+use super::check_ts;
+use super::output;
+
+/// Runs both engines over `path` and reports the merged result.
+///
+/// The gate comes from the Rust config (`fail_on`, `[gate]`) and applies to
+/// the combined counts. Returns `true` if the result fails it.
+pub fn run(
+    path: &std::path::Path,
+    options: &CheckOptions,
+    source: &crate::config_resolver::ConfigSource,
+) -> Result<bool> {
+    let config = check::load_config(source)?;
+    let gate = check::resolve_gate(options, &config)?;
+
+    let declarative = check::load_declarative(source)?;
+    let analyzer = check::build_analyzer(&[path.to_path_buf()], config, declarative, options)?;
+    tracing::info!("Analyzing {:?} with {} rules", path, analyzer.rule_count());
+
+    let mut result = analyzer.analyze().context("Analysis failed")?;
+    let (ts_result, ts_root) = check_ts::analyze(path, options.absolute_paths, source)?;
+    result.violations.extend(ts_result.violations);
+    result.files_checked += ts_result.files_checked;
+
+    let mut snippets = options
+        .snippets
+        .then(|| output::Snippets::new(vec![analyzer.root().to_path_buf(), ts_root]));
+    output::print(
+        &result,
+        options.format,
+        options.column_base,
+        options.layout,
+        snippets.as_mut(),
+    )?;
+
+    if options.stats {
+        output::print_stats(&result.counts_by_rule(), options.format);
+    }
+    if let Some(timings) = analyzer.timings() {
+        output::print_timings(&timings);
+    }
+    let summary = result.summary();
+    gate.report_breaches(&summary);
+    Ok(gate.fails(&summary))
+}
@@ -8,15 +8,49 @@ use arch_lint_core::LintResult;
 use arch_lint_ts::{ArchConfig, ArchRuleEngine, KotlinExtractor, LanguageExtractor};
 use std::path::{Path, PathBuf};
 
-use crate::OutputFormat;
+use super::check::{self, CheckOptions};
+use super::output::{self, Snippets};
 
 /// Runs the tree-sitter check command.
+///
+/// Rule selection, exclude and cache options do not apply to this engine.
+/// The gate comes from the same config settings as the Rust engine's
+/// (`fail_on`, `deny_warnings`, `[gate]`). Returns `true` if the result
+/// fails it.
 pub fn run(
     path: &Path,
-    format: OutputFormat,
+    options: &CheckOptions,
     source: &crate::config_resolver::ConfigSource,
-) -> Result<()> {
-    let config = load_ts_config(source)?;
+) -> Result<bool> {
+    let gate = check::resolve_gate(options, &check::load_config(source)?)?;
+    let (result, root) = analyze(path, options.absolute_paths, source)?;
+
+    let mut snippets = options.snippets.then(|| Snippets::new(vec![root]));
+    output::print(
+        &result,
+        options.format,
+        options.column_base,
+        options.layout,
+        snippets.as_mut(),
+    )?;
+
+    if options.stats {
+        output::print_stats(&result.counts_by_rule(), options.format);
+    }
+    let summary = result.summary();
+    gate.report_breaches(&summary);
+    Ok(gate.fails(&summary))
+}
+
+/// Runs the tree-sitter engine over the files under `path`.
+///
+/// Also returns the source root that reported paths are relative to.
+pub fn analyze(
+    path: &Path,
+    absolute_paths: bool,
+    source: &crate::config_resolver::ConfigSource,
+) -> Result<(LintResult, PathBuf)> {
+    let config = load_config(source)?;
     config.validate().context("Config validation failed")?;
 
     let engine = ArchRuleEngine::new(config.clone());
@@ -33,6 +67,7 @@ pub fn run(
     tracing::info!("Analyzing {} files with tree-sitter engine", files.len());
 
     let mut result = LintResult::new();
+    let mut file_analyses = Vec::new();
 
     for file_path in &files {
         let ext = file_path
@@ -51,19 +86,28 @@ pub fn run(
         let source = std::fs::read_to_string(file_path)
             .with_context(|| format!("Failed to read {}", file_path.display()))?;
 
-        let rel = file_path
-            .strip_prefix(&root)
-            .unwrap_or(file_path)
-            .to_path_buf();
+        let reported = if absolute_paths {
+            std::fs::canonicalize(file_path).unwrap_or_else(|_| file_path.clone())
+        } else {
+            file_path
+                .strip_prefix(&root)
+                .unwrap_or(file_path)
+                .to_path_buf()
+        };
 
         let mut analysis = extractor.analyze(&source);
-        analysis.file_path = rel;
+        analysis.file_path = reported;
 
         let violations = engine.check(&analysis);
         result.violations.extend(violations);
         result.files_checked += 1;
+        file_analyses.push(analysis);
     }
 
+    result
+        .violations
+        .extend(engine.check_project(&file_analyses));
+
     // Sort by file, then line
     result.violations.sort_by(|a, b| {
         a.location
@@ -72,20 +116,26 @@ pub fn run(
             .then(a.location.line.cmp(&b.location.line))
     });
 
-    super::output::print(&result, format)?;
-
-    if result.has_errors() {
-        std::process::exit(1);
-    }
-
-    Ok(())
+    Ok((result, root))
 }
 
-fn load_ts_config(source: &crate::config_resolver::ConfigSource) -> Result<ArchConfig> {
+/// Loads the tree-sitter engine config. It must come from a config file.
+pub fn load_config(source: &crate::config_resolver::ConfigSource) -> Result<ArchConfig> {
     match source {
         crate::config_resolver::ConfigSource::Default => {
             anyhow::bail!("No arch-lint.toml found. Run `arch-lint init --ts` to create one.")
         }
+        crate::config_resolver::ConfigSource::CargoMetadata(manifest) => {
+            let content = arch_lint_core::cargo_metadata_config(manifest).with_context(|| {
+                format!("No [package.metadata.arch-lint] in {}", manifest.display())
+            })?;
+            ArchConfig::parse(&content).with_context(|| {
+                format!(
+                    "Failed to load config from Cargo metadata: {}",
+                    manifest.display()
+                )
+            })
+        }
         other => {
             let p = other.path().context("resolved config has no path")?;
             if source.is_global() {
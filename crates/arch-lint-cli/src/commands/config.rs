@@ -0,0 +1,572 @@
+//! Config command implementation.
+//!
+//! `config show` prints the configuration `check` would run with, as TOML:
+//! the preset, gate, analyzer and file settings, every rule that runs (and
+//! every rule the config mentions, with why it does not run), and the
+//! declarative `[[scopes]]`. Each value carries a trailing comment naming
+//! where it comes from: a command-line flag, the config, the preset, or the
+//! built-in default, in that order of precedence.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+
+use anyhow::{Context, Result};
+use arch_lint_core::{Config, ConfigFormat, Rule, RuleRegistry};
+use arch_lint_rules::rule_registry;
+use clap::ValueEnum;
+
+use super::check;
+use crate::config_resolver::ConfigSource;
+use crate::FailOn;
+
+/// Column at which origin comments start, when the value fits before it.
+const COMMENT_COLUMN: usize = 36;
+
+/// Command-line flags that take precedence over the config, as `check`
+/// accepts them (plus `--preset`).
+#[derive(Debug, Default)]
+pub struct Overrides {
+    /// Preset to use instead of the config's.
+    pub preset: Option<String>,
+    /// Comma-separated rule names or codes to run instead of the preset.
+    pub rules_filter: Option<String>,
+    /// Comma-separated rule names or codes to remove from the active set.
+    pub skip_rules: Option<String>,
+    /// Additional exclude patterns.
+    pub exclude: Vec<String>,
+    /// Additional cfg options assumed enabled (`--cfg`).
+    pub cfg: Vec<String>,
+    /// Severity threshold override (`--fail-on`).
+    pub fail_on: Option<FailOn>,
+    /// Maximum tolerated warnings (`--max-warnings`).
+    pub max_warnings: Option<usize>,
+    /// Treat warnings as errors (`--deny-warnings`).
+    pub deny_warnings: bool,
+}
+
+/// Runs `config show`.
+pub fn show(source: &ConfigSource, overrides: &Overrides) -> Result<()> {
+    let config = check::load_config(source)?;
+    let raw = raw_table(source)?;
+    print!("{}", render(source, &config, &raw, overrides)?);
+    Ok(())
+}
+
+/// Where an effective value comes from, highest precedence first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Origin<'a> {
+    /// A command-line flag.
+    Flag(&'static str),
+    /// The loaded config, described by where it was found.
+    Config(&'static str),
+    /// The rule set of a preset, by name.
+    Preset(&'a str),
+    /// Built-in default.
+    Default,
+}
+
+impl fmt::Display for Origin<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag(flag) => f.write_str(flag),
+            Self::Config(label) => f.write_str(label),
+            Self::Preset(name) => write!(f, "preset {name}"),
+            Self::Default => f.write_str("default"),
+        }
+    }
+}
+
+/// Reads the config as an untyped table, to tell which keys it sets.
+fn raw_table(source: &ConfigSource) -> Result<toml::Table> {
+    let Some((content, format)) = source.read()? else {
+        return Ok(toml::Table::new());
+    };
+    let table = match format {
+        ConfigFormat::Toml => toml::from_str(&content).map_err(anyhow::Error::from),
+        ConfigFormat::Yaml => serde_yaml::from_str(&content).map_err(anyhow::Error::from),
+        ConfigFormat::Json => serde_json::from_str(&content).map_err(anyhow::Error::from),
+    };
+    table.context("Failed to parse config")
+}
+
+/// Returns `true` if `raw` sets the value at `path`.
+fn is_set(raw: &toml::Table, path: &[&str]) -> bool {
+    let Some((last, parents)) = path.split_last() else {
+        return false;
+    };
+    let mut table = raw;
+    for key in parents {
+        match table.get(*key).and_then(toml::Value::as_table) {
+            Some(inner) => table = inner,
+            None => return false,
+        }
+    }
+    table.contains_key(*last)
+}
+
+/// Tells which values come from the config.
+#[derive(Clone, Copy)]
+struct Origins<'a> {
+    raw: &'a toml::Table,
+    config: Origin<'static>,
+}
+
+impl Origins<'_> {
+    /// Returns the config's origin if it sets `path`, else `fallback`.
+    fn of<'o>(self, path: &[&str], fallback: Origin<'o>) -> Origin<'o> {
+        if is_set(self.raw, path) {
+            self.config
+        } else {
+            fallback
+        }
+    }
+}
+
+/// Renders the effective configuration, annotated with origins, under a
+/// header naming where the config was loaded from.
+fn render(
+    source: &ConfigSource,
+    config: &Config,
+    raw: &toml::Table,
+    flags: &Overrides,
+) -> Result<String> {
+    let origins = Origins {
+        raw,
+        config: Origin::Config(match source {
+            ConfigSource::CargoMetadata(_) => "Cargo metadata",
+            ConfigSource::Global(_) => "global config",
+            _ => "config",
+        }),
+    };
+    let from_config = origins.config;
+
+    let mut out = String::new();
+    let location = match source.path() {
+        Some(path) => path.display().to_string(),
+        None => "defaults (no config file found)".to_string(),
+    };
+    let _ = writeln!(out, "# Effective arch-lint configuration");
+    let _ = writeln!(out, "# Source: {location}");
+    let _ = writeln!(
+        out,
+        "# Each value is followed by where it comes from (flag > config > preset > default).\n"
+    );
+
+    let (preset_name, preset_origin) = match (&flags.preset, &config.preset) {
+        (Some(name), _) => (Some(name.as_str()), Origin::Flag("--preset")),
+        (None, Some(name)) => (Some(name.as_str()), from_config),
+        (None, None) => (None, Origin::Default),
+    };
+    let preset = check::resolve_preset(preset_name, config)?;
+    value(&mut out, "preset", preset.name.as_str(), preset_origin);
+    render_gate(&mut out, config, flags, origins);
+    render_files(&mut out, config, flags, origins);
+    render_rules(&mut out, config, flags, preset, origins);
+
+    let presets: BTreeMap<_, _> = config.presets.iter().collect();
+    for (name, custom) in presets {
+        let _ = writeln!(out, "\n[presets.{}]", key(name));
+        if let Some(extends) = &custom.extends {
+            value(&mut out, "extends", extends.as_str(), from_config);
+        }
+        if let Some(rules) = &custom.rules {
+            value(&mut out, "rules", rules.clone(), from_config);
+        }
+        for (list, names) in [("enable", &custom.enable), ("disable", &custom.disable)] {
+            if !names.is_empty() {
+                value(&mut out, list, names.clone(), from_config);
+            }
+        }
+    }
+
+    if let Some(scopes) = raw.get("scopes").and_then(toml::Value::as_array) {
+        for scope in scopes.iter().filter_map(toml::Value::as_table) {
+            let _ = writeln!(out, "\n[[scopes]]");
+            for (key, scope_value) in scope {
+                value(&mut out, key, scope_value.clone(), from_config);
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Renders `fail_on`, `deny_warnings` and the `[gate]` limits.
+fn render_gate(out: &mut String, config: &Config, flags: &Overrides, origins: Origins<'_>) {
+    let from_config = origins.config;
+
+    match flags.fail_on.and_then(|flag| flag.to_possible_value()) {
+        Some(flag) => value(out, "fail_on", flag.get_name(), Origin::Flag("--fail-on")),
+        None => value(
+            out,
+            "fail_on",
+            config.fail_on.as_deref().unwrap_or("error"),
+            origins.of(&["fail_on"], Origin::Default),
+        ),
+    }
+    if flags.deny_warnings {
+        value(out, "deny_warnings", true, Origin::Flag("--deny-warnings"));
+    } else {
+        value(
+            out,
+            "deny_warnings",
+            config.deny_warnings,
+            origins.of(&["deny_warnings"], Origin::Default),
+        );
+    }
+
+    let _ = writeln!(out, "\n[gate]");
+    let gate = &config.gate;
+    let max_warnings = match flags.max_warnings {
+        Some(max) => Some((max, Origin::Flag("--max-warnings"))),
+        None => gate.max_warnings.map(|max| (max, from_config)),
+    };
+    for (key, limit) in [
+        ("max_errors", gate.max_errors.map(|max| (max, from_config))),
+        ("max_warnings", max_warnings),
+        ("max_infos", gate.max_infos.map(|max| (max, from_config))),
+    ] {
+        match limit {
+            Some((max, origin)) => value(out, key, count(max), origin),
+            None => unset(out, key, "no limit"),
+        }
+    }
+}
+
+/// Renders the `[analyzer]`, `[files]` and `[logging]` sections.
+fn render_files(out: &mut String, config: &Config, flags: &Overrides, origins: Origins<'_>) {
+    let _ = writeln!(out, "\n[analyzer]");
+    let analyzer = &config.analyzer;
+    value(
+        out,
+        "root",
+        analyzer.root.display().to_string(),
+        origins.of(&["analyzer", "root"], Origin::Default),
+    );
+    value(
+        out,
+        "include",
+        analyzer.include.clone(),
+        origins.of(&["analyzer", "include"], Origin::Default),
+    );
+    let exclude_origin = origins
+        .of(&["analyzer", "exclude"], Origin::Default)
+        .to_string();
+    if flags.exclude.is_empty() {
+        value(out, "exclude", analyzer.exclude.clone(), exclude_origin);
+    } else {
+        let patterns = analyzer.exclude.iter().chain(&flags.exclude).cloned();
+        let exclude: Vec<String> = patterns.collect();
+        value(
+            out,
+            "exclude",
+            exclude,
+            format!("{exclude_origin} + --exclude"),
+        );
+    }
+    let cfg_origin = origins.of(&["analyzer", "cfg"], Origin::Default);
+    if flags.cfg.is_empty() {
+        value(out, "cfg", analyzer.cfg.clone(), cfg_origin);
+    } else {
+        let cfg: Vec<String> = analyzer.cfg.iter().chain(&flags.cfg).cloned().collect();
+        value(out, "cfg", cfg, format!("{cfg_origin} + --cfg"));
+    }
+    value(
+        out,
+        "respect_gitignore",
+        analyzer.respect_gitignore,
+        origins.of(&["analyzer", "respect_gitignore"], Origin::Default),
+    );
+    match analyzer.parallelism {
+        Some(n) => value(out, "parallelism", count(n), origins.config),
+        None => unset(out, "parallelism", "no limit"),
+    }
+
+    let _ = writeln!(out, "\n[files]");
+    value(
+        out,
+        "extensions",
+        config.files.extensions.clone(),
+        origins.of(&["files", "extensions"], Origin::Default),
+    );
+    value(
+        out,
+        "extra",
+        config.files.extra.clone(),
+        origins.of(&["files", "extra"], Origin::Default),
+    );
+
+    if let Some(logging) = &config.logging {
+        let _ = writeln!(out, "\n[logging]");
+        value(
+            out,
+            "macros",
+            logging.macros.clone(),
+            origins.of(&["logging", "macros"], Origin::Default),
+        );
+    }
+}
+
+/// Renders a `[rules.*]` table for each rule `check` runs and each rule the
+/// config or `--skip-rules` mentions, with a header comment saying whether
+/// it runs.
+fn render_rules(
+    out: &mut String,
+    config: &Config,
+    flags: &Overrides,
+    preset: check::ActivePreset,
+    origins: Origins<'_>,
+) {
+    let from_config = origins.config;
+    let filter = flags.rules_filter.as_deref();
+    let active = check::select_rules(preset.rules, filter, flags.skip_rules.as_deref());
+    let known = rule_registry();
+    let mut rules: BTreeMap<&str, Option<&dyn Rule>> = active
+        .iter()
+        .map(|rule| (rule.name(), Some(rule)))
+        .collect();
+    for name in config.rules.keys() {
+        rules
+            .entry(name.as_str())
+            .or_insert_with(|| known.by_name(name));
+    }
+    let skipped = flags.skip_rules.as_deref().map(check::split_names);
+    for rule in skipped.iter().flatten().filter_map(|name| known.get(name)) {
+        rules.entry(rule.name()).or_insert(Some(rule));
+    }
+
+    for (name, rule) in rules {
+        let entry = config.rules.get(name);
+        let enabled = entry.and_then(|e| e.enabled).unwrap_or(true);
+        let status = match rule {
+            None => "unknown rule, ignored".to_string(),
+            Some(rule) => format!(
+                "{}, {}",
+                rule.code(),
+                status(rule, enabled, &active, flags, &preset.name)
+            ),
+        };
+        out.push('\n');
+        line(out, &format!("[rules.{}]", key(name)), status);
+
+        let selected_by = if !active.contains(name) {
+            Origin::Default
+        } else if filter.is_some() {
+            Origin::Flag("--rules")
+        } else {
+            Origin::Preset(&preset.name)
+        };
+        let enabled_origin = origins.of(&["rules", name, "enabled"], selected_by);
+        value(out, "enabled", enabled, enabled_origin);
+
+        match (entry.and_then(|e| e.severity), rule) {
+            (Some(severity), _) => value(out, "severity", severity.to_string(), from_config),
+            (None, Some(rule)) => value(
+                out,
+                "severity",
+                rule.default_severity().to_string(),
+                Origin::Default,
+            ),
+            (None, None) => {}
+        }
+        let Some(entry) = entry else {
+            continue;
+        };
+        if let Some(doc_ref) = &entry.doc_ref {
+            value(out, "doc_ref", doc_ref.as_str(), from_config);
+        }
+        if let Some(message) = &entry.message {
+            value(out, "message", message.as_str(), from_config);
+        }
+        let options: BTreeMap<_, _> = entry.options.iter().collect();
+        for (option, option_value) in options {
+            value(out, option, option_value.clone(), from_config);
+        }
+    }
+}
+
+/// Says whether `rule` runs, and if not, why.
+fn status(
+    rule: &dyn Rule,
+    enabled: bool,
+    active: &RuleRegistry,
+    flags: &Overrides,
+    preset: &str,
+) -> String {
+    if active.contains(rule.name()) {
+        return if enabled { "runs" } else { "not run: disabled" }.to_string();
+    }
+    let skipped = flags.skip_rules.as_deref().is_some_and(|skip| {
+        check::split_names(skip)
+            .iter()
+            .any(|name| *name == rule.name() || *name == rule.code())
+    });
+    if skipped {
+        "not run: removed by --skip-rules".to_string()
+    } else if flags.rules_filter.is_some() {
+        "not run: not selected by --rules".to_string()
+    } else {
+        format!("not run: not in preset {preset} (select it with --rules)")
+    }
+}
+
+/// Writes `key = value` followed by its origin.
+fn value(out: &mut String, key_name: &str, v: impl Into<toml::Value>, origin: impl fmt::Display) {
+    line(out, &format!("{} = {}", key(key_name), v.into()), origin);
+}
+
+/// Writes a commented-out line for a setting that has no value.
+fn unset(out: &mut String, key_name: &str, meaning: &str) {
+    line(
+        out,
+        &format!("# {key_name} unset"),
+        format!("{meaning}, default"),
+    );
+}
+
+fn line(out: &mut String, content: &str, comment: impl fmt::Display) {
+    let _ = writeln!(out, "{content:<COMMENT_COLUMN$}  # {comment}");
+}
+
+/// Formats `name` as a TOML key, quoting it unless it is a bare key.
+fn key(name: &str) -> String {
+    let bare = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        name.to_string()
+    } else {
+        toml::Value::from(name).to_string()
+    }
+}
+
+fn count(n: usize) -> toml::Value {
+    toml::Value::Integer(i64::try_from(n).unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_lint_rules::Preset;
+
+    fn show(content: &str, flags: &Overrides) -> String {
+        let config = Config::parse(content).unwrap();
+        let raw = toml::from_str(content).unwrap();
+        let source = ConfigSource::Project("arch-lint.toml".into());
+        render(&source, &config, &raw, flags).unwrap()
+    }
+
+    fn line_of<'a>(rendered: &'a str, start: &str) -> &'a str {
+        rendered
+            .lines()
+            .find(|line| line.starts_with(start))
+            .unwrap_or_else(|| panic!("no line starting with {start:?} in\n{rendered}"))
+    }
+
+    #[test]
+    fn values_are_annotated_with_origin() {
+        let rendered = show(
+            "preset = \"minimal\"\nfail_on = \"warning\"\n\n[gate]\nmax_errors = 3\n\n\
+             [rules.no-unwrap-expect]\nseverity = \"warning\"\n",
+            &Overrides {
+                max_warnings: Some(10),
+                ..Overrides::default()
+            },
+        );
+        assert!(line_of(&rendered, "preset = \"minimal\"").ends_with("# config"));
+        assert!(line_of(&rendered, "fail_on = \"warning\"").ends_with("# config"));
+        assert!(line_of(&rendered, "deny_warnings = false").ends_with("# default"));
+        assert!(line_of(&rendered, "max_errors = 3").ends_with("# config"));
+        assert!(line_of(&rendered, "max_warnings = 10").ends_with("# --max-warnings"));
+        assert!(line_of(&rendered, "# max_infos unset").ends_with("# no limit, default"));
+        assert!(line_of(&rendered, "[rules.no-unwrap-expect]").ends_with("# AL001, runs"));
+        assert!(line_of(&rendered, "enabled = true").ends_with("# preset minimal"));
+        assert!(line_of(&rendered, "severity = \"warning\"").ends_with("# config"));
+
+        let reparsed = Config::parse(&rendered).unwrap();
+        assert_eq!(reparsed.preset.as_deref(), Some("minimal"));
+        assert_eq!(reparsed.rules.len(), Preset::Minimal.rules().len());
+        assert!(
+            rendered.starts_with("# Effective arch-lint configuration\n# Source: arch-lint.toml")
+        );
+    }
+
+    #[test]
+    fn flags_take_precedence_over_config() {
+        let rendered = show(
+            "preset = \"minimal\"\nfail_on = \"warning\"\n\n[analyzer]\nexclude = [\"gen/**\"]\n",
+            &Overrides {
+                preset: Some("strict".to_string()),
+                fail_on: Some(FailOn::Never),
+                exclude: vec!["tmp/**".to_string()],
+                cfg: vec!["feature=serde".to_string()],
+                ..Overrides::default()
+            },
+        );
+        assert!(line_of(&rendered, "preset = \"strict\"").ends_with("# --preset"));
+        assert!(line_of(&rendered, "fail_on = \"never\"").ends_with("# --fail-on"));
+        assert!(line_of(&rendered, "exclude = [\"gen/**\", \"tmp/**\"]")
+            .ends_with("# config + --exclude"));
+        assert!(line_of(&rendered, "cfg = [\"feature=serde\"]").ends_with("# default + --cfg"));
+        assert!(line_of(&rendered, "[rules.handler-complexity]").ends_with("# AL004, runs"));
+    }
+
+    #[test]
+    fn rules_that_do_not_run_say_why() {
+        let rendered = show(
+            "[rules.no-sync-io]\nenabled = false\n\n[rules.require-doc-comments]\n\
+             require_fn_docs = false\n\n[rules.no-such-rule]\nseverity = \"info\"\n",
+            &Overrides {
+                skip_rules: Some("AL003".to_string()),
+                ..Overrides::default()
+            },
+        );
+        assert!(line_of(&rendered, "[rules.no-sync-io]").ends_with("# AL002, not run: disabled"));
+        assert!(line_of(&rendered, "[rules.require-doc-comments]")
+            .ends_with("not run: not in preset recommended (select it with --rules)"));
+        assert!(line_of(&rendered, "require_fn_docs = false").ends_with("# config"));
+        assert!(line_of(&rendered, "[rules.no-such-rule]").ends_with("# unknown rule, ignored"));
+        assert!(line_of(&rendered, "[rules.no-error-swallowing]")
+            .ends_with("# AL003, not run: removed by --skip-rules"));
+        assert!(
+            !rendered.contains("[rules.handler-complexity]"),
+            "{rendered}"
+        );
+    }
+
+    #[test]
+    fn scopes_are_listed() {
+        let rendered = show(
+            "[[scopes]]\nname = \"domain\"\npaths = [\"src/domain/**\"]\n",
+            &Overrides::default(),
+        );
+        assert!(rendered.contains("\n[[scopes]]\n"));
+        assert!(line_of(&rendered, "paths = [\"src/domain/**\"]").ends_with("# config"));
+        let raw: toml::Table = rendered.parse().unwrap();
+        assert_eq!(raw["scopes"][0]["name"].as_str(), Some("domain"));
+    }
+
+    #[test]
+    fn custom_preset_is_resolved() {
+        let rendered = show(
+            "preset = \"team\"\n\n[presets.team]\nextends = \"minimal\"\nenable = [\"AL002\"]\n",
+            &Overrides::default(),
+        );
+        assert!(line_of(&rendered, "preset = \"team\"").ends_with("# config"));
+        assert!(rendered.contains("[rules.no-sync-io]"));
+        assert!(line_of(&rendered, "enabled = true").ends_with("# preset team"));
+        assert!(line_of(&rendered, "enable = [\"AL002\"]").ends_with("# config"));
+
+        let reparsed = Config::parse(&rendered).unwrap();
+        assert_eq!(reparsed.presets["team"].extends.as_deref(), Some("minimal"));
+    }
+
+    #[test]
+    fn unknown_preset_is_rejected() {
+        let config = Config::parse("preset = \"lenient\"\n").unwrap();
+        let raw = toml::Table::new();
+        let err = render(&ConfigSource::Default, &config, &raw, &Overrides::default()).unwrap_err();
+        assert!(err.to_string().contains("unknown preset `lenient`"));
+    }
+}
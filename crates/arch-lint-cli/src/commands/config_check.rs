@@ -0,0 +1,190 @@
+//! `check --config-check`: validates the config without analyzing.
+//!
+//! Collects every problem it can find instead of stopping at the first:
+//! the config parses, the preset exists, `[analyzer]` globs compile, every
+//! `[rules.*]` entry names a known rule with valid options, and the
+//! declarative sections resolve their cross-references. With the
+//! tree-sitter engine, `[[layers]]` and its constraints are checked too.
+
+use anyhow::Result;
+use arch_lint_core::declarative::loader::LoadError;
+use arch_lint_core::declarative::{self, LoadRulesError};
+use arch_lint_core::utils::allowance;
+use arch_lint_core::{Config, ConfigFormat};
+use arch_lint_rules::{all_rules, rule_registry};
+
+use super::{check, check_ts};
+use crate::config_resolver::ConfigSource;
+use crate::EngineHint;
+
+/// Runs the config check for `engine`, printing each problem to stderr.
+///
+/// Returns `true` if any problem was found.
+pub fn run(source: &ConfigSource, engine: EngineHint) -> Result<bool> {
+    let location = match source.path() {
+        Some(path) => path.display().to_string(),
+        None => "defaults (no config file found)".to_string(),
+    };
+
+    let mut problems = Vec::new();
+    if matches!(engine, EngineHint::Syn | EngineHint::All) {
+        problems.extend(rust_problems(source)?);
+    }
+    if matches!(engine, EngineHint::Ts | EngineHint::All) {
+        problems.extend(ts_problems(source));
+    }
+
+    if problems.is_empty() {
+        println!("Config OK: {location}");
+        return Ok(false);
+    }
+    for problem in &problems {
+        eprintln!("error: {problem}");
+    }
+    eprintln!("\n{} problem(s) in {location}", problems.len());
+    Ok(true)
+}
+
+/// Problems in the Rust-engine config and its declarative sections.
+fn rust_problems(source: &ConfigSource) -> Result<Vec<String>> {
+    let Some((content, format)) = source.read()? else {
+        return Ok(Vec::new());
+    };
+    let config = match Config::parse_as(&content, format) {
+        Ok(config) => config,
+        Err(e) => return Ok(vec![e.to_string()]),
+    };
+    let (declarative_names, declarative_problems) = match declarative_rule_names(&content, format) {
+        Ok(names) => (names, Vec::new()),
+        Err(LoadRulesError::Load(LoadError::CrossRef(errors))) => {
+            (Vec::new(), errors.iter().map(ToString::to_string).collect())
+        }
+        Err(e) => (Vec::new(), vec![e.to_string()]),
+    };
+
+    let mut problems = Vec::new();
+    if let Err(e) = check::resolve_preset(config.preset.as_deref(), &config) {
+        problems.push(e.to_string());
+    }
+    for (key, patterns) in [
+        ("analyzer.include", &config.analyzer.include),
+        ("analyzer.exclude", &config.analyzer.exclude),
+    ] {
+        for pattern in patterns {
+            if let Err(e) = glob::Pattern::new(pattern) {
+                problems.push(format!("{key}: invalid glob `{pattern}`: {e}"));
+            }
+        }
+    }
+    problems.extend(rule_problems(&config, &declarative_names));
+    problems.extend(declarative_problems);
+    Ok(problems)
+}
+
+/// Names of the declarative rules the config defines.
+fn declarative_rule_names(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<Vec<&'static str>, LoadRulesError> {
+    let rules = declarative::load_rules(content, format)?;
+    let project_rules = declarative::load_project_rules(content, format)?;
+    Ok(rules
+        .iter()
+        .map(|rule| rule.name())
+        .chain(project_rules.iter().map(|rule| rule.name()))
+        .collect())
+}
+
+/// Problems in `[rules.*]`: unknown names and invalid options.
+fn rule_problems(config: &Config, declarative_names: &[&str]) -> Vec<String> {
+    let known = rule_registry();
+    let mut rules = all_rules();
+    let mut names: Vec<&String> = config.rules.keys().collect();
+    names.sort();
+
+    let mut problems = Vec::new();
+    for name in names {
+        let rule_config = &config.rules[name];
+        if let Some(rule) = rules.iter_mut().find(|rule| rule.name() == name) {
+            if let Err(e) = rule.configure(rule_config) {
+                problems.push(format!("rules.{name}: {e}"));
+            }
+        } else if let Some(rule) = known.by_code(name) {
+            problems.push(format!(
+                "rules.{name}: rules are configured by name; use `[rules.{}]`",
+                rule.name()
+            ));
+        } else if ![allowance::DIRECTIVE_NAME, allowance::EXPIRED_NAME].contains(&name.as_str())
+            && !declarative_names.contains(&name.as_str())
+        {
+            problems.push(format!(
+                "rules.{name}: unknown rule (see `arch-lint list-rules`)"
+            ));
+        }
+    }
+    problems
+}
+
+/// Problems in the tree-sitter engine's `[[layers]]` config.
+fn ts_problems(source: &ConfigSource) -> Vec<String> {
+    let config = match check_ts::load_config(source) {
+        Ok(config) => config,
+        Err(e) => return vec![format!("{e:#}")],
+    };
+    match config.validate() {
+        Ok(()) => Vec::new(),
+        Err(arch_lint_ts::config::ConfigError::Invalid(problems)) => {
+            problems.iter().map(ToString::to_string).collect()
+        }
+        Err(e) => vec![e.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(content: &str) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("arch-lint.toml");
+        std::fs::write(&path, content).unwrap();
+        rust_problems(&ConfigSource::Project(path)).unwrap()
+    }
+
+    #[test]
+    fn valid_config_has_no_problems() {
+        let content = "preset = \"strict\"\n\n[rules.no-unwrap-expect]\nseverity = \"warning\"\n\n\
+                       [rules.allow-directive]\nenabled = false\n\n\
+                       [[scopes]]\nname = \"domain\"\npaths = [\"src/domain/**\"]\n\n\
+                       [[restrict-use]]\nname = \"no-db\"\nscope = \"domain\"\ndeny = [\"sqlx\"]\nmessage = \"m\"\n\n\
+                       [rules.restrict-use]\nseverity = \"warning\"\n";
+        assert_eq!(problems(content), Vec::<String>::new());
+        assert!(rust_problems(&ConfigSource::Default).unwrap().is_empty());
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let found = problems(
+            "preset = \"lenient\"\n\n[analyzer]\nexclude = [\"gen/[*\"]\n\n\
+             [rules.no-such-rule]\nenabled = false\n\n[rules.AL001]\nenabled = false\n\n\
+             [[restrict-use]]\nname = \"a\"\nscope = \"missing\"\ndeny = [\"x\"]\nmessage = \"m\"\n\n\
+             [[restrict-use]]\nname = \"b\"\nscope = \"absent\"\ndeny = [\"y\"]\nmessage = \"m\"\n",
+        );
+        assert_eq!(found.len(), 6, "{found:#?}");
+        assert!(found[0].contains("unknown preset `lenient`"));
+        assert!(found[1].starts_with("analyzer.exclude: invalid glob `gen/[*`"));
+        assert_eq!(
+            found[2],
+            "rules.AL001: rules are configured by name; use `[rules.no-unwrap-expect]`"
+        );
+        assert!(found[3].starts_with("rules.no-such-rule: unknown rule"));
+        assert!(found[4].contains("missing"), "{found:#?}");
+        assert!(found[5].contains("absent"), "{found:#?}");
+    }
+
+    #[test]
+    fn parse_errors_stop_the_check() {
+        let found = problems("preset = [\n\n[rules.no-such-rule]\n");
+        assert_eq!(found.len(), 1, "{found:#?}");
+    }
+}
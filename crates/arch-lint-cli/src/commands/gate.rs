@@ -0,0 +1,204 @@
+//! Exit-status gating for check results.
+//!
+//! Mirrors the `check!()` runner's `fail_on` resolution:
+//! `--fail-on` flag > `fail_on` in config > `"error"`. The config's `[gate]`
+//! count limits apply as well, with `--max-warnings` overriding
+//! `max_warnings`.
+
+use anyhow::{Context, Result};
+use arch_lint_core::{AnalysisSummary, GateConfig, Severity, ThresholdBreach};
+
+use crate::FailOn;
+
+/// Decides whether a lint result should fail the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gate {
+    /// Minimum severity that fails, or `None` to never fail on severity.
+    fail_on: Option<Severity>,
+    /// Maximum counts per severity tolerated regardless of `fail_on`.
+    thresholds: GateConfig,
+}
+
+impl Gate {
+    /// Resolves the gate from CLI flags and the config's `fail_on` value
+    /// and `[gate]` section.
+    pub fn resolve(
+        flag: Option<FailOn>,
+        config_fail_on: Option<&str>,
+        config_gate: GateConfig,
+        max_warnings: Option<usize>,
+    ) -> Result<Self> {
+        let fail_on = match flag {
+            Some(FailOn::Error) => Some(Severity::Error),
+            Some(FailOn::Warning) => Some(Severity::Warning),
+            Some(FailOn::Info) => Some(Severity::Info),
+            Some(FailOn::Never) => None,
+            None => Some(
+                config_fail_on
+                    .unwrap_or("error")
+                    .parse()
+                    .context("invalid `fail_on` in config")?,
+            ),
+        };
+
+        let thresholds = GateConfig {
+            max_warnings: max_warnings.or(config_gate.max_warnings),
+            ..config_gate
+        };
+
+        Ok(Self {
+            fail_on,
+            thresholds,
+        })
+    }
+
+    /// Treats warnings as errors (`--deny-warnings`): lowers the severity
+    /// threshold to `warning` if it is stricter. `never` stays `never`.
+    #[must_use]
+    pub fn deny_warnings(mut self, deny: bool) -> Self {
+        if deny {
+            self.fail_on = self.fail_on.map(|s| s.min(Severity::Warning));
+        }
+        self
+    }
+
+    /// Returns `true` if the counts should produce a failing exit code.
+    pub fn fails(&self, summary: &AnalysisSummary) -> bool {
+        if self
+            .fail_on
+            .is_some_and(|severity| summary.has_violations_at(severity))
+        {
+            return true;
+        }
+
+        !self.breaches(summary).is_empty()
+    }
+
+    /// Returns the count limits that `summary` exceeds.
+    pub fn breaches(&self, summary: &AnalysisSummary) -> Vec<ThresholdBreach> {
+        self.thresholds.summary_breaches(summary)
+    }
+
+    /// Prints each exceeded count limit to stderr.
+    pub fn report_breaches(&self, summary: &AnalysisSummary) {
+        for breach in self.breaches(summary) {
+            eprintln!("arch-lint: gate failed: {breach}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_lint_core::{LintResult, Location, Violation};
+    use std::path::PathBuf;
+
+    fn summary_with(severities: &[Severity]) -> AnalysisSummary {
+        let mut result = LintResult::new();
+        for &severity in severities {
+            result.violations.push(Violation::new(
+                "AL001",
+                "no-unwrap-expect",
+                severity,
+                Location::new(PathBuf::from("src/lib.rs"), 1, 1),
+                "msg",
+            ));
+        }
+        result.summary()
+    }
+
+    #[test]
+    fn defaults_to_error() {
+        let gate = Gate::resolve(None, None, GateConfig::default(), None).unwrap();
+        assert!(!gate.fails(&summary_with(&[Severity::Warning])));
+        assert!(gate.fails(&summary_with(&[Severity::Error])));
+    }
+
+    #[test]
+    fn flag_overrides_config() {
+        let gate = Gate::resolve(
+            Some(FailOn::Warning),
+            Some("error"),
+            GateConfig::default(),
+            None,
+        )
+        .unwrap();
+        assert!(gate.fails(&summary_with(&[Severity::Warning])));
+
+        let gate = Gate::resolve(None, Some("info"), GateConfig::default(), None).unwrap();
+        assert!(gate.fails(&summary_with(&[Severity::Info])));
+    }
+
+    #[test]
+    fn never_ignores_severity() {
+        let gate = Gate::resolve(Some(FailOn::Never), None, GateConfig::default(), None).unwrap();
+        assert!(!gate.fails(&summary_with(&[Severity::Error])));
+    }
+
+    #[test]
+    fn max_warnings_applies_below_threshold() {
+        let gate =
+            Gate::resolve(Some(FailOn::Never), None, GateConfig::default(), Some(1)).unwrap();
+        assert!(!gate.fails(&summary_with(&[Severity::Warning])));
+        assert!(gate.fails(&summary_with(&[Severity::Warning, Severity::Warning])));
+    }
+
+    #[test]
+    fn config_thresholds_apply_alongside_fail_on() {
+        let config_gate = GateConfig {
+            max_errors: Some(1),
+            max_warnings: Some(2),
+            max_infos: None,
+        };
+        let gate = Gate::resolve(Some(FailOn::Never), None, config_gate, None).unwrap();
+        assert!(!gate.fails(&summary_with(&[Severity::Error])));
+        assert!(gate.fails(&summary_with(&[Severity::Error, Severity::Error])));
+        assert!(!gate.fails(&summary_with(&[Severity::Warning, Severity::Warning])));
+        assert!(gate.fails(&summary_with(&[Severity::Warning; 3])));
+
+        // `fail_on` still fails below the count limits.
+        let gate = Gate::resolve(None, None, config_gate, None).unwrap();
+        assert!(gate.fails(&summary_with(&[Severity::Error])));
+    }
+
+    #[test]
+    fn max_warnings_flag_overrides_config() {
+        let config_gate = GateConfig {
+            max_warnings: Some(0),
+            ..GateConfig::default()
+        };
+        let gate = Gate::resolve(Some(FailOn::Never), None, config_gate, Some(2)).unwrap();
+        assert!(!gate.fails(&summary_with(&[Severity::Warning; 2])));
+        let breaches = gate.breaches(&summary_with(&[Severity::Warning; 3]));
+        assert_eq!(breaches.len(), 1);
+        assert_eq!(
+            breaches[0].to_string(),
+            "3 warning(s) exceed max_warnings = 2"
+        );
+    }
+
+    #[test]
+    fn deny_warnings_lowers_threshold() {
+        let gate = Gate::resolve(None, None, GateConfig::default(), None)
+            .unwrap()
+            .deny_warnings(true);
+        assert!(gate.fails(&summary_with(&[Severity::Warning])));
+        assert!(!gate.fails(&summary_with(&[Severity::Info])));
+
+        let gate = Gate::resolve(Some(FailOn::Info), None, GateConfig::default(), None)
+            .unwrap()
+            .deny_warnings(true);
+        assert!(gate.fails(&summary_with(&[Severity::Info])));
+
+        let gate = Gate::resolve(Some(FailOn::Never), None, GateConfig::default(), None)
+            .unwrap()
+            .deny_warnings(true);
+        assert!(!gate.fails(&summary_with(&[Severity::Error])));
+    }
+
+    #[test]
+    fn invalid_config_value_is_an_error() {
+        let err = Gate::resolve(None, Some("critical"), GateConfig::default(), None).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown severity `critical`"));
+    }
+}
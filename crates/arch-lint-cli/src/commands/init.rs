@@ -1,10 +1,25 @@
 //! Init command implementation.
+//!
+//! Inspects the project in the current directory and writes a commented
+//! `arch-lint.toml` tailored to it:
+//!
+//! - crates found in `Cargo.toml` (`tokio`, `tracing`, `thiserror`) toggle
+//!   the rules that only make sense with them
+//! - a `src/{domain,application,infra}` layout pre-populates `[[scopes]]`
+//!   and a starter `[[deny-scope-dep]]`
 
 use anyhow::{bail, Result};
+use std::fmt::Write as _;
 use std::path::Path;
 
-const DEFAULT_CONFIG: &str = r#"# arch-lint configuration
-# See https://github.com/example/arch-lint for documentation
+const HEADER: &str = r#"# arch-lint configuration
+# Generated by `arch-lint init`. See https://github.com/ynishi/arch-lint for documentation.
+
+# Preset: "recommended" (default), "strict", or "minimal"
+# preset = "recommended"
+
+# Severity that fails `check!()` / CI: "error" (default), "warning", or "info"
+# fail_on = "error"
 
 [analyzer]
 # Root directory to analyze (default: current directory)
@@ -20,23 +35,45 @@ exclude = [
 # Respect .gitignore files
 respect_gitignore = true
 
-# Rule configurations
+# ─── Rules ───────────────────────────────────────────────────────────
 # Each rule can be enabled/disabled and have its severity overridden
+# with `severity = "error" | "warning" | "info"`.
+# Run `arch-lint list-rules` to see every available rule.
 
 [rules.no-unwrap-expect]
 enabled = true
 # severity = "warning"  # Override default severity
-allow_in_tests = true
+allow_in_tests = true   # .unwrap() is fine in #[test] / #[cfg(test)] code
 
-[rules.no-sync-io]
+[rules.no-error-swallowing]
 enabled = true
 
 # [rules.handler-complexity]
 # enabled = true
-# max_lines = 150
+# max_handler_lines = 150
 # max_match_arms = 20
 "#;
 
+/// Architectural layers recognized under `src/`, with accepted directory names.
+const LAYERS: &[(&str, &[&str])] = &[
+    ("domain", &["domain"]),
+    (
+        "application",
+        &["application", "app", "usecase", "usecases"],
+    ),
+    ("infra", &["infra", "infrastructure"]),
+];
+
+/// What `init` found in the project.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Detection {
+    tokio: bool,
+    tracing: bool,
+    thiserror: bool,
+    /// `(scope name, path globs)` for each detected layer.
+    layers: Vec<(&'static str, Vec<String>)>,
+}
+
 /// Runs the init command.
 pub fn run(force: bool) -> Result<()> {
     let config_path = Path::new("arch-lint.toml");
@@ -48,12 +85,306 @@ pub fn run(force: bool) -> Result<()> {
         );
     }
 
-    std::fs::write(config_path, DEFAULT_CONFIG)?;
+    let detection = detect(Path::new("."));
+    std::fs::write(config_path, render(&detection))?;
 
     println!("Created arch-lint.toml");
+    print_summary(&detection);
     println!("\nNext steps:");
     println!("  1. Edit arch-lint.toml to configure rules");
     println!("  2. Run: arch-lint check");
+    println!("  3. Run: arch-lint config show  (every rule the preset enables)");
 
     Ok(())
 }
+
+/// Inspects `Cargo.toml` and the `src/` layout under `dir`.
+fn detect(dir: &Path) -> Detection {
+    let deps = read_dependency_names(&dir.join("Cargo.toml"));
+    let has = |name: &str| deps.iter().any(|d| d == name);
+
+    Detection {
+        tokio: has("tokio"),
+        tracing: has("tracing"),
+        thiserror: has("thiserror"),
+        layers: detect_layers(&dir.join("src")),
+    }
+}
+
+/// Collects dependency names from `[dependencies]`, `[dev-dependencies]`,
+/// and `[workspace.dependencies]`.
+fn read_dependency_names(cargo_toml: &Path) -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string(cargo_toml) else {
+        return Vec::new();
+    };
+    let Ok(table) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    let sections = [
+        table.get("dependencies"),
+        table.get("dev-dependencies"),
+        table.get("workspace").and_then(|w| w.get("dependencies")),
+    ];
+
+    sections
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_table)
+        .flat_map(|t| t.keys().cloned())
+        .collect()
+}
+
+/// Finds layer modules (`src/<name>/` or `src/<name>.rs`).
+fn detect_layers(src: &Path) -> Vec<(&'static str, Vec<String>)> {
+    let mut layers = Vec::new();
+
+    for (scope, dir_names) in LAYERS {
+        let mut paths = Vec::new();
+        for dir_name in *dir_names {
+            if src.join(format!("{dir_name}.rs")).is_file() {
+                paths.push(format!("src/{dir_name}.rs"));
+            }
+            if src.join(dir_name).is_dir() {
+                paths.push(format!("src/{dir_name}/**"));
+            }
+        }
+        if !paths.is_empty() {
+            layers.push((*scope, paths));
+        }
+    }
+
+    layers
+}
+
+/// Renders the commented config for a detection result.
+fn render(detection: &Detection) -> String {
+    let mut out = String::from(HEADER);
+
+    let _ = writeln!(out);
+    if detection.tokio {
+        let _ = writeln!(
+            out,
+            "# tokio detected: blocking std::fs / std::net calls stall the runtime."
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "# tokio not detected: enable if you add an async runtime."
+        );
+    }
+    let _ = writeln!(out, "[rules.no-sync-io]\nenabled = {}\n", detection.tokio);
+
+    if detection.thiserror {
+        let _ = writeln!(
+            out,
+            "# thiserror detected: error enums should derive thiserror::Error."
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "# thiserror not detected: enable to require thiserror-based errors."
+        );
+    }
+    let _ = writeln!(
+        out,
+        "[rules.require-thiserror]\nenabled = {}\n",
+        detection.thiserror
+    );
+
+    if detection.tracing {
+        let _ = writeln!(
+            out,
+            "# tracing detected: prefer it over `log` and initialize from RUST_LOG."
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "# tracing not detected: enable once you adopt structured logging."
+        );
+    }
+    let _ = writeln!(
+        out,
+        "[rules.require-tracing]\nenabled = {}\n",
+        detection.tracing
+    );
+    let _ = writeln!(
+        out,
+        "[rules.tracing-env-init]\nenabled = {}",
+        detection.tracing
+    );
+
+    render_scopes(&mut out, &detection.layers);
+
+    out
+}
+
+/// Renders `[[scopes]]` and a starter `[[deny-scope-dep]]` for detected layers.
+fn render_scopes(out: &mut String, layers: &[(&'static str, Vec<String>)]) {
+    let _ = writeln!(
+        out,
+        "\n# ─── Architecture scopes ─────────────────────────────────────────────\n\
+         # Scopes name groups of files; declarative rules below refer to them."
+    );
+
+    if layers.is_empty() {
+        let _ = writeln!(
+            out,
+            "# No src/domain, src/application, or src/infra layout detected. Example:\n\
+             #\n\
+             # [[scopes]]\n\
+             # name = \"domain\"\n\
+             # paths = [\"src/domain/**\"]\n\
+             #\n\
+             # [[deny-scope-dep]]\n\
+             # from = \"domain\"\n\
+             # to = [\"infra\"]\n\
+             # message = \"Domain must not depend on infrastructure.\""
+        );
+        return;
+    }
+
+    for (name, paths) in layers {
+        let quoted: Vec<String> = paths.iter().map(|p| format!("\"{p}\"")).collect();
+        let _ = writeln!(
+            out,
+            "\n[[scopes]]\nname = \"{name}\"\npaths = [{}]",
+            quoted.join(", ")
+        );
+    }
+
+    let has = |name: &str| layers.iter().any(|(n, _)| *n == name);
+    let outer: Vec<&str> = ["application", "infra"]
+        .into_iter()
+        .filter(|n| has(n))
+        .collect();
+
+    let _ = writeln!(
+        out,
+        "\n# ─── Dependency direction ────────────────────────────────────────────\n\
+         # Inner layers must not import outer ones. Adjust to your architecture."
+    );
+    if has("domain") && !outer.is_empty() {
+        let targets: Vec<String> = outer.iter().map(|n| format!("\"{n}\"")).collect();
+        let _ = writeln!(
+            out,
+            "\n[[deny-scope-dep]]\nfrom = \"domain\"\nto = [{}]\n\
+             message = \"Domain must stay independent of application and infrastructure code.\"",
+            targets.join(", ")
+        );
+    }
+    if has("application") && has("infra") {
+        let _ = writeln!(
+            out,
+            "\n[[deny-scope-dep]]\nfrom = \"application\"\nto = [\"infra\"]\n\
+             message = \"Application code should depend on ports (traits), not infrastructure.\""
+        );
+    }
+}
+
+fn print_summary(detection: &Detection) {
+    let mark = |found: bool| if found { "found" } else { "not found" };
+
+    println!("\nDetected:");
+    println!("  tokio      {}  -> no-sync-io", mark(detection.tokio));
+    println!(
+        "  thiserror  {}  -> require-thiserror",
+        mark(detection.thiserror)
+    );
+    println!(
+        "  tracing    {}  -> require-tracing, tracing-env-init",
+        mark(detection.tracing)
+    );
+    if detection.layers.is_empty() {
+        println!("  layers     none (add [[scopes]] manually)");
+    } else {
+        for (name, paths) in &detection.layers {
+            println!("  scope      {name}: {}", paths.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn project(cargo_toml: &str, src_entries: &[&str]) -> TempDir {
+        let tmp = TempDir::new().expect("create temp dir");
+        fs::write(tmp.path().join("Cargo.toml"), cargo_toml).expect("write Cargo.toml");
+        fs::create_dir(tmp.path().join("src")).expect("create src");
+        for entry in src_entries {
+            let path = tmp.path().join("src").join(entry);
+            if Path::new(entry).extension().is_some() {
+                fs::write(path, "").expect("write source file");
+            } else {
+                fs::create_dir_all(path).expect("create source dir");
+            }
+        }
+        tmp
+    }
+
+    #[test]
+    fn detects_dependencies() {
+        let tmp = project(
+            "[package]\nname = \"x\"\n\n[dependencies]\ntokio = \"1\"\nthiserror = \"2\"\n\n\
+             [dev-dependencies]\ntracing = \"0.1\"\n",
+            &[],
+        );
+        let d = detect(tmp.path());
+        assert!(d.tokio && d.thiserror && d.tracing);
+    }
+
+    #[test]
+    fn detects_workspace_dependencies() {
+        let tmp = project("[workspace.dependencies]\ntracing = \"0.1\"\n", &[]);
+        let d = detect(tmp.path());
+        assert!(d.tracing);
+        assert!(!d.tokio);
+    }
+
+    #[test]
+    fn detects_layers_from_dirs_and_files() {
+        let tmp = project("", &["domain", "infrastructure", "app.rs", "main.rs"]);
+        let d = detect(tmp.path());
+        assert_eq!(
+            d.layers,
+            vec![
+                ("domain", vec!["src/domain/**".to_string()]),
+                ("application", vec!["src/app.rs".to_string()]),
+                ("infra", vec!["src/infrastructure/**".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn rendered_config_is_valid() {
+        let tmp = project(
+            "[dependencies]\ntokio = \"1\"\n",
+            &["domain", "application", "infra"],
+        );
+        let config = render(&detect(tmp.path()));
+
+        let parsed = arch_lint_core::Config::parse(&config).expect("parse rendered config");
+        assert!(parsed.is_rule_enabled("no-sync-io"));
+        assert!(!parsed.is_rule_enabled("require-thiserror"));
+
+        let declarative =
+            arch_lint_core::declarative::load_all(&config, arch_lint_core::ConfigFormat::Toml)
+                .expect("load rendered declarative rules");
+        assert!(!declarative.rules.is_empty());
+        assert!(config.contains("from = \"domain\"\nto = [\"application\", \"infra\"]"));
+        assert!(config.contains("from = \"application\"\nto = [\"infra\"]"));
+    }
+
+    #[test]
+    fn rendered_config_without_layers_has_no_scopes() {
+        let config = render(&Detection::default());
+        arch_lint_core::Config::parse(&config).expect("parse rendered config");
+        let rules = arch_lint_core::declarative::load_rules_from_toml(&config)
+            .expect("load rendered declarative rules");
+        assert!(rules.is_empty());
+        assert!(config.contains("# [[scopes]]"));
+    }
+}
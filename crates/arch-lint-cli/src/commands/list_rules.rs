@@ -1,28 +1,237 @@
 //! List rules command implementation.
 
-use arch_lint_rules::all_rules;
+use anyhow::{bail, Result};
+use arch_lint_core::declarative;
+use arch_lint_core::Severity;
+use arch_lint_rules::{all_rules, project_rules, Preset};
+use serde::Serialize;
+
+use crate::OutputFormat;
+
+/// Rule metadata as emitted by `list-rules`.
+#[derive(Debug, Serialize)]
+struct RuleInfo {
+    code: &'static str,
+    name: &'static str,
+    description: &'static str,
+    default_severity: Severity,
+    presets: Vec<&'static str>,
+    /// Config section that enables a declarative rule (`[[restrict-use]]`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config_section: Option<String>,
+}
 
 /// Runs the list-rules command.
-pub fn run() {
-    println!("Available rules:\n");
-    println!("{:<10} {:<25} Description", "Code", "Name");
+pub fn run(format: OutputFormat, preset: Option<&str>) -> Result<()> {
+    let preset = match preset {
+        Some(name) => match Preset::from_name(name) {
+            Some(p) => Some(p),
+            None => bail!("unknown preset `{name}`. Valid presets: recommended, strict, minimal"),
+        },
+        None => None,
+    };
+
+    let rules = collect(preset);
+
+    match format {
+        OutputFormat::Text => print_text(&rules, preset),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&rules)?),
+        OutputFormat::JsonSummary => {
+            println!("{}", serde_json::json!({ "rules": rules.len() }));
+        }
+        OutputFormat::Compact => {
+            for rule in &rules {
+                println!("{} {}", rule.code, rule.name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds rule metadata from the rule registry, optionally limited to a preset.
+///
+/// Built-in project rules run in every preset and follow the per-file rules.
+/// Declarative rules come last; they belong to no preset, so a preset filter
+/// leaves them out.
+fn collect(preset: Option<Preset>) -> Vec<RuleInfo> {
+    let built_in = all_rules()
+        .iter()
+        .filter(|rule| preset.map_or(true, |p| p.contains(rule.code())))
+        .map(|rule| RuleInfo {
+            code: rule.code(),
+            name: rule.name(),
+            description: rule.description(),
+            default_severity: rule.default_severity(),
+            presets: Preset::ALL
+                .into_iter()
+                .filter(|p| p.contains(rule.code()))
+                .map(Preset::name)
+                .collect(),
+            config_section: None,
+        })
+        .collect::<Vec<_>>();
+    let project = project_rules().into_iter().map(|rule| RuleInfo {
+        code: rule.code(),
+        name: rule.name(),
+        description: rule.description(),
+        default_severity: rule.default_severity(),
+        presets: Preset::ALL.into_iter().map(Preset::name).collect(),
+        config_section: None,
+    });
+    let declarative = declarative::rule_catalog()
+        .into_iter()
+        .filter(|_| preset.is_none())
+        .map(|rule| RuleInfo {
+            code: rule.code,
+            name: rule.name,
+            description: rule.description,
+            default_severity: rule.default_severity,
+            presets: Vec::new(),
+            config_section: Some(format!("[[{}]]", rule.name)),
+        });
+    built_in
+        .into_iter()
+        .chain(project)
+        .chain(declarative)
+        .collect()
+}
+
+fn print_row(rule: &RuleInfo) {
+    println!(
+        "{:<10} {:<25} {:<9} {}",
+        rule.code,
+        rule.name,
+        rule.default_severity.to_string(),
+        rule.description
+    );
+}
+
+fn print_text(rules: &[RuleInfo], preset: Option<Preset>) {
+    match preset {
+        Some(p) => println!("Rules in preset '{}':\n", p.name()),
+        None => println!("Available rules:\n"),
+    }
+    println!(
+        "{:<10} {:<25} {:<9} Description",
+        "Code", "Name", "Severity"
+    );
     println!("{}", "-".repeat(80));
 
-    for rule in all_rules() {
+    for rule in rules.iter().filter(|r| r.config_section.is_none()) {
+        print_row(rule);
+    }
+
+    let declarative: Vec<&RuleInfo> = rules
+        .iter()
+        .filter(|r| r.config_section.is_some())
+        .collect();
+    if !declarative.is_empty() {
         println!(
-            "{:<10} {:<25} {}",
-            rule.code(),
-            rule.name(),
-            rule.description()
+            "\nDeclarative rules (run by `check` and `check!()` in every preset when their \
+             config section has entries):\n"
         );
+        for rule in declarative {
+            print_row(rule);
+        }
+        println!("\nConfigure them with TOML sections named after the rule, e.g.:");
+        println!("  [[restrict-use]]");
+        println!("  name = \"no-sqlx-in-domain\"");
+        println!("  scope = \"domain\"");
+        println!("  deny = [\"sqlx::*\"]");
+        println!("Disable one with `[rules.<name>] enabled = false`.");
     }
 
-    println!("\nPresets:");
-    println!("  recommended  - AL001, AL002, AL003, AL005, AL006, AL007, AL013 (default)");
-    println!("  strict       - All rules with stricter settings");
-    println!("  minimal      - AL001 only (for gradual adoption)");
+    if preset.is_none() {
+        println!("\nPresets:");
+        for p in Preset::ALL {
+            let codes: Vec<&str> = rules
+                .iter()
+                .filter(|r| r.presets.contains(&p.name()))
+                .map(|r| r.code)
+                .collect();
+            println!(
+                "  {:<12} - {} [{}]",
+                p.name(),
+                p.description(),
+                codes.join(", ")
+            );
+        }
+    }
 
     println!("\nUse --rules to filter specific rules, e.g.:");
     println!("  arch-lint check --rules no-unwrap-expect,no-sync-io");
     println!("  arch-lint check --rules AL001,AL002,AL003");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_all_rules_reports_preset_membership() {
+        let rules = collect(None);
+        let al001 = rules
+            .iter()
+            .find(|r| r.code == "AL001")
+            .expect("AL001 listed");
+        assert_eq!(al001.presets, vec!["recommended", "strict", "minimal"]);
+
+        let al004 = rules
+            .iter()
+            .find(|r| r.code == "AL004")
+            .expect("AL004 listed");
+        assert_eq!(al004.presets, vec!["strict"]);
+    }
+
+    #[test]
+    fn collect_filters_by_preset() {
+        let codes: Vec<_> = collect(Some(Preset::Minimal))
+            .iter()
+            .map(|r| r.code)
+            .collect();
+        assert_eq!(codes, vec!["AL001", "AL040"]);
+    }
+
+    #[test]
+    fn collect_lists_project_rules_in_every_preset() {
+        let rules = collect(None);
+        let al040 = rules
+            .iter()
+            .find(|r| r.code == "AL040")
+            .expect("AL040 listed");
+        assert_eq!(al040.name, "orphan-modules");
+        assert_eq!(al040.presets, vec!["recommended", "strict", "minimal"]);
+        assert!(al040.config_section.is_none());
+    }
+
+    #[test]
+    fn collect_lists_declarative_rules() {
+        let rules = collect(None);
+        let codes: Vec<_> = rules
+            .iter()
+            .filter(|r| r.config_section.is_some())
+            .map(|r| r.code)
+            .collect();
+        assert_eq!(
+            codes,
+            ["ALD001", "ALD002", "ALD003", "ALD004", "ALD005", "ALD006", "ALD007", "ALD008"]
+        );
+
+        let ald001 = rules.iter().find(|r| r.code == "ALD001").expect("ALD001");
+        assert_eq!(ald001.name, "restrict-use");
+        assert_eq!(ald001.config_section.as_deref(), Some("[[restrict-use]]"));
+        assert!(ald001.presets.is_empty());
+    }
+
+    #[test]
+    fn json_contains_expected_fields() {
+        let json = serde_json::to_value(collect(Some(Preset::Minimal))).expect("serialize");
+        let rule = &json[0];
+        assert_eq!(rule["code"], "AL001");
+        assert_eq!(rule["name"], "no-unwrap-expect");
+        assert_eq!(rule["default_severity"], "error");
+        assert!(rule["description"].is_string());
+        assert!(rule["presets"].is_array());
+    }
+}
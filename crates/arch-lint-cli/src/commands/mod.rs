@@ -1,8 +1,13 @@
 //! CLI command implementations.
 
 pub mod check;
+pub mod check_all;
 pub mod check_ts;
+pub mod config;
+pub mod config_check;
+pub mod gate;
 pub mod init;
 pub mod init_ts;
 pub mod list_rules;
-mod output;
+pub mod output;
+pub mod rules_catalog;
@@ -1,45 +1,230 @@
 //! Shared output formatting for lint results.
+//!
+//! Violations are arranged into a sorted (and optionally grouped) view
+//! without reordering the underlying [`LintResult`].
 
 use anyhow::Result;
-use arch_lint_core::{LintResult, Severity};
+use arch_lint_core::{AnalysisSummary, LintResult, Location, Severity, Timings, Violation};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-use crate::OutputFormat;
+use crate::{ColumnBase, GroupBy, OutputFormat, SortBy};
+
+/// Ordering and grouping of violations in text and compact output.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Layout {
+    /// Group violations under a header per file, rule, or severity.
+    pub group_by: Option<GroupBy>,
+    /// Ordering of violations (within each group when grouped).
+    pub sort: SortBy,
+}
+
+impl Layout {
+    /// Returns `true` if text output can be printed as violations arrive:
+    /// no grouping and the default location order, which streaming
+    /// approximates file by file.
+    pub fn is_streamable(self) -> bool {
+        self.group_by.is_none() && self.sort == SortBy::Location
+    }
+}
+
+/// Source lines shown under text-format violations, each file read once.
+#[derive(Debug, Default)]
+pub struct Snippets {
+    /// Directories relative violation paths are resolved against, in order.
+    bases: Vec<PathBuf>,
+    /// File contents by reported path; `None` if unreadable.
+    files: HashMap<PathBuf, Option<String>>,
+}
+
+impl Snippets {
+    /// Creates a reader resolving relative paths against the first of
+    /// `bases` that contains the file.
+    pub fn new(bases: Vec<PathBuf>) -> Self {
+        Self {
+            bases,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Renders the source line of `location` underlined with `marker`
+    /// (`^` for the primary span, `-` for labels), or `None` if the file or
+    /// line cannot be read.
+    pub fn render(&mut self, location: &Location, marker: char) -> Option<String> {
+        if location.line == 0 || location.column == 0 {
+            return None;
+        }
+        let source = self.source(&location.file)?;
+        let text = source.lines().nth(location.line - 1)?;
+        Some(render_snippet(source, text, location, marker))
+    }
+
+    fn source(&mut self, file: &Path) -> Option<&str> {
+        let bases = &self.bases;
+        self.files
+            .entry(file.to_path_buf())
+            .or_insert_with(|| {
+                if file.is_absolute() {
+                    return std::fs::read_to_string(file).ok();
+                }
+                bases
+                    .iter()
+                    .find_map(|base| std::fs::read_to_string(base.join(file)).ok())
+            })
+            .as_deref()
+    }
+}
+
+/// Formats `text` (line `location.line` of `source`) ruff-style, underlining
+/// the span from `location.offset`/`length`, or the word at the column when
+/// no span is recorded.
+fn render_snippet(source: &str, text: &str, location: &Location, marker: char) -> String {
+    let start = location.column - 1;
+    let rest: Vec<char> = text.chars().skip(start).collect();
+    let width = if location.length > 0 {
+        source
+            .get(location.offset..location.offset + location.length)
+            .and_then(|span| span.lines().next())
+            .map_or(1, |span| span.chars().count())
+    } else {
+        rest.iter()
+            .take_while(|c| c.is_alphanumeric() || **c == '_')
+            .count()
+    };
+    let width = width.clamp(1, rest.len().max(1));
+
+    // Keep tabs so the underline lines up with the source.
+    let indent: String = text
+        .chars()
+        .take(start)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let gutter = location.line.to_string();
+    let blank = " ".repeat(gutter.len());
+    format!(
+        "  {blank} |\n  {gutter} | {text}\n  {blank} | {indent}{}\n",
+        marker.to_string().repeat(width)
+    )
+}
+
+/// A run of violations sharing a group key.
+struct Group<'a> {
+    label: Option<String>,
+    violations: Vec<&'a Violation>,
+}
 
 /// Print lint results in the specified format.
-pub fn print(result: &LintResult, format: OutputFormat) -> Result<()> {
+///
+/// Text output shows source lines from `snippets` when given. JSON columns
+/// are numbered from `column_base`; text and compact output stay 1-based.
+pub fn print(
+    result: &LintResult,
+    format: OutputFormat,
+    column_base: ColumnBase,
+    layout: Layout,
+    snippets: Option<&mut Snippets>,
+) -> Result<()> {
     match format {
-        OutputFormat::Text => print_text(result),
-        OutputFormat::Json => return print_json(result),
-        OutputFormat::Compact => print_compact(result),
+        OutputFormat::Text => print_text(result, layout, snippets),
+        OutputFormat::Json => return print_json(result, column_base),
+        OutputFormat::JsonSummary => return print_json_summary(result),
+        OutputFormat::Compact => print_compact(result, layout),
     }
     Ok(())
 }
 
-fn print_text(result: &LintResult) {
-    let (errors, warnings, infos) = result.count_by_severity();
+/// Sorts violations and splits them into groups according to `layout`.
+fn arrange(violations: &[Violation], layout: Layout) -> Vec<Group<'_>> {
+    let mut sorted: Vec<&Violation> = violations.iter().collect();
+    sorted.sort_by(|a, b| {
+        compare_group(a, b, layout.group_by).then_with(|| compare(a, b, layout.sort))
+    });
 
-    for violation in &result.violations {
-        let severity_indicator = match violation.severity {
-            Severity::Error => "\x1b[31merror\x1b[0m",
-            Severity::Warning => "\x1b[33mwarning\x1b[0m",
-            Severity::Info => "\x1b[34minfo\x1b[0m",
-        };
+    let Some(group_by) = layout.group_by else {
+        return vec![Group {
+            label: None,
+            violations: sorted,
+        }];
+    };
 
-        println!(
-            "{} {} at {}:{}:{}",
-            violation.code,
-            violation.rule,
-            violation.location.file.display(),
-            violation.location.line,
-            violation.location.column,
-        );
-        println!("  {}: {}", severity_indicator, violation.message);
-        if let Some(suggestion) = &violation.suggestion {
-            println!("  = help: {}", suggestion.message);
+    let mut groups: Vec<Group<'_>> = Vec::new();
+    for violation in sorted {
+        let label = group_label(violation, group_by);
+        match groups.last_mut() {
+            Some(group) if group.label.as_deref() == Some(label.as_str()) => {
+                group.violations.push(violation);
+            }
+            _ => groups.push(Group {
+                label: Some(label),
+                violations: vec![violation],
+            }),
         }
-        println!();
     }
+    groups
+}
+
+fn compare_group(a: &Violation, b: &Violation, group_by: Option<GroupBy>) -> Ordering {
+    match group_by {
+        None => Ordering::Equal,
+        Some(GroupBy::File) => a.location.file.cmp(&b.location.file),
+        Some(GroupBy::Rule) => a.code.cmp(&b.code).then_with(|| a.rule.cmp(&b.rule)),
+        Some(GroupBy::Severity) => Reverse(a.severity).cmp(&Reverse(b.severity)),
+    }
+}
+
+fn compare(a: &Violation, b: &Violation, sort: SortBy) -> Ordering {
+    let by_location = || {
+        a.location
+            .file
+            .cmp(&b.location.file)
+            .then(a.location.line.cmp(&b.location.line))
+            .then(a.location.column.cmp(&b.location.column))
+    };
 
+    match sort {
+        SortBy::Location => by_location(),
+        SortBy::Severity => Reverse(a.severity)
+            .cmp(&Reverse(b.severity))
+            .then_with(by_location),
+        SortBy::Code => a.code.cmp(&b.code).then_with(by_location),
+    }
+}
+
+fn group_label(violation: &Violation, group_by: GroupBy) -> String {
+    match group_by {
+        GroupBy::File => violation.location.file.display().to_string(),
+        GroupBy::Rule => format!("{} {}", violation.code, violation.rule),
+        GroupBy::Severity => violation.severity.to_string(),
+    }
+}
+
+fn print_text(result: &LintResult, layout: Layout, mut snippets: Option<&mut Snippets>) {
+    for group in arrange(&result.violations, layout) {
+        if let Some(label) = &group.label {
+            println!("\x1b[1m{label}\x1b[0m\n");
+        }
+
+        for violation in &group.violations {
+            print_violation(violation, snippets.as_deref_mut());
+        }
+
+        if let Some(label) = &group.label {
+            println!("  {} violation(s) in {}\n", group.violations.len(), label);
+        }
+    }
+
+    print_text_summary(&result.summary());
+}
+
+/// Prints the colored `Found N error(s), ...` line.
+pub fn print_text_summary(summary: &AnalysisSummary) {
+    let AnalysisSummary {
+        errors,
+        warnings,
+        infos,
+        ..
+    } = *summary;
     let summary_color = if errors > 0 {
         "\x1b[31m"
     } else if warnings > 0 {
@@ -50,26 +235,404 @@ fn print_text(result: &LintResult) {
 
     println!(
         "{}Found {} error(s), {} warning(s), {} info(s) in {} file(s)\x1b[0m",
-        summary_color, errors, warnings, infos, result.files_checked
+        summary_color, errors, warnings, infos, summary.files_checked
+    );
+}
+
+/// Prints per-rule violation counts (as from
+/// [`LintResult::counts_by_rule`]), most violated first.
+///
+/// Goes to stdout for text output and to stderr for the JSON formats, so
+/// their stdout stays parseable.
+pub fn print_stats(counts: &BTreeMap<String, usize>, format: OutputFormat) {
+    let table = format_stats(counts);
+    match format {
+        OutputFormat::Text | OutputFormat::Compact => print!("{table}"),
+        OutputFormat::Json | OutputFormat::JsonSummary => eprint!("{table}"),
+    }
+}
+
+fn format_stats(counts: &BTreeMap<String, usize>) -> String {
+    use std::fmt::Write;
+
+    let mut counts: Vec<(&String, usize)> = counts.iter().map(|(rule, n)| (rule, *n)).collect();
+    if counts.is_empty() {
+        return String::new();
+    }
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+
+    let width = counts[0].1.to_string().len();
+    let mut table = String::from("\nViolations by rule:\n");
+    for (rule, count) in counts {
+        let _ = writeln!(table, "  {count:>width$}  {rule}");
+    }
+    table
+}
+
+/// Prints the `--timings` breakdown, slowest first, to stderr.
+pub fn print_timings(timings: &Timings) {
+    eprint!("{}", format_timings(timings));
+}
+
+fn format_timings(timings: &Timings) -> String {
+    use std::fmt::Write;
+
+    let millis = |d: std::time::Duration| format!("{:.1}ms", d.as_secs_f64() * 1000.0);
+    let rows: Vec<(String, String)> = timings
+        .breakdown()
+        .into_iter()
+        .map(|(name, elapsed)| (millis(elapsed), name))
+        .collect();
+    let width = rows.iter().map(|(time, _)| time.len()).max().unwrap_or(0);
+
+    let mut table = format!("\nTimings (total {}):\n", millis(timings.total));
+    for (time, name) in rows {
+        let _ = writeln!(table, "  {time:>width$}  {name}");
+    }
+    table
+}
+
+/// Prints one violation in text format, with its source line when
+/// `snippets` is given.
+pub fn print_violation(violation: &Violation, mut snippets: Option<&mut Snippets>) {
+    let severity_indicator = match violation.severity {
+        Severity::Error => "\x1b[31merror\x1b[0m",
+        Severity::Warning => "\x1b[33mwarning\x1b[0m",
+        Severity::Info => "\x1b[34minfo\x1b[0m",
+    };
+
+    println!(
+        "{} {} at {}:{}:{}",
+        violation.code,
+        violation.rule,
+        violation.location.file.display(),
+        violation.location.line,
+        violation.location.column,
     );
+    println!("  {}: {}", severity_indicator, violation.message);
+    if let Some(snippet) = snippets
+        .as_deref_mut()
+        .and_then(|s| s.render(&violation.location, '^'))
+    {
+        print!("{snippet}");
+    }
+    for label in &violation.labels {
+        println!("  = note: {label}");
+        if let Some(snippet) = snippets
+            .as_deref_mut()
+            .and_then(|s| s.render(&label.location, '-'))
+        {
+            print!("{snippet}");
+        }
+    }
+    if let Some(suggestion) = &violation.suggestion {
+        println!("  = help: {}", suggestion.message);
+    }
+    for origin in &violation.origins {
+        println!("  = defined by: {origin}");
+    }
+    println!();
 }
 
-fn print_json(result: &LintResult) -> Result<()> {
-    let json = serde_json::to_string_pretty(result)?;
+fn print_json(result: &LintResult, column_base: ColumnBase) -> Result<()> {
+    let json = match column_base {
+        ColumnBase::One => serde_json::to_string_pretty(result)?,
+        ColumnBase::Zero => serde_json::to_string_pretty(&zero_based(result))?,
+    };
     println!("{json}");
     Ok(())
 }
 
-fn print_compact(result: &LintResult) {
-    for violation in &result.violations {
-        println!(
-            "{}:{}:{}: {} [{}] {}",
-            violation.location.file.display(),
-            violation.location.line,
-            violation.location.column,
-            violation.severity,
-            violation.code,
-            violation.message,
+/// Returns a copy of `result` with 0-based violation and label columns.
+///
+/// Columns are stored 1-based. An unknown column (0) stays 0.
+fn zero_based(result: &LintResult) -> LintResult {
+    let mut violations = result.violations.clone();
+    for violation in &mut violations {
+        let labels = violation.labels.iter_mut().map(|label| &mut label.location);
+        for location in std::iter::once(&mut violation.location).chain(labels) {
+            location.column = location.column.saturating_sub(1);
+        }
+    }
+    LintResult {
+        violations,
+        files_checked: result.files_checked,
+        files_skipped: result.files_skipped,
+    }
+}
+
+fn print_json_summary(result: &LintResult) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&summary(result))?);
+    Ok(())
+}
+
+/// Builds the `json-summary` object.
+fn summary(result: &LintResult) -> serde_json::Value {
+    let (errors, warnings, infos) = result.count_by_severity();
+    serde_json::json!({
+        "errors": errors,
+        "warnings": warnings,
+        "infos": infos,
+        "files_checked": result.files_checked,
+    })
+}
+
+fn print_compact(result: &LintResult, layout: Layout) {
+    let layout = Layout {
+        group_by: None,
+        ..layout
+    };
+    for group in arrange(&result.violations, layout) {
+        for violation in group.violations {
+            println!("{}", compact_line(violation));
+        }
+    }
+}
+
+/// Formats a violation as `path:line:col: [CODE] severity message`, like
+/// [`Violation`]'s `Display`, but always on one line and with `/` path
+/// separators so `errorformat`-style parsers can rely on it.
+fn compact_line(violation: &Violation) -> String {
+    let file = violation.location.file.display().to_string();
+    let file = if cfg!(windows) {
+        file.replace('\\', "/")
+    } else {
+        file
+    };
+    let doc_ref = violation
+        .doc_ref
+        .as_ref()
+        .map(|doc_ref| format!(" (see: {doc_ref})"))
+        .unwrap_or_default();
+    format!(
+        "{file}:{}:{}: [{}] {} {}{doc_ref}",
+        violation.location.line,
+        violation.location.column,
+        violation.code,
+        violation.severity,
+        violation
+            .message
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_lint_core::Label;
+
+    fn violation(file: &str, line: usize, code: &str, severity: Severity) -> Violation {
+        Violation::new(
+            code,
+            "rule",
+            severity,
+            Location::new(PathBuf::from(file), line, 1),
+            "msg",
+        )
+    }
+
+    fn sample() -> Vec<Violation> {
+        vec![
+            violation("b.rs", 2, "AL002", Severity::Warning),
+            violation("a.rs", 9, "AL001", Severity::Error),
+            violation("b.rs", 1, "AL001", Severity::Info),
+            violation("a.rs", 3, "AL002", Severity::Warning),
+        ]
+    }
+
+    fn positions(group: &Group<'_>) -> Vec<String> {
+        group
+            .violations
+            .iter()
+            .map(|v| format!("{}:{}", v.location.file.display(), v.location.line))
+            .collect()
+    }
+
+    #[test]
+    fn summary_counts_by_severity() {
+        let result = LintResult {
+            violations: sample(),
+            files_checked: 2,
+            files_skipped: 0,
+        };
+        assert_eq!(
+            summary(&result),
+            serde_json::json!({"errors": 1, "warnings": 2, "infos": 1, "files_checked": 2})
+        );
+    }
+
+    #[test]
+    fn zero_based_shifts_violation_and_label_columns() {
+        let mut v = violation("a.rs", 1, "AL001", Severity::Error);
+        v.location.column = 5;
+        let v = v.with_label(Label::new(
+            Location::new(PathBuf::from("a.rs"), 2, 3),
+            "here",
+        ));
+        let result = LintResult {
+            violations: vec![v],
+            files_checked: 1,
+            files_skipped: 0,
+        };
+
+        let shifted = zero_based(&result);
+        assert_eq!(shifted.violations[0].location.column, 4);
+        assert_eq!(shifted.violations[0].labels[0].location.column, 2);
+        assert_eq!(shifted.files_checked, 1);
+        assert_eq!(result.violations[0].location.column, 5);
+    }
+
+    #[test]
+    fn default_layout_sorts_by_location() {
+        let violations = sample();
+        let groups = arrange(&violations, Layout::default());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            positions(&groups[0]),
+            vec!["a.rs:3", "a.rs:9", "b.rs:1", "b.rs:2"]
+        );
+    }
+
+    #[test]
+    fn sort_by_severity_puts_errors_first() {
+        let violations = sample();
+        let layout = Layout {
+            group_by: None,
+            sort: SortBy::Severity,
+        };
+        let groups = arrange(&violations, layout);
+        assert_eq!(
+            positions(&groups[0]),
+            vec!["a.rs:9", "a.rs:3", "b.rs:2", "b.rs:1"]
+        );
+    }
+
+    #[test]
+    fn group_by_rule_sorts_within_group() {
+        let violations = sample();
+        let layout = Layout {
+            group_by: Some(GroupBy::Rule),
+            sort: SortBy::Location,
+        };
+        let groups = arrange(&violations, layout);
+        let labels: Vec<_> = groups.iter().map(|g| g.label.clone().unwrap()).collect();
+        assert_eq!(labels, vec!["AL001 rule", "AL002 rule"]);
+        assert_eq!(positions(&groups[0]), vec!["a.rs:9", "b.rs:1"]);
+        assert_eq!(positions(&groups[1]), vec!["a.rs:3", "b.rs:2"]);
+    }
+
+    #[test]
+    fn group_by_severity_orders_groups_by_severity() {
+        let violations = sample();
+        let layout = Layout {
+            group_by: Some(GroupBy::Severity),
+            sort: SortBy::Location,
+        };
+        let labels: Vec<_> = arrange(&violations, layout)
+            .iter()
+            .map(|g| (g.label.clone().unwrap(), g.violations.len()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("error".to_string(), 1),
+                ("warning".to_string(), 2),
+                ("info".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn stats_sorted_by_count_then_name() {
+        let mut result = LintResult::new();
+        for (rule, n) in [("b-rule", 2), ("a-rule", 2), ("c-rule", 11)] {
+            for _ in 0..n {
+                let mut v = violation("a.rs", 1, "AL001", Severity::Warning);
+                v.rule = rule.to_string();
+                result.violations.push(v);
+            }
+        }
+        assert_eq!(
+            format_stats(&result.counts_by_rule()),
+            "\nViolations by rule:\n  11  c-rule\n   2  a-rule\n   2  b-rule\n"
+        );
+        assert_eq!(format_stats(&BTreeMap::new()), "");
+    }
+
+    #[test]
+    fn timings_table_right_aligns_times() {
+        let ms = std::time::Duration::from_millis;
+        let mut timings = Timings {
+            total: ms(1250),
+            discovery: ms(3),
+            parsing: ms(120),
+            ..Timings::default()
+        };
+        timings.rules.insert("no-sync-io".to_string(), ms(40));
+        assert_eq!(
+            format_timings(&timings),
+            "\nTimings (total 1250.0ms):\n  120.0ms  parsing\n   40.0ms  no-sync-io\n    3.0ms  discovery\n"
+        );
+    }
+
+    #[test]
+    fn snippet_underlines_word_at_column() {
+        let source = "fn f() {\n    let v = x.unwrap();\n}\n";
+        let location = Location::new(PathBuf::from("a.rs"), 2, 15);
+        assert_eq!(
+            render_snippet(source, "    let v = x.unwrap();", &location, '^'),
+            "    |\n  2 |     let v = x.unwrap();\n    |               ^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn snippet_uses_recorded_span_and_keeps_tabs() {
+        let source = "\tfoo(bar)\n";
+        let location = Location::new(PathBuf::from("a.rs"), 1, 2).with_span(1, 8);
+        assert_eq!(
+            render_snippet(source, "\tfoo(bar)", &location, '-'),
+            "    |\n  1 | \tfoo(bar)\n    | \t--------\n"
+        );
+    }
+
+    #[test]
+    fn snippets_cache_file_reads() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}\n").expect("write a");
+        let mut snippets = Snippets::new(vec![PathBuf::from("/nonexistent"), dir.path().into()]);
+        let location = Location::new(PathBuf::from("a.rs"), 1, 4);
+
+        let first = snippets.render(&location, '^').expect("rendered");
+        assert!(first.ends_with("   ^\n"), "{first}");
+        std::fs::remove_file(dir.path().join("a.rs")).expect("remove a");
+        assert_eq!(snippets.render(&location, '^'), Some(first));
+
+        assert!(snippets
+            .render(&Location::new(PathBuf::from("a.rs"), 5, 1), '^')
+            .is_none());
+        assert!(snippets
+            .render(&Location::new(PathBuf::from("missing.rs"), 1, 1), '^')
+            .is_none());
+    }
+
+    #[test]
+    fn compact_line_matches_display_on_one_line() {
+        let violation =
+            violation("src/a.rs", 3, "AL001", Severity::Error).with_doc_ref("docs/adr.md");
+        assert_eq!(compact_line(&violation), violation.to_string());
+        assert_eq!(
+            compact_line(&violation),
+            "src/a.rs:3:1: [AL001] error msg (see: docs/adr.md)"
+        );
+
+        let mut multiline = violation;
+        multiline.message = "first line\n  second line".to_string();
+        assert_eq!(
+            compact_line(&multiline),
+            "src/a.rs:3:1: [AL001] error first line second line (see: docs/adr.md)"
         );
     }
 }
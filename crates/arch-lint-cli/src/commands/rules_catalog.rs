@@ -0,0 +1,165 @@
+//! Rules catalog command implementation.
+//!
+//! Prints every built-in rule and preset as JSON for docs sites and editor
+//! plugins. Unlike `list-rules`, the output is a stable export: within a
+//! `schema_version`, fields are only ever added.
+
+use anyhow::Result;
+use arch_lint_core::Severity;
+use arch_lint_rules::{all_rules, project_rules, Preset};
+use serde::Serialize;
+
+/// Version of the catalog's JSON shape.
+const SCHEMA_VERSION: u32 = 1;
+
+/// The full catalog.
+#[derive(Debug, Serialize)]
+struct Catalog {
+    schema_version: u32,
+    rules: Vec<CatalogRule>,
+    presets: Vec<CatalogPreset>,
+}
+
+/// One rule entry.
+#[derive(Debug, Serialize)]
+struct CatalogRule {
+    code: &'static str,
+    name: &'static str,
+    description: &'static str,
+    explanation: &'static str,
+    default_severity: Severity,
+    configurable_keys: &'static [&'static str],
+    presets: Vec<&'static str>,
+}
+
+/// One preset entry.
+#[derive(Debug, Serialize)]
+struct CatalogPreset {
+    name: &'static str,
+    description: &'static str,
+    rules: Vec<&'static str>,
+}
+
+/// Runs the rules-catalog command.
+pub fn run() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&build())?);
+    Ok(())
+}
+
+/// Builds the catalog from the rule registry and preset definitions.
+///
+/// Built-in project rules run in every preset, so each preset lists them.
+fn build() -> Catalog {
+    let rules = all_rules();
+    let project = project_rules();
+    let preset_codes = |preset: Preset| -> Vec<&'static str> {
+        preset
+            .rules()
+            .iter()
+            .map(|rule| rule.code())
+            .chain(project.iter().map(|rule| rule.code()))
+            .collect()
+    };
+
+    Catalog {
+        schema_version: SCHEMA_VERSION,
+        rules: rules
+            .iter()
+            .map(|rule| CatalogRule {
+                code: rule.code(),
+                name: rule.name(),
+                description: rule.description(),
+                explanation: rule.explanation(),
+                default_severity: rule.default_severity(),
+                configurable_keys: rule.configurable_keys(),
+                presets: Preset::ALL
+                    .into_iter()
+                    .filter(|p| p.contains(rule.code()))
+                    .map(Preset::name)
+                    .collect(),
+            })
+            .chain(project.iter().map(|rule| CatalogRule {
+                code: rule.code(),
+                name: rule.name(),
+                description: rule.description(),
+                explanation: rule.explanation(),
+                default_severity: rule.default_severity(),
+                configurable_keys: &[],
+                presets: Preset::ALL.into_iter().map(Preset::name).collect(),
+            }))
+            .collect(),
+        presets: Preset::ALL
+            .into_iter()
+            .map(|preset| CatalogPreset {
+                name: preset.name(),
+                description: preset.description(),
+                rules: preset_codes(preset),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_covers_every_registered_rule() {
+        let catalog = build();
+        assert_eq!(catalog.schema_version, SCHEMA_VERSION);
+        assert_eq!(
+            catalog.rules.len(),
+            all_rules().len() + project_rules().len()
+        );
+        assert!(catalog.rules.iter().all(|r| !r.explanation.is_empty()));
+
+        let al009 = catalog
+            .rules
+            .iter()
+            .find(|r| r.code == "AL009")
+            .expect("AL009 listed");
+        assert!(al009.configurable_keys.contains(&"runtime"));
+    }
+
+    #[test]
+    fn presets_match_preset_definitions() {
+        let catalog = build();
+        for preset in Preset::ALL {
+            let entry = catalog
+                .presets
+                .iter()
+                .find(|p| p.name == preset.name())
+                .expect("preset listed");
+            assert_eq!(
+                entry.rules.len(),
+                preset.rules().len() + project_rules().len()
+            );
+            for code in &entry.rules {
+                let rule = catalog
+                    .rules
+                    .iter()
+                    .find(|r| r.code == *code)
+                    .expect("preset rule listed");
+                assert!(rule.presets.contains(&preset.name()));
+            }
+        }
+    }
+
+    #[test]
+    fn json_field_names_are_stable() {
+        let json = serde_json::to_value(build()).expect("serialize");
+        let rule = &json["rules"][0];
+        for field in [
+            "code",
+            "name",
+            "description",
+            "explanation",
+            "default_severity",
+            "configurable_keys",
+            "presets",
+        ] {
+            assert!(rule.get(field).is_some(), "missing {field}");
+        }
+        assert_eq!(json["presets"][0]["name"], "recommended");
+    }
+}
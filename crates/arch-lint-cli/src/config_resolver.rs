@@ -3,12 +3,20 @@
 //! Resolves the configuration file path using a deterministic priority order:
 //!
 //! 1. `--config` flag (explicit path)
-//! 2. `{project}/arch-lint.toml` or `.arch-lint.toml`
-//! 3. `~/.arch-lint/config.toml` (global fallback)
-//! 4. No config found → defaults
+//! 2. The nearest `arch-lint.toml`, `.arch-lint.toml`, `arch-lint.yaml`,
+//!    `arch-lint.yml` or `arch-lint.json` from the project directory up to
+//!    its workspace root ([`arch_lint_core::find_config`], shared with
+//!    `check!()`)
+//! 3. A `[package.metadata.arch-lint]` or `[workspace.metadata.arch-lint]`
+//!    table in `Cargo.toml` along the same path
+//! 4. `~/.arch-lint/config.toml` (global fallback)
+//! 5. No config found → defaults
 
 use std::path::{Path, PathBuf};
 
+use anyhow::{Context, Result};
+use arch_lint_core::ConfigFormat;
+
 /// Where the configuration was found.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfigSource {
@@ -16,6 +24,9 @@ pub enum ConfigSource {
     Explicit(PathBuf),
     /// Found in the project directory.
     Project(PathBuf),
+    /// Embedded in this `Cargo.toml` under `[package.metadata.arch-lint]`
+    /// or `[workspace.metadata.arch-lint]`.
+    CargoMetadata(PathBuf),
     /// Loaded from the global config directory (`~/.arch-lint/`).
     Global(PathBuf),
     /// No config found; defaults will be used.
@@ -27,7 +38,9 @@ impl ConfigSource {
     #[must_use]
     pub fn path(&self) -> Option<&Path> {
         match self {
-            Self::Explicit(p) | Self::Project(p) | Self::Global(p) => Some(p),
+            Self::Explicit(p) | Self::Project(p) | Self::CargoMetadata(p) | Self::Global(p) => {
+                Some(p)
+            }
             Self::Default => None,
         }
     }
@@ -37,10 +50,27 @@ impl ConfigSource {
     pub fn is_global(&self) -> bool {
         matches!(self, Self::Global(_))
     }
-}
 
-/// Project-level config file names, checked in order.
-const PROJECT_CONFIG_NAMES: &[&str] = &["arch-lint.toml", ".arch-lint.toml"];
+    /// Reads the config's content and format; `None` for defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config file cannot be read.
+    pub fn read(&self) -> Result<Option<(String, ConfigFormat)>> {
+        match self {
+            Self::Default => Ok(None),
+            Self::CargoMetadata(manifest) => Ok(Some((
+                arch_lint_core::cargo_metadata_config(manifest).unwrap_or_default(),
+                ConfigFormat::Toml,
+            ))),
+            Self::Explicit(path) | Self::Project(path) | Self::Global(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read config: {}", path.display()))?;
+                Ok(Some((content, ConfigFormat::from_path(path))))
+            }
+        }
+    }
+}
 
 /// Config file name within the global config directory.
 const GLOBAL_CONFIG_NAME: &str = "config.toml";
@@ -65,15 +95,18 @@ fn resolve_inner(
     }
 
     // 2. Project-level config
-    for name in PROJECT_CONFIG_NAMES {
-        let candidate = project_dir.join(name);
-        if candidate.exists() {
-            tracing::debug!("Found project config: {}", candidate.display());
-            return ConfigSource::Project(candidate);
-        }
+    if let Some(candidate) = arch_lint_core::find_config(project_dir) {
+        tracing::debug!("Found project config: {}", candidate.display());
+        return ConfigSource::Project(candidate);
+    }
+
+    // 3. Config embedded in Cargo.toml
+    if let Some(manifest) = arch_lint_core::find_cargo_metadata(project_dir) {
+        tracing::debug!("Found config in Cargo metadata: {}", manifest.display());
+        return ConfigSource::CargoMetadata(manifest);
     }
 
-    // 3. Global fallback
+    // 4. Global fallback
     if let Some(dir) = global_dir {
         let candidate = dir.join(GLOBAL_CONFIG_NAME);
         if candidate.exists() {
@@ -145,6 +178,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn yaml_found_but_toml_preferred() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("arch-lint.yaml"), "").unwrap();
+
+        let result = resolve_inner(tmp.path(), None, None);
+        assert_eq!(
+            result,
+            ConfigSource::Project(tmp.path().join("arch-lint.yaml"))
+        );
+
+        fs::write(tmp.path().join("arch-lint.toml"), "").unwrap();
+        let result = resolve_inner(tmp.path(), None, None);
+        assert_eq!(
+            result,
+            ConfigSource::Project(tmp.path().join("arch-lint.toml"))
+        );
+    }
+
     #[test]
     fn project_dot_arch_lint_toml_found() {
         let tmp = TempDir::new().unwrap();
@@ -170,6 +222,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nested_crate_finds_workspace_config_like_check_macro() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("arch-lint.toml"), "").unwrap();
+        let member = tmp.path().join("crates/app");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(member.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+
+        let result = resolve_inner(&member, None, None);
+        assert_eq!(
+            result,
+            ConfigSource::Project(tmp.path().join("arch-lint.toml"))
+        );
+        assert_eq!(
+            result.path(),
+            arch_lint_core::find_config(&member).as_deref()
+        );
+    }
+
+    #[test]
+    fn cargo_metadata_between_project_file_and_global() {
+        let project = TempDir::new().unwrap();
+        let manifest = project.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            "[package]\nname = \"app\"\n\n[package.metadata.arch-lint]\npreset = \"strict\"\n",
+        )
+        .unwrap();
+        let global = TempDir::new().unwrap();
+        fs::write(global.path().join("config.toml"), "").unwrap();
+
+        let result = resolve_inner(project.path(), None, Some(global.path().to_path_buf()));
+        assert_eq!(result, ConfigSource::CargoMetadata(manifest));
+
+        fs::write(project.path().join("arch-lint.toml"), "").unwrap();
+        let result = resolve_inner(project.path(), None, None);
+        assert!(matches!(result, ConfigSource::Project(_)));
+    }
+
     #[test]
     fn global_fallback_when_no_project_config() {
         let project = TempDir::new().unwrap();
@@ -222,6 +318,10 @@ mod tests {
         let p = PathBuf::from("/tmp/test.toml");
         assert_eq!(ConfigSource::Explicit(p.clone()).path(), Some(p.as_path()));
         assert_eq!(ConfigSource::Project(p.clone()).path(), Some(p.as_path()));
+        assert_eq!(
+            ConfigSource::CargoMetadata(p.clone()).path(),
+            Some(p.as_path())
+        );
         assert_eq!(ConfigSource::Global(p.clone()).path(), Some(p.as_path()));
     }
 
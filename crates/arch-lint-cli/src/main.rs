@@ -4,12 +4,19 @@
 //! ```bash
 //! arch-lint check [OPTIONS] [PATH]
 //! arch-lint list-rules
+//! arch-lint rules-catalog
 //! arch-lint init
 //! ```
+//!
+//! Exit codes: `0` clean, `1` violations at or above the `--fail-on`
+//! threshold (or over a `[gate]` / `--max-warnings` count limit), `2`
+//! internal error.
 
 use anyhow::Result;
+use arch_lint_core::ConfigFormat;
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use tracing_subscriber::EnvFilter;
 
 mod commands;
@@ -36,30 +43,123 @@ struct Cli {
 enum Commands {
     /// Run lint checks
     Check {
-        /// Path to analyze (default: current directory)
-        #[arg(default_value = ".")]
-        path: PathBuf,
+        /// Paths to analyze (default: current directory). With several
+        /// paths, reported files are relative to their common ancestor. A
+        /// path to a `.rs` file analyzes just that file.
+        #[arg(default_value = ".", value_name = "PATH")]
+        paths: Vec<PathBuf>,
+
+        /// Analyze the files listed in FILE instead of PATH, one per line
+        /// (`-` for stdin), e.g. the staged files in a pre-commit hook.
+        /// Config is looked up from the current directory; missing and
+        /// non-Rust files are ignored, and project-wide rules are skipped
+        #[arg(long, value_name = "FILE", conflicts_with = "paths")]
+        files_from: Option<PathBuf>,
 
         /// Output format
         #[arg(short, long, default_value = "text")]
         format: OutputFormat,
 
+        /// Number columns in JSON output from 0 (as LSP does) or 1. Text
+        /// and compact output are always 1-based
+        #[arg(long, default_value = "1", value_name = "BASE")]
+        column_base: ColumnBase,
+
         /// Only run specific rules (comma-separated)
         #[arg(long)]
         rules: Option<String>,
 
+        /// Skip specific rules by name or code (comma-separated); applied
+        /// after `--rules`
+        #[arg(long, value_name = "RULES")]
+        skip_rules: Option<String>,
+
         /// Exclude patterns (can be specified multiple times)
         #[arg(short, long)]
         exclude: Vec<String>,
 
-        /// Engine hint: "syn" (Rust AST) or "ts" (Tree-sitter).
-        /// Auto-detected from config if omitted.
+        /// Assume a cfg option is enabled, as `name` or `key=value` (e.g.
+        /// `--cfg feature=serde`; can be specified multiple times). Items
+        /// compiled out under the given options are not analyzed.
+        #[arg(long, value_name = "OPTION")]
+        cfg: Vec<String>,
+
+        /// Engine hint: "syn" (Rust AST), "ts" (Tree-sitter), or "all" (both,
+        /// one merged report). Auto-detected from config if omitted.
         #[arg(long)]
         engine: Option<EngineHint>,
+
+        /// Disable the incremental cache (`target/arch-lint-cache.json`)
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Minimum severity that causes exit code 1 (default: `fail_on`
+        /// from config, else "error")
+        #[arg(long)]
+        fail_on: Option<FailOn>,
+
+        /// Exit with code 1 if there are more than N warnings (overrides
+        /// `[gate] max_warnings`)
+        #[arg(long, value_name = "N")]
+        max_warnings: Option<usize>,
+
+        /// Treat warnings as errors for the exit code (lowers `--fail-on`
+        /// to "warning"; reported severities are unchanged)
+        #[arg(long)]
+        deny_warnings: bool,
+
+        /// Group text output by file, rule, or severity
+        #[arg(long)]
+        group_by: Option<GroupBy>,
+
+        /// Order of violations in text and compact output
+        #[arg(long, default_value = "location")]
+        sort: SortBy,
+
+        /// Report absolute file paths instead of paths relative to PATH
+        #[arg(long)]
+        absolute_paths: bool,
+
+        /// Omit the source line and `^^^` underline under each violation
+        /// in text output
+        #[arg(long)]
+        no_snippets: bool,
+
+        /// Print a table of violation counts per rule, most violated first
+        #[arg(long)]
+        stats: bool,
+
+        /// Print time spent in discovery, parsing and each rule to stderr,
+        /// slowest first
+        #[arg(long)]
+        timings: bool,
+
+        /// Only validate the config (rule names and options, globs,
+        /// declarative cross-references) and list every problem, without
+        /// analyzing
+        #[arg(long)]
+        config_check: bool,
     },
 
     /// List available rules
-    ListRules,
+    ListRules {
+        /// Output format
+        #[arg(short, long, default_value = "text")]
+        format: OutputFormat,
+
+        /// Only list rules included in this preset (recommended, strict, minimal)
+        #[arg(long)]
+        preset: Option<String>,
+    },
+
+    /// Print every rule and preset as JSON for docs and editor tooling
+    RulesCatalog,
+
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
 
     /// Initialize configuration file
     Init {
@@ -73,6 +173,51 @@ enum Commands {
     },
 }
 
+/// `config` subcommands.
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the effective configuration as TOML, with every rule `check`
+    /// runs and where each value comes from
+    Show {
+        /// Directory to look up the config from (default: current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Preset to expand instead of the config's (recommended, strict, minimal)
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// Only run specific rules (comma-separated), as for `check`
+        #[arg(long)]
+        rules: Option<String>,
+
+        /// Skip specific rules by name or code (comma-separated), as for
+        /// `check`
+        #[arg(long, value_name = "RULES")]
+        skip_rules: Option<String>,
+
+        /// Exclude patterns (can be specified multiple times), as for `check`
+        #[arg(short, long)]
+        exclude: Vec<String>,
+
+        /// Assume a cfg option is enabled, as for `check`
+        #[arg(long, value_name = "OPTION")]
+        cfg: Vec<String>,
+
+        /// Minimum severity that causes exit code 1, as for `check`
+        #[arg(long)]
+        fail_on: Option<FailOn>,
+
+        /// Maximum tolerated warnings, as for `check`
+        #[arg(long, value_name = "N")]
+        max_warnings: Option<usize>,
+
+        /// Treat warnings as errors, as for `check`
+        #[arg(long)]
+        deny_warnings: bool,
+    },
+}
+
 /// Output format for lint results.
 #[derive(Clone, Copy, Debug, Default, clap::ValueEnum)]
 pub enum OutputFormat {
@@ -81,10 +226,61 @@ pub enum OutputFormat {
     Text,
     /// JSON output.
     Json,
+    /// JSON object with only the counts:
+    /// `{errors, warnings, infos, files_checked}`.
+    JsonSummary,
     /// One-line-per-violation compact format.
     Compact,
 }
 
+/// First column number in JSON output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColumnBase {
+    /// The first column is 0.
+    #[value(name = "0")]
+    Zero,
+    /// The first column is 1, as in text output.
+    #[default]
+    #[value(name = "1")]
+    One,
+}
+
+/// Severity threshold for a failing exit code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum FailOn {
+    /// Fail on errors only.
+    Error,
+    /// Fail on warnings and errors.
+    Warning,
+    /// Fail on any violation.
+    Info,
+    /// Never fail on severity (`[gate]` limits and `--max-warnings` still apply).
+    Never,
+}
+
+/// Grouping key for text output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum GroupBy {
+    /// One group per file.
+    File,
+    /// One group per rule.
+    Rule,
+    /// One group per severity, most severe first.
+    Severity,
+}
+
+/// Sort order for violations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortBy {
+    /// File, then line, then column.
+    #[default]
+    Location,
+    /// Most severe first, then location.
+    Severity,
+    /// Rule code, then location.
+    Code,
+}
+
 /// Engine selection hint.
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
 pub enum EngineHint {
@@ -92,9 +288,11 @@ pub enum EngineHint {
     Syn,
     /// Tree-sitter based cross-language analysis (layer enforcement)
     Ts,
+    /// Both engines, merged into one report and exit code
+    All,
 }
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     let cli = Cli::parse();
 
     let filter = if cli.verbose {
@@ -103,53 +301,210 @@ fn main() -> Result<()> {
         EnvFilter::new("info")
     };
 
+    // Logs go to stderr so JSON output on stdout stays parseable.
     tracing_subscriber::fmt()
         .with_env_filter(filter)
         .with_target(false)
+        .with_writer(std::io::stderr)
         .init();
 
+    match run(cli) {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Dispatches the subcommand. Returns `true` if violations should fail the run.
+fn run(cli: Cli) -> Result<bool> {
     match cli.command {
         Commands::Check {
-            path,
+            paths,
+            files_from,
             format,
+            column_base,
             rules,
+            skip_rules,
             exclude,
+            cfg,
             engine,
+            no_cache,
+            fail_on,
+            max_warnings,
+            deny_warnings,
+            group_by,
+            sort,
+            absolute_paths,
+            no_snippets,
+            stats,
+            timings,
+            config_check,
         } => {
-            let source = config_resolver::resolve(&path, cli.config.as_deref());
+            let layout = commands::output::Layout { group_by, sort };
+            // Project config is looked up from the first path.
+            let source = config_resolver::resolve(&paths[0], cli.config.as_deref());
             let engine = engine.unwrap_or_else(|| detect_engine(&source));
+            if config_check {
+                return commands::config_check::run(&source, engine);
+            }
+            let Some(paths) = check_paths(paths, files_from.as_deref(), engine)? else {
+                tracing::info!("No files to check");
+                return Ok(false);
+            };
+            let options = commands::check::CheckOptions {
+                format,
+                column_base,
+                rules_filter: rules,
+                skip_rules,
+                exclude,
+                cfg,
+                use_cache: !no_cache,
+                fail_on,
+                max_warnings,
+                deny_warnings,
+                layout,
+                absolute_paths,
+                snippets: !no_snippets,
+                stats,
+                timings,
+            };
             match engine {
-                EngineHint::Syn => commands::check::run(&path, format, rules, exclude, &source),
-                EngineHint::Ts => commands::check_ts::run(&path, format, &source),
+                EngineHint::Syn => commands::check::run(&paths, &options, &source),
+                EngineHint::All => {
+                    let [path] = paths.as_slice() else {
+                        anyhow::bail!("--engine all checks a single PATH");
+                    };
+                    commands::check_all::run(path, &options, &source)
+                }
+                EngineHint::Ts => {
+                    let [path] = paths.as_slice() else {
+                        anyhow::bail!("the tree-sitter engine checks a single PATH");
+                    };
+                    commands::check_ts::run(path, &options, &source)
+                }
             }
         }
-        Commands::ListRules => {
-            commands::list_rules::run();
-            Ok(())
+        Commands::ListRules { format, preset } => {
+            commands::list_rules::run(format, preset.as_deref())?;
+            Ok(false)
+        }
+        Commands::RulesCatalog => {
+            commands::rules_catalog::run()?;
+            Ok(false)
+        }
+        Commands::Config { command } => {
+            run_config(command, cli.config.as_deref())?;
+            Ok(false)
         }
         Commands::Init { force, ts } => {
             if ts {
-                commands::init_ts::run(force)
+                commands::init_ts::run(force)?;
             } else {
-                commands::init::run(force)
+                commands::init::run(force)?;
             }
+            Ok(false)
         }
     }
 }
 
-/// Auto-detect engine from config: if `[[layers]]` present → ts, else → syn.
-fn detect_engine(source: &config_resolver::ConfigSource) -> EngineHint {
-    if let Some(p) = source.path() {
-        if let Ok(content) = std::fs::read_to_string(p) {
-            if content.contains("[[layers]]") {
-                tracing::info!(
-                    "Detected [[layers]] in {}, using tree-sitter engine",
-                    p.display()
-                );
-                return EngineHint::Ts;
-            }
+/// Returns the paths to check: PATH, or the files listed by `--files-from`
+/// (`None` if the list names no existing file).
+fn check_paths(
+    paths: Vec<PathBuf>,
+    files_from: Option<&Path>,
+    engine: EngineHint,
+) -> Result<Option<Vec<PathBuf>>> {
+    let Some(list) = files_from else {
+        return Ok(Some(paths));
+    };
+    if !matches!(engine, EngineHint::Syn) {
+        anyhow::bail!("--files-from is only supported by the Rust engine");
+    }
+    let files = commands::check::read_file_list(list)?;
+    Ok((!files.is_empty()).then_some(files))
+}
+
+/// Dispatches a `config` subcommand.
+fn run_config(command: ConfigCommand, config: Option<&Path>) -> Result<()> {
+    match command {
+        ConfigCommand::Show {
+            path,
+            preset,
+            rules,
+            skip_rules,
+            exclude,
+            cfg,
+            fail_on,
+            max_warnings,
+            deny_warnings,
+        } => {
+            let source = config_resolver::resolve(&path, config);
+            let overrides = commands::config::Overrides {
+                preset,
+                rules_filter: rules,
+                skip_rules,
+                exclude,
+                cfg,
+                fail_on,
+                max_warnings,
+                deny_warnings,
+            };
+            commands::config::show(&source, &overrides)
         }
     }
+}
 
+/// Auto-detect engine from config: `[analyzer] engines = ["syn", "ts"]` →
+/// all, else if `[[layers]]` present → ts, else → syn.
+///
+/// The config is read in its own format (TOML, YAML, or JSON), or from the
+/// `Cargo.toml` metadata table it was found in.
+fn detect_engine(source: &config_resolver::ConfigSource) -> EngineHint {
+    let (Some(p), Ok(Some((content, format)))) = (source.path(), source.read()) else {
+        return EngineHint::Syn;
+    };
+    let Some(config) = config_value(&content, format) else {
+        return EngineHint::Syn;
+    };
+
+    if enables_both_engines(&config) {
+        tracing::info!("{} enables both engines", p.display());
+        return EngineHint::All;
+    }
+    if has_layers(&config) {
+        tracing::info!(
+            "Detected [[layers]] in {}, using tree-sitter engine",
+            p.display()
+        );
+        return EngineHint::Ts;
+    }
     EngineHint::Syn
 }
+
+/// Parses a config into a format-neutral value, or `None` if it is invalid.
+fn config_value(content: &str, format: ConfigFormat) -> Option<serde_json::Value> {
+    match format {
+        ConfigFormat::Toml => toml::from_str(content).ok(),
+        ConfigFormat::Yaml => serde_yaml::from_str(content).ok(),
+        ConfigFormat::Json => serde_json::from_str(content).ok(),
+    }
+}
+
+/// Whether `[analyzer] engines` lists both `"syn"` and `"ts"`.
+fn enables_both_engines(config: &serde_json::Value) -> bool {
+    let Some(engines) = config["analyzer"]["engines"].as_array() else {
+        return false;
+    };
+    let has = |name: &str| engines.iter().any(|e| e.as_str() == Some(name));
+    has("syn") && has("ts")
+}
+
+/// Whether the config declares any `[[layers]]`.
+fn has_layers(config: &serde_json::Value) -> bool {
+    config["layers"]
+        .as_array()
+        .is_some_and(|layers| !layers.is_empty())
+}
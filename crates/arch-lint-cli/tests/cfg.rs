@@ -0,0 +1,57 @@
+//! `--cfg` and `[analyzer] cfg` skip items compiled out under the given
+//! options.
+
+#![allow(clippy::expect_used)]
+
+use std::process::{Command, Output};
+
+fn check(project: &std::path::Path, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(project)
+        .args(["--format", "compact", "--no-cache", "--rules", "AL001"])
+        .args(extra)
+        .output()
+        .expect("run arch-lint")
+}
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "#[cfg(feature = \"sqlx\")]\npub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+    )
+    .expect("write lib.rs");
+    dir
+}
+
+#[test]
+fn items_under_inactive_features_are_skipped() {
+    let dir = project();
+    assert_eq!(check(dir.path(), &[]).status.code(), Some(1));
+    assert_eq!(
+        check(dir.path(), &["--cfg", "feature=sqlx"]).status.code(),
+        Some(1)
+    );
+    assert_eq!(
+        check(dir.path(), &["--cfg", "feature=serde"]).status.code(),
+        Some(0)
+    );
+
+    std::fs::write(
+        dir.path().join("arch-lint.toml"),
+        "[analyzer]\ncfg = [\"feature=\\\"serde\\\"\"]\n",
+    )
+    .expect("write config");
+    assert_eq!(check(dir.path(), &[]).status.code(), Some(0));
+}
+
+#[test]
+fn malformed_cfg_option_is_an_error() {
+    let dir = project();
+    let output = check(dir.path(), &["--cfg", "feature serde"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("analyzer.cfg"), "{stderr}");
+}
@@ -0,0 +1,47 @@
+//! `--column-base 0` renumbers JSON columns; text and compact stay 1-based.
+
+#![allow(clippy::expect_used)]
+
+use std::process::Command;
+
+fn check(extra: &[&str]) -> String {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n",
+    )
+    .expect("write lib.rs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(dir.path())
+        .args(["--no-cache", "--no-snippets"])
+        .args(extra)
+        .output()
+        .expect("run arch-lint");
+    String::from_utf8(output.stdout).expect("stdout is UTF-8")
+}
+
+fn json_column(extra: &[&str]) -> serde_json::Value {
+    let stdout = check(&[&["--format", "json"], extra].concat());
+    let result: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is JSON");
+    result["violations"][0]["location"]["column"].clone()
+}
+
+#[test]
+fn json_columns_follow_column_base() {
+    // `unwrap` starts at the 7th character of `    x.unwrap()`.
+    assert_eq!(json_column(&[]), 7);
+    assert_eq!(json_column(&["--column-base", "1"]), 7);
+    assert_eq!(json_column(&["--column-base", "0"]), 6);
+}
+
+#[test]
+fn text_and_compact_stay_one_based() {
+    let text = check(&["--column-base", "0"]);
+    assert!(text.contains("src/lib.rs:2:7\n"), "{text}");
+
+    let compact = check(&["--format", "compact", "--column-base", "0"]);
+    assert!(compact.contains("src/lib.rs:2:7: "), "{compact}");
+}
@@ -0,0 +1,36 @@
+//! `--format compact` prints one `path:line:col: [CODE] severity message`
+//! line per violation, with root-relative paths.
+
+#![allow(clippy::expect_used)]
+
+use std::process::Command;
+
+#[test]
+fn compact_output_matches_golden() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src/io")).expect("create src/io");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub mod io;\n\npub fn f(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n",
+    )
+    .expect("write lib.rs");
+    std::fs::write(
+        dir.path().join("src/io/read.rs"),
+        "pub fn g(x: Result<u8, ()>) -> u8 {\n    x.expect(\"set\")\n}\n",
+    )
+    .expect("write read.rs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(dir.path())
+        .args(["--no-cache", "--format", "compact", "--rules", "AL001"])
+        .output()
+        .expect("run arch-lint");
+    let stdout = String::from_utf8(output.stdout).expect("stdout is UTF-8");
+
+    assert_eq!(
+        stdout,
+        "src/io/read.rs:2:7: [AL001] error .expect() is forbidden in production code\n\
+         src/lib.rs:4:7: [AL001] error .unwrap() is forbidden in production code\n"
+    );
+}
@@ -0,0 +1,46 @@
+//! `check --config-check` validates the config without analyzing.
+
+#![allow(clippy::expect_used)]
+
+use std::process::{Command, Output};
+
+fn config_check(project: &std::path::Path, config: &str) -> Output {
+    std::fs::write(project.join("arch-lint.toml"), config).expect("write config");
+    Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(project)
+        .arg("--config-check")
+        .output()
+        .expect("run arch-lint")
+}
+
+#[test]
+fn lists_every_problem_and_fails() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    // Would fail `check`; `--config-check` does not analyze.
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+    )
+    .expect("write lib.rs");
+
+    let output = config_check(dir.path(), "preset = \"strict\"\n");
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Config OK: "), "{stdout}");
+
+    let output = config_check(
+        dir.path(),
+        "[rules.no-such-rule]\nenabled = false\n\n\
+         [[restrict-use]]\nname = \"a\"\nscope = \"missing\"\ndeny = [\"x\"]\nmessage = \"m\"\n",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("error: rules.no-such-rule: unknown rule"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("missing"), "{stderr}");
+    assert!(stderr.contains("2 problem(s) in "), "{stderr}");
+}
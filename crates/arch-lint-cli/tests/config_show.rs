@@ -0,0 +1,83 @@
+//! `config show` prints the effective config with the preset's rules
+//! expanded, and `check` runs the rules it lists.
+
+#![allow(clippy::expect_used)]
+
+use std::process::Command;
+
+#[test]
+fn config_show_expands_preset_and_keeps_overrides() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::write(
+        dir.path().join("arch-lint.toml"),
+        "preset = \"minimal\"\n\n[rules.no-sync-io]\nenabled = false\n",
+    )
+    .expect("write config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .args(["config", "show"])
+        .arg(dir.path())
+        .output()
+        .expect("run arch-lint");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is UTF-8");
+    let config: toml::Table = stdout.parse().expect("stdout is TOML");
+    assert_eq!(config["preset"].as_str(), Some("minimal"));
+    let rules = config["rules"].as_table().expect("rules table");
+    assert_eq!(
+        rules["no-unwrap-expect"]["severity"].as_str(),
+        Some("error")
+    );
+    assert_eq!(rules["no-sync-io"]["enabled"].as_bool(), Some(false));
+    assert!(!rules.contains_key("no-error-swallowing"), "{stdout}");
+}
+
+#[test]
+fn check_runs_the_rules_config_show_lists() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::write(dir.path().join("arch-lint.toml"), "preset = \"minimal\"\n")
+        .expect("write config");
+    std::fs::create_dir(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f() -> Result<(), std::io::Error> {\n    let _ = std::fs::read(\"x\");\n    Ok(())\n}\n",
+    )
+    .expect("write source");
+
+    let show = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .args(["config", "show", "--fail-on", "warning"])
+        .arg(dir.path())
+        .output()
+        .expect("run arch-lint");
+    let stdout = String::from_utf8(show.stdout).expect("stdout is UTF-8");
+    assert!(stdout.contains("preset = \"minimal\""), "{stdout}");
+    assert!(stdout.contains("# --fail-on"), "{stdout}");
+    assert!(
+        !stdout.contains("[rules.no-silent-result-drop]"),
+        "{stdout}"
+    );
+
+    // AL013 would flag the dropped Result, but the minimal preset omits it.
+    let check = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .args(["check", "--no-cache", "--fail-on", "info"])
+        .arg(dir.path())
+        .output()
+        .expect("run arch-lint");
+    let stdout = String::from_utf8(check.stdout).expect("stdout is UTF-8");
+    assert!(check.status.success(), "{stdout}");
+    assert!(!stdout.contains("AL013"), "{stdout}");
+}
+
+#[test]
+fn config_show_rejects_unknown_preset() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .args(["config", "show", "--preset", "lenient"])
+        .arg(dir.path())
+        .output()
+        .expect("run arch-lint");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is UTF-8");
+    assert!(stderr.contains("unknown preset `lenient`"), "{stderr}");
+}
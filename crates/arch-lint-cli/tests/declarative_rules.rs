@@ -0,0 +1,121 @@
+//! `check` enforces the config's declarative rules, in every config format,
+//! like `check!()` does.
+
+#![allow(clippy::expect_used)]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn check(project: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(project)
+        .args(["--format", "compact", "--no-cache"])
+        .output()
+        .expect("run arch-lint")
+}
+
+/// A project whose domain module uses `sqlx`, with `config` written to
+/// `config_file`.
+fn project(config_file: &str, config: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src/domain")).expect("create src/domain");
+    std::fs::write(dir.path().join("src/lib.rs"), "mod domain;\n").expect("write lib.rs");
+    std::fs::write(dir.path().join("src/domain/mod.rs"), "use sqlx::PgPool;\n")
+        .expect("write domain/mod.rs");
+    std::fs::write(dir.path().join(config_file), config).expect("write config");
+    dir
+}
+
+fn assert_restrict_use_fails(output: &Output) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(
+        stdout.contains("[ALD001] error Domain must not use sqlx."),
+        "{stdout}"
+    );
+    assert!(stdout.contains("domain/mod.rs:1:"), "{stdout}");
+}
+
+#[test]
+fn toml_restrict_use_violation_fails_check() {
+    let dir = project(
+        "arch-lint.toml",
+        r#"
+[[restrict-use]]
+name = "no-sqlx-in-domain"
+files = ["src/domain/**"]
+deny = ["sqlx::*"]
+message = "Domain must not use sqlx."
+"#,
+    );
+    assert_restrict_use_fails(&check(dir.path()));
+}
+
+#[test]
+fn yaml_restrict_use_violation_fails_check() {
+    let dir = project(
+        "arch-lint.yaml",
+        r#"
+restrict-use:
+  - name: no-sqlx-in-domain
+    files: ["src/domain/**"]
+    deny: ["sqlx::*"]
+    message: Domain must not use sqlx.
+"#,
+    );
+    assert_restrict_use_fails(&check(dir.path()));
+}
+
+#[test]
+fn json_restrict_use_violation_fails_check() {
+    let dir = project(
+        "arch-lint.json",
+        r#"{
+  "restrict-use": [{
+    "name": "no-sqlx-in-domain",
+    "files": ["src/domain/**"],
+    "deny": ["sqlx::*"],
+    "message": "Domain must not use sqlx."
+  }]
+}"#,
+    );
+    assert_restrict_use_fails(&check(dir.path()));
+}
+
+#[test]
+fn cargo_metadata_restrict_use_violation_fails_check() {
+    let dir = project(
+        "Cargo.toml",
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[[package.metadata.arch-lint.restrict-use]]
+name = "no-sqlx-in-domain"
+files = ["src/domain/**"]
+deny = ["sqlx::*"]
+message = "Domain must not use sqlx."
+"#,
+    );
+    assert_restrict_use_fails(&check(dir.path()));
+}
+
+#[test]
+fn declarative_project_rules_run() {
+    let dir = project(
+        "arch-lint.toml",
+        r#"
+[[scopes]]
+name = "infra"
+paths = ["src/infra/**"]
+"#,
+    );
+    let output = check(dir.path());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Scope `infra` matches no source files"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("arch-lint.toml:3:"), "{stdout}");
+}
@@ -0,0 +1,64 @@
+//! `--deny-warnings` / `deny_warnings = true` fail the run on warnings
+//! without changing the reported severity.
+
+#![allow(clippy::expect_used)]
+
+use std::process::{Command, Output};
+
+fn check(config: &str, extra: &[&str]) -> Output {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+    )
+    .expect("write lib.rs");
+    std::fs::write(
+        dir.path().join("arch-lint.toml"),
+        format!("{config}\n[rules.no-unwrap-expect]\nseverity = \"warning\"\n"),
+    )
+    .expect("write config");
+
+    Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(dir.path())
+        .args(["--format", "json", "--no-cache"])
+        .args(extra)
+        .output()
+        .expect("run arch-lint")
+}
+
+fn severities(output: &Output) -> Vec<String> {
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    result["violations"]
+        .as_array()
+        .expect("violations array")
+        .iter()
+        .map(|v| v["severity"].as_str().expect("severity").to_string())
+        .collect()
+}
+
+#[test]
+fn warnings_pass_by_default() {
+    let output = check("", &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(severities(&output), ["warning"]);
+}
+
+#[test]
+fn flag_fails_on_warnings_and_keeps_severity() {
+    let output = check("", &["--deny-warnings"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(severities(&output), ["warning"]);
+}
+
+#[test]
+fn config_fails_on_warnings() {
+    let output = check("deny_warnings = true", &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(severities(&output), ["warning"]);
+
+    // `--fail-on never` still disables severity gating.
+    let output = check("deny_warnings = true", &["--fail-on", "never"]);
+    assert_eq!(output.status.code(), Some(0));
+}
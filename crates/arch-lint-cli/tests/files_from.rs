@@ -0,0 +1,87 @@
+//! `check --files-from` analyzes exactly the listed files, as a pre-commit
+//! hook passes them.
+
+#![allow(clippy::expect_used)]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn repo() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"repo\"\n",
+    )
+    .expect("write Cargo.toml");
+    std::fs::write(
+        dir.path().join("arch-lint.toml"),
+        "[rules.no-unwrap-expect]\nseverity = \"warning\"\n",
+    )
+    .expect("write config");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n",
+    )
+    .expect("write lib.rs");
+    std::fs::write(
+        dir.path().join("src/other.rs"),
+        "pub fn g(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n",
+    )
+    .expect("write other.rs");
+    std::fs::write(dir.path().join("README.md"), "# repo\n").expect("write README");
+    dir
+}
+
+/// Runs `check --files-from -` in `dir` with `list` on stdin.
+fn check_staged(dir: &tempfile::TempDir, list: &str, extra: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .current_dir(dir.path())
+        .args([
+            "check",
+            "--files-from",
+            "-",
+            "--format",
+            "compact",
+            "--no-cache",
+        ])
+        .args(["--rules", "AL001"])
+        .args(extra)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run arch-lint");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(list.as_bytes())
+        .expect("write file list");
+    child.wait_with_output().expect("wait for arch-lint")
+}
+
+#[test]
+fn analyzes_only_listed_files_with_repo_config() {
+    let dir = repo();
+    let output = check_staged(&dir, "src/lib.rs\nREADME.md\nsrc/deleted.rs\n\n", &[]);
+    let stdout = String::from_utf8(output.stdout).expect("utf-8 output");
+
+    // The repo config lowers AL001 to a warning, below the default gate.
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert_eq!(
+        stdout,
+        "src/lib.rs:2:7: [AL001] warning .unwrap() is forbidden in production code\n"
+    );
+
+    let output = check_staged(&dir, "src/lib.rs\r\n", &["--fail-on", "warning"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn empty_list_passes() {
+    let dir = repo();
+    let output = check_staged(&dir, "README.md\n", &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,32 @@
+//! `--format json-summary` prints only the violation counts.
+
+#![allow(clippy::expect_used)]
+
+use std::process::Command;
+
+#[test]
+fn json_summary_prints_counts_only() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+    )
+    .expect("write lib.rs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(dir.path())
+        .args(["--format", "json-summary", "--no-cache"])
+        .output()
+        .expect("run arch-lint");
+
+    assert_eq!(output.status.code(), Some(1));
+    let summary: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    assert_eq!(summary["errors"], 1);
+    assert_eq!(summary["warnings"], 0);
+    assert_eq!(summary["infos"], 0);
+    assert_eq!(summary["files_checked"], 1);
+    assert_eq!(summary.as_object().map(serde_json::Map::len), Some(4));
+}
@@ -0,0 +1,190 @@
+//! `[analyzer] engines = ["syn", "ts"]` merges Rust and Kotlin results.
+
+#![allow(clippy::expect_used)]
+
+use std::path::Path;
+use std::process::Command;
+
+const CONFIG: &str = r#"
+[analyzer]
+engines = ["syn", "ts"]
+
+[[layers]]
+name = "domain"
+packages = ["com.example.domain"]
+
+[[layers]]
+name = "infra"
+packages = ["com.example.infra"]
+
+[dependencies]
+domain = []
+infra = ["domain"]
+"#;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    std::fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+    std::fs::write(path, content).expect("write file");
+}
+
+fn mixed_project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    write(dir.path(), "arch-lint.toml", CONFIG);
+    write(
+        dir.path(),
+        "src/lib.rs",
+        "pub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+    );
+    write(
+        dir.path(),
+        "kotlin/com/example/domain/User.kt",
+        "package com.example.domain\n\nimport com.example.infra.Db\n\nclass User\n",
+    );
+    dir
+}
+
+fn check_json(root: &Path, extra: &[&str]) -> (Option<i32>, serde_json::Value) {
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(root)
+        .args(["--format", "json", "--no-cache"])
+        .args(extra)
+        .output()
+        .expect("run arch-lint");
+    let json = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    (output.status.code(), json)
+}
+
+fn codes(json: &serde_json::Value) -> Vec<String> {
+    let mut codes: Vec<String> = json["violations"]
+        .as_array()
+        .expect("violations array")
+        .iter()
+        .map(|v| v["code"].as_str().expect("code").to_string())
+        .collect();
+    codes.sort();
+    codes
+}
+
+#[test]
+fn config_enabling_both_engines_merges_results() {
+    let dir = mixed_project();
+    let (status, json) = check_json(dir.path(), &[]);
+
+    assert_eq!(status, Some(1));
+    assert_eq!(codes(&json), ["AL001", "LAYER001"]);
+    assert_eq!(json["files_checked"], 2);
+}
+
+#[test]
+fn engine_flag_overrides_config() {
+    let dir = mixed_project();
+
+    let (_, json) = check_json(dir.path(), &["--engine", "ts"]);
+    assert_eq!(codes(&json), ["LAYER001"]);
+
+    let (_, json) = check_json(dir.path(), &["--engine", "syn"]);
+    assert_eq!(codes(&json), ["AL001"]);
+}
+
+#[test]
+fn gate_applies_to_merged_counts() {
+    let dir = mixed_project();
+    write(
+        dir.path(),
+        "arch-lint.toml",
+        &format!("[gate]\nmax_errors = 1\n{CONFIG}"),
+    );
+
+    // One error per engine: each alone is within the limit, together not.
+    let (status, _) = check_json(dir.path(), &["--engine", "syn", "--fail-on", "never"]);
+    assert_eq!(status, Some(0));
+    let (status, _) = check_json(dir.path(), &["--fail-on", "never"]);
+    assert_eq!(status, Some(1));
+}
+
+#[test]
+fn tree_sitter_engine_uses_config_gate() {
+    let dir = mixed_project();
+    let (status, _) = check_json(dir.path(), &["--engine", "ts", "--fail-on", "never"]);
+    assert_eq!(status, Some(0));
+
+    write(
+        dir.path(),
+        "arch-lint.toml",
+        &format!("[gate]\nmax_errors = 0\n{CONFIG}"),
+    );
+    let (status, _) = check_json(dir.path(), &["--engine", "ts", "--fail-on", "never"]);
+    assert_eq!(status, Some(1), "config [gate] applies");
+}
+
+#[test]
+fn yaml_and_json_configs_select_engines() {
+    let yaml = r#"
+analyzer:
+  engines: [syn, ts]
+layers:
+  - name: domain
+    packages: [com.example.domain]
+  - name: infra
+    packages: [com.example.infra]
+dependencies:
+  domain: []
+  infra: [domain]
+"#;
+    let json = r#"{
+  "layers": [
+    {"name": "domain", "packages": ["com.example.domain"]},
+    {"name": "infra", "packages": ["com.example.infra"]}
+  ],
+  "dependencies": {"domain": [], "infra": ["domain"]}
+}"#;
+
+    let dir = mixed_project();
+    std::fs::remove_file(dir.path().join("arch-lint.toml")).expect("remove toml config");
+    write(dir.path(), "arch-lint.yaml", yaml);
+    let (_, result) = check_json(dir.path(), &[]);
+    assert_eq!(codes(&result), ["AL001", "LAYER001"]);
+
+    std::fs::remove_file(dir.path().join("arch-lint.yaml")).expect("remove yaml config");
+    write(dir.path(), "arch-lint.json", json);
+    let (_, result) = check_json(dir.path(), &[]);
+    assert_eq!(
+        codes(&result),
+        ["LAYER001"],
+        "layers alone select tree-sitter"
+    );
+}
+
+#[test]
+fn cargo_metadata_config_selects_engines() {
+    let dir = mixed_project();
+    std::fs::remove_file(dir.path().join("arch-lint.toml")).expect("remove toml config");
+    write(
+        dir.path(),
+        "Cargo.toml",
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[package.metadata.arch-lint.analyzer]
+engines = ["syn", "ts"]
+
+[[package.metadata.arch-lint.layers]]
+name = "domain"
+packages = ["com.example.domain"]
+
+[[package.metadata.arch-lint.layers]]
+name = "infra"
+packages = ["com.example.infra"]
+
+[package.metadata.arch-lint.dependencies]
+domain = []
+infra = ["domain"]
+"#,
+    );
+
+    let (_, json) = check_json(dir.path(), &[]);
+    assert_eq!(codes(&json), ["AL001", "LAYER001"]);
+}
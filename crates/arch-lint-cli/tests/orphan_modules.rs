@@ -0,0 +1,51 @@
+//! `check` runs the built-in `orphan-modules` project rule in every preset,
+//! unless the config or `--skip-rules` opts out.
+
+#![allow(clippy::expect_used)]
+
+use std::process::{Command, Output};
+
+fn check(project: &std::path::Path, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(project)
+        .args(["--format", "compact", "--no-cache"])
+        .args(extra)
+        .output()
+        .expect("run arch-lint")
+}
+
+/// A project with `src/dead.rs`, which no `mod` declaration reaches.
+fn project(config: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(dir.path().join("src/lib.rs"), "pub fn f() {}\n").expect("write lib.rs");
+    std::fs::write(dir.path().join("src/dead.rs"), "pub fn g() {}\n").expect("write dead.rs");
+    std::fs::write(dir.path().join("arch-lint.toml"), config).expect("write config");
+    dir
+}
+
+#[test]
+fn orphan_file_is_reported_in_every_preset() {
+    for preset in ["recommended", "strict", "minimal"] {
+        let dir = project(&format!("preset = \"{preset}\"\n"));
+        let output = check(dir.path(), &[]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("[AL040]"), "{preset}: {stdout}");
+        assert!(stdout.contains("dead.rs"), "{preset}: {stdout}");
+    }
+}
+
+#[test]
+fn orphan_modules_can_be_disabled() {
+    let dir = project("[rules.orphan-modules]\nenabled = false\n");
+    let stdout = String::from_utf8_lossy(&check(dir.path(), &[]).stdout).into_owned();
+    assert!(!stdout.contains("[AL040]"), "{stdout}");
+
+    let dir = project("");
+    let output = check(dir.path(), &["--skip-rules", "orphan-modules"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("[AL040]"), "{stdout}");
+    let logs = String::from_utf8_lossy(&output.stderr);
+    assert!(!logs.contains("Unknown rule"), "{logs}");
+}
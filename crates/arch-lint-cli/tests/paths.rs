@@ -0,0 +1,112 @@
+//! Output paths must not depend on the working directory.
+
+#![allow(clippy::expect_used)]
+
+use std::path::Path;
+use std::process::Command;
+
+/// Runs `check --format compact` and returns the violation lines.
+fn check_compact(cwd: &Path, target: &Path, extra: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .current_dir(cwd)
+        .arg("check")
+        .arg(target)
+        .args(["--format", "compact", "--no-cache"])
+        .args(extra)
+        .output()
+        .expect("run arch-lint");
+    assert_eq!(output.status.code(), Some(1), "expected violations");
+    // Keep only the compact violation lines.
+    String::from_utf8(output.stdout)
+        .expect("utf-8 output")
+        .lines()
+        .filter(|line| line.contains(" [AL"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("project/src")).expect("create src");
+    std::fs::write(
+        dir.path().join("project/src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+    )
+    .expect("write lib.rs");
+    dir
+}
+
+#[test]
+fn output_is_identical_from_any_cwd() {
+    let dir = project();
+    let project = dir.path().join("project");
+
+    let from_project = check_compact(&project, Path::new("."), &[]);
+    let from_parent = check_compact(dir.path(), Path::new("project"), &[]);
+    let from_elsewhere = check_compact(&project.join("src"), &project, &[]);
+
+    assert!(from_project.starts_with("src/lib.rs:1:"), "{from_project}");
+    assert_eq!(from_project, from_parent);
+    assert_eq!(from_project, from_elsewhere);
+}
+
+#[test]
+fn absolute_paths_flag_reports_full_paths() {
+    let dir = project();
+    let project = dir.path().join("project");
+
+    let output = check_compact(dir.path(), Path::new("project"), &["--absolute-paths"]);
+    let expected = project.join("src/lib.rs");
+    assert!(
+        output.starts_with(&format!("{}:1:", expected.display())),
+        "{output}"
+    );
+}
+
+#[test]
+fn multiple_paths_report_relative_to_common_ancestor() {
+    let dir = project();
+    std::fs::create_dir_all(dir.path().join("other/src")).expect("create src");
+    std::fs::write(
+        dir.path().join("other/src/lib.rs"),
+        "pub fn g(x: Option<u8>) -> u8 { x.expect(\"g\") }\n",
+    )
+    .expect("write lib.rs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .current_dir(dir.path())
+        .args([
+            "check",
+            "project",
+            "other",
+            "--format",
+            "compact",
+            "--no-cache",
+        ])
+        .output()
+        .expect("run arch-lint");
+    assert_eq!(output.status.code(), Some(1), "expected violations");
+    let stdout = String::from_utf8(output.stdout).expect("utf-8 output");
+    assert!(stdout.contains("project/src/lib.rs:1:"), "{stdout}");
+    assert!(stdout.contains("other/src/lib.rs:1:"), "{stdout}");
+}
+
+#[test]
+fn single_file_path_checks_only_that_file() {
+    let dir = project();
+    let project = dir.path().join("project");
+    std::fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"project\"\n",
+    )
+    .expect("write Cargo.toml");
+    std::fs::write(
+        project.join("src/other.rs"),
+        "pub fn g(x: Option<u8>) -> u8 { x.expect(\"g\") }\n",
+    )
+    .expect("write other.rs");
+
+    let output = check_compact(dir.path(), Path::new("project/src/lib.rs"), &[]);
+    assert!(output.starts_with("src/lib.rs:1:"), "{output}");
+    assert!(!output.contains("other.rs"), "{output}");
+}
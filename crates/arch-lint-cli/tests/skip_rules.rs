@@ -0,0 +1,56 @@
+//! `--skip-rules` removes rules from the active set.
+
+#![allow(clippy::expect_used)]
+
+use std::process::{Command, Output};
+
+fn check(project: &std::path::Path, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(project)
+        .args(["--format", "compact", "--no-cache"])
+        .args(extra)
+        .output()
+        .expect("run arch-lint")
+}
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+    )
+    .expect("write lib.rs");
+    dir
+}
+
+#[test]
+fn skipping_the_only_failing_rule_passes() {
+    let dir = project();
+    assert_eq!(check(dir.path(), &[]).status.code(), Some(1));
+    assert_eq!(
+        check(dir.path(), &["--skip-rules", "no-unwrap-expect"])
+            .status
+            .code(),
+        Some(0)
+    );
+    assert_eq!(
+        check(
+            dir.path(),
+            &["--rules", "AL001,AL002", "--skip-rules", "AL001"]
+        )
+        .status
+        .code(),
+        Some(0)
+    );
+}
+
+#[test]
+fn unknown_skip_name_warns_without_failing() {
+    let dir = project();
+    let output = check(dir.path(), &["--skip-rules", "AL001,not-a-rule"]);
+    assert_eq!(output.status.code(), Some(0));
+    let logs = String::from_utf8_lossy(&output.stderr);
+    assert!(logs.contains("not-a-rule"), "{logs}");
+}
@@ -0,0 +1,45 @@
+//! Text output shows the offending source line under each violation.
+
+#![allow(clippy::expect_used)]
+
+use std::process::Command;
+
+fn check(extra: &[&str]) -> String {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>) -> u8 {\n    x.unwrap()\n}\n",
+    )
+    .expect("write lib.rs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(dir.path())
+        .arg("--no-cache")
+        .args(extra)
+        .output()
+        .expect("run arch-lint");
+    String::from_utf8(output.stdout).expect("stdout is UTF-8")
+}
+
+#[test]
+fn underlines_violation_column() {
+    let stdout = check(&[]);
+    assert!(stdout.contains("  2 |     x.unwrap()\n"), "{stdout}");
+    let source = stdout
+        .lines()
+        .position(|line| line == "  2 |     x.unwrap()")
+        .expect("source line");
+    let underline = stdout.lines().nth(source + 1).expect("underline");
+    assert!(underline.trim_start_matches(['|', ' ']).starts_with('^'));
+    assert!(underline.ends_with('^'), "{underline}");
+}
+
+#[test]
+fn no_snippets_restores_terse_output() {
+    let stdout = check(&["--no-snippets"]);
+    assert!(stdout.contains("unwrap"), "{stdout}");
+    assert!(!stdout.contains(" | "), "{stdout}");
+    assert!(!stdout.contains('^'), "{stdout}");
+}
@@ -0,0 +1,48 @@
+//! `--stats` prints violation counts per rule, most violated first.
+
+#![allow(clippy::expect_used)]
+
+use std::process::{Command, Output};
+
+fn check(extra: &[&str]) -> Output {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub fn f(x: Option<u8>, y: Option<u8>) -> u8 {\n    \
+         let _ = std::fs::read(\"a\");\n    x.unwrap() + y.unwrap()\n}\n",
+    )
+    .expect("write lib.rs");
+
+    Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(dir.path())
+        .args([
+            "--no-cache",
+            "--stats",
+            "--rules",
+            "no-unwrap-expect,no-sync-io",
+        ])
+        .args(extra)
+        .output()
+        .expect("run arch-lint")
+}
+
+#[test]
+fn text_output_ends_with_stats_table() {
+    let stdout = String::from_utf8(check(&[]).stdout).expect("stdout is UTF-8");
+    let table = stdout
+        .split("Violations by rule:\n")
+        .nth(1)
+        .expect("stats table");
+    assert_eq!(table, "  2  no-unwrap-expect\n  1  no-sync-io\n");
+}
+
+#[test]
+fn json_stdout_stays_parseable() {
+    let output = check(&["--format", "json"]);
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    assert_eq!(result["violations"].as_array().map(Vec::len), Some(3));
+    let stderr = String::from_utf8(output.stderr).expect("stderr is UTF-8");
+    assert!(stderr.contains("Violations by rule:"), "{stderr}");
+}
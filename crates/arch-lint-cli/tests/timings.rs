@@ -0,0 +1,56 @@
+//! `--timings` prints a per-phase and per-rule time breakdown to stderr.
+
+#![allow(clippy::expect_used)]
+
+use std::process::Command;
+
+#[test]
+fn timings_table_lists_phases_and_rules_on_stderr() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(dir.path().join("src/lib.rs"), "pub fn f() {}\n").expect("write lib.rs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .arg("check")
+        .arg(dir.path())
+        .args([
+            "--no-cache",
+            "--timings",
+            "--format",
+            "json",
+            "--rules",
+            "no-unwrap-expect,no-sync-io",
+        ])
+        .output()
+        .expect("run arch-lint");
+
+    let _: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    let stderr = String::from_utf8(output.stderr).expect("stderr is UTF-8");
+    let table = stderr
+        .split("Timings (total ")
+        .nth(1)
+        .expect("timings table");
+    for name in ["discovery", "parsing", "no-unwrap-expect", "no-sync-io"] {
+        assert!(
+            table
+                .lines()
+                .any(|line| line.ends_with(&format!("ms  {name}"))),
+            "{name} missing from:\n{table}"
+        );
+    }
+}
+
+#[test]
+fn no_timings_without_flag() {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    std::fs::create_dir_all(dir.path().join("src")).expect("create src");
+    std::fs::write(dir.path().join("src/lib.rs"), "pub fn f() {}\n").expect("write lib.rs");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_arch-lint"))
+        .args(["check", "--no-cache"])
+        .arg(dir.path())
+        .output()
+        .expect("run arch-lint");
+    let stderr = String::from_utf8(output.stderr).expect("stderr is UTF-8");
+    assert!(!stderr.contains("Timings"), "{stderr}");
+}
@@ -1,11 +1,24 @@
 //! Core analyzer for orchestrating lint execution.
 
-use crate::config::{Config, RuleConfig};
-use crate::context::{FileContext, ProjectContext};
+use crate::cache::{self, AnalysisCache};
+use crate::config::{Config, ConfigError, RuleConfig};
+use crate::context::{CargoTomlContext, FileContext, ProjectContext, TargetResolver};
+use crate::ignore_file::IgnoreFiles;
+use crate::registry::RuleRegistry;
 use crate::rule::{ProjectRule, ProjectRuleBox, Rule, RuleBox};
-use crate::types::{LintResult, Violation};
+use crate::timings::Timings;
+use crate::types::{AnalysisSummary, LintResult, Location, Severity, Suggestion, Violation};
+use crate::utils::allowance::{self, AllowDate};
+use crate::utils::CfgSet;
 
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Instant;
 use thiserror::Error;
 use tracing::{debug, info, warn};
 
@@ -32,18 +45,33 @@ pub enum AnalyzerError {
     /// Configuration error.
     #[error("Configuration error: {0}")]
     Config(#[from] crate::config::ConfigError),
+
+    /// Invalid `.arch-lintignore` file.
+    #[error("Invalid ignore file: {0}")]
+    IgnoreFile(#[from] ignore::Error),
 }
 
 /// Builder for configuring an [`Analyzer`].
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct AnalyzerBuilder {
-    root: Option<PathBuf>,
+    roots: Vec<PathBuf>,
     rules: Vec<RuleBox>,
     project_rules: Vec<ProjectRuleBox>,
     exclude_patterns: Vec<String>,
     include_patterns: Vec<String>,
     config: Option<Config>,
     fail_on_parse_error: bool,
+    cache: bool,
+    cache_path: Option<PathBuf>,
+    absolute_paths: bool,
+    share_asts: Option<bool>,
+    today: Option<AllowDate>,
+    parallel: Option<bool>,
+    split_rules: bool,
+    dedupe: Option<bool>,
+    timings: bool,
+    max_file_bytes: Option<usize>,
 }
 
 impl AnalyzerBuilder {
@@ -54,9 +82,30 @@ impl AnalyzerBuilder {
     }
 
     /// Sets the root directory to analyze.
+    ///
+    /// A path to a file analyzes just that file; see [`roots`](Self::roots).
     #[must_use]
     pub fn root(mut self, path: impl Into<PathBuf>) -> Self {
-        self.root = Some(path.into());
+        self.roots = vec![path.into()];
+        self
+    }
+
+    /// Sets several root directories to analyze together.
+    ///
+    /// Files are discovered under every root and project rules see the
+    /// combined set. Reported paths are relative to the roots' deepest
+    /// common ancestor (e.g. `crate-a/src/lib.rs`), while module paths,
+    /// include patterns, and test/binary detection use each file's own root.
+    ///
+    /// A root that is a file is analyzed on its own, as if its crate (the
+    /// nearest directory with a `Cargo.toml` or config file) were the root
+    /// but only that file were discovered. Include and exclude patterns and
+    /// `[files] extensions` still apply to it, and `Cargo.toml` checks are
+    /// skipped. When every root is a file, project rules are skipped with a
+    /// warning, since they would only see part of the tree.
+    #[must_use]
+    pub fn roots(mut self, paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        self.roots = paths.into_iter().map(Into::into).collect();
         self
     }
 
@@ -74,6 +123,13 @@ impl AnalyzerBuilder {
         self
     }
 
+    /// Adds every rule in `registry` to the analyzer.
+    #[must_use]
+    pub fn registry(mut self, registry: RuleRegistry) -> Self {
+        self.rules.extend(registry);
+        self
+    }
+
     /// Adds a project-wide rule to the analyzer.
     #[must_use]
     pub fn project_rule<R: ProjectRule + 'static>(mut self, rule: R) -> Self {
@@ -108,6 +164,10 @@ impl AnalyzerBuilder {
     }
 
     /// Adds an include glob pattern.
+    ///
+    /// When at least one include pattern is set, only files matching one of
+    /// them (and no exclude) are analyzed. Relative patterns such as `src/**`
+    /// are matched against the path relative to the root.
     #[must_use]
     pub fn include(mut self, pattern: impl Into<String>) -> Self {
         self.include_patterns.push(pattern.into());
@@ -128,22 +188,181 @@ impl AnalyzerBuilder {
         self
     }
 
+    /// Enables the incremental on-disk cache (default: false).
+    ///
+    /// Unchanged files reuse the violations recorded on the previous run.
+    /// The cache is invalidated whenever the rule set or configuration changes.
+    #[must_use]
+    pub fn cache(mut self, enabled: bool) -> Self {
+        self.cache = enabled;
+        self
+    }
+
+    /// Sets the cache file location.
+    ///
+    /// Relative paths are resolved against the analysis root.
+    /// Defaults to [`DEFAULT_CACHE_FILE`](crate::DEFAULT_CACHE_FILE).
+    #[must_use]
+    pub fn cache_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_path = Some(path.into());
+        self
+    }
+
+    /// Reports violation locations as absolute paths (default: false).
+    ///
+    /// By default every `Location.file` is made relative to the analysis
+    /// root, so output does not depend on the working directory.
+    #[must_use]
+    pub fn absolute_paths(mut self, absolute: bool) -> Self {
+        self.absolute_paths = absolute;
+        self
+    }
+
+    /// Shares ASTs parsed by per-file rules with project rules (default: true).
+    ///
+    /// Project rules read them through [`ProjectContext::ast`]. Sharing keeps
+    /// every parsed file in memory until project rules finish; disable it to
+    /// bound memory on very large trees, at the cost of project rules
+    /// re-parsing each file they inspect (without caching). Has no effect
+    /// when no project rules are registered.
+    ///
+    /// `syn` ASTs cannot cross threads, so ASTs are only handed over when
+    /// files are checked sequentially (`parallel(false)`). With
+    /// [`parallel`](Self::parallel) analysis, the default, project rules
+    /// parse each file on first access instead, and this setting only
+    /// controls whether those parses are cached. Choose `parallel(false)`
+    /// when project rules dominate the run; see `ARCHITECTURE.md` for
+    /// measurements.
+    #[must_use]
+    pub fn share_asts(mut self, enabled: bool) -> Self {
+        self.share_asts = Some(enabled);
+        self
+    }
+
+    /// Checks files on a thread pool (default: true).
+    ///
+    /// Files are spread over the pool. Results are merged in file order, so
+    /// output is identical to a sequential run. The pool size is
+    /// `[analyzer] parallelism` when set, else `RAYON_NUM_THREADS`, else one
+    /// thread per CPU.
+    ///
+    /// Parallel runs do not hand ASTs to project rules; see
+    /// [`share_asts`](Self::share_asts).
+    #[must_use]
+    pub fn parallel(mut self, enabled: bool) -> Self {
+        self.parallel = Some(enabled);
+        self
+    }
+
+    /// Also splits the rules of a file with many rules into groups that
+    /// idle workers can take (default: false; needs
+    /// [`parallel`](Self::parallel)).
+    ///
+    /// Lets a single large file (or
+    /// [`analyze_source`](Analyzer::analyze_source)) use several threads.
+    /// A worker taking a group parses the file again, since `syn` ASTs
+    /// cannot cross threads, so this only pays off with several cores and
+    /// few, large files; see `ARCHITECTURE.md` for measurements. Results
+    /// are merged in rule order.
+    #[must_use]
+    pub fn split_rules(mut self, enabled: bool) -> Self {
+        self.split_rules = enabled;
+        self
+    }
+
+    /// Drops repeated violations (default: true).
+    ///
+    /// Two violations are duplicates when code, rule, file, line, column,
+    /// and message all match, e.g. when overlapping declarative rules share
+    /// a name or a rule visits the same node twice. The first is kept.
+    #[must_use]
+    pub fn dedupe(mut self, enabled: bool) -> Self {
+        self.dedupe = Some(enabled);
+        self
+    }
+
+    /// Records where analysis time goes (default: false).
+    ///
+    /// Read the result of the last run with [`Analyzer::timings`]. Off by
+    /// default so unprofiled runs pay nothing for it.
+    #[must_use]
+    pub fn timings(mut self, enabled: bool) -> Self {
+        self.timings = enabled;
+        self
+    }
+
+    /// Skips files larger than `bytes` (default: no limit).
+    ///
+    /// Huge generated sources can make parsing dominate the run. Skipped
+    /// files are not read, parsed, or passed to project rules; they are
+    /// logged at debug level and counted in [`LintResult::files_skipped`].
+    #[must_use]
+    pub fn max_file_bytes(mut self, bytes: usize) -> Self {
+        self.max_file_bytes = Some(bytes);
+        self
+    }
+
+    /// Fixes the date used to evaluate `expires` on allow directives.
+    ///
+    /// Defaults to the current UTC date.
+    #[must_use]
+    pub fn today(mut self, date: AllowDate) -> Self {
+        self.today = Some(date);
+        self
+    }
+
     /// Builds the analyzer.
     ///
+    /// Exclude patterns from the builder and from `[analyzer] exclude` are
+    /// merged with any `.arch-lintignore` found in the root or its ancestors
+    /// (see [`IGNORE_FILE_NAME`](crate::IGNORE_FILE_NAME)). A file is skipped
+    /// if either source excludes it; `!pattern` lines only re-include files
+    /// ignored by an `.arch-lintignore`, never config excludes.
+    ///
+    /// Include patterns (builder and `[analyzer] include`) are applied first:
+    /// a file outside every include is skipped, and an included file can still
+    /// be excluded.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the root directory doesn't exist.
+    /// Returns an error if the root directory doesn't exist, an include
+    /// pattern is not a valid glob, an `.arch-lintignore` file cannot be
+    /// parsed, or a rule rejects its `[rules.<name>]` options.
     pub fn build(self) -> Result<Analyzer, AnalyzerError> {
-        let root = self
-            .root
-            .or_else(|| self.config.as_ref().map(|c| c.analyzer.root.clone()))
-            .unwrap_or_else(|| PathBuf::from("."));
+        let mut roots = self.roots;
+        if roots.is_empty() {
+            roots.push(
+                self.config
+                    .as_ref()
+                    .map_or_else(|| PathBuf::from("."), |c| c.analyzer.root.clone()),
+            );
+        }
 
-        let root = if root.is_absolute() {
-            root
-        } else {
-            std::env::current_dir()?.join(&root)
-        };
+        let cwd = std::env::current_dir()?;
+        let mut resolved: Vec<PathBuf> = Vec::with_capacity(roots.len());
+        let mut scan_roots = Vec::new();
+        let mut files = Vec::new();
+        for root in roots {
+            let root = if root.is_absolute() {
+                root
+            } else {
+                cwd.join(&root)
+            };
+            // Drop `.` components so stripped and joined paths stay clean.
+            let mut root: PathBuf = root.components().collect();
+            if root.is_file() {
+                let file = root;
+                root = crate::discovery::source_root_of(&file);
+                files.push(file);
+            } else if !scan_roots.contains(&root) {
+                scan_roots.push(root.clone());
+            }
+            if !resolved.contains(&root) {
+                resolved.push(root);
+            }
+        }
+        let roots = resolved;
+        let root = common_ancestor(&roots);
 
         // Merge exclude patterns from config
         let mut exclude_patterns = self.exclude_patterns;
@@ -151,19 +370,102 @@ impl AnalyzerBuilder {
             exclude_patterns.extend(config.analyzer.exclude.clone());
         }
 
+        let cfg = match self.config {
+            Some(ref config) => {
+                config.files.validate()?;
+                config.analyzer.cfg_set()?
+            }
+            None => CfgSet::default(),
+        };
+
+        let mut include_patterns = Vec::new();
+        for pattern in self.include_patterns.iter().chain(
+            self.config
+                .as_ref()
+                .map(|c| c.analyzer.include.as_slice())
+                .unwrap_or_default(),
+        ) {
+            include_patterns.push(glob::Pattern::new(pattern)?);
+        }
+
         // Add default excludes if none specified
         if exclude_patterns.is_empty() {
             exclude_patterns.extend(["**/target/**".to_string(), "**/vendor/**".to_string()]);
         }
 
+        let ignore_files = IgnoreFiles::discover_all(&roots)?;
+        if !ignore_files.is_empty() {
+            debug!("Loaded {} for {}", crate::IGNORE_FILE_NAME, root.display());
+        }
+
+        let cache_path = self.cache.then(|| {
+            let path = self
+                .cache_path
+                .unwrap_or_else(|| PathBuf::from(cache::DEFAULT_CACHE_FILE));
+            if path.is_absolute() {
+                path
+            } else {
+                root.join(path)
+            }
+        });
+
+        let parallel = self.parallel.unwrap_or(true);
+        let pool = match self.config.as_ref().and_then(|c| c.analyzer.parallelism) {
+            Some(0) => {
+                return Err(ConfigError::InvalidValue {
+                    key: "analyzer.parallelism".to_string(),
+                    message: "must be at least 1".to_string(),
+                }
+                .into())
+            }
+            Some(threads) if parallel => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .map_err(|e| ConfigError::InvalidValue {
+                        key: "analyzer.parallelism".to_string(),
+                        message: e.to_string(),
+                    })?,
+            ),
+            _ => None,
+        };
+
+        let mut rules = self.rules;
+        if let Some(ref config) = self.config {
+            for rule in &mut rules {
+                if let Some(logging) = &config.logging {
+                    rule.configure_logging(logging);
+                }
+                if let Some(rule_config) = config.rules.get(rule.name()) {
+                    rule.configure(rule_config)?;
+                }
+            }
+        }
+
         Ok(Analyzer {
             root,
-            rules: self.rules,
+            roots,
+            scan_roots,
+            files,
+            rules: rules.into(),
             project_rules: self.project_rules,
             exclude_patterns,
-            include_patterns: self.include_patterns,
+            ignore_files,
+            include_patterns,
+            cfg,
             config: self.config.unwrap_or_default(),
             fail_on_parse_error: self.fail_on_parse_error,
+            cache_path,
+            absolute_paths: self.absolute_paths,
+            share_asts: self.share_asts.unwrap_or(true),
+            today: self.today,
+            parallel,
+            split_rules: self.split_rules,
+            pool,
+            dedupe: self.dedupe.unwrap_or(true),
+            timings: self.timings.then(|| Mutex::new(None)),
+            max_file_bytes: self.max_file_bytes,
+            targets: TargetResolver::default(),
         })
     }
 }
@@ -171,15 +473,137 @@ impl AnalyzerBuilder {
 /// The main analyzer that orchestrates lint execution.
 ///
 /// Use [`Analyzer::builder()`] to construct an instance.
+#[allow(clippy::struct_excessive_bools)]
 pub struct Analyzer {
+    /// Deepest common ancestor of `roots`; reported paths are relative to it.
     root: PathBuf,
-    rules: Vec<RuleBox>,
+    roots: Vec<PathBuf>,
+    /// Roots given as directories; only these are searched for files.
+    scan_roots: Vec<PathBuf>,
+    /// Roots given as files, analyzed without searching their root.
+    files: Vec<PathBuf>,
+    rules: RuleRegistry,
     project_rules: Vec<ProjectRuleBox>,
     exclude_patterns: Vec<String>,
-    #[allow(dead_code)] // Reserved for future include pattern support
-    include_patterns: Vec<String>,
+    ignore_files: IgnoreFiles,
+    include_patterns: Vec<glob::Pattern>,
+    /// Items compiled out under these options are not analyzed.
+    cfg: CfgSet,
     config: Config,
     fail_on_parse_error: bool,
+    cache_path: Option<PathBuf>,
+    absolute_paths: bool,
+    share_asts: bool,
+    today: Option<AllowDate>,
+    parallel: bool,
+    split_rules: bool,
+    /// Pool sized by `[analyzer] parallelism`; the global pool when unset.
+    pool: Option<rayon::ThreadPool>,
+    dedupe: bool,
+    /// Timings of the last finished run, when enabled.
+    timings: Option<Mutex<Option<Timings>>>,
+    /// Files larger than this are skipped.
+    max_file_bytes: Option<usize>,
+    /// Cargo targets of analyzed files, from manifests read once per crate.
+    targets: TargetResolver,
+}
+
+/// Fewest rules run as one group when a file's rules are split across
+/// the pool, so a group that re-parses the file has enough work to pay
+/// for it.
+const RULES_PER_GROUP: usize = 8;
+
+thread_local! {
+    /// AST of the file whose rules this thread is splitting into groups,
+    /// with its id, so groups that stay on this thread reuse it.
+    static LOCAL_AST: RefCell<Option<(u64, Rc<syn::File>)>> = const { RefCell::new(None) };
+}
+
+/// Source of [`LOCAL_AST`] ids.
+static NEXT_AST_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Receives each file's outcome, in file order; an error stops the run.
+type OutcomeHandler<'a> =
+    dyn FnMut(&Path, Result<FileOutcome, AnalyzerError>) -> Result<(), AnalyzerError> + 'a;
+
+/// Identity of a violation for de-duplication: code, rule, file, line,
+/// column, and message.
+type DedupeKey = (String, String, PathBuf, usize, usize, String);
+
+/// Normalizes, de-duplicates, and counts violations on their way out.
+struct Emitter<'a> {
+    analyzer: &'a Analyzer,
+    emit: &'a mut dyn FnMut(Violation),
+    seen: HashSet<DedupeKey>,
+    summary: AnalysisSummary,
+}
+
+impl<'a> Emitter<'a> {
+    fn new(analyzer: &'a Analyzer, emit: &'a mut dyn FnMut(Violation)) -> Self {
+        Self {
+            analyzer,
+            emit,
+            seen: HashSet::new(),
+            summary: AnalysisSummary::default(),
+        }
+    }
+
+    /// Records a checked file and emits its violations.
+    fn file_checked(&mut self, violations: Vec<Violation>) {
+        self.summary.files_checked += 1;
+        self.emit(violations);
+    }
+
+    fn emit(&mut self, mut violations: Vec<Violation>) {
+        for violation in &mut violations {
+            self.analyzer.normalize_location(&mut violation.location);
+            for label in &mut violation.labels {
+                self.analyzer.normalize_location(&mut label.location);
+            }
+        }
+        sort_violations(&mut violations);
+
+        for violation in violations {
+            if self.analyzer.dedupe {
+                let key = (
+                    violation.code.clone(),
+                    violation.rule.clone(),
+                    violation.location.file.clone(),
+                    violation.location.line,
+                    violation.location.column,
+                    violation.message.clone(),
+                );
+                if !self.seen.insert(key) {
+                    continue;
+                }
+            }
+            self.summary.record(violation.severity);
+            (self.emit)(violation);
+        }
+    }
+}
+
+/// Sorts violations by file, then line, then column.
+fn sort_violations(violations: &mut [Violation]) {
+    violations.sort_by(|a, b| {
+        a.location
+            .file
+            .cmp(&b.location.file)
+            .then(a.location.line.cmp(&b.location.line))
+            .then(a.location.column.cmp(&b.location.column))
+    });
+}
+
+/// Result of checking one file, before it is merged into the [`LintResult`].
+struct FileOutcome {
+    content_hash: String,
+    /// Earliest pending `expires` date in the file; see
+    /// [`allowance::next_expiry`].
+    valid_until: Option<AllowDate>,
+    /// Violations, or the error that prevented checking the file.
+    violations: Result<Vec<Violation>, AnalyzerError>,
+    /// Time spent on this file, when timings are enabled.
+    timings: Option<Timings>,
 }
 
 impl Analyzer {
@@ -190,189 +614,898 @@ impl Analyzer {
     }
 
     /// Returns the root directory being analyzed.
+    ///
+    /// With several roots this is their deepest common ancestor.
     #[must_use]
     pub fn root(&self) -> &Path {
         &self.root
     }
 
+    /// Returns every root directory being analyzed.
+    #[must_use]
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Returns the registered per-file rules.
+    #[must_use]
+    pub fn registry(&self) -> &RuleRegistry {
+        &self.rules
+    }
+
+    /// Returns the timings of the last analysis run, if enabled with
+    /// [`AnalyzerBuilder::timings`] and a run has finished.
+    #[must_use]
+    pub fn timings(&self) -> Option<Timings> {
+        self.timings.as_ref()?.lock().ok()?.clone()
+    }
+
     /// Returns the number of registered rules.
     #[must_use]
     pub fn rule_count(&self) -> usize {
         self.rules.len() + self.project_rules.len()
     }
 
+    /// Runs the per-file rules on `content` as the source of `path`, e.g. an
+    /// unsaved editor buffer.
+    ///
+    /// The file need not exist or be under a root; include and exclude
+    /// patterns, the cache, and project rules are not applied. Violations
+    /// are located like those of [`analyze`](Self::analyze) and sorted.
+    /// Cargo targets come from manifests read once per analyzer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnalyzerError::Parse`] if `content` is not valid Rust.
+    pub fn analyze_source(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<Vec<Violation>, AnalyzerError> {
+        let check = || {
+            self.analyze_file(path, content, None)
+                .map(|(violations, _)| violations)
+        };
+        let violations = if self.parallel {
+            // Run on the pool, so the rule groups kept on the worker that
+            // parsed the file reuse its AST.
+            let today = self.today.unwrap_or_else(AllowDate::today);
+            self.in_pool(|| rayon::scope(|_| allowance::with_today(today, check)))?
+        } else {
+            match self.today {
+                Some(date) => allowance::with_today(date, check)?,
+                None => check()?,
+            }
+        };
+        let mut out = Vec::new();
+        let mut emit = |v| out.push(v);
+        Emitter::new(self, &mut emit).emit(violations);
+        Ok(out)
+    }
+
     /// Analyzes all files and returns the results.
     ///
+    /// Buffers everything reported by [`analyze_streaming`](Self::analyze_streaming)
+    /// and sorts it by file, line, and column.
+    ///
     /// # Errors
     ///
     /// Returns an error if file discovery or parsing fails.
     pub fn analyze(&self) -> Result<LintResult, AnalyzerError> {
+        let mut violations = Vec::new();
+        let summary = self.stream(&mut |v| violations.push(v))?;
+        sort_violations(&mut violations);
+        Ok(LintResult {
+            violations,
+            files_checked: summary.files_checked,
+            files_skipped: summary.files_skipped,
+        })
+    }
+
+    /// Analyzes all files, passing each violation to `on_violation` as soon
+    /// as its file has been checked.
+    ///
+    /// Files are reported in discovery order, each file's violations sorted
+    /// by line and column; project-rule violations come last. A file is
+    /// passed on as soon as it and every earlier file are checked; only
+    /// files finishing ahead of a slower earlier one are held back, so
+    /// output can start before a large tree is finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if file discovery or parsing fails. Violations from
+    /// files checked before the error have already been passed on.
+    pub fn analyze_streaming(
+        &self,
+        mut on_violation: impl FnMut(&Violation),
+    ) -> Result<AnalysisSummary, AnalyzerError> {
+        self.stream(&mut |v| on_violation(&v))
+    }
+
+    fn stream(&self, emit: &mut dyn FnMut(Violation)) -> Result<AnalysisSummary, AnalyzerError> {
+        match self.today {
+            Some(date) => allowance::with_today(date, || self.run(emit)),
+            None => self.run(emit),
+        }
+    }
+
+    fn run(&self, emit: &mut dyn FnMut(Violation)) -> Result<AnalysisSummary, AnalyzerError> {
         info!("Starting analysis at {:?}", self.root);
 
-        let mut result = LintResult::new();
+        let started = Instant::now();
+        let mut timings = self.timings.as_ref().map(|_| Timings::default());
+        let mut emitter = Emitter::new(self, emit);
         let files = self.discover_files()?;
+        let files = self.skip_oversized(files, &mut emitter.summary);
+        if let Some(timings) = &mut timings {
+            timings.discovery = started.elapsed();
+        }
 
         info!("Found {} files to analyze", files.len());
 
-        // Run per-file rules
-        for file_path in &files {
-            match self.analyze_file(file_path) {
-                Ok(violations) => {
-                    result.violations.extend(violations);
-                    result.files_checked += 1;
+        let ruleset_hash = self.ruleset_hash();
+        let previous = self
+            .cache_path
+            .as_deref()
+            .map(|p| AnalysisCache::load(p, &ruleset_hash));
+        let mut next = AnalysisCache::new(ruleset_hash);
+        let mut asts = (self.share_asts && !self.project_rules.is_empty()).then(Vec::new);
+
+        // Run per-file rules, handing on each file's results as they arrive
+        self.check_files(
+            &files,
+            previous.as_ref(),
+            asts.as_mut(),
+            &mut |file_path, outcome| {
+                let FileOutcome {
+                    content_hash,
+                    valid_until,
+                    violations,
+                    timings: file_timings,
+                } = outcome?;
+                if let (Some(timings), Some(file_timings)) = (&mut timings, file_timings) {
+                    timings.merge(file_timings);
                 }
-                Err(AnalyzerError::Parse { path, message }) => {
-                    warn!("Failed to parse {}: {}", path.display(), message);
-                    if self.fail_on_parse_error {
-                        return Err(AnalyzerError::Parse { path, message });
+                match violations {
+                    Ok(violations) => {
+                        next.insert(
+                            file_path.to_path_buf(),
+                            self.root_of(file_path).to_path_buf(),
+                            content_hash,
+                            violations.clone(),
+                            valid_until,
+                        );
+                        emitter.file_checked(violations);
                     }
+                    Err(AnalyzerError::Parse { path, message }) => {
+                        warn!("Failed to parse {}: {}", path.display(), message);
+                        if self.fail_on_parse_error {
+                            return Err(AnalyzerError::Parse { path, message });
+                        }
+                    }
+                    Err(e) => return Err(e),
                 }
-                Err(e) => return Err(e),
+                Ok(())
+            },
+        )?;
+
+        if let Some(path) = &self.cache_path {
+            if let Some(previous) = previous {
+                next.carry_over(previous);
+            }
+            if let Err(e) = next.save(path) {
+                warn!("Failed to write cache {}: {}", path.display(), e);
             }
         }
 
-        // Run project-wide rules
-        let project_ctx = ProjectContext::new(&self.root)
-            .with_source_files(files.clone())
-            .with_cargo_files(self.discover_cargo_files()?);
+        let cargo_files = self.discover_cargo_files()?;
+        self.check_manifests(&cargo_files, &mut emitter, timings.as_mut());
 
-        for rule in &self.project_rules {
-            if !self.config.is_rule_enabled(rule.name()) {
-                debug!("Skipping disabled rule: {}", rule.name());
-                continue;
-            }
+        self.check_project(files, cargo_files, asts, &mut emitter, timings.as_mut());
 
-            let violations = rule.check_project(&project_ctx);
-            let violations = self.apply_severity_override(rule.name(), violations);
-            result.violations.extend(violations);
+        if let (Some(mut timings), Some(slot)) = (timings, &self.timings) {
+            timings.total = started.elapsed();
+            for (name, elapsed) in timings.breakdown() {
+                debug!("Timing: {name} took {elapsed:?}");
+            }
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(timings);
+            }
         }
 
-        // Sort violations by file, then line
-        result.violations.sort_by(|a, b| {
-            a.location
-                .file
-                .cmp(&b.location.file)
-                .then(a.location.line.cmp(&b.location.line))
-                .then(a.location.column.cmp(&b.location.column))
-        });
-
+        let summary = emitter.summary;
         info!(
             "Analysis complete: {} violations in {} files",
-            result.violations.len(),
-            result.files_checked
+            summary.total(),
+            summary.files_checked
         );
 
-        Ok(result)
+        Ok(summary)
     }
 
-    /// Analyzes a single file and returns violations.
-    fn analyze_file(&self, path: &Path) -> Result<Vec<Violation>, AnalyzerError> {
-        debug!("Analyzing: {}", path.display());
-
-        let content = std::fs::read_to_string(path)?;
-        let ast = syn::parse_file(&content).map_err(|e| AnalyzerError::Parse {
-            path: path.to_path_buf(),
-            message: e.to_string(),
-        })?;
-
-        let ctx = FileContext::new(path, &content, &self.root);
-        let mut violations = Vec::new();
+    /// Drops files over [`AnalyzerBuilder::max_file_bytes`], counting them
+    /// in `summary`.
+    ///
+    /// A file whose size cannot be read is kept, so reading it reports the
+    /// error as usual.
+    fn skip_oversized(&self, files: Vec<PathBuf>, summary: &mut AnalysisSummary) -> Vec<PathBuf> {
+        let Some(max) = self.max_file_bytes else {
+            return files;
+        };
+        files
+            .into_iter()
+            .filter(|path| {
+                let Ok(metadata) = std::fs::metadata(path) else {
+                    return true;
+                };
+                if usize::try_from(metadata.len()).map_or(true, |len| len > max) {
+                    debug!(
+                        "Skipping {} ({} bytes, max_file_bytes is {max})",
+                        path.display(),
+                        metadata.len()
+                    );
+                    summary.files_skipped += 1;
+                    return false;
+                }
+                true
+            })
+            .collect()
+    }
 
-        for rule in &self.rules {
-            if !self.config.is_rule_enabled(rule.name()) {
-                debug!("Skipping disabled rule: {}", rule.name());
-                continue;
+    /// Checks `files`, in parallel if enabled, passing each outcome to
+    /// `handle` in file order as soon as it and all earlier files are done.
+    ///
+    /// Pool workers send outcomes back over a channel; ones that finish
+    /// ahead of an earlier file wait in a reorder buffer. Checking stops
+    /// early once `handle` fails.
+    fn check_files(
+        &self,
+        files: &[PathBuf],
+        previous: Option<&AnalysisCache>,
+        mut asts: Option<&mut Vec<(PathBuf, syn::File)>>,
+        handle: &mut OutcomeHandler<'_>,
+    ) -> Result<(), AnalyzerError> {
+        if !self.parallel {
+            for file_path in files {
+                handle(
+                    file_path,
+                    self.check_file(file_path, previous, asts.as_deref_mut()),
+                )?;
             }
-
-            let rule_violations = rule.check(&ctx, &ast);
-            let rule_violations = self.apply_severity_override(rule.name(), rule_violations);
-            violations.extend(rule_violations);
+            return Ok(());
         }
 
-        Ok(violations)
-    }
+        // Worker threads do not inherit a pinned date; resolve it here.
+        let today = AllowDate::today();
+        let check = |file_path: &PathBuf| {
+            allowance::with_today(today, || self.check_file(file_path, previous, None))
+        };
 
-    /// Applies severity overrides from configuration.
-    fn apply_severity_override(
-        &self,
-        rule_name: &str,
-        mut violations: Vec<Violation>,
-    ) -> Vec<Violation> {
-        if let Some(severity) = self.config.rule_severity(rule_name) {
-            for v in &mut violations {
-                v.severity = severity;
+        if rayon::current_thread_index().is_some() {
+            // Waiting on the channel here could block the pool's only
+            // worker, so outcomes are handed on once all files are checked.
+            let outcomes: Vec<_> = self.in_pool(|| files.par_iter().map(check).collect());
+            for (file_path, outcome) in files.iter().zip(outcomes) {
+                handle(file_path, outcome)?;
             }
+            return Ok(());
         }
-        violations
-    }
-
-    /// Discovers all Rust source files to analyze.
-    fn discover_files(&self) -> Result<Vec<PathBuf>, AnalyzerError> {
-        let pattern = format!("{}/**/*.rs", self.root.display());
-        let mut files = Vec::new();
 
-        for entry in glob::glob(&pattern)? {
-            let path = entry.map_err(|e| AnalyzerError::Io(e.into_error()))?;
+        let (sender, receiver) = mpsc::channel();
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                // Fails, and stops checking, once the receiver is dropped.
+                self.in_pool(|| {
+                    files
+                        .par_iter()
+                        .enumerate()
+                        .try_for_each_with(sender, |sender, (index, file_path)| {
+                            sender.send((index, check(file_path))).map_err(drop)
+                        })
+                        .ok()
+                });
+            });
 
-            // Check exclude patterns
-            if self.should_exclude(&path) {
-                debug!("Excluding: {}", path.display());
-                continue;
+            // Dropped on return, so an error also stops the workers.
+            let receiver = receiver;
+            let mut pending = HashMap::new();
+            let mut next = 0;
+            for (index, outcome) in &receiver {
+                pending.insert(index, outcome);
+                while let Some(outcome) = pending.remove(&next) {
+                    handle(&files[next], outcome)?;
+                    next += 1;
+                }
             }
+            Ok(())
+        })
+    }
 
-            files.push(path);
+    /// Runs `f` on the configured pool, or the global one if none is set.
+    fn in_pool<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(f),
+            None => f(),
         }
-
-        Ok(files)
     }
 
-    /// Discovers Cargo.toml files in the project.
-    fn discover_cargo_files(&self) -> Result<Vec<PathBuf>, AnalyzerError> {
-        let pattern = format!("{}/**/Cargo.toml", self.root.display());
-        let mut files = Vec::new();
+    /// Reads a file and checks it, reusing cached violations when possible.
+    ///
+    /// The parsed AST is pushed to `asts` when given.
+    fn check_file(
+        &self,
+        path: &Path,
+        previous: Option<&AnalysisCache>,
+        asts: Option<&mut Vec<(PathBuf, syn::File)>>,
+    ) -> Result<FileOutcome, AnalyzerError> {
+        let content = std::fs::read_to_string(path)?;
+        let content_hash = cache::content_hash(&content);
 
-        for entry in glob::glob(&pattern)? {
-            let path = entry.map_err(|e| AnalyzerError::Io(e.into_error()))?;
-            if !self.should_exclude(&path) {
-                files.push(path);
-            }
+        // An entry is only reused until an `expires` date in the file passes.
+        let today = AllowDate::today();
+        if let Some((cached, valid_until)) =
+            previous.and_then(|c| c.get(path, self.root_of(path), &content_hash, today))
+        {
+            debug!("Cache hit: {}", path.display());
+            return Ok(FileOutcome {
+                content_hash,
+                valid_until,
+                violations: Ok(cached.to_vec()),
+                timings: None,
+            });
         }
 
-        Ok(files)
+        let mut timings = self.timings.as_ref().map(|_| Timings::default());
+        let mut valid_until = None;
+        let violations =
+            self.analyze_file(path, &content, timings.as_mut())
+                .map(|(violations, ast)| {
+                    valid_until = allowance::next_expiry(&content, &ast);
+                    if let Some(asts) = asts {
+                        asts.push((path.to_path_buf(), ast));
+                    }
+                    violations
+                });
+        Ok(FileOutcome {
+            content_hash,
+            valid_until,
+            violations,
+            timings,
+        })
     }
 
-    /// Checks if a path should be excluded.
-    fn should_exclude(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
+    /// Analyzes a single file and returns violations along with its AST.
+    ///
+    /// Parse and rule times are added to `timings` when given.
+    fn analyze_file(
+        &self,
+        path: &Path,
+        content: &str,
+        timings: Option<&mut Timings>,
+    ) -> Result<(Vec<Violation>, syn::File), AnalyzerError> {
+        debug!("Analyzing: {}", path.display());
 
-        for pattern in &self.exclude_patterns {
-            // Simple glob matching
-            if let Ok(glob_pattern) = glob::Pattern::new(pattern) {
-                if glob_pattern.matches(&path_str) {
-                    return true;
+        let mut timings = timings;
+        let ast = self.parse(path, content, timings.as_deref_mut())?;
+        let file_root = self.root_of(path);
+        let target = self.targets.resolve(path, file_root);
+        let ctx = FileContext::with_target(path, content, file_root, target);
+
+        let rules: Vec<&dyn Rule> = self
+            .rules
+            .iter()
+            .filter(|rule| {
+                let enabled = self.config.is_rule_enabled(rule.name());
+                if !enabled {
+                    debug!("Skipping disabled rule: {}", rule.name());
                 }
-            }
+                enabled && rule.applies_to(&ctx)
+            })
+            .collect();
+        let (mut violations, ast) = if self.split_rules
+            && self.parallel
+            && rules.len() > RULES_PER_GROUP
+            && rayon::current_num_threads() > 1
+        {
+            self.check_rules_parallel(&rules, &ctx, ast, timings)?
+        } else {
+            let violations = self.check_rules(&rules, &ctx, &ast, timings);
+            (violations, ast)
+        };
 
-            // Also check as substring for patterns like "**/target/**"
-            let normalized_pattern = pattern.replace("**", "");
-            if !normalized_pattern.is_empty() && path_str.contains(&normalized_pattern) {
-                return true;
+        if self.config.is_rule_enabled(allowance::DIRECTIVE_NAME) {
+            let problems = ctx.allowances().problem_violations(&ctx.relative_path);
+            violations.extend(self.apply_rule_overrides(allowance::DIRECTIVE_NAME, problems));
+        }
+
+        if self.config.is_rule_enabled(allowance::EXPIRED_NAME) {
+            let expired = allowance::expired_allow_violations(content, &ast, &ctx.relative_path);
+            violations.extend(self.apply_rule_overrides(allowance::EXPIRED_NAME, expired));
+        }
+
+        // Locations are relative to the file's own root; anchor them so they
+        // can be made relative to the common root on output.
+        for violation in &mut violations {
+            if violation.location.file.is_relative() {
+                violation.location.file = file_root.join(&violation.location.file);
             }
         }
 
-        false
+        Ok((violations, ast))
     }
 
-    /// Gets the rule configuration for a specific rule.
-    #[must_use]
-    pub fn rule_config(&self, rule_name: &str) -> Option<&RuleConfig> {
+    /// Parses a file and strips items compiled out by the configured cfg.
+    ///
+    /// Parse time is added to `timings` when given.
+    fn parse(
+        &self,
+        path: &Path,
+        content: &str,
+        timings: Option<&mut Timings>,
+    ) -> Result<syn::File, AnalyzerError> {
+        let started = Instant::now();
+        let mut ast = syn::parse_file(content).map_err(|e| AnalyzerError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        if !self.cfg.is_empty() {
+            self.cfg.strip_inactive(&mut ast);
+        }
+        if let Some(timings) = timings {
+            timings.parsing += started.elapsed();
+        }
+        Ok(ast)
+    }
+
+    /// Runs `rules` on one file in order.
+    fn check_rules(
+        &self,
+        rules: &[&dyn Rule],
+        ctx: &FileContext<'_>,
+        ast: &syn::File,
+        mut timings: Option<&mut Timings>,
+    ) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for rule in rules {
+            let rule_started = Instant::now();
+            let rule_violations = apply_allowances(*rule, ctx, rule.check(ctx, ast));
+            if let Some(timings) = timings.as_deref_mut() {
+                timings.record_rule(rule.name(), rule_started);
+            }
+            violations.extend(self.apply_rule_overrides(rule.name(), rule_violations));
+        }
+        violations
+    }
+
+    /// Runs `rules` on one file in groups on the thread pool, returning
+    /// their violations in rule order along with `ast`.
+    ///
+    /// `syn` ASTs cannot cross threads, so only groups that run on this
+    /// thread use `ast`; a group taken by an idle worker parses the file
+    /// again. A busy pool therefore pays no extra parses.
+    fn check_rules_parallel(
+        &self,
+        rules: &[&dyn Rule],
+        ctx: &FileContext<'_>,
+        ast: syn::File,
+        timings: Option<&mut Timings>,
+    ) -> Result<(Vec<Violation>, syn::File), AnalyzerError> {
+        let id = NEXT_AST_ID.fetch_add(1, Ordering::Relaxed);
+        let ast = Rc::new(ast);
+        let outer = LOCAL_AST.with(|local| local.replace(Some((id, Rc::clone(&ast)))));
+
+        // Worker threads do not inherit a pinned date; resolve it here.
+        let today = AllowDate::today();
+        let with_timings = timings.is_some();
+        let group_size = rules
+            .len()
+            .div_ceil(rayon::current_num_threads())
+            .max(RULES_PER_GROUP);
+        let groups: Vec<Result<_, AnalyzerError>> = rules
+            .par_chunks(group_size)
+            .map(|group| {
+                allowance::with_today(today, || {
+                    let mut group_timings = with_timings.then(Timings::default);
+                    let local = LOCAL_AST.with(|local| {
+                        local
+                            .borrow()
+                            .as_ref()
+                            .filter(|(local_id, _)| *local_id == id)
+                            .map(|(_, ast)| Rc::clone(ast))
+                    });
+                    let ast = match local {
+                        Some(ast) => ast,
+                        None => {
+                            Rc::new(self.parse(ctx.path, ctx.content, group_timings.as_mut())?)
+                        }
+                    };
+                    let violations = self.check_rules(group, ctx, &ast, group_timings.as_mut());
+                    Ok((violations, group_timings))
+                })
+            })
+            .collect();
+        LOCAL_AST.with(|local| local.replace(outer));
+
+        let mut violations = Vec::new();
+        let mut timings = timings;
+        for group in groups {
+            let (group_violations, group_timings) = group?;
+            violations.extend(group_violations);
+            if let (Some(timings), Some(group_timings)) = (timings.as_deref_mut(), group_timings) {
+                timings.merge(group_timings);
+            }
+        }
+        let ast = Rc::try_unwrap(ast).unwrap_or_else(|ast| (*ast).clone());
+        Ok((violations, ast))
+    }
+
+    /// Rewrites a location's file to be root-relative, or absolute if
+    /// [`AnalyzerBuilder::absolute_paths`] is set.
+    fn normalize_location(&self, location: &mut Location) {
+        let file: PathBuf = location.file.components().collect();
+        location.file = if self.absolute_paths {
+            if file.is_absolute() {
+                file
+            } else {
+                self.root.join(file)
+            }
+        } else {
+            match file.strip_prefix(&self.root) {
+                Ok(rel) if rel.as_os_str().is_empty() => PathBuf::from("."),
+                Ok(rel) => rel.to_path_buf(),
+                Err(_) => file,
+            }
+        };
+    }
+
+    /// Computes the cache key for the registered rules and configuration.
+    fn ruleset_hash(&self) -> String {
+        let rule_ids = self
+            .rules
+            .iter()
+            .map(|r| (r.code(), r.cache_key()))
+            .chain(self.project_rules.iter().map(|r| (r.code(), r.cache_key())));
+        // Round-trip through `toml::Value` so map keys are emitted in sorted order.
+        let config_repr = toml::Value::try_from(&self.config)
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        cache::ruleset_hash(rule_ids, &config_repr)
+    }
+
+    /// Runs the enabled project rules over the analyzed files.
+    ///
+    /// `asts` holds the ASTs parsed by per-file analysis, if shared.
+    fn check_project(
+        &self,
+        files: Vec<PathBuf>,
+        cargo_files: Vec<PathBuf>,
+        asts: Option<Vec<(PathBuf, syn::File)>>,
+        emitter: &mut Emitter<'_>,
+        mut timings: Option<&mut Timings>,
+    ) {
+        if self.parallel && self.share_asts && !self.project_rules.is_empty() {
+            info!("Project rules parse files again: ASTs are only shared with parallel(false)");
+        }
+
+        let project_ctx = ProjectContext::new(&self.root)
+            .with_source_files(files)
+            .with_cargo_files(cargo_files)
+            .with_asts(asts.unwrap_or_default())
+            .with_ast_cache(self.share_asts);
+
+        for rule in &self.project_rules {
+            if !self.config.is_rule_enabled(rule.name()) {
+                debug!("Skipping disabled rule: {}", rule.name());
+                continue;
+            }
+            if self.scan_roots.is_empty() {
+                warn!(
+                    "Skipping project rule {}: it needs the whole tree, but only files were given",
+                    rule.name()
+                );
+                continue;
+            }
+
+            let rule_started = Instant::now();
+            let violations = rule.check_project(&project_ctx);
+            if let Some(timings) = timings.as_deref_mut() {
+                *timings
+                    .project_rules
+                    .entry(rule.name().to_string())
+                    .or_default() += rule_started.elapsed();
+            }
+            emitter.emit(self.apply_rule_overrides(rule.name(), violations));
+        }
+    }
+
+    /// Runs [`Rule::check_cargo_toml`] for every enabled rule on each manifest.
+    fn check_manifests(
+        &self,
+        cargo_files: &[PathBuf],
+        emitter: &mut Emitter<'_>,
+        mut timings: Option<&mut Timings>,
+    ) {
+        for path in cargo_files {
+            let content = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("Failed to read {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            let manifest: toml::Table = match toml::from_str(&content) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    warn!("Failed to parse {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            let ctx = CargoTomlContext::new(path, &content, &self.root);
+            for rule in &self.rules {
+                if !self.config.is_rule_enabled(rule.name()) {
+                    continue;
+                }
+                let rule_started = Instant::now();
+                let violations = rule.check_cargo_toml(&ctx, &manifest);
+                if let Some(timings) = timings.as_deref_mut() {
+                    timings.record_rule(rule.name(), rule_started);
+                }
+                emitter.emit(self.apply_rule_overrides(rule.name(), violations));
+            }
+        }
+    }
+
+    /// Applies severity, `doc_ref` and message template overrides from
+    /// configuration.
+    fn apply_rule_overrides(
+        &self,
+        rule_name: &str,
+        mut violations: Vec<Violation>,
+    ) -> Vec<Violation> {
+        if let Some(severity) = self.config.rule_severity(rule_name) {
+            for v in &mut violations {
+                v.severity = severity;
+            }
+        }
+        if let Some(doc_ref) = self.config.rule_doc_ref(rule_name) {
+            for v in &mut violations {
+                v.doc_ref = Some(doc_ref.to_string());
+            }
+        }
+        if let Some(template) = self.config.rule_message(rule_name) {
+            for v in &mut violations {
+                v.message = render_message(template, v);
+            }
+        }
+        violations
+    }
+
+    /// Returns the root a file was discovered under (the deepest match).
+    fn root_of(&self, path: &Path) -> &Path {
+        self.roots
+            .iter()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .unwrap_or(&self.root)
+    }
+
+    /// Globs `pattern` under every directory root, skipping files already
+    /// seen when roots overlap.
+    fn glob_roots(&self, pattern: &str) -> Result<Vec<PathBuf>, AnalyzerError> {
+        let mut seen = HashSet::new();
+        let mut files = Vec::new();
+        for root in &self.scan_roots {
+            for entry in glob::glob(&format!("{}/{pattern}", root.display()))? {
+                let path = entry.map_err(|e| AnalyzerError::Io(e.into()))?;
+                if seen.insert(path.clone()) {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Discovers all Rust source files to analyze.
+    ///
+    /// Files with a `[files]` extension and roots given as files are
+    /// filtered by the include and exclude patterns; `[files] extra`
+    /// matches are always added.
+    fn discover_files(&self) -> Result<Vec<PathBuf>, AnalyzerError> {
+        let mut seen = HashSet::new();
+        let mut files = Vec::new();
+
+        let mut candidates = Vec::new();
+        for pattern in self.config.files.extension_globs() {
+            candidates.extend(self.glob_roots(&pattern)?);
+        }
+        candidates.extend(
+            self.files
+                .iter()
+                .filter(|file| self.config.files.matches_extension(file))
+                .cloned(),
+        );
+        for path in candidates {
+            if !self.should_include(&path) {
+                debug!("Not included: {}", path.display());
+                continue;
+            }
+
+            // Check exclude patterns
+            if self.should_exclude(&path) {
+                debug!("Excluding: {}", path.display());
+                continue;
+            }
+
+            if seen.insert(path.clone()) {
+                files.push(path);
+            }
+        }
+
+        for pattern in &self.config.files.extra {
+            for path in self.glob_roots(pattern)? {
+                if path.is_file() && seen.insert(path.clone()) {
+                    files.push(path);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Discovers Cargo.toml files in the project.
+    fn discover_cargo_files(&self) -> Result<Vec<PathBuf>, AnalyzerError> {
+        let mut files = Vec::new();
+
+        for path in self.glob_roots("**/Cargo.toml")? {
+            if !self.should_exclude(&path) {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Checks if a path matches the include patterns (always true if none are set).
+    fn should_include(&self, path: &Path) -> bool {
+        if self.include_patterns.is_empty() {
+            return true;
+        }
+
+        let relative = path.strip_prefix(self.root_of(path)).unwrap_or(path);
+        self.include_patterns
+            .iter()
+            .any(|p| p.matches_path(relative) || p.matches_path(path))
+    }
+
+    /// Checks if a path should be excluded.
+    fn should_exclude(&self, path: &Path) -> bool {
+        if self.ignore_files.is_ignored(path) {
+            return true;
+        }
+
+        let path_str = path.to_string_lossy();
+
+        for pattern in &self.exclude_patterns {
+            // Simple glob matching
+            if let Ok(glob_pattern) = glob::Pattern::new(pattern) {
+                if glob_pattern.matches(&path_str) {
+                    return true;
+                }
+            }
+
+            // Also check as substring for patterns like "**/target/**"
+            let normalized_pattern = pattern.replace("**", "");
+            if !normalized_pattern.is_empty() && path_str.contains(&normalized_pattern) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Gets the rule configuration for a specific rule.
+    #[must_use]
+    pub fn rule_config(&self, rule_name: &str) -> Option<&RuleConfig> {
         self.config.rules.get(rule_name)
     }
 }
 
+/// Drops violations that an allow directive covers, so rules need not check
+/// directives themselves.
+///
+/// A directive without the reason the rule requires turns the violation
+/// into a missing-reason warning instead, as rules checking directives do;
+/// those warnings pass through unchanged.
+fn apply_allowances(
+    rule: &dyn Rule,
+    ctx: &FileContext<'_>,
+    violations: Vec<Violation>,
+) -> Vec<Violation> {
+    let missing_reason = format!(
+        "Allow directive for '{}' is missing required reason",
+        rule.name()
+    );
+    violations
+        .into_iter()
+        .filter_map(|violation| {
+            if violation.message == missing_reason {
+                return Some(violation);
+            }
+            let allow = ctx.check_allow_for_rule(violation.location.line, rule.name(), rule.code());
+            if !allow.is_allowed() {
+                return Some(violation);
+            }
+            (rule.requires_allow_reason() && allow.reason().is_none()).then(|| {
+                Violation::new(
+                    violation.code,
+                    violation.rule,
+                    Severity::Warning,
+                    violation.location,
+                    missing_reason.clone(),
+                )
+                .with_suggestion(Suggestion::new(
+                    "Add reason=\"...\" to explain why this exception is necessary",
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Fills a `[rules.<name>] message` template from `violation`.
+///
+/// `{code}`, `{rule}`, `{doc_ref}` (empty without one) and `{message}` are
+/// replaced in a single pass, so substituted text is never expanded again.
+/// Any other `{...}` is kept as written.
+fn render_message(template: &str, violation: &Violation) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let value = match &after[..close] {
+                "code" => violation.code.as_str(),
+                "rule" => violation.rule.as_str(),
+                "doc_ref" => violation.doc_ref.as_deref().unwrap_or_default(),
+                "message" => violation.message.as_str(),
+                _ => return None,
+            };
+            Some((value, close))
+        });
+        if let Some((value, close)) = value {
+            out.push_str(value);
+            rest = &after[close + 1..];
+        } else {
+            out.push('{');
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Returns the deepest directory containing every root.
+fn common_ancestor(roots: &[PathBuf]) -> PathBuf {
+    let Some((first, rest)) = roots.split_first() else {
+        return PathBuf::from(".");
+    };
+    let mut common = first.clone();
+    for root in rest {
+        while !root.starts_with(&common) {
+            if !common.pop() {
+                break;
+            }
+        }
+    }
+    common
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     #[test]
     fn test_builder() {
@@ -398,4 +1531,1167 @@ mod tests {
         assert!(analyzer.should_exclude(Path::new("/foo/vendor/lib.rs")));
         assert!(!analyzer.should_exclude(Path::new("/foo/src/lib.rs")));
     }
+
+    #[test]
+    fn test_no_include_patterns_includes_everything() {
+        let analyzer = Analyzer::builder()
+            .root("/project")
+            .build()
+            .expect("Failed to build analyzer");
+
+        assert!(analyzer.should_include(Path::new("/project/build.rs")));
+        assert!(analyzer.should_include(Path::new("/project/src/lib.rs")));
+    }
+
+    #[test]
+    fn test_include_patterns_relative_to_root() {
+        let analyzer = Analyzer::builder()
+            .root("/project")
+            .include("src/**")
+            .build()
+            .expect("Failed to build analyzer");
+
+        assert!(analyzer.should_include(Path::new("/project/src/lib.rs")));
+        assert!(analyzer.should_include(Path::new("/project/src/a/b.rs")));
+        assert!(!analyzer.should_include(Path::new("/project/build.rs")));
+        assert!(!analyzer.should_include(Path::new("/project/tests/it.rs")));
+    }
+
+    #[test]
+    fn test_include_and_exclude_interaction() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        for file in [
+            "build.rs",
+            "src/lib.rs",
+            "src/generated/api.rs",
+            "tests/it.rs",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().expect("has parent")).expect("create dir");
+            std::fs::write(path, "").expect("write file");
+        }
+
+        let analyzer = Analyzer::builder()
+            .root(dir.path())
+            .include("src/**")
+            .exclude("**/generated/**")
+            .build()
+            .expect("Failed to build analyzer");
+
+        let files = analyzer.discover_files().expect("discover files");
+        let relative: Vec<_> = files
+            .iter()
+            .map(|f| f.strip_prefix(dir.path()).expect("under root"))
+            .collect();
+        assert_eq!(relative, vec![Path::new("src/lib.rs")]);
+    }
+
+    #[test]
+    fn test_files_extensions_and_extra() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        for file in [
+            "src/lib.rs",
+            "src/api.rs.in",
+            "src/notes.txt",
+            "target/gen/out.rs",
+            "target/debug/dep.rs",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().expect("has parent")).expect("create dir");
+            std::fs::write(path, "").expect("write file");
+        }
+
+        let config = Config::parse(
+            "[files]\nextensions = [\"rs\", \".rs.in\"]\nextra = [\"target/gen/**/*.rs\"]\n",
+        )
+        .expect("parse");
+        let analyzer = Analyzer::builder()
+            .root(dir.path())
+            .config(config)
+            .build()
+            .expect("Failed to build analyzer");
+
+        let files = analyzer.discover_files().expect("discover files");
+        let mut relative: Vec<_> = files
+            .iter()
+            .map(|f| f.strip_prefix(dir.path()).expect("under root"))
+            .collect();
+        relative.sort();
+        assert_eq!(
+            relative,
+            vec![
+                Path::new("src/api.rs.in"),
+                Path::new("src/lib.rs"),
+                Path::new("target/gen/out.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_include_patterns_from_config() {
+        let config = Config::parse("[analyzer]\ninclude = [\"src/**\"]\n").expect("parse");
+        let analyzer = Analyzer::builder()
+            .root("/project")
+            .config(config)
+            .build()
+            .expect("Failed to build analyzer");
+
+        assert!(analyzer.should_include(Path::new("/project/src/lib.rs")));
+        assert!(!analyzer.should_include(Path::new("/project/benches/b.rs")));
+    }
+
+    #[test]
+    fn test_invalid_include_pattern() {
+        let result = Analyzer::builder()
+            .root("/project")
+            .include("src/[")
+            .build();
+        assert!(matches!(result, Err(AnalyzerError::Glob(_))));
+    }
+
+    /// Records the `limit` option it was configured with.
+    #[derive(Debug)]
+    struct ConfigurableRule(Arc<AtomicUsize>);
+
+    impl Rule for ConfigurableRule {
+        fn name(&self) -> &'static str {
+            "configurable"
+        }
+
+        fn code(&self) -> &'static str {
+            "T004"
+        }
+
+        fn configure(&mut self, config: &RuleConfig) -> Result<(), crate::ConfigError> {
+            let limit = config.get_int("limit", 0);
+            let limit = usize::try_from(limit).map_err(|_| crate::ConfigError::InvalidOption {
+                rule: self.name().to_string(),
+                key: "limit".to_string(),
+                message: "must not be negative".to_string(),
+            })?;
+            self.0.store(limit, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn check(&self, _ctx: &FileContext<'_>, _ast: &syn::File) -> Vec<Violation> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_build_applies_rule_options() {
+        let limit = Arc::new(AtomicUsize::new(0));
+        let config = Config::parse("[rules.configurable]\nlimit = 7\n").expect("parse");
+        Analyzer::builder()
+            .root("/project")
+            .config(config)
+            .rule(ConfigurableRule(Arc::clone(&limit)))
+            .build()
+            .expect("build");
+        assert_eq!(limit.load(Ordering::SeqCst), 7);
+
+        let config = Config::parse("[rules.configurable]\nlimit = -1\n").expect("parse");
+        let result = Analyzer::builder()
+            .root("/project")
+            .config(config)
+            .rule(ConfigurableRule(Arc::clone(&limit)))
+            .build();
+        assert!(matches!(
+            result,
+            Err(AnalyzerError::Config(
+                crate::ConfigError::InvalidOption { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_arch_lintignore_merged_with_excludes() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join(".git")).expect("create .git");
+        std::fs::write(dir.path().join(crate::IGNORE_FILE_NAME), "src/generated/\n")
+            .expect("write ignore file");
+
+        let analyzer = Analyzer::builder()
+            .root(dir.path())
+            .exclude("**/vendor/**")
+            .build()
+            .expect("Failed to build analyzer");
+
+        assert!(analyzer.should_exclude(&dir.path().join("src/generated/api.rs")));
+        assert!(analyzer.should_exclude(&dir.path().join("vendor/lib.rs")));
+        assert!(!analyzer.should_exclude(&dir.path().join("src/lib.rs")));
+    }
+
+    /// Flags every file and counts how often it actually runs.
+    #[derive(Debug)]
+    struct CountingRule(Arc<AtomicUsize>);
+
+    impl Rule for CountingRule {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn code(&self) -> &'static str {
+            "T001"
+        }
+
+        // The run counter is not a setting.
+        fn cache_key(&self) -> String {
+            self.name().to_string()
+        }
+
+        fn check(&self, ctx: &FileContext<'_>, _ast: &syn::File) -> Vec<Violation> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            vec![Violation::new(
+                self.code(),
+                self.name(),
+                crate::Severity::Warning,
+                crate::Location::new(ctx.relative_path.clone(), 1, 1),
+                "checked",
+            )]
+        }
+    }
+
+    #[derive(Debug)]
+    struct AbsolutePathRule;
+
+    impl ProjectRule for AbsolutePathRule {
+        fn name(&self) -> &'static str {
+            "absolute-path"
+        }
+
+        fn code(&self) -> &'static str {
+            "T002"
+        }
+
+        fn check_project(&self, ctx: &ProjectContext) -> Vec<Violation> {
+            ctx.source_files
+                .iter()
+                .map(|path| {
+                    Violation::new(
+                        self.code(),
+                        self.name(),
+                        crate::Severity::Warning,
+                        crate::Location::new(path.clone(), 1, 1),
+                        "project",
+                    )
+                    .with_label(crate::Label::new(
+                        crate::Location::new(ctx.root.to_path_buf(), 0, 0),
+                        "root",
+                    ))
+                })
+                .collect()
+        }
+    }
+
+    /// Counts runs like [`CountingRule`], but only applies to files
+    /// mentioning `marker`.
+    #[derive(Debug)]
+    struct MarkerRule(Arc<AtomicUsize>);
+
+    impl Rule for MarkerRule {
+        fn name(&self) -> &'static str {
+            "marker"
+        }
+
+        fn code(&self) -> &'static str {
+            "T002"
+        }
+
+        fn applies_to(&self, ctx: &FileContext) -> bool {
+            ctx.content.contains("marker")
+        }
+
+        fn check(&self, _ctx: &FileContext<'_>, _ast: &syn::File) -> Vec<Violation> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_max_file_bytes_skips_large_files() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/small.rs"), "fn small() {}").expect("write small");
+        let generated = "const X: u8 = 0;\n".repeat(100_000);
+        std::fs::write(dir.path().join("src/generated.rs"), &generated).expect("write generated");
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let result = Analyzer::builder()
+            .root(dir.path())
+            .rule(CountingRule(Arc::clone(&runs)))
+            .project_rule(AbsolutePathRule)
+            .max_file_bytes(64 * 1024)
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+
+        assert_eq!(result.files_checked, 1);
+        assert_eq!(result.files_skipped, 1);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(result
+            .violations
+            .iter()
+            .all(|v| !v.location.file.ends_with("generated.rs")));
+    }
+
+    #[test]
+    fn test_applies_to_skips_check() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "// marker\nfn a() {}").expect("write a");
+        std::fs::write(dir.path().join("src/b.rs"), "fn b() {}").expect("write b");
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let result = Analyzer::builder()
+            .root(dir.path())
+            .rule(MarkerRule(Arc::clone(&runs)))
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+
+        assert_eq!(result.files_checked, 2);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    fn run_paths(root: &Path, absolute: bool) -> Vec<(PathBuf, PathBuf)> {
+        Analyzer::builder()
+            .root(root)
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .project_rule(AbsolutePathRule)
+            .absolute_paths(absolute)
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed")
+            .violations
+            .into_iter()
+            .map(|v| {
+                let label = v.labels.first().map(|l| l.location.file.clone());
+                (v.location.file, label.unwrap_or_default())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_locations_relative_to_root() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+
+        let paths = run_paths(dir.path(), false);
+        assert_eq!(
+            paths,
+            vec![
+                (PathBuf::from("src/a.rs"), PathBuf::new()),
+                (PathBuf::from("src/a.rs"), PathBuf::from(".")),
+            ]
+        );
+
+        // `root/.` resolves to the same root and yields identical output.
+        assert_eq!(run_paths(&dir.path().join("."), false), paths);
+    }
+
+    #[test]
+    fn test_absolute_paths_opt_in() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+
+        let expected = dir.path().join("src/a.rs");
+        for (file, _) in run_paths(dir.path(), true) {
+            assert_eq!(file, expected);
+        }
+    }
+
+    #[test]
+    fn test_config_doc_ref_overrides_violations() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+
+        let config = Config::parse(
+            "[rules.counting]\ndoc_ref = \"docs/adr/0007.md\"\n\n\
+             [rules.absolute-path]\ndoc_ref = \"docs/adr/0008.md\"\n",
+        )
+        .expect("parse");
+        assert!(config.rules["counting"].options.is_empty());
+
+        let result = Analyzer::builder()
+            .root(dir.path())
+            .config(config)
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .project_rule(AbsolutePathRule)
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+
+        let doc_refs: Vec<_> = result
+            .violations
+            .iter()
+            .map(|v| (v.code.as_str(), v.doc_ref.as_deref()))
+            .collect();
+        assert_eq!(
+            doc_refs,
+            vec![
+                ("T001", Some("docs/adr/0007.md")),
+                ("T002", Some("docs/adr/0008.md")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_message_template() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+
+        let config = Config::parse(
+            "[rules.counting]\ndoc_ref = \"docs/adr/0007.md\"\n\
+             message = \"[{code}/{rule}] {message}, see {doc_ref} {unknown}\"\n",
+        )
+        .expect("parse");
+        assert!(config.rules["counting"].options.is_empty());
+
+        let result = Analyzer::builder()
+            .root(dir.path())
+            .config(config)
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+        assert_eq!(
+            result.violations[0].message,
+            "[T001/counting] checked, see docs/adr/0007.md {unknown}"
+        );
+    }
+
+    #[test]
+    fn test_render_message() {
+        let mut violation = Violation::new(
+            "AL001",
+            "no-unwrap-expect",
+            crate::Severity::Error,
+            crate::Location::new(PathBuf::from("src/a.rs"), 1, 1),
+            "uses {rule}",
+        );
+        assert_eq!(
+            render_message("Avoid .unwrap() ({code}) - see {doc_ref}", &violation),
+            "Avoid .unwrap() (AL001) - see "
+        );
+        violation.doc_ref = Some("docs/errors.md".to_string());
+        assert_eq!(
+            render_message("{message} - see {doc_ref}", &violation),
+            "uses {rule} - see docs/errors.md",
+            "substituted text is not expanded again"
+        );
+        assert_eq!(render_message("{rule", &violation), "{rule");
+        assert_eq!(render_message("{}{{code}}", &violation), "{}{AL001}");
+    }
+
+    #[test]
+    fn test_rules_check_cargo_toml() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir_all(dir.path().join("app/src")).expect("create src");
+        std::fs::write(dir.path().join("app/src/lib.rs"), "fn a() {}").expect("write lib");
+        std::fs::write(
+            dir.path().join("app/Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nlog = \"0.4\"\n",
+        )
+        .expect("write manifest");
+
+        let rule = crate::RequiredCrateRule::new("T005", "prefer-tracing")
+            .prefer("tracing")
+            .over(&["log"])
+            .detect_cargo_toml();
+        let result = Analyzer::builder()
+            .root(dir.path())
+            .rule(rule)
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+
+        assert_eq!(result.violations.len(), 1);
+        let location = &result.violations[0].location;
+        assert_eq!(location.file, PathBuf::from("app/Cargo.toml"));
+        assert_eq!(location.line, 5);
+    }
+
+    fn run_cached(root: &Path, runs: &Arc<AtomicUsize>) -> LintResult {
+        Analyzer::builder()
+            .root(root)
+            .rule(CountingRule(Arc::clone(runs)))
+            .cache(true)
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed")
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_files() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+        std::fs::write(dir.path().join("src/b.rs"), "fn b() {}").expect("write b");
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let first = run_cached(dir.path(), &runs);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert!(dir.path().join(cache::DEFAULT_CACHE_FILE).exists());
+
+        let second = run_cached(dir.path(), &runs);
+        assert_eq!(runs.load(Ordering::SeqCst), 2, "unchanged files re-checked");
+        assert_eq!(second.violations.len(), first.violations.len());
+        assert_eq!(second.files_checked, 2);
+
+        std::fs::write(dir.path().join("src/b.rs"), "fn b2() {}").expect("rewrite b");
+        run_cached(dir.path(), &runs);
+        assert_eq!(
+            runs.load(Ordering::SeqCst),
+            3,
+            "only the edited file re-checked"
+        );
+    }
+
+    #[test]
+    fn test_single_file_run_keeps_other_cache_entries() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n")
+            .expect("write manifest");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+        std::fs::write(dir.path().join("src/b.rs"), "fn b() {}").expect("write b");
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        run_cached(dir.path(), &runs);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        std::fs::write(dir.path().join("src/a.rs"), "fn a2() {}").expect("rewrite a");
+        run_cached(&dir.path().join("src/a.rs"), &runs);
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        run_cached(dir.path(), &runs);
+        assert_eq!(
+            runs.load(Ordering::SeqCst),
+            3,
+            "the single-file run kept b.rs cached"
+        );
+    }
+
+    #[test]
+    fn test_cache_expires_with_allow_directive() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(
+            dir.path().join("src/a.rs"),
+            "// arch-lint: allow(all) expires=\"2025-06-01\"\nfn a() {}\n",
+        )
+        .expect("write a");
+        let runs = Arc::new(AtomicUsize::new(0));
+        let run = |today: &str| {
+            Analyzer::builder()
+                .root(dir.path())
+                .rule(CountingRule(Arc::clone(&runs)))
+                .cache(true)
+                .today(AllowDate::parse(today).expect("date"))
+                .build()
+                .expect("Failed to build analyzer")
+                .analyze()
+                .expect("analysis failed")
+        };
+
+        run("2025-05-30");
+        run("2025-06-01");
+        assert_eq!(runs.load(Ordering::SeqCst), 1, "reused until expiry");
+
+        let expired = run("2025-06-02");
+        assert_eq!(runs.load(Ordering::SeqCst), 2, "re-checked once expired");
+        assert!(expired
+            .violations
+            .iter()
+            .any(|v| v.rule == allowance::EXPIRED_NAME));
+
+        run("2025-06-03");
+        assert_eq!(
+            runs.load(Ordering::SeqCst),
+            2,
+            "an expired directive no longer changes"
+        );
+    }
+
+    #[test]
+    fn test_ruleset_hash_covers_rule_options() {
+        let hash = |limit: usize| {
+            Analyzer::builder()
+                .root("/project")
+                .rule(ConfigurableRule(Arc::new(AtomicUsize::new(limit))))
+                .build()
+                .expect("build")
+                .ruleset_hash()
+        };
+        assert_eq!(hash(1), hash(1));
+        assert_ne!(hash(1), hash(2), "options set in code must change the key");
+    }
+
+    /// Reports each `fn` item found through the shared ASTs.
+    #[derive(Debug)]
+    struct FnItemRule;
+
+    impl ProjectRule for FnItemRule {
+        fn name(&self) -> &'static str {
+            "fn-items"
+        }
+
+        fn code(&self) -> &'static str {
+            "T003"
+        }
+
+        fn check_project(&self, ctx: &ProjectContext) -> Vec<Violation> {
+            ctx.asts()
+                .flat_map(|(path, ast)| {
+                    ast.items
+                        .iter()
+                        .filter_map(|item| match item {
+                            syn::Item::Fn(f) => Some(Violation::new(
+                                self.code(),
+                                self.name(),
+                                crate::Severity::Info,
+                                crate::Location::new(path.to_path_buf(), 1, 1),
+                                f.sig.ident.to_string(),
+                            )),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_project_rules_see_parsed_asts() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {} fn b() {}").expect("write a");
+
+        for share in [true, false] {
+            let result = Analyzer::builder()
+                .root(dir.path())
+                .project_rule(FnItemRule)
+                .share_asts(share)
+                .build()
+                .expect("Failed to build analyzer")
+                .analyze()
+                .expect("analysis failed");
+            assert_eq!(result.violations.len(), 2, "share_asts({share})");
+        }
+    }
+
+    fn write_crates(dir: &Path, names: &[&str]) {
+        for name in names {
+            std::fs::create_dir_all(dir.join(name).join("src")).expect("create src");
+            std::fs::write(
+                dir.join(name).join("src/lib.rs"),
+                format!("fn {name}() {{}}"),
+            )
+            .expect("write lib");
+        }
+    }
+
+    #[test]
+    fn test_multiple_roots_union_files() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        write_crates(dir.path(), &["a", "b", "c"]);
+
+        let analyzer = Analyzer::builder()
+            .roots([dir.path().join("a"), dir.path().join("b")])
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .project_rule(FnItemRule)
+            .build()
+            .expect("Failed to build analyzer");
+        assert_eq!(analyzer.root(), dir.path());
+        assert_eq!(analyzer.roots().len(), 2);
+
+        let result = analyzer.analyze().expect("analysis failed");
+        assert_eq!(result.files_checked, 2);
+
+        let mut files: Vec<_> = result
+            .violations
+            .iter()
+            .filter(|v| v.code == "T001")
+            .map(|v| v.location.file.clone())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![PathBuf::from("a/src/lib.rs"), PathBuf::from("b/src/lib.rs")]
+        );
+
+        let mut fns: Vec<_> = result
+            .violations
+            .iter()
+            .filter(|v| v.code == "T003")
+            .map(|v| v.message.clone())
+            .collect();
+        fns.sort();
+        assert_eq!(fns, ["a", "b"], "project rules see every root");
+    }
+
+    #[test]
+    fn test_overlapping_roots_dedupe_files() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        write_crates(dir.path(), &["a"]);
+
+        let result = Analyzer::builder()
+            .roots([dir.path().to_path_buf(), dir.path().join("a")])
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+        assert_eq!(result.files_checked, 1);
+        assert_eq!(
+            result.violations[0].location.file,
+            PathBuf::from("a/src/lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_file_root_analyzes_only_that_file() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        write_crates(dir.path(), &["a"]);
+        std::fs::write(dir.path().join("a/Cargo.toml"), "[package]\nname = \"a\"\n")
+            .expect("write manifest");
+        std::fs::write(dir.path().join("a/src/other.rs"), "fn other() {}").expect("write other");
+
+        let analyzer = Analyzer::builder()
+            .root(dir.path().join("a/src/lib.rs"))
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .project_rule(FnItemRule)
+            .build()
+            .expect("Failed to build analyzer");
+        assert_eq!(analyzer.root(), dir.path().join("a"));
+
+        let result = analyzer.analyze().expect("analysis failed");
+        assert_eq!(result.files_checked, 1);
+        assert_eq!(
+            result.violations[0].location.file,
+            PathBuf::from("src/lib.rs")
+        );
+        assert!(
+            result.violations.iter().all(|v| v.code != "T003"),
+            "project rules are skipped without a directory root"
+        );
+    }
+
+    #[test]
+    fn test_common_ancestor() {
+        let roots = [
+            PathBuf::from("/work/repo/crate-a"),
+            PathBuf::from("/work/repo/crate-b/src"),
+        ];
+        assert_eq!(common_ancestor(&roots), PathBuf::from("/work/repo"));
+        assert_eq!(common_ancestor(&roots[..1]), roots[0]);
+        assert_eq!(common_ancestor(&[]), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        for i in 0..8 {
+            std::fs::write(
+                dir.path().join(format!("src/m{i}.rs")),
+                "// arch-lint: allow(all) expires=\"2025-06-01\"\nfn f() {}\n",
+            )
+            .expect("write module");
+        }
+        std::fs::write(dir.path().join("src/broken.rs"), "fn (").expect("write broken");
+
+        let run = |parallel: bool| {
+            Analyzer::builder()
+                .root(dir.path())
+                .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+                .parallel(parallel)
+                .today(AllowDate::parse("2025-06-02").expect("date"))
+                .build()
+                .expect("Failed to build analyzer")
+                .analyze()
+                .expect("analysis failed")
+        };
+
+        let sequential = run(false);
+        let parallel = run(true);
+        assert_eq!(parallel.files_checked, 8);
+        assert_eq!(parallel.files_checked, sequential.files_checked);
+        // The pinned date reaches worker threads.
+        assert!(parallel
+            .violations
+            .iter()
+            .any(|v| v.rule == allowance::EXPIRED_NAME));
+
+        let key = |v: &Violation| (v.location.clone(), v.rule.clone(), v.message.clone());
+        let parallel: Vec<_> = parallel.violations.iter().map(key).collect();
+        let sequential: Vec<_> = sequential.violations.iter().map(key).collect();
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn test_parallel_rule_groups_match_sequential() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        let source = "fn a() {}\nfn b() {}\n";
+        for name in ["a", "b"] {
+            std::fs::write(dir.path().join(format!("src/{name}.rs")), source)
+                .expect("write module");
+        }
+        let path = dir.path().join("src/a.rs");
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .expect("build pool");
+
+        let run = |parallel: bool| {
+            let analyzer = (0..RULES_PER_GROUP * 3)
+                .fold(Analyzer::builder().root(dir.path()), |builder, _| {
+                    builder.rule(ItemRule)
+                })
+                .parallel(parallel)
+                .split_rules(true)
+                .dedupe(false)
+                .build()
+                .expect("Failed to build analyzer");
+            let key = |v: &Violation| (v.location.clone(), v.message.clone());
+            pool.install(|| {
+                let result = analyzer.analyze().expect("analysis failed");
+                let source = analyzer
+                    .analyze_source(&path, source)
+                    .expect("analysis failed");
+                (
+                    result.violations.iter().map(key).collect::<Vec<_>>(),
+                    source.iter().map(key).collect::<Vec<_>>(),
+                )
+            })
+        };
+
+        let (sequential, sequential_source) = run(false);
+        let (parallel, parallel_source) = run(true);
+        assert_eq!(parallel.len(), RULES_PER_GROUP * 3 * 2 * 2);
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel_source.len(), RULES_PER_GROUP * 3 * 2);
+        assert_eq!(parallel_source, sequential_source);
+    }
+
+    /// Records the size of the pool it runs on.
+    #[derive(Debug)]
+    struct PoolSizeRule(Arc<AtomicUsize>);
+
+    impl Rule for PoolSizeRule {
+        fn name(&self) -> &'static str {
+            "pool-size"
+        }
+
+        fn code(&self) -> &'static str {
+            "T004"
+        }
+
+        fn check(&self, _ctx: &FileContext<'_>, _ast: &syn::File) -> Vec<Violation> {
+            self.0.store(rayon::current_num_threads(), Ordering::SeqCst);
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_parallelism_sizes_the_pool() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+
+        let threads = Arc::new(AtomicUsize::new(0));
+        let mut config = Config::default();
+        config.analyzer.parallelism = Some(3);
+        Analyzer::builder()
+            .root(dir.path())
+            .config(config.clone())
+            .rule(PoolSizeRule(Arc::clone(&threads)))
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+        assert_eq!(threads.load(Ordering::SeqCst), 3);
+
+        config.analyzer.parallelism = Some(0);
+        let err = Analyzer::builder()
+            .root(dir.path())
+            .config(config)
+            .build()
+            .err()
+            .expect("zero threads rejected");
+        assert!(err.to_string().contains("analyzer.parallelism"), "{err}");
+    }
+
+    #[test]
+    fn test_allow_range_suppresses_declarative_violation() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "// arch-lint: allow-begin(restrict-use) reason=\"migration\"\n\
+             use sqlx::PgPool;\n\
+             // arch-lint: allow-end(restrict-use)\n\
+             // arch-lint: allow-begin(ALD001)\n\
+             use sqlx::Row;\n\
+             // arch-lint: allow-end(ALD001)\n\
+             use sqlx::Pool;\n",
+        )
+        .expect("write lib");
+        let declarative = crate::declarative::load_all(
+            "[[restrict-use]]\nname = \"no-sqlx\"\nfiles = [\"src/**\"]\ndeny = [\"sqlx::*\"]\nmessage = \"No sqlx.\"\n",
+            crate::ConfigFormat::Toml,
+        )
+        .expect("load declarative rules");
+        let builder = declarative.rules.into_iter().fold(
+            Analyzer::builder().root(dir.path()),
+            AnalyzerBuilder::rule_box,
+        );
+
+        let result = builder
+            .build()
+            .expect("Failed to build analyzer")
+            .analyze()
+            .expect("analysis failed");
+        let found: Vec<_> = result
+            .violations
+            .iter()
+            .map(|v| {
+                (
+                    v.location.line,
+                    v.severity,
+                    v.message.contains("missing required reason"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![(5, Severity::Warning, true), (7, Severity::Error, false)],
+            "{:?}",
+            result.violations
+        );
+    }
+
+    #[test]
+    fn test_dedupe_identical_violations() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+
+        let run = |dedupe: bool| {
+            Analyzer::builder()
+                .root(dir.path())
+                .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+                .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+                .dedupe(dedupe)
+                .build()
+                .expect("Failed to build analyzer")
+                .analyze()
+                .expect("analysis failed")
+                .violations
+                .len()
+        };
+
+        assert_eq!(run(true), 1);
+        assert_eq!(run(false), 2);
+    }
+
+    #[test]
+    fn test_analyze_source_checks_unsaved_content() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let analyzer = Analyzer::builder()
+            .root(dir.path())
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .build()
+            .expect("Failed to build analyzer");
+
+        let path = dir.path().join("src/new.rs");
+        let violations = analyzer
+            .analyze_source(&path, "fn a() {}")
+            .expect("analysis failed");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location.file, PathBuf::from("src/new.rs"));
+
+        assert!(matches!(
+            analyzer.analyze_source(&path, "fn ("),
+            Err(AnalyzerError::Parse { .. })
+        ));
+    }
+
+    /// Reports each top-level item by name.
+    #[derive(Debug)]
+    struct ItemRule;
+
+    impl Rule for ItemRule {
+        fn name(&self) -> &'static str {
+            "items"
+        }
+
+        fn code(&self) -> &'static str {
+            "T004"
+        }
+
+        fn check(&self, ctx: &FileContext<'_>, ast: &syn::File) -> Vec<Violation> {
+            ast.items
+                .iter()
+                .filter_map(|item| match item {
+                    syn::Item::Fn(f) => Some(f.sig.ident.to_string()),
+                    _ => None,
+                })
+                .map(|name| {
+                    Violation::new(
+                        self.code(),
+                        self.name(),
+                        crate::Severity::Warning,
+                        crate::Location::new(ctx.relative_path.clone(), 1, 1),
+                        name,
+                    )
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_cfg_options_skip_inactive_items() {
+        let source = "#[cfg(feature = \"sqlx\")]\nfn db() {}\n\
+                      #[cfg(feature = \"serde\")]\nfn ser() {}\n\
+                      #[cfg(windows)]\nfn win() {}\n";
+        let names = |cfg: &[&str]| {
+            let mut config = Config::default();
+            config.analyzer.cfg = cfg.iter().map(ToString::to_string).collect();
+            Analyzer::builder()
+                .root(".")
+                .config(config)
+                .rule(ItemRule)
+                .build()
+                .expect("Failed to build analyzer")
+                .analyze_source(Path::new("src/lib.rs"), source)
+                .expect("analysis failed")
+                .into_iter()
+                .map(|v| v.message)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&[]), ["db", "ser", "win"]);
+        assert_eq!(names(&["feature=serde"]), ["ser", "win"]);
+
+        let mut config = Config::default();
+        config.analyzer.cfg = vec!["not valid".to_string()];
+        let err = Analyzer::builder().config(config).build();
+        assert!(matches!(err, Err(AnalyzerError::Config(_))));
+    }
+
+    #[test]
+    fn test_timings_cover_rules_when_enabled() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+
+        let build = |timings: bool| {
+            Analyzer::builder()
+                .root(dir.path())
+                .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+                .project_rule(FnItemRule)
+                .timings(timings)
+                .build()
+                .expect("Failed to build analyzer")
+        };
+
+        let analyzer = build(false);
+        analyzer.analyze().expect("analysis failed");
+        assert!(analyzer.timings().is_none());
+
+        let analyzer = build(true);
+        assert!(analyzer.timings().is_none(), "no run yet");
+        analyzer.analyze().expect("analysis failed");
+        let timings = analyzer.timings().expect("timings recorded");
+        assert!(timings.rules.contains_key("counting"));
+        assert_eq!(timings.project_rules.len(), 1);
+        assert!(timings.total >= timings.discovery);
+        let names: Vec<_> = timings.breakdown().into_iter().map(|(n, _)| n).collect();
+        assert!(names.contains(&"parsing".to_string()), "{names:?}");
+    }
+
+    #[test]
+    fn test_analyze_streaming_reports_per_file() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/a.rs"), "fn a() {}").expect("write a");
+        std::fs::write(dir.path().join("src/b.rs"), "fn b() {}").expect("write b");
+
+        let analyzer = Analyzer::builder()
+            .root(dir.path())
+            .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+            .project_rule(FnItemRule)
+            .build()
+            .expect("Failed to build analyzer");
+
+        let mut streamed = Vec::new();
+        let summary = analyzer
+            .analyze_streaming(|v| streamed.push((v.rule.clone(), v.location.file.clone())))
+            .expect("analysis failed");
+
+        assert_eq!(summary.files_checked, 2);
+        assert_eq!(summary.warnings, 2);
+        assert_eq!(summary.infos, 2);
+        assert_eq!(summary.total(), streamed.len());
+        // Per-file violations first, project rules last.
+        assert_eq!(streamed[0], ("counting".into(), PathBuf::from("src/a.rs")));
+        assert_eq!(streamed[1], ("counting".into(), PathBuf::from("src/b.rs")));
+        assert!(streamed[2..].iter().all(|(rule, _)| rule == "fn-items"));
+
+        let buffered = analyzer.analyze().expect("analysis failed");
+        assert_eq!(buffered.violations.len(), streamed.len());
+        assert_eq!(
+            buffered.violations[0].location.file,
+            PathBuf::from("src/a.rs")
+        );
+    }
+
+    #[test]
+    fn test_parallel_streaming_keeps_file_order_and_stops_on_error() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        for i in 0..24 {
+            // Uneven sizes, so files finish out of order.
+            let body = "fn f() {}\n".repeat((24 - i) * 20);
+            std::fs::write(dir.path().join(format!("src/m{i:02}.rs")), body).expect("write module");
+        }
+
+        let build = |fail_on_parse_error: bool| {
+            Analyzer::builder()
+                .root(dir.path())
+                .rule(CountingRule(Arc::new(AtomicUsize::new(0))))
+                .fail_on_parse_error(fail_on_parse_error)
+                .build()
+                .expect("Failed to build analyzer")
+        };
+
+        let mut streamed = Vec::new();
+        build(false)
+            .analyze_streaming(|v| streamed.push(v.location.file.clone()))
+            .expect("analysis failed");
+        let expected: Vec<_> = (0..24)
+            .map(|i| PathBuf::from(format!("src/m{i:02}.rs")))
+            .collect();
+        assert_eq!(streamed, expected);
+
+        std::fs::write(dir.path().join("src/m05.rs"), "fn (").expect("write broken");
+        let mut streamed = Vec::new();
+        let result = build(true).analyze_streaming(|v| streamed.push(v.location.file.clone()));
+        assert!(matches!(result, Err(AnalyzerError::Parse { .. })));
+        assert_eq!(streamed, expected[..5]);
+    }
 }
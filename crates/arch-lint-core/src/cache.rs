@@ -0,0 +1,321 @@
+//! On-disk incremental cache for per-file lint results.
+//!
+//! Each entry is keyed by file path and the blake3 hash of its content.
+//! Entries for files with a pending `expires` date on an allow directive
+//! also record that date and go stale once it has passed.
+//! The whole cache is tied to a ruleset hash (registered rules + config +
+//! arch-lint version) and discarded wholesale when that hash changes.
+//!
+//! A run that checks only some files (a single path, `--files-from`) keeps
+//! the entries of the others, so it does not throw away a full-tree cache.
+//! Each entry records the analysis root its file was checked under, since
+//! the root decides the file's relative path.
+
+use crate::types::Violation;
+use crate::utils::allowance::AllowDate;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Default cache file location, relative to the analysis root.
+pub const DEFAULT_CACHE_FILE: &str = "target/arch-lint-cache.json";
+
+/// Cached violations for a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    content_hash: String,
+    root: PathBuf,
+    violations: Vec<Violation>,
+    /// Earliest pending `expires` date (`YYYY-MM-DD`); the entry is valid
+    /// up to and including it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    valid_until: Option<String>,
+}
+
+/// Persistent cache of per-file violations.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct AnalysisCache {
+    ruleset_hash: String,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl AnalysisCache {
+    /// Creates an empty cache bound to the given ruleset hash.
+    pub(crate) fn new(ruleset_hash: String) -> Self {
+        Self {
+            ruleset_hash,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Loads the cache from disk.
+    ///
+    /// Returns an empty cache if the file is missing, unreadable, or was
+    /// written for a different ruleset.
+    pub(crate) fn load(path: &Path, ruleset_hash: &str) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::new(ruleset_hash.to_string());
+        };
+
+        match serde_json::from_str::<Self>(&content) {
+            Ok(cache) if cache.ruleset_hash == ruleset_hash => cache,
+            Ok(_) => {
+                debug!("Ruleset changed, discarding cache at {}", path.display());
+                Self::new(ruleset_hash.to_string())
+            }
+            Err(e) => {
+                warn!("Ignoring corrupt cache at {}: {}", path.display(), e);
+                Self::new(ruleset_hash.to_string())
+            }
+        }
+    }
+
+    /// Writes the cache to disk, creating parent directories as needed.
+    pub(crate) fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// Returns cached violations, and the date they are valid until, if the
+    /// file content and root are unchanged and that date has not passed by
+    /// `today`.
+    pub(crate) fn get(
+        &self,
+        path: &Path,
+        root: &Path,
+        content_hash: &str,
+        today: AllowDate,
+    ) -> Option<(&[Violation], Option<AllowDate>)> {
+        let entry = self
+            .entries
+            .get(path)
+            .filter(|entry| entry.content_hash == content_hash && entry.root == root)?;
+        let valid_until = match entry.valid_until.as_deref() {
+            Some(date) => Some(AllowDate::parse(date).filter(|d| today <= *d)?),
+            None => None,
+        };
+        Some((entry.violations.as_slice(), valid_until))
+    }
+
+    /// Records the violations produced for a file, valid up to and including
+    /// `valid_until` when given.
+    pub(crate) fn insert(
+        &mut self,
+        path: PathBuf,
+        root: PathBuf,
+        content_hash: String,
+        violations: Vec<Violation>,
+        valid_until: Option<AllowDate>,
+    ) {
+        self.entries.insert(
+            path,
+            CacheEntry {
+                content_hash,
+                root,
+                violations,
+                valid_until: valid_until.map(|date| date.to_string()),
+            },
+        );
+    }
+
+    /// Copies over `previous` entries for files this run did not check,
+    /// dropping those whose file no longer exists.
+    pub(crate) fn carry_over(&mut self, previous: Self) {
+        for (path, entry) in previous.entries {
+            if !self.entries.contains_key(&path) && path.is_file() {
+                self.entries.insert(path, entry);
+            }
+        }
+    }
+}
+
+/// Hashes file content for cache lookup.
+pub(crate) fn content_hash(content: &str) -> String {
+    blake3::hash(content.as_bytes()).to_hex().to_string()
+}
+
+/// Computes a hash identifying the active ruleset.
+///
+/// Any change to the registered rules (each given as its code and
+/// [`Rule::cache_key`](crate::Rule::cache_key)), the configuration, or the
+/// arch-lint version yields a different hash.
+pub(crate) fn ruleset_hash<'a, K: AsRef<str>>(
+    rule_ids: impl IntoIterator<Item = (&'a str, K)>,
+    config_repr: &str,
+) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    for (code, key) in rule_ids {
+        hasher.update(b"\0");
+        hasher.update(code.as_bytes());
+        hasher.update(b":");
+        hasher.update(key.as_ref().as_bytes());
+    }
+    hasher.update(b"\0");
+    hasher.update(config_repr.as_bytes());
+    hasher.finalize().to_hex().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Location, Severity};
+
+    fn date(s: &str) -> AllowDate {
+        AllowDate::parse(s).expect("valid date")
+    }
+
+    fn root() -> PathBuf {
+        PathBuf::from("/p")
+    }
+
+    fn sample_violation() -> Violation {
+        Violation::new(
+            "AL001",
+            "no-unwrap-expect",
+            Severity::Error,
+            Location::new(PathBuf::from("src/lib.rs"), 3, 5),
+            "Use of .unwrap()",
+        )
+    }
+
+    #[test]
+    fn test_get_requires_matching_hash() {
+        let mut cache = AnalysisCache::new("rs".into());
+        let path = PathBuf::from("/p/src/lib.rs");
+        cache.insert(
+            path.clone(),
+            root(),
+            content_hash("a"),
+            vec![sample_violation()],
+            None,
+        );
+
+        assert_eq!(
+            cache
+                .get(&path, &root(), &content_hash("a"), date("2025-06-01"))
+                .map(|(violations, _)| violations.len()),
+            Some(1)
+        );
+        assert!(cache
+            .get(&path, &root(), &content_hash("b"), date("2025-06-01"))
+            .is_none());
+        assert!(cache
+            .get(
+                &path,
+                Path::new("/p/src"),
+                &content_hash("a"),
+                date("2025-06-01")
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_carry_over_keeps_unchecked_existing_files() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let kept = dir.path().join("a.rs");
+        let checked = dir.path().join("b.rs");
+        std::fs::write(&kept, "fn a() {}").expect("write a");
+        std::fs::write(&checked, "fn b() {}").expect("write b");
+        let root = dir.path().to_path_buf();
+        let today = date("2025-06-01");
+
+        let mut previous = AnalysisCache::new("rs".into());
+        for path in [&kept, &checked, &dir.path().join("deleted.rs")] {
+            previous.insert(
+                path.clone(),
+                root.clone(),
+                content_hash("old"),
+                vec![sample_violation()],
+                None,
+            );
+        }
+
+        let mut next = AnalysisCache::new("rs".into());
+        next.insert(
+            checked.clone(),
+            root.clone(),
+            content_hash("new"),
+            Vec::new(),
+            None,
+        );
+        next.carry_over(previous);
+
+        assert!(next
+            .get(&kept, &root, &content_hash("old"), today)
+            .is_some());
+        assert!(next
+            .get(&checked, &root, &content_hash("new"), today)
+            .is_some());
+        assert_eq!(
+            next.entries.len(),
+            2,
+            "entries for deleted files are dropped"
+        );
+    }
+
+    #[test]
+    fn test_get_honors_valid_until() {
+        let mut cache = AnalysisCache::new("rs".into());
+        let path = PathBuf::from("/p/src/lib.rs");
+        cache.insert(
+            path.clone(),
+            root(),
+            content_hash("a"),
+            Vec::new(),
+            Some(date("2025-06-01")),
+        );
+
+        let hash = content_hash("a");
+        assert!(cache
+            .get(&path, &root(), &hash, date("2025-05-31"))
+            .is_some());
+        assert!(cache
+            .get(&path, &root(), &hash, date("2025-06-01"))
+            .is_some());
+        assert!(cache
+            .get(&path, &root(), &hash, date("2025-06-02"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_ruleset_hash_changes_with_rules_and_config() {
+        let base = ruleset_hash([("AL001", "no-unwrap-expect")], "");
+        assert_eq!(base, ruleset_hash([("AL001", "no-unwrap-expect")], ""));
+        assert_ne!(base, ruleset_hash([("AL002", "no-sync-io")], ""));
+        assert_ne!(base, ruleset_hash([("AL001", "no-unwrap-expect")], "x"));
+    }
+
+    #[test]
+    fn test_roundtrip_and_ruleset_invalidation() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let file = dir.path().join("target/cache.json");
+        let path = PathBuf::from("/p/src/lib.rs");
+
+        let mut cache = AnalysisCache::new("v1".into());
+        cache.insert(
+            path.clone(),
+            root(),
+            content_hash("a"),
+            vec![sample_violation()],
+            Some(date("2025-06-01")),
+        );
+        cache.save(&file).expect("save cache");
+
+        let today = date("2025-05-01");
+        let loaded = AnalysisCache::load(&file, "v1");
+        assert!(loaded
+            .get(&path, &root(), &content_hash("a"), today)
+            .is_some());
+
+        let invalidated = AnalysisCache::load(&file, "v2");
+        assert!(invalidated
+            .get(&path, &root(), &content_hash("a"), today)
+            .is_none());
+    }
+}
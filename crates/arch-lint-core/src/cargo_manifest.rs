@@ -0,0 +1,251 @@
+//! Minimal `Cargo.toml` reader for project rules.
+//!
+//! Extracts the package name and dependency entries together with the line
+//! each dependency is declared on, so violations can point at the manifest.
+
+use std::path::{Path, PathBuf};
+
+/// Which dependency table an entry came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DependencyKind {
+    /// `[dependencies]` or `[target.'...'.dependencies]`.
+    Normal,
+    /// `[dev-dependencies]` or its target-specific form.
+    Dev,
+    /// `[build-dependencies]` or its target-specific form.
+    Build,
+}
+
+impl DependencyKind {
+    const ALL: [Self; 3] = [Self::Normal, Self::Dev, Self::Build];
+
+    fn table_name(self) -> &'static str {
+        match self {
+            Self::Normal => "dependencies",
+            Self::Dev => "dev-dependencies",
+            Self::Build => "build-dependencies",
+        }
+    }
+}
+
+/// A single dependency entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ManifestDependency {
+    /// Package name (honors `package = "..."` renames).
+    pub(crate) name: String,
+    /// Key as written in the manifest.
+    pub(crate) key: String,
+    /// Table the entry was declared in.
+    pub(crate) kind: DependencyKind,
+    /// 1-indexed line of the declaration (1 if it cannot be located).
+    pub(crate) line: usize,
+}
+
+/// Parsed view of a `Cargo.toml`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CargoManifest {
+    /// `[package] name`, absent for virtual workspace manifests.
+    pub(crate) package_name: Option<String>,
+    /// All dependency entries across normal, dev, build, and target tables.
+    pub(crate) dependencies: Vec<ManifestDependency>,
+    /// Explicit `[[bin]] path` entries, relative to the manifest directory.
+    pub(crate) bin_paths: Vec<PathBuf>,
+    /// Explicit `[[example]] path` entries, relative to the manifest directory.
+    pub(crate) example_paths: Vec<PathBuf>,
+    /// Build script path (`package.build`, defaulting to `build.rs`).
+    pub(crate) build_script: Option<PathBuf>,
+}
+
+impl CargoManifest {
+    /// Reads and parses a manifest file.
+    pub(crate) fn from_file(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        match Self::parse(&content) {
+            Ok(manifest) => Some(manifest),
+            Err(e) => {
+                tracing::warn!("Failed to parse {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Parses manifest content.
+    pub(crate) fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        let table: toml::Table = toml::from_str(content)?;
+
+        let package_name = table
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(toml::Value::as_str)
+            .map(String::from);
+
+        let mut dependencies = Vec::new();
+        let target_tables = table
+            .get("target")
+            .and_then(toml::Value::as_table)
+            .into_iter()
+            .flat_map(|targets| targets.values());
+
+        for scope in std::iter::once(&table).chain(target_tables.filter_map(toml::Value::as_table))
+        {
+            for kind in DependencyKind::ALL {
+                let Some(deps) = scope.get(kind.table_name()).and_then(toml::Value::as_table)
+                else {
+                    continue;
+                };
+                for (key, value) in deps {
+                    let name = value
+                        .get("package")
+                        .and_then(toml::Value::as_str)
+                        .unwrap_or(key)
+                        .to_string();
+                    dependencies.push(ManifestDependency {
+                        name,
+                        key: key.clone(),
+                        kind,
+                        line: find_dependency_line(content, kind, key).unwrap_or(1),
+                    });
+                }
+            }
+        }
+
+        let target_paths = |key: &str| -> Vec<PathBuf> {
+            table
+                .get(key)
+                .and_then(toml::Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|target| target.get("path").and_then(toml::Value::as_str))
+                .map(PathBuf::from)
+                .collect()
+        };
+        let build_script = match table.get("package").and_then(|p| p.get("build")) {
+            Some(toml::Value::String(path)) => Some(PathBuf::from(path)),
+            Some(toml::Value::Boolean(false)) => None,
+            _ => Some(PathBuf::from("build.rs")),
+        };
+
+        Ok(Self {
+            package_name,
+            dependencies,
+            bin_paths: target_paths("bin"),
+            example_paths: target_paths("example"),
+            build_script,
+        })
+    }
+}
+
+/// Locates the line declaring `key` in a dependency table of `kind`.
+///
+/// Handles `key = ...`, dotted `key.workspace = true`, and
+/// `[dependencies.key]` table headers.
+fn find_dependency_line(content: &str, kind: DependencyKind, key: &str) -> Option<usize> {
+    let table = kind.table_name();
+    let in_kind_table = |header: &str| {
+        header == table || (header.starts_with("target.") && header.ends_with(&format!(".{table}")))
+    };
+    let key_matches = |candidate: &str| candidate.trim().trim_matches('"') == key;
+
+    let mut current: Option<&str> = None;
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed
+            .strip_prefix('[')
+            .and_then(|h| h.split(']').next())
+            .filter(|_| !trimmed.starts_with("[["))
+        {
+            let header = header.trim();
+            if let Some((parent, child)) = header.rsplit_once('.') {
+                if in_kind_table(parent) && key_matches(child) {
+                    return Some(i + 1);
+                }
+            }
+            current = Some(header);
+            continue;
+        }
+
+        if current.is_some_and(in_kind_table) {
+            let entry_key = trimmed.split(['=', '.']).next().unwrap_or_default();
+            if !trimmed.starts_with('#') && key_matches(entry_key) {
+                return Some(i + 1);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "app-domain"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+db = { package = "app-infra", path = "../infra" }
+tokio.workspace = true
+
+[dependencies.sqlx]
+version = "0.7"
+
+[dev-dependencies]
+"tempfile" = "3"
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+"#;
+
+    fn dep<'a>(manifest: &'a CargoManifest, key: &str) -> &'a ManifestDependency {
+        manifest
+            .dependencies
+            .iter()
+            .find(|d| d.key == key)
+            .expect("dependency present")
+    }
+
+    #[test]
+    fn test_parses_package_and_dependencies() {
+        let manifest = CargoManifest::parse(MANIFEST).expect("parse");
+        assert_eq!(manifest.package_name.as_deref(), Some("app-domain"));
+        assert_eq!(manifest.dependencies.len(), 6);
+
+        let db = dep(&manifest, "db");
+        assert_eq!(db.name, "app-infra");
+        assert_eq!(db.kind, DependencyKind::Normal);
+        assert_eq!(dep(&manifest, "tempfile").kind, DependencyKind::Dev);
+        assert_eq!(dep(&manifest, "libc").kind, DependencyKind::Normal);
+    }
+
+    #[test]
+    fn test_locates_dependency_lines() {
+        let manifest = CargoManifest::parse(MANIFEST).expect("parse");
+        assert_eq!(dep(&manifest, "serde").line, 5);
+        assert_eq!(dep(&manifest, "db").line, 6);
+        assert_eq!(dep(&manifest, "tokio").line, 7);
+        assert_eq!(dep(&manifest, "sqlx").line, 9);
+        assert_eq!(dep(&manifest, "tempfile").line, 13);
+        assert_eq!(dep(&manifest, "libc").line, 16);
+    }
+
+    #[test]
+    fn test_parses_target_paths() {
+        let manifest = CargoManifest::parse(
+            "[package]\nname = \"tool\"\nbuild = \"codegen/build.rs\"\n\n[[bin]]\nname = \"tool\"\npath = \"cli/main.rs\"\n\n[[example]]\nname = \"demo\"\npath = \"demos/demo.rs\"\n",
+        )
+        .expect("parse");
+        assert_eq!(manifest.bin_paths, vec![PathBuf::from("cli/main.rs")]);
+        assert_eq!(manifest.example_paths, vec![PathBuf::from("demos/demo.rs")]);
+        assert_eq!(
+            manifest.build_script,
+            Some(PathBuf::from("codegen/build.rs"))
+        );
+    }
+
+    #[test]
+    fn test_virtual_manifest_has_no_package() {
+        let manifest = CargoManifest::parse("[workspace]\nmembers = [\"a\"]\n").expect("parse");
+        assert!(manifest.package_name.is_none());
+        assert!(manifest.dependencies.is_empty());
+    }
+}
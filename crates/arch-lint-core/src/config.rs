@@ -2,27 +2,56 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::types::{AnalysisSummary, LintResult, Severity};
+use crate::utils::CfgSet;
 
 /// Top-level configuration for arch-lint.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
-    /// Preset to use (e.g., "recommended", "strict", "minimal").
+    /// Preset to use (e.g., "recommended", "strict", "minimal"), or the
+    /// name of a custom preset from `presets`.
     #[serde(default)]
     pub preset: Option<String>,
 
+    /// Custom presets (`[presets.<name>]`), selectable with `preset`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub presets: HashMap<String, PresetConfig>,
+
     /// Severity threshold for test failure (default: "error").
     /// Violations at or above this severity cause `check!()` to fail.
     #[serde(default)]
     pub fail_on: Option<String>,
 
+    /// Treat warnings as errors for gating (`-D warnings`): lowers the
+    /// `fail_on` threshold to `warning` if it is stricter. Reported
+    /// severities are unchanged.
+    #[serde(default)]
+    pub deny_warnings: bool,
+
+    /// Per-severity violation count limits (`[gate]`), applied alongside
+    /// `fail_on`.
+    #[serde(default)]
+    pub gate: GateConfig,
+
     /// Analyzer configuration.
     #[serde(default)]
     pub analyzer: AnalyzerConfig,
 
+    /// Which source files are discovered (`[files]`).
+    #[serde(default)]
+    pub files: FilesConfig,
+
     /// Per-rule configurations.
     #[serde(default)]
     pub rules: HashMap<String, RuleConfig>,
+
+    /// Logging crates shared by rules that recognize logging calls
+    /// (`[logging]`). When absent, each rule keeps its own defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<LoggingConfig>,
 }
 
 impl Config {
@@ -32,17 +61,18 @@ impl Config {
         Self::default()
     }
 
-    /// Loads configuration from a TOML file.
+    /// Loads configuration from a file, in the [`ConfigFormat`] given by
+    /// its extension (TOML unless `.yaml`, `.yml` or `.json`).
     ///
     /// # Errors
     ///
     /// Returns an error if the file cannot be read or parsed.
-    pub fn from_file(path: &std::path::Path) -> Result<Self, ConfigError> {
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
         let content = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
             path: path.to_path_buf(),
             source: e,
         })?;
-        Self::parse(&content)
+        Self::parse_as(&content, ConfigFormat::from_path(path))
     }
 
     /// Parses configuration from a TOML string.
@@ -51,9 +81,110 @@ impl Config {
     ///
     /// Returns an error if the TOML is invalid.
     pub fn parse(content: &str) -> Result<Self, ConfigError> {
-        toml::from_str(content).map_err(|e| ConfigError::Parse {
-            message: e.to_string(),
-        })
+        Self::parse_as(content, ConfigFormat::Toml)
+    }
+
+    /// Parses configuration from a string in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content is invalid for `format`.
+    pub fn parse_as(content: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        let parsed = match format {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        };
+        let mut config: Self = parsed.map_err(|message| ConfigError::Parse { message })?;
+        config.expand_env()?;
+        config.files.validate()?;
+        config.analyzer.cfg_set()?;
+        Ok(config)
+    }
+
+    /// Expands `${VAR}`, `${VAR:-default}` and `$$` in every string value
+    /// against the process environment.
+    fn expand_env(&mut self) -> Result<(), ConfigError> {
+        fn expand_all(key: &str, values: &mut [String]) -> Result<(), ConfigError> {
+            for value in values {
+                *value = crate::interpolate::expand(key, value)?;
+            }
+            Ok(())
+        }
+
+        for (key, value) in [("preset", &mut self.preset), ("fail_on", &mut self.fail_on)] {
+            if let Some(value) = value {
+                *value = crate::interpolate::expand(key, value)?;
+            }
+        }
+        if let Some(logging) = &mut self.logging {
+            expand_all("logging.macros", &mut logging.macros)?;
+        }
+        expand_all("files.extensions", &mut self.files.extensions)?;
+        expand_all("files.extra", &mut self.files.extra)?;
+
+        let analyzer = &mut self.analyzer;
+        if let Some(root) = analyzer.root.to_str().filter(|root| root.contains('$')) {
+            analyzer.root = PathBuf::from(crate::interpolate::expand("analyzer.root", root)?);
+        }
+        expand_all("analyzer.exclude", &mut analyzer.exclude)?;
+        expand_all("analyzer.include", &mut analyzer.include)?;
+        expand_all("analyzer.cfg", &mut analyzer.cfg)?;
+
+        for (name, rule) in &mut self.rules {
+            if let Some(doc_ref) = &mut rule.doc_ref {
+                *doc_ref = crate::interpolate::expand(&format!("rules.{name}.doc_ref"), doc_ref)?;
+            }
+            if let Some(message) = &mut rule.message {
+                *message = crate::interpolate::expand(&format!("rules.{name}.message"), message)?;
+            }
+            for (key, value) in &mut rule.options {
+                crate::interpolate::expand_toml(&format!("rules.{name}.{key}"), value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the configuration a preset implies: `preset` set to `name`
+    /// and an explicit entry for each of its `rules`, enabled at the rule's
+    /// default severity.
+    ///
+    /// `arch-lint-rules` provides this for its built-in presets as
+    /// `Preset::default_config`.
+    #[must_use]
+    pub fn default_for_preset(name: &str, rules: &[crate::RuleBox]) -> Self {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let config = RuleConfig {
+                    enabled: Some(true),
+                    severity: Some(rule.default_severity()),
+                    ..RuleConfig::default()
+                };
+                (rule.name().to_string(), config)
+            })
+            .collect();
+        Self {
+            preset: Some(name.to_string()),
+            rules,
+            ..Self::default()
+        }
+    }
+
+    /// Fills in what this config leaves unset from `defaults`, such as
+    /// [`default_for_preset`](Self::default_for_preset): the preset, rule
+    /// entries, and `enabled`/`severity` within existing entries.
+    #[must_use]
+    pub fn with_defaults(mut self, defaults: &Self) -> Self {
+        if self.preset.is_none() {
+            self.preset.clone_from(&defaults.preset);
+        }
+        for (name, default) in &defaults.rules {
+            let entry = self.rules.entry(name.clone()).or_default();
+            entry.enabled = entry.enabled.or(default.enabled);
+            entry.severity = entry.severity.or(default.severity);
+        }
+        self
     }
 
     /// Checks if a rule is enabled.
@@ -69,6 +200,259 @@ impl Config {
     pub fn rule_severity(&self, rule_name: &str) -> Option<crate::Severity> {
         self.rules.get(rule_name).and_then(|c| c.severity)
     }
+
+    /// Gets the design document reference override for a rule.
+    #[must_use]
+    pub fn rule_doc_ref(&self, rule_name: &str) -> Option<&str> {
+        self.rules.get(rule_name).and_then(|c| c.doc_ref.as_deref())
+    }
+
+    /// Gets the message template for a rule.
+    #[must_use]
+    pub fn rule_message(&self, rule_name: &str) -> Option<&str> {
+        self.rules.get(rule_name).and_then(|c| c.message.as_deref())
+    }
+}
+
+/// Syntax of a configuration file.
+///
+/// TOML is the default and documented format; YAML and JSON files are
+/// deserialized into the same types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// TOML (`arch-lint.toml`).
+    #[default]
+    Toml,
+    /// YAML (`arch-lint.yaml`, `arch-lint.yml`).
+    Yaml,
+    /// JSON (`arch-lint.json`).
+    Json,
+}
+
+impl ConfigFormat {
+    /// Picks the format from the file extension; unknown extensions are TOML.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("json") => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+}
+
+/// A custom preset (`[presets.<name>]`).
+///
+/// Its rule set starts from `rules` if given, otherwise from the preset
+/// named by `extends`; then `enable` adds rules and `disable` removes them.
+/// Every list takes rule names or codes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PresetConfig {
+    /// Built-in or custom preset to start from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Exact rule list to start from, instead of `extends`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<String>>,
+    /// Rules added to the base set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enable: Vec<String>,
+    /// Rules removed from the base set, after `enable`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<String>,
+}
+
+/// The `[gate]` section: maximum violation counts per severity.
+///
+/// A run fails when any count exceeds its limit, independently of
+/// `fail_on`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GateConfig {
+    /// Maximum number of errors tolerated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_errors: Option<usize>,
+    /// Maximum number of warnings tolerated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_warnings: Option<usize>,
+    /// Maximum number of infos tolerated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_infos: Option<usize>,
+}
+
+impl GateConfig {
+    /// Returns the thresholds that `result` exceeds, most severe first.
+    #[must_use]
+    pub fn breaches(&self, result: &LintResult) -> Vec<ThresholdBreach> {
+        self.summary_breaches(&result.summary())
+    }
+
+    /// Returns the thresholds that the counts in `summary` exceed, most
+    /// severe first.
+    #[must_use]
+    pub fn summary_breaches(&self, summary: &AnalysisSummary) -> Vec<ThresholdBreach> {
+        let AnalysisSummary {
+            errors,
+            warnings,
+            infos,
+            ..
+        } = *summary;
+        [
+            (Severity::Error, errors, self.max_errors),
+            (Severity::Warning, warnings, self.max_warnings),
+            (Severity::Info, infos, self.max_infos),
+        ]
+        .into_iter()
+        .filter_map(|(severity, count, max)| {
+            let max = max?;
+            (count > max).then_some(ThresholdBreach {
+                severity,
+                count,
+                max,
+            })
+        })
+        .collect()
+    }
+}
+
+/// A `[gate]` limit exceeded by a lint result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdBreach {
+    /// Severity whose count exceeded the limit.
+    pub severity: Severity,
+    /// Number of violations at that severity.
+    pub count: usize,
+    /// The configured limit.
+    pub max: usize,
+}
+
+impl fmt::Display for ThresholdBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}(s) exceed max_{}s = {}",
+            self.count, self.severity, self.severity, self.max
+        )
+    }
+}
+
+/// The `[logging]` section: which crates count as logging.
+///
+/// Rules that recognize logging calls (e.g. `no-error-swallowing`,
+/// `require-tracing`) read it through
+/// [`Rule::configure_logging`](crate::Rule::configure_logging).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// Crates whose level macros (`error!`, `warn!`, `info!`, `debug!`,
+    /// `trace!`) are logging calls.
+    #[serde(default = "default_logging_macros")]
+    pub macros: Vec<String>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            macros: default_logging_macros(),
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// Level macro names shared by the common logging crates.
+    pub const LEVELS: [&'static str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+    /// Returns the full path of every level macro of every configured crate
+    /// (`log::error`, `log::warn`, ..., `tracing::trace`).
+    #[must_use]
+    pub fn level_macros(&self) -> Vec<String> {
+        self.macros
+            .iter()
+            .flat_map(|krate| {
+                Self::LEVELS
+                    .iter()
+                    .map(move |level| format!("{krate}::{level}"))
+            })
+            .collect()
+    }
+}
+
+fn default_logging_macros() -> Vec<String> {
+    vec!["log".to_string(), "tracing".to_string()]
+}
+
+/// The `[files]` section: which files the analyzer discovers.
+///
+/// By default every `*.rs` file under the roots is analyzed. `extensions`
+/// replaces the extension list (e.g. `["rs", "rs.in"]` for templates) and
+/// `extra` names further files by glob, relative to each root, such as
+/// generated sources. `extra` files are analyzed even when `[analyzer]`
+/// include/exclude patterns or ignore files would skip them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilesConfig {
+    /// File extensions to discover, without the leading dot.
+    #[serde(default = "default_extensions")]
+    pub extensions: Vec<String>,
+    /// Glob patterns of additional files to analyze.
+    #[serde(default)]
+    pub extra: Vec<String>,
+}
+
+impl Default for FilesConfig {
+    fn default() -> Self {
+        Self {
+            extensions: default_extensions(),
+            extra: Vec::new(),
+        }
+    }
+}
+
+impl FilesConfig {
+    /// Returns the discovery glob for each extension (`**/*.rs`, ...).
+    #[must_use]
+    pub fn extension_globs(&self) -> Vec<String> {
+        self.extensions
+            .iter()
+            .map(|ext| format!("**/*.{}", ext.trim_start_matches('.')))
+            .collect()
+    }
+
+    /// Returns `true` if `path` ends in one of the extensions.
+    #[must_use]
+    pub fn matches_extension(&self, path: &Path) -> bool {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        self.extensions
+            .iter()
+            .any(|ext| name.ends_with(&format!(".{}", ext.trim_start_matches('.'))))
+    }
+
+    /// Checks that every extension is a plain name and every `extra`
+    /// pattern is a valid glob.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidValue`] for the first bad entry.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for ext in &self.extensions {
+            let ext = ext.trim_start_matches('.');
+            if ext.is_empty() || ext.contains(['/', '\\', '*', '?', '[', '{']) {
+                return Err(ConfigError::InvalidValue {
+                    key: "files.extensions".to_string(),
+                    message: format!("`{ext}` is not a file extension"),
+                });
+            }
+        }
+        for pattern in &self.extra {
+            glob::Pattern::new(pattern).map_err(|e| ConfigError::InvalidValue {
+                key: "files.extra".to_string(),
+                message: format!("invalid glob `{pattern}`: {e}"),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn default_extensions() -> Vec<String> {
+    vec!["rs".to_string()]
 }
 
 /// Analyzer-level configuration.
@@ -90,9 +474,30 @@ pub struct AnalyzerConfig {
     #[serde(default = "default_true")]
     pub respect_gitignore: bool,
 
-    /// Maximum number of parallel file analyses.
+    /// Threads used to check files; defaults to `RAYON_NUM_THREADS`, else
+    /// one per CPU. Must be at least 1.
     #[serde(default)]
     pub parallelism: Option<usize>,
+
+    /// `cfg` options assumed enabled, as `name` or `key=value` (e.g.
+    /// `feature=serde`). When set, items compiled out under them are not
+    /// analyzed.
+    #[serde(default)]
+    pub cfg: Vec<String>,
+}
+
+impl AnalyzerConfig {
+    /// Parses [`cfg`](Self::cfg) into a [`CfgSet`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidValue`] for the first malformed option.
+    pub fn cfg_set(&self) -> Result<CfgSet, ConfigError> {
+        CfgSet::parse(&self.cfg).map_err(|option| ConfigError::InvalidValue {
+            key: "analyzer.cfg".to_string(),
+            message: format!("`{option}` is not a cfg option (expected `name` or `key=value`)"),
+        })
+    }
 }
 
 impl Default for AnalyzerConfig {
@@ -103,6 +508,7 @@ impl Default for AnalyzerConfig {
             include: Vec::new(),
             respect_gitignore: true,
             parallelism: None,
+            cfg: Vec::new(),
         }
     }
 }
@@ -126,6 +532,17 @@ pub struct RuleConfig {
     #[serde(default)]
     pub severity: Option<crate::Severity>,
 
+    /// Design document attached to every violation of this rule
+    /// (e.g. `"docs/adr/0007.md"`), overriding any the rule sets itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_ref: Option<String>,
+
+    /// Template replacing the message of every violation of this rule.
+    /// `{code}`, `{rule}`, `{doc_ref}` and `{message}` (the rule's own
+    /// message) are substituted; other text is kept as written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
     /// Rule-specific options as key-value pairs.
     #[serde(flatten)]
     pub options: HashMap<String, toml::Value>,
@@ -200,6 +617,35 @@ pub enum ConfigError {
         /// Parse error message.
         message: String,
     },
+
+    /// A `${VAR}` reference names an environment variable that is not set.
+    #[error("Environment variable `{name}` referenced in `{key}` is not set (use `${{{name}:-default}}` for a fallback)")]
+    UndefinedEnvVar {
+        /// Variable name.
+        name: String,
+        /// Dotted key whose value references it.
+        key: String,
+    },
+
+    /// Invalid value for a top-level setting.
+    #[error("Invalid value for `{key}`: {message}")]
+    InvalidValue {
+        /// Dotted key (e.g. `files.extra`).
+        key: String,
+        /// What was wrong with the value.
+        message: String,
+    },
+
+    /// Invalid value for a rule-specific option.
+    #[error("Invalid value for `{key}` in [rules.{rule}]: {message}")]
+    InvalidOption {
+        /// Rule name.
+        rule: String,
+        /// Option key.
+        key: String,
+        /// What was wrong with the value.
+        message: String,
+    },
 }
 
 #[cfg(test)]
@@ -233,4 +679,265 @@ allow_in_tests = true
         let rule_config = config.rules.get("no-unwrap-expect").unwrap();
         assert!(rule_config.get_bool("allow_in_tests", false));
     }
+
+    #[test]
+    fn test_parse_logging_section() {
+        assert!(Config::default().logging.is_none());
+
+        let config = Config::parse("[logging]\nmacros = [\"slog\"]\n").expect("Failed to parse");
+        let logging = config.logging.expect("[logging] section");
+        assert_eq!(logging.level_macros()[0], "slog::error");
+        assert_eq!(logging.level_macros().len(), 5);
+
+        let config = Config::parse("[logging]\n").expect("Failed to parse");
+        assert_eq!(config.logging, Some(LoggingConfig::default()));
+    }
+
+    #[test]
+    fn test_gate_breaches_at_above_and_below_thresholds() {
+        let config = Config::parse("[gate]\nmax_errors = 0\nmax_warnings = 2\n").expect("parse");
+        assert_eq!(config.gate.max_infos, None);
+
+        let result = |severities: &[Severity]| LintResult {
+            violations: severities
+                .iter()
+                .map(|&severity| {
+                    crate::Violation::new(
+                        "AL001",
+                        "rule",
+                        severity,
+                        crate::Location::new(PathBuf::from("a.rs"), 1, 1),
+                        "msg",
+                    )
+                })
+                .collect(),
+            files_checked: 1,
+            files_skipped: 0,
+        };
+
+        // Below and at the limits.
+        assert!(config
+            .gate
+            .breaches(&result(&[Severity::Warning]))
+            .is_empty());
+        assert!(config
+            .gate
+            .breaches(&result(&[
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Info
+            ]))
+            .is_empty());
+
+        // Above: one error exceeds max_errors = 0, three warnings exceed 2.
+        let breaches = config.gate.breaches(&result(&[
+            Severity::Warning,
+            Severity::Error,
+            Severity::Warning,
+            Severity::Warning,
+        ]));
+        assert_eq!(
+            breaches.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "1 error(s) exceed max_errors = 0",
+                "3 warning(s) exceed max_warnings = 2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_config_in_toml_yaml_and_json() {
+        let toml = r#"
+preset = "strict"
+fail_on = "warning"
+
+[gate]
+max_warnings = 3
+
+[analyzer]
+exclude = ["**/generated/**"]
+
+[rules.no-unwrap-expect]
+severity = "warning"
+allow_in_tests = true
+
+[logging]
+macros = ["log", "slog"]
+"#;
+        let yaml = r#"
+preset: strict
+fail_on: warning
+gate:
+  max_warnings: 3
+analyzer:
+  exclude: ["**/generated/**"]
+rules:
+  no-unwrap-expect:
+    severity: warning
+    allow_in_tests: true
+logging:
+  macros: [log, slog]
+"#;
+        let json = r#"{
+  "preset": "strict",
+  "fail_on": "warning",
+  "gate": { "max_warnings": 3 },
+  "analyzer": { "exclude": ["**/generated/**"] },
+  "rules": { "no-unwrap-expect": { "severity": "warning", "allow_in_tests": true } },
+  "logging": { "macros": ["log", "slog"] }
+}"#;
+
+        let parsed: Vec<serde_json::Value> = [
+            (toml, ConfigFormat::Toml),
+            (yaml, ConfigFormat::Yaml),
+            (json, ConfigFormat::Json),
+        ]
+        .into_iter()
+        .map(|(content, format)| {
+            let config = Config::parse_as(content, format).unwrap();
+            serde_json::to_value(config).unwrap()
+        })
+        .collect();
+
+        assert_eq!(parsed[0], parsed[1]);
+        assert_eq!(parsed[0], parsed[2]);
+        let config = Config::parse_as(yaml, ConfigFormat::Yaml).unwrap();
+        assert_eq!(config.gate.max_warnings, Some(3));
+        assert!(config.rules["no-unwrap-expect"].get_bool("allow_in_tests", false));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("arch-lint.yml")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("arch-lint.json")),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new(".arch-lint.toml")),
+            ConfigFormat::Toml
+        );
+        assert!(Config::parse_as("preset: [", ConfigFormat::Yaml).is_err());
+    }
+
+    #[test]
+    fn test_files_section_defaults_and_validation() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.files, FilesConfig::default());
+        assert_eq!(config.files.extension_globs(), ["**/*.rs"]);
+        assert!(config.files.matches_extension(Path::new("src/lib.rs")));
+        assert!(!config.files.matches_extension(Path::new("README.md")));
+
+        let config = Config::parse("[files]\nextra = [\"generated/**/*.rs\"]\n").unwrap();
+        assert_eq!(config.files.extensions, ["rs"]);
+        assert_eq!(config.files.extra, ["generated/**/*.rs"]);
+
+        let err = Config::parse("[files]\nextra = [\"gen/[*.rs\"]\n").unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "files.extra"));
+        assert!(err.to_string().contains("gen/[*.rs"), "{err}");
+
+        let err = Config::parse("[files]\nextensions = [\"*.rs\"]\n").unwrap_err();
+        assert!(err.to_string().contains("files.extensions"), "{err}");
+    }
+
+    #[test]
+    fn test_analyzer_cfg_options() {
+        let config = Config::parse("[analyzer]\ncfg = [\"feature=serde\", \"unix\"]\n").unwrap();
+        assert_eq!(config.analyzer.cfg, ["feature=serde", "unix"]);
+        assert!(!config.analyzer.cfg_set().unwrap().is_empty());
+
+        let err = Config::parse("[analyzer]\ncfg = [\"feature serde\"]\n").unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref key, .. } if key == "analyzer.cfg"));
+    }
+
+    #[test]
+    fn test_env_interpolation() {
+        std::env::set_var("ARCH_LINT_TEST_DOCS_BASE", "https://docs.example.com");
+        let config = Config::parse(
+            r#"
+[analyzer]
+exclude = ["${ARCH_LINT_TEST_DOCS_BASE_UNSET:-target}/**", "price$$"]
+
+[rules.no-unwrap-expect]
+doc_ref = "${ARCH_LINT_TEST_DOCS_BASE}/adr/0007.md"
+message = "see ${ARCH_LINT_TEST_DOCS_BASE}"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.analyzer.exclude, ["target/**", "price$"]);
+        let rule = &config.rules["no-unwrap-expect"];
+        assert_eq!(
+            rule.doc_ref.as_deref(),
+            Some("https://docs.example.com/adr/0007.md")
+        );
+        assert_eq!(
+            rule.message.as_deref(),
+            Some("see https://docs.example.com")
+        );
+    }
+
+    #[test]
+    fn test_env_interpolation_unset_variable() {
+        let err = Config::parse(
+            "[rules.no-unwrap-expect]\ndoc_ref = \"${ARCH_LINT_TEST_DOCS_BASE_UNSET}/adr.md\"\n",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                ConfigError::UndefinedEnvVar { name, key }
+                    if name == "ARCH_LINT_TEST_DOCS_BASE_UNSET" && key == "rules.no-unwrap-expect.doc_ref"
+            ),
+            "{err}"
+        );
+    }
+
+    #[derive(Debug)]
+    struct Stub(&'static str, Severity);
+
+    impl crate::Rule for Stub {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn code(&self) -> &'static str {
+            "AL999"
+        }
+
+        fn default_severity(&self) -> Severity {
+            self.1
+        }
+
+        fn check(&self, _: &crate::FileContext, _: &syn::File) -> Vec<crate::Violation> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_default_for_preset_and_with_defaults() {
+        let rules: Vec<crate::RuleBox> = vec![
+            Box::new(Stub("a", Severity::Error)),
+            Box::new(Stub("b", Severity::Warning)),
+        ];
+        let defaults = Config::default_for_preset("strict", &rules);
+        assert_eq!(defaults.preset.as_deref(), Some("strict"));
+        assert_eq!(defaults.rules.len(), 2);
+        assert_eq!(defaults.rule_severity("b"), Some(Severity::Warning));
+        assert_eq!(defaults.rules["a"].enabled, Some(true));
+
+        let config = Config::parse(
+            "[rules.a]\nenabled = false\n\n[rules.b]\nseverity = \"info\"\nmax = 3\n",
+        )
+        .unwrap()
+        .with_defaults(&defaults);
+        assert_eq!(config.preset.as_deref(), Some("strict"));
+        assert!(!config.is_rule_enabled("a"));
+        assert_eq!(config.rule_severity("a"), Some(Severity::Error));
+        assert!(config.is_rule_enabled("b"));
+        assert_eq!(config.rule_severity("b"), Some(Severity::Info));
+        assert_eq!(config.rules["b"].get_option::<i64>("max"), Some(3));
+    }
 }
@@ -1,12 +1,22 @@
 //! Context types for rule execution.
 
-use std::path::{Path, PathBuf};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use crate::cargo_manifest::CargoManifest;
+use crate::utils::allowance::{AllowCheck, BlockAllowances};
 
 /// Context provided to per-file rules.
 ///
 /// Contains metadata about the file being analyzed that rules can use
 /// to make context-aware decisions (e.g., skip checks in test files).
+/// Construct it with [`new`](Self::new) or [`with_target`](Self::with_target).
 #[derive(Debug, Clone)]
+#[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
 pub struct FileContext<'a> {
     /// Absolute path to the file.
     pub path: &'a Path,
@@ -18,12 +28,32 @@ pub struct FileContext<'a> {
     pub module_path: Vec<String>,
     /// Path relative to the project root.
     pub relative_path: PathBuf,
+    /// Whether this file is a binary target (`src/main.rs`, `src/bin/`, or a `[[bin]]`).
+    pub is_binary: bool,
+    /// Whether this file is an example (`examples/` or an `[[example]]`).
+    pub is_example: bool,
+    /// Whether this file is the crate's build script (`build.rs` or `package.build`).
+    pub is_build_script: bool,
+    /// Allowance directives, scanned on first use.
+    allowances: OnceLock<BlockAllowances>,
 }
 
 impl<'a> FileContext<'a> {
     /// Creates a new file context.
+    ///
+    /// The Cargo target is inferred from the default layout of the path
+    /// relative to `root`; no manifest is read. Use
+    /// [`with_target`](Self::with_target) when the target is known.
     #[must_use]
     pub fn new(path: &'a Path, content: &'a str, root: &Path) -> Self {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let target = TargetKind::from_layout(relative, Some(Path::new("build.rs")));
+        Self::with_target(path, content, root, target)
+    }
+
+    /// Creates a new file context for a file of the given Cargo target.
+    #[must_use]
+    pub fn with_target(path: &'a Path, content: &'a str, root: &Path, target: TargetKind) -> Self {
         let is_test = Self::detect_test_file(path);
         let relative_path = path
             .strip_prefix(root)
@@ -36,6 +66,10 @@ impl<'a> FileContext<'a> {
             is_test,
             module_path,
             relative_path,
+            is_binary: target == TargetKind::Binary,
+            is_example: target == TargetKind::Example,
+            is_build_script: target == TargetKind::BuildScript,
+            allowances: OnceLock::new(),
         }
     }
 
@@ -43,7 +77,7 @@ impl<'a> FileContext<'a> {
     fn detect_test_file(path: &Path) -> bool {
         // Check path components for test directories
         for component in path.components() {
-            if let std::path::Component::Normal(s) = component {
+            if let Component::Normal(s) = component {
                 let s = s.to_string_lossy();
                 if s == "tests" || s == "test" || s == "benches" {
                     return true;
@@ -71,7 +105,7 @@ impl<'a> FileContext<'a> {
             .with_extension("")
             .components()
             .filter_map(|c| {
-                if let std::path::Component::Normal(s) = c {
+                if let Component::Normal(s) = c {
                     s.to_str().map(String::from)
                 } else {
                     None
@@ -94,6 +128,28 @@ impl<'a> FileContext<'a> {
         parts
     }
 
+    /// Returns the file's allowance directives, scanning it on first use.
+    #[must_use]
+    pub fn allowances(&self) -> &BlockAllowances {
+        self.allowances
+            .get_or_init(|| BlockAllowances::scan(self.content))
+    }
+
+    /// Checks for an allow directive covering `line` (1-indexed) for a rule
+    /// named by name or code.
+    ///
+    /// Same as [`check_allow_for_rule`](crate::utils::check_allow_for_rule),
+    /// but the file is scanned once and reused by every check.
+    #[must_use]
+    pub fn check_allow_for_rule(
+        &self,
+        line: usize,
+        rule_name: &str,
+        rule_code: &str,
+    ) -> AllowCheck {
+        self.allowances().check_for_rule(line, rule_name, rule_code)
+    }
+
     /// Calculates byte offset for a given line and column.
     ///
     /// # Arguments
@@ -122,6 +178,142 @@ impl<'a> FileContext<'a> {
     }
 }
 
+/// Cargo target a source file belongs to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TargetKind {
+    /// Library code, or any file not matched by another target.
+    #[default]
+    Library,
+    /// A binary target (`src/main.rs`, `src/bin/`, or a `[[bin]]`).
+    Binary,
+    /// An example (`examples/` or an `[[example]]`).
+    Example,
+    /// The crate's build script (`build.rs` or `package.build`).
+    BuildScript,
+}
+
+impl TargetKind {
+    /// Classifies `path` using its owning crate directory and manifest,
+    /// falling back to the layout relative to `root` without one.
+    fn detect(path: &Path, root: &Path, owner: Option<(&Path, &CargoManifest)>) -> Self {
+        let Some((crate_dir, manifest)) = owner else {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            return Self::from_layout(relative, Some(Path::new("build.rs")));
+        };
+        let Ok(relative) = path.strip_prefix(crate_dir) else {
+            return Self::Library;
+        };
+        let is_listed = |paths: &[PathBuf]| paths.iter().any(|p| clean(p) == relative);
+
+        if is_listed(&manifest.bin_paths) {
+            Self::Binary
+        } else if is_listed(&manifest.example_paths) {
+            Self::Example
+        } else {
+            Self::from_layout(relative, manifest.build_script.as_deref())
+        }
+    }
+
+    /// Applies Cargo's target auto-discovery conventions to a path
+    /// relative to the crate directory.
+    fn from_layout(relative: &Path, build_script: Option<&Path>) -> Self {
+        if build_script.is_some_and(|build| clean(build) == relative) {
+            return Self::BuildScript;
+        }
+
+        let parts: Vec<_> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(s) => s.to_str(),
+                _ => None,
+            })
+            .collect();
+        match parts.as_slice() {
+            ["src", "main.rs"] | ["src", "bin", ..] => Self::Binary,
+            ["examples", ..] => Self::Example,
+            _ => Self::Library,
+        }
+    }
+}
+
+/// Drops `.` components so `./src/main.rs` compares equal to `src/main.rs`.
+fn clean(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+/// Resolves the [`TargetKind`] of source files, reading and parsing each
+/// directory's `Cargo.toml` at most once.
+#[derive(Debug, Default)]
+pub(crate) struct TargetResolver {
+    /// Manifest per directory checked; `None` if absent or invalid.
+    manifests: Mutex<HashMap<PathBuf, Option<CargoManifest>>>,
+}
+
+impl TargetResolver {
+    /// Classifies `path` using the owning `Cargo.toml` (the nearest one at
+    /// or below `root`) and Cargo's default target layout.
+    pub(crate) fn resolve(&self, path: &Path, root: &Path) -> TargetKind {
+        let mut manifests = self
+            .manifests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let crate_dir = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(root))
+            .find(|dir| {
+                manifests
+                    .entry(dir.to_path_buf())
+                    .or_insert_with(|| CargoManifest::from_file(&dir.join("Cargo.toml")))
+                    .is_some()
+            });
+        let owner = crate_dir.and_then(|dir| Some((dir, manifests.get(dir)?.as_ref()?)));
+        TargetKind::detect(path, root, owner)
+    }
+}
+
+/// Context provided to [`Rule::check_cargo_toml`](crate::Rule::check_cargo_toml).
+#[derive(Debug, Clone)]
+pub struct CargoTomlContext<'a> {
+    /// Absolute path to the manifest.
+    pub path: &'a Path,
+    /// Manifest contents as a string.
+    pub content: &'a str,
+    /// Path relative to the project root.
+    pub relative_path: PathBuf,
+}
+
+impl<'a> CargoTomlContext<'a> {
+    /// Creates a new manifest context.
+    #[must_use]
+    pub fn new(path: &'a Path, content: &'a str, root: &Path) -> Self {
+        let relative_path = path
+            .strip_prefix(root)
+            .map_or_else(|_| path.to_path_buf(), Path::to_path_buf);
+        Self {
+            path,
+            content,
+            relative_path,
+        }
+    }
+
+    /// Returns the 1-indexed line declaring dependency `key`, if any.
+    ///
+    /// Looks through `[dependencies]`, `[dev-dependencies]`,
+    /// `[build-dependencies]`, and their target-specific forms.
+    #[must_use]
+    pub fn dependency_line(&self, key: &str) -> Option<usize> {
+        CargoManifest::parse(self.content)
+            .ok()?
+            .dependencies
+            .into_iter()
+            .find(|dep| dep.key == key)
+            .map(|dep| dep.line)
+    }
+}
+
 /// Context provided to project-wide rules.
 ///
 /// Contains information about the project being analyzed.
@@ -133,6 +325,10 @@ pub struct ProjectContext<'a> {
     pub source_files: Vec<PathBuf>,
     /// List of Cargo.toml files found.
     pub cargo_files: Vec<PathBuf>,
+    /// Parsed ASTs keyed by path, filled by the analyzer and on demand.
+    asts: RefCell<HashMap<PathBuf, Rc<syn::File>>>,
+    /// Whether ASTs parsed on demand are kept for later calls.
+    cache_asts: bool,
 }
 
 impl<'a> ProjectContext<'a> {
@@ -143,6 +339,8 @@ impl<'a> ProjectContext<'a> {
             root,
             source_files: Vec::new(),
             cargo_files: Vec::new(),
+            asts: RefCell::new(HashMap::new()),
+            cache_asts: true,
         }
     }
 
@@ -159,6 +357,53 @@ impl<'a> ProjectContext<'a> {
         self.cargo_files = files;
         self
     }
+
+    /// Seeds the AST cache with files already parsed by per-file analysis.
+    #[must_use]
+    pub fn with_asts(self, asts: impl IntoIterator<Item = (PathBuf, syn::File)>) -> Self {
+        self.asts
+            .borrow_mut()
+            .extend(asts.into_iter().map(|(path, ast)| (path, Rc::new(ast))));
+        self
+    }
+
+    /// Sets whether ASTs parsed by [`ast`](Self::ast) are cached (default: true).
+    ///
+    /// Every cached AST stays in memory until the context is dropped, which
+    /// for large projects can be several times the size of the sources.
+    /// When disabled, each call re-parses the file.
+    #[must_use]
+    pub fn with_ast_cache(mut self, enabled: bool) -> Self {
+        self.cache_asts = enabled;
+        self
+    }
+
+    /// Returns the parsed AST of a source file, parsing it on first use.
+    ///
+    /// Returns `None` if the file cannot be read or is not valid Rust.
+    #[must_use]
+    pub fn ast(&self, path: &Path) -> Option<Rc<syn::File>> {
+        if let Some(ast) = self.asts.borrow().get(path) {
+            return Some(Rc::clone(ast));
+        }
+
+        let content = std::fs::read_to_string(path).ok()?;
+        let ast = Rc::new(syn::parse_file(&content).ok()?);
+        if self.cache_asts {
+            self.asts
+                .borrow_mut()
+                .insert(path.to_path_buf(), Rc::clone(&ast));
+        }
+        Some(ast)
+    }
+
+    /// Iterates over the parsed ASTs of all source files, skipping files
+    /// that fail to parse.
+    pub fn asts(&self) -> impl Iterator<Item = (&Path, Rc<syn::File>)> + '_ {
+        self.source_files
+            .iter()
+            .filter_map(|path| Some((path.as_path(), self.ast(path)?)))
+    }
 }
 
 #[cfg(test)]
@@ -189,16 +434,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_target_kind_from_layout() {
+        let build = Some(Path::new("build.rs"));
+        let kind = |p: &str| TargetKind::from_layout(Path::new(p), build);
+
+        assert_eq!(kind("src/main.rs"), TargetKind::Binary);
+        assert_eq!(kind("src/bin/tool/main.rs"), TargetKind::Binary);
+        assert_eq!(kind("examples/demo.rs"), TargetKind::Example);
+        assert_eq!(kind("build.rs"), TargetKind::BuildScript);
+        assert_eq!(kind("src/lib.rs"), TargetKind::Library);
+        assert_eq!(kind("src/build.rs"), TargetKind::Library);
+        assert_eq!(
+            TargetKind::from_layout(Path::new("build.rs"), None),
+            TargetKind::Library
+        );
+    }
+
+    #[test]
+    fn test_resolver_uses_owning_manifest_targets() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let crate_dir = dir.path().join("crates/tool");
+        std::fs::create_dir_all(&crate_dir).expect("create crate dir");
+        let manifest = crate_dir.join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"tool\"\n\n[[bin]]\nname = \"tool\"\npath = \"cli/main.rs\"\n",
+        )
+        .expect("write manifest");
+
+        let resolver = TargetResolver::default();
+        let kind = |rel: &str| resolver.resolve(&crate_dir.join(rel), dir.path());
+        assert_eq!(kind("cli/main.rs"), TargetKind::Binary);
+        assert_eq!(kind("src/main.rs"), TargetKind::Binary);
+        assert_eq!(kind("build.rs"), TargetKind::BuildScript);
+        assert_eq!(kind("src/lib.rs"), TargetKind::Library);
+
+        // Manifests are read once per directory.
+        std::fs::write(&manifest, "[package]\nname = \"tool\"\n").expect("rewrite manifest");
+        assert_eq!(kind("cli/main.rs"), TargetKind::Binary);
+        assert_eq!(
+            TargetResolver::default().resolve(&crate_dir.join("cli/main.rs"), dir.path()),
+            TargetKind::Library
+        );
+    }
+
+    #[test]
+    fn test_new_and_with_target() {
+        let root = Path::new("/project");
+        let main = Path::new("/project/src/main.rs");
+        assert!(FileContext::new(main, "", root).is_binary);
+
+        let ctx = FileContext::with_target(main, "", root, TargetKind::Example);
+        assert!(ctx.is_example && !ctx.is_binary && !ctx.is_build_script);
+        assert_eq!(ctx.relative_path, PathBuf::from("src/main.rs"));
+    }
+
+    #[test]
+    fn test_project_ast_cache() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let a = dir.path().join("a.rs");
+        let b = dir.path().join("b.rs");
+        std::fs::write(&a, "struct A;").expect("write a");
+        std::fs::write(&b, "not rust").expect("write b");
+
+        let seeded: syn::File = syn::parse_quote!(
+            struct Seeded;
+        );
+        let ctx = ProjectContext::new(dir.path())
+            .with_source_files(vec![a.clone(), b.clone()])
+            .with_asts([(a.clone(), seeded)]);
+
+        // Seeded ASTs win over the file on disk.
+        let ast = ctx.ast(&a).expect("ast for a");
+        assert!(matches!(&ast.items[0], syn::Item::Struct(s) if s.ident == "Seeded"));
+        assert!(ctx.ast(&b).is_none());
+        assert_eq!(ctx.asts().count(), 1);
+    }
+
+    #[test]
+    fn test_project_ast_cache_disabled() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let a = dir.path().join("a.rs");
+        std::fs::write(&a, "struct A;").expect("write a");
+
+        let ctx = ProjectContext::new(dir.path()).with_ast_cache(false);
+        let first = ctx.ast(&a).expect("ast");
+        let second = ctx.ast(&a).expect("ast");
+        assert!(!Rc::ptr_eq(&first, &second));
+
+        let ctx = ProjectContext::new(dir.path());
+        let first = ctx.ast(&a).expect("ast");
+        assert!(Rc::ptr_eq(&first, &ctx.ast(&a).expect("ast")));
+    }
+
     #[test]
     fn test_offset_calculation() {
         let content = "line1\nline2\nline3";
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content,
-            is_test: false,
-            module_path: vec![],
-            relative_path: PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), content, Path::new(""));
 
         assert_eq!(ctx.offset_for(1, 1), 0); // Start of line 1
         assert_eq!(ctx.offset_for(2, 1), 6); // Start of line 2
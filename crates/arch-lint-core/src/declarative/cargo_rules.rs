@@ -0,0 +1,340 @@
+//! Declarative project rules that inspect `Cargo.toml` manifests.
+//!
+//! These complement the file-scope rules in [`super::rules`] with
+//! crate-level architecture enforcement.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::cargo_manifest::{CargoManifest, DependencyKind};
+use crate::context::ProjectContext;
+use crate::declarative::model::DeclarativeConfig;
+use crate::rule::ProjectRule;
+use crate::types::{Location, Violation};
+
+// ────────────────────────────────────────────
+// CrateLayerRule
+// ────────────────────────────────────────────
+
+const CRATE_LAYERS_NAME: &str = "crate-layers";
+const CRATE_LAYERS_CODE: &str = "ALD004";
+
+/// A project rule that enforces `[[crate-layers]]` ordering.
+///
+/// Builds the inter-crate dependency graph from each manifest's
+/// `[dependencies]` (including target-specific tables) and reports edges
+/// from an inner layer to an outer one. Dev- and build-dependencies are
+/// not checked.
+#[derive(Debug)]
+pub struct CrateLayerRule {
+    config: Arc<DeclarativeConfig>,
+}
+
+impl CrateLayerRule {
+    /// Creates a new crate-layers rule backed by the given config.
+    #[must_use]
+    pub fn new(config: Arc<DeclarativeConfig>) -> Self {
+        Self { config }
+    }
+}
+
+impl ProjectRule for CrateLayerRule {
+    fn name(&self) -> &'static str {
+        CRATE_LAYERS_NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CRATE_LAYERS_CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Enforce crate dependency direction between layers"
+    }
+
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Violation> {
+        let layers = self.config.crate_layers();
+        let mut violations = Vec::new();
+
+        for cargo_file in &ctx.cargo_files {
+            let Some(manifest) = CargoManifest::from_file(cargo_file) else {
+                continue;
+            };
+            let Some(package) = manifest.package_name.as_deref() else {
+                continue;
+            };
+            let Some(from_index) = self.config.crate_layer_index(package) else {
+                continue;
+            };
+            let from_layer = &layers[from_index];
+
+            for dep in &manifest.dependencies {
+                if dep.kind != DependencyKind::Normal {
+                    continue;
+                }
+                let Some(to_index) = self.config.crate_layer_index(&dep.name) else {
+                    continue;
+                };
+                if to_index <= from_index {
+                    continue;
+                }
+                let to_layer = &layers[to_index];
+
+                let mut violation = Violation::new(
+                    CRATE_LAYERS_CODE,
+                    CRATE_LAYERS_NAME,
+                    from_layer.severity(),
+                    Location::new(cargo_file.clone(), dep.line, 1),
+                    format!(
+                        "Crate `{package}` (layer `{}`) must not depend on `{}` (layer `{}`)",
+                        from_layer.name(),
+                        dep.name,
+                        to_layer.name()
+                    ),
+                );
+                if let Some(doc) = from_layer.doc_ref() {
+                    violation = violation.with_doc_ref(doc);
+                }
+                violations.push(violation);
+            }
+        }
+
+        violations
+    }
+}
+
+// ────────────────────────────────────────────
+// ForbidCrateDepRule
+// ────────────────────────────────────────────
+
+const FORBID_CRATE_DEP_NAME: &str = "forbid-crate-dep";
+const FORBID_CRATE_DEP_CODE: &str = "ALD005";
+
+/// A project rule that enforces `[[forbid-crate-dep]]` bans.
+///
+/// Only `[dependencies]` (including target-specific tables) are checked,
+/// so a denied crate is reported as soon as it is declared, whether or not
+/// any code uses it yet.
+#[derive(Debug)]
+pub struct ForbidCrateDepRule {
+    config: Arc<DeclarativeConfig>,
+}
+
+impl ForbidCrateDepRule {
+    /// Creates a new forbid-crate-dep rule backed by the given config.
+    #[must_use]
+    pub fn new(config: Arc<DeclarativeConfig>) -> Self {
+        Self { config }
+    }
+}
+
+impl ProjectRule for ForbidCrateDepRule {
+    fn name(&self) -> &'static str {
+        FORBID_CRATE_DEP_NAME
+    }
+
+    fn code(&self) -> &'static str {
+        FORBID_CRATE_DEP_CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbid specific crates as dependencies of selected crates"
+    }
+
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for cargo_file in &ctx.cargo_files {
+            let Some(manifest) = CargoManifest::from_file(cargo_file) else {
+                continue;
+            };
+            let Some(package) = manifest.package_name.as_deref() else {
+                continue;
+            };
+            let manifest_dir = cargo_file.parent().map_or(Path::new(""), |dir| {
+                dir.strip_prefix(ctx.root).unwrap_or(dir)
+            });
+
+            for rule in self.config.forbid_crate_deps() {
+                if !rule.applies_to(package, manifest_dir) {
+                    continue;
+                }
+                for dep in &manifest.dependencies {
+                    if dep.kind != DependencyKind::Normal || !rule.is_denied(&dep.name) {
+                        continue;
+                    }
+
+                    let mut violation = Violation::new(
+                        FORBID_CRATE_DEP_CODE,
+                        FORBID_CRATE_DEP_NAME,
+                        rule.severity(),
+                        Location::new(cargo_file.clone(), dep.line, 1),
+                        format!(
+                            "[{}] Crate `{package}` must not depend on `{}`: {}",
+                            rule.name(),
+                            dep.name,
+                            rule.message()
+                        ),
+                    );
+                    if let Some(doc) = rule.doc_ref() {
+                        violation = violation.with_doc_ref(doc);
+                    }
+                    violations.push(violation);
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_crate(root: &Path, dir: &str, manifest: &str) -> std::path::PathBuf {
+        let path = root.join(dir).join("Cargo.toml");
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        std::fs::write(&path, manifest).expect("write manifest");
+        path
+    }
+
+    fn rule(toml_str: &str) -> CrateLayerRule {
+        let dto = toml::from_str(toml_str).expect("parse dto");
+        let config = crate::declarative::loader::load(dto).expect("load config");
+        CrateLayerRule::new(Arc::new(config))
+    }
+
+    const LAYERS: &str = r#"
+[[crate-layers]]
+name = "domain"
+crates = ["app-domain"]
+doc = "ARCHITECTURE.md#layers"
+
+[[crate-layers]]
+name = "infra"
+crates = ["app-infra"]
+"#;
+
+    #[test]
+    fn reports_inner_to_outer_dependency() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let domain = write_crate(
+            dir.path(),
+            "domain",
+            "[package]\nname = \"app-domain\"\n\n[dependencies]\nserde = \"1\"\napp-infra = { path = \"../infra\" }\n",
+        );
+        let infra = write_crate(
+            dir.path(),
+            "infra",
+            "[package]\nname = \"app-infra\"\n\n[dependencies]\napp-domain = { path = \"../domain\" }\n",
+        );
+
+        let ctx = ProjectContext::new(dir.path()).with_cargo_files(vec![domain.clone(), infra]);
+        let violations = rule(LAYERS).check_project(&ctx);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, CRATE_LAYERS_CODE);
+        assert_eq!(violations[0].location.file, domain);
+        assert_eq!(violations[0].location.line, 6);
+        assert!(violations[0]
+            .message
+            .contains("`app-infra` (layer `infra`)"));
+        assert_eq!(
+            violations[0].doc_ref.as_deref(),
+            Some("ARCHITECTURE.md#layers")
+        );
+    }
+
+    #[test]
+    fn ignores_dev_dependencies_and_unlayered_crates() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let domain = write_crate(
+            dir.path(),
+            "domain",
+            "[package]\nname = \"app-domain\"\n\n[dev-dependencies]\napp-infra = { path = \"../infra\" }\n",
+        );
+        let tool = write_crate(
+            dir.path(),
+            "tool",
+            "[package]\nname = \"tool\"\n\n[dependencies]\napp-infra = { path = \"../infra\" }\n",
+        );
+
+        let ctx = ProjectContext::new(dir.path()).with_cargo_files(vec![domain, tool]);
+        assert!(rule(LAYERS).check_project(&ctx).is_empty());
+    }
+
+    #[test]
+    fn follows_package_renames() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let domain = write_crate(
+            dir.path(),
+            "domain",
+            "[package]\nname = \"app-domain\"\n\n[dependencies.infra]\npackage = \"app-infra\"\npath = \"../infra\"\n",
+        );
+
+        let ctx = ProjectContext::new(dir.path()).with_cargo_files(vec![domain]);
+        let violations = rule(LAYERS).check_project(&ctx);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location.line, 4);
+    }
+
+    const FORBID: &str = r#"
+[[forbid-crate-dep]]
+name = "no-sqlx-in-domain"
+crates = ["*-domain"]
+paths = ["crates/core"]
+deny = ["sqlx"]
+message = "Domain crates must stay persistence-agnostic."
+"#;
+
+    fn forbid_rule() -> ForbidCrateDepRule {
+        let dto = toml::from_str(FORBID).expect("parse dto");
+        let config = crate::declarative::loader::load(dto).expect("load config");
+        ForbidCrateDepRule::new(Arc::new(config))
+    }
+
+    #[test]
+    fn forbids_denied_dependency_by_crate_name_and_path() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let domain = write_crate(
+            dir.path(),
+            "crates/domain",
+            "[package]\nname = \"app-domain\"\n\n[dependencies]\nsqlx = \"0.7\"\n",
+        );
+        let core = write_crate(
+            dir.path(),
+            "crates/core",
+            "[package]\nname = \"core\"\n\n[dependencies.sqlx]\nversion = \"0.7\"\n",
+        );
+        let infra = write_crate(
+            dir.path(),
+            "crates/infra",
+            "[package]\nname = \"app-infra\"\n\n[dependencies]\nsqlx = \"0.7\"\n",
+        );
+
+        let ctx =
+            ProjectContext::new(dir.path()).with_cargo_files(vec![domain.clone(), core, infra]);
+        let mut violations = forbid_rule().check_project(&ctx);
+        violations.sort_by(|a, b| a.location.file.cmp(&b.location.file));
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].code, FORBID_CRATE_DEP_CODE);
+        assert_eq!(violations[0].location.line, 4);
+        assert_eq!(violations[1].location.file, domain);
+        assert_eq!(violations[1].location.line, 5);
+        assert!(violations[1].message.contains("no-sqlx-in-domain"));
+    }
+
+    #[test]
+    fn forbid_ignores_dev_dependencies() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let domain = write_crate(
+            dir.path(),
+            "domain",
+            "[package]\nname = \"app-domain\"\n\n[dev-dependencies]\nsqlx = \"0.7\"\n",
+        );
+
+        let ctx = ProjectContext::new(dir.path()).with_cargo_files(vec![domain]);
+        assert!(forbid_rule().check_project(&ctx).is_empty());
+    }
+}
@@ -8,7 +8,9 @@ use serde::Deserialize;
 /// Raw TOML representation of declarative rules.
 ///
 /// Extends the base `Config` with `[[scopes]]`, `[[restrict-use]]`,
-/// `[[require-use]]`, and `[[deny-scope-dep]]` sections.
+/// `[[require-use]]`, `[[deny-scope-dep]]`, `[[crate-layers]]`,
+/// `[[forbid-crate-dep]]`, `[[forbid-pattern]]`,
+/// `[[forbid-sibling-import]]`, and `[[restrict-derive]]` sections.
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct DeclarativeConfigDto {
     /// Named scope definitions.
@@ -26,6 +28,26 @@ pub struct DeclarativeConfigDto {
     /// Scope dependency constraints.
     #[serde(rename = "deny-scope-dep", default)]
     pub deny_scope_dep: Vec<ScopeDepDto>,
+
+    /// Crate layer ordering, innermost first.
+    #[serde(rename = "crate-layers", default)]
+    pub crate_layers: Vec<CrateLayerDto>,
+
+    /// Crate dependency bans.
+    #[serde(rename = "forbid-crate-dep", default)]
+    pub forbid_crate_dep: Vec<ForbidCrateDepDto>,
+
+    /// Generic syntax bans.
+    #[serde(rename = "forbid-pattern", default)]
+    pub forbid_pattern: Vec<ForbidPatternDto>,
+
+    /// Feature isolation rules.
+    #[serde(rename = "forbid-sibling-import", default)]
+    pub forbid_sibling_import: Vec<ForbidSiblingImportDto>,
+
+    /// Derive restrictions.
+    #[serde(rename = "restrict-derive", default)]
+    pub restrict_derive: Vec<RestrictDeriveDto>,
 }
 
 /// TOML representation of a named scope.
@@ -105,6 +127,112 @@ pub struct ScopeDepDto {
     pub severity: String,
 }
 
+/// TOML representation of a crate layer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CrateLayerDto {
+    /// Layer name (e.g., "domain").
+    pub name: String,
+    /// Crate-name glob patterns (e.g., `["app-domain", "*-model"]`).
+    pub crates: Vec<String>,
+    /// Document reference.
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Severity (default: "error").
+    #[serde(default = "default_severity_str")]
+    pub severity: String,
+}
+
+/// TOML representation of a forbid-crate-dep rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForbidCrateDepDto {
+    /// Rule name (e.g., "no-sqlx-in-domain").
+    pub name: String,
+    /// Package-name globs selecting the crates to check.
+    #[serde(default)]
+    pub crates: Vec<String>,
+    /// Manifest directory globs (relative to the root) selecting the crates to check.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Denied dependency names (globs).
+    pub deny: Vec<String>,
+    /// Violation message.
+    pub message: String,
+    /// Document reference.
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Severity (default: "error").
+    #[serde(default = "default_severity_str")]
+    pub severity: String,
+}
+
+/// TOML representation of a forbid-pattern rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForbidPatternDto {
+    /// Rule name (e.g., "no-dbg-in-lib").
+    pub name: String,
+    /// Named scope reference (mutually exclusive with `files`).
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Inline file patterns (mutually exclusive with `scope`).
+    #[serde(default)]
+    pub files: Option<Vec<String>>,
+    /// Match kind: "method-call", "macro", or "type-suffix".
+    pub kind: String,
+    /// Method names, macro paths, or type-name suffixes to forbid.
+    pub patterns: Vec<String>,
+    /// Violation message.
+    pub message: String,
+    /// Document reference.
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Severity (default: "error").
+    #[serde(default = "default_severity_str")]
+    pub severity: String,
+}
+
+/// TOML representation of a forbid-sibling-import rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForbidSiblingImportDto {
+    /// Rule name (e.g., "feature-isolation").
+    pub name: String,
+    /// Module holding the features (e.g., "features" for `crate::features`).
+    pub parent: String,
+    /// Modules any feature may import (e.g., `["features::shared"]`).
+    #[serde(default)]
+    pub shared: Vec<String>,
+    /// Violation message.
+    pub message: String,
+    /// Document reference.
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Severity (default: "error").
+    #[serde(default = "default_severity_str")]
+    pub severity: String,
+}
+
+/// TOML representation of a restrict-derive rule.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RestrictDeriveDto {
+    /// Rule name (e.g., "no-serde-in-domain").
+    pub name: String,
+    /// Named scope reference (mutually exclusive with `files`).
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Inline file patterns (mutually exclusive with `scope`).
+    #[serde(default)]
+    pub files: Option<Vec<String>>,
+    /// Derive macros to forbid (e.g., `["serde::Serialize", "sqlx::FromRow"]`).
+    pub deny: Vec<String>,
+    /// Violation message.
+    pub message: String,
+    /// Document reference.
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Severity (default: "error").
+    #[serde(default = "default_severity_str")]
+    pub severity: String,
+}
+
 fn default_severity_str() -> String {
     "error".to_string()
 }
@@ -154,6 +282,14 @@ message = "Use tracing, not log."
 from = "domain"
 to = ["infra"]
 message = "Domain must not depend on infra."
+
+[[crate-layers]]
+name = "domain"
+crates = ["app-domain"]
+
+[[crate-layers]]
+name = "infra"
+crates = ["app-infra", "*-adapter"]
 "#;
         let dto: DeclarativeConfigDto = toml::from_str(toml_str).unwrap();
         assert_eq!(dto.scopes.len(), 2);
@@ -163,6 +299,8 @@ message = "Domain must not depend on infra."
         assert_eq!(dto.require_use.len(), 1);
         assert_eq!(dto.deny_scope_dep.len(), 1);
         assert_eq!(dto.deny_scope_dep[0].severity, "error");
+        assert_eq!(dto.crate_layers.len(), 2);
+        assert_eq!(dto.crate_layers[1].crates, vec!["app-infra", "*-adapter"]);
     }
 
     #[test]
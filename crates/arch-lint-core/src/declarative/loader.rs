@@ -3,11 +3,14 @@
 use crate::types::Severity;
 
 use super::config_dto::{
-    DeclarativeConfigDto, RequireUseDto, RestrictUseDto, ScopeDepDto, ScopeDto,
+    CrateLayerDto, DeclarativeConfigDto, ForbidCrateDepDto, ForbidPatternDto,
+    ForbidSiblingImportDto, RequireUseDto, RestrictDeriveDto, RestrictUseDto, ScopeDepDto,
+    ScopeDto,
 };
 use super::model::{
-    DeclarativeConfig, GlobPattern, ModelError, RequireUse, RestrictUse, Scope, ScopeDep,
-    ScopeName, ScopeRef, UsePattern,
+    CrateLayer, CratePattern, DeclarativeConfig, ForbidCrateDep, ForbidPattern,
+    ForbidSiblingImport, GlobPattern, MatchKind, ModelError, RequireUse, RestrictDerive,
+    RestrictUse, Scope, ScopeDep, ScopeName, ScopeRef, UsePattern,
 };
 
 /// Errors during DTO → Domain conversion.
@@ -29,6 +32,22 @@ pub enum LoadError {
         rule_name: String,
     },
 
+    /// A forbid-crate-dep rule selects no crates.
+    #[error("{rule_name}: at least one of `crates` or `paths` must be set")]
+    MissingCrateSelector {
+        /// The rule without a selector.
+        rule_name: String,
+    },
+
+    /// Unknown forbid-pattern match kind.
+    #[error("{rule_name}: unknown kind `{value}`, expected: {}", MatchKind::NAMES.join(", "))]
+    UnknownMatchKind {
+        /// The rule with the invalid kind.
+        rule_name: String,
+        /// The invalid value.
+        value: String,
+    },
+
     /// Unknown severity string.
     #[error("{context}: unknown severity `{value}`, expected: error, warning, info")]
     UnknownSeverity {
@@ -83,7 +102,43 @@ pub fn load(dto: DeclarativeConfigDto) -> Result<DeclarativeConfig, LoadError> {
         .map(|(i, d)| convert_scope_dep(d, i))
         .collect::<Result<Vec<_>, _>>()?;
 
+    let crate_layers = dto
+        .crate_layers
+        .iter()
+        .enumerate()
+        .map(|(i, l)| convert_crate_layer(l, i))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let forbid_crate_deps = dto
+        .forbid_crate_dep
+        .into_iter()
+        .map(convert_forbid_crate_dep)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let forbid_patterns = dto
+        .forbid_pattern
+        .into_iter()
+        .map(convert_forbid_pattern)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let forbid_sibling_imports = dto
+        .forbid_sibling_import
+        .into_iter()
+        .map(convert_forbid_sibling_import)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let restrict_derives = dto
+        .restrict_derive
+        .into_iter()
+        .map(convert_restrict_derive)
+        .collect::<Result<Vec<_>, _>>()?;
+
     DeclarativeConfig::new(scopes, restrict_uses, require_uses, scope_deps)
+        .and_then(|config| config.with_crate_layers(crate_layers))
+        .map(|config| config.with_forbid_crate_deps(forbid_crate_deps))
+        .and_then(|config| config.with_forbid_patterns(forbid_patterns))
+        .map(|config| config.with_forbid_sibling_imports(forbid_sibling_imports))
+        .and_then(|config| config.with_restrict_derives(restrict_derives))
         .map_err(LoadError::CrossRef)
 }
 
@@ -214,16 +269,140 @@ fn convert_scope_dep(dto: ScopeDepDto, index: usize) -> Result<ScopeDep, LoadErr
     ))
 }
 
-fn parse_severity(value: &str, context: &str) -> Result<Severity, LoadError> {
-    match value {
-        "error" => Ok(Severity::Error),
-        "warning" => Ok(Severity::Warning),
-        "info" => Ok(Severity::Info),
-        _ => Err(LoadError::UnknownSeverity {
-            context: context.to_string(),
-            value: value.to_string(),
-        }),
+fn convert_crate_layer(dto: &CrateLayerDto, index: usize) -> Result<CrateLayer, LoadError> {
+    let ctx = format!("crate-layers[{index}]");
+    let name = ScopeName::new(&dto.name).map_err(|e| LoadError::Validation {
+        context: format!("{ctx}.name"),
+        source: e,
+    })?;
+
+    let crates = dto
+        .crates
+        .iter()
+        .enumerate()
+        .map(|(j, p)| {
+            CratePattern::new(p).map_err(|e| LoadError::Validation {
+                context: format!("{ctx}.crates[{j}]"),
+                source: e,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let severity = parse_severity(&dto.severity, &ctx)?;
+
+    Ok(CrateLayer::new(name, crates, dto.doc.clone(), severity))
+}
+
+fn convert_forbid_crate_dep(dto: ForbidCrateDepDto) -> Result<ForbidCrateDep, LoadError> {
+    let ctx = format!("forbid-crate-dep '{}'", dto.name);
+    if dto.crates.is_empty() && dto.paths.is_empty() {
+        return Err(LoadError::MissingCrateSelector {
+            rule_name: dto.name,
+        });
     }
+
+    let crate_patterns = |field: &str, patterns: &[String]| {
+        patterns
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                CratePattern::new(p).map_err(|e| LoadError::Validation {
+                    context: format!("{ctx} {field}[{i}]"),
+                    source: e,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let crates = crate_patterns("crates", &dto.crates)?;
+    let deny = crate_patterns("deny", &dto.deny)?;
+
+    let paths = dto
+        .paths
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            GlobPattern::new(p).map_err(|e| LoadError::Validation {
+                context: format!("{ctx} paths[{i}]"),
+                source: e,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let severity = parse_severity(&dto.severity, &ctx)?;
+
+    Ok(ForbidCrateDep::new(
+        dto.name,
+        crates,
+        paths,
+        deny,
+        dto.message,
+        dto.doc,
+        severity,
+    ))
+}
+
+fn convert_forbid_pattern(dto: ForbidPatternDto) -> Result<ForbidPattern, LoadError> {
+    let ctx = format!("forbid-pattern '{}'", dto.name);
+    let scope = resolve_scope_ref(dto.scope, dto.files, &dto.name)?;
+    let kind = MatchKind::parse(&dto.kind).ok_or_else(|| LoadError::UnknownMatchKind {
+        rule_name: dto.name.clone(),
+        value: dto.kind.clone(),
+    })?;
+    let severity = parse_severity(&dto.severity, &ctx)?;
+
+    ForbidPattern::new(
+        dto.name,
+        scope,
+        kind,
+        dto.patterns,
+        dto.message,
+        dto.doc,
+        severity,
+    )
+    .map_err(|e| LoadError::Validation {
+        context: format!("{ctx} patterns"),
+        source: e,
+    })
+}
+
+fn convert_forbid_sibling_import(
+    dto: ForbidSiblingImportDto,
+) -> Result<ForbidSiblingImport, LoadError> {
+    let ctx = format!("forbid-sibling-import '{}'", dto.name);
+    let severity = parse_severity(&dto.severity, &ctx)?;
+
+    ForbidSiblingImport::new(
+        dto.name,
+        &dto.parent,
+        &dto.shared,
+        dto.message,
+        dto.doc,
+        severity,
+    )
+    .map_err(|e| LoadError::Validation {
+        context: ctx,
+        source: e,
+    })
+}
+
+fn convert_restrict_derive(dto: RestrictDeriveDto) -> Result<RestrictDerive, LoadError> {
+    let ctx = format!("restrict-derive '{}'", dto.name);
+    let scope = resolve_scope_ref(dto.scope, dto.files, &dto.name)?;
+    let severity = parse_severity(&dto.severity, &ctx)?;
+
+    RestrictDerive::new(dto.name, scope, dto.deny, dto.message, dto.doc, severity).map_err(|e| {
+        LoadError::Validation {
+            context: format!("{ctx} deny"),
+            source: e,
+        }
+    })
+}
+
+fn parse_severity(value: &str, context: &str) -> Result<Severity, LoadError> {
+    value.parse().map_err(|_| LoadError::UnknownSeverity {
+        context: context.to_string(),
+        value: value.to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -374,4 +553,293 @@ message = "msg"
         );
         assert!(matches!(result, Err(LoadError::CrossRef(_))));
     }
+
+    #[test]
+    fn load_crate_layers_in_order() {
+        let config = parse_and_load(
+            r#"
+[[crate-layers]]
+name = "domain"
+crates = ["app-domain", "*-model"]
+
+[[crate-layers]]
+name = "infra"
+crates = ["app-infra"]
+severity = "warning"
+"#,
+        )
+        .unwrap();
+        assert!(!config.is_empty());
+        assert_eq!(config.crate_layer_index("app-domain"), Some(0));
+        assert_eq!(config.crate_layer_index("user-model"), Some(0));
+        assert_eq!(config.crate_layer_index("app-infra"), Some(1));
+        assert_eq!(config.crate_layer_index("serde"), None);
+        assert_eq!(config.crate_layers()[1].severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn load_rejects_duplicate_crate_layer() {
+        let result = parse_and_load(
+            r#"
+[[crate-layers]]
+name = "domain"
+crates = ["a"]
+
+[[crate-layers]]
+name = "domain"
+crates = ["b"]
+"#,
+        );
+        assert!(matches!(result, Err(LoadError::CrossRef(_))));
+    }
+
+    #[test]
+    fn load_forbid_crate_dep() {
+        let config = parse_and_load(
+            r#"
+[[forbid-crate-dep]]
+name = "no-sqlx-in-domain"
+crates = ["*-domain"]
+paths = ["crates/core"]
+deny = ["sqlx", "diesel*"]
+message = "Domain crates must not depend on database drivers."
+"#,
+        )
+        .unwrap();
+        let rule = &config.forbid_crate_deps()[0];
+        assert!(rule.applies_to("app-domain", std::path::Path::new("x")));
+        assert!(rule.applies_to("core", std::path::Path::new("crates/core")));
+        assert!(!rule.applies_to("app-infra", std::path::Path::new("crates/infra")));
+        assert!(rule.is_denied("diesel_migrations"));
+        assert!(!rule.is_denied("serde"));
+    }
+
+    #[test]
+    fn load_rejects_forbid_crate_dep_without_selector() {
+        let result = parse_and_load(
+            r#"
+[[forbid-crate-dep]]
+name = "bad"
+deny = ["sqlx"]
+message = "msg"
+"#,
+        );
+        assert!(matches!(
+            result,
+            Err(LoadError::MissingCrateSelector { .. })
+        ));
+    }
+
+    #[test]
+    fn load_forbid_pattern() {
+        let config = parse_and_load(
+            r#"
+[[scopes]]
+name = "domain"
+paths = ["src/domain/**"]
+
+[[forbid-pattern]]
+name = "no-dbg"
+scope = "domain"
+kind = "macro"
+patterns = ["dbg"]
+message = "Remove debugging output."
+"#,
+        )
+        .unwrap();
+        let rule = &config.forbid_patterns()[0];
+        assert_eq!(rule.kind(), MatchKind::Macro);
+        assert!(rule.matches("std::dbg"));
+    }
+
+    #[test]
+    fn load_rejects_unknown_match_kind() {
+        let result = parse_and_load(
+            r#"
+[[forbid-pattern]]
+name = "bad"
+files = ["src/**"]
+kind = "function"
+patterns = ["foo"]
+message = "msg"
+"#,
+        );
+        let err = result.unwrap_err();
+        assert!(matches!(err, LoadError::UnknownMatchKind { .. }));
+        assert!(err.to_string().contains("method-call, macro, type-suffix"));
+    }
+
+    #[test]
+    fn load_rejects_forbid_pattern_unknown_scope() {
+        let result = parse_and_load(
+            r#"
+[[forbid-pattern]]
+name = "bad"
+scope = "nowhere"
+kind = "method-call"
+patterns = ["clone"]
+message = "msg"
+"#,
+        );
+        assert!(matches!(result, Err(LoadError::CrossRef(_))));
+    }
+
+    #[test]
+    fn load_forbid_sibling_import() {
+        let config = parse_and_load(
+            r#"
+[[forbid-sibling-import]]
+name = "feature-isolation"
+parent = "features"
+shared = ["features::shared"]
+message = "Go through features::shared."
+severity = "warning"
+"#,
+        )
+        .unwrap();
+        let rule = &config.forbid_sibling_imports()[0];
+        assert_eq!(rule.severity(), Severity::Warning);
+        assert_eq!(
+            rule.sibling_target("billing", "crate::features::orders::Order"),
+            Some("orders")
+        );
+        assert!(!config.is_empty());
+    }
+
+    #[test]
+    fn load_rejects_forbid_sibling_import_bad_parent() {
+        let result = parse_and_load(
+            r#"
+[[forbid-sibling-import]]
+name = "bad"
+parent = "src/features"
+message = "msg"
+"#,
+        );
+        let err = result.unwrap_err();
+        assert!(matches!(err, LoadError::Validation { .. }));
+        assert!(
+            err.to_string().contains("forbid-sibling-import 'bad'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn load_restrict_derive() {
+        let config = parse_and_load(
+            r#"
+[[scopes]]
+name = "domain"
+paths = ["src/domain/**"]
+
+[[restrict-derive]]
+name = "no-serde-in-domain"
+scope = "domain"
+deny = ["serde::Serialize", "sqlx::FromRow"]
+message = "Domain models stay free of infrastructure derives."
+doc = "docs/adr/0003.md"
+severity = "warning"
+"#,
+        )
+        .unwrap();
+        let rule = &config.restrict_derives()[0];
+        assert_eq!(rule.severity(), Severity::Warning);
+        assert_eq!(rule.doc_ref(), Some("docs/adr/0003.md"));
+        assert!(rule.is_denied("Serialize"));
+        assert!(!config.is_empty());
+    }
+
+    #[test]
+    fn load_rejects_restrict_derive_errors() {
+        let unknown_scope = parse_and_load(
+            r#"
+[[restrict-derive]]
+name = "bad"
+scope = "nowhere"
+deny = ["serde::Serialize"]
+message = "msg"
+"#,
+        );
+        assert!(matches!(unknown_scope, Err(LoadError::CrossRef(_))));
+
+        let empty = parse_and_load(
+            r#"
+[[restrict-derive]]
+name = "bad"
+files = ["src/**"]
+deny = [" "]
+message = "msg"
+"#,
+        );
+        let err = empty.unwrap_err();
+        assert!(
+            err.to_string().contains("restrict-derive 'bad' deny"),
+            "{err}"
+        );
+    }
+
+    // -- Conflicts --
+
+    #[test]
+    fn load_rejects_preferred_crate_denied_in_overlapping_scope() {
+        let result = parse_and_load(
+            r#"
+[[scopes]]
+name = "app"
+paths = ["src/**"]
+
+[[restrict-use]]
+name = "no-tokio"
+scope = "app"
+deny = ["tokio::*"]
+message = "No tokio."
+
+[[require-use]]
+name = "prefer-tokio"
+files = ["src/runtime/**"]
+prefer = "tokio"
+over = ["async-std"]
+message = "Use tokio."
+"#,
+        );
+        let Err(LoadError::CrossRef(errors)) = result else {
+            panic!("expected a conflict error");
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ModelError::ConflictingRules { require, restrict, krate }
+                if require == "prefer-tokio" && restrict == "no-tokio" && krate == "tokio"
+        ));
+    }
+
+    #[test]
+    fn load_allows_denial_in_disjoint_scope_or_of_part_of_crate() {
+        let config = parse_and_load(
+            r#"
+[[restrict-use]]
+name = "no-tokio-in-domain"
+files = ["src/domain/**"]
+deny = ["tokio::*"]
+message = "No tokio in domain."
+
+[[restrict-use]]
+name = "no-tokio-fs"
+files = ["src/**"]
+deny = ["tokio::fs::*"]
+message = "No async fs."
+
+[[require-use]]
+name = "prefer-tokio"
+files = ["src/runtime/**"]
+prefer = "tokio"
+over = ["async-std"]
+message = "Use tokio."
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.restrict_uses().len(), 2);
+        assert_eq!(config.require_uses().len(), 1);
+    }
 }
@@ -1,4 +1,4 @@
-//! Declarative architecture rules driven by TOML configuration.
+//! Declarative architecture rules driven by TOML (or YAML/JSON) configuration.
 //!
 //! This module provides a scope-based model for defining architecture
 //! constraints without writing Rust rule code.
@@ -15,12 +15,18 @@
 //! Vec<RuleBox>
 //! ```
 
+use std::path::Path;
 use std::sync::Arc;
 
+use crate::config::ConfigFormat;
+use crate::types::Severity;
+
+pub mod cargo_rules;
 pub mod config_dto;
 pub mod loader;
 pub mod model;
 pub mod rules;
+pub mod scope_rules;
 
 /// Errors from parsing TOML and loading declarative rules.
 #[derive(Debug, thiserror::Error)]
@@ -29,11 +35,72 @@ pub enum LoadRulesError {
     #[error("TOML parse error: {0}")]
     Toml(#[from] toml::de::Error),
 
+    /// YAML deserialization failed.
+    #[error("YAML parse error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    /// JSON deserialization failed.
+    #[error("JSON parse error: {0}")]
+    Json(#[from] serde_json::Error),
+
     /// Domain model validation failed.
     #[error("{0}")]
     Load(#[from] loader::LoadError),
 }
 
+/// Metadata of one declarative rule kind, as listed by `list-rules`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeclarativeRuleInfo {
+    /// Rule code (`ALD001`, ...).
+    pub code: &'static str,
+    /// Rule name, which is also its config section (`[[restrict-use]]`).
+    pub name: &'static str,
+    /// Short description.
+    pub description: &'static str,
+    /// Severity of entries that do not set `severity`.
+    pub default_severity: Severity,
+}
+
+/// Returns the metadata of every declarative rule kind, in code order.
+///
+/// The metadata is read from the rule implementations, instantiated over
+/// an empty config. These rules are not part of any preset: each one runs
+/// whenever its config section has entries.
+#[must_use]
+pub fn rule_catalog() -> Vec<DeclarativeRuleInfo> {
+    let config = Arc::new(model::DeclarativeConfig::default());
+    let rules: Vec<crate::rule::RuleBox> = vec![
+        Box::new(rules::RestrictUseRule::new(Arc::clone(&config))),
+        Box::new(rules::RequireUseRule::new(Arc::clone(&config))),
+        Box::new(rules::ScopeDepRule::new(Arc::clone(&config))),
+        Box::new(rules::ForbidPatternRule::new(Arc::clone(&config))),
+        Box::new(rules::ForbidSiblingImportRule::new(Arc::clone(&config))),
+        Box::new(rules::RestrictDeriveRule::new(Arc::clone(&config))),
+    ];
+    let project_rules: Vec<crate::rule::ProjectRuleBox> = vec![
+        Box::new(cargo_rules::CrateLayerRule::new(Arc::clone(&config))),
+        Box::new(cargo_rules::ForbidCrateDepRule::new(config)),
+    ];
+
+    let mut catalog: Vec<DeclarativeRuleInfo> = rules
+        .iter()
+        .map(|rule| DeclarativeRuleInfo {
+            code: rule.code(),
+            name: rule.name(),
+            description: rule.description(),
+            default_severity: rule.default_severity(),
+        })
+        .chain(project_rules.iter().map(|rule| DeclarativeRuleInfo {
+            code: rule.code(),
+            name: rule.name(),
+            description: rule.description(),
+            default_severity: rule.default_severity(),
+        }))
+        .collect();
+    catalog.sort_by_key(|info| info.code);
+    catalog
+}
+
 /// Parses TOML content and creates all applicable declarative rules.
 ///
 /// Returns `Ok(vec![])` if no declarative sections are present.
@@ -42,11 +109,115 @@ pub enum LoadRulesError {
 ///
 /// Returns an error if TOML parsing or model validation fails.
 pub fn load_rules_from_toml(content: &str) -> Result<Vec<crate::rule::RuleBox>, LoadRulesError> {
-    let dto: config_dto::DeclarativeConfigDto = toml::from_str(content)?;
-    let config = loader::load(dto)?;
+    load_rules(content, ConfigFormat::Toml)
+}
+
+/// Like [`load_rules_from_toml`], for content in any [`ConfigFormat`].
+///
+/// # Errors
+///
+/// Returns an error if parsing or model validation fails.
+pub fn load_rules(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<Vec<crate::rule::RuleBox>, LoadRulesError> {
+    let config = loader::load(parse_dto(content, format)?)?;
     Ok(create_rules(config))
 }
 
+/// Parses TOML content and creates all declarative project-wide rules
+/// (`[[crate-layers]]`, `[[forbid-crate-dep]]`, and the empty-scope check).
+///
+/// Returns `Ok(vec![])` if no such sections are present.
+///
+/// # Errors
+///
+/// Returns an error if TOML parsing or model validation fails.
+pub fn load_project_rules_from_toml(
+    content: &str,
+) -> Result<Vec<crate::rule::ProjectRuleBox>, LoadRulesError> {
+    load_project_rules(content, ConfigFormat::Toml)
+}
+
+/// Like [`load_project_rules_from_toml`], for content in any [`ConfigFormat`].
+///
+/// # Errors
+///
+/// Returns an error if parsing or model validation fails.
+pub fn load_project_rules(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<Vec<crate::rule::ProjectRuleBox>, LoadRulesError> {
+    let config = loader::load(parse_dto(content, format)?)?;
+    Ok(create_project_rules(config))
+}
+
+/// The declarative rules defined by one config file.
+#[derive(Default)]
+pub struct DeclarativeRules {
+    /// Per-file rules (`[[restrict-use]]`, `[[forbid-pattern]]`, ...).
+    pub rules: Vec<crate::rule::RuleBox>,
+    /// Project-wide rules (`[[crate-layers]]`, `[[forbid-crate-dep]]`, and
+    /// the empty-scope check).
+    pub project_rules: Vec<crate::rule::ProjectRuleBox>,
+}
+
+/// Parses config content once and creates both its per-file and its
+/// project-wide declarative rules.
+///
+/// This is the loader shared by the CLI, `check!()` and the language
+/// server, so a config is enforced the same way by each. Empty content
+/// (no config file) yields no rules.
+///
+/// # Errors
+///
+/// Returns an error if parsing or model validation fails.
+pub fn load_all(content: &str, format: ConfigFormat) -> Result<DeclarativeRules, LoadRulesError> {
+    load(content, format, None)
+}
+
+/// Like [`load_all`] for content read from `path`, so configuration
+/// diagnostics (such as an empty scope) point at their entry in that file.
+///
+/// For a `[package.metadata.arch-lint]` table, `path` is the manifest.
+///
+/// # Errors
+///
+/// Returns an error if parsing or model validation fails.
+pub fn load_file(
+    path: &Path,
+    content: &str,
+    format: ConfigFormat,
+) -> Result<DeclarativeRules, LoadRulesError> {
+    load(content, format, Some(path))
+}
+
+fn load(
+    content: &str,
+    format: ConfigFormat,
+    config_file: Option<&Path>,
+) -> Result<DeclarativeRules, LoadRulesError> {
+    if content.trim().is_empty() {
+        return Ok(DeclarativeRules::default());
+    }
+    let config = loader::load(parse_dto(content, format)?)?;
+    Ok(DeclarativeRules {
+        rules: create_rules(config.clone()),
+        project_rules: project_rules(config, config_file),
+    })
+}
+
+fn parse_dto(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<config_dto::DeclarativeConfigDto, LoadRulesError> {
+    Ok(match format {
+        ConfigFormat::Toml => toml::from_str(content)?,
+        ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+        ConfigFormat::Json => serde_json::from_str(content)?,
+    })
+}
+
 /// Creates all declarative rules from a validated [`model::DeclarativeConfig`].
 ///
 /// Returns an empty vec if no declarative rules are defined.
@@ -66,7 +237,53 @@ pub fn create_rules(config: model::DeclarativeConfig) -> Vec<crate::rule::RuleBo
         result.push(Box::new(rules::RequireUseRule::new(Arc::clone(&config))));
     }
     if !config.scope_deps().is_empty() {
-        result.push(Box::new(rules::ScopeDepRule::new(config)));
+        result.push(Box::new(rules::ScopeDepRule::new(Arc::clone(&config))));
+    }
+    if !config.forbid_patterns().is_empty() {
+        result.push(Box::new(rules::ForbidPatternRule::new(Arc::clone(&config))));
+    }
+    if !config.forbid_sibling_imports().is_empty() {
+        result.push(Box::new(rules::ForbidSiblingImportRule::new(Arc::clone(
+            &config,
+        ))));
+    }
+    if !config.restrict_derives().is_empty() {
+        result.push(Box::new(rules::RestrictDeriveRule::new(config)));
+    }
+
+    result
+}
+
+/// Creates all declarative project-wide rules from a validated
+/// [`model::DeclarativeConfig`].
+#[must_use]
+pub fn create_project_rules(config: model::DeclarativeConfig) -> Vec<crate::rule::ProjectRuleBox> {
+    project_rules(config, None)
+}
+
+fn project_rules(
+    config: model::DeclarativeConfig,
+    config_file: Option<&Path>,
+) -> Vec<crate::rule::ProjectRuleBox> {
+    let config = Arc::new(config);
+    let mut result: Vec<crate::rule::ProjectRuleBox> = Vec::new();
+
+    if !config.crate_layers().is_empty() {
+        result.push(Box::new(cargo_rules::CrateLayerRule::new(Arc::clone(
+            &config,
+        ))));
+    }
+    if !config.forbid_crate_deps().is_empty() {
+        result.push(Box::new(cargo_rules::ForbidCrateDepRule::new(Arc::clone(
+            &config,
+        ))));
+    }
+    if !config.scopes().is_empty() {
+        let rule = scope_rules::EmptyScopeRule::new(config);
+        result.push(Box::new(match config_file {
+            Some(path) => rule.with_config_file(path),
+            None => rule,
+        }));
     }
 
     result
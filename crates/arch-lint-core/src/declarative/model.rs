@@ -4,6 +4,7 @@
 //! All invariants are enforced at construction time via validated newtypes.
 
 use crate::types::Severity;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 use std::path::Path;
@@ -80,9 +81,16 @@ impl GlobPattern {
     }
 
     /// Tests whether a relative file path matches this pattern.
+    ///
+    /// `\` separators are treated as `/`, so patterns match Windows paths.
     #[must_use]
     pub fn matches(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy();
+        let path_str = if path_str.contains('\\') {
+            Cow::Owned(path_str.replace('\\', "/"))
+        } else {
+            path_str
+        };
         if self.compiled.matches(&path_str) {
             return true;
         }
@@ -108,6 +116,17 @@ impl GlobPattern {
     pub fn as_str(&self) -> &str {
         &self.raw
     }
+
+    /// Tests whether two patterns may match the same file.
+    ///
+    /// Conservative: true when either pattern matches the other's text
+    /// (`src/**` overlaps `src/domain/**`).
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.raw == other.raw
+            || self.matches(Path::new(&other.raw))
+            || other.matches(Path::new(&self.raw))
+    }
 }
 
 /// A validated use-path pattern for matching Rust import paths.
@@ -138,6 +157,14 @@ impl UsePattern {
         crate::utils::paths::path_matches(use_path, &self.0)
     }
 
+    /// Tests whether this pattern denies the whole crate `krate`
+    /// (`tokio`, `tokio::*`, `tokio::**`), not just part of it.
+    #[must_use]
+    pub fn covers_crate(&self, krate: &str) -> bool {
+        let krate = krate.replace('-', "_");
+        self.matches(&krate) || self.matches(&format!("{krate}::*"))
+    }
+
     /// Returns the pattern as a string slice.
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -145,6 +172,46 @@ impl UsePattern {
     }
 }
 
+/// A validated glob pattern for crate (package) names, e.g. `*-domain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CratePattern {
+    raw: String,
+    compiled: glob::Pattern,
+}
+
+impl CratePattern {
+    /// Creates a new crate-name pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the pattern is empty or has invalid glob syntax.
+    pub fn new(pattern: &str) -> Result<Self, ModelError> {
+        if pattern.is_empty() {
+            return Err(ModelError::EmptyGlobPattern);
+        }
+        let compiled = glob::Pattern::new(pattern).map_err(|e| ModelError::InvalidGlobPattern {
+            pattern: pattern.to_string(),
+            reason: e.to_string(),
+        })?;
+        Ok(Self {
+            raw: pattern.to_string(),
+            compiled,
+        })
+    }
+
+    /// Tests whether a crate name matches this pattern.
+    #[must_use]
+    pub fn matches(&self, crate_name: &str) -> bool {
+        self.compiled.matches(crate_name)
+    }
+
+    /// Returns the pattern as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+}
+
 // ────────────────────────────────────────────
 // Domain entities
 // ────────────────────────────────────────────
@@ -436,6 +503,526 @@ impl ScopeDep {
     }
 }
 
+/// A crate-level architecture layer.
+///
+/// Layers are ordered from innermost to outermost; a crate may depend on
+/// crates in its own or an inner layer, never on an outer one.
+#[derive(Debug, Clone)]
+pub struct CrateLayer {
+    name: ScopeName,
+    crates: Vec<CratePattern>,
+    doc_ref: Option<String>,
+    severity: Severity,
+}
+
+impl CrateLayer {
+    /// Creates a new crate layer.
+    #[must_use]
+    pub fn new(
+        name: ScopeName,
+        crates: Vec<CratePattern>,
+        doc_ref: Option<String>,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            name,
+            crates,
+            doc_ref,
+            severity,
+        }
+    }
+
+    /// Returns the layer name.
+    #[must_use]
+    pub fn name(&self) -> &ScopeName {
+        &self.name
+    }
+
+    /// Returns the crate-name patterns belonging to this layer.
+    #[must_use]
+    pub fn crates(&self) -> &[CratePattern] {
+        &self.crates
+    }
+
+    /// Returns the document reference.
+    #[must_use]
+    pub fn doc_ref(&self) -> Option<&str> {
+        self.doc_ref.as_deref()
+    }
+
+    /// Returns the severity for violations originating in this layer.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Checks whether a crate belongs to this layer.
+    #[must_use]
+    pub fn contains(&self, crate_name: &str) -> bool {
+        self.crates.iter().any(|p| p.matches(crate_name))
+    }
+}
+
+/// A crate dependency ban: selected crates must not list denied crates
+/// in `[dependencies]`.
+#[derive(Debug, Clone)]
+pub struct ForbidCrateDep {
+    name: String,
+    crates: Vec<CratePattern>,
+    paths: Vec<GlobPattern>,
+    deny: Vec<CratePattern>,
+    message: String,
+    doc_ref: Option<String>,
+    severity: Severity,
+}
+
+impl ForbidCrateDep {
+    /// Creates a new forbid-crate-dep rule.
+    ///
+    /// A crate is selected if its package name matches one of `crates`
+    /// or its manifest directory matches one of `paths`.
+    #[must_use]
+    pub fn new(
+        name: String,
+        crates: Vec<CratePattern>,
+        paths: Vec<GlobPattern>,
+        deny: Vec<CratePattern>,
+        message: String,
+        doc_ref: Option<String>,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            name,
+            crates,
+            paths,
+            deny,
+            message,
+            doc_ref,
+            severity,
+        }
+    }
+
+    /// Returns the rule name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the violation message.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the document reference.
+    #[must_use]
+    pub fn doc_ref(&self) -> Option<&str> {
+        self.doc_ref.as_deref()
+    }
+
+    /// Returns the severity.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Checks whether the rule applies to a crate, given its package name
+    /// and manifest directory relative to the project root.
+    #[must_use]
+    pub fn applies_to(&self, crate_name: &str, manifest_dir: &Path) -> bool {
+        self.crates.iter().any(|p| p.matches(crate_name))
+            || self.paths.iter().any(|p| p.matches(manifest_dir))
+    }
+
+    /// Checks if a dependency is denied.
+    #[must_use]
+    pub fn is_denied(&self, dependency: &str) -> bool {
+        self.deny.iter().any(|p| p.matches(dependency))
+    }
+}
+
+/// What a [`ForbidPattern`] matches against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Method-call names (`x.clone()` → `clone`).
+    MethodCall,
+    /// Macro paths (`dbg!`, `tracing::debug!`).
+    Macro,
+    /// Type-name suffixes, in type references and type declarations.
+    TypeSuffix,
+}
+
+impl MatchKind {
+    /// All kinds, as written in TOML.
+    pub const NAMES: &'static [&'static str] = &["method-call", "macro", "type-suffix"];
+
+    /// Parses a kind as written in TOML (`method-call`, `macro`, `type-suffix`).
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "method-call" => Some(Self::MethodCall),
+            "macro" => Some(Self::Macro),
+            "type-suffix" => Some(Self::TypeSuffix),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MethodCall => "method-call",
+            Self::Macro => "macro",
+            Self::TypeSuffix => "type-suffix",
+        })
+    }
+}
+
+/// A generic syntax ban: forbid method calls, macros, or type names
+/// matching the given patterns within a scope.
+#[derive(Debug, Clone)]
+pub struct ForbidPattern {
+    name: String,
+    scope: ScopeRef,
+    kind: MatchKind,
+    patterns: Vec<String>,
+    message: String,
+    doc_ref: Option<String>,
+    severity: Severity,
+}
+
+impl ForbidPattern {
+    /// Creates a new forbid-pattern rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is empty.
+    pub fn new(
+        name: String,
+        scope: ScopeRef,
+        kind: MatchKind,
+        patterns: Vec<String>,
+        message: String,
+        doc_ref: Option<String>,
+        severity: Severity,
+    ) -> Result<Self, ModelError> {
+        if patterns.iter().any(|p| p.trim().is_empty()) {
+            return Err(ModelError::EmptyMatchPattern);
+        }
+        Ok(Self {
+            name,
+            scope,
+            kind,
+            patterns,
+            message,
+            doc_ref,
+            severity,
+        })
+    }
+
+    /// Returns the rule name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the scope reference.
+    #[must_use]
+    pub fn scope(&self) -> &ScopeRef {
+        &self.scope
+    }
+
+    /// Returns the match kind.
+    #[must_use]
+    pub fn kind(&self) -> MatchKind {
+        self.kind
+    }
+
+    /// Returns the violation message.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the document reference.
+    #[must_use]
+    pub fn doc_ref(&self) -> Option<&str> {
+        self.doc_ref.as_deref()
+    }
+
+    /// Returns the severity.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Tests whether a method name, macro path, or type name matches.
+    ///
+    /// - Method calls match by exact name.
+    /// - Macros match by full path, or by last segment when the pattern has
+    ///   no `::` (`debug` matches `tracing::debug!`).
+    /// - Type names match by suffix.
+    #[must_use]
+    pub fn matches(&self, candidate: &str) -> bool {
+        self.patterns.iter().any(|p| match self.kind {
+            MatchKind::MethodCall => candidate == p,
+            MatchKind::Macro => {
+                candidate == p
+                    || (!p.contains("::") && candidate.rsplit("::").next() == Some(p.as_str()))
+            }
+            MatchKind::TypeSuffix => candidate.ends_with(p.as_str()),
+        })
+    }
+}
+
+/// A feature-isolation rule: modules under `parent` (the features) must
+/// not import each other, except through `shared` modules.
+///
+/// A file's feature is the module directly below `parent`, derived from its
+/// path: with parent `features`, `src/features/billing/invoice.rs` and
+/// `src/features/billing.rs` both belong to `billing`.
+#[derive(Debug, Clone)]
+pub struct ForbidSiblingImport {
+    name: String,
+    parent: Vec<String>,
+    shared: Vec<UsePattern>,
+    message: String,
+    doc_ref: Option<String>,
+    severity: Severity,
+}
+
+impl ForbidSiblingImport {
+    /// Creates a new forbid-sibling-import rule.
+    ///
+    /// `parent` and each `shared` entry are module paths relative to the
+    /// crate root (`features`, `features::shared`), optionally prefixed
+    /// with `crate::`. A shared entry covers the module and everything
+    /// below it; `*` matches any one segment (`features::*::api`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `parent` or a `shared` entry is not a module path.
+    pub fn new(
+        name: String,
+        parent: &str,
+        shared: &[String],
+        message: String,
+        doc_ref: Option<String>,
+        severity: Severity,
+    ) -> Result<Self, ModelError> {
+        let parent = module_segments(parent, false)?;
+        let shared = shared
+            .iter()
+            .map(|entry| {
+                let segments = module_segments(entry, true)?;
+                UsePattern::new(&format!("{}::**", segments.join("::")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            name,
+            parent,
+            shared,
+            message,
+            doc_ref,
+            severity,
+        })
+    }
+
+    /// Returns the rule name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the violation message.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the document reference.
+    #[must_use]
+    pub fn doc_ref(&self) -> Option<&str> {
+        self.doc_ref.as_deref()
+    }
+
+    /// Returns the severity.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the feature a file belongs to, given its path relative to
+    /// the project root, or `None` if it is not below `src/<parent>/`.
+    ///
+    /// The parent module's own file (`src/features/mod.rs`) belongs to no
+    /// feature.
+    #[must_use]
+    pub fn feature_of(&self, path: &Path) -> Option<String> {
+        let components: Vec<String> = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let prefix: Vec<&str> = std::iter::once("src")
+            .chain(self.parent.iter().map(String::as_str))
+            .collect();
+        let after = components
+            .windows(prefix.len())
+            .position(|window| window.iter().zip(&prefix).all(|(a, b)| a == b))?
+            + prefix.len();
+
+        let next = components.get(after)?;
+        let feature = if after + 1 == components.len() {
+            next.strip_suffix(".rs")?
+        } else {
+            next.as_str()
+        };
+        (feature != "mod").then(|| feature.to_string())
+    }
+
+    /// Returns the sibling feature a `crate::` use path reaches into from
+    /// `own`, or `None` if the import is allowed.
+    ///
+    /// Imports of the own feature, of `shared` modules, and of items of the
+    /// parent module itself (capitalized names such as `features::Registry`)
+    /// are allowed. Relative paths (`super::`, `self::`) are not checked.
+    #[must_use]
+    pub fn sibling_target<'p>(&self, own: &str, use_path: &'p str) -> Option<&'p str> {
+        let rest = use_path.strip_prefix("crate::")?;
+        let mut segments = rest.split("::");
+        if !self
+            .parent
+            .iter()
+            .all(|parent| segments.next() == Some(parent.as_str()))
+        {
+            return None;
+        }
+        let target = segments.next()?;
+        let is_module = target
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c == '_');
+        if target == own || !is_module || self.shared.iter().any(|s| s.matches(rest)) {
+            return None;
+        }
+        Some(target)
+    }
+}
+
+/// Splits a module path (`features::shared`, optionally `crate::`-prefixed)
+/// into its segments, allowing `*` segments if `wildcards` is set.
+fn module_segments(path: &str, wildcards: bool) -> Result<Vec<String>, ModelError> {
+    let trimmed = path.strip_prefix("crate::").unwrap_or(path);
+    let segments: Vec<String> = trimmed.split("::").map(str::to_string).collect();
+    let valid = segments.iter().all(|segment| {
+        (wildcards && segment == "*")
+            || (!segment.is_empty()
+                && !segment.starts_with(|c: char| c.is_ascii_digit())
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_'))
+    });
+    if valid {
+        Ok(segments)
+    } else {
+        Err(ModelError::InvalidModulePath {
+            path: path.to_string(),
+        })
+    }
+}
+
+/// A derive restriction: forbid deriving the given traits on structs and
+/// enums within a scope, e.g. `serde::Serialize` on domain models.
+#[derive(Debug, Clone)]
+pub struct RestrictDerive {
+    name: String,
+    scope: ScopeRef,
+    deny: Vec<String>,
+    message: String,
+    doc_ref: Option<String>,
+    severity: Severity,
+}
+
+impl RestrictDerive {
+    /// Creates a new restrict-derive rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a denied derive is empty.
+    pub fn new(
+        name: String,
+        scope: ScopeRef,
+        deny: Vec<String>,
+        message: String,
+        doc_ref: Option<String>,
+        severity: Severity,
+    ) -> Result<Self, ModelError> {
+        if deny.iter().any(|d| d.trim().is_empty()) {
+            return Err(ModelError::EmptyMatchPattern);
+        }
+        Ok(Self {
+            name,
+            scope,
+            deny,
+            message,
+            doc_ref,
+            severity,
+        })
+    }
+
+    /// Returns the rule name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the scope reference.
+    #[must_use]
+    pub fn scope(&self) -> &ScopeRef {
+        &self.scope
+    }
+
+    /// Returns the denied derives.
+    #[must_use]
+    pub fn deny(&self) -> &[String] {
+        &self.deny
+    }
+
+    /// Returns the violation message.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the document reference.
+    #[must_use]
+    pub fn doc_ref(&self) -> Option<&str> {
+        self.doc_ref.as_deref()
+    }
+
+    /// Returns the severity.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Tests whether a derive path from a `#[derive(...)]` list is denied.
+    ///
+    /// Paths match in full. When either side is a single segment, the last
+    /// segments are compared instead: `#[derive(Serialize)]` (imported with
+    /// `use serde::Serialize;`) matches `serde::Serialize`, and a bare
+    /// `FromRow` entry matches `#[derive(sqlx::FromRow)]`.
+    #[must_use]
+    pub fn is_denied(&self, derived: &str) -> bool {
+        let last = |path: &str| path.rsplit("::").next().unwrap_or(path).to_string();
+        self.deny.iter().any(|denied| {
+            derived == denied
+                || ((!derived.contains("::") || !denied.contains("::"))
+                    && last(derived) == last(denied))
+        })
+    }
+}
+
 // ────────────────────────────────────────────
 // Aggregate root
 // ────────────────────────────────────────────
@@ -443,13 +1030,39 @@ impl ScopeDep {
 /// Validated declarative configuration.
 ///
 /// All cross-references are verified at construction time.
-/// This is the aggregate root — all queries go through here.
-#[derive(Debug, Clone)]
+/// This is the aggregate root — all queries go through here. The default
+/// is an empty config, which is trivially valid.
+#[derive(Clone, Default)]
 pub struct DeclarativeConfig {
     scopes: HashMap<ScopeName, Scope>,
     restrict_uses: Vec<RestrictUse>,
     require_uses: Vec<RequireUse>,
     scope_deps: Vec<ScopeDep>,
+    crate_layers: Vec<CrateLayer>,
+    forbid_crate_deps: Vec<ForbidCrateDep>,
+    forbid_patterns: Vec<ForbidPattern>,
+    forbid_sibling_imports: Vec<ForbidSiblingImport>,
+    restrict_derives: Vec<RestrictDerive>,
+}
+
+impl fmt::Debug for DeclarativeConfig {
+    /// Lists scopes sorted by name, so the output (part of the rules' cache
+    /// keys) is the same on every run.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut scopes: Vec<&Scope> = self.scopes.values().collect();
+        scopes.sort_by(|a, b| a.name.0.cmp(&b.name.0));
+        f.debug_struct("DeclarativeConfig")
+            .field("scopes", &scopes)
+            .field("restrict_uses", &self.restrict_uses)
+            .field("require_uses", &self.require_uses)
+            .field("scope_deps", &self.scope_deps)
+            .field("crate_layers", &self.crate_layers)
+            .field("forbid_crate_deps", &self.forbid_crate_deps)
+            .field("forbid_patterns", &self.forbid_patterns)
+            .field("forbid_sibling_imports", &self.forbid_sibling_imports)
+            .field("restrict_derives", &self.restrict_derives)
+            .finish()
+    }
 }
 
 impl DeclarativeConfig {
@@ -493,6 +1106,23 @@ impl DeclarativeConfig {
             }
         }
 
+        // Detect require-use rules whose preferred crate is denied
+        for require in &require_uses {
+            for restrict in &restrict_uses {
+                let denied = restrict
+                    .deny
+                    .iter()
+                    .any(|p| p.covers_crate(&require.prefer));
+                if denied && scopes_overlap(&require.scope, &restrict.scope, &scope_map) {
+                    errors.push(ModelError::ConflictingRules {
+                        require: require.name.clone(),
+                        restrict: restrict.name.clone(),
+                        krate: require.prefer.clone(),
+                    });
+                }
+            }
+        }
+
         // Validate scope-dep refs
         for dep in &scope_deps {
             if !scope_map.contains_key(&dep.from) {
@@ -517,12 +1147,114 @@ impl DeclarativeConfig {
                 restrict_uses,
                 require_uses,
                 scope_deps,
+                crate_layers: Vec::new(),
+                forbid_crate_deps: Vec::new(),
+                forbid_patterns: Vec::new(),
+                forbid_sibling_imports: Vec::new(),
+                restrict_derives: Vec::new(),
             })
         } else {
             Err(errors)
         }
     }
 
+    /// Sets the crate layer ordering (innermost first).
+    ///
+    /// # Errors
+    ///
+    /// Returns errors if a layer name is declared more than once.
+    pub fn with_crate_layers(mut self, layers: Vec<CrateLayer>) -> Result<Self, Vec<ModelError>> {
+        let mut errors = Vec::new();
+        for (i, layer) in layers.iter().enumerate() {
+            if layers[..i].iter().any(|l| l.name == layer.name) {
+                errors.push(ModelError::DuplicateCrateLayer {
+                    name: layer.name.clone(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            self.crate_layers = layers;
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Sets the crate dependency bans.
+    #[must_use]
+    pub fn with_forbid_crate_deps(mut self, rules: Vec<ForbidCrateDep>) -> Self {
+        self.forbid_crate_deps = rules;
+        self
+    }
+
+    /// Sets the forbid-pattern rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors if a rule references an undefined scope.
+    pub fn with_forbid_patterns(
+        mut self,
+        rules: Vec<ForbidPattern>,
+    ) -> Result<Self, Vec<ModelError>> {
+        let errors: Vec<ModelError> = rules
+            .iter()
+            .filter_map(|rule| match &rule.scope {
+                ScopeRef::Named(name) if !self.scopes.contains_key(name) => {
+                    Some(ModelError::UnknownScope {
+                        context: format!("forbid-pattern '{}'", rule.name),
+                        name: name.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        if errors.is_empty() {
+            self.forbid_patterns = rules;
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Sets the forbid-sibling-import rules.
+    #[must_use]
+    pub fn with_forbid_sibling_imports(mut self, rules: Vec<ForbidSiblingImport>) -> Self {
+        self.forbid_sibling_imports = rules;
+        self
+    }
+
+    /// Sets the restrict-derive rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors if a rule references an undefined scope.
+    pub fn with_restrict_derives(
+        mut self,
+        rules: Vec<RestrictDerive>,
+    ) -> Result<Self, Vec<ModelError>> {
+        let errors: Vec<ModelError> = rules
+            .iter()
+            .filter_map(|rule| match &rule.scope {
+                ScopeRef::Named(name) if !self.scopes.contains_key(name) => {
+                    Some(ModelError::UnknownScope {
+                        context: format!("restrict-derive '{}'", rule.name),
+                        name: name.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+
+        if errors.is_empty() {
+            self.restrict_derives = rules;
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Creates an empty declarative config (no declarative rules).
     #[must_use]
     pub fn empty() -> Self {
@@ -531,13 +1263,25 @@ impl DeclarativeConfig {
             restrict_uses: Vec::new(),
             require_uses: Vec::new(),
             scope_deps: Vec::new(),
+            crate_layers: Vec::new(),
+            forbid_crate_deps: Vec::new(),
+            forbid_patterns: Vec::new(),
+            forbid_sibling_imports: Vec::new(),
+            restrict_derives: Vec::new(),
         }
     }
 
     /// Returns true if no declarative rules are defined.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.restrict_uses.is_empty() && self.require_uses.is_empty() && self.scope_deps.is_empty()
+        self.restrict_uses.is_empty()
+            && self.require_uses.is_empty()
+            && self.scope_deps.is_empty()
+            && self.crate_layers.is_empty()
+            && self.forbid_crate_deps.is_empty()
+            && self.forbid_patterns.is_empty()
+            && self.forbid_sibling_imports.is_empty()
+            && self.restrict_derives.is_empty()
     }
 
     /// Returns all defined scopes.
@@ -564,6 +1308,46 @@ impl DeclarativeConfig {
         &self.scope_deps
     }
 
+    /// Returns the crate layers, innermost first.
+    #[must_use]
+    pub fn crate_layers(&self) -> &[CrateLayer] {
+        &self.crate_layers
+    }
+
+    /// Returns all forbid-crate-dep rules.
+    #[must_use]
+    pub fn forbid_crate_deps(&self) -> &[ForbidCrateDep] {
+        &self.forbid_crate_deps
+    }
+
+    /// Returns all forbid-pattern rules.
+    #[must_use]
+    pub fn forbid_patterns(&self) -> &[ForbidPattern] {
+        &self.forbid_patterns
+    }
+
+    /// Returns all forbid-sibling-import rules.
+    #[must_use]
+    pub fn forbid_sibling_imports(&self) -> &[ForbidSiblingImport] {
+        &self.forbid_sibling_imports
+    }
+
+    /// Returns all restrict-derive rules.
+    #[must_use]
+    pub fn restrict_derives(&self) -> &[RestrictDerive] {
+        &self.restrict_derives
+    }
+
+    /// Resolves the layer index (innermost = 0) a crate belongs to.
+    ///
+    /// The first matching layer wins.
+    #[must_use]
+    pub fn crate_layer_index(&self, crate_name: &str) -> Option<usize> {
+        self.crate_layers
+            .iter()
+            .position(|layer| layer.contains(crate_name))
+    }
+
     /// Gets a scope by name.
     #[must_use]
     pub fn scope(&self, name: &ScopeName) -> Option<&Scope> {
@@ -593,6 +1377,31 @@ impl DeclarativeConfig {
     }
 }
 
+/// Tests whether two scope references may cover the same file.
+///
+/// Unknown named scopes never overlap; they are reported separately.
+fn scopes_overlap(a: &ScopeRef, b: &ScopeRef, scopes: &HashMap<ScopeName, Scope>) -> bool {
+    fn patterns<'a>(
+        scope_ref: &'a ScopeRef,
+        scopes: &'a HashMap<ScopeName, Scope>,
+    ) -> &'a [GlobPattern] {
+        match scope_ref {
+            ScopeRef::Named(name) => scopes.get(name).map_or(&[], Scope::patterns),
+            ScopeRef::Inline(patterns) => patterns,
+        }
+    }
+
+    if let (ScopeRef::Named(a), ScopeRef::Named(b)) = (a, b) {
+        if a == b {
+            return true;
+        }
+    }
+    let b = patterns(b, scopes);
+    patterns(a, scopes)
+        .iter()
+        .any(|p| b.iter().any(|q| p.overlaps(q)))
+}
+
 // ────────────────────────────────────────────
 // Errors
 // ────────────────────────────────────────────
@@ -628,6 +1437,17 @@ pub enum ModelError {
     #[error("use pattern must not be empty")]
     EmptyUsePattern,
 
+    /// A forbid-pattern entry is empty.
+    #[error("match pattern must not be empty")]
+    EmptyMatchPattern,
+
+    /// A module path is empty or has a segment that is not an identifier.
+    #[error("invalid module path `{path}`: expected `name::name`")]
+    InvalidModulePath {
+        /// The invalid path.
+        path: String,
+    },
+
     /// A scope reference points to an undefined scope.
     #[error("{context}: unknown scope `{name}`")]
     UnknownScope {
@@ -636,6 +1456,28 @@ pub enum ModelError {
         /// The undefined scope name.
         name: ScopeName,
     },
+
+    /// A require-use rule prefers a crate that a restrict-use rule denies
+    /// in an overlapping scope.
+    #[error(
+        "require-use '{require}' prefers `{krate}`, but restrict-use '{restrict}' \
+         denies it in an overlapping scope"
+    )]
+    ConflictingRules {
+        /// The require-use rule.
+        require: String,
+        /// The restrict-use rule.
+        restrict: String,
+        /// The preferred crate.
+        krate: String,
+    },
+
+    /// A crate layer name is declared more than once.
+    #[error("crate-layers: duplicate layer `{name}`")]
+    DuplicateCrateLayer {
+        /// The duplicated layer name.
+        name: ScopeName,
+    },
 }
 
 // ────────────────────────────────────────────
@@ -698,6 +1540,14 @@ mod tests {
         assert!(!pat.matches(Path::new("src/infra/db.rs")));
     }
 
+    #[test]
+    fn glob_pattern_matches_windows_separators() {
+        let path = std::path::PathBuf::from(["src", "domain", "entity.rs"].join("\\"));
+        assert!(GlobPattern::new("src/domain/**").unwrap().matches(&path));
+        assert!(GlobPattern::new("src/**/*.rs").unwrap().matches(&path));
+        assert!(!GlobPattern::new("src/infra/**").unwrap().matches(&path));
+    }
+
     // -- UsePattern --
 
     #[test]
@@ -813,6 +1663,107 @@ mod tests {
         assert_eq!(dep.display_name(), "deny-scope-dep:domain");
     }
 
+    // -- ForbidSiblingImport --
+
+    fn sibling_rule(shared: &[&str]) -> ForbidSiblingImport {
+        ForbidSiblingImport::new(
+            "feature-isolation".to_string(),
+            "crate::features",
+            &shared.iter().map(|s| (*s).to_string()).collect::<Vec<_>>(),
+            "msg".to_string(),
+            None,
+            Severity::Error,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn sibling_import_feature_from_path() {
+        let rule = sibling_rule(&[]);
+        let feature = |p: &str| rule.feature_of(Path::new(p));
+        assert_eq!(
+            feature("src/features/billing/invoice.rs").as_deref(),
+            Some("billing")
+        );
+        assert_eq!(
+            feature("src/features/billing/mod.rs").as_deref(),
+            Some("billing")
+        );
+        assert_eq!(
+            feature("src/features/billing.rs").as_deref(),
+            Some("billing")
+        );
+        assert_eq!(
+            feature("crates/app/src/features/orders.rs").as_deref(),
+            Some("orders")
+        );
+        assert_eq!(feature("src/features/mod.rs"), None);
+        assert_eq!(feature("src/features.rs"), None);
+        assert_eq!(feature("src/other/features/billing.rs"), None);
+    }
+
+    #[test]
+    fn sibling_import_target() {
+        let rule = sibling_rule(&["features::shared", "features::*::api"]);
+        let target = |p: &str| rule.sibling_target("billing", p).map(str::to_string);
+        assert_eq!(
+            target("crate::features::orders::Order").as_deref(),
+            Some("orders")
+        );
+        assert_eq!(target("crate::features::orders").as_deref(), Some("orders"));
+        assert_eq!(target("crate::features::billing::Invoice").as_deref(), None);
+        assert_eq!(target("crate::features::shared::Money").as_deref(), None);
+        assert_eq!(target("crate::features::shared").as_deref(), None);
+        assert_eq!(
+            target("crate::features::orders::api::place").as_deref(),
+            None
+        );
+        assert_eq!(target("crate::features::Registry").as_deref(), None);
+        assert_eq!(target("crate::domain::orders").as_deref(), None);
+        assert_eq!(target("super::orders::Order").as_deref(), None);
+    }
+
+    #[test]
+    fn sibling_import_rejects_invalid_module_path() {
+        for (parent, shared) in [
+            ("", "x"),
+            ("features::", "x"),
+            ("features", "shared-kernel"),
+        ] {
+            let result = ForbidSiblingImport::new(
+                "bad".to_string(),
+                parent,
+                &[shared.to_string()],
+                "msg".to_string(),
+                None,
+                Severity::Error,
+            );
+            assert!(
+                matches!(result, Err(ModelError::InvalidModulePath { .. })),
+                "{parent:?} {shared:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn restrict_derive_matches_full_path_or_last_segment() {
+        let rule = RestrictDerive::new(
+            "no-serde".to_string(),
+            ScopeRef::Inline(vec![GlobPattern::new("src/domain/**").unwrap()]),
+            vec!["serde::Serialize".to_string(), "FromRow".to_string()],
+            "msg".to_string(),
+            None,
+            Severity::Error,
+        )
+        .unwrap();
+        assert!(rule.is_denied("serde::Serialize"));
+        assert!(rule.is_denied("Serialize"));
+        assert!(rule.is_denied("sqlx::FromRow"));
+        assert!(!rule.is_denied("other::Serialize"));
+        assert!(!rule.is_denied("Deserialize"));
+        assert!(!rule.is_denied("Clone"));
+    }
+
     // -- DeclarativeConfig (aggregate root validation) --
 
     #[test]
@@ -9,76 +9,13 @@ use syn::spanned::Spanned;
 use syn::visit::Visit;
 
 use crate::context::FileContext;
-use crate::declarative::model::{DeclarativeConfig, RequireUse, RestrictUse, ScopeDep};
+use crate::declarative::model::{
+    DeclarativeConfig, ForbidPattern, ForbidSiblingImport, GlobPattern, MatchKind, RequireUse,
+    RestrictDerive, RestrictUse, ScopeDep, ScopeName,
+};
 use crate::rule::Rule;
-use crate::types::{Location, Severity, Violation};
-
-// ────────────────────────────────────────────
-// UseTree expansion
-// ────────────────────────────────────────────
-
-/// A resolved use-path with its source span.
-pub(crate) struct ResolvedUse {
-    /// Full path like `sqlx::Pool` or `std::collections::HashMap`.
-    pub(crate) path: String,
-    /// Span of the leaf node for error reporting.
-    pub(crate) span: proc_macro2::Span,
-}
-
-/// Recursively expands a [`syn::UseTree`] into flat `::` separated paths.
-///
-/// For example, `use std::collections::{HashMap, BTreeMap};` expands to
-/// `["std::collections::HashMap", "std::collections::BTreeMap"]`.
-pub(crate) fn expand_use_tree(tree: &syn::UseTree, prefix: &str) -> Vec<ResolvedUse> {
-    match tree {
-        syn::UseTree::Path(p) => {
-            let new_prefix = if prefix.is_empty() {
-                p.ident.to_string()
-            } else {
-                format!("{prefix}::{}", p.ident)
-            };
-            expand_use_tree(&p.tree, &new_prefix)
-        }
-        syn::UseTree::Name(n) => {
-            let path = if prefix.is_empty() {
-                n.ident.to_string()
-            } else {
-                format!("{prefix}::{}", n.ident)
-            };
-            vec![ResolvedUse {
-                path,
-                span: n.ident.span(),
-            }]
-        }
-        syn::UseTree::Rename(r) => {
-            let path = if prefix.is_empty() {
-                r.ident.to_string()
-            } else {
-                format!("{prefix}::{}", r.ident)
-            };
-            vec![ResolvedUse {
-                path,
-                span: r.ident.span(),
-            }]
-        }
-        syn::UseTree::Glob(g) => {
-            let path = if prefix.is_empty() {
-                "*".to_string()
-            } else {
-                format!("{prefix}::*")
-            };
-            vec![ResolvedUse {
-                path,
-                span: g.span(),
-            }]
-        }
-        syn::UseTree::Group(g) => g
-            .items
-            .iter()
-            .flat_map(|item| expand_use_tree(item, prefix))
-            .collect(),
-    }
-}
+use crate::types::{Location, RuleOrigin, Severity, Violation};
+use crate::utils::paths::expand_use_tree;
 
 // ────────────────────────────────────────────
 // RestrictUseRule
@@ -91,6 +28,7 @@ const RESTRICT_USE_CODE: &str = "ALD001";
 ///
 /// For each file, determines which restrict-use rules apply based on
 /// scope membership, then checks every `use` import against the deny list.
+#[derive(Debug)]
 pub struct RestrictUseRule {
     config: Arc<DeclarativeConfig>,
 }
@@ -189,6 +127,7 @@ const REQUIRE_USE_CODE: &str = "ALD002";
 ///
 /// Flags imports of discouraged crates (`over`) and suggests the
 /// preferred crate (`prefer`) instead.
+#[derive(Debug)]
 pub struct RequireUseRule {
     config: Arc<DeclarativeConfig>,
 }
@@ -304,6 +243,7 @@ const SCOPE_DEP_CODE: &str = "ALD003";
 ///
 /// - Only checks `crate::` prefixed paths (not `self::` or `super::`)
 /// - Assumes standard `src/` layout for module-to-file mapping
+#[derive(Debug)]
 pub struct ScopeDepRule {
     config: Arc<DeclarativeConfig>,
 }
@@ -404,6 +344,30 @@ fn resolve_target_scopes<'a>(
     vec![]
 }
 
+/// Describes the `[[deny-scope-dep]]` entry behind a violation.
+fn scope_dep_origin(dep: &ScopeDep) -> RuleOrigin {
+    let to: Vec<String> = dep
+        .denied_targets()
+        .iter()
+        .map(|scope| format!("`{scope}`"))
+        .collect();
+    RuleOrigin::new(format!(
+        "[[deny-scope-dep]] `{}`: from `{}` to {}",
+        dep.display_name(),
+        dep.from_scope(),
+        to.join(", ")
+    ))
+}
+
+/// Describes the `[[scopes]]` entry named `name`.
+fn scope_origin(config: &DeclarativeConfig, name: &ScopeName) -> RuleOrigin {
+    let patterns: Vec<&str> = config
+        .scope(name)
+        .map(|scope| scope.patterns().iter().map(GlobPattern::as_str).collect())
+        .unwrap_or_default();
+    RuleOrigin::new(format!("[[scopes]] `{name}`: {}", patterns.join(", ")))
+}
+
 impl<'ast> Visit<'ast> for ScopeDepVisitor<'_> {
     fn visit_item_use(&mut self, node: &'ast syn::ItemUse) {
         let resolved = expand_use_tree(&node.tree, "");
@@ -437,6 +401,10 @@ impl<'ast> Visit<'ast> for ScopeDepVisitor<'_> {
                         if let Some(doc) = dep.doc_ref() {
                             violation = violation.with_doc_ref(doc);
                         }
+                        violation = violation
+                            .with_origin(scope_dep_origin(dep))
+                            .with_origin(scope_origin(self.config, dep.from_scope()))
+                            .with_origin(scope_origin(self.config, target_scope));
 
                         self.violations.push(violation);
                     }
@@ -448,6 +416,409 @@ impl<'ast> Visit<'ast> for ScopeDepVisitor<'_> {
     }
 }
 
+// ────────────────────────────────────────────
+// ForbidPatternRule
+// ────────────────────────────────────────────
+
+const FORBID_PATTERN_NAME: &str = "forbid-pattern";
+const FORBID_PATTERN_CODE: &str = "ALD006";
+
+/// A per-file rule that enforces `[[forbid-pattern]]` declarations.
+///
+/// Reports method calls, macro invocations, and type names matching
+/// the patterns of any rule whose scope contains the file.
+#[derive(Debug)]
+pub struct ForbidPatternRule {
+    config: Arc<DeclarativeConfig>,
+}
+
+impl ForbidPatternRule {
+    /// Creates a new forbid-pattern rule backed by the given config.
+    #[must_use]
+    pub fn new(config: Arc<DeclarativeConfig>) -> Self {
+        Self { config }
+    }
+}
+
+impl Rule for ForbidPatternRule {
+    fn name(&self) -> &'static str {
+        FORBID_PATTERN_NAME
+    }
+
+    fn code(&self) -> &'static str {
+        FORBID_PATTERN_CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Deny method calls, macros, or type names within a scope"
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        let applicable: Vec<&ForbidPattern> = self
+            .config
+            .forbid_patterns()
+            .iter()
+            .filter(|r| {
+                self.config
+                    .scope_ref_contains(r.scope(), &ctx.relative_path)
+            })
+            .collect();
+
+        if applicable.is_empty() {
+            return vec![];
+        }
+
+        let mut visitor = ForbidPatternVisitor {
+            ctx,
+            applicable,
+            violations: Vec::new(),
+        };
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct ForbidPatternVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    applicable: Vec<&'a ForbidPattern>,
+    violations: Vec<Violation>,
+}
+
+impl ForbidPatternVisitor<'_> {
+    fn check(&mut self, kind: MatchKind, candidate: &str, span: proc_macro2::Span) {
+        for rule in &self.applicable {
+            if rule.kind() != kind || !rule.matches(candidate) {
+                continue;
+            }
+
+            let start = span.start();
+            let location =
+                Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+            let mut violation = Violation::new(
+                FORBID_PATTERN_CODE,
+                rule.name(),
+                rule.severity(),
+                location,
+                format!("{}: `{candidate}`", rule.message()),
+            );
+            if let Some(doc) = rule.doc_ref() {
+                violation = violation.with_doc_ref(doc);
+            }
+            self.violations.push(violation);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ForbidPatternVisitor<'_> {
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        self.check(
+            MatchKind::MethodCall,
+            &node.method.to_string(),
+            node.method.span(),
+        );
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        let path = crate::utils::path_to_string(&node.path);
+        self.check(MatchKind::Macro, &path, node.path.span());
+        syn::visit::visit_macro(self, node);
+    }
+
+    fn visit_type_path(&mut self, node: &'ast syn::TypePath) {
+        if let Some(segment) = node.path.segments.last() {
+            self.check(
+                MatchKind::TypeSuffix,
+                &segment.ident.to_string(),
+                segment.ident.span(),
+            );
+        }
+        syn::visit::visit_type_path(self, node);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        self.check(
+            MatchKind::TypeSuffix,
+            &node.ident.to_string(),
+            node.ident.span(),
+        );
+        syn::visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast syn::ItemEnum) {
+        self.check(
+            MatchKind::TypeSuffix,
+            &node.ident.to_string(),
+            node.ident.span(),
+        );
+        syn::visit::visit_item_enum(self, node);
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast syn::ItemTrait) {
+        self.check(
+            MatchKind::TypeSuffix,
+            &node.ident.to_string(),
+            node.ident.span(),
+        );
+        syn::visit::visit_item_trait(self, node);
+    }
+
+    fn visit_item_type(&mut self, node: &'ast syn::ItemType) {
+        self.check(
+            MatchKind::TypeSuffix,
+            &node.ident.to_string(),
+            node.ident.span(),
+        );
+        syn::visit::visit_item_type(self, node);
+    }
+}
+
+// ────────────────────────────────────────────
+// ForbidSiblingImportRule
+// ────────────────────────────────────────────
+
+const FORBID_SIBLING_IMPORT_NAME: &str = "forbid-sibling-import";
+const FORBID_SIBLING_IMPORT_CODE: &str = "ALD007";
+
+/// A per-file rule that enforces `[[forbid-sibling-import]]` declarations.
+///
+/// For files inside a feature module, reports `use crate::<parent>::<other>`
+/// imports of sibling features that do not go through a shared module.
+///
+/// # Limitations (v1)
+///
+/// - Only checks `crate::` prefixed paths (not `self::` or `super::`)
+/// - Assumes standard `src/` layout for module-to-file mapping
+#[derive(Debug)]
+pub struct ForbidSiblingImportRule {
+    config: Arc<DeclarativeConfig>,
+}
+
+impl ForbidSiblingImportRule {
+    /// Creates a new forbid-sibling-import rule backed by the given config.
+    #[must_use]
+    pub fn new(config: Arc<DeclarativeConfig>) -> Self {
+        Self { config }
+    }
+}
+
+impl Rule for ForbidSiblingImportRule {
+    fn name(&self) -> &'static str {
+        FORBID_SIBLING_IMPORT_NAME
+    }
+
+    fn code(&self) -> &'static str {
+        FORBID_SIBLING_IMPORT_CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Deny imports between sibling feature modules"
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        let applicable: Vec<(&ForbidSiblingImport, String)> = self
+            .config
+            .forbid_sibling_imports()
+            .iter()
+            .filter_map(|r| Some((r, r.feature_of(&ctx.relative_path)?)))
+            .collect();
+
+        if applicable.is_empty() {
+            return vec![];
+        }
+
+        let mut visitor = ForbidSiblingImportVisitor {
+            ctx,
+            applicable,
+            violations: Vec::new(),
+        };
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct ForbidSiblingImportVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    /// Applicable rules, with the feature this file belongs to.
+    applicable: Vec<(&'a ForbidSiblingImport, String)>,
+    violations: Vec<Violation>,
+}
+
+impl<'ast> Visit<'ast> for ForbidSiblingImportVisitor<'_> {
+    fn visit_item_use(&mut self, node: &'ast syn::ItemUse) {
+        let resolved = expand_use_tree(&node.tree, "");
+
+        for use_item in &resolved {
+            for (rule, feature) in &self.applicable {
+                let Some(target) = rule.sibling_target(feature, &use_item.path) else {
+                    continue;
+                };
+                let start = use_item.span.start();
+                let location =
+                    Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+
+                let mut violation = Violation::new(
+                    FORBID_SIBLING_IMPORT_CODE,
+                    rule.name(),
+                    rule.severity(),
+                    location,
+                    format!(
+                        "{}: `{}` (feature `{feature}` \u{2192} feature `{target}`)",
+                        rule.message(),
+                        use_item.path,
+                    ),
+                );
+                if let Some(doc) = rule.doc_ref() {
+                    violation = violation.with_doc_ref(doc);
+                }
+
+                self.violations.push(violation);
+            }
+        }
+
+        syn::visit::visit_item_use(self, node);
+    }
+}
+
+// ────────────────────────────────────────────
+// RestrictDeriveRule
+// ────────────────────────────────────────────
+
+const RESTRICT_DERIVE_NAME: &str = "restrict-derive";
+const RESTRICT_DERIVE_CODE: &str = "ALD008";
+
+/// A per-file rule that enforces `[[restrict-derive]]` declarations.
+///
+/// Reports denied derives on structs and enums in files within a rule's
+/// scope, from `#[derive(...)]` and `#[cfg_attr(..., derive(...))]`.
+#[derive(Debug)]
+pub struct RestrictDeriveRule {
+    config: Arc<DeclarativeConfig>,
+}
+
+impl RestrictDeriveRule {
+    /// Creates a new restrict-derive rule backed by the given config.
+    #[must_use]
+    pub fn new(config: Arc<DeclarativeConfig>) -> Self {
+        Self { config }
+    }
+}
+
+impl Rule for RestrictDeriveRule {
+    fn name(&self) -> &'static str {
+        RESTRICT_DERIVE_NAME
+    }
+
+    fn code(&self) -> &'static str {
+        RESTRICT_DERIVE_CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Deny derives of specified traits on types within a scope"
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        let applicable: Vec<&RestrictDerive> = self
+            .config
+            .restrict_derives()
+            .iter()
+            .filter(|r| {
+                self.config
+                    .scope_ref_contains(r.scope(), &ctx.relative_path)
+            })
+            .collect();
+
+        if applicable.is_empty() {
+            return vec![];
+        }
+
+        let mut visitor = RestrictDeriveVisitor {
+            ctx,
+            applicable,
+            violations: Vec::new(),
+        };
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+/// Collects the paths in `#[derive(...)]` lists, including those nested in
+/// `#[cfg_attr(predicate, derive(...))]`.
+fn derived_paths(attrs: &[syn::Attribute]) -> Vec<syn::Path> {
+    type Paths = syn::punctuated::Punctuated<syn::Path, syn::Token![,]>;
+    type Metas = syn::punctuated::Punctuated<syn::Meta, syn::Token![,]>;
+
+    let mut paths = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("derive") {
+            if let Ok(list) = attr.parse_args_with(Paths::parse_terminated) {
+                paths.extend(list);
+            }
+        } else if attr.path().is_ident("cfg_attr") {
+            let Ok(metas) = attr.parse_args_with(Metas::parse_terminated) else {
+                continue;
+            };
+            // The first entry is the predicate.
+            for meta in metas.iter().skip(1) {
+                if let syn::Meta::List(list) = meta {
+                    if list.path.is_ident("derive") {
+                        if let Ok(list) = list.parse_args_with(Paths::parse_terminated) {
+                            paths.extend(list);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    paths
+}
+
+struct RestrictDeriveVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    applicable: Vec<&'a RestrictDerive>,
+    violations: Vec<Violation>,
+}
+
+impl RestrictDeriveVisitor<'_> {
+    fn check(&mut self, attrs: &[syn::Attribute], ident: &syn::Ident) {
+        for path in derived_paths(attrs) {
+            let derived = crate::utils::path_to_string(&path);
+            for rule in &self.applicable {
+                if !rule.is_denied(&derived) {
+                    continue;
+                }
+
+                let start = path.span().start();
+                let location =
+                    Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+                let mut violation = Violation::new(
+                    RESTRICT_DERIVE_CODE,
+                    rule.name(),
+                    rule.severity(),
+                    location,
+                    format!("{}: `{derived}` derived on `{ident}`", rule.message()),
+                );
+                if let Some(doc) = rule.doc_ref() {
+                    violation = violation.with_doc_ref(doc);
+                }
+                self.violations.push(violation);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for RestrictDeriveVisitor<'_> {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        self.check(&node.attrs, &node.ident);
+        syn::visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast syn::ItemEnum) {
+        self.check(&node.attrs, &node.ident);
+        syn::visit::visit_item_enum(self, node);
+    }
+}
+
 // ────────────────────────────────────────────
 // Tests
 // ────────────────────────────────────────────
@@ -456,20 +827,14 @@ impl<'ast> Visit<'ast> for ScopeDepVisitor<'_> {
 mod tests {
     use super::*;
     use crate::declarative::model::*;
-    use std::path::PathBuf;
+    use std::path::Path;
 
     fn parse_file(code: &str) -> syn::File {
         syn::parse_file(code).expect("test code should parse")
     }
 
     fn make_ctx<'a>(path: &'a str, content: &'a str) -> FileContext<'a> {
-        FileContext {
-            path: std::path::Path::new(path),
-            content,
-            is_test: false,
-            module_path: vec![],
-            relative_path: PathBuf::from(path),
-        }
+        FileContext::new(Path::new(path), content, Path::new(""))
     }
 
     fn extract_use_tree(code: &str) -> syn::ItemUse {
@@ -959,4 +1324,213 @@ mod tests {
         let scopes = resolve_target_scopes(&config, "self::utils::helper");
         assert!(scopes.is_empty());
     }
+
+    // ── ForbidPatternRule ──
+
+    fn make_forbid_pattern_config() -> Arc<DeclarativeConfig> {
+        let scope = |p: &str| ScopeRef::Inline(vec![GlobPattern::new(p).unwrap()]);
+        let rule = |name: &str, kind, patterns: &[&str]| {
+            ForbidPattern::new(
+                name.to_string(),
+                scope("src/domain/**"),
+                kind,
+                patterns.iter().map(|p| (*p).to_string()).collect(),
+                "Not allowed in domain".to_string(),
+                Some("docs/domain.md".to_string()),
+                Severity::Error,
+            )
+            .unwrap()
+        };
+        let config = DeclarativeConfig::new(vec![], vec![], vec![], vec![])
+            .unwrap()
+            .with_forbid_patterns(vec![
+                rule("no-clone", MatchKind::MethodCall, &["clone"]),
+                rule(
+                    "no-debug-output",
+                    MatchKind::Macro,
+                    &["dbg", "tracing::debug"],
+                ),
+                rule("no-dto", MatchKind::TypeSuffix, &["Dto"]),
+            ])
+            .unwrap();
+        Arc::new(config)
+    }
+
+    #[test]
+    fn forbid_pattern_detects_each_kind() {
+        let rule = ForbidPatternRule::new(make_forbid_pattern_config());
+        let code = r#"
+struct UserDto;
+fn f(user: &UserDto) -> UserDto {
+    dbg!(user);
+    tracing::debug!("x");
+    debug!("not tracing");
+    user.clone()
+}
+"#;
+        let ctx = make_ctx("src/domain/user.rs", code);
+        let violations = rule.check(&ctx, &parse_file(code));
+
+        let found: Vec<_> = violations
+            .iter()
+            .map(|v| (v.rule.as_str(), v.location.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("no-dto", 2),
+                ("no-dto", 3),
+                ("no-dto", 3),
+                ("no-debug-output", 4),
+                ("no-debug-output", 5),
+                ("no-clone", 7),
+            ]
+        );
+        assert_eq!(violations[0].code, FORBID_PATTERN_CODE);
+        assert_eq!(violations[0].doc_ref.as_deref(), Some("docs/domain.md"));
+        assert!(violations[5].message.contains("`clone`"));
+    }
+
+    #[test]
+    fn forbid_pattern_skips_file_outside_scope() {
+        let rule = ForbidPatternRule::new(make_forbid_pattern_config());
+        let code = "fn f(x: &String) -> String { dbg!(x); x.clone() }";
+        let ctx = make_ctx("src/infra/db.rs", code);
+        assert!(rule.check(&ctx, &parse_file(code)).is_empty());
+    }
+
+    // -- ForbidSiblingImportRule --
+
+    fn make_sibling_config() -> Arc<DeclarativeConfig> {
+        let rule = ForbidSiblingImport::new(
+            "feature-isolation".to_string(),
+            "features",
+            &["features::shared".to_string()],
+            "Features must not import each other".to_string(),
+            Some("docs/features.md".to_string()),
+            Severity::Error,
+        )
+        .unwrap();
+        let config = DeclarativeConfig::new(vec![], vec![], vec![], vec![])
+            .unwrap()
+            .with_forbid_sibling_imports(vec![rule]);
+        Arc::new(config)
+    }
+
+    #[test]
+    fn forbid_sibling_import_detects_cross_feature_use() {
+        let rule = ForbidSiblingImportRule::new(make_sibling_config());
+        let code = r"
+use crate::features::orders::{Order, OrderId};
+use crate::features::billing::Invoice;
+use crate::features::shared::Money;
+use crate::infra::db;
+";
+        let ctx = make_ctx("src/features/billing/service.rs", code);
+        let violations = rule.check(&ctx, &parse_file(code));
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].code, FORBID_SIBLING_IMPORT_CODE);
+        assert_eq!(violations[0].rule, "feature-isolation");
+        assert_eq!(violations[0].location.line, 2);
+        assert_eq!(
+            violations[0].message,
+            "Features must not import each other: `crate::features::orders::Order` \
+             (feature `billing` \u{2192} feature `orders`)"
+        );
+        assert_eq!(violations[0].doc_ref.as_deref(), Some("docs/features.md"));
+    }
+
+    #[test]
+    fn forbid_sibling_import_skips_files_outside_features() {
+        let rule = ForbidSiblingImportRule::new(make_sibling_config());
+        let code = "use crate::features::orders::Order;";
+        for path in ["src/main.rs", "src/features/mod.rs"] {
+            let ctx = make_ctx(path, code);
+            assert!(rule.check(&ctx, &parse_file(code)).is_empty(), "{path}");
+        }
+    }
+
+    // -- RestrictDeriveRule --
+
+    fn make_restrict_derive_config() -> Arc<DeclarativeConfig> {
+        let rule = RestrictDerive::new(
+            "no-infra-derives".to_string(),
+            ScopeRef::Inline(vec![GlobPattern::new("src/domain/**").unwrap()]),
+            vec!["serde::Serialize".to_string(), "sqlx::FromRow".to_string()],
+            "Domain models must not derive infrastructure traits".to_string(),
+            Some("docs/domain.md".to_string()),
+            Severity::Warning,
+        )
+        .unwrap();
+        let config = DeclarativeConfig::new(vec![], vec![], vec![], vec![])
+            .unwrap()
+            .with_restrict_derives(vec![rule])
+            .unwrap();
+        Arc::new(config)
+    }
+
+    #[test]
+    fn restrict_derive_detects_denied_derives() {
+        let rule = RestrictDeriveRule::new(make_restrict_derive_config());
+        let code = r#"
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct User;
+
+#[derive(sqlx::FromRow)]
+pub enum Status { Active }
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Order;
+"#;
+        let ctx = make_ctx("src/domain/user.rs", code);
+        let violations = rule.check(&ctx, &parse_file(code));
+
+        let found: Vec<_> = violations
+            .iter()
+            .map(|v| (v.location.line, v.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    4,
+                    "Domain models must not derive infrastructure traits: \
+                     `Serialize` derived on `User`"
+                ),
+                (
+                    7,
+                    "Domain models must not derive infrastructure traits: \
+                     `sqlx::FromRow` derived on `Status`"
+                ),
+                (
+                    10,
+                    "Domain models must not derive infrastructure traits: \
+                     `serde::Serialize` derived on `Order`"
+                ),
+            ]
+        );
+        assert_eq!(violations[0].code, RESTRICT_DERIVE_CODE);
+        assert_eq!(violations[0].rule, "no-infra-derives");
+        assert_eq!(violations[0].severity, Severity::Warning);
+        assert_eq!(violations[0].location.column, 24);
+        assert_eq!(violations[0].doc_ref.as_deref(), Some("docs/domain.md"));
+    }
+
+    #[test]
+    fn restrict_derive_allows_unmatched_derives() {
+        let rule = RestrictDeriveRule::new(make_restrict_derive_config());
+        let code = r"
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, other::Serialize)]
+pub struct User;
+";
+        let ctx = make_ctx("src/domain/user.rs", code);
+        assert!(rule.check(&ctx, &parse_file(code)).is_empty());
+
+        let code = "#[derive(serde::Serialize)]\npub struct UserDto;\n";
+        let ctx = make_ctx("src/infra/dto.rs", code);
+        assert!(rule.check(&ctx, &parse_file(code)).is_empty());
+    }
 }
@@ -0,0 +1,237 @@
+//! Project-wide validation of `[[scopes]]` against the discovered files.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::context::ProjectContext;
+use crate::declarative::model::{DeclarativeConfig, GlobPattern, Scope};
+use crate::rule::ProjectRule;
+use crate::types::{Location, Severity, Violation};
+use crate::utils::allowance::DIRECTIVE_CODE;
+
+const EMPTY_SCOPE_NAME: &str = "empty-scope";
+
+/// A project rule that warns about `[[scopes]]` matching no source file.
+///
+/// A typo in a scope glob (`src/doman/**`) silently disables every rule
+/// referencing the scope. Reported under `AL000` like other configuration
+/// diagnostics, at the scope's entry in the config file when the file is
+/// known; disable with `[rules.empty-scope] enabled = false`.
+#[derive(Debug)]
+pub struct EmptyScopeRule {
+    config: Arc<DeclarativeConfig>,
+    config_file: Option<PathBuf>,
+}
+
+impl EmptyScopeRule {
+    /// Creates a new empty-scope rule backed by the given config.
+    #[must_use]
+    pub fn new(config: Arc<DeclarativeConfig>) -> Self {
+        Self {
+            config,
+            config_file: None,
+        }
+    }
+
+    /// Reports empty scopes at their entry in `path`, the file the config
+    /// was loaded from, instead of at the project root.
+    #[must_use]
+    pub fn with_config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_file = Some(path.into());
+        self
+    }
+
+    /// Where to report `scope`: its `name` entry in the config file, the
+    /// config file itself if the entry is not found, or the project root
+    /// if the config file is unknown.
+    fn location(&self, scope: &Scope, content: Option<&str>, root: &Path) -> Location {
+        let Some(path) = &self.config_file else {
+            return Location::new(root.to_path_buf(), 0, 0);
+        };
+        match content.and_then(|c| scope_line(c, scope.name().as_str())) {
+            Some(line) => Location::new(path.clone(), line, 1),
+            None => Location::new(path.clone(), 0, 0),
+        }
+    }
+}
+
+impl ProjectRule for EmptyScopeRule {
+    fn name(&self) -> &'static str {
+        EMPTY_SCOPE_NAME
+    }
+
+    fn code(&self) -> &'static str {
+        DIRECTIVE_CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Warn about scopes whose patterns match no source file"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Violation> {
+        let mut empty: Vec<&Scope> = self
+            .config
+            .scopes()
+            .values()
+            .filter(|scope| !ctx.source_files.iter().any(|f| covers(scope, ctx.root, f)))
+            .collect();
+        empty.sort_by(|a, b| a.name().as_str().cmp(b.name().as_str()));
+        if empty.is_empty() {
+            return Vec::new();
+        }
+
+        let content = self
+            .config_file
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok());
+        empty
+            .into_iter()
+            .map(|scope| {
+                let patterns: Vec<&str> =
+                    scope.patterns().iter().map(GlobPattern::as_str).collect();
+                Violation::new(
+                    DIRECTIVE_CODE,
+                    EMPTY_SCOPE_NAME,
+                    Severity::Warning,
+                    self.location(scope, content.as_deref(), ctx.root),
+                    format!(
+                        "Scope `{}` matches no source files (paths: {})",
+                        scope.name(),
+                        patterns.join(", ")
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Finds the 1-based line declaring the scope `name` in config `content`:
+/// the first `name = "..."` (TOML), `name: ...` (YAML) or `"name": "..."`
+/// (JSON) entry with that value from the first mention of `scopes` on.
+fn scope_line(content: &str, name: &str) -> Option<usize> {
+    let start = content.lines().position(|line| line.contains("scopes"))?;
+    content
+        .lines()
+        .enumerate()
+        .skip(start)
+        .find_map(|(index, line)| {
+            let entry = line.trim().trim_start_matches("- ").trim_start_matches('{');
+            let entry = entry.trim_start();
+            let rest = entry
+                .strip_prefix("\"name\"")
+                .or_else(|| entry.strip_prefix("name"))?;
+            let value = rest.trim_start().strip_prefix(['=', ':'])?.trim();
+            let value = value.trim_end_matches(',').trim_matches(['"', '\'']);
+            (value == name).then_some(index + 1)
+        })
+}
+
+/// Tests whether `scope` contains `file`, relative to `root` or to any of
+/// its ancestors below `root` (files under a nested analysis root are
+/// matched relative to that root).
+fn covers(scope: &Scope, root: &Path, file: &Path) -> bool {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let components: Vec<_> = relative.components().collect();
+    (0..components.len()).any(|skip| {
+        let suffix: std::path::PathBuf = components[skip..].iter().collect();
+        scope.contains(&suffix)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::declarative::loader;
+
+    fn rule(toml_str: &str) -> EmptyScopeRule {
+        let dto = toml::from_str(toml_str).expect("parse scope config");
+        EmptyScopeRule::new(Arc::new(loader::load(dto).expect("load scope config")))
+    }
+
+    #[test]
+    fn reports_scope_matching_no_files() {
+        let rule = rule(
+            r#"
+[[scopes]]
+name = "domain"
+paths = ["src/doman/**"]
+
+[[scopes]]
+name = "infra"
+paths = ["src/infra/**"]
+"#,
+        );
+        let root = Path::new("/project");
+        let ctx = ProjectContext::new(root).with_source_files(vec![
+            root.join("src/domain/user.rs"),
+            root.join("src/infra/db.rs"),
+        ]);
+
+        let violations = rule.check_project(&ctx);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, "AL000");
+        assert_eq!(violations[0].rule, "empty-scope");
+        assert_eq!(violations[0].severity, Severity::Warning);
+        assert!(violations[0].message.contains("`domain`"));
+        assert!(violations[0].message.contains("src/doman/**"));
+    }
+
+    #[test]
+    fn matches_files_under_nested_roots() {
+        let rule = rule(
+            r#"
+[[scopes]]
+name = "domain"
+paths = ["src/domain/**"]
+"#,
+        );
+        let root = Path::new("/workspace");
+        let ctx = ProjectContext::new(root)
+            .with_source_files(vec![root.join("crates/core/src/domain/user.rs")]);
+
+        assert!(rule.check_project(&ctx).is_empty());
+    }
+
+    #[test]
+    fn reports_at_scope_entry_in_config_file() {
+        let config = r#"
+preset = "minimal"
+
+[[scopes]]
+name = "domain"
+paths = ["src/domain/**"]
+
+[[scopes]]
+name = "infra"
+paths = ["src/infra/**"]
+"#;
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let path = dir.path().join("arch-lint.toml");
+        std::fs::write(&path, config).expect("write config");
+        let rule = rule(config).with_config_file(&path);
+        let ctx = ProjectContext::new(dir.path())
+            .with_source_files(vec![dir.path().join("src/domain/user.rs")]);
+
+        let violations = rule.check_project(&ctx);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location.file, path);
+        assert_eq!(violations[0].location.line, 9);
+    }
+
+    #[test]
+    fn finds_scope_line_in_every_format() {
+        let yaml = "preset: minimal\nscopes:\n  - name: domain\n    paths: [\"src/**\"]\n  - name: infra\n";
+        assert_eq!(scope_line(yaml, "infra"), Some(5));
+
+        let json = "{\n  \"scopes\": [\n    {\n      \"name\": \"domain\",\n      \"paths\": []\n    }\n  ]\n}";
+        assert_eq!(scope_line(json, "domain"), Some(4));
+
+        let toml = "[[restrict-use]]\nname = \"domain\"\n\n[[scopes]]\nname = \"domain\"\n";
+        assert_eq!(scope_line(toml, "domain"), Some(5));
+        assert_eq!(scope_line(toml, "infra"), None);
+    }
+}
@@ -0,0 +1,246 @@
+//! Project root and config file discovery.
+//!
+//! Shared by the `check!()` test runner and the CLI so that `cargo test`
+//! and `arch-lint check` pick the same config:
+//!
+//! 1. The project root is the nearest ancestor whose `Cargo.toml` has a
+//!    `[workspace]` section, or the starting directory itself.
+//! 2. The config is the first of [`CONFIG_FILE_NAMES`] found in the
+//!    starting directory or an ancestor up to the project root; the
+//!    nearest directory wins.
+//! 3. Without a config file, a `[package.metadata.arch-lint]` or
+//!    `[workspace.metadata.arch-lint]` table in a `Cargo.toml` along the
+//!    same path is used ([`find_cargo_metadata`]).
+
+use std::path::{Component, Path, PathBuf};
+
+/// Config file names, in priority order within one directory.
+pub const CONFIG_FILE_NAMES: &[&str] = &[
+    "arch-lint.toml",
+    ".arch-lint.toml",
+    "arch-lint.yaml",
+    "arch-lint.yml",
+    "arch-lint.json",
+];
+
+/// Returns the workspace root enclosing `start`, or `start` if it is not
+/// inside a Cargo workspace.
+#[must_use]
+pub fn find_project_root(start: &Path) -> PathBuf {
+    let start = absolute(start);
+    start
+        .ancestors()
+        .find(|dir| has_workspace_section(&dir.join("Cargo.toml")))
+        .map_or_else(|| start.clone(), Path::to_path_buf)
+}
+
+/// Returns the nearest config file from `start` up to its project root.
+#[must_use]
+pub fn find_config(start: &Path) -> Option<PathBuf> {
+    let start = absolute(start);
+    let root = find_project_root(&start);
+    start
+        .ancestors()
+        .take_while(|dir| dir.starts_with(&root))
+        .find_map(|dir| {
+            CONFIG_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|candidate| candidate.is_file())
+        })
+}
+
+/// Returns the directory a single source file is analyzed from: the nearest
+/// ancestor with a `Cargo.toml` or config file, or the file's directory.
+///
+/// Paths, module paths and test detection for the file are then the same
+/// as when its crate is checked as a whole.
+pub(crate) fn source_root_of(file: &Path) -> PathBuf {
+    let dir = absolute(file);
+    dir.ancestors()
+        .find(|dir| {
+            dir.join("Cargo.toml").is_file()
+                || CONFIG_FILE_NAMES
+                    .iter()
+                    .any(|name| dir.join(name).is_file())
+        })
+        .map_or_else(|| dir.clone(), Path::to_path_buf)
+}
+
+/// Returns the nearest `Cargo.toml` from `start` up to its project root
+/// that embeds a config table, `[package.metadata.arch-lint]` or
+/// `[workspace.metadata.arch-lint]`.
+///
+/// Config files found by [`find_config`] take precedence over it.
+#[must_use]
+pub fn find_cargo_metadata(start: &Path) -> Option<PathBuf> {
+    let start = absolute(start);
+    let root = find_project_root(&start);
+    start
+        .ancestors()
+        .take_while(|dir| dir.starts_with(&root))
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| cargo_metadata_config(manifest).is_some())
+}
+
+/// Returns the embedded config table of a `Cargo.toml` as TOML text, in
+/// the same shape as `arch-lint.toml`.
+///
+/// `[package.metadata.arch-lint]` is preferred over
+/// `[workspace.metadata.arch-lint]` when a manifest has both.
+#[must_use]
+pub fn cargo_metadata_config(manifest: &Path) -> Option<String> {
+    let table = read_manifest(manifest)?;
+    let embedded = ["package", "workspace"].into_iter().find_map(|section| {
+        table
+            .get(section)?
+            .get("metadata")?
+            .get("arch-lint")?
+            .as_table()
+            .cloned()
+    })?;
+    toml::to_string(&embedded).ok()
+}
+
+/// Makes `path` absolute against the current directory and resolves `.`
+/// and `..` lexically, so walking its ancestors visits real parents. Files
+/// are replaced by their parent directory.
+fn absolute(path: &Path) -> PathBuf {
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path))
+    };
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    if normalized.is_file() {
+        normalized.pop();
+    }
+    normalized
+}
+
+/// Checks whether a `Cargo.toml` file defines a `[workspace]` section
+/// by parsing as TOML, avoiding false positives from comments or strings.
+fn has_workspace_section(cargo_toml: &Path) -> bool {
+    read_manifest(cargo_toml).is_some_and(|table| table.contains_key("workspace"))
+}
+
+fn read_manifest(cargo_toml: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(cargo_toml).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A workspace with a member crate at `crates/a`.
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("crates/a/src")).unwrap();
+        fs::write(
+            dir.path().join("crates/a/Cargo.toml"),
+            "[package]\nname = \"a\"\n# [workspace] in a comment does not count\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn project_root_is_workspace_root() {
+        let ws = workspace();
+        let root = ws.path();
+        assert_eq!(find_project_root(&root.join("crates/a")), root);
+        assert_eq!(find_project_root(&root.join("crates/a/src/../src")), root);
+    }
+
+    #[test]
+    fn project_root_without_workspace_is_start() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(find_project_root(dir.path()), dir.path());
+    }
+
+    #[test]
+    fn config_found_at_workspace_root_from_nested_crate() {
+        let ws = workspace();
+        fs::write(ws.path().join("arch-lint.yaml"), "").unwrap();
+        fs::write(ws.path().join(".arch-lint.toml"), "").unwrap();
+
+        let found = find_config(&ws.path().join("crates/a/src"));
+        assert_eq!(found, Some(ws.path().join(".arch-lint.toml")));
+    }
+
+    #[test]
+    fn nearest_config_wins() {
+        let ws = workspace();
+        fs::write(ws.path().join("arch-lint.toml"), "").unwrap();
+        fs::write(ws.path().join("crates/a/arch-lint.json"), "").unwrap();
+
+        fs::write(ws.path().join("crates/a/src/lib.rs"), "").unwrap();
+        assert_eq!(
+            find_config(&ws.path().join("crates/a/src/lib.rs")),
+            Some(ws.path().join("crates/a/arch-lint.json"))
+        );
+    }
+
+    #[test]
+    fn search_stops_at_project_root() {
+        let outer = tempfile::tempdir().unwrap();
+        fs::write(outer.path().join("arch-lint.toml"), "").unwrap();
+        fs::create_dir(outer.path().join("project")).unwrap();
+
+        assert_eq!(find_config(&outer.path().join("project")), None);
+    }
+
+    #[test]
+    fn cargo_metadata_parses_into_config() {
+        let ws = workspace();
+        fs::write(
+            ws.path().join("crates/a/Cargo.toml"),
+            "[package]\nname = \"a\"\n\n[package.metadata.arch-lint]\npreset = \"strict\"\n\n\
+             [package.metadata.arch-lint.rules.no-sync-io]\nenabled = false\n",
+        )
+        .unwrap();
+
+        let manifest = find_cargo_metadata(&ws.path().join("crates/a/src")).unwrap();
+        assert_eq!(manifest, ws.path().join("crates/a/Cargo.toml"));
+        let config = crate::Config::parse(&cargo_metadata_config(&manifest).unwrap()).unwrap();
+        assert_eq!(config.preset.as_deref(), Some("strict"));
+        assert!(!config.is_rule_enabled("no-sync-io"));
+    }
+
+    #[test]
+    fn workspace_metadata_is_found_from_member() {
+        let ws = workspace();
+        fs::write(
+            ws.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.metadata.arch-lint]\nfail_on = \"warning\"\n",
+        )
+        .unwrap();
+
+        let manifest = find_cargo_metadata(&ws.path().join("crates/a")).unwrap();
+        assert_eq!(manifest, ws.path().join("Cargo.toml"));
+        let content = cargo_metadata_config(&manifest).unwrap();
+        assert_eq!(
+            crate::Config::parse(&content).unwrap().fail_on.as_deref(),
+            Some("warning")
+        );
+        assert_eq!(
+            cargo_metadata_config(&ws.path().join("crates/a/Cargo.toml")),
+            None
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Support for `.arch-lintignore` files.
+//!
+//! An `.arch-lintignore` uses gitignore syntax and is discovered in the
+//! analysis root and its ancestors, up to the enclosing git repository root.
+//! Patterns are relative to the directory containing the file.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// File name of the dedicated ignore file.
+pub const IGNORE_FILE_NAME: &str = ".arch-lintignore";
+
+/// Matchers built from every `.arch-lintignore` that applies to a root.
+#[derive(Debug, Default)]
+pub(crate) struct IgnoreFiles {
+    /// `(directory, matcher)` pairs, nearest directory first.
+    matchers: Vec<(PathBuf, Gitignore)>,
+}
+
+impl IgnoreFiles {
+    /// Discovers and parses ignore files from `root` upwards.
+    ///
+    /// The walk stops at the first directory containing `.git`.
+    pub(crate) fn discover(root: &Path) -> Result<Self, ignore::Error> {
+        let mut matchers = Vec::new();
+
+        for dir in root.ancestors() {
+            let file = dir.join(IGNORE_FILE_NAME);
+            if file.is_file() {
+                let mut builder = GitignoreBuilder::new(dir);
+                if let Some(err) = builder.add(&file) {
+                    return Err(err);
+                }
+                matchers.push((dir.to_path_buf(), builder.build()?));
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+
+        Ok(Self { matchers })
+    }
+
+    /// Discovers ignore files for several roots, keeping each directory once.
+    pub(crate) fn discover_all(roots: &[PathBuf]) -> Result<Self, ignore::Error> {
+        let mut matchers: Vec<(PathBuf, Gitignore)> = Vec::new();
+        for root in roots {
+            for (dir, matcher) in Self::discover(root)?.matchers {
+                if !matchers.iter().any(|(seen, _)| *seen == dir) {
+                    matchers.push((dir, matcher));
+                }
+            }
+        }
+        // Only ancestors of a path apply to it, so deepest-first keeps the
+        // nearest-first order for every path.
+        matchers.sort_by_key(|(dir, _)| std::cmp::Reverse(dir.components().count()));
+        Ok(Self { matchers })
+    }
+
+    /// Returns `true` if no ignore file was found.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.matchers.is_empty()
+    }
+
+    /// Checks whether a file is ignored.
+    ///
+    /// The nearest ignore file with a matching pattern decides, so a
+    /// `!pattern` in a subdirectory can re-include a file ignored higher up.
+    pub(crate) fn is_ignored(&self, path: &Path) -> bool {
+        for (dir, matcher) in &self.matchers {
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let matched = matcher.matched_path_or_any_parents(relative, false);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_ignore_file() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join(".git")).expect("create .git");
+
+        let files = IgnoreFiles::discover(dir.path()).expect("discover");
+        assert!(files.is_empty());
+        assert!(!files.is_ignored(&dir.path().join("src/lib.rs")));
+    }
+
+    #[test]
+    fn test_gitignore_syntax() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join(".git")).expect("create .git");
+        std::fs::write(
+            dir.path().join(IGNORE_FILE_NAME),
+            "# generated code\nsrc/generated/\n*_pb.rs\n!src/generated/keep.rs\n",
+        )
+        .expect("write ignore file");
+
+        let files = IgnoreFiles::discover(dir.path()).expect("discover");
+        assert!(files.is_ignored(&dir.path().join("src/generated/api.rs")));
+        assert!(files.is_ignored(&dir.path().join("src/proto/user_pb.rs")));
+        assert!(!files.is_ignored(&dir.path().join("src/lib.rs")));
+    }
+
+    #[test]
+    fn test_discovered_up_the_tree() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir(dir.path().join(".git")).expect("create .git");
+        let crate_dir = dir.path().join("crates/app");
+        std::fs::create_dir_all(&crate_dir).expect("create crate dir");
+        std::fs::write(dir.path().join(IGNORE_FILE_NAME), "*.gen.rs\n").expect("write parent");
+        std::fs::write(crate_dir.join(IGNORE_FILE_NAME), "!keep.gen.rs\n").expect("write child");
+
+        let files = IgnoreFiles::discover(&crate_dir).expect("discover");
+        assert!(files.is_ignored(&crate_dir.join("src/api.gen.rs")));
+        assert!(!files.is_ignored(&crate_dir.join("src/keep.gen.rs")));
+    }
+
+    #[test]
+    fn test_stops_at_repository_root() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let repo = dir.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).expect("create .git");
+        std::fs::write(dir.path().join(IGNORE_FILE_NAME), "*.rs\n").expect("write outer");
+
+        let files = IgnoreFiles::discover(&repo).expect("discover");
+        assert!(files.is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+//! Environment-variable interpolation for config string values.
+//!
+//! Supported forms:
+//!
+//! - `${VAR}`: the value of `VAR`; an error if it is not set
+//! - `${VAR:-default}`: the value of `VAR`, or `default` if it is unset or empty
+//! - `$$`: a literal `$`
+//!
+//! A `$` followed by anything else is kept as is.
+
+use crate::config::ConfigError;
+
+/// Expands variables in `value` against the process environment.
+///
+/// `key` names the setting being expanded and only appears in errors.
+pub(crate) fn expand(key: &str, value: &str) -> Result<String, ConfigError> {
+    expand_with(key, value, |name| std::env::var(name).ok())
+}
+
+/// Like [`expand`], resolving variables with `lookup`.
+fn expand_with(
+    key: &str,
+    value: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, ConfigError> {
+    if !value.contains('$') {
+        return Ok(value.to_string());
+    }
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        if let Some(tail) = after.strip_prefix('$') {
+            out.push('$');
+            rest = tail;
+        } else if let Some(tail) = after.strip_prefix('{') {
+            let end = tail.find('}').ok_or_else(|| ConfigError::InvalidValue {
+                key: key.to_string(),
+                message: format!("unterminated `${{` in {value:?}"),
+            })?;
+            let (name, default) = match tail[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&tail[..end], None),
+            };
+            if name.is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    key: key.to_string(),
+                    message: format!("empty variable name in {value:?}"),
+                });
+            }
+            let resolved = match (lookup(name), default) {
+                (Some(set), None) => set,
+                (Some(set), Some(_)) if !set.is_empty() => set,
+                (_, Some(default)) => default.to_string(),
+                (None, None) => {
+                    return Err(ConfigError::UndefinedEnvVar {
+                        name: name.to_string(),
+                        key: key.to_string(),
+                    })
+                }
+            };
+            out.push_str(&resolved);
+            rest = &tail[end + 1..];
+        } else {
+            out.push('$');
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Expands every string inside `value`, recursing into arrays and tables.
+pub(crate) fn expand_toml(key: &str, value: &mut toml::Value) -> Result<(), ConfigError> {
+    match value {
+        toml::Value::String(s) => *s = expand(key, s)?,
+        toml::Value::Array(items) => {
+            for item in items {
+                expand_toml(key, item)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (name, item) in table.iter_mut() {
+                expand_toml(&format!("{key}.{name}"), item)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "DOCS_BASE" => Some("https://docs.example.com".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    fn expand_test(value: &str) -> Result<String, ConfigError> {
+        expand_with("rules.x.doc_ref", value, lookup)
+    }
+
+    #[test]
+    fn test_expands_set_variable() {
+        assert_eq!(
+            expand_test("${DOCS_BASE}/adr/0007.md").unwrap(),
+            "https://docs.example.com/adr/0007.md"
+        );
+        assert_eq!(expand_test("plain").unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_unset_variable_is_an_error() {
+        let err = expand_test("${MISSING}/adr.md").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::UndefinedEnvVar { name, key } if name == "MISSING" && key == "rules.x.doc_ref"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_default_form() {
+        assert_eq!(expand_test("${MISSING:-docs}/a.md").unwrap(), "docs/a.md");
+        assert_eq!(expand_test("${EMPTY:-docs}").unwrap(), "docs");
+        assert_eq!(
+            expand_test("${DOCS_BASE:-docs}").unwrap(),
+            "https://docs.example.com"
+        );
+        assert_eq!(expand_test("${MISSING:-}x").unwrap(), "x");
+    }
+
+    #[test]
+    fn test_dollar_escapes() {
+        assert_eq!(expand_test("$${DOCS_BASE}").unwrap(), "${DOCS_BASE}");
+        assert_eq!(expand_test("cost: $5 $$").unwrap(), "cost: $5 $");
+    }
+
+    #[test]
+    fn test_malformed_references() {
+        assert!(matches!(
+            expand_test("${DOCS_BASE"),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            expand_test("${}"),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+    }
+}
@@ -28,10 +28,17 @@
 #![warn(missing_docs)]
 
 mod analyzer;
+mod cache;
+mod cargo_manifest;
 mod config;
 mod context;
+mod discovery;
+mod ignore_file;
+mod interpolate;
+mod registry;
 mod required_crate;
 mod rule;
+mod timings;
 mod types;
 
 /// Declarative architecture rules driven by TOML configuration.
@@ -41,9 +48,22 @@ pub mod declarative;
 pub mod utils;
 
 pub use analyzer::{Analyzer, AnalyzerBuilder};
-pub use config::Config;
-pub use context::{FileContext, ProjectContext};
+pub use cache::DEFAULT_CACHE_FILE;
+pub use config::{
+    Config, ConfigError, ConfigFormat, FilesConfig, GateConfig, LoggingConfig, PresetConfig,
+    RuleConfig, ThresholdBreach,
+};
+pub use context::{CargoTomlContext, FileContext, ProjectContext, TargetKind};
+pub use discovery::{
+    cargo_metadata_config, find_cargo_metadata, find_config, find_project_root, CONFIG_FILE_NAMES,
+};
+pub use ignore_file::IGNORE_FILE_NAME;
+pub use registry::RuleRegistry;
 pub use required_crate::{DetectionPattern, RequiredCrateRule};
 pub use rule::{ProjectRule, ProjectRuleBox, Rule, RuleBox};
-pub use types::{Label, LintResult, Location, Replacement, Severity, Suggestion, Violation};
+pub use timings::Timings;
+pub use types::{
+    AnalysisSummary, Label, LintResult, Location, ParseSeverityError, Replacement, RuleOrigin,
+    Severity, Suggestion, Violation,
+};
 pub use utils::allowance::{AllowCheck, AllowState};
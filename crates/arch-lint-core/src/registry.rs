@@ -0,0 +1,206 @@
+//! Name and code lookup over a set of per-file rules.
+
+use std::fmt;
+
+use crate::rule::{Rule, RuleBox};
+
+/// An ordered set of rules, addressable by name (`no-unwrap-expect`) or
+/// code (`AL001`).
+///
+/// Build one from a preset or a custom list:
+///
+/// ```ignore
+/// let registry: RuleRegistry = arch_lint_rules::recommended_rules().into_iter().collect();
+/// assert!(registry.by_code("AL001").is_some());
+/// ```
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: Vec<RuleBox>,
+}
+
+impl RuleRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule after the existing ones.
+    pub fn register(&mut self, rule: RuleBox) {
+        self.rules.push(rule);
+    }
+
+    /// Looks up a rule by its kebab-case name.
+    #[must_use]
+    pub fn by_name(&self, name: &str) -> Option<&dyn Rule> {
+        self.iter().find(|rule| rule.name() == name)
+    }
+
+    /// Looks up a rule by its code.
+    #[must_use]
+    pub fn by_code(&self, code: &str) -> Option<&dyn Rule> {
+        self.iter().find(|rule| rule.code() == code)
+    }
+
+    /// Looks up a rule by name or code.
+    #[must_use]
+    pub fn get(&self, name_or_code: &str) -> Option<&dyn Rule> {
+        self.by_name(name_or_code)
+            .or_else(|| self.by_code(name_or_code))
+    }
+
+    /// Returns `true` if a rule has the given name or code.
+    #[must_use]
+    pub fn contains(&self, name_or_code: &str) -> bool {
+        self.get(name_or_code).is_some()
+    }
+
+    /// Returns the rule names in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.iter().map(Rule::name)
+    }
+
+    /// Returns the rule codes in registration order.
+    pub fn codes(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.iter().map(Rule::code)
+    }
+
+    /// Iterates over the rules in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Rule> + '_ {
+        self.rules.iter().map(AsRef::as_ref)
+    }
+
+    /// Keeps only the rules for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&dyn Rule) -> bool) {
+        self.rules.retain(|rule| keep(rule.as_ref()));
+    }
+
+    /// Returns the number of rules.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns `true` if no rules are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl fmt::Debug for RuleRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.iter()
+                    .map(|rule| format!("{} ({})", rule.code(), rule.name())),
+            )
+            .finish()
+    }
+}
+
+impl From<Vec<RuleBox>> for RuleRegistry {
+    fn from(rules: Vec<RuleBox>) -> Self {
+        Self { rules }
+    }
+}
+
+impl FromIterator<RuleBox> for RuleRegistry {
+    fn from_iter<I: IntoIterator<Item = RuleBox>>(iter: I) -> Self {
+        Self {
+            rules: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<RuleBox> for RuleRegistry {
+    fn extend<I: IntoIterator<Item = RuleBox>>(&mut self, iter: I) {
+        self.rules.extend(iter);
+    }
+}
+
+impl IntoIterator for RuleRegistry {
+    type Item = RuleBox;
+    type IntoIter = std::vec::IntoIter<RuleBox>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rules.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RuleRegistry {
+    type Item = &'a RuleBox;
+    type IntoIter = std::slice::Iter<'a, RuleBox>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rules.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileContext, Violation};
+
+    #[derive(Debug)]
+    struct Named(&'static str, &'static str);
+
+    impl Rule for Named {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn code(&self) -> &'static str {
+            self.1
+        }
+
+        fn check(&self, _ctx: &FileContext, _ast: &syn::File) -> Vec<Violation> {
+            Vec::new()
+        }
+    }
+
+    fn registry() -> RuleRegistry {
+        vec![
+            Box::new(Named("no-unwrap-expect", "AL001")) as RuleBox,
+            Box::new(Named("no-sync-io", "AL002")),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn test_lookup_by_name_and_code() {
+        let registry = registry();
+        assert_eq!(
+            registry.by_name("no-sync-io").map(Rule::code),
+            Some("AL002")
+        );
+        assert_eq!(
+            registry.by_code("AL001").map(Rule::name),
+            Some("no-unwrap-expect")
+        );
+        assert!(registry.by_name("AL001").is_none());
+        assert!(registry.by_code("no-sync-io").is_none());
+        assert!(registry.contains("AL002"));
+        assert!(registry.contains("no-unwrap-expect"));
+        assert!(!registry.contains("unknown"));
+    }
+
+    #[test]
+    fn test_names_codes_and_retain() {
+        let mut registry = registry();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["no-unwrap-expect", "no-sync-io"]
+        );
+        assert_eq!(registry.codes().collect::<Vec<_>>(), ["AL001", "AL002"]);
+
+        registry.retain(|rule| rule.code() != "AL001");
+        registry.register(Box::new(Named("custom", "PROJ001")));
+        assert_eq!(registry.codes().collect::<Vec<_>>(), ["AL002", "PROJ001"]);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            format!("{registry:?}"),
+            r#"["AL002 (no-sync-io)", "PROJ001 (custom)"]"#
+        );
+    }
+}
@@ -14,12 +14,18 @@
 //!     .detect_macro_path()
 //!     .build();
 //! ```
-
-use crate::utils::allowance::check_allow_with_reason;
-use crate::utils::{check_arch_lint_allow, path_to_string};
-use crate::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+//!
+//! With [`RequiredCrateRule::detect_cargo_toml`] the rule inspects
+//! `Cargo.toml` instead and reports an alternative declared as a dependency
+//! while the preferred crate is not.
+
+use crate::cargo_manifest::{CargoManifest, DependencyKind};
+use crate::utils::paths::last_segment;
+use crate::utils::{check_arch_lint_allow_for_rule, path_to_string, ContextTracker};
+use crate::{CargoTomlContext, FileContext, Location, Rule, Severity, Suggestion, Violation};
+use syn::punctuated::Punctuated;
 use syn::visit::Visit;
-use syn::{ItemFn, ItemImpl, ItemMod};
+use syn::{Attribute, ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStruct, Token};
 
 /// Detection pattern for required crate checks.
 #[derive(Debug, Clone)]
@@ -39,7 +45,9 @@ pub enum DetectionPattern {
         expected_derive: String,
     },
 
-    /// Checks Cargo.toml dependencies (future).
+    /// Checks `Cargo.toml` dependencies.
+    ///
+    /// Example: Detect `log` declared in `[dependencies]` when `tracing` is not
     CargoToml,
 }
 
@@ -111,7 +119,10 @@ impl RequiredCrateRule {
 
     /// Uses type suffix detection pattern.
     ///
-    /// Detects types ending with `suffix` and checks for `expected_derive`.
+    /// Detects structs and enums whose name ends with `suffix` and reports
+    /// those whose `#[derive(...)]` lists lack `expected_derive`. The derive
+    /// matches by full path (`thiserror::Error`) or by its last segment
+    /// (`Error`, as written after `use thiserror::Error;`).
     #[must_use]
     pub fn detect_type_suffix(
         mut self,
@@ -125,6 +136,18 @@ impl RequiredCrateRule {
         self
     }
 
+    /// Uses `Cargo.toml` detection pattern.
+    ///
+    /// Reports alternatives declared in `[dependencies]` or
+    /// `[build-dependencies]` (including target-specific tables) of a
+    /// manifest that does not also declare the preferred crate.
+    /// Dev-dependencies are ignored.
+    #[must_use]
+    pub fn detect_cargo_toml(mut self) -> Self {
+        self.detection = DetectionPattern::CargoToml;
+        self
+    }
+
     /// Sets the severity level.
     #[must_use]
     pub fn severity(mut self, severity: Severity) -> Self {
@@ -180,16 +203,72 @@ impl Rule for RequiredCrateRule {
                 visitor.visit_file(ast);
                 visitor.violations
             }
-            DetectionPattern::TypeSuffix { .. } => {
-                // TODO: Implement type suffix detection
-                Vec::new()
-            }
-            DetectionPattern::CargoToml => {
-                // TODO: Implement Cargo.toml detection
-                Vec::new()
+            DetectionPattern::TypeSuffix {
+                suffix,
+                expected_derive,
+            } => {
+                let mut visitor = TypeSuffixVisitor {
+                    ctx,
+                    rule: self,
+                    suffix,
+                    expected_derive,
+                    violations: Vec::new(),
+                    context: ContextTracker::new(self.name, self.code),
+                };
+                visitor.visit_file(ast);
+                visitor.violations
             }
+            // Checked in `check_cargo_toml` instead.
+            DetectionPattern::CargoToml => Vec::new(),
         }
     }
+
+    fn check_cargo_toml(&self, ctx: &CargoTomlContext, _manifest: &toml::Table) -> Vec<Violation> {
+        if !matches!(self.detection, DetectionPattern::CargoToml) {
+            return Vec::new();
+        }
+        let Ok(manifest) = CargoManifest::parse(ctx.content) else {
+            return Vec::new();
+        };
+
+        let same_crate = |a: &str, b: &str| a.replace('-', "_") == b.replace('-', "_");
+        let dependencies: Vec<_> = manifest
+            .dependencies
+            .iter()
+            .filter(|dep| dep.kind != DependencyKind::Dev)
+            .collect();
+        if dependencies
+            .iter()
+            .any(|dep| same_crate(&dep.name, &self.preferred))
+        {
+            return Vec::new();
+        }
+
+        dependencies
+            .into_iter()
+            .filter(|dep| {
+                self.alternatives
+                    .iter()
+                    .any(|alt| same_crate(&dep.name, alt))
+            })
+            .map(|dep| {
+                Violation::new(
+                    self.code,
+                    self.name,
+                    self.severity,
+                    Location::new(ctx.relative_path.clone(), dep.line, 1),
+                    format!(
+                        "Dependency `{}` is declared but `{}` is not; use `{}` instead",
+                        dep.name, self.preferred, self.preferred
+                    ),
+                )
+                .with_suggestion(Suggestion::new(format!(
+                    "Replace `{}` with `{}` in Cargo.toml",
+                    dep.key, self.preferred
+                )))
+            })
+            .collect()
+    }
 }
 
 struct MacroPathVisitor<'a> {
@@ -203,7 +282,8 @@ impl<'ast> Visit<'ast> for MacroPathVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, self.rule.name).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, self.rule.name, self.rule.code).is_allowed()
+        {
             self.in_allowed_context = true;
         }
 
@@ -214,7 +294,8 @@ impl<'ast> Visit<'ast> for MacroPathVisitor<'_> {
     fn visit_item_fn(&mut self, node: &'ast ItemFn) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, self.rule.name).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, self.rule.name, self.rule.code).is_allowed()
+        {
             self.in_allowed_context = true;
         }
 
@@ -225,7 +306,8 @@ impl<'ast> Visit<'ast> for MacroPathVisitor<'_> {
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, self.rule.name).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, self.rule.name, self.rule.code).is_allowed()
+        {
             self.in_allowed_context = true;
         }
 
@@ -249,35 +331,16 @@ impl<'ast> Visit<'ast> for MacroPathVisitor<'_> {
             let span = first_segment.ident.span();
             let start = span.start();
 
-            // Check for inline allow comment
-            let allow_check = check_allow_with_reason(self.ctx.content, start.line, self.rule.name);
-            if allow_check.is_allowed() {
-                if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
-                    let location =
-                        Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-                    self.violations.push(
-                        Violation::new(
-                            self.rule.code,
-                            self.rule.name,
-                            Severity::Warning,
-                            location,
-                            format!(
-                                "Allow directive for '{}' is missing required reason",
-                                self.rule.name
-                            ),
-                        )
-                        .with_suggestion(Suggestion::new(
-                            "Add reason=\"...\" to explain why this exception is necessary",
-                        )),
-                    );
-                }
+            let location =
+                Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+            if self
+                .rule
+                .is_allowed_inline(self.ctx, start.line, &location, &mut self.violations)
+            {
                 syn::visit::visit_macro(self, node);
                 return;
             }
 
-            let location =
-                Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-
             if let Some(macro_name) = self.rule.get_macro_name(&path_str) {
                 self.violations.push(
                     Violation::new(
@@ -302,6 +365,141 @@ impl<'ast> Visit<'ast> for MacroPathVisitor<'_> {
     }
 }
 
+struct TypeSuffixVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a RequiredCrateRule,
+    suffix: &'a str,
+    expected_derive: &'a str,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl TypeSuffixVisitor<'_> {
+    fn check_type(&mut self, ident: &syn::Ident, attrs: &[Attribute]) {
+        let name = ident.to_string();
+        if !name.ends_with(self.suffix)
+            || self.context.is_skipped(false)
+            || check_arch_lint_allow_for_rule(attrs, self.rule.name, self.rule.code).is_allowed()
+            || has_derive(attrs, self.expected_derive)
+        {
+            return;
+        }
+
+        let start = ident.span().start();
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        // Comments above the attributes cover the item too.
+        let first_line = attrs
+            .iter()
+            .map(|attr| attr.pound_token.span.start().line)
+            .min()
+            .unwrap_or(start.line);
+        if self
+            .rule
+            .is_allowed_inline(self.ctx, first_line, &location, &mut self.violations)
+        {
+            return;
+        }
+
+        self.violations.push(
+            Violation::new(
+                self.rule.code,
+                self.rule.name,
+                self.rule.severity,
+                location,
+                format!("Type `{name}` should derive `{}`", self.expected_derive),
+            )
+            .with_suggestion(Suggestion::new(format!(
+                "Add `{}` to the `#[derive(...)]` list",
+                self.expected_derive
+            ))),
+        );
+    }
+}
+
+impl<'ast> Visit<'ast> for TypeSuffixVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        self.check_type(&node.ident, &node.attrs);
+        syn::visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast ItemEnum) {
+        self.check_type(&node.ident, &node.attrs);
+        syn::visit::visit_item_enum(self, node);
+    }
+}
+
+impl RequiredCrateRule {
+    /// Returns `true` if an inline allow comment covers `line`.
+    ///
+    /// Reports a missing reason at `location` when one is required.
+    fn is_allowed_inline(
+        &self,
+        ctx: &FileContext,
+        line: usize,
+        location: &Location,
+        violations: &mut Vec<Violation>,
+    ) -> bool {
+        let allow_check = ctx.check_allow_for_rule(line, self.name, self.code);
+        if !allow_check.is_allowed() {
+            return false;
+        }
+        if self.requires_allow_reason() && allow_check.reason().is_none() {
+            violations.push(
+                Violation::new(
+                    self.code,
+                    self.name,
+                    Severity::Warning,
+                    location.clone(),
+                    format!(
+                        "Allow directive for '{}' is missing required reason",
+                        self.name
+                    ),
+                )
+                .with_suggestion(Suggestion::new(
+                    "Add reason=\"...\" to explain why this exception is necessary",
+                )),
+            );
+        }
+        true
+    }
+}
+
+/// Checks whether a `#[derive(...)]` list contains `expected`, by full path
+/// or by last segment.
+fn has_derive(attrs: &[Attribute], expected: &str) -> bool {
+    let expected_name = last_segment(expected);
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| {
+            let derived = path_to_string(&path);
+            derived == expected || (path.segments.len() == 1 && derived == expected_name)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,13 +507,7 @@ mod tests {
 
     fn check_code(rule: &RequiredCrateRule, code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         rule.check(&ctx, &ast)
     }
 
@@ -380,6 +572,179 @@ fn foo() {
         assert!(violations[1].message.contains("utoipa::openapi"));
     }
 
+    fn check_manifest(rule: &RequiredCrateRule, content: &str) -> Vec<Violation> {
+        let manifest: toml::Table = toml::from_str(content).expect("Failed to parse manifest");
+        let ctx = CargoTomlContext::new(
+            Path::new("/project/Cargo.toml"),
+            content,
+            Path::new("/project"),
+        );
+        rule.check_cargo_toml(&ctx, &manifest)
+    }
+
+    fn cargo_rule() -> RequiredCrateRule {
+        RequiredCrateRule::new("TEST004", "test-rule")
+            .prefer("tracing")
+            .over(&["log", "slog"])
+            .detect_cargo_toml()
+    }
+
+    #[test]
+    fn test_cargo_toml_reports_alternative_dependency() {
+        let violations = check_manifest(
+            &cargo_rule(),
+            r#"[package]
+name = "app"
+
+[dependencies]
+serde = "1"
+log = "0.4"
+
+[target.'cfg(unix)'.build-dependencies]
+slog = "2"
+"#,
+        );
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].location.file, Path::new("Cargo.toml"));
+        assert_eq!(violations[0].location.line, 6);
+        assert!(violations[0].message.contains("`log`"));
+        assert!(violations[0].message.contains("`tracing`"));
+        assert_eq!(violations[1].location.line, 9);
+    }
+
+    #[test]
+    fn test_cargo_toml_allows_when_preferred_declared() {
+        let violations = check_manifest(
+            &cargo_rule(),
+            "[dependencies]\nlog = \"0.4\"\ntracing = \"0.1\"\n",
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_cargo_toml_ignores_dev_dependencies_and_follows_renames() {
+        let violations = check_manifest(
+            &cargo_rule(),
+            "[dev-dependencies]\nlog = \"0.4\"\n\n[dependencies.logging]\npackage = \"log\"\nversion = \"0.4\"\n",
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location.line, 4);
+        assert!(violations[0]
+            .suggestion
+            .as_ref()
+            .is_some_and(|s| s.message.contains("`logging`")));
+    }
+
+    #[test]
+    fn test_cargo_toml_only_with_cargo_detection() {
+        let rule = RequiredCrateRule::new("TEST005", "test-rule")
+            .prefer("tracing")
+            .over(&["log"])
+            .detect_macro_path();
+        assert!(check_manifest(&rule, "[dependencies]\nlog = \"0.4\"\n").is_empty());
+        assert!(check_code(&cargo_rule(), "fn foo() { log::info!(\"x\"); }").is_empty());
+    }
+
+    fn suffix_rule() -> RequiredCrateRule {
+        RequiredCrateRule::new("TEST006", "require-thiserror-derive")
+            .prefer("thiserror")
+            .detect_type_suffix("Error", "thiserror::Error")
+    }
+
+    #[test]
+    fn test_type_suffix_flags_missing_derive() {
+        let violations = check_code(
+            &suffix_rule(),
+            r"
+#[derive(Debug)]
+pub struct ParseError;
+
+pub enum IoError { Closed }
+",
+        );
+
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].message.contains("`ParseError`"));
+        assert!(violations[0].message.contains("`thiserror::Error`"));
+        assert_eq!(violations[0].location.line, 3);
+        assert!(violations[1].message.contains("`IoError`"));
+    }
+
+    #[test]
+    fn test_type_suffix_accepts_expected_derive() {
+        let violations = check_code(
+            &suffix_rule(),
+            r#"
+#[derive(Debug, thiserror::Error)]
+#[error("parse failed")]
+pub struct ParseError;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IoError { Closed }
+"#,
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_type_suffix_ignores_unmatched_names() {
+        let violations = check_code(
+            &suffix_rule(),
+            r"
+#[derive(Debug)]
+pub struct ErrorKind;
+
+pub enum Failure { Closed }
+",
+        );
+        assert!(violations.is_empty());
+
+        let rule = RequiredCrateRule::new("TEST007", "require-serde-dto")
+            .prefer("serde")
+            .detect_type_suffix("Dto", "serde::Serialize");
+        let violations = check_code(
+            &rule,
+            r"
+#[derive(serde::Serialize)]
+pub struct UserDto;
+
+#[derive(Debug)]
+pub struct OrderDto;
+
+pub struct ParseError;
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("`OrderDto`"));
+    }
+
+    #[test]
+    fn test_type_suffix_suppression() {
+        let violations = check_code(
+            &suffix_rule(),
+            r#"
+// arch-lint: allow(require-thiserror-derive) reason="FFI error mirror"
+#[derive(Debug)]
+pub struct FfiError;
+
+#[arch_lint::allow(require_thiserror_derive, reason = "legacy")]
+pub struct LegacyError;
+
+#[arch_lint::allow(require_thiserror_derive, reason = "generated")]
+mod generated {
+    pub struct WireError;
+}
+
+// arch-lint: allow(require-thiserror-derive)
+pub struct UnexplainedError;
+"#,
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
     #[test]
     fn test_severity_setting() {
         let rule = RequiredCrateRule::new("TEST003", "test-rule")
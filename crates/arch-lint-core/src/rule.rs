@@ -1,6 +1,7 @@
 //! Rule traits for defining lint rules.
 
-use crate::context::{FileContext, ProjectContext};
+use crate::config::{ConfigError, LoggingConfig, RuleConfig};
+use crate::context::{CargoTomlContext, FileContext, ProjectContext};
 use crate::types::{Severity, Violation};
 
 /// A per-file lint rule based on `syn` AST analysis.
@@ -14,6 +15,7 @@ use crate::types::{Severity, Violation};
 /// use arch_lint_core::{Rule, FileContext, Violation, Severity};
 /// use syn::visit::Visit;
 ///
+/// #[derive(Debug)]
 /// pub struct NoTodoComments;
 ///
 /// impl Rule for NoTodoComments {
@@ -27,7 +29,7 @@ use crate::types::{Severity, Violation};
 ///     }
 /// }
 /// ```
-pub trait Rule: Send + Sync {
+pub trait Rule: Send + Sync + std::fmt::Debug {
     /// Returns the kebab-case name of this rule (e.g., "no-unwrap-expect").
     fn name(&self) -> &'static str;
 
@@ -39,11 +41,35 @@ pub trait Rule: Send + Sync {
         ""
     }
 
+    /// Returns a longer explanation of why the rule exists, for docs and
+    /// tooling (e.g. `arch-lint rules-catalog`).
+    fn explanation(&self) -> &'static str {
+        ""
+    }
+
+    /// Returns the rule-specific option keys read by
+    /// [`configure`](Self::configure).
+    ///
+    /// `enabled`, `severity`, and `doc_ref` apply to every rule and are not listed.
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// Returns the default severity for violations from this rule.
     fn default_severity(&self) -> Severity {
         Severity::Error
     }
 
+    /// Returns a fingerprint of the rule's settings for the incremental cache.
+    ///
+    /// Two instances with the same name but different options (e.g. set in
+    /// code instead of `[rules.<name>]`) must return different keys, or one
+    /// reuses results cached for the other. Defaults to the name plus the
+    /// `Debug` output; override it if that output is not stable across runs.
+    fn cache_key(&self) -> String {
+        format!("{}:{self:?}", self.name())
+    }
+
     /// Whether this rule requires a reason when using allow directives.
     ///
     /// By default, rules with `Severity::Error` require a reason.
@@ -52,6 +78,36 @@ pub trait Rule: Send + Sync {
         self.default_severity() == Severity::Error
     }
 
+    /// Applies rule-specific options from `[rules.<name>]` in the config.
+    ///
+    /// Called once by [`AnalyzerBuilder::build`](crate::AnalyzerBuilder::build)
+    /// for rules that have a config section. The default ignores options.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::InvalidOption`] if an option has an invalid value.
+    fn configure(&mut self, _config: &RuleConfig) -> Result<(), ConfigError> {
+        Ok(())
+    }
+
+    /// Applies the shared `[logging]` section.
+    ///
+    /// Called by [`AnalyzerBuilder::build`](crate::AnalyzerBuilder::build)
+    /// before [`configure`](Self::configure), and only when the config has
+    /// a `[logging]` section. Rules that recognize logging macros override
+    /// this; the default ignores it.
+    fn configure_logging(&mut self, _logging: &LoggingConfig) {}
+
+    /// Returns `false` if [`check`](Self::check) cannot report anything in
+    /// this file.
+    ///
+    /// The analyzer asks before each `check`, so rules can skip the AST walk
+    /// with a cheap test, typically a substring search on `ctx.content`. It
+    /// must never change results. The default is `true`.
+    fn applies_to(&self, _ctx: &FileContext) -> bool {
+        true
+    }
+
     /// Checks a single file and returns any violations found.
     ///
     /// # Arguments
@@ -63,6 +119,15 @@ pub trait Rule: Send + Sync {
     ///
     /// A vector of violations found in this file.
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation>;
+
+    /// Checks a `Cargo.toml` manifest and returns any violations found.
+    ///
+    /// Called once per manifest under the analysis roots, with the parsed
+    /// document in `manifest`. Rules that only inspect source code keep the
+    /// default, which reports nothing.
+    fn check_cargo_toml(&self, _ctx: &CargoTomlContext, _manifest: &toml::Table) -> Vec<Violation> {
+        Vec::new()
+    }
 }
 
 /// Type alias for boxed Rule trait objects.
@@ -79,6 +144,7 @@ pub type RuleBox = Box<dyn Rule>;
 /// ```ignore
 /// use arch_lint_core::{ProjectRule, ProjectContext, Violation, Severity};
 ///
+/// #[derive(Debug)]
 /// pub struct RequireReadme;
 ///
 /// impl ProjectRule for RequireReadme {
@@ -100,7 +166,7 @@ pub type RuleBox = Box<dyn Rule>;
 ///     }
 /// }
 /// ```
-pub trait ProjectRule: Send + Sync {
+pub trait ProjectRule: Send + Sync + std::fmt::Debug {
     /// Returns the kebab-case name of this rule.
     fn name(&self) -> &'static str;
 
@@ -112,11 +178,27 @@ pub trait ProjectRule: Send + Sync {
         ""
     }
 
+    /// Returns a longer explanation of why the rule exists, for docs and
+    /// tooling (e.g. `arch-lint rules-catalog`).
+    fn explanation(&self) -> &'static str {
+        ""
+    }
+
     /// Returns the default severity for violations from this rule.
     fn default_severity(&self) -> Severity {
         Severity::Error
     }
 
+    /// Returns a fingerprint of the rule's settings for the incremental cache.
+    ///
+    /// Two instances with the same name but different options (e.g. set in
+    /// code instead of `[rules.<name>]`) must return different keys, or one
+    /// reuses results cached for the other. Defaults to the name plus the
+    /// `Debug` output; override it if that output is not stable across runs.
+    fn cache_key(&self) -> String {
+        format!("{}:{self:?}", self.name())
+    }
+
     /// Whether this rule requires a reason when using allow directives.
     ///
     /// By default, rules with `Severity::Error` require a reason.
@@ -145,6 +227,7 @@ mod tests {
     use super::*;
     use crate::types::Location;
 
+    #[derive(Debug)]
     struct TestRule;
 
     impl Rule for TestRule {
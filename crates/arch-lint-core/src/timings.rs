@@ -0,0 +1,101 @@
+//! Wall-clock timings of an analysis run.
+//!
+//! Collected only when enabled with
+//! [`AnalyzerBuilder::timings`](crate::AnalyzerBuilder::timings), and read
+//! back with [`Analyzer::timings`](crate::Analyzer::timings).
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Where the time of an analysis run went.
+///
+/// Per-file phases (`parsing`, `rules`) are summed across files; with
+/// parallel analysis they add up time from all worker threads and can
+/// exceed `total`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timings {
+    /// Wall-clock time of the whole run.
+    pub total: Duration,
+    /// Time spent discovering files.
+    pub discovery: Duration,
+    /// Time spent parsing source files.
+    pub parsing: Duration,
+    /// Time per per-file rule, by rule name, including `Cargo.toml` checks.
+    pub rules: BTreeMap<String, Duration>,
+    /// Time per project rule, by rule name.
+    pub project_rules: BTreeMap<String, Duration>,
+}
+
+impl Timings {
+    /// Adds the time elapsed since `start` to `rule`.
+    pub(crate) fn record_rule(&mut self, rule: &str, start: Instant) {
+        *self.rules.entry(rule.to_string()).or_default() += start.elapsed();
+    }
+
+    /// Adds another set of per-file timings to this one.
+    pub(crate) fn merge(&mut self, other: Self) {
+        self.parsing += other.parsing;
+        for (rule, elapsed) in other.rules {
+            *self.rules.entry(rule).or_default() += elapsed;
+        }
+    }
+
+    /// Returns every phase and rule with its time, slowest first.
+    ///
+    /// Rules are labeled by name, project rules as `name (project)`.
+    #[must_use]
+    pub fn breakdown(&self) -> Vec<(String, Duration)> {
+        let mut entries: Vec<(String, Duration)> = [
+            ("discovery".to_string(), self.discovery),
+            ("parsing".to_string(), self.parsing),
+        ]
+        .into_iter()
+        .chain(self.rules.iter().map(|(name, d)| (name.clone(), *d)))
+        .chain(
+            self.project_rules
+                .iter()
+                .map(|(name, d)| (format!("{name} (project)"), *d)),
+        )
+        .collect();
+        entries.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_and_breakdown() {
+        let ms = Duration::from_millis;
+        let mut timings = Timings {
+            discovery: ms(2),
+            parsing: ms(5),
+            ..Timings::default()
+        };
+        timings.rules.insert("no-sync-io".to_string(), ms(1));
+        timings
+            .project_rules
+            .insert("orphan-modules".to_string(), ms(3));
+
+        let mut file = Timings {
+            parsing: ms(4),
+            ..Timings::default()
+        };
+        file.rules.insert("no-sync-io".to_string(), ms(2));
+        file.rules.insert("no-unwrap-expect".to_string(), ms(3));
+        timings.merge(file);
+
+        assert_eq!(
+            timings.breakdown(),
+            vec![
+                ("parsing".to_string(), ms(9)),
+                ("no-sync-io".to_string(), ms(3)),
+                ("no-unwrap-expect".to_string(), ms(3)),
+                ("orphan-modules (project)".to_string(), ms(3)),
+                ("discovery".to_string(), ms(2)),
+            ]
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Core types for lint violations and results.
 
-use miette::{Diagnostic, SourceSpan};
+use miette::{Diagnostic, LabeledSpan, SourceSpan};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Severity level for lint violations.
@@ -26,6 +27,44 @@ impl std::fmt::Display for Severity {
     }
 }
 
+impl std::str::FromStr for Severity {
+    type Err = ParseSeverityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            other => Err(ParseSeverityError {
+                value: other.to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<&str> for Severity {
+    type Error = ParseSeverityError;
+
+    fn try_from(value: &str) -> Result<Self, ParseSeverityError> {
+        value.parse()
+    }
+}
+
+/// Error returned when a string is not a valid [`Severity`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown severity `{value}`, expected one of: error, warning, info")]
+pub struct ParseSeverityError {
+    value: String,
+}
+
+impl ParseSeverityError {
+    /// Returns the rejected input.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
 /// Source code location.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Location {
@@ -96,6 +135,67 @@ impl Label {
     }
 }
 
+impl std::fmt::Display for Label {
+    /// Formats as `message (file:line:column)`, or just the message when the
+    /// label has no line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if self.location.line > 0 {
+            write!(
+                f,
+                " ({}:{}:{})",
+                self.location.file.display(),
+                self.location.line,
+                self.location.column
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Where the rule behind a violation is defined, so users can go from the
+/// violation to the rule that caused it.
+///
+/// Declarative rules use it to point at their `arch-lint.toml` entry and
+/// the scopes it names.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleOrigin {
+    /// Description of the definition, e.g. ``scope `infra` (src/infra/**)``.
+    pub message: String,
+    /// Location of the definition, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+}
+
+impl RuleOrigin {
+    /// Creates an origin known only by its description.
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// Sets the location of the definition.
+    #[must_use]
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+}
+
+impl std::fmt::Display for RuleOrigin {
+    /// Formats as `message (file:line:column)`, or just the message when the
+    /// location is unknown.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{}", Label::new(location.clone(), &self.message)),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
 /// A suggested fix for a violation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Suggestion {
@@ -165,6 +265,9 @@ pub struct Violation {
     /// Reference to design document (e.g., "ARCHITECTURE.md L85").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub doc_ref: Option<String>,
+    /// Definitions of the rule that reported this violation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub origins: Vec<RuleOrigin>,
 }
 
 impl Violation {
@@ -186,6 +289,7 @@ impl Violation {
             suggestion: None,
             labels: Vec::new(),
             doc_ref: None,
+            origins: Vec::new(),
         }
     }
 
@@ -210,6 +314,51 @@ impl Violation {
         self
     }
 
+    /// Adds a definition of the rule that reported this violation.
+    #[must_use]
+    pub fn with_origin(mut self, origin: RuleOrigin) -> Self {
+        self.origins.push(origin);
+        self
+    }
+
+    /// Returns an edit that suppresses this violation with
+    /// `#[arch_lint::allow(rule, reason = "TODO")]`.
+    ///
+    /// `ast` is the parsed source of the violation's file. The attribute goes
+    /// above the innermost `fn`, method, `impl`, or inline `mod` containing
+    /// the violation's line, indented like the item. Outside any such item,
+    /// the edit is a `// arch-lint: allow(rule) reason="TODO"` comment above
+    /// the flagged line instead.
+    ///
+    /// The edit inserts at column 1 of `location.line`; `location.offset` is
+    /// not set, as the AST carries no byte offsets.
+    #[must_use]
+    pub fn suppression_edit(&self, ast: &syn::File) -> Replacement {
+        use syn::visit::Visit;
+
+        let mut finder = EnclosingItem {
+            line: self.location.line,
+            start: None,
+        };
+        finder.visit_file(ast);
+
+        let (line, new_text) = match finder.start {
+            Some(start) => (
+                start.line,
+                format!(
+                    "{}#[arch_lint::allow({}, reason = \"TODO\")]\n",
+                    " ".repeat(start.column),
+                    self.rule.replace('-', "_"),
+                ),
+            ),
+            None => (
+                self.location.line,
+                format!("// arch-lint: allow({}) reason=\"TODO\"\n", self.rule),
+            ),
+        };
+        Replacement::new(Location::new(self.location.file.clone(), line, 1), new_text)
+    }
+
     /// Formats the violation for terminal output.
     #[must_use]
     pub fn format(&self) -> String {
@@ -223,12 +372,18 @@ impl Violation {
             self.location.column,
         );
         let _ = writeln!(output, "  {}: {}", self.severity, self.message);
+        for label in &self.labels {
+            let _ = writeln!(output, "  = note: {label}");
+        }
         if let Some(suggestion) = &self.suggestion {
             let _ = writeln!(output, "  = help: {}", suggestion.message);
         }
         if let Some(doc_ref) = &self.doc_ref {
             let _ = writeln!(output, "  = see: {doc_ref}");
         }
+        for origin in &self.origins {
+            let _ = writeln!(output, "  = defined by: {origin}");
+        }
         output
     }
 }
@@ -237,12 +392,12 @@ impl std::fmt::Display for Violation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}:{}:{}: {} [{}] {}",
+            "{}:{}:{}: [{}] {} {}",
             self.location.file.display(),
             self.location.line,
             self.location.column,
-            self.severity,
             self.code,
+            self.severity,
             self.message
         )?;
         if let Some(doc_ref) = &self.doc_ref {
@@ -253,6 +408,10 @@ impl std::fmt::Display for Violation {
 }
 
 /// Converts a Violation to a miette Diagnostic for rich error display.
+///
+/// The primary span is labeled with the rule name; secondary [`Label`]s in
+/// the same file become additional spans, others are listed in the help.
+/// Each [`RuleOrigin`] becomes a related advice.
 #[allow(dead_code)] // Public API for miette integration
 #[derive(Debug, thiserror::Error, Diagnostic)]
 #[error("{message}")]
@@ -263,15 +422,92 @@ pub struct ViolationDiagnostic {
     #[label("{label_message}")]
     span: SourceSpan,
     label_message: String,
+    #[label(collection)]
+    labels: Vec<LabeledSpan>,
+    #[related]
+    related: Vec<OriginDiagnostic>,
 }
 
+/// A [`RuleOrigin`] as a related miette diagnostic.
+#[derive(Debug, thiserror::Error, Diagnostic)]
+#[error("defined by {0}")]
+#[diagnostic(severity(Advice))]
+pub struct OriginDiagnostic(String);
+
 impl From<&Violation> for ViolationDiagnostic {
     fn from(v: &Violation) -> Self {
+        let (local, elsewhere): (Vec<&Label>, Vec<&Label>) = v
+            .labels
+            .iter()
+            .partition(|label| label.location.file == v.location.file);
+
+        let mut help: Vec<String> = v.suggestion.iter().map(|s| s.message.clone()).collect();
+        help.extend(elsewhere.iter().map(ToString::to_string));
+
         Self {
             message: format!("[{}] {}", v.code, v.message),
-            help: v.suggestion.as_ref().map(|s| s.message.clone()),
+            help: (!help.is_empty()).then(|| help.join("\n")),
             span: SourceSpan::from((v.location.offset, v.location.length)),
             label_message: v.rule.clone(),
+            labels: local
+                .into_iter()
+                .map(|label| {
+                    LabeledSpan::new(
+                        Some(label.message.clone()),
+                        label.location.offset,
+                        label.location.length,
+                    )
+                })
+                .collect(),
+            related: v
+                .origins
+                .iter()
+                .map(|origin| OriginDiagnostic(origin.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Counts returned by [`Analyzer::analyze_streaming`](crate::Analyzer::analyze_streaming).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalysisSummary {
+    /// Number of files checked.
+    pub files_checked: usize,
+    /// Number of files skipped for exceeding
+    /// [`max_file_bytes`](crate::AnalyzerBuilder::max_file_bytes).
+    #[serde(default)]
+    pub files_skipped: usize,
+    /// Number of error-severity violations.
+    pub errors: usize,
+    /// Number of warning-severity violations.
+    pub warnings: usize,
+    /// Number of info-severity violations.
+    pub infos: usize,
+}
+
+impl AnalysisSummary {
+    /// Returns the total number of violations.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.errors + self.warnings + self.infos
+    }
+
+    /// Returns true if any violation is at or above `severity`.
+    #[must_use]
+    pub fn has_violations_at(&self, severity: Severity) -> bool {
+        match severity {
+            Severity::Error => self.errors > 0,
+            Severity::Warning => self.errors + self.warnings > 0,
+            Severity::Info => self.total() > 0,
+        }
+    }
+
+    /// Counts one violation of the given severity.
+    pub(crate) fn record(&mut self, severity: Severity) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Info => self.infos += 1,
         }
     }
 }
@@ -283,6 +519,10 @@ pub struct LintResult {
     pub violations: Vec<Violation>,
     /// Number of files checked.
     pub files_checked: usize,
+    /// Number of files skipped for exceeding
+    /// [`max_file_bytes`](crate::AnalyzerBuilder::max_file_bytes).
+    #[serde(default)]
+    pub files_skipped: usize,
 }
 
 impl LintResult {
@@ -338,6 +578,30 @@ impl LintResult {
         (errors, warnings, infos)
     }
 
+    /// Returns the file and severity counts of this result.
+    #[must_use]
+    pub fn summary(&self) -> AnalysisSummary {
+        let (errors, warnings, infos) = self.count_by_severity();
+        AnalysisSummary {
+            files_checked: self.files_checked,
+            files_skipped: self.files_skipped,
+            errors,
+            warnings,
+            infos,
+        }
+    }
+
+    /// Counts violations per rule name, e.g. to find which rule's debt to
+    /// pay down first.
+    #[must_use]
+    pub fn counts_by_rule(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for violation in &self.violations {
+            *counts.entry(violation.rule.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
     /// Prints a summary report to stdout.
     pub fn print_report(&self) {
         let (errors, warnings, infos) = self.count_by_severity();
@@ -384,12 +648,18 @@ impl LintResult {
                 v.location.column,
             );
             let _ = writeln!(report, "  {}: {}", v.severity, v.message);
+            for label in &v.labels {
+                let _ = writeln!(report, "  = note: {label}");
+            }
             if let Some(suggestion) = &v.suggestion {
                 let _ = writeln!(report, "  = help: {}", suggestion.message);
             }
             if let Some(doc_ref) = &v.doc_ref {
                 let _ = writeln!(report, "  = see: {doc_ref}");
             }
+            for origin in &v.origins {
+                let _ = writeln!(report, "  = defined by: {origin}");
+            }
             let _ = writeln!(report);
         }
 
@@ -413,6 +683,54 @@ impl LintResult {
     pub fn extend(&mut self, other: Self) {
         self.violations.extend(other.violations);
         self.files_checked += other.files_checked;
+        self.files_skipped += other.files_skipped;
+    }
+}
+
+/// Finds the start of the innermost item spanning `line` that accepts
+/// `#[arch_lint::allow(...)]`.
+struct EnclosingItem {
+    line: usize,
+    start: Option<proc_macro2::LineColumn>,
+}
+
+impl EnclosingItem {
+    /// Records `node` if it spans the line; nested items start no earlier
+    /// than their parents, so the last one recorded is the innermost.
+    fn enter(&mut self, node: &impl syn::spanned::Spanned) -> bool {
+        let span = node.span();
+        if !(span.start().line..=span.end().line).contains(&self.line) {
+            return false;
+        }
+        self.start = Some(span.start());
+        true
+    }
+}
+
+impl<'ast> syn::visit::Visit<'ast> for EnclosingItem {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if self.enter(node) {
+            syn::visit::visit_item_fn(self, node);
+        }
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        if self.enter(node) {
+            syn::visit::visit_impl_item_fn(self, node);
+        }
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if self.enter(node) {
+            syn::visit::visit_item_impl(self, node);
+        }
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        // `mod foo;` has no body to contain the violation.
+        if node.content.is_some() && self.enter(node) {
+            syn::visit::visit_item_mod(self, node);
+        }
     }
 }
 
@@ -420,6 +738,29 @@ impl LintResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_severity_from_str_round_trips_display() {
+        for severity in [Severity::Info, Severity::Warning, Severity::Error] {
+            assert_eq!(severity.to_string().parse::<Severity>(), Ok(severity));
+            assert_eq!(
+                Severity::try_from(severity.to_string().as_str()),
+                Ok(severity)
+            );
+        }
+    }
+
+    #[test]
+    fn test_severity_from_str_rejects_unknown() {
+        let err = "critical".parse::<Severity>().unwrap_err();
+        assert_eq!(err.value(), "critical");
+        assert_eq!(
+            err.to_string(),
+            "unknown severity `critical`, expected one of: error, warning, info"
+        );
+        assert!("Error".parse::<Severity>().is_err());
+        assert!(Severity::try_from("").is_err());
+    }
+
     fn make_violation(severity: Severity) -> Violation {
         Violation::new(
             "AL001",
@@ -430,6 +771,78 @@ mod tests {
         )
     }
 
+    // --- Violation suppression_edit tests ---
+
+    fn violation_at(line: usize, column: usize) -> Violation {
+        let location = Location::new(PathBuf::from("src/lib.rs"), line, column);
+        Violation::new(
+            "AL001",
+            "no-unwrap-expect",
+            Severity::Error,
+            location,
+            "unwrap",
+        )
+    }
+
+    fn suppression(line: usize, code: &str) -> Replacement {
+        let ast = syn::parse_file(code).unwrap();
+        violation_at(line, 1).suppression_edit(&ast)
+    }
+
+    #[test]
+    fn test_suppression_edit_on_free_function() {
+        let edit = suppression(
+            4,
+            "use std::fs;\n\n#[inline]\npub fn load() -> u8 {\n    x.unwrap()\n}\n",
+        );
+        // Above the function's attributes.
+        assert_eq!(edit.location.line, 3);
+        assert_eq!(edit.location.column, 1);
+        assert_eq!(
+            edit.new_text,
+            "#[arch_lint::allow(no_unwrap_expect, reason = \"TODO\")]\n"
+        );
+    }
+
+    #[test]
+    fn test_suppression_edit_on_method() {
+        let code = "\
+mod inner {
+    impl S {
+        fn a() {}
+
+        fn b() {
+            x.unwrap();
+        }
+    }
+}
+";
+        let edit = suppression(6, code);
+        assert_eq!(edit.location.line, 5);
+        assert_eq!(
+            edit.new_text,
+            "        #[arch_lint::allow(no_unwrap_expect, reason = \"TODO\")]\n"
+        );
+
+        // Between methods, the impl is the innermost item.
+        assert_eq!(suppression(4, code).location.line, 2);
+    }
+
+    #[test]
+    fn test_suppression_edit_outside_items_is_comment() {
+        let edit = suppression(1, "static X: u8 = Y.unwrap();\n");
+        assert_eq!(edit.location.line, 1);
+        assert_eq!(
+            edit.new_text,
+            "// arch-lint: allow(no-unwrap-expect) reason=\"TODO\"\n"
+        );
+
+        // The inserted comment carries a reason as far as the parser is concerned.
+        let fixed = format!("{}static X: u8 = Y.unwrap();\n", edit.new_text);
+        let check = crate::utils::allowance::check_allow_with_reason(&fixed, 2, "no-unwrap-expect");
+        assert_eq!(check.reason(), Some("TODO"));
+    }
+
     // --- Violation doc_ref tests ---
 
     #[test]
@@ -474,6 +887,24 @@ mod tests {
 
     // --- LintResult tests ---
 
+    #[test]
+    fn summary_counts_result_violations() {
+        let mut result = LintResult::new();
+        result.violations.push(make_violation(Severity::Warning));
+        result.violations.push(make_violation(Severity::Info));
+        result.files_checked = 3;
+
+        let summary = result.summary();
+        assert_eq!((summary.errors, summary.warnings, summary.infos), (0, 1, 1));
+        assert_eq!(summary.files_checked, 3);
+        for severity in [Severity::Error, Severity::Warning, Severity::Info] {
+            assert_eq!(
+                summary.has_violations_at(severity),
+                result.has_violations_at(severity)
+            );
+        }
+    }
+
     #[test]
     fn has_violations_at_error_only() {
         let mut result = LintResult::new();
@@ -482,6 +913,107 @@ mod tests {
         assert!(result.has_violations_at(Severity::Warning));
     }
 
+    #[test]
+    fn labels_are_formatted_and_become_diagnostic_spans() {
+        let v = make_violation(Severity::Error)
+            .with_label(Label::new(
+                Location::new(PathBuf::from("src/lib.rs"), 3, 9).with_span(40, 15),
+                "error logged here",
+            ))
+            .with_label(Label::new(
+                Location::new(PathBuf::from("src/other.rs"), 7, 1),
+                "defined here",
+            ));
+
+        let formatted = v.format();
+        assert!(
+            formatted.contains("  = note: error logged here (src/lib.rs:3:9)\n"),
+            "{formatted}"
+        );
+        assert!(formatted.contains("  = note: defined here (src/other.rs:7:1)\n"));
+
+        let diagnostic = ViolationDiagnostic::from(&v);
+        let spans: Vec<LabeledSpan> = diagnostic.labels().expect("labels").collect();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[1].label(), Some("error logged here"));
+        assert_eq!((spans[1].offset(), spans[1].len()), (40, 15));
+        let help = diagnostic.help().expect("help").to_string();
+        assert!(help.contains("defined here (src/other.rs:7:1)"), "{help}");
+    }
+
+    #[test]
+    fn origins_are_formatted_and_become_related_diagnostics() {
+        let v = make_violation(Severity::Error)
+            .with_origin(RuleOrigin::new("[[deny-scope-dep]] `no-infra`"))
+            .with_origin(
+                RuleOrigin::new("scope `infra`").with_location(Location::new(
+                    PathBuf::from("arch-lint.toml"),
+                    4,
+                    1,
+                )),
+            );
+
+        let formatted = v.format();
+        assert!(
+            formatted.contains("  = defined by: [[deny-scope-dep]] `no-infra`\n"),
+            "{formatted}"
+        );
+        assert!(formatted.contains("  = defined by: scope `infra` (arch-lint.toml:4:1)\n"));
+        let mut result = LintResult::new();
+        result.violations.push(v.clone());
+        let report = result.format_test_report(Severity::Error);
+        assert!(report.contains("  = defined by: [[deny-scope-dep]] `no-infra`\n"));
+
+        let diagnostic = ViolationDiagnostic::from(&v);
+        let related: Vec<String> = diagnostic
+            .related()
+            .expect("related")
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            related,
+            [
+                "defined by [[deny-scope-dep]] `no-infra`",
+                "defined by scope `infra` (arch-lint.toml:4:1)"
+            ]
+        );
+
+        let json = serde_json::to_value(&v).expect("serialize");
+        assert_eq!(
+            json["origins"][0]["message"],
+            "[[deny-scope-dep]] `no-infra`"
+        );
+        assert!(json["origins"][0].get("location").is_none());
+        assert!(serde_json::to_value(make_violation(Severity::Error))
+            .expect("serialize")
+            .get("origins")
+            .is_none());
+    }
+
+    #[test]
+    fn counts_by_rule_tallies_mixed_rules() {
+        let mut result = LintResult::new();
+        for rule in ["no-sync-io", "no-unwrap-expect", "no-sync-io", "no-sync-io"] {
+            result.violations.push(Violation::new(
+                "AL000",
+                rule,
+                Severity::Warning,
+                Location::new(PathBuf::from("src/lib.rs"), 1, 1),
+                "msg",
+            ));
+        }
+
+        let counts = result.counts_by_rule();
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            [
+                ("no-sync-io".to_string(), 3),
+                ("no-unwrap-expect".to_string(), 1)
+            ]
+        );
+        assert!(LintResult::new().counts_by_rule().is_empty());
+    }
+
     #[test]
     fn format_test_report_filters_by_severity() {
         let mut result = LintResult::new();
@@ -2,12 +2,24 @@
 
 pub mod allowance;
 pub mod attributes;
+pub mod cfg;
+pub mod context;
 pub mod paths;
 
 // Re-export commonly used utilities for rule implementations
 #[doc(inline)]
-pub use allowance::{check_allow_comment, check_allow_with_reason, AllowCheck, AllowState};
+pub use allowance::{
+    check_allow_comment, check_allow_for_rule, check_allow_with_reason, AllowCheck, AllowDate,
+    AllowState, BlockAllowances,
+};
 #[doc(inline)]
-pub use attributes::{check_arch_lint_allow, has_allow_attr, has_cfg_test, has_test_attr};
+pub use attributes::{
+    check_arch_lint_allow, check_arch_lint_allow_for_rule, has_allow_attr, has_cfg_test,
+    has_test_attr,
+};
 #[doc(inline)]
-pub use paths::path_to_string;
+pub use cfg::{cfg_predicate, CfgExpr, CfgSet};
+#[doc(inline)]
+pub use context::{ContextState, ContextTracker};
+#[doc(inline)]
+pub use paths::{expand_use_tree, path_to_string, ResolvedUse};
@@ -3,9 +3,129 @@
 //! Supports directives like:
 //! ```text
 //! // arch-lint: allow(no-unwrap-expect) reason="startup initialization"
+//! let x = y.unwrap(); // arch-lint: allow(no-unwrap-expect) reason="checked above"
+//!
+//! // arch-lint: allow-next-line(no-sync-io) reason="startup initialization"
+//!
+//! // arch-lint: allow-begin(no-sync-io) reason="CLI bootstrap"
+//! ...
+//! // arch-lint: allow-end(no-sync-io)
 //! ```
+//!
+//! Any directive (and `#[arch_lint::allow(...)]`) may carry an
+//! `expires="YYYY-MM-DD"` date. From the day after that date the directive
+//! no longer suppresses anything and is reported as [`EXPIRED_NAME`].
+
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use syn::visit::Visit;
+
+use super::attributes::parse_arch_lint_allow_attr;
+use crate::types::{Location, Severity, Violation};
+
+/// Code used for diagnostics about malformed allow directives.
+pub const DIRECTIVE_CODE: &str = "AL000";
+
+/// Rule name used for diagnostics about malformed allow directives.
+pub const DIRECTIVE_NAME: &str = "allow-directive";
+
+/// Rule name used for allow directives past their `expires` date.
+///
+/// Shares [`DIRECTIVE_CODE`].
+pub const EXPIRED_NAME: &str = "expired-allow";
+
+/// A calendar date as written in `expires="YYYY-MM-DD"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AllowDate {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+thread_local! {
+    static TODAY_OVERRIDE: Cell<Option<AllowDate>> = const { Cell::new(None) };
+}
+
+impl AllowDate {
+    /// Creates a date, returning `None` if it does not exist.
+    #[must_use]
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month)
+            .contains(&day)
+            .then_some(Self { year, month, day })
+    }
+
+    /// Parses a `YYYY-MM-DD` date.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().splitn(3, '-');
+        let year = parts.next().filter(|y| y.len() == 4)?.parse().ok()?;
+        let month = parts.next().filter(|m| m.len() == 2)?.parse().ok()?;
+        let day = parts.next().filter(|d| d.len() == 2)?.parse().ok()?;
+        Self::new(year, month, day)
+    }
+
+    /// Returns the current UTC date, or the date set by [`with_today`].
+    #[must_use]
+    pub fn today() -> Self {
+        if let Some(date) = TODAY_OVERRIDE.with(Cell::get) {
+            return date;
+        }
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_days_since_epoch(i64::try_from(secs / 86_400).unwrap_or(i64::MAX))
+    }
+
+    /// Converts days since 1970-01-01 to a civil date.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's `civil_from_days`.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for AllowDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
 
-use std::collections::HashSet;
+/// Runs `f` with [`AllowDate::today`] fixed to `date` on this thread.
+///
+/// Lets tests and callers that pin a date evaluate `expires` deterministically.
+pub fn with_today<R>(date: AllowDate, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<AllowDate>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            TODAY_OVERRIDE.with(|today| today.set(self.0));
+        }
+    }
+
+    let _restore = Restore(TODAY_OVERRIDE.with(|today| today.replace(Some(date))));
+    f()
+}
 
 /// State of allowance for a rule.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,12 +174,14 @@ impl AllowCheck {
 }
 
 /// Parsed allowance directive.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AllowDirective {
     /// Rule names that are allowed.
     pub rules: HashSet<String>,
     /// Optional reason for the allowance.
     pub reason: Option<String>,
+    /// Optional `expires` date, as written.
+    pub expires: Option<String>,
 }
 
 /// Checks source code for allowance comments (legacy API).
@@ -91,8 +213,15 @@ pub fn check_allow_comment(content: &str, line: usize, rule_name: &str) -> Allow
 /// Looks for comments in the format:
 /// ```text
 /// // arch-lint: allow(rule1, rule2) reason="explanation"
+/// // arch-lint: allow-next-line(rule1) reason="explanation"
 /// ```
 ///
+/// An `allow(...)` applies when it is on the checked line (standalone or
+/// trailing the code) or is a standalone comment directly above it. An
+/// `allow-next-line(...)` applies only to the line directly below it.
+/// Lines inside an `allow-begin(...)` / `allow-end(...)` block are also
+/// allowed; see [`BlockAllowances`].
+///
 /// # Arguments
 ///
 /// * `content` - Source code content
@@ -104,8 +233,43 @@ pub fn check_allow_comment(content: &str, line: usize, rule_name: &str) -> Allow
 /// `AllowCheck::Allowed` with optional reason if an allowance directive is found.
 #[must_use]
 pub fn check_allow_with_reason(content: &str, line: usize, rule_name: &str) -> AllowCheck {
-    // Check the line itself and the line before
+    check_allow_names(content, line, &[rule_name])
+}
+
+/// Checks source code for allowance comments naming a rule or its code.
+///
+/// Same as [`check_allow_with_reason`], but a directive may refer to the
+/// rule either by name (`no-unwrap-expect`) or by code (`AL001`).
+///
+/// Each call scans the whole file; rules should use
+/// [`FileContext::check_allow_for_rule`](crate::FileContext::check_allow_for_rule),
+/// which reuses one scan per file.
+#[must_use]
+pub fn check_allow_for_rule(
+    content: &str,
+    line: usize,
+    rule_name: &str,
+    rule_code: &str,
+) -> AllowCheck {
+    check_allow_names(content, line, &[rule_name, rule_code])
+}
+
+/// Checks for line, next-line, and block directives covering any of `names`.
+fn check_allow_names(content: &str, line: usize, names: &[&str]) -> AllowCheck {
+    BlockAllowances::scan(content).check_all(line, names)
+}
+
+/// Returns the unexpired `allow(...)` and `allow-next-line(...)` directives
+/// that apply to `line` (1-indexed), the line above first.
+///
+/// Placement follows [`check_allow_with_reason`]; block directives are not
+/// included. Any language with `//` comments can use this, e.g. the
+/// tree-sitter extractors for import lines.
+#[must_use]
+pub fn line_directives(content: &str, line: usize) -> Vec<AllowDirective> {
+    // Check the line before and the line itself
     let lines: Vec<&str> = content.lines().collect();
+    let mut directives = Vec::new();
 
     for check_line in [line.saturating_sub(1), line] {
         if check_line == 0 || check_line > lines.len() {
@@ -113,34 +277,370 @@ pub fn check_allow_with_reason(content: &str, line: usize, rule_name: &str) -> A
         }
 
         let line_content = lines[check_line - 1];
-        if let Some(directive) = parse_allow_directive(line_content) {
-            if directive.rules.contains(rule_name) || directive.rules.contains("all") {
-                return AllowCheck::Allowed {
-                    reason: directive.reason,
-                };
+        if let Some((kind, directive)) = parse_directive(line_content) {
+            if kind.covers(is_standalone(line_content), check_line < line)
+                && !directive.is_expired()
+            {
+                directives.push(directive);
             }
         }
     }
 
-    AllowCheck::Denied
+    directives
+}
+
+/// Returns true if `line` holds only a comment.
+fn is_standalone(line: &str) -> bool {
+    line.trim_start().starts_with("//")
 }
 
-/// Parses an allowance directive from a comment line.
-fn parse_allow_directive(line: &str) -> Option<AllowDirective> {
+impl AllowDirective {
+    /// Returns true if the directive names the rule (or `all`).
+    #[must_use]
+    pub fn covers(&self, rule_name: &str) -> bool {
+        self.rules.contains(rule_name) || self.rules.contains("all")
+    }
+
+    /// Returns true if the directive's `expires` date has passed.
+    ///
+    /// An `expires` value that is not a valid `YYYY-MM-DD` date counts as
+    /// expired, so a typo cannot make a suppression permanent.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.expires
+            .as_deref()
+            .is_some_and(|raw| AllowDate::parse(raw).map_or(true, |d| d < AllowDate::today()))
+    }
+
+    /// Describes why the directive expired, if it has.
+    fn expiry_message(&self) -> Option<String> {
+        let raw = self.expires.as_deref()?;
+        let mut rules: Vec<&str> = self.rules.iter().map(String::as_str).collect();
+        rules.sort_unstable();
+        let rules = rules.join(", ");
+        match AllowDate::parse(raw) {
+            Some(date) if date < AllowDate::today() => Some(format!(
+                "allow({rules}) expired on {date}; fix the code or renew the suppression"
+            )),
+            Some(_) => None,
+            None => Some(format!(
+                "allow({rules}) has invalid expires=\"{raw}\" (expected YYYY-MM-DD)"
+            )),
+        }
+    }
+}
+
+/// Kind of `arch-lint:` comment directive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirectiveKind {
+    /// `allow(...)`: applies to its own line and the next one.
+    Allow,
+    /// `allow-next-line(...)`: applies to the following line only.
+    NextLine,
+    /// `allow-begin(...)`: opens a suppressed range.
+    Begin,
+    /// `allow-end(...)`: closes a suppressed range.
+    End,
+}
+
+impl DirectiveKind {
+    /// Returns true if a line directive applies to the checked line, given
+    /// whether it is a standalone comment and sits on the line above.
+    fn covers(self, standalone: bool, is_previous: bool) -> bool {
+        match self {
+            // A directive trailing code only covers its own line
+            Self::Allow => standalone || !is_previous,
+            Self::NextLine => standalone && is_previous,
+            Self::Begin | Self::End => false,
+        }
+    }
+}
+
+/// An `allow(...)` or `allow-next-line(...)` directive found by
+/// [`BlockAllowances::scan`].
+#[derive(Debug, Clone)]
+struct LineDirective {
+    kind: DirectiveKind,
+    standalone: bool,
+    directive: AllowDirective,
+}
+
+/// A suppressed, inclusive line range.
+#[derive(Debug, Clone)]
+struct AllowRange {
+    start: usize,
+    end: usize,
+    reason: Option<String>,
+}
+
+/// Problem found while pairing `allow-begin` / `allow-end` directives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectiveProblem {
+    /// Line of the offending directive (1-indexed).
+    pub line: usize,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Allowance directives pre-scanned from a file: suppressed line ranges
+/// built from `allow-begin` / `allow-end` pairs, plus the line and
+/// next-line directives, so repeated checks need not rescan the file.
+///
+/// Begin/end pairs are matched per rule, innermost first, so ranges may be
+/// nested or interleaved across rules. An `allow-end` without a matching
+/// begin, or a begin that is never closed, suppresses nothing and is
+/// reported as a [`DirectiveProblem`].
+#[derive(Debug, Clone, Default)]
+pub struct BlockAllowances {
+    ranges: HashMap<String, Vec<AllowRange>>,
+    problems: Vec<DirectiveProblem>,
+    /// Line directives by line number.
+    lines: HashMap<usize, LineDirective>,
+}
+
+impl BlockAllowances {
+    /// Pre-scans a file for allowance directives.
+    #[must_use]
+    pub fn scan(content: &str) -> Self {
+        let mut open: HashMap<String, Vec<(usize, Option<String>)>> = HashMap::new();
+        // Expired ranges still pair with their `allow-end`, but suppress nothing.
+        let mut expired_begins = HashSet::new();
+        let mut result = Self::default();
+
+        for (idx, line) in content.lines().enumerate() {
+            let line_no = idx + 1;
+            let Some((kind, directive)) = parse_directive(line) else {
+                continue;
+            };
+
+            match kind {
+                DirectiveKind::Allow | DirectiveKind::NextLine => {
+                    result.lines.insert(
+                        line_no,
+                        LineDirective {
+                            kind,
+                            standalone: is_standalone(line),
+                            directive,
+                        },
+                    );
+                }
+                DirectiveKind::Begin => {
+                    if directive.is_expired() {
+                        expired_begins.insert(line_no);
+                    }
+                    for rule in directive.rules {
+                        open.entry(rule)
+                            .or_default()
+                            .push((line_no, directive.reason.clone()));
+                    }
+                }
+                DirectiveKind::End => {
+                    for rule in directive.rules {
+                        if let Some((start, reason)) = open.get_mut(&rule).and_then(Vec::pop) {
+                            if !expired_begins.contains(&start) {
+                                result.ranges.entry(rule).or_default().push(AllowRange {
+                                    start,
+                                    end: line_no,
+                                    reason,
+                                });
+                            }
+                        } else {
+                            result.problems.push(DirectiveProblem {
+                                line: line_no,
+                                message: format!(
+                                    "allow-end({rule}) has no matching allow-begin({rule})"
+                                ),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        for (rule, starts) in open {
+            for (start, _) in starts {
+                result.problems.push(DirectiveProblem {
+                    line: start,
+                    message: format!("allow-begin({rule}) is never closed by allow-end({rule})"),
+                });
+            }
+        }
+        result.problems.sort_by_key(|p| p.line);
+
+        result
+    }
+
+    /// Checks whether a line falls inside a suppressed range for the rule.
+    ///
+    /// The innermost enclosing range supplies the reason.
+    #[must_use]
+    pub fn check(&self, line: usize, rule_name: &str) -> AllowCheck {
+        self.check_names(line, &[rule_name])
+    }
+
+    /// Checks line, next-line, and block directives for a rule named by
+    /// name or code.
+    ///
+    /// Same as [`check_allow_for_rule`] on the scanned content.
+    #[must_use]
+    pub fn check_for_rule(&self, line: usize, rule_name: &str, rule_code: &str) -> AllowCheck {
+        self.check_all(line, &[rule_name, rule_code])
+    }
+
+    fn check_all(&self, line: usize, names: &[&str]) -> AllowCheck {
+        let line_directive = [line.saturating_sub(1), line]
+            .into_iter()
+            .filter_map(|check_line| Some((check_line, self.lines.get(&check_line)?)))
+            .find(|(check_line, d)| {
+                d.kind.covers(d.standalone, *check_line < line)
+                    && !d.directive.is_expired()
+                    && names.iter().any(|name| d.directive.covers(name))
+            });
+        if let Some((_, d)) = line_directive {
+            return AllowCheck::Allowed {
+                reason: d.directive.reason.clone(),
+            };
+        }
+
+        self.check_names(line, names)
+    }
+
+    fn check_names(&self, line: usize, names: &[&str]) -> AllowCheck {
+        names
+            .iter()
+            .chain(&["all"])
+            .filter_map(|rule| self.ranges.get(*rule))
+            .flatten()
+            .filter(|r| r.start <= line && line <= r.end)
+            .min_by_key(|r| r.end - r.start)
+            .map_or(AllowCheck::Denied, |r| AllowCheck::Allowed {
+                reason: r.reason.clone(),
+            })
+    }
+
+    /// Returns mismatched begin/end directives.
+    #[must_use]
+    pub fn problems(&self) -> &[DirectiveProblem] {
+        &self.problems
+    }
+
+    /// Converts directive problems into warnings for the given file.
+    #[must_use]
+    pub fn problem_violations(&self, file: &Path) -> Vec<Violation> {
+        self.problems
+            .iter()
+            .map(|p| {
+                Violation::new(
+                    DIRECTIVE_CODE,
+                    DIRECTIVE_NAME,
+                    Severity::Warning,
+                    Location::new(file.to_path_buf(), p.line, 1),
+                    p.message.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Reports allow directives whose `expires` date has passed.
+///
+/// Covers comment directives and `#[arch_lint::allow(...)]` attributes.
+#[must_use]
+pub fn expired_allow_violations(content: &str, ast: &syn::File, file: &Path) -> Vec<Violation> {
+    let violation = |line: usize, message: String| {
+        Violation::new(
+            DIRECTIVE_CODE,
+            EXPIRED_NAME,
+            Severity::Warning,
+            Location::new(file.to_path_buf(), line, 1),
+            message,
+        )
+    };
+
+    let mut violations: Vec<Violation> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let (_, directive) = parse_directive(line)?;
+            Some(violation(idx + 1, directive.expiry_message()?))
+        })
+        .collect();
+
+    let mut attrs = AllowAttrs::default();
+    attrs.visit_file(ast);
+    violations.extend(
+        attrs
+            .found
+            .into_iter()
+            .filter_map(|(line, directive)| Some(violation(line, directive.expiry_message()?))),
+    );
+    violations.sort_by_key(|v| v.location.line);
+    violations
+}
+
+/// Returns the earliest `expires` date in a file that has not passed yet.
+///
+/// Until the day after it, checking the file gives the same result, so the
+/// incremental cache can reuse violations up to and including that date.
+/// Covers comment directives and `#[arch_lint::allow(...)]` attributes.
+#[must_use]
+pub fn next_expiry(content: &str, ast: &syn::File) -> Option<AllowDate> {
+    let mut attrs = AllowAttrs::default();
+    attrs.visit_file(ast);
+    let today = AllowDate::today();
+    content
+        .lines()
+        .filter_map(|line| parse_directive(line).map(|(_, directive)| directive))
+        .chain(attrs.found.into_iter().map(|(_, directive)| directive))
+        .filter_map(|directive| AllowDate::parse(directive.expires.as_deref()?))
+        .filter(|date| *date >= today)
+        .min()
+}
+
+/// Collects `#[arch_lint::allow(...)]` attributes anywhere in a file.
+#[derive(Default)]
+struct AllowAttrs {
+    found: Vec<(usize, AllowDirective)>,
+}
+
+impl<'ast> Visit<'ast> for AllowAttrs {
+    fn visit_attribute(&mut self, attr: &'ast syn::Attribute) {
+        if let Some(directive) = parse_arch_lint_allow_attr(attr) {
+            let line = syn::spanned::Spanned::span(attr).start().line;
+            self.found.push((line, directive));
+        }
+    }
+}
+
+/// Parses any `arch-lint:` directive from a comment line.
+///
+/// The directive may be a standalone comment or trail code on the same line.
+fn parse_directive(line: &str) -> Option<(DirectiveKind, AllowDirective)> {
     let line = line.trim();
 
-    // Check for // or /// comment
+    // Check for // or /// comment, or a trailing `// arch-lint:` comment
     let comment_content = if let Some(rest) = line.strip_prefix("///") {
         rest.trim()
     } else if let Some(rest) = line.strip_prefix("//") {
         rest.trim()
     } else {
-        return None;
+        let comment = &line[trailing_comment_start(line)?..];
+        let start = comment.find("// arch-lint:")?;
+        comment[start + 2..].trim()
     };
 
     // Check for arch-lint: allow(...) directive
     let directive = comment_content.strip_prefix("arch-lint:")?.trim();
-    let allow_content = directive.strip_prefix("allow(")?.trim();
+    let (kind, allow_content) = if let Some(rest) = directive.strip_prefix("allow-begin(") {
+        (DirectiveKind::Begin, rest)
+    } else if let Some(rest) = directive.strip_prefix("allow-end(") {
+        (DirectiveKind::End, rest)
+    } else if let Some(rest) = directive.strip_prefix("allow-next-line(") {
+        (DirectiveKind::NextLine, rest)
+    } else {
+        (DirectiveKind::Allow, directive.strip_prefix("allow(")?)
+    };
+    let allow_content = allow_content.trim();
 
     // Find closing paren
     let paren_end = allow_content.find(')')?;
@@ -157,27 +657,115 @@ fn parse_allow_directive(line: &str) -> Option<AllowDirective> {
         return None;
     }
 
-    // Parse optional reason
-    let rest = &allow_content[paren_end + 1..].trim();
-    let reason = if let Some(reason_part) = rest.strip_prefix("reason=") {
-        let reason_part = reason_part.trim();
-        if reason_part.starts_with('"') && reason_part.len() > 1 {
-            let end = reason_part[1..].find('"').map(|i| i + 1)?;
-            Some(reason_part[1..end].to_string())
-        } else {
-            None
+    // Parse optional `reason="..."` and `expires="..."`, in any order
+    let mut rest = allow_content[paren_end + 1..].trim();
+    let mut reason = None;
+    let mut expires = None;
+    while let Some((key, value)) = rest.split_once('=') {
+        let value = value.trim_start();
+        let Some(quoted) = value.strip_prefix('"') else {
+            break;
+        };
+        let end = quoted.find('"')?;
+        match key.trim() {
+            "reason" => reason = Some(quoted[..end].to_string()),
+            "expires" => expires = Some(quoted[..end].to_string()),
+            _ => {}
         }
-    } else {
-        None
-    };
+        rest = quoted[end + 1..].trim_start();
+    }
 
-    Some(AllowDirective { rules, reason })
+    Some((
+        kind,
+        AllowDirective {
+            rules,
+            reason,
+            expires,
+        },
+    ))
+}
+
+/// Returns the byte offset of the first `//` on `line` that is not inside
+/// a string or character literal.
+///
+/// Literals are tracked within the line only, so a line continuing a
+/// multi-line string is read as code.
+fn trailing_comment_start(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => return Some(i),
+            b'"' => i = skip_string(bytes, i + 1, false, 0),
+            // Raw strings: `r"..."`, `r#"..."#`, `br"..."`.
+            b'r' if i == 0 || !is_ident_byte(bytes[i - 1]) || bytes[i - 1] == b'b' => {
+                let hashes = bytes[i + 1..].iter().take_while(|&&b| b == b'#').count();
+                if bytes.get(i + 1 + hashes) == Some(&b'"') {
+                    i = skip_string(bytes, i + 2 + hashes, true, hashes);
+                } else {
+                    i += 1;
+                }
+            }
+            // Character literals (`'"'`, `'\''`); a lifetime has no closing quote.
+            b'\'' => {
+                let len = if bytes.get(i + 1) == Some(&b'\\') {
+                    3
+                } else {
+                    2
+                };
+                let close = line[i + 1..]
+                    .char_indices()
+                    .nth(len - 1)
+                    .map(|(offset, _)| i + 1 + offset);
+                i = match close {
+                    Some(close) if bytes.get(close) == Some(&b'\'') => close + 1,
+                    _ => i + 1,
+                };
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Returns the index just past the string literal whose content starts at
+/// `start`, closed by `"` plus `hashes` `#`s. Escapes only apply to
+/// non-raw strings.
+fn skip_string(bytes: &[u8], start: usize, raw: bool, hashes: usize) -> usize {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if !raw => i += 2,
+            b'"' if bytes[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&b| b == b'#')
+                .count()
+                == hashes =>
+            {
+                return i + 1 + hashes;
+            }
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse_allow_directive(line: &str) -> Option<AllowDirective> {
+        match parse_directive(line)? {
+            (DirectiveKind::Allow, directive) => Some(directive),
+            _ => None,
+        }
+    }
+
     #[test]
     fn test_parse_allow_directive() {
         let directive = parse_allow_directive("// arch-lint: allow(no-unwrap-expect)");
@@ -248,6 +836,187 @@ mod tests {
         assert_eq!(result.reason(), None);
     }
 
+    #[test]
+    fn test_block_range_suppresses_lines() {
+        let content = r#"fn main() {
+    // arch-lint: allow-begin(no-sync-io) reason="CLI bootstrap"
+    let a = std::fs::read("a");
+    let b = std::fs::read("b");
+    // arch-lint: allow-end(no-sync-io)
+    let c = std::fs::read("c");
+}"#;
+
+        let result = check_allow_with_reason(content, 4, "no-sync-io");
+        assert!(result.is_allowed());
+        assert_eq!(result.reason(), Some("CLI bootstrap"));
+        assert!(!check_allow_with_reason(content, 6, "no-sync-io").is_allowed());
+        assert!(!check_allow_with_reason(content, 4, "no-unwrap-expect").is_allowed());
+    }
+
+    #[test]
+    fn test_block_range_nesting() {
+        let content = r#"// arch-lint: allow-begin(r) reason="outer"
+a
+// arch-lint: allow-begin(r) reason="inner"
+b
+// arch-lint: allow-end(r)
+c
+// arch-lint: allow-end(r)
+d"#;
+
+        let blocks = BlockAllowances::scan(content);
+        assert!(blocks.problems().is_empty());
+        assert_eq!(blocks.check(2, "r").reason(), Some("outer"));
+        assert_eq!(blocks.check(4, "r").reason(), Some("inner"));
+        assert_eq!(blocks.check(6, "r").reason(), Some("outer"));
+        assert!(!blocks.check(8, "r").is_allowed());
+    }
+
+    #[test]
+    fn test_block_range_all_and_multiple_rules() {
+        let content = "// arch-lint: allow-begin(a, b)\nx\n// arch-lint: allow-end(a)\ny\n// arch-lint: allow-end(b)\n// arch-lint: allow-begin(all)\nz\n// arch-lint: allow-end(all)";
+
+        let blocks = BlockAllowances::scan(content);
+        assert!(blocks.problems().is_empty());
+        assert!(blocks.check(2, "a").is_allowed());
+        assert!(!blocks.check(4, "a").is_allowed());
+        assert!(blocks.check(4, "b").is_allowed());
+        assert!(blocks.check(7, "anything").is_allowed());
+    }
+
+    #[test]
+    fn test_block_range_mismatched_directives() {
+        let content = "// arch-lint: allow-end(a)\nx\n// arch-lint: allow-begin(b)\ny";
+
+        let blocks = BlockAllowances::scan(content);
+        assert!(!blocks.check(2, "a").is_allowed());
+        assert!(!blocks.check(4, "b").is_allowed());
+
+        let problems = blocks.problems();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].line, 1);
+        assert!(problems[0].message.contains("no matching allow-begin(a)"));
+        assert_eq!(problems[1].line, 3);
+        assert!(problems[1].message.contains("never closed"));
+
+        let violations = blocks.problem_violations(Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].code, DIRECTIVE_CODE);
+        assert_eq!(violations[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_allow_begin_is_not_a_line_allow() {
+        assert!(parse_allow_directive("// arch-lint: allow-begin(a)").is_none());
+        assert!(parse_allow_directive("// arch-lint: allow-end(a)").is_none());
+    }
+
+    #[test]
+    fn test_allow_next_line() {
+        let content = r#"fn foo() {
+    // arch-lint: allow-next-line(no-sync-io) reason="startup only"
+    std::fs::read("a");
+    std::fs::read("b");
+}"#;
+
+        let result = check_allow_with_reason(content, 3, "no-sync-io");
+        assert!(result.is_allowed());
+        assert_eq!(result.reason(), Some("startup only"));
+        assert!(!check_allow_with_reason(content, 2, "no-sync-io").is_allowed());
+        assert!(!check_allow_with_reason(content, 4, "no-sync-io").is_allowed());
+        assert!(!check_allow_with_reason(content, 3, "no-unwrap-expect").is_allowed());
+    }
+
+    #[test]
+    fn test_allow_trailing_same_line() {
+        let content = r#"fn foo() {
+    value.unwrap(); // arch-lint: allow(no-unwrap-expect) reason="checked above"
+    other.unwrap();
+}"#;
+
+        let result = check_allow_with_reason(content, 2, "no-unwrap-expect");
+        assert!(result.is_allowed());
+        assert_eq!(result.reason(), Some("checked above"));
+        assert!(!check_allow_with_reason(content, 3, "no-unwrap-expect").is_allowed());
+    }
+
+    #[test]
+    fn test_directive_inside_string_literal_is_ignored() {
+        let content = r#"fn foo() {
+    let s = "// arch-lint: allow(no-unwrap-expect)"; x.unwrap();
+    let r = r"// arch-lint: allow(no-unwrap-expect)"; y.unwrap();
+    let c = '"'; z.unwrap(); // arch-lint: allow(no-unwrap-expect)
+    let f = |s: &'static str| s.parse::<u8>().unwrap(); // arch-lint: allow(no-unwrap-expect)
+}"#;
+
+        assert!(!check_allow_with_reason(content, 2, "no-unwrap-expect").is_allowed());
+        assert!(!check_allow_with_reason(content, 3, "no-unwrap-expect").is_allowed());
+        assert!(check_allow_with_reason(content, 4, "no-unwrap-expect").is_allowed());
+        assert!(check_allow_with_reason(content, 5, "no-unwrap-expect").is_allowed());
+    }
+
+    #[test]
+    fn test_allow_next_line_is_not_a_line_allow() {
+        assert!(parse_allow_directive("// arch-lint: allow-next-line(a)").is_none());
+        let (kind, directive) =
+            parse_directive("// arch-lint: allow-next-line(a, b)").expect("directive");
+        assert_eq!(kind, DirectiveKind::NextLine);
+        assert!(directive.covers("b"));
+    }
+
+    #[test]
+    fn test_allow_by_rule_code() {
+        let content = r#"fn foo() {
+    // arch-lint: allow(AL001) reason="checked"
+    a.unwrap();
+    // arch-lint: allow(no-unwrap-expect) reason="checked"
+    b.unwrap();
+    // arch-lint: allow-begin(AL001)
+    c.unwrap();
+    // arch-lint: allow-end(AL001)
+}"#;
+
+        for line in [3, 5, 7] {
+            assert!(
+                check_allow_for_rule(content, line, "no-unwrap-expect", "AL001").is_allowed(),
+                "line {line}"
+            );
+        }
+        assert!(!check_allow_with_reason(content, 3, "no-unwrap-expect").is_allowed());
+        assert!(!check_allow_for_rule(content, 3, "no-sync-io", "AL002").is_allowed());
+    }
+
+    #[test]
+    fn test_scanned_allowances_match_line_checks() {
+        let content = r#"fn foo() {
+    // arch-lint: allow(AL001) reason="above"
+    a.unwrap();
+    b.unwrap(); // arch-lint: allow(no-unwrap-expect)
+    c.unwrap();
+    // arch-lint: allow-next-line(all)
+    d.unwrap();
+    // arch-lint: allow-begin(AL001) reason="block"
+    e.unwrap();
+    // arch-lint: allow-end(AL001)
+}"#;
+
+        let scanned = BlockAllowances::scan(content);
+        for line in 0..=12 {
+            assert_eq!(
+                scanned.check_for_rule(line, "no-unwrap-expect", "AL001"),
+                check_allow_for_rule(content, line, "no-unwrap-expect", "AL001"),
+                "line {line}"
+            );
+        }
+        assert!(!scanned
+            .check_for_rule(5, "no-unwrap-expect", "AL001")
+            .is_allowed());
+        assert_eq!(
+            scanned.check_for_rule(9, "x", "AL001").reason(),
+            Some("block")
+        );
+    }
+
     #[test]
     fn test_check_allow_denied() {
         let content = r#"fn foo() {
@@ -258,4 +1027,71 @@ mod tests {
         assert!(!result.is_allowed());
         assert_eq!(result.reason(), None);
     }
+
+    fn date(s: &str) -> AllowDate {
+        AllowDate::parse(s).expect("valid date")
+    }
+
+    #[test]
+    fn test_allow_date_parse_and_epoch() {
+        assert_eq!(date("2024-02-29").to_string(), "2024-02-29");
+        assert!(AllowDate::parse("2025-02-29").is_none());
+        assert!(AllowDate::parse("2025-6-01").is_none());
+        assert!(AllowDate::parse("June 1st").is_none());
+        assert!(date("2025-06-01") < date("2025-06-02"));
+
+        assert_eq!(AllowDate::from_days_since_epoch(0), date("1970-01-01"));
+        assert_eq!(AllowDate::from_days_since_epoch(20_240), date("2025-06-01"));
+        assert_eq!(
+            with_today(date("2001-01-01"), AllowDate::today),
+            date("2001-01-01")
+        );
+    }
+
+    #[test]
+    fn test_expired_comment_directives_stop_suppressing() {
+        let content = r#"fn foo() {
+    // arch-lint: allow(no-unwrap-expect) reason="temporary" expires="2025-06-01"
+    a.unwrap();
+    // arch-lint: allow-begin(no-unwrap-expect) expires="2025-06-01"
+    b.unwrap();
+    // arch-lint: allow-end(no-unwrap-expect)
+}"#;
+
+        with_today(date("2025-06-01"), || {
+            let result = check_allow_with_reason(content, 3, "no-unwrap-expect");
+            assert_eq!(result.reason(), Some("temporary"));
+            assert!(check_allow_with_reason(content, 5, "no-unwrap-expect").is_allowed());
+        });
+        with_today(date("2025-06-02"), || {
+            assert!(!check_allow_with_reason(content, 3, "no-unwrap-expect").is_allowed());
+            let blocks = BlockAllowances::scan(content);
+            assert!(!blocks.check(5, "no-unwrap-expect").is_allowed());
+            assert!(blocks.problems().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_expired_allow_violations() {
+        let content = r#"// arch-lint: allow(a) expires="2025-06-01"
+// arch-lint: allow(b) expires="2030-01-01"
+// arch-lint: allow(c) expires="soon"
+// arch-lint: allow(d)
+#[arch_lint::allow(no_sync_io, reason = "legacy", expires = "2024-12-31")]
+fn f() {}
+"#;
+        let ast = syn::parse_file(content).expect("parse");
+
+        let violations = with_today(date("2025-06-02"), || {
+            expired_allow_violations(content, &ast, Path::new("src/lib.rs"))
+        });
+        let lines: Vec<usize> = violations.iter().map(|v| v.location.line).collect();
+        assert_eq!(lines, vec![1, 3, 5]);
+        assert!(violations
+            .iter()
+            .all(|v| v.code == DIRECTIVE_CODE && v.rule == EXPIRED_NAME));
+        assert!(violations[0].message.contains("expired on 2025-06-01"));
+        assert!(violations[1].message.contains("invalid expires"));
+        assert!(violations[2].message.contains("allow(no_sync_io)"));
+    }
 }
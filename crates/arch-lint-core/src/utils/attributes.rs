@@ -44,22 +44,14 @@ pub fn has_test_attr(attrs: &[Attribute]) -> bool {
     attrs.iter().any(|attr| attr.path().is_ident("test"))
 }
 
-/// Checks if attributes contain a `#[cfg(test)]` attribute.
+/// Checks if attributes gate an item on `test`.
+///
+/// True when the item's `cfg` predicates cannot hold without `test`, e.g.
+/// `#[cfg(test)]` or `#[cfg(all(test, feature = "x"))]`, but not
+/// `#[cfg(not(test))]` or `#[cfg(any(test, feature = "x"))]`.
 #[must_use]
 pub fn has_cfg_test(attrs: &[Attribute]) -> bool {
-    for attr in attrs {
-        if !attr.path().is_ident("cfg") {
-            continue;
-        }
-
-        // Convert to string and check for "test"
-        let attr_str = quote::quote!(#attr).to_string();
-        if attr_str.contains("test") {
-            return true;
-        }
-    }
-
-    false
+    super::cfg::cfg_predicate(attrs).requires_test()
 }
 
 /// Checks if attributes contain a specific custom attribute.
@@ -109,16 +101,34 @@ pub fn get_attr_value(attrs: &[Attribute], name: &str) -> Option<String> {
 /// `AllowCheck::Allowed` with optional reason if the rule is allowed.
 #[must_use]
 pub fn check_arch_lint_allow(attrs: &[Attribute], rule_name: &str) -> AllowCheck {
+    check_arch_lint_allow_names(attrs, &[rule_name])
+}
+
+/// Checks if attributes allow a rule by name or by code.
+///
+/// Same as [`check_arch_lint_allow`], but also accepts the rule code,
+/// e.g. `#[arch_lint::allow(AL001, reason = "...")]`.
+#[must_use]
+pub fn check_arch_lint_allow_for_rule(
+    attrs: &[Attribute],
+    rule_name: &str,
+    rule_code: &str,
+) -> AllowCheck {
+    check_arch_lint_allow_names(attrs, &[rule_name, rule_code])
+}
+
+fn check_arch_lint_allow_names(attrs: &[Attribute], names: &[&str]) -> AllowCheck {
+    // Normalize rule names (support both kebab-case and snake_case)
+    let normalized: Vec<String> = names.iter().map(|n| n.replace('-', "_")).collect();
+
     for attr in attrs {
         if let Some(directive) = parse_arch_lint_allow_attr(attr) {
-            // Normalize rule names (support both kebab-case and snake_case)
-            let normalized_rule = rule_name.replace('-', "_");
             let has_rule = directive.rules.iter().any(|r| {
                 let normalized_r = r.replace('-', "_");
-                normalized_r == normalized_rule || r == "all"
+                normalized.contains(&normalized_r) || r == "all"
             });
 
-            if has_rule {
+            if has_rule && !directive.is_expired() {
                 return AllowCheck::Allowed {
                     reason: directive.reason,
                 };
@@ -146,7 +156,7 @@ fn is_arch_lint_allow_path(attr: &Attribute) -> bool {
 }
 
 /// Parses `#[arch_lint::allow(rule1, rule2, reason = "...")]` attribute.
-fn parse_arch_lint_allow_attr(attr: &Attribute) -> Option<AllowDirective> {
+pub(super) fn parse_arch_lint_allow_attr(attr: &Attribute) -> Option<AllowDirective> {
     if !is_arch_lint_allow_path(attr) {
         return None;
     }
@@ -167,9 +177,11 @@ fn parse_arch_lint_allow_attr(attr: &Attribute) -> Option<AllowDirective> {
 /// - `rule1, rule2`
 /// - `rule1, reason = "explanation"`
 /// - `rule1, rule2, reason = "explanation"`
+/// - `rule1, reason = "explanation", expires = "2025-06-01"`
 fn parse_allow_attr_tokens(tokens: &str) -> Option<AllowDirective> {
     let mut rules = HashSet::new();
     let mut reason = None;
+    let mut expires = None;
 
     // Split by comma, but be careful with reason="..." containing commas
     let mut remaining = tokens.trim();
@@ -180,18 +192,20 @@ fn parse_allow_attr_tokens(tokens: &str) -> Option<AllowDirective> {
             break;
         }
 
-        // Check for reason = "..."
-        if remaining.starts_with("reason") {
-            if let Some(rest) = remaining.strip_prefix("reason") {
-                let rest = rest.trim();
-                if let Some(rest) = rest.strip_prefix('=') {
-                    let rest = rest.trim();
-                    if let Some(rest) = rest.strip_prefix('"') {
-                        if let Some(end) = rest.find('"') {
-                            reason = Some(rest[..end].to_string());
-                            remaining = rest[end + 1..].trim();
-                            continue;
+        // Check for reason = "..." / expires = "..."
+        if let Some((key, rest)) = remaining.split_once('=') {
+            let key = key.trim();
+            if key == "reason" || key == "expires" {
+                if let Some(rest) = rest.trim().strip_prefix('"') {
+                    if let Some(end) = rest.find('"') {
+                        let value = Some(rest[..end].to_string());
+                        if key == "reason" {
+                            reason = value;
+                        } else {
+                            expires = value;
                         }
+                        remaining = rest[end + 1..].trim();
+                        continue;
                     }
                 }
             }
@@ -202,7 +216,7 @@ fn parse_allow_attr_tokens(tokens: &str) -> Option<AllowDirective> {
             .find(|c: char| c == ',' || c.is_whitespace())
             .unwrap_or(remaining.len());
         let rule = remaining[..end].trim();
-        if !rule.is_empty() && rule != "reason" {
+        if !rule.is_empty() && rule != "reason" && rule != "expires" {
             rules.insert(rule.to_string());
         }
         remaining = &remaining[end..];
@@ -212,7 +226,11 @@ fn parse_allow_attr_tokens(tokens: &str) -> Option<AllowDirective> {
         return None;
     }
 
-    Some(AllowDirective { rules, reason })
+    Some(AllowDirective {
+        rules,
+        reason,
+        expires,
+    })
 }
 
 #[cfg(test)]
@@ -243,6 +261,15 @@ mod tests {
 
         let attrs: Vec<Attribute> = vec![parse_quote!(#[cfg(feature = "foo")])];
         assert!(!has_cfg_test(&attrs));
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[cfg(all(test, feature = "x"))])];
+        assert!(has_cfg_test(&attrs));
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[cfg(not(test))])];
+        assert!(!has_cfg_test(&attrs));
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[cfg(feature = "testing")])];
+        assert!(!has_cfg_test(&attrs));
     }
 
     #[test]
@@ -286,6 +313,22 @@ mod tests {
         assert!(!result.is_allowed());
     }
 
+    #[test]
+    fn test_check_arch_lint_allow_by_code() {
+        let by_code: Vec<Attribute> =
+            vec![parse_quote!(#[arch_lint::allow(AL001, reason = "validated")])];
+        let by_name: Vec<Attribute> =
+            vec![parse_quote!(#[arch_lint::allow(no_unwrap_expect, reason = "validated")])];
+
+        for attrs in [&by_code, &by_name] {
+            let result = check_arch_lint_allow_for_rule(attrs, "no-unwrap-expect", "AL001");
+            assert!(result.is_allowed());
+            assert_eq!(result.reason(), Some("validated"));
+        }
+        assert!(!check_arch_lint_allow(&by_code, "no-unwrap-expect").is_allowed());
+        assert!(!check_arch_lint_allow_for_rule(&by_code, "no-sync-io", "AL002").is_allowed());
+    }
+
     #[test]
     fn test_check_arch_lint_allow_wrong_attr() {
         let attrs: Vec<Attribute> = vec![parse_quote!(#[allow(unused)])];
@@ -309,4 +352,23 @@ mod tests {
         assert!(directive.rules.contains("rule2"));
         assert_eq!(directive.reason, Some("multi".to_string()));
     }
+
+    #[test]
+    fn test_check_arch_lint_allow_expires() {
+        use crate::utils::allowance::{with_today, AllowDate};
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(
+            #[arch_lint::allow(no_unwrap_expect, reason = "legacy", expires = "2025-06-01")]
+        )];
+        let directive = parse_arch_lint_allow_attr(&attrs[0]).expect("directive");
+        assert_eq!(directive.expires.as_deref(), Some("2025-06-01"));
+        assert!(!directive.rules.contains("expires"));
+
+        let check_on = |day: &str| {
+            let today = AllowDate::parse(day).expect("date");
+            with_today(today, || check_arch_lint_allow(&attrs, "no_unwrap_expect"))
+        };
+        assert_eq!(check_on("2025-06-01").reason(), Some("legacy"));
+        assert!(!check_on("2025-06-02").is_allowed());
+    }
 }
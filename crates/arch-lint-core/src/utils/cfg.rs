@@ -0,0 +1,356 @@
+//! `#[cfg(...)]` predicate parsing and evaluation.
+//!
+//! [`cfg_predicate`] turns an item's `cfg` attributes into a [`CfgExpr`].
+//! Without a build to ask, most options are unknown, so evaluation is
+//! three-valued: `Some(true)`/`Some(false)` when the known options decide
+//! the predicate, `None` otherwise. [`CfgExpr::requires_test`] answers
+//! "is this test-only code?", and a [`CfgSet`] (from `[analyzer] cfg` or
+//! `--cfg`) decides which feature-gated items are compiled at all.
+
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Item, Meta, Token};
+
+/// A parsed `cfg` predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgExpr {
+    /// A name option such as `test` or `unix`.
+    Name(String),
+    /// A key-value option such as `feature = "serde"`.
+    KeyValue {
+        /// The key (`feature`).
+        key: String,
+        /// The value (`serde`).
+        value: String,
+    },
+    /// `all(...)`: true when every predicate is (and for no predicates).
+    All(Vec<CfgExpr>),
+    /// `any(...)`: true when some predicate is (false for no predicates).
+    Any(Vec<CfgExpr>),
+    /// `not(...)`.
+    Not(Box<CfgExpr>),
+}
+
+impl CfgExpr {
+    /// Parses a predicate such as `all(test, feature = "x")`.
+    ///
+    /// Returns `None` for malformed predicates.
+    #[must_use]
+    pub fn parse(meta: &Meta) -> Option<Self> {
+        match meta {
+            Meta::Path(path) => Some(Self::Name(path.get_ident()?.to_string())),
+            Meta::NameValue(nv) => {
+                let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) = &nv.value
+                else {
+                    return None;
+                };
+                Some(Self::KeyValue {
+                    key: nv.path.get_ident()?.to_string(),
+                    value: value.value(),
+                })
+            }
+            Meta::List(list) => {
+                let args = list
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .ok()?
+                    .iter()
+                    .map(Self::parse)
+                    .collect::<Option<Vec<_>>>()?;
+                match list.path.get_ident()?.to_string().as_str() {
+                    "all" => Some(Self::All(args)),
+                    "any" => Some(Self::Any(args)),
+                    "not" => match <[Self; 1]>::try_from(args) {
+                        Ok([inner]) => Some(Self::Not(Box::new(inner))),
+                        Err(_) => None,
+                    },
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Evaluates the predicate, asking `option` about each name (`value`
+    /// `None`) and key-value option. `None` from `option` means unknown.
+    #[must_use]
+    pub fn eval(&self, option: &impl Fn(&str, Option<&str>) -> Option<bool>) -> Option<bool> {
+        match self {
+            Self::Name(name) => option(name, None),
+            Self::KeyValue { key, value } => option(key, Some(value)),
+            Self::All(exprs) => {
+                let mut result = Some(true);
+                for expr in exprs {
+                    match expr.eval(option) {
+                        Some(false) => return Some(false),
+                        None => result = None,
+                        Some(true) => {}
+                    }
+                }
+                result
+            }
+            Self::Any(exprs) => {
+                let mut result = Some(false);
+                for expr in exprs {
+                    match expr.eval(option) {
+                        Some(true) => return Some(true),
+                        None => result = None,
+                        Some(false) => {}
+                    }
+                }
+                result
+            }
+            Self::Not(expr) => expr.eval(option).map(|b| !b),
+        }
+    }
+
+    /// Returns `true` if the predicate cannot hold without `test`, whatever
+    /// the other options: `test`, `all(test, feature = "x")`, but not
+    /// `any(test, feature = "x")` or `not(test)`.
+    #[must_use]
+    pub fn requires_test(&self) -> bool {
+        self.eval(&|name, value| (value.is_none() && name == "test").then_some(false))
+            == Some(false)
+    }
+}
+
+/// Combines every `#[cfg(...)]` on an item into one predicate.
+///
+/// Several `cfg` attributes must all hold; an item without any yields an
+/// empty `all()`, which always holds. Malformed predicates are ignored.
+#[must_use]
+pub fn cfg_predicate(attrs: &[Attribute]) -> CfgExpr {
+    CfgExpr::All(
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .filter_map(|attr| attr.parse_args::<Meta>().ok())
+            .filter_map(|meta| CfgExpr::parse(&meta))
+            .collect(),
+    )
+}
+
+/// The options assumed enabled, as with `rustc --cfg`.
+///
+/// Options are written `name` or `key=value` (the value may be quoted:
+/// `feature="serde"`). A key given here is complete: with
+/// `feature=serde`, `feature = "sqlx"` is off. Names and keys not given
+/// stay unknown, so code gated on them is kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgSet {
+    options: Vec<(String, Option<String>)>,
+}
+
+impl CfgSet {
+    /// Parses options written `name` or `key=value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first option that is not a valid `cfg` option.
+    pub fn parse<S: AsRef<str>>(specs: &[S]) -> Result<Self, String> {
+        let mut options = Vec::new();
+        for spec in specs {
+            let spec = spec.as_ref();
+            let (key, value) = match spec.split_once('=') {
+                Some((key, value)) => {
+                    let value = value.trim();
+                    let value = value
+                        .strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .unwrap_or(value);
+                    (key.trim(), Some(value.to_string()))
+                }
+                None => (spec.trim(), None),
+            };
+            if syn::parse_str::<syn::Ident>(key).is_err() {
+                return Err(spec.to_string());
+            }
+            options.push((key.to_string(), value));
+        }
+        Ok(Self { options })
+    }
+
+    /// Returns `true` if no options are set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    /// Evaluates `expr` under these options.
+    #[must_use]
+    pub fn eval(&self, expr: &CfgExpr) -> Option<bool> {
+        expr.eval(&|name, value| {
+            let set = self
+                .options
+                .iter()
+                .any(|(k, v)| k == name && v.as_deref() == value);
+            if set {
+                Some(true)
+            } else if value.is_some() && self.options.iter().any(|(k, v)| k == name && v.is_some())
+            {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns `true` if items with these attributes are compiled out.
+    #[must_use]
+    pub fn is_inactive(&self, attrs: &[Attribute]) -> bool {
+        self.eval(&cfg_predicate(attrs)) == Some(false)
+    }
+
+    /// Removes the items, impl items, and trait items that these options
+    /// compile out, in `file` and the inline modules within it.
+    pub fn strip_inactive(&self, file: &mut syn::File) {
+        self.strip_items(&mut file.items);
+    }
+
+    fn strip_items(&self, items: &mut Vec<Item>) {
+        items.retain(|item| !item_attrs(item).is_some_and(|attrs| self.is_inactive(attrs)));
+        for item in items {
+            match item {
+                Item::Mod(module) => {
+                    if let Some((_, items)) = &mut module.content {
+                        self.strip_items(items);
+                    }
+                }
+                Item::Impl(block) => block.items.retain(|item| {
+                    let attrs = match item {
+                        syn::ImplItem::Const(i) => &i.attrs,
+                        syn::ImplItem::Fn(i) => &i.attrs,
+                        syn::ImplItem::Type(i) => &i.attrs,
+                        syn::ImplItem::Macro(i) => &i.attrs,
+                        _ => return true,
+                    };
+                    !self.is_inactive(attrs)
+                }),
+                Item::Trait(block) => block.items.retain(|item| {
+                    let attrs = match item {
+                        syn::TraitItem::Const(i) => &i.attrs,
+                        syn::TraitItem::Fn(i) => &i.attrs,
+                        syn::TraitItem::Type(i) => &i.attrs,
+                        syn::TraitItem::Macro(i) => &i.attrs,
+                        _ => return true,
+                    };
+                    !self.is_inactive(attrs)
+                }),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn item_attrs(item: &Item) -> Option<&[Attribute]> {
+    Some(match item {
+        Item::Const(i) => &i.attrs,
+        Item::Enum(i) => &i.attrs,
+        Item::ExternCrate(i) => &i.attrs,
+        Item::Fn(i) => &i.attrs,
+        Item::ForeignMod(i) => &i.attrs,
+        Item::Impl(i) => &i.attrs,
+        Item::Macro(i) => &i.attrs,
+        Item::Mod(i) => &i.attrs,
+        Item::Static(i) => &i.attrs,
+        Item::Struct(i) => &i.attrs,
+        Item::Trait(i) => &i.attrs,
+        Item::TraitAlias(i) => &i.attrs,
+        Item::Type(i) => &i.attrs,
+        Item::Union(i) => &i.attrs,
+        Item::Use(i) => &i.attrs,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn predicate(attr: Attribute) -> CfgExpr {
+        cfg_predicate(&[attr])
+    }
+
+    #[test]
+    fn test_parse_nested_predicate() {
+        assert_eq!(
+            predicate(parse_quote!(#[cfg(all(test, not(feature = "x")))])),
+            CfgExpr::All(vec![CfgExpr::All(vec![
+                CfgExpr::Name("test".to_string()),
+                CfgExpr::Not(Box::new(CfgExpr::KeyValue {
+                    key: "feature".to_string(),
+                    value: "x".to_string(),
+                })),
+            ])])
+        );
+        assert_eq!(predicate(parse_quote!(#[inline])), CfgExpr::All(vec![]));
+    }
+
+    #[test]
+    fn test_requires_test() {
+        let requires = |attr: Attribute| predicate(attr).requires_test();
+        assert!(requires(parse_quote!(#[cfg(test)])));
+        assert!(requires(parse_quote!(#[cfg(all(test, feature = "x"))])));
+        assert!(requires(
+            parse_quote!(#[cfg(all(unix, any(test, all(test, doc))))])
+        ));
+        assert!(!requires(parse_quote!(#[cfg(any(test, feature = "x"))])));
+        assert!(!requires(parse_quote!(#[cfg(not(test))])));
+        assert!(!requires(parse_quote!(#[cfg(feature = "test")])));
+        assert!(!requires(parse_quote!(#[cfg(feature = "testing")])));
+        assert!(requires(parse_quote!(#[cfg(not(not(test)))])));
+    }
+
+    #[test]
+    fn test_cfg_set_eval() {
+        let set = CfgSet::parse(&["feature=serde", "unix"]).unwrap();
+        let eval = |attr: Attribute| set.eval(&predicate(attr));
+        assert_eq!(eval(parse_quote!(#[cfg(feature = "serde")])), Some(true));
+        assert_eq!(eval(parse_quote!(#[cfg(feature = "sqlx")])), Some(false));
+        assert_eq!(eval(parse_quote!(#[cfg(not(unix))])), Some(false));
+        assert_eq!(eval(parse_quote!(#[cfg(windows)])), None);
+        assert_eq!(eval(parse_quote!(#[cfg(target_os = "linux")])), None);
+        assert_eq!(
+            eval(parse_quote!(#[cfg(all(test, feature = "x"))])),
+            Some(false)
+        );
+        assert_eq!(eval(parse_quote!(#[cfg(any(test, feature = "x"))])), None);
+    }
+
+    #[test]
+    fn test_cfg_set_parse() {
+        let quoted = CfgSet::parse(&["feature=\"serde\""]).unwrap();
+        assert_eq!(quoted, CfgSet::parse(&["feature = serde"]).unwrap());
+        assert_eq!(CfgSet::parse(&["not a cfg"]), Err("not a cfg".to_string()));
+        assert!(CfgSet::parse::<&str>(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_strip_inactive() {
+        let mut file: syn::File = parse_quote! {
+            #[cfg(feature = "sqlx")]
+            fn db() {}
+            fn always() {}
+            mod inner {
+                #[cfg(not(feature = "serde"))]
+                struct Plain;
+            }
+            impl S {
+                #[cfg(feature = "sqlx")]
+                fn query(&self) {}
+                #[cfg(test)]
+                fn helper(&self) {}
+            }
+        };
+        CfgSet::parse(&["feature=serde"])
+            .unwrap()
+            .strip_inactive(&mut file);
+
+        let stripped = quote::quote!(#file).to_string().replace(' ', "");
+        assert_eq!(
+            stripped,
+            "fnalways(){}modinner{}implS{#[cfg(test)]fnhelper(&self){}}"
+        );
+    }
+}
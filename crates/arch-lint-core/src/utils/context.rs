@@ -0,0 +1,167 @@
+//! Test/allow context tracking for rule visitors.
+//!
+//! Most rules skip code inside `#[cfg(test)]` modules and `#[test]`
+//! functions, and code covered by `#[arch_lint::allow(...)]`. A
+//! [`ContextTracker`] keeps that state so a visitor only has to call
+//! `enter_*` before descending into an item and [`ContextTracker::restore`]
+//! afterwards:
+//!
+//! ```ignore
+//! fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+//!     let saved = self.context.enter_fn(&node.attrs);
+//!     syn::visit::visit_item_fn(self, node);
+//!     self.context.restore(saved);
+//! }
+//! ```
+
+use super::attributes::{
+    check_arch_lint_allow_for_rule, has_allow_attr, has_cfg_test, has_test_attr,
+};
+use syn::Attribute;
+
+/// Snapshot of the context at a point in the AST.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextState {
+    /// Inside a `#[cfg(test)]` module or `#[test]` function.
+    pub in_test: bool,
+    /// Inside an item that allows the rule.
+    pub in_allowed: bool,
+}
+
+/// Tracks test and allowed contexts while visiting a file.
+#[derive(Debug, Clone)]
+pub struct ContextTracker {
+    rule_name: &'static str,
+    rule_code: &'static str,
+    allow_lints: &'static [&'static str],
+    state: ContextState,
+}
+
+impl ContextTracker {
+    /// Creates a tracker for the rule with the given name and code.
+    #[must_use]
+    pub fn new(rule_name: &'static str, rule_code: &'static str) -> Self {
+        Self {
+            rule_name,
+            rule_code,
+            allow_lints: &[],
+            state: ContextState::default(),
+        }
+    }
+
+    /// Also treats `#[allow(<lint>)]` on functions as allowing the rule
+    /// (e.g. `clippy::unwrap_used`).
+    #[must_use]
+    pub fn allow_lints(mut self, lints: &'static [&'static str]) -> Self {
+        self.allow_lints = lints;
+        self
+    }
+
+    /// Returns the current state.
+    #[must_use]
+    pub fn state(&self) -> ContextState {
+        self.state
+    }
+
+    /// Returns `true` inside test code.
+    #[must_use]
+    pub fn in_test(&self) -> bool {
+        self.state.in_test
+    }
+
+    /// Returns `true` inside an item that allows the rule.
+    #[must_use]
+    pub fn in_allowed(&self) -> bool {
+        self.state.in_allowed
+    }
+
+    /// Returns `true` if violations should be suppressed here.
+    ///
+    /// `allow_in_tests` is the rule's own test-code setting.
+    #[must_use]
+    pub fn is_skipped(&self, allow_in_tests: bool) -> bool {
+        self.state.in_allowed || (allow_in_tests && self.state.in_test)
+    }
+
+    /// Enters a module; `#[cfg(test)]` marks test code.
+    pub fn enter_mod(&mut self, attrs: &[Attribute]) -> ContextState {
+        let saved = self.state;
+        self.state.in_test |= has_cfg_test(attrs);
+        self.state.in_allowed |= self.allows(attrs);
+        saved
+    }
+
+    /// Enters a function; `#[test]` marks test code.
+    pub fn enter_fn(&mut self, attrs: &[Attribute]) -> ContextState {
+        let saved = self.state;
+        self.state.in_test |= has_test_attr(attrs);
+        self.state.in_allowed |= has_allow_attr(attrs, self.allow_lints) || self.allows(attrs);
+        saved
+    }
+
+    /// Enters an `impl` block.
+    pub fn enter_impl(&mut self, attrs: &[Attribute]) -> ContextState {
+        let saved = self.state;
+        self.state.in_allowed |= self.allows(attrs);
+        saved
+    }
+
+    /// Restores the state returned by an `enter_*` call.
+    pub fn restore(&mut self, saved: ContextState) {
+        self.state = saved;
+    }
+
+    fn allows(&self, attrs: &[Attribute]) -> bool {
+        check_arch_lint_allow_for_rule(attrs, self.rule_name, self.rule_code).is_allowed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_enter_and_restore() {
+        let mut tracker = ContextTracker::new("no-unwrap-expect", "AL001");
+
+        let outer = tracker.enter_mod(&[parse_quote!(#[cfg(test)])]);
+        assert!(tracker.in_test());
+        assert!(!tracker.in_allowed());
+
+        let inner = tracker.enter_impl(&[parse_quote!(#[arch_lint::allow(AL001)])]);
+        assert_eq!(
+            tracker.state(),
+            ContextState {
+                in_test: true,
+                in_allowed: true
+            }
+        );
+
+        tracker.restore(inner);
+        assert!(!tracker.in_allowed());
+        tracker.restore(outer);
+        assert_eq!(tracker.state(), ContextState::default());
+    }
+
+    #[test]
+    fn test_fn_allow_lints_and_skip() {
+        let mut tracker =
+            ContextTracker::new("no-unwrap-expect", "AL001").allow_lints(&["clippy::unwrap_used"]);
+
+        let saved = tracker.enter_fn(&[parse_quote!(#[test])]);
+        assert!(tracker.is_skipped(true));
+        assert!(!tracker.is_skipped(false));
+        tracker.restore(saved);
+
+        tracker.enter_fn(&[parse_quote!(#[allow(clippy::unwrap_used)])]);
+        assert!(tracker.is_skipped(false));
+    }
+
+    #[test]
+    fn test_other_rule_allow_is_ignored() {
+        let mut tracker = ContextTracker::new("no-unwrap-expect", "AL001");
+        tracker.enter_fn(&[parse_quote!(#[arch_lint::allow(no_sync_io)])]);
+        assert!(!tracker.in_allowed());
+    }
+}
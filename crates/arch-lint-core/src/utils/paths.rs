@@ -1,5 +1,6 @@
 //! Path utilities for AST analysis.
 
+use syn::spanned::Spanned;
 use syn::Path;
 
 /// Converts a syn Path to a string representation.
@@ -20,6 +21,71 @@ pub fn path_to_string(path: &Path) -> String {
         .join("::")
 }
 
+/// A resolved use-path with its source span.
+#[derive(Debug, Clone)]
+pub struct ResolvedUse {
+    /// Full path like `sqlx::Pool` or `std::collections::HashMap`.
+    pub path: String,
+    /// Span of the leaf node for error reporting.
+    pub span: proc_macro2::Span,
+}
+
+/// Recursively expands a [`syn::UseTree`] into flat `::` separated paths.
+///
+/// For example, `use std::collections::{HashMap, BTreeMap};` expands to
+/// `["std::collections::HashMap", "std::collections::BTreeMap"]`.
+#[must_use]
+pub fn expand_use_tree(tree: &syn::UseTree, prefix: &str) -> Vec<ResolvedUse> {
+    match tree {
+        syn::UseTree::Path(p) => {
+            let new_prefix = if prefix.is_empty() {
+                p.ident.to_string()
+            } else {
+                format!("{prefix}::{}", p.ident)
+            };
+            expand_use_tree(&p.tree, &new_prefix)
+        }
+        syn::UseTree::Name(n) => {
+            let path = if prefix.is_empty() {
+                n.ident.to_string()
+            } else {
+                format!("{prefix}::{}", n.ident)
+            };
+            vec![ResolvedUse {
+                path,
+                span: n.ident.span(),
+            }]
+        }
+        syn::UseTree::Rename(r) => {
+            let path = if prefix.is_empty() {
+                r.ident.to_string()
+            } else {
+                format!("{prefix}::{}", r.ident)
+            };
+            vec![ResolvedUse {
+                path,
+                span: r.ident.span(),
+            }]
+        }
+        syn::UseTree::Glob(g) => {
+            let path = if prefix.is_empty() {
+                "*".to_string()
+            } else {
+                format!("{prefix}::*")
+            };
+            vec![ResolvedUse {
+                path,
+                span: g.span(),
+            }]
+        }
+        syn::UseTree::Group(g) => g
+            .items
+            .iter()
+            .flat_map(|item| expand_use_tree(item, prefix))
+            .collect(),
+    }
+}
+
 /// Checks if a path matches a pattern.
 ///
 /// Supports wildcards:
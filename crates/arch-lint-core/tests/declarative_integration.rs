@@ -149,6 +149,21 @@ fn scope_dep_violation_details() {
         .file
         .to_string_lossy()
         .contains("domain/service.rs"));
+
+    let origins: Vec<&str> = scope_dep
+        .origins
+        .iter()
+        .map(|origin| origin.message.as_str())
+        .collect();
+    assert!(origins[0].starts_with("[[deny-scope-dep]] `deny-scope-dep:domain`: from `domain`"));
+    assert!(
+        origins[1].starts_with("[[scopes]] `domain`: "),
+        "{origins:?}"
+    );
+    assert!(
+        origins[2].starts_with("[[scopes]] `infra`: "),
+        "{origins:?}"
+    );
 }
 
 // ── Edge case: empty config produces no violations ──
@@ -0,0 +1,257 @@
+//! Code actions offered for published violations.
+//!
+//! Every violation gets two suppressions: an `#[arch_lint::allow(...)]`
+//! attribute on the innermost enclosing function, impl, or module, and an
+//! `// arch-lint: allow(...)` comment above the flagged line. Violations
+//! whose suggestion carries a [`Replacement`](arch_lint_core::Replacement)
+//! also get a preferred quick-fix applying it.
+
+use crate::convert;
+use arch_lint_core::Violation;
+use std::collections::HashMap;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, Position, Range, TextEdit, Url,
+    WorkspaceEdit,
+};
+
+/// Placeholder reason inserted with suppressions, to be filled in. Matches
+/// [`Violation::suppression_edit`].
+const REASON: &str = "TODO";
+
+/// Returns the code actions for the violations in `text` whose range
+/// overlaps `range`.
+pub(crate) fn code_actions(
+    uri: &Url,
+    text: &str,
+    violations: &[Violation],
+    range: Range,
+) -> Vec<CodeActionOrCommand> {
+    let ast = syn::parse_file(text).ok();
+    let mut actions = Vec::new();
+    for violation in violations {
+        let diagnostic = convert::diagnostic(violation, text, uri, std::path::Path::new(""));
+        if !overlaps(diagnostic.range, range) {
+            continue;
+        }
+        if let Some(action) = quick_fix(uri, text, violation, &diagnostic) {
+            actions.push(action);
+        }
+        if let Some(action) = ast
+            .as_ref()
+            .and_then(|ast| allow_attribute(uri, ast, violation, &diagnostic))
+        {
+            actions.push(action);
+        }
+        actions.push(allow_comment(uri, text, violation, &diagnostic));
+    }
+    actions.into_iter().map(CodeActionOrCommand::from).collect()
+}
+
+/// Applies the violation's suggested replacement, if it has one.
+fn quick_fix(
+    uri: &Url,
+    text: &str,
+    violation: &Violation,
+    diagnostic: &Diagnostic,
+) -> Option<CodeAction> {
+    let suggestion = violation.suggestion.as_ref()?;
+    let replacement = suggestion.replacement.as_ref()?;
+    let location = &replacement.location;
+    let start = convert::position_at(text, location.offset)?;
+    let end = convert::position_at(text, location.offset + location.length)?;
+    Some(action(
+        suggestion.message.clone(),
+        CodeActionKind::QUICKFIX,
+        uri,
+        TextEdit::new(Range::new(start, end), replacement.new_text.clone()),
+        diagnostic,
+        true,
+    ))
+}
+
+/// Inserts `#[arch_lint::allow(...)]` above the innermost item that
+/// encloses the violation, if there is one.
+fn allow_attribute(
+    uri: &Url,
+    ast: &syn::File,
+    violation: &Violation,
+    diagnostic: &Diagnostic,
+) -> Option<CodeAction> {
+    let replacement = violation.suppression_edit(ast);
+    // Outside any item the edit is the same comment as `allow_comment`.
+    if !replacement.new_text.trim_start().starts_with("#[") {
+        return None;
+    }
+    Some(action(
+        format!("Allow `{}` on the enclosing item", violation.rule),
+        CodeActionKind::QUICKFIX,
+        uri,
+        insert_line(replacement.location.line, replacement.new_text),
+        diagnostic,
+        false,
+    ))
+}
+
+/// Inserts `// arch-lint: allow(...)` above the flagged line.
+fn allow_comment(
+    uri: &Url,
+    text: &str,
+    violation: &Violation,
+    diagnostic: &Diagnostic,
+) -> CodeAction {
+    let line = violation.location.line;
+    let indent = indentation(text, line);
+    let new_text = format!(
+        "{indent}// arch-lint: allow({}) reason=\"{REASON}\"\n",
+        violation.rule
+    );
+    action(
+        format!("Allow `{}` on this line", violation.rule),
+        CodeActionKind::QUICKFIX,
+        uri,
+        insert_line(line, new_text),
+        diagnostic,
+        false,
+    )
+}
+
+fn action(
+    title: String,
+    kind: CodeActionKind,
+    uri: &Url,
+    edit: TextEdit,
+    diagnostic: &Diagnostic,
+    is_preferred: bool,
+) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some(kind),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+            ..WorkspaceEdit::default()
+        }),
+        is_preferred: is_preferred.then_some(true),
+        ..CodeAction::default()
+    }
+}
+
+/// An edit inserting `new_text` at the start of the 1-based `line`.
+fn insert_line(line: usize, new_text: String) -> TextEdit {
+    let at = Position::new(convert::line_index(line), 0);
+    TextEdit::new(Range::new(at, at), new_text)
+}
+
+/// Returns the leading whitespace of the 1-based `line`.
+fn indentation(text: &str, line: usize) -> &str {
+    let content = text.lines().nth(line.saturating_sub(1)).unwrap_or_default();
+    &content[..content.len() - content.trim_start().len()]
+}
+
+fn overlaps(a: Range, b: Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_lint_core::{Location, Replacement, Severity, Suggestion};
+    use std::path::PathBuf;
+
+    fn uri() -> Url {
+        Url::parse("file:///project/src/lib.rs").expect("valid url")
+    }
+
+    fn violation(line: usize, column: usize) -> Violation {
+        Violation::new(
+            "AL001",
+            "no-unwrap-expect",
+            Severity::Error,
+            Location::new(PathBuf::from("src/lib.rs"), line, column),
+            "Use of unwrap()",
+        )
+    }
+
+    fn edit(action: &CodeActionOrCommand) -> (&str, &TextEdit) {
+        let CodeActionOrCommand::CodeAction(action) = action else {
+            panic!("expected a code action");
+        };
+        let changes = action.edit.as_ref().and_then(|e| e.changes.as_ref());
+        let edits = changes.and_then(|c| c.get(&uri())).expect("edit for uri");
+        (&action.title, &edits[0])
+    }
+
+    fn whole_file() -> Range {
+        Range::new(Position::new(0, 0), Position::new(u32::MAX, 0))
+    }
+
+    #[test]
+    fn test_allow_attribute_on_innermost_fn() {
+        let text = "\
+impl S {
+    #[inline]
+    fn run(&self) {
+        x.unwrap();
+    }
+}
+";
+        let actions = code_actions(&uri(), text, &[violation(4, 11)], whole_file());
+        assert_eq!(actions.len(), 2);
+
+        let (title, attribute) = edit(&actions[0]);
+        assert_eq!(title, "Allow `no-unwrap-expect` on the enclosing item");
+        assert_eq!(attribute.range.start, Position::new(1, 0));
+        assert_eq!(
+            attribute.new_text,
+            format!("    #[arch_lint::allow(no_unwrap_expect, reason = \"{REASON}\")]\n")
+        );
+
+        let (title, comment) = edit(&actions[1]);
+        assert_eq!(title, "Allow `no-unwrap-expect` on this line");
+        assert_eq!(comment.range.start, Position::new(3, 0));
+        assert_eq!(
+            comment.new_text,
+            format!("        // arch-lint: allow(no-unwrap-expect) reason=\"{REASON}\"\n")
+        );
+    }
+
+    #[test]
+    fn test_only_comment_outside_items() {
+        let text = "static X: u8 = y.unwrap();\n";
+        let actions = code_actions(&uri(), text, &[violation(1, 18)], whole_file());
+        assert_eq!(actions.len(), 1);
+        assert_eq!(edit(&actions[0]).1.range.start, Position::new(0, 0));
+    }
+
+    #[test]
+    fn test_quick_fix_from_replacement() {
+        let text = "fn f() {\n    let y = x.into();\n}\n";
+        let offset = text.find("x.into()").expect("present");
+        let location = Location::new(PathBuf::from("src/lib.rs"), 2, 13).with_span(offset, 8);
+        let v = violation(2, 13).with_suggestion(Suggestion::with_fix(
+            "Use `From`",
+            Replacement::new(location, "Y::from(x)"),
+        ));
+
+        let actions = code_actions(&uri(), text, &[v], whole_file());
+        assert_eq!(actions.len(), 3);
+        let CodeActionOrCommand::CodeAction(fix) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        assert_eq!(fix.is_preferred, Some(true));
+        let (title, edit) = edit(&actions[0]);
+        assert_eq!(title, "Use `From`");
+        assert_eq!(
+            edit.range,
+            Range::new(Position::new(1, 12), Position::new(1, 20))
+        );
+        assert_eq!(edit.new_text, "Y::from(x)");
+    }
+
+    #[test]
+    fn test_skips_violations_outside_range() {
+        let text = "fn f() {\n    x.unwrap();\n}\n";
+        let range = Range::new(Position::new(0, 0), Position::new(0, 2));
+        assert!(code_actions(&uri(), text, &[violation(2, 7)], range).is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! Conversion from arch-lint violations to LSP types.
+//!
+//! Violations count lines from 1 and columns in characters from 1; LSP
+//! positions count both from 0, with columns in UTF-16 code units.
+
+use arch_lint_core::{Location, Severity, Violation};
+use std::path::Path;
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, NumberOrString, Position, Range,
+    Url,
+};
+
+/// The `source` of every published diagnostic.
+pub(crate) const SOURCE: &str = "arch-lint";
+
+/// Converts a violation found in `text` (the document at `uri`) into a
+/// diagnostic.
+///
+/// Labels become related information; labels in other files are resolved
+/// against `root`.
+pub(crate) fn diagnostic(violation: &Violation, text: &str, uri: &Url, root: &Path) -> Diagnostic {
+    let related = violation
+        .labels
+        .iter()
+        .filter_map(|label| {
+            let location = if label.location.file == violation.location.file {
+                tower_lsp::lsp_types::Location::new(uri.clone(), range(text, &label.location))
+            } else {
+                let other = Url::from_file_path(root.join(&label.location.file)).ok()?;
+                let start = Position::new(
+                    line_index(label.location.line),
+                    line_index(label.location.column),
+                );
+                tower_lsp::lsp_types::Location::new(other, Range::new(start, start))
+            };
+            Some(DiagnosticRelatedInformation {
+                location,
+                message: label.message.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut message = violation.message.clone();
+    if let Some(suggestion) = &violation.suggestion {
+        message.push_str("\nhelp: ");
+        message.push_str(&suggestion.message);
+    }
+
+    Diagnostic {
+        range: range(text, &violation.location),
+        severity: Some(severity(violation.severity)),
+        code: Some(NumberOrString::String(violation.code.clone())),
+        source: Some(SOURCE.to_string()),
+        message,
+        related_information: (!related.is_empty()).then_some(related),
+        ..Diagnostic::default()
+    }
+}
+
+/// Maps a violation severity to a diagnostic severity.
+pub(crate) fn severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Error => DiagnosticSeverity::ERROR,
+    }
+}
+
+/// Returns the range of `location` in `text`.
+///
+/// Uses the byte span when the rule recorded one; otherwise the range
+/// covers the word starting at the line and column.
+pub(crate) fn range(text: &str, location: &Location) -> Range {
+    if location.length > 0 {
+        if let (Some(start), Some(end)) = (
+            position_at(text, location.offset),
+            position_at(text, location.offset + location.length),
+        ) {
+            return Range::new(start, end);
+        }
+    }
+
+    let line = line_index(location.line);
+    let Some(content) = text.lines().nth(line as usize) else {
+        let start = Position::new(line, 0);
+        return Range::new(start, start);
+    };
+    let chars = location.column.saturating_sub(1);
+    let start = utf16_len(content.chars().take(chars));
+    let word = utf16_len(
+        content
+            .chars()
+            .skip(chars)
+            .take_while(|c| c.is_alphanumeric() || *c == '_'),
+    );
+    Range::new(
+        Position::new(line, start),
+        Position::new(line, start + word),
+    )
+}
+
+/// Returns the position of the byte `offset` in `text`, or `None` if it is
+/// out of bounds or not on a character boundary.
+pub(crate) fn position_at(text: &str, offset: usize) -> Option<Position> {
+    let before = text.get(..offset)?;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before.matches('\n').count();
+    Some(Position::new(
+        u32::try_from(line).unwrap_or(u32::MAX),
+        utf16_len(before[line_start..].chars()),
+    ))
+}
+
+/// Converts a 1-based line or column into a 0-based index.
+pub(crate) fn line_index(one_based: usize) -> u32 {
+    u32::try_from(one_based.saturating_sub(1)).unwrap_or(u32::MAX)
+}
+
+fn utf16_len(chars: impl Iterator<Item = char>) -> u32 {
+    u32::try_from(chars.map(char::len_utf16).sum::<usize>()).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_lint_core::{Label, Suggestion};
+    use std::path::PathBuf;
+
+    fn uri() -> Url {
+        Url::parse("file:///project/src/lib.rs").expect("valid url")
+    }
+
+    #[test]
+    fn test_range_covers_word_at_column() {
+        let text = "fn f() {\n    let s = \"é\"; x.unwrap();\n}\n";
+        let location = Location::new(PathBuf::from("src/lib.rs"), 2, 20);
+        assert_eq!(
+            range(text, &location),
+            Range::new(Position::new(1, 19), Position::new(1, 25))
+        );
+    }
+
+    #[test]
+    fn test_range_uses_byte_span_in_utf16() {
+        let text = "// 𝄞\nlet x = y.into();\n";
+        let offset = text.find("y.into()").expect("present");
+        let location = Location::new(PathBuf::from("src/lib.rs"), 2, 9).with_span(offset, 8);
+        assert_eq!(
+            range(text, &location),
+            Range::new(Position::new(1, 8), Position::new(1, 16))
+        );
+        assert_eq!(position_at("// 𝄞", 7), Some(Position::new(0, 5)));
+        assert_eq!(position_at("// 𝄞", 4), None);
+    }
+
+    #[test]
+    fn test_diagnostic_maps_fields_and_labels() {
+        let text = "fn main() {\n    x.unwrap();\n}\n";
+        let file = PathBuf::from("src/lib.rs");
+        let violation = Violation::new(
+            "AL001",
+            "no-unwrap-expect",
+            Severity::Error,
+            Location::new(file.clone(), 2, 7),
+            "Use of unwrap()",
+        )
+        .with_label(Label::new(Location::new(file, 1, 4), "in this function"))
+        .with_label(Label::new(
+            Location::new(PathBuf::from("src/other.rs"), 3, 1),
+            "defined here",
+        ))
+        .with_suggestion(Suggestion::new("Use `?` instead"));
+
+        let diagnostic = diagnostic(&violation, text, &uri(), Path::new("/project"));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("AL001".to_string()))
+        );
+        assert_eq!(diagnostic.source.as_deref(), Some(SOURCE));
+        assert_eq!(diagnostic.message, "Use of unwrap()\nhelp: Use `?` instead");
+        assert_eq!(
+            diagnostic.range,
+            Range::new(Position::new(1, 6), Position::new(1, 12))
+        );
+
+        let related = diagnostic.related_information.expect("labels");
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].location.uri, uri());
+        assert_eq!(related[0].message, "in this function");
+        assert_eq!(
+            related[1].location.uri.as_str(),
+            "file:///project/src/other.rs"
+        );
+        assert_eq!(related[1].location.range.start, Position::new(2, 0));
+    }
+}
@@ -0,0 +1,27 @@
+//! Language server for arch-lint.
+//!
+//! Speaks LSP over stdin/stdout and publishes arch-lint violations as
+//! diagnostics for open Rust documents, with code actions to suppress them
+//! or apply a rule's suggested fix. See the README for the supported
+//! protocol surface.
+//!
+//! Logs go to stderr and are filtered with `RUST_LOG`.
+
+mod actions;
+mod convert;
+mod server;
+
+use tower_lsp::{LspService, Server};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+
+    let (service, socket) = LspService::new(server::Backend::new);
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+}
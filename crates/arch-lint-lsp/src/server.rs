@@ -0,0 +1,381 @@
+//! The language server: document tracking, analysis, and LSP handlers.
+
+use crate::{actions, convert};
+use arch_lint_core::{Analyzer, Config, ConfigFormat, Violation};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::{
+    CodeActionParams, CodeActionProviderCapability, CodeActionResponse,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, InitializeParams, InitializeResult, InitializedParams, MessageType,
+    ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url,
+};
+use tower_lsp::{Client, LanguageServer};
+
+/// An open document and the violations last computed for it.
+struct Document {
+    text: String,
+    /// LSP version of `text`, if the client has sent one.
+    version: Option<i32>,
+    /// Revision of the update that stored `text`.
+    revision: u64,
+    /// Violations computed on `text`; `None` while it is being analyzed or
+    /// does not parse, since their offsets would not match it.
+    violations: Option<Vec<Violation>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Project root that violation paths are relative to.
+    root: PathBuf,
+    /// `None` until initialized, or if the config could not be loaded.
+    analyzer: Option<Arc<Analyzer>>,
+    documents: HashMap<Url, Document>,
+    /// Last revision handed out by [`begin_update`](Self::begin_update).
+    revision: u64,
+}
+
+impl State {
+    /// Stores new text for `uri` and returns the revision to analyze it
+    /// under, with the document's version.
+    ///
+    /// Returns `None` if `version` is older than the stored one: handlers
+    /// run concurrently, so a stale `didChange` may arrive late.
+    fn begin_update(
+        &mut self,
+        uri: &Url,
+        text: &str,
+        version: Option<i32>,
+    ) -> Option<(u64, Option<i32>)> {
+        self.revision += 1;
+        let revision = self.revision;
+        let Some(doc) = self.documents.get_mut(uri) else {
+            self.documents.insert(
+                uri.clone(),
+                Document {
+                    text: text.to_string(),
+                    version,
+                    revision,
+                    violations: None,
+                },
+            );
+            return Some((revision, version));
+        };
+        if version
+            .zip(doc.version)
+            .is_some_and(|(new, stored)| new < stored)
+        {
+            return None;
+        }
+        text.clone_into(&mut doc.text);
+        doc.version = version.or(doc.version);
+        doc.revision = revision;
+        doc.violations = None;
+        Some((revision, doc.version))
+    }
+
+    /// Stores the violations found for `revision` of `uri`.
+    ///
+    /// Returns `false`, storing nothing, if the document was closed or got
+    /// newer text since the analysis started.
+    fn finish_update(&mut self, uri: &Url, revision: u64, violations: Vec<Violation>) -> bool {
+        match self.documents.get_mut(uri) {
+            Some(doc) if doc.revision == revision => {
+                doc.violations = Some(violations);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The arch-lint language server.
+pub(crate) struct Backend {
+    client: Client,
+    state: RwLock<State>,
+}
+
+impl Backend {
+    pub(crate) fn new(client: Client) -> Self {
+        Self {
+            client,
+            state: RwLock::new(State::default()),
+        }
+    }
+
+    /// Stores the new text of `uri`, analyzes it, and publishes the result.
+    ///
+    /// Text that does not parse keeps the previous diagnostics, so they do
+    /// not flicker while typing, but offers no code actions until it
+    /// parses again. Results for text that has since changed are dropped.
+    async fn update(&self, uri: Url, text: String, version: Option<i32>) {
+        let Ok(path) = uri.to_file_path() else {
+            return;
+        };
+        let (analyzer, root, revision, version) = {
+            let mut state = self.state.write().await;
+            let Some(analyzer) = state.analyzer.clone() else {
+                return;
+            };
+            let Some((revision, version)) = state.begin_update(&uri, &text, version) else {
+                tracing::debug!("Ignoring outdated version {version:?} of {uri}");
+                return;
+            };
+            (analyzer, state.root.clone(), revision, version)
+        };
+
+        let source = text.clone();
+        let outcome =
+            tokio::task::spawn_blocking(move || analyzer.analyze_source(&path, &source)).await;
+        let violations = match outcome {
+            Ok(Ok(violations)) => violations,
+            Ok(Err(e)) => {
+                tracing::debug!("Skipping {uri}: {e}");
+                return;
+            }
+            Err(e) => {
+                tracing::error!("Analysis of {uri} panicked: {e}");
+                return;
+            }
+        };
+
+        let diagnostics = violations
+            .iter()
+            .map(|v| convert::diagnostic(v, &text, &uri, &root))
+            .collect();
+        // Publish under the lock, so a newer update cannot publish first.
+        let mut state = self.state.write().await;
+        if !state.finish_update(&uri, revision, violations) {
+            tracing::debug!("Dropping outdated analysis of {uri}");
+            return;
+        }
+        self.client
+            .publish_diagnostics(uri, diagnostics, version)
+            .await;
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        #[allow(deprecated)]
+        let folder = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| &folder.uri)
+            .or(params.root_uri.as_ref())
+            .and_then(|uri| uri.to_file_path().ok())
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+
+        let root = arch_lint_core::find_project_root(&folder);
+        match build_analyzer(&root, &folder) {
+            Ok(analyzer) => {
+                tracing::info!(
+                    "arch-lint: {} rules for {}",
+                    analyzer.rule_count(),
+                    root.display()
+                );
+                self.state.write().await.analyzer = Some(Arc::new(analyzer));
+            }
+            Err(e) => {
+                self.client
+                    .show_message(MessageType::ERROR, format!("arch-lint: {e}"))
+                    .await;
+            }
+        }
+        self.state.write().await.root = root;
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::FULL),
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                        ..TextDocumentSyncOptions::default()
+                    },
+                )),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                ..ServerCapabilities::default()
+            },
+            server_info: Some(ServerInfo {
+                name: env!("CARGO_PKG_NAME").to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        tracing::info!("arch-lint language server initialized");
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let doc = params.text_document;
+        self.update(doc.uri, doc.text, Some(doc.version)).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Full sync: the last change holds the whole document.
+        if let Some(change) = params.content_changes.into_iter().last() {
+            let doc = params.text_document;
+            self.update(doc.uri, change.text, Some(doc.version)).await;
+        }
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let uri = params.text_document.uri;
+        let text = match params.text {
+            Some(text) => Some(text),
+            None => self
+                .state
+                .read()
+                .await
+                .documents
+                .get(&uri)
+                .map(|doc| doc.text.clone()),
+        };
+        if let Some(text) = text {
+            self.update(uri, text, None).await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.state.write().await.documents.remove(&uri);
+        self.client.publish_diagnostics(uri, Vec::new(), None).await;
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let state = self.state.read().await;
+        let uri = params.text_document.uri;
+        let Some((doc, violations)) = state
+            .documents
+            .get(&uri)
+            .and_then(|doc| Some((doc, doc.violations.as_ref()?)))
+        else {
+            return Ok(None);
+        };
+        let actions = actions::code_actions(&uri, &doc.text, violations, params.range);
+        Ok((!actions.is_empty()).then_some(actions))
+    }
+}
+
+/// Builds the analyzer for `root` from the config discovered from `start`,
+/// like `arch-lint check` run in that directory.
+fn build_analyzer(root: &Path, start: &Path) -> std::result::Result<Analyzer, String> {
+    let (content, format, config_file) = if let Some(path) = arch_lint_core::find_config(start) {
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        (content, ConfigFormat::from_path(&path), Some(path))
+    } else {
+        let manifest = arch_lint_core::find_cargo_metadata(start);
+        let content = manifest
+            .as_deref()
+            .and_then(arch_lint_core::cargo_metadata_config)
+            .unwrap_or_default();
+        (content, ConfigFormat::Toml, manifest)
+    };
+
+    let config = if content.is_empty() {
+        Config::default()
+    } else {
+        Config::parse_as(&content, format).map_err(|e| format!("invalid config: {e}"))?
+    };
+    let preset =
+        arch_lint_rules::preset_rules(config.preset.as_deref().unwrap_or("recommended"), &config)
+            .map_err(|e| format!("invalid preset: {e}"))?;
+    let declarative = match &config_file {
+        Some(path) => arch_lint_core::declarative::load_file(path, &content, format),
+        None => arch_lint_core::declarative::load_all(&content, format),
+    }
+    .map_err(|e| format!("declarative config error: {e}"))?;
+
+    let mut builder = Analyzer::builder().root(root).config(config);
+    for rule in preset.into_iter().chain(declarative.rules) {
+        builder = builder.rule_box(rule);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_analyzer_uses_discovered_config() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("arch-lint.toml"),
+            "preset = \"minimal\"\n\n[rules.no-unwrap-expect]\nenabled = false\n",
+        )
+        .expect("write config");
+
+        let analyzer = build_analyzer(dir.path(), dir.path()).expect("analyzer");
+        let violations = analyzer
+            .analyze_source(
+                &dir.path().join("src/lib.rs"),
+                "pub fn f(x: Option<u8>) -> u8 { x.unwrap() }\n",
+            )
+            .expect("parses");
+        assert!(violations.is_empty(), "{violations:?}");
+
+        std::fs::write(dir.path().join("arch-lint.toml"), "preset = [").expect("write");
+        let Err(err) = build_analyzer(dir.path(), dir.path()) else {
+            panic!("expected an invalid config error");
+        };
+        assert!(err.starts_with("invalid config"), "{err}");
+
+        std::fs::write(
+            dir.path().join("arch-lint.toml"),
+            "preset = \"strictest\"\n",
+        )
+        .expect("write");
+        let Err(err) = build_analyzer(dir.path(), dir.path()) else {
+            panic!("expected an invalid preset error");
+        };
+        assert!(err.starts_with("invalid preset"), "{err}");
+    }
+
+    fn uri() -> Url {
+        Url::parse("file:///project/src/lib.rs").expect("valid uri")
+    }
+
+    #[test]
+    fn test_outdated_versions_are_ignored() {
+        let mut state = State::default();
+        let (first, _) = state.begin_update(&uri(), "v1", Some(1)).expect("first");
+        let (second, _) = state.begin_update(&uri(), "v3", Some(3)).expect("newer");
+        assert!(state.begin_update(&uri(), "v2", Some(2)).is_none());
+        assert_eq!(state.documents[&uri()].text, "v3");
+
+        // The slower analysis of v1 finishes last and is dropped.
+        assert!(state.finish_update(&uri(), second, Vec::new()));
+        assert!(!state.finish_update(&uri(), first, Vec::new()));
+
+        // A save without a version keeps the stored one.
+        let (_, version) = state.begin_update(&uri(), "v3", None).expect("save");
+        assert_eq!(version, Some(3));
+    }
+
+    #[test]
+    fn test_new_text_clears_violations_until_analyzed() {
+        let mut state = State::default();
+        let (revision, _) = state.begin_update(&uri(), "v1", Some(1)).expect("open");
+        state.finish_update(&uri(), revision, Vec::new());
+        assert!(state.documents[&uri()].violations.is_some());
+
+        // The new text does not parse, so it is never finished.
+        state.begin_update(&uri(), "fn (", Some(2)).expect("change");
+        assert!(state.documents[&uri()].violations.is_none());
+    }
+}
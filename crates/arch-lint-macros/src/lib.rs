@@ -23,6 +23,9 @@
 //!     config = "arch-lint.toml",
 //!     fail_on = "warning",
 //! }
+//!
+//! // With custom rules alongside the preset
+//! arch_lint::check!(rules = [MyRule::new()]);
 //! ```
 
 #![forbid(unsafe_code)]
@@ -30,7 +33,7 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{Ident, LitStr, Token};
+use syn::{ExprArray, Ident, LitStr, Token};
 
 /// Suppresses specified arch-lint rules for the annotated item.
 ///
@@ -41,6 +44,7 @@ use syn::{Ident, LitStr, Token};
 ///
 /// * `rules` - Comma-separated rule names to allow (e.g., `no_unwrap_expect`)
 /// * `reason` - Required for error-severity rules; explains why suppression is acceptable
+/// * `expires` - Optional `"YYYY-MM-DD"`; after this date the suppression stops applying
 ///
 /// # Examples
 ///
@@ -80,6 +84,8 @@ struct CheckArgs {
     preset: Option<String>,
     config: Option<String>,
     fail_on: Option<String>,
+    /// Custom rule expressions, each evaluating to a `Rule`.
+    rules: Option<ExprArray>,
 }
 
 impl Parse for CheckArgs {
@@ -87,20 +93,23 @@ impl Parse for CheckArgs {
         let mut preset = None;
         let mut config = None;
         let mut fail_on = None;
+        let mut rules = None;
 
         while !input.is_empty() {
             let key: Ident = input.parse()?;
             let _: Token![=] = input.parse()?;
-            let value: LitStr = input.parse()?;
 
             match key.to_string().as_str() {
-                "preset" => preset = Some(value.value()),
-                "config" => config = Some(value.value()),
-                "fail_on" => fail_on = Some(value.value()),
+                "preset" => preset = Some(input.parse::<LitStr>()?.value()),
+                "config" => config = Some(input.parse::<LitStr>()?.value()),
+                "fail_on" => fail_on = Some(input.parse::<LitStr>()?.value()),
+                "rules" => rules = Some(input.parse::<ExprArray>()?),
                 other => {
                     return Err(syn::Error::new(
                         key.span(),
-                        format!("unknown option `{other}`, expected: preset, config, fail_on"),
+                        format!(
+                            "unknown option `{other}`, expected: preset, config, fail_on, rules"
+                        ),
                     ));
                 }
             }
@@ -115,6 +124,7 @@ impl Parse for CheckArgs {
             preset,
             config,
             fail_on,
+            rules,
         })
     }
 }
@@ -140,6 +150,11 @@ impl Parse for CheckArgs {
 /// // Fail on warnings too (default: fail on errors only)
 /// arch_lint::check!(fail_on = "warning");
 ///
+/// // Custom `Rule` implementations, run alongside the preset and the
+/// // config's declarative rules. A custom rule replaces a preset rule of
+/// // the same name, and `[rules.<name>]` in the config applies to it.
+/// arch_lint::check!(rules = [NoTodoComments::new(), MyLayering::default()]);
+///
 /// // Combined
 /// arch_lint::check! {
 ///     preset = "strict",
@@ -167,6 +182,15 @@ pub fn check(input: TokenStream) -> TokenStream {
         quote! { None }
     };
 
+    // Built without `quote!` repetition, whose expansion uses `#[allow]`,
+    // ambiguous with this crate's `allow` attribute.
+    let rules: proc_macro2::TokenStream = args
+        .rules
+        .iter()
+        .flat_map(|array| &array.elems)
+        .map(|rule| quote! { ::std::boxed::Box::new(#rule) as ::arch_lint::RuleBox, })
+        .collect();
+
     let output = quote! {
         #[test]
         fn arch_lint_check() {
@@ -174,6 +198,7 @@ pub fn check(input: TokenStream) -> TokenStream {
                 #preset_expr,
                 #config_expr,
                 #fail_on_expr,
+                ::std::vec![#rules],
             );
         }
     };
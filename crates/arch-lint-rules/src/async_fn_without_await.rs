@@ -0,0 +1,339 @@
+//! Rule to detect `async fn` bodies that never `.await`.
+//!
+//! # Rationale
+//!
+//! An `async fn` without any `.await` is usually a synchronous function
+//! wearing async clothes: callers pay for a state machine and must `.await`
+//! a future that never yields, and blocking work inside it silently stalls
+//! the executor.
+//!
+//! # Detected Patterns
+//!
+//! - `async fn` (free functions and inherent methods) whose body contains no
+//!   `.await` expression
+//!
+//! Functions in `impl Trait for Type` blocks and trait definitions are
+//! skipped because the trait dictates the signature. `.await` inside macro
+//! invocations (e.g. `tokio::select!`) counts as awaiting.
+//!
+//! # Good Patterns
+//!
+//! ```ignore
+//! // Make it sync if there is nothing to await
+//! fn parse(input: &str) -> Config { ... }
+//!
+//! async fn load(path: &Path) -> io::Result<String> {
+//!     tokio::fs::read_to_string(path).await
+//! }
+//! ```
+
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use proc_macro2::{TokenStream, TokenTree};
+use syn::visit::Visit;
+use syn::{ExprAwait, ImplItemFn, ItemFn, ItemImpl, ItemMod, ItemTrait};
+
+/// Rule code for async-fn-without-await.
+pub const CODE: &str = "AL028";
+
+/// Rule name for async-fn-without-await.
+pub const NAME: &str = "async-fn-without-await";
+
+/// Flags `async fn` bodies that contain no `.await`.
+#[derive(Debug, Clone)]
+pub struct AsyncFnWithoutAwait {
+    /// Custom severity.
+    pub severity: Severity,
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+}
+
+impl Default for AsyncFnWithoutAwait {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsyncFnWithoutAwait {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+            allow_in_tests: true,
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+}
+
+impl Rule for AsyncFnWithoutAwait {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags async functions that never await"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "An `async fn` without any `.await` is usually a synchronous function wearing \
+         async clothes: callers pay for a state machine and must `.await` a future that \
+         never yields, and blocking work inside it silently stalls the executor."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        ctx.content.contains("async")
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = AsyncFnVisitor {
+            ctx,
+            rule: self,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct AsyncFnVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a AsyncFnWithoutAwait,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl AsyncFnVisitor<'_> {
+    fn check_fn(&mut self, sig: &syn::Signature, block: &syn::Block) {
+        if sig.asyncness.is_none() || self.context.is_skipped(self.rule.allow_in_tests) {
+            return;
+        }
+
+        let mut finder = AwaitFinder::default();
+        finder.visit_block(block);
+        if finder.found {
+            return;
+        }
+
+        let start = sig.fn_token.span.start();
+        if self
+            .ctx
+            .check_allow_for_rule(start.line, NAME, CODE)
+            .is_allowed()
+        {
+            return;
+        }
+
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!("`async fn {}` never awaits", sig.ident),
+            )
+            .with_suggestion(Suggestion::new(
+                "Remove `async` and make the function synchronous, or await the async work it should do",
+            )),
+        );
+    }
+}
+
+impl<'ast> Visit<'ast> for AsyncFnVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        self.check_fn(&node.sig, &node.block);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        // The trait dictates the signature of its impls.
+        if node.trait_.is_some() {
+            return;
+        }
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        self.check_fn(&node.sig, &node.block);
+        syn::visit::visit_impl_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_trait(&mut self, _node: &'ast ItemTrait) {}
+}
+
+/// Looks for `.await` in a function body, including inside macro tokens.
+#[derive(Default)]
+struct AwaitFinder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for AwaitFinder {
+    fn visit_expr_await(&mut self, _node: &'ast ExprAwait) {
+        self.found = true;
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        self.found |= contains_await(node.tokens.clone());
+    }
+
+    fn visit_item(&mut self, _node: &'ast syn::Item) {
+        // Nested items (e.g. inner `async fn`) are checked on their own.
+    }
+}
+
+fn contains_await(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "await",
+        TokenTree::Group(group) => contains_await(group.stream()),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        AsyncFnWithoutAwait::new().check(&ctx, &ast)
+    }
+
+    #[test]
+    fn test_applies_only_to_files_mentioning_async() {
+        let ctx = |content| FileContext::new(Path::new("test.rs"), content, Path::new(""));
+        let rule = AsyncFnWithoutAwait::new();
+        assert!(!rule.applies_to(&ctx("fn parse() {}")));
+        assert!(rule.applies_to(&ctx("async fn parse() {}")));
+    }
+
+    #[test]
+    fn test_detects_false_async() {
+        let violations = check_code(
+            r#"
+pub async fn parse(input: &str) -> usize {
+    input.len()
+}
+
+struct Service;
+impl Service {
+    async fn name(&self) -> &'static str { "svc" }
+}
+"#,
+        );
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].code, CODE);
+        assert!(violations[0].message.contains("parse"));
+        assert!(violations[1].message.contains("name"));
+    }
+
+    #[test]
+    fn test_allows_genuine_async() {
+        let violations = check_code(
+            r"
+async fn load(path: &str) -> std::io::Result<String> {
+    tokio::fs::read_to_string(path).await
+}
+
+async fn nested() {
+    let f = async { other().await };
+    drop(f);
+}
+
+async fn selected() {
+    tokio::select! {
+        _ = a().await => {}
+    }
+}
+",
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_skips_trait_impls_and_sync_fns() {
+        let violations = check_code(
+            r"
+fn sync() {}
+
+trait Store {
+    async fn get(&self) -> u8 { 0 }
+}
+
+impl Store for Memory {
+    async fn get(&self) -> u8 { 1 }
+}
+",
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_inner_fn_await_does_not_count_for_outer() {
+        let violations = check_code(
+            r"
+async fn outer() {
+    async fn inner() { work().await }
+}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("outer"));
+    }
+
+    #[test]
+    fn test_skips_test_code() {
+        let violations = check_code(
+            r"
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn works() {
+        assert!(true);
+    }
+}
+",
+        );
+        assert!(violations.is_empty());
+    }
+}
@@ -12,6 +12,17 @@
 //! - `#[async_trait]` without `?Send` (warns to consider if Send is needed)
 //! - Suggests using `#[async_trait(?Send)]` for single-threaded contexts
 //!
+//! # Runtime Modes
+//!
+//! - [`RuntimeMode::SingleThread`] (default): futures never cross threads, so
+//!   `#[async_trait]` without `?Send` is reported as an unnecessary bound.
+//! - [`RuntimeMode::MultiThread`]: work-stealing runtimes need `Send`
+//!   futures, so the default `#[async_trait]` is correct and nothing is
+//!   reported.
+//!
+//! Set the mode with `runtime = "current-thread"` or
+//! `runtime = "multi-thread"` under `[rules.async-trait-send-check]`.
+//!
 //! # Good Patterns
 //!
 //! ```ignore
@@ -28,9 +39,10 @@
 //! }
 //! ```
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::check_arch_lint_allow;
-use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use arch_lint_core::utils::check_arch_lint_allow_for_rule;
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
 use syn::spanned::Spanned;
 use syn::visit::Visit;
 use syn::{Attribute, ItemMod, ItemTrait};
@@ -46,7 +58,7 @@ pub const NAME: &str = "async-trait-send-check";
 pub struct AsyncTraitSendCheck {
     /// Severity level.
     pub severity: Severity,
-    /// Runtime mode: "single-thread" or "multi-thread".
+    /// Runtime the checked code runs on.
     pub runtime_mode: RuntimeMode,
 }
 
@@ -59,6 +71,21 @@ pub enum RuntimeMode {
     MultiThread,
 }
 
+impl RuntimeMode {
+    /// Looks up a mode by its config name.
+    ///
+    /// Accepts `current-thread` / `single-thread` and `multi-thread`, with
+    /// `-` or `_` as separator (matching tokio's `flavor` names).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.replace('_', "-").as_str() {
+            "current-thread" | "single-thread" => Some(Self::SingleThread),
+            "multi-thread" => Some(Self::MultiThread),
+            _ => None,
+        }
+    }
+}
+
 impl Default for AsyncTraitSendCheck {
     fn default() -> Self {
         Self::new()
@@ -103,10 +130,42 @@ impl Rule for AsyncTraitSendCheck {
         "Checks proper usage of async_trait Send bounds"
     }
 
+    fn explanation(&self) -> &'static str {
+        "`#[async_trait]` adds `Send` bounds to async trait methods by default. On \
+         single-threaded runtimes or local executors that bound is unnecessary and makes \
+         the trait harder to implement; use `#[async_trait(?Send)]` there."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
 
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["runtime", "runtime_mode"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        // `runtime_mode` is the older spelling of the key.
+        for key in ["runtime", "runtime_mode"] {
+            let Some(value) = config.options.get(key) else {
+                continue;
+            };
+            self.runtime_mode =
+                value
+                    .as_str()
+                    .and_then(RuntimeMode::from_name)
+                    .ok_or_else(|| ConfigError::InvalidOption {
+                        rule: NAME.to_string(),
+                        key: key.to_string(),
+                        message: format!(
+                            "expected \"current-thread\" or \"multi-thread\", got {value}"
+                        ),
+                    })?;
+            break;
+        }
+        Ok(())
+    }
+
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
         // Skip check for multi-threaded runtime
         if self.runtime_mode == RuntimeMode::MultiThread {
@@ -136,7 +195,7 @@ impl<'ast> Visit<'ast> for AsyncTraitVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -146,7 +205,9 @@ impl<'ast> Visit<'ast> for AsyncTraitVisitor<'_> {
 
     fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
         // Check if this trait is explicitly allowed
-        if self.in_allowed_context || check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if self.in_allowed_context
+            || check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed()
+        {
             syn::visit::visit_item_trait(self, node);
             return;
         }
@@ -163,7 +224,7 @@ impl<'ast> Visit<'ast> for AsyncTraitVisitor<'_> {
             let span = attr.span();
             let start = span.start();
 
-            let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+            let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
             if allow_check.is_allowed() {
                 // If reason is required but not provided, create a separate violation
                 if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -239,13 +300,7 @@ mod tests {
 
     fn check_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         AsyncTraitSendCheck::new().check(&ctx, &ast)
     }
 
@@ -323,13 +378,7 @@ trait MyTrait {
         )
         .expect("Failed to parse");
 
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: "",
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), "", Path::new(""));
 
         let rule = AsyncTraitSendCheck::new().runtime_mode(RuntimeMode::MultiThread);
         let violations = rule.check(&ctx, &ast);
@@ -356,4 +405,37 @@ trait Trait2 {
         );
         assert_eq!(violations.len(), 2);
     }
+
+    fn configured(toml: &str) -> Result<AsyncTraitSendCheck, ConfigError> {
+        let config = arch_lint_core::Config::parse(toml).expect("parse config");
+        let mut rule = AsyncTraitSendCheck::new();
+        rule.configure(&config.rules[NAME])?;
+        Ok(rule)
+    }
+
+    #[test]
+    fn test_configure_runtime_modes() {
+        let rule = configured("[rules.async-trait-send-check]\nruntime = \"current-thread\"\n")
+            .expect("valid runtime");
+        assert_eq!(rule.runtime_mode, RuntimeMode::SingleThread);
+
+        let rule = configured("[rules.async-trait-send-check]\nruntime = \"multi_thread\"\n")
+            .expect("valid runtime");
+        assert_eq!(rule.runtime_mode, RuntimeMode::MultiThread);
+
+        let rule = configured("[rules.async-trait-send-check]\nruntime_mode = \"multi-thread\"\n")
+            .expect("legacy key");
+        assert_eq!(rule.runtime_mode, RuntimeMode::MultiThread);
+
+        let rule = configured("[rules.async-trait-send-check]\nseverity = \"error\"\n")
+            .expect("no runtime key");
+        assert_eq!(rule.runtime_mode, RuntimeMode::SingleThread);
+    }
+
+    #[test]
+    fn test_configure_rejects_unknown_runtime() {
+        let err = configured("[rules.async-trait-send-check]\nruntime = \"green-threads\"\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("`runtime`"), "{err}");
+    }
 }
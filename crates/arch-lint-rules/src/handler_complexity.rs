@@ -9,18 +9,27 @@
 //! # Detected Patterns
 //!
 //! - Functions named `handle_*`, `process_*`, `on_*` with too many lines
+//! - Functions with too high cyclomatic complexity
 //! - Match expressions with too many arms
 //! - Action/Message enums with too many variants
 //!
+//! # Cyclomatic Complexity
+//!
+//! Starts at 1 and adds one for each `if`, each loop (`for`, `while`,
+//! `loop`), each `&&`/`||`, each `?`, and each match arm after the first.
+//! Closures count toward the enclosing function; nested `fn` items do not.
+//!
 //! # Configuration
 //!
 //! - `max_handler_lines`: Maximum lines in handler body (default: 150)
+//! - `max_cyclomatic_complexity`: Maximum cyclomatic complexity (default: 20)
+//! - `handlers_only`: Only check functions with handler names (default: true)
 //! - `max_match_arms`: Maximum arms in a match expression (default: 20)
 //! - `max_enum_variants`: Maximum variants in Action enum (default: 30)
 
 use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
 use syn::visit::Visit;
-use syn::{Expr, ExprMatch, ItemEnum, ItemFn};
+use syn::{BinOp, Expr, ExprBinary, ExprMatch, ImplItemFn, Item, ItemEnum, ItemFn};
 
 /// Rule code for handler-complexity.
 pub const CODE: &str = "AL004";
@@ -39,6 +48,10 @@ const ACTION_PATTERNS: &[&str] = &["Action", "Message", "Msg", "Event", "Command
 pub struct HandlerComplexityConfig {
     /// Maximum lines in a handler function body.
     pub max_handler_lines: usize,
+    /// Maximum cyclomatic complexity of a function.
+    pub max_cyclomatic_complexity: usize,
+    /// Apply function-level limits only to functions with handler names.
+    pub handlers_only: bool,
     /// Maximum arms in a match expression.
     pub max_match_arms: usize,
     /// Maximum variants in an Action enum.
@@ -49,6 +62,8 @@ impl Default for HandlerComplexityConfig {
     fn default() -> Self {
         Self {
             max_handler_lines: 150,
+            max_cyclomatic_complexity: 20,
+            handlers_only: true,
             max_match_arms: 20,
             max_enum_variants: 30,
         }
@@ -85,6 +100,22 @@ impl HandlerComplexity {
         self
     }
 
+    /// Sets maximum cyclomatic complexity.
+    #[must_use]
+    pub fn max_cyclomatic_complexity(mut self, max: usize) -> Self {
+        self.config.max_cyclomatic_complexity = max;
+        self
+    }
+
+    /// Sets whether function-level limits apply only to handler functions.
+    ///
+    /// When `false`, line and complexity limits apply to every function.
+    #[must_use]
+    pub fn handlers_only(mut self, handlers_only: bool) -> Self {
+        self.config.handlers_only = handlers_only;
+        self
+    }
+
     /// Sets maximum match arms.
     #[must_use]
     pub fn max_match_arms(mut self, max: usize) -> Self {
@@ -120,6 +151,12 @@ impl Rule for HandlerComplexity {
         "Limits complexity of handler functions"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Handler functions (especially in TEA/Elm architecture) tend to grow large with \
+         many match arms. Limiting their size and complexity encourages decomposition \
+         into smaller, focused functions."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
@@ -144,42 +181,72 @@ struct ComplexityVisitor<'a> {
     current_fn: Option<String>,
 }
 
-impl<'ast> Visit<'ast> for ComplexityVisitor<'_> {
-    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
-        let fn_name = node.sig.ident.to_string();
+impl ComplexityVisitor<'_> {
+    fn check_function(&mut self, ident: &syn::Ident, block: &syn::Block) {
+        let fn_name = ident.to_string();
+        let is_handler = is_handler_function(&fn_name);
+        if self.rule.config.handlers_only && !is_handler {
+            return;
+        }
 
-        // Check if this is a handler function
-        if is_handler_function(&fn_name) {
-            self.current_fn = Some(fn_name.clone());
+        let kind = if is_handler { "Handler" } else { "Function" };
+        let start = ident.span().start();
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
 
-            // Check line count
-            let line_count = count_block_lines(&node.block);
-            if line_count > self.rule.config.max_handler_lines {
-                let span = node.sig.ident.span();
-                let start = span.start();
-                let location =
-                    Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        // Check line count
+        let line_count = count_block_lines(block);
+        if line_count > self.rule.config.max_handler_lines {
+            self.violations.push(
+                Violation::new(
+                    CODE,
+                    NAME,
+                    self.rule.severity,
+                    location.clone(),
+                    format!(
+                        "{kind} `{fn_name}` has {line_count} lines (max: {})",
+                        self.rule.config.max_handler_lines
+                    ),
+                )
+                .with_suggestion(Suggestion::new(
+                    "Split into smaller functions or extract match arms to separate handlers",
+                )),
+            );
+        }
 
-                self.violations.push(
-                    Violation::new(
-                        CODE,
-                        NAME,
-                        self.rule.severity,
-                        location,
-                        format!(
-                            "Handler `{}` has {} lines (max: {})",
-                            fn_name, line_count, self.rule.config.max_handler_lines
-                        ),
-                    )
-                    .with_suggestion(Suggestion::new(
-                        "Split into smaller functions or extract match arms to separate handlers",
-                    )),
-                );
-            }
+        let complexity = cyclomatic_complexity(block);
+        if complexity > self.rule.config.max_cyclomatic_complexity {
+            self.violations.push(
+                Violation::new(
+                    CODE,
+                    NAME,
+                    self.rule.severity,
+                    location,
+                    format!(
+                        "{kind} `{fn_name}` has cyclomatic complexity {complexity} (max: {})",
+                        self.rule.config.max_cyclomatic_complexity
+                    ),
+                )
+                .with_suggestion(Suggestion::new(
+                    "Extract branches into helper functions or flatten with early returns",
+                )),
+            );
         }
+    }
+}
 
+impl<'ast> Visit<'ast> for ComplexityVisitor<'_> {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let outer_fn = self.current_fn.replace(node.sig.ident.to_string());
+        self.check_function(&node.sig.ident, &node.block);
         syn::visit::visit_item_fn(self, node);
-        self.current_fn = None;
+        self.current_fn = outer_fn;
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let outer_fn = self.current_fn.replace(node.sig.ident.to_string());
+        self.check_function(&node.sig.ident, &node.block);
+        syn::visit::visit_impl_item_fn(self, node);
+        self.current_fn = outer_fn;
     }
 
     fn visit_expr_match(&mut self, node: &'ast ExprMatch) {
@@ -264,62 +331,49 @@ fn is_action_enum(name: &str) -> bool {
     ACTION_PATTERNS.iter().any(|pattern| name.contains(pattern))
 }
 
-/// Counts lines in a block using span information.
-fn count_block_lines(block: &syn::Block) -> usize {
-    if block.stmts.is_empty() {
-        return 0;
-    }
-
-    let first_span = block.stmts.first().map(stmt_span);
-    let last_span = block.stmts.last().map(stmt_span);
+/// Computes the cyclomatic complexity of a function body.
+fn cyclomatic_complexity(block: &syn::Block) -> usize {
+    let mut counter = CyclomaticCounter { decisions: 0 };
+    counter.visit_block(block);
+    counter.decisions + 1
+}
 
-    match (first_span, last_span) {
-        (Some(first), Some(last)) => {
-            let start = first.start().line;
-            let end = last.end().line;
-            end.saturating_sub(start) + 1
-        }
-        _ => 0,
-    }
+/// Counts decision points, skipping nested items.
+struct CyclomaticCounter {
+    decisions: usize,
 }
 
-/// Gets the span of a statement.
-fn stmt_span(stmt: &syn::Stmt) -> proc_macro2::Span {
-    match stmt {
-        syn::Stmt::Local(local) => local.let_token.span,
-        syn::Stmt::Item(item) => item_span(item),
-        syn::Stmt::Expr(expr, _) => expr_span(expr),
-        syn::Stmt::Macro(m) => m
-            .mac
-            .path
-            .segments
-            .first()
-            .map_or_else(proc_macro2::Span::call_site, |s| s.ident.span()),
+impl<'ast> Visit<'ast> for CyclomaticCounter {
+    fn visit_item(&mut self, _node: &'ast Item) {}
+
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        match node {
+            Expr::If(_) | Expr::ForLoop(_) | Expr::While(_) | Expr::Loop(_) | Expr::Try(_) => {
+                self.decisions += 1;
+            }
+            Expr::Match(m) => self.decisions += m.arms.len().saturating_sub(1),
+            _ => {}
+        }
+        syn::visit::visit_expr(self, node);
     }
-}
 
-fn item_span(item: &syn::Item) -> proc_macro2::Span {
-    match item {
-        syn::Item::Fn(f) => f.sig.fn_token.span,
-        syn::Item::Struct(s) => s.ident.span(),
-        syn::Item::Enum(e) => e.ident.span(),
-        _ => proc_macro2::Span::call_site(),
+    fn visit_expr_binary(&mut self, node: &'ast ExprBinary) {
+        if matches!(node.op, BinOp::And(_) | BinOp::Or(_)) {
+            self.decisions += 1;
+        }
+        syn::visit::visit_expr_binary(self, node);
     }
 }
 
-fn expr_span(expr: &Expr) -> proc_macro2::Span {
-    match expr {
-        Expr::Match(m) => m.match_token.span,
-        Expr::If(i) => i.if_token.span,
-        Expr::Call(c) => expr_span(&c.func),
-        Expr::MethodCall(m) => m.method.span(),
-        Expr::Path(p) => p
-            .path
-            .segments
-            .first()
-            .map_or_else(proc_macro2::Span::call_site, |s| s.ident.span()),
-        _ => proc_macro2::Span::call_site(),
-    }
+/// Counts lines in a block, from the opening to the closing brace.
+///
+/// Uses the brace span rather than statement spans, so multi-line
+/// statements (e.g. one large `match`) are counted in full.
+fn count_block_lines(block: &syn::Block) -> usize {
+    let span = block.brace_token.span;
+    let start = span.open().start().line;
+    let end = span.close().end().line;
+    end.saturating_sub(start) + 1
 }
 
 #[cfg(test)]
@@ -329,13 +383,7 @@ mod tests {
 
     fn check_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         HandlerComplexity::new()
             .max_match_arms(3)
             .max_enum_variants(3)
@@ -392,4 +440,93 @@ fn handle_action(action: Action) {
         );
         assert!(violations.is_empty());
     }
+
+    fn complexity_of(body: &str) -> usize {
+        let item: ItemFn = syn::parse_str(body).expect("Failed to parse fn");
+        cyclomatic_complexity(&item.block)
+    }
+
+    #[test]
+    fn test_cyclomatic_complexity_values() {
+        assert_eq!(complexity_of("fn f() { g(); }"), 1);
+        assert_eq!(
+            complexity_of("fn f(a: bool) { if a { g(); } else { h(); } }"),
+            2
+        );
+        assert_eq!(
+            complexity_of("fn f(a: bool, b: bool) { if a && b { g(); } else if a || b { h(); } }"),
+            5
+        );
+        assert_eq!(
+            complexity_of(
+                "fn f(x: u8) -> Result<(), E> { match x { 0 => {}, 1 => {}, _ => {} } for _ in 0..3 { g()?; } Ok(()) }"
+            ),
+            5
+        );
+        assert_eq!(
+            complexity_of(
+                "fn f() { loop { while c() { let h = |x: bool| if x { 1 } else { 2 }; } } }"
+            ),
+            4
+        );
+    }
+
+    #[test]
+    fn test_cyclomatic_complexity_skips_nested_fns() {
+        assert_eq!(
+            complexity_of("fn f() { fn inner(a: bool) { if a {} if a {} } inner(true); }"),
+            1
+        );
+    }
+
+    #[test]
+    fn test_detects_complex_handler() {
+        let code = r"
+fn handle_input(a: bool, b: bool) {
+    if a && b { x(); }
+    if a || b { y(); }
+}
+
+fn helper(a: bool, b: bool) {
+    if a && b { x(); }
+    if a || b { y(); }
+}
+";
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+
+        let rule = HandlerComplexity::new().max_cyclomatic_complexity(4);
+        let violations = rule.check(&ctx, &ast);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0]
+            .message
+            .contains("Handler `handle_input` has cyclomatic complexity 5 (max: 4)"));
+
+        let violations = rule.handlers_only(false).check(&ctx, &ast);
+        assert_eq!(violations.len(), 2);
+        assert!(violations[1].message.starts_with("Function `helper`"));
+    }
+
+    #[test]
+    fn test_counts_full_span_of_multiline_statement() {
+        let arms = (0..12)
+            .map(|i| format!("        {i} => {{\n            step_{i}();\n        }}\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        let code = format!(
+            "fn handle_key(key: u8) {{\n    match key {{\n{arms}        _ => {{}}\n    }}\n}}\n"
+        );
+        let ast = syn::parse_file(&code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("test.rs"), &code, Path::new(""));
+
+        // One `match` statement spanning 39 lines; the old statement-span
+        // count reported it as a single line.
+        let violations = HandlerComplexity::new()
+            .max_handler_lines(40)
+            .check(&ctx, &ast);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0]
+            .message
+            .contains("Handler `handle_key` has 41 lines (max: 40)"));
+    }
 }
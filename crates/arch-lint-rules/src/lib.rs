@@ -21,6 +21,20 @@
 //! | AL011 | `no-panic-in-lib` | Forbids panic macros in library code |
 //! | AL012 | `require-doc-comments` | Requires documentation comments on public items |
 //! | AL013 | `no-silent-result-drop` | Forbids silently discarding Result error information |
+//! | AL027 | `no-anyhow-in-public-api` | Forbids `anyhow` and boxed errors in public library APIs |
+//! | AL028 | `async-fn-without-await` | Flags `async fn` bodies that never `.await` |
+//! | AL029 | `require-allow-reason` | Requires a reason on `#[allow(...)]` lint suppressions |
+//! | AL030 | `require-module-docs` | Requires a `//!` module doc comment in each source file |
+//! | AL031 | `require-constructor` | Forbids struct literals of types that must be built via a constructor |
+//! | AL032 | `max-fn-params` | Flags functions with too many parameters |
+//! | AL033 | `no-leaky-reexport` | Forbids `pub use` re-exports from internal modules |
+//! | AL034 | `no-lock-unwrap` | Forbids `.unwrap()`/`.expect()` on lock results |
+//! | AL035 | `no-blocking-in-iterator-closure` | Forbids blocking calls in iterator closures within async code |
+//! | AL036 | `tracked-todos` | Reports TODO/FIXME/XXX comments, optionally requiring an issue reference |
+//! | AL037 | `require-cfg-test-on-test-mods` | Requires `#[cfg(test)]` on test modules |
+//! | AL038 | `no-catch-unwind-in-lib` | Forbids `std::panic::catch_unwind` in library code |
+//! | AL039 | `no-log-and-return-error` | Forbids logging an error and then returning it unchanged |
+//! | AL040 | `orphan-modules` | Flags files under `src/` that no `mod` declaration reaches (project rule) |
 //!
 //! ## Usage
 //!
@@ -38,35 +52,65 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod async_fn_without_await;
 mod async_trait_send_check;
 mod handler_complexity;
+mod max_fn_params;
+mod no_anyhow_in_public_api;
+mod no_blocking_in_iterator_closure;
+mod no_catch_unwind_in_lib;
 mod no_error_swallowing;
+mod no_leaky_reexport;
+mod no_lock_unwrap;
+mod no_log_and_return_error;
 mod no_panic_in_lib;
 mod no_silent_result_drop;
 mod no_sync_io;
 mod no_unwrap_expect;
+mod orphan_modules;
 mod prefer_from_over_into;
 mod prefer_utoipa;
 mod presets;
+mod require_allow_reason;
+mod require_cfg_test_on_test_mods;
+mod require_constructor;
 mod require_doc_comments;
+mod require_module_docs;
 mod require_thiserror;
 mod require_tracing;
 mod require_tracing_v2;
 mod tracing_env_init;
+mod tracked_todos;
 
+pub use async_fn_without_await::AsyncFnWithoutAwait;
 pub use async_trait_send_check::{AsyncTraitSendCheck, RuntimeMode};
 pub use handler_complexity::{HandlerComplexity, HandlerComplexityConfig};
+pub use max_fn_params::MaxFnParams;
+pub use no_anyhow_in_public_api::NoAnyhowInPublicApi;
+pub use no_blocking_in_iterator_closure::NoBlockingInIteratorClosure;
+pub use no_catch_unwind_in_lib::NoCatchUnwindInLib;
 pub use no_error_swallowing::NoErrorSwallowing;
+pub use no_leaky_reexport::NoLeakyReexport;
+pub use no_lock_unwrap::NoLockUnwrap;
+pub use no_log_and_return_error::NoLogAndReturnError;
 pub use no_panic_in_lib::NoPanicInLib;
 pub use no_silent_result_drop::NoSilentResultDrop;
 pub use no_sync_io::NoSyncIo;
 pub use no_unwrap_expect::NoUnwrapExpect;
+pub use orphan_modules::OrphanModules;
 pub use prefer_from_over_into::PreferFromOverInto;
-pub use presets::{all_rules, recommended_rules, strict_rules, Preset};
+pub use presets::{
+    all_rules, preset_rules, project_rules, recommended_rules, rule_registry, strict_rules, Preset,
+};
+pub use require_allow_reason::RequireAllowReason;
+pub use require_cfg_test_on_test_mods::RequireCfgTestOnTestMods;
+pub use require_constructor::{ConstructorRequirement, RequireConstructor};
 pub use require_doc_comments::RequireDocComments;
-pub use require_thiserror::RequireThiserror;
+pub use require_module_docs::RequireModuleDocs;
+pub use require_thiserror::{ProjectWideRequireThiserror, RequireThiserror};
 pub use require_tracing::RequireTracing;
 pub use tracing_env_init::TracingEnvInit;
+pub use tracked_todos::TrackedTodos;
 
 /// Re-export core types for convenience.
 pub use arch_lint_core::{Rule, Severity, Violation};
@@ -0,0 +1,352 @@
+//! Rule to limit the number of function parameters.
+//!
+//! # Rationale
+//!
+//! A function with a long parameter list is hard to call correctly: arguments
+//! of the same type are easily swapped, and every new option changes every
+//! call site. Grouping parameters into a struct names them and lets the list
+//! grow without breaking callers.
+//!
+//! # Detected Patterns
+//!
+//! - `pub fn` items and inherent `pub fn` methods with more than
+//!   `max_params` parameters (`self` is not counted)
+//!
+//! Methods in `impl Trait for Type` blocks are skipped, since the trait
+//! fixes their signature. `#[allow(clippy::too_many_arguments)]` on a
+//! function also allows this rule.
+//!
+//! # Configuration
+//!
+//! - `max_params`: Maximum parameters, excluding `self` (default: 7)
+//! - `include_private`: Also check non-`pub` functions (default: false)
+//! - `allow_in_tests`: Skip test code (default: true)
+
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::visit::Visit;
+use syn::{FnArg, ImplItemFn, ItemFn, ItemImpl, ItemMod, Signature, Visibility};
+
+/// Rule code for max-fn-params.
+pub const CODE: &str = "AL032";
+
+/// Rule name for max-fn-params.
+pub const NAME: &str = "max-fn-params";
+
+/// Flags functions with too many parameters.
+#[derive(Debug, Clone)]
+pub struct MaxFnParams {
+    /// Custom severity.
+    pub severity: Severity,
+    /// Maximum parameters, excluding `self`.
+    pub max_params: usize,
+    /// Also check non-`pub` functions.
+    pub include_private: bool,
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+}
+
+impl Default for MaxFnParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MaxFnParams {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+            max_params: 7,
+            include_private: false,
+            allow_in_tests: true,
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets the maximum number of parameters, excluding `self`.
+    #[must_use]
+    pub fn max_params(mut self, max: usize) -> Self {
+        self.max_params = max;
+        self
+    }
+
+    /// Sets whether non-`pub` functions are checked too.
+    #[must_use]
+    pub fn include_private(mut self, include: bool) -> Self {
+        self.include_private = include;
+        self
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+}
+
+impl Rule for MaxFnParams {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags functions with too many parameters"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Long parameter lists are easy to call with swapped arguments and change at every \
+         call site when they grow. Group related parameters into a struct."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["max_params", "include_private", "allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        if let Some(value) = config.options.get("max_params") {
+            self.max_params = value
+                .as_integer()
+                .and_then(|n| usize::try_from(n).ok())
+                .ok_or_else(|| ConfigError::InvalidOption {
+                    rule: NAME.to_string(),
+                    key: "max_params".to_string(),
+                    message: format!("expected a non-negative integer, got {value}"),
+                })?;
+        }
+        for (key, field) in [
+            ("include_private", &mut self.include_private),
+            ("allow_in_tests", &mut self.allow_in_tests),
+        ] {
+            let Some(value) = config.options.get(key) else {
+                continue;
+            };
+            *field = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: key.to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = ParamsVisitor {
+            ctx,
+            rule: self,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE).allow_lints(&["clippy::too_many_arguments"]),
+            in_trait_impl: false,
+        };
+
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct ParamsVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a MaxFnParams,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+    in_trait_impl: bool,
+}
+
+impl ParamsVisitor<'_> {
+    fn check_signature(&mut self, vis: &Visibility, sig: &Signature) {
+        if self.context.is_skipped(self.rule.allow_in_tests)
+            || (!self.rule.include_private && !matches!(vis, Visibility::Public(_)))
+        {
+            return;
+        }
+        let count = sig
+            .inputs
+            .iter()
+            .filter(|arg| matches!(arg, FnArg::Typed(_)))
+            .count();
+        if count <= self.rule.max_params {
+            return;
+        }
+
+        let start = sig.ident.span().start();
+        if self
+            .ctx
+            .check_allow_for_rule(start.line, NAME, CODE)
+            .is_allowed()
+        {
+            return;
+        }
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!(
+                    "Function `{}` has {count} parameters (max: {})",
+                    sig.ident, self.rule.max_params
+                ),
+            )
+            .with_suggestion(Suggestion::new(
+                "Group related parameters into a struct (e.g. an options or params type)",
+            )),
+        );
+    }
+}
+
+impl<'ast> Visit<'ast> for ParamsVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        self.check_signature(&node.vis, &node.sig);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        let outer = std::mem::replace(&mut self.in_trait_impl, node.trait_.is_some());
+        syn::visit::visit_item_impl(self, node);
+        self.in_trait_impl = outer;
+        self.context.restore(saved);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        if !self.in_trait_impl {
+            self.check_signature(&node.vis, &node.sig);
+        }
+        syn::visit::visit_impl_item_fn(self, node);
+        self.context.restore(saved);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(rule: &MaxFnParams, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&MaxFnParams::new(), code)
+    }
+
+    #[test]
+    fn test_detects_too_many_params() {
+        let violations = check_code(
+            r"
+pub fn eight(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {}
+pub fn seven(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8) {}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(
+            violations[0].message,
+            "Function `eight` has 8 parameters (max: 7)"
+        );
+        assert_eq!(violations[0].location.line, 2);
+    }
+
+    #[test]
+    fn test_self_is_not_counted() {
+        let violations = check_code(
+            r"
+pub struct S;
+impl S {
+    pub fn seven(&self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8) {}
+    pub fn eight(self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) {}
+}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("`eight`"));
+    }
+
+    #[test]
+    fn test_private_functions_are_opt_in() {
+        let code = r"
+fn private(a: u8, b: u8, c: u8) {}
+pub(crate) fn crate_visible(a: u8, b: u8, c: u8) {}
+";
+        let rule = MaxFnParams::new().max_params(2);
+        assert!(check_with(&rule, code).is_empty());
+        assert_eq!(check_with(&rule.include_private(true), code).len(), 2);
+    }
+
+    #[test]
+    fn test_skips_trait_impls_tests_and_allowed() {
+        let rule = MaxFnParams::new().max_params(1);
+        let violations = check_with(
+            &rule,
+            r"
+impl Handler for S {
+    pub fn call(&self, a: u8, b: u8) {}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ffi(a: u8, b: u8) {}
+
+// arch-lint: allow(max-fn-params) reason: mirrors the C API
+pub fn mirrored(a: u8, b: u8) {}
+
+#[cfg(test)]
+mod tests {
+    pub fn helper(a: u8, b: u8) {}
+}
+",
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    fn configured(toml: &str) -> Result<MaxFnParams, ConfigError> {
+        let config = arch_lint_core::Config::parse(toml).expect("parse config");
+        let mut rule = MaxFnParams::new();
+        rule.configure(&config.rules[NAME])?;
+        Ok(rule)
+    }
+
+    #[test]
+    fn test_configure_options() {
+        let rule = configured("[rules.max-fn-params]\nmax_params = 4\ninclude_private = true\n")
+            .expect("valid options");
+        assert_eq!(rule.max_params, 4);
+        assert!(rule.include_private);
+
+        let err = configured("[rules.max-fn-params]\nmax_params = -1\n").expect_err("negative");
+        assert!(err.to_string().contains("max_params"), "{err}");
+    }
+}
@@ -0,0 +1,663 @@
+//! Rule to keep type-erased error types out of public library APIs.
+//!
+//! # Rationale
+//!
+//! Returning `anyhow::Error` (or `Box<dyn Error>`) from a public function
+//! forces every caller to depend on `anyhow` and makes it impossible to
+//! match on specific failures. Libraries should expose a crate-local error
+//! type instead; binaries and tests are free to use `anyhow`.
+//!
+//! # Detected Patterns
+//!
+//! - `pub fn f() -> Result<T, anyhow::Error>`
+//! - `pub fn f() -> anyhow::Result<T>`
+//! - `pub fn f() -> Result<T, Box<dyn std::error::Error>>`
+//!
+//! Public functions, public inherent methods, and methods of public traits
+//! are checked. Names imported with `use` are resolved, so
+//! `use anyhow::Result;` followed by `-> Result<T>` is reported too.
+//!
+//! Files matching `strict_paths` globs (e.g. `src/domain/**`) are held to
+//! the same standard internally: functions of any visibility are checked
+//! there, even in binaries.
+//!
+//! # Good Patterns
+//!
+//! ```ignore
+//! #[derive(Debug, thiserror::Error)]
+//! pub enum ConfigError {
+//!     #[error("missing key: {0}")]
+//!     MissingKey(String),
+//! }
+//!
+//! pub fn load() -> Result<Config, ConfigError> { ... }
+//! ```
+//!
+//! # Configuration
+//!
+//! - `forbidden_error_types`: Forbidden error types (default: `anyhow::Error`,
+//!   `eyre::Report`, `Box<dyn Error>`)
+//! - `strict_paths`: Globs of files checked regardless of visibility
+//!   (default: none)
+//! - `allow_in_tests`: Allow in test code (default: true)
+
+use std::collections::HashMap;
+
+use arch_lint_core::declarative::model::GlobPattern;
+use arch_lint_core::utils::{path_to_string, ContextTracker};
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{
+    GenericArgument, ImplItemFn, ItemFn, ItemImpl, ItemMod, ItemTrait, ItemUse, PathArguments,
+    ReturnType, Type, TypeParamBound, UseTree, Visibility,
+};
+
+/// Rule code for no-anyhow-in-public-api.
+pub const CODE: &str = "AL027";
+
+/// Rule name for no-anyhow-in-public-api.
+pub const NAME: &str = "no-anyhow-in-public-api";
+
+/// Error types forbidden by default.
+const DEFAULT_FORBIDDEN: &[&str] = &["anyhow::Error", "eyre::Report", "Box<dyn Error>"];
+
+/// Forbids type-erased error types in the return types of public functions.
+#[derive(Debug, Clone)]
+pub struct NoAnyhowInPublicApi {
+    /// Custom severity.
+    pub severity: Severity,
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+    /// Forbidden error types: full paths such as `anyhow::Error`, or
+    /// `Box<dyn Trait>` to match boxed trait objects by trait name.
+    pub forbidden_error_types: Vec<String>,
+    /// Glob patterns of files where functions of any visibility are checked.
+    pub strict_paths: Vec<GlobPattern>,
+}
+
+impl Default for NoAnyhowInPublicApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoAnyhowInPublicApi {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+            allow_in_tests: true,
+            forbidden_error_types: DEFAULT_FORBIDDEN.iter().map(ToString::to_string).collect(),
+            strict_paths: Vec::new(),
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Adds an error type to forbid (e.g. `my_util::DynError`).
+    #[must_use]
+    pub fn forbid_error_type(mut self, error_type: impl Into<String>) -> Self {
+        self.forbidden_error_types.push(error_type.into());
+        self
+    }
+
+    /// Removes an error type from the forbidden list (e.g. `Box<dyn Error>`).
+    #[must_use]
+    pub fn allow_error_type(mut self, error_type: &str) -> Self {
+        self.forbidden_error_types.retain(|t| t != error_type);
+        self
+    }
+
+    /// Checks every function, not only public ones, in files matching a
+    /// glob pattern relative to the analysis root. Invalid patterns are
+    /// ignored.
+    #[must_use]
+    pub fn strict_path(mut self, pattern: &str) -> Self {
+        self.strict_paths.extend(GlobPattern::new(pattern).ok());
+        self
+    }
+
+    /// Returns the forbidden entry matching a resolved error type path.
+    fn forbidden_path(&self, path: &str) -> Option<&str> {
+        self.forbidden_error_types
+            .iter()
+            .find(|t| *t == path)
+            .map(String::as_str)
+    }
+
+    /// Returns the forbidden entry matching `Box<dyn {trait_name}>`.
+    fn forbidden_dyn(&self, trait_name: &str) -> Option<&str> {
+        self.forbidden_error_types
+            .iter()
+            .find(|t| {
+                t.strip_prefix("Box<dyn ")
+                    .and_then(|rest| rest.strip_suffix('>'))
+                    .is_some_and(|name| name.trim() == trait_name)
+            })
+            .map(String::as_str)
+    }
+
+    /// Returns the forbidden entry whose module provides a `Result` alias,
+    /// so `anyhow::Result<T>` matches `anyhow::Error`.
+    fn forbidden_in_module(&self, module: &str) -> Option<&str> {
+        self.forbidden_error_types
+            .iter()
+            .find(|t| t.rsplit_once("::").is_some_and(|(m, _)| m == module))
+            .map(String::as_str)
+    }
+}
+
+impl Rule for NoAnyhowInPublicApi {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids anyhow and boxed errors in public library APIs"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Returning `anyhow::Error` or `Box<dyn Error>` from a public function forces \
+         every caller to depend on it and makes it impossible to match on specific \
+         failures. Libraries should expose a crate-local error type; binaries and tests \
+         are free to use `anyhow`."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["forbidden_error_types", "strict_paths", "allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        let invalid = |key: &str, message: String| ConfigError::InvalidOption {
+            rule: NAME.to_string(),
+            key: key.to_string(),
+            message,
+        };
+        if let Some(value) = config.options.get("forbidden_error_types") {
+            self.forbidden_error_types =
+                config.get_option("forbidden_error_types").ok_or_else(|| {
+                    invalid(
+                        "forbidden_error_types",
+                        format!("expected an array of types, got {value}"),
+                    )
+                })?;
+        }
+        if let Some(value) = config.options.get("strict_paths") {
+            let patterns: Vec<String> = config.get_option("strict_paths").ok_or_else(|| {
+                invalid(
+                    "strict_paths",
+                    format!("expected an array of globs, got {value}"),
+                )
+            })?;
+            self.strict_paths = patterns
+                .iter()
+                .map(|p| GlobPattern::new(p).map_err(|e| invalid("strict_paths", e.to_string())))
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| {
+                invalid("allow_in_tests", format!("expected a boolean, got {value}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        let strict = self
+            .strict_paths
+            .iter()
+            .any(|p| p.matches(&ctx.relative_path));
+        if !strict && (ctx.is_binary || ctx.is_example || ctx.is_build_script) {
+            return Vec::new();
+        }
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut imports = ImportCollector::default();
+        imports.visit_file(ast);
+
+        let mut visitor = PublicApiVisitor {
+            ctx,
+            rule: self,
+            imports: imports.imports,
+            strict,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct PublicApiVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a NoAnyhowInPublicApi,
+    imports: HashMap<String, String>,
+    /// Whether the file is under a `strict_paths` glob.
+    strict: bool,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl PublicApiVisitor<'_> {
+    /// Resolves a path's first segment through the file's `use` items.
+    fn resolve(&self, path: &syn::Path) -> String {
+        let full = path_to_string(path);
+        let (first, rest) = full.split_once("::").unwrap_or((&full, ""));
+        match self.imports.get(first) {
+            Some(imported) if rest.is_empty() => imported.clone(),
+            Some(imported) => format!("{imported}::{rest}"),
+            None => full,
+        }
+    }
+
+    /// Returns the forbidden entry matched by a function's return type.
+    fn forbidden_in_return(&self, output: &ReturnType) -> Option<String> {
+        let ReturnType::Type(_, ty) = output else {
+            return None;
+        };
+        let Type::Path(type_path) = ty.as_ref() else {
+            return None;
+        };
+        let resolved = self.resolve(&type_path.path);
+        let (module, name) = resolved.rsplit_once("::").unwrap_or(("", &resolved));
+        if name != "Result" {
+            return None;
+        }
+
+        let args = generic_types(type_path.path.segments.last()?);
+        match args.as_slice() {
+            [_, error] => self.forbidden_error(error),
+            [_] if !module.is_empty() => self.rule.forbidden_in_module(module).map(String::from),
+            _ => None,
+        }
+    }
+
+    fn forbidden_error(&self, ty: &Type) -> Option<String> {
+        let Type::Path(type_path) = ty else {
+            return None;
+        };
+        let last = type_path.path.segments.last()?;
+        if last.ident == "Box" {
+            let [Type::TraitObject(object)] = generic_types(last).as_slice() else {
+                return None;
+            };
+            return object.bounds.iter().find_map(|bound| match bound {
+                TypeParamBound::Trait(bound) => {
+                    let trait_path = bound.path.segments.last()?;
+                    self.rule
+                        .forbidden_dyn(&trait_path.ident.to_string())
+                        .map(String::from)
+                }
+                _ => None,
+            });
+        }
+        self.rule
+            .forbidden_path(&self.resolve(&type_path.path))
+            .map(String::from)
+    }
+
+    fn check_signature(&mut self, sig: &syn::Signature) {
+        if self.context.is_skipped(self.rule.allow_in_tests) {
+            return;
+        }
+        let Some(forbidden) = self.forbidden_in_return(&sig.output) else {
+            return;
+        };
+
+        let start = sig.output.span().start();
+        if self
+            .ctx
+            .check_allow_for_rule(start.line, NAME, CODE)
+            .is_allowed()
+        {
+            return;
+        }
+
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                if self.strict {
+                    format!(
+                        "Function `{}` returns `{forbidden}`; this path requires typed errors",
+                        sig.ident
+                    )
+                } else {
+                    format!(
+                        "Public function `{}` returns `{forbidden}`, which leaks into callers' APIs",
+                        sig.ident
+                    )
+                },
+            )
+            .with_suggestion(Suggestion::new(
+                "Define a crate-local error type (e.g. with `thiserror`) and return `Result<T, MyError>`",
+            )),
+        );
+    }
+}
+
+impl<'ast> Visit<'ast> for PublicApiVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        if self.strict || is_public(&node.vis) {
+            self.check_signature(&node.sig);
+        }
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        // Trait impls follow the trait's signature; only inherent methods
+        // define new API.
+        if node.trait_.is_some() {
+            return;
+        }
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        if self.strict || is_public(&node.vis) {
+            self.check_signature(&node.sig);
+        }
+        self.context.restore(saved);
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
+        if !self.strict && !is_public(&node.vis) {
+            return;
+        }
+        for item in &node.items {
+            if let syn::TraitItem::Fn(method) = item {
+                let saved = self.context.enter_fn(&method.attrs);
+                self.check_signature(&method.sig);
+                self.context.restore(saved);
+            }
+        }
+    }
+}
+
+fn is_public(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+/// Returns the type arguments of a path segment (`Result<T, E>` → `[T, E]`).
+fn generic_types(segment: &syn::PathSegment) -> Vec<&Type> {
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Vec::new();
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Collects `use` imports as local name → full path.
+#[derive(Default)]
+struct ImportCollector {
+    imports: HashMap<String, String>,
+}
+
+impl ImportCollector {
+    fn collect(&mut self, tree: &UseTree, prefix: &mut Vec<String>) {
+        match tree {
+            UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                self.collect(&path.tree, prefix);
+                prefix.pop();
+            }
+            UseTree::Name(name) => {
+                let ident = name.ident.to_string();
+                if ident == "self" {
+                    if let Some(last) = prefix.last() {
+                        self.imports.insert(last.clone(), prefix.join("::"));
+                    }
+                } else {
+                    let mut full = prefix.clone();
+                    full.push(ident.clone());
+                    self.imports.insert(ident, full.join("::"));
+                }
+            }
+            UseTree::Rename(rename) => {
+                let mut full = prefix.clone();
+                if rename.ident != "self" {
+                    full.push(rename.ident.to_string());
+                }
+                self.imports
+                    .insert(rename.rename.to_string(), full.join("::"));
+            }
+            UseTree::Glob(_) => {}
+            UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect(item, prefix);
+                }
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ImportCollector {
+    fn visit_item_use(&mut self, node: &'ast ItemUse) {
+        self.collect(&node.tree, &mut Vec::new());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arch_lint_core::TargetKind;
+    use std::path::Path;
+
+    fn check_with(rule: &NoAnyhowInPublicApi, code: &str, is_binary: bool) -> Vec<Violation> {
+        check_at(rule, "src/lib.rs", code, is_binary)
+    }
+
+    fn check_at(
+        rule: &NoAnyhowInPublicApi,
+        path: &str,
+        code: &str,
+        is_binary: bool,
+    ) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let target = if is_binary {
+            TargetKind::Binary
+        } else {
+            TargetKind::Library
+        };
+        let ctx = FileContext::with_target(Path::new(path), code, Path::new(""), target);
+        rule.check(&ctx, &ast)
+    }
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoAnyhowInPublicApi::new(), code, false)
+    }
+
+    #[test]
+    fn test_detects_anyhow_in_public_fns() {
+        let violations = check_code(
+            r"
+pub fn a() -> Result<(), anyhow::Error> { Ok(()) }
+pub fn b() -> anyhow::Result<u8> { Ok(1) }
+pub fn c() -> Result<(), Box<dyn std::error::Error + Send + Sync>> { Ok(()) }
+
+pub struct Client;
+impl Client {
+    pub fn d(&self) -> eyre::Result<()> { Ok(()) }
+}
+",
+        );
+        assert_eq!(violations.len(), 4);
+        assert_eq!(violations[0].code, CODE);
+        assert!(violations[0].message.contains("`a`"));
+        assert!(violations[2].message.contains("Box<dyn Error>"));
+    }
+
+    #[test]
+    fn test_resolves_imports() {
+        let violations = check_code(
+            r"
+use anyhow::Result;
+use anyhow::Error as AnyError;
+
+pub fn a() -> Result<()> { Ok(()) }
+pub fn b() -> std::result::Result<(), AnyError> { Ok(()) }
+",
+        );
+        assert_eq!(violations.len(), 2);
+    }
+
+    #[test]
+    fn test_ignores_private_typed_and_trait_impls() {
+        let violations = check_code(
+            r"
+#[derive(Debug, thiserror::Error)]
+#[error('x')]
+pub struct MyError;
+
+pub fn typed() -> Result<(), MyError> { Ok(()) }
+fn private() -> anyhow::Result<()> { Ok(()) }
+pub(crate) fn internal() -> anyhow::Result<()> { Ok(()) }
+
+impl std::str::FromStr for MyError {
+    type Err = anyhow::Error;
+    fn from_str(_: &str) -> Result<Self, anyhow::Error> { Ok(MyError) }
+}
+
+#[cfg(test)]
+mod tests {
+    pub fn helper() -> anyhow::Result<()> { Ok(()) }
+}
+",
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_allows_binaries() {
+        let code = "pub fn run() -> anyhow::Result<()> { Ok(()) }";
+        assert!(check_with(&NoAnyhowInPublicApi::new(), code, true).is_empty());
+    }
+
+    #[test]
+    fn test_checks_public_trait_methods() {
+        let violations = check_code(
+            r"
+pub trait Store {
+    fn get(&self) -> Result<u8, anyhow::Error>;
+}
+trait Private {
+    fn get(&self) -> anyhow::Result<u8>;
+}
+",
+        );
+        assert_eq!(violations.len(), 1);
+    }
+
+    #[test]
+    fn test_forbidden_types_are_configurable() {
+        let code = r"
+pub fn a() -> Result<(), my_util::DynError> { todo!() }
+pub fn b() -> Result<(), Box<dyn Error>> { todo!() }
+";
+        let rule = NoAnyhowInPublicApi::new()
+            .forbid_error_type("my_util::DynError")
+            .allow_error_type("Box<dyn Error>");
+        let violations = check_with(&rule, code, false);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("my_util::DynError"));
+    }
+
+    #[test]
+    fn test_strict_paths_check_every_visibility() {
+        let code = r"
+fn load() -> anyhow::Result<()> { Ok(()) }
+pub(crate) fn save() -> Result<(), Box<dyn Error>> { Ok(()) }
+trait Repo {
+    fn get(&self) -> anyhow::Result<u8>;
+}
+";
+        let rule = NoAnyhowInPublicApi::new()
+            .strict_path("src/domain/**")
+            .strict_path("src/app/**");
+
+        let domain = check_at(&rule, "src/domain/user.rs", code, false);
+        assert_eq!(domain.len(), 3, "{domain:?}");
+        assert!(domain[0].message.contains("requires typed errors"));
+        assert_eq!(check_at(&rule, "src/app/main.rs", code, true).len(), 3);
+
+        assert!(check_at(&rule, "src/bin/tool.rs", code, true).is_empty());
+        assert!(check_at(&rule, "src/infra/db.rs", code, false).is_empty());
+    }
+
+    #[test]
+    fn test_configure_strict_paths() {
+        let config = arch_lint_core::Config::parse(
+            "[rules.no-anyhow-in-public-api]\nstrict_paths = [\"src/domain/**\"]\nforbidden_error_types = [\"anyhow::Error\"]\n",
+        )
+        .expect("parse config");
+        let mut rule = NoAnyhowInPublicApi::new();
+        rule.configure(&config.rules[NAME]).expect("valid options");
+        let code = "fn load() -> anyhow::Result<()> { Ok(()) }";
+        assert_eq!(check_at(&rule, "src/domain/user.rs", code, false).len(), 1);
+        assert!(check_at(&rule, "src/bin/tool.rs", code, true).is_empty());
+        assert_eq!(rule.forbidden_error_types, ["anyhow::Error"]);
+
+        let config = arch_lint_core::Config::parse(
+            "[rules.no-anyhow-in-public-api]\nstrict_paths = \"src/domain/**\"\n",
+        )
+        .expect("parse config");
+        assert!(NoAnyhowInPublicApi::new()
+            .configure(&config.rules[NAME])
+            .is_err());
+    }
+
+    #[test]
+    fn test_allow_comment() {
+        let violations = check_code(
+            r#"
+// arch-lint: allow(no-anyhow-in-public-api) reason="internal tooling crate"
+pub fn a() -> anyhow::Result<()> { Ok(()) }
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+}
@@ -0,0 +1,405 @@
+//! Rule to forbid blocking calls in iterator closures within async code.
+//!
+//! # Rationale
+//!
+//! Closures passed to iterator combinators are synchronous even when the
+//! surrounding function is `async`, so they cannot `.await`. Blocking I/O or
+//! a nested `block_on` inside such a closure stalls the runtime's worker
+//! thread for every element of the pipeline.
+//!
+//! # Detected Patterns
+//!
+//! Inside a non-async closure passed to `.map()`, `.filter()`,
+//! `.filter_map()`, `.flat_map()` or `.for_each()` within an `async fn`,
+//! async block, or async closure:
+//!
+//! - blocking I/O calls and `Path` methods, as reported by `no-sync-io`
+//! - `.block_on(..)` and `block_on(..)` calls
+//!
+//! # Limits
+//!
+//! Detection is by AST shape only: the receiver of the combinator is not
+//! known to be an iterator (so `Option::map` and `StreamExt::map` closures
+//! are checked too), closures passed by name are not followed, and blocking
+//! calls hidden inside other functions are not found. Async blocks inside
+//! the closure are not checked; `no-sync-io` covers them.
+//!
+//! # Configuration
+//!
+//! - `allow_in_tests`: Allow in test code (default: true)
+
+use crate::no_sync_io::{is_blocking_call, is_blocking_path_method};
+use arch_lint_core::utils::{path_to_string, ContextTracker};
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::visit::Visit;
+use syn::{
+    Expr, ExprAsync, ExprCall, ExprClosure, ExprMethodCall, ExprPath, ImplItemFn, ItemFn, ItemImpl,
+    ItemMod,
+};
+
+/// Rule code for no-blocking-in-iterator-closure.
+pub const CODE: &str = "AL035";
+
+/// Rule name for no-blocking-in-iterator-closure.
+pub const NAME: &str = "no-blocking-in-iterator-closure";
+
+/// Combinators whose closure argument is checked.
+const ITERATOR_ADAPTERS: &[&str] = &["map", "filter", "filter_map", "flat_map", "for_each"];
+
+/// Forbids blocking calls in iterator closures within async code.
+#[derive(Debug, Clone)]
+pub struct NoBlockingInIteratorClosure {
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+    /// Custom severity.
+    pub severity: Severity,
+}
+
+impl Default for NoBlockingInIteratorClosure {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoBlockingInIteratorClosure {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            allow_in_tests: true,
+            severity: Severity::Warning,
+        }
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Rule for NoBlockingInIteratorClosure {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids blocking calls in iterator closures within async code"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Closures passed to `.map()`, `.filter()` or `.for_each()` are synchronous even in \
+         an async fn. Blocking I/O or `block_on` inside them stalls the runtime for every \
+         element. Use an async stream (`futures::stream::iter(..).then(..)`) or move the \
+         work to `spawn_blocking`."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: "allow_in_tests".to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        ctx.content.contains("async")
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = IteratorClosureVisitor {
+            ctx,
+            rule: self,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+            in_async: false,
+            adapter: None,
+        };
+
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct IteratorClosureVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a NoBlockingInIteratorClosure,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+    in_async: bool,
+    /// The combinator whose closure is being visited, if any.
+    adapter: Option<String>,
+}
+
+impl IteratorClosureVisitor<'_> {
+    /// Visits with the given async state, outside any iterator closure.
+    fn with_async<F: FnOnce(&mut Self)>(&mut self, is_async: bool, visit: F) {
+        let was_async = std::mem::replace(&mut self.in_async, is_async);
+        let adapter = self.adapter.take();
+        visit(self);
+        self.in_async = was_async;
+        self.adapter = adapter;
+    }
+
+    fn report(&mut self, span: proc_macro2::Span, call: &str) {
+        let Some(adapter) = &self.adapter else {
+            return;
+        };
+        if self.context.is_skipped(self.rule.allow_in_tests) {
+            return;
+        }
+        let start = span.start();
+        if self
+            .ctx
+            .check_allow_for_rule(start.line, NAME, CODE)
+            .is_allowed()
+        {
+            return;
+        }
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!("Blocking `{call}` inside a `.{adapter}()` closure in async code"),
+            )
+            .with_suggestion(Suggestion::new(
+                "Use an async stream (`futures::stream::iter(..).then(..)`) and await the async \
+                 equivalent, or move the blocking work into `spawn_blocking`",
+            )),
+        );
+    }
+}
+
+impl<'ast> Visit<'ast> for IteratorClosureVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        self.with_async(node.sig.asyncness.is_some(), |v| {
+            syn::visit::visit_item_fn(v, node);
+        });
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        self.with_async(node.sig.asyncness.is_some(), |v| {
+            syn::visit::visit_impl_item_fn(v, node);
+        });
+        self.context.restore(saved);
+    }
+
+    fn visit_expr_async(&mut self, node: &'ast ExprAsync) {
+        self.with_async(true, |v| syn::visit::visit_expr_async(v, node));
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast ExprClosure) {
+        if node.asyncness.is_some() {
+            self.with_async(true, |v| syn::visit::visit_expr_closure(v, node));
+        } else {
+            syn::visit::visit_expr_closure(self, node);
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        let method = node.method.to_string();
+        if method == "block_on" || is_blocking_path_method(&method) {
+            self.report(node.method.span(), &format!(".{method}()"));
+        }
+
+        self.visit_expr(&node.receiver);
+        let checks_closure = self.in_async && ITERATOR_ADAPTERS.contains(&method.as_str());
+        for arg in &node.args {
+            if checks_closure && matches!(arg, Expr::Closure(c) if c.asyncness.is_none()) {
+                let outer = self.adapter.replace(method.clone());
+                self.visit_expr(arg);
+                self.adapter = outer;
+            } else {
+                self.visit_expr(arg);
+            }
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast ExprCall) {
+        if let Expr::Path(ExprPath { path, .. }) = &*node.func {
+            let path_str = path_to_string(path);
+            let is_block_on = path.segments.last().is_some_and(|s| s.ident == "block_on");
+            if is_block_on || is_blocking_call(&path_str) {
+                let span = path
+                    .segments
+                    .last()
+                    .map_or_else(proc_macro2::Span::call_site, |s| s.ident.span());
+                self.report(span, &path_str);
+            }
+        }
+
+        syn::visit::visit_expr_call(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(rule: &NoBlockingInIteratorClosure, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoBlockingInIteratorClosure::new(), code)
+    }
+
+    fn messages(violations: &[Violation]) -> Vec<&str> {
+        violations.iter().map(|v| v.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_detects_blocking_io_and_block_on_in_async_fn() {
+        let violations = check_code(
+            r"
+async fn load(paths: Vec<PathBuf>, rt: Runtime) {
+    let texts: Vec<_> = paths.iter().map(|p| std::fs::read_to_string(p)).collect();
+    paths.iter().filter(|p| p.exists()).count();
+    paths.into_iter().for_each(|p| {
+        rt.block_on(upload(p));
+    });
+}
+",
+        );
+        assert_eq!(
+            messages(&violations),
+            [
+                "Blocking `std::fs::read_to_string` inside a `.map()` closure in async code",
+                "Blocking `.exists()` inside a `.filter()` closure in async code",
+                "Blocking `.block_on()` inside a `.for_each()` closure in async code",
+            ]
+        );
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(violations[0].location.line, 3);
+    }
+
+    #[test]
+    fn test_checks_async_blocks_and_methods() {
+        let violations = check_code(
+            r"
+struct S;
+impl S {
+    async fn run(&self, ids: &[u8]) {
+        ids.iter().map(|id| futures::executor::block_on(fetch(*id)));
+    }
+}
+
+fn spawn(ids: Vec<u8>) {
+    tokio::spawn(async move {
+        ids.iter().for_each(|_| std::thread::sleep(D));
+    });
+}
+",
+        );
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        assert!(violations[0].message.contains("block_on"));
+        assert!(violations[1].message.contains("std::thread::sleep"));
+    }
+
+    #[test]
+    fn test_ignores_sync_code_and_non_closure_positions() {
+        let violations = check_code(
+            r"
+fn sync(paths: &[PathBuf]) {
+    paths.iter().map(|p| std::fs::read(p));
+}
+
+async fn outside_closure(paths: &[PathBuf]) {
+    let data = std::fs::read(&paths[0]);
+    paths.iter().map(|p| async move { std::fs::read(p) });
+    paths.iter().map(load_sync);
+    paths.iter().map(|p| {
+        fn helper(p: &Path) -> bool { p.exists() }
+        helper(p)
+    });
+}
+",
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_nested_adapters_report_once() {
+        let violations = check_code(
+            r"
+async fn f(dirs: &[Vec<PathBuf>]) {
+    dirs.iter().map(|d| d.iter().map(|p| std::fs::read(p)).count());
+}
+",
+        );
+        assert_eq!(
+            messages(&violations),
+            ["Blocking `std::fs::read` inside a `.map()` closure in async code"]
+        );
+    }
+
+    #[test]
+    fn test_skips_tests_and_allowed() {
+        let code = r#"
+async fn f(paths: &[PathBuf]) {
+    // arch-lint: allow(no-blocking-in-iterator-closure) reason="tiny files"
+    paths.iter().map(|p| std::fs::read(p));
+}
+
+#[cfg(test)]
+mod tests {
+    async fn t(paths: &[PathBuf]) {
+        paths.iter().map(|p| std::fs::read(p));
+    }
+}
+"#;
+        assert!(check_code(code).is_empty());
+        let strict = NoBlockingInIteratorClosure::new().allow_in_tests(false);
+        assert_eq!(check_with(&strict, code).len(), 1);
+    }
+}
@@ -0,0 +1,440 @@
+//! Rule to forbid `catch_unwind` in library code.
+//!
+//! # Rationale
+//!
+//! `std::panic::catch_unwind` in a library usually means panics are being
+//! used as recoverable errors. That breaks as soon as a consumer builds
+//! with `panic = "abort"` (nothing is caught), and it hides failures that
+//! callers could have handled had they been returned as `Result`.
+//!
+//! # Detected Patterns
+//!
+//! - `std::panic::catch_unwind(...)` and `::std::panic::catch_unwind(...)`
+//! - `panic::catch_unwind(...)` after `use std::panic;`
+//! - `catch_unwind(...)` after `use std::panic::catch_unwind;` (also
+//!   renamed or glob imports)
+//!
+//! Binaries, examples and build scripts are not checked. Allow directives
+//! for this rule must give a `reason`, e.g. for an FFI boundary where
+//! unwinding into foreign code would be undefined behavior.
+//!
+//! # Good Patterns
+//!
+//! ```ignore
+//! pub fn parse(input: &str) -> Result<Config, ParseError> {
+//!     let value = input.parse()?;
+//!     Ok(Config { value })
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use arch_lint_core::utils::{path_to_string, ContextTracker};
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::visit::Visit;
+use syn::{Expr, ExprCall, ExprPath, ItemFn, ItemImpl, ItemMod, ItemUse, UseTree};
+
+/// Rule code for no-catch-unwind-in-lib.
+pub const CODE: &str = "AL038";
+
+/// Rule name for no-catch-unwind-in-lib.
+pub const NAME: &str = "no-catch-unwind-in-lib";
+
+/// Fully qualified path of the forbidden function.
+const CATCH_UNWIND: &str = "std::panic::catch_unwind";
+
+/// Forbids `std::panic::catch_unwind` in library code.
+#[derive(Debug, Clone)]
+pub struct NoCatchUnwindInLib {
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+    /// Custom severity.
+    pub severity: Severity,
+}
+
+impl Default for NoCatchUnwindInLib {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoCatchUnwindInLib {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            allow_in_tests: true,
+            severity: Severity::Warning,
+        }
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Rule for NoCatchUnwindInLib {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids std::panic::catch_unwind in library code"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Catching panics in a library treats them as recoverable errors, which stops \
+         working under `panic = \"abort\"` and hides failures from callers. Return \
+         `Result` and propagate errors with `?` instead."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn requires_allow_reason(&self) -> bool {
+        true
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: "allow_in_tests".to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        ctx.content.contains("catch_unwind")
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if ctx.is_binary || ctx.is_example || ctx.is_build_script {
+            return Vec::new();
+        }
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut imports = Imports::default();
+        imports.visit_file(ast);
+
+        let mut visitor = CatchUnwindVisitor {
+            ctx,
+            rule: self,
+            imports,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+/// Local names brought in by `use` items anywhere in the file, mapped to
+/// the paths they stand for. Glob imports are kept as their prefixes.
+#[derive(Default)]
+struct Imports {
+    names: HashMap<String, String>,
+    globs: Vec<String>,
+}
+
+impl Imports {
+    fn add(&mut self, tree: &UseTree, prefix: &str) {
+        let join = |ident: &syn::Ident| {
+            if prefix.is_empty() {
+                ident.to_string()
+            } else {
+                format!("{prefix}::{ident}")
+            }
+        };
+        match tree {
+            UseTree::Path(p) => self.add(&p.tree, &join(&p.ident)),
+            // `use std::panic::{self}` imports `panic` itself.
+            UseTree::Name(n) if n.ident == "self" => {
+                let name = prefix.rsplit("::").next().unwrap_or(prefix);
+                self.names.insert(name.to_string(), prefix.to_string());
+            }
+            UseTree::Name(n) => {
+                self.names.insert(n.ident.to_string(), join(&n.ident));
+            }
+            UseTree::Rename(r) => {
+                self.names.insert(r.rename.to_string(), join(&r.ident));
+            }
+            UseTree::Glob(_) => self.globs.push(prefix.to_string()),
+            UseTree::Group(g) => {
+                for item in &g.items {
+                    self.add(item, prefix);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if `path`, as written at a call site, names
+    /// `std::panic::catch_unwind`.
+    fn is_catch_unwind(&self, path: &syn::Path) -> bool {
+        // `::std::...` has no leading segment to strip.
+        let written = path_to_string(path);
+        if written == CATCH_UNWIND {
+            return true;
+        }
+        let (first, rest) = match written.split_once("::") {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (written.as_str(), None),
+        };
+        match (self.names.get(first), rest) {
+            (Some(full), Some(rest)) => format!("{full}::{rest}") == CATCH_UNWIND,
+            (Some(full), None) => full == CATCH_UNWIND,
+            (None, None) => {
+                first == "catch_unwind" && self.globs.iter().any(|glob| glob == "std::panic")
+            }
+            (None, Some(_)) => false,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Imports {
+    fn visit_item_use(&mut self, node: &'ast ItemUse) {
+        self.add(&node.tree, "");
+    }
+}
+
+struct CatchUnwindVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a NoCatchUnwindInLib,
+    imports: Imports,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl CatchUnwindVisitor<'_> {
+    fn report(&mut self, path: &syn::Path) {
+        let span = path
+            .segments
+            .last()
+            .map_or_else(proc_macro2::Span::call_site, |s| s.ident.span());
+        let start = span.start();
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
+        if allow_check.is_allowed() {
+            if allow_check.reason().is_none() {
+                self.violations.push(
+                    Violation::new(
+                        CODE,
+                        NAME,
+                        Severity::Warning,
+                        location,
+                        format!("Allow directive for '{NAME}' is missing required reason"),
+                    )
+                    .with_suggestion(Suggestion::new(
+                        "Add reason=\"...\" to explain why this exception is necessary",
+                    )),
+                );
+            }
+            return;
+        }
+
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!("`{CATCH_UNWIND}` is forbidden in library code"),
+            )
+            .with_suggestion(Suggestion::new(
+                "Return `Result` and propagate errors with `?` instead of catching panics",
+            )),
+        );
+    }
+}
+
+impl<'ast> Visit<'ast> for CatchUnwindVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast ExprCall) {
+        if !self.context.is_skipped(self.rule.allow_in_tests) {
+            if let Expr::Path(ExprPath { path, .. }) = &*node.func {
+                if self.imports.is_catch_unwind(path) {
+                    self.report(path);
+                }
+            }
+        }
+        syn::visit::visit_expr_call(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoCatchUnwindInLib::new(), code, false)
+    }
+
+    fn check_with(rule: &NoCatchUnwindInLib, code: &str, is_binary: bool) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let mut ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        ctx.is_binary = is_binary;
+        rule.check(&ctx, &ast)
+    }
+
+    #[test]
+    fn test_detects_qualified_calls() {
+        let violations = check_code(
+            r"
+pub fn run(f: fn()) -> bool {
+    let a = std::panic::catch_unwind(f).is_ok();
+    let b = ::std::panic::catch_unwind(f).is_ok();
+    a && b
+}
+",
+        );
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(
+            violations[0].message,
+            "`std::panic::catch_unwind` is forbidden in library code"
+        );
+        assert_eq!(
+            (violations[0].location.line, violations[0].location.column),
+            (3, 25)
+        );
+        assert!(violations[0]
+            .suggestion
+            .as_ref()
+            .is_some_and(|s| s.message.contains("Result")));
+    }
+
+    #[test]
+    fn test_detects_imported_forms() {
+        let violations = check_code(
+            r"
+use std::panic;
+use std::panic::catch_unwind;
+use std::panic::catch_unwind as guard;
+
+pub fn run(f: fn()) {
+    let _ = panic::catch_unwind(f);
+    let _ = catch_unwind(f);
+    let _ = guard(f);
+}
+
+mod inner {
+    use std::panic::*;
+    pub fn run(f: fn()) {
+        let _ = catch_unwind(f);
+    }
+}
+",
+        );
+        assert_eq!(violations.len(), 4, "{violations:?}");
+    }
+
+    #[test]
+    fn test_ignores_unrelated_catch_unwind() {
+        let violations = check_code(
+            r"
+use futures::FutureExt;
+
+fn catch_unwind(f: fn()) {}
+
+pub async fn run(fut: impl std::future::Future<Output = ()>, f: fn()) {
+    let _ = fut.catch_unwind().await;
+    let _ = my_crate::catch_unwind(f);
+}
+",
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_allowed_in_tests_and_binaries() {
+        let code = r"
+pub fn run(f: fn()) {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn t() {
+        let caught = std::panic::catch_unwind(|| panic!());
+        assert!(caught.is_err());
+    }
+}
+";
+        assert!(check_code(code).is_empty());
+        assert_eq!(
+            check_with(
+                &NoCatchUnwindInLib::new().allow_in_tests(false),
+                code,
+                false
+            )
+            .len(),
+            1
+        );
+
+        let main = "fn main() { let _ = std::panic::catch_unwind(|| ()); }";
+        assert!(check_with(&NoCatchUnwindInLib::new(), main, true).is_empty());
+    }
+
+    #[test]
+    fn test_suppression_requires_reason() {
+        let violations = check_code(
+            r#"
+pub extern "C" fn callback() {
+    // arch-lint: allow(no-catch-unwind-in-lib) reason="must not unwind into C"
+    let _ = std::panic::catch_unwind(|| ());
+}
+
+pub fn run() {
+    // arch-lint: allow(no-catch-unwind-in-lib)
+    let _ = std::panic::catch_unwind(|| ());
+}
+"#,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].severity, Severity::Warning);
+        assert!(violations[0].message.contains("missing required reason"));
+    }
+}
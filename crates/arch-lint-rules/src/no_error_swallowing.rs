@@ -30,7 +30,8 @@
 //! // GOOD: Error is propagated
 //! result?;
 //!
-//! // GOOD: Error is propagated with context
+//! // GOOD: Error is propagated with context (the opt-in AL039
+//! // `no-log-and-return-error` flags this as double reporting)
 //! result.map_err(|e| {
 //!     tracing::error!("Failed: {}", e);
 //!     e
@@ -45,12 +46,28 @@
 //!     }
 //! };
 //! ```
+//!
+//! # Logging Macros
+//!
+//! Only the `log` and `tracing` level macros count as logging by default.
+//! Fully qualified calls (`tracing::error!`) always match; bare calls
+//! (`error!`) match only when the file imports the macro, e.g. via
+//! `use tracing::error;` or `use log::*;`. Unrelated macros that happen to
+//! be named `info!` are ignored. Entries without a path (e.g. `"eprintln"`)
+//! can be added with [`NoErrorSwallowing::add_logging_macro`] to match bare
+//! macros such as `eprintln!`.
+//!
+//! A `[logging]` section in the config (`macros = ["log", "tracing", "slog"]`)
+//! replaces the defaults with the level macros of the listed crates.
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use std::collections::HashMap;
+
+use arch_lint_core::{
+    FileContext, Label, Location, LoggingConfig, Rule, Severity, Suggestion, Violation,
+};
 use syn::spanned::Spanned;
 use syn::visit::Visit;
-use syn::{Arm, Expr, ExprIf, ExprMatch, Pat, Stmt};
+use syn::{Arm, Expr, ExprIf, ExprMatch, ItemUse, Pat, Stmt, UseTree};
 
 /// Rule code for no-error-swallowing.
 pub const CODE: &str = "AL003";
@@ -58,32 +75,14 @@ pub const CODE: &str = "AL003";
 /// Rule name for no-error-swallowing.
 pub const NAME: &str = "no-error-swallowing";
 
-/// Logging macro names to detect.
-const LOGGING_MACROS: &[&str] = &[
-    "error",
-    "warn",
-    "info",
-    "debug",
-    "trace",
-    "log::error",
-    "log::warn",
-    "log::info",
-    "log::debug",
-    "log::trace",
-    "tracing::error",
-    "tracing::warn",
-    "tracing::info",
-    "tracing::debug",
-    "tracing::trace",
-    "eprintln",
-    "println",
-];
-
 /// Forbids catching errors with only logging (no propagation).
 #[derive(Debug, Clone)]
 pub struct NoErrorSwallowing {
     /// Custom severity.
     pub severity: Severity,
+    /// Macros treated as logging: full paths, or bare names for
+    /// unqualified macros like `eprintln`.
+    pub logging_macros: Vec<String>,
 }
 
 impl Default for NoErrorSwallowing {
@@ -98,6 +97,7 @@ impl NoErrorSwallowing {
     pub fn new() -> Self {
         Self {
             severity: Severity::Warning,
+            logging_macros: LoggingConfig::default().level_macros(),
         }
     }
 
@@ -107,6 +107,28 @@ impl NoErrorSwallowing {
         self.severity = severity;
         self
     }
+
+    /// Replaces the set of logging macros.
+    #[must_use]
+    pub fn logging_macros(mut self, macros: &[&str]) -> Self {
+        self.logging_macros = macros.iter().map(|s| (*s).to_string()).collect();
+        self
+    }
+
+    /// Replaces the set of logging macros with the level macros of the
+    /// crates in `logging`.
+    #[must_use]
+    pub fn logging(mut self, logging: &LoggingConfig) -> Self {
+        self.configure_logging(logging);
+        self
+    }
+
+    /// Adds a logging macro (e.g. `"slog::error"` or `"eprintln"`).
+    #[must_use]
+    pub fn add_logging_macro(mut self, name: impl Into<String>) -> Self {
+        self.logging_macros.push(name.into());
+        self
+    }
 }
 
 impl Rule for NoErrorSwallowing {
@@ -122,14 +144,25 @@ impl Rule for NoErrorSwallowing {
         "Forbids catching errors with only logging (no propagation)"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Catching an error and only logging it hides the failure from callers and makes \
+         debugging difficult. Errors should either be propagated or handled with explicit \
+         recovery logic."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
 
+    fn configure_logging(&mut self, logging: &LoggingConfig) {
+        self.logging_macros = logging.level_macros();
+    }
+
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
         let mut visitor = ErrorSwallowingVisitor {
             ctx,
             rule: self,
+            macros: LoggingMacros::new(&self.logging_macros, ast),
             violations: Vec::new(),
         };
 
@@ -141,15 +174,136 @@ impl Rule for NoErrorSwallowing {
 struct ErrorSwallowingVisitor<'a> {
     ctx: &'a FileContext<'a>,
     rule: &'a NoErrorSwallowing,
+    macros: LoggingMacros<'a>,
     violations: Vec<Violation>,
 }
 
+/// Resolves macro invocations against the configured logging macros,
+/// using the file's `use` items for unqualified calls.
+pub(crate) struct LoggingMacros<'a> {
+    configured: &'a [String],
+    /// Imported name → full path (`error` → `tracing::error`).
+    imports: HashMap<String, String>,
+    /// Prefixes of glob imports (`use log::*` → `log`).
+    glob_prefixes: Vec<String>,
+}
+
+impl<'a> LoggingMacros<'a> {
+    pub(crate) fn new(configured: &'a [String], ast: &syn::File) -> Self {
+        let mut collector = UseCollector::default();
+        collector.visit_file(ast);
+        Self {
+            configured,
+            imports: collector.imports,
+            glob_prefixes: collector.glob_prefixes,
+        }
+    }
+
+    fn is_configured(&self, path: &str) -> bool {
+        self.configured.iter().any(|name| name == path)
+    }
+
+    /// Checks if a macro is a logging macro.
+    pub(crate) fn is_logging_macro(&self, mac: &syn::Macro) -> bool {
+        let segments: Vec<String> = mac
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        let Some((first, rest)) = segments.split_first() else {
+            return false;
+        };
+
+        // Bare configured names (e.g. `eprintln`) match unqualified calls.
+        if rest.is_empty() && self.is_configured(first) {
+            return true;
+        }
+
+        let head = self.imports.get(first).unwrap_or(first);
+        let resolved = std::iter::once(head.as_str())
+            .chain(rest.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("::");
+        if resolved.contains("::") && self.is_configured(&resolved) {
+            return true;
+        }
+
+        rest.is_empty()
+            && !self.imports.contains_key(first)
+            && self
+                .glob_prefixes
+                .iter()
+                .any(|prefix| self.is_configured(&format!("{prefix}::{first}")))
+    }
+}
+
+/// Collects names brought into scope by `use` items.
+#[derive(Default)]
+struct UseCollector {
+    imports: HashMap<String, String>,
+    glob_prefixes: Vec<String>,
+}
+
+impl UseCollector {
+    fn collect(&mut self, tree: &UseTree, prefix: &mut Vec<String>) {
+        match tree {
+            UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                self.collect(&path.tree, prefix);
+                prefix.pop();
+            }
+            UseTree::Name(name) => {
+                let ident = name.ident.to_string();
+                if ident == "self" {
+                    if let Some(last) = prefix.last() {
+                        self.imports.insert(last.clone(), prefix.join("::"));
+                    }
+                } else {
+                    self.imports
+                        .insert(ident.clone(), join_path(prefix, &ident));
+                }
+            }
+            UseTree::Rename(rename) => {
+                let ident = rename.ident.to_string();
+                let full = if ident == "self" {
+                    prefix.join("::")
+                } else {
+                    join_path(prefix, &ident)
+                };
+                self.imports.insert(rename.rename.to_string(), full);
+            }
+            UseTree::Glob(_) => self.glob_prefixes.push(prefix.join("::")),
+            UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect(item, prefix);
+                }
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for UseCollector {
+    fn visit_item_use(&mut self, node: &'ast ItemUse) {
+        self.collect(&node.tree, &mut Vec::new());
+    }
+}
+
+fn join_path(prefix: &[String], ident: &str) -> String {
+    if prefix.is_empty() {
+        ident.to_string()
+    } else {
+        format!("{}::{ident}", prefix.join("::"))
+    }
+}
+
 impl<'ast> Visit<'ast> for ErrorSwallowingVisitor<'_> {
     fn visit_expr_if(&mut self, node: &'ast ExprIf) {
         // Check for `if let Err(e) = expr { logging_only }`
         if let Expr::Let(expr_let) = &*node.cond {
-            if is_err_pattern(&expr_let.pat) && is_only_logging_block(&node.then_branch.stmts) {
-                self.report_violation(node.if_token.span);
+            let stmts = &node.then_branch.stmts;
+            if is_err_pattern(&expr_let.pat) && self.macros.is_only_logging_block(stmts) {
+                self.report_violation(node.if_token.span, self.macros.first_in_block(stmts));
             }
         }
 
@@ -158,7 +312,7 @@ impl<'ast> Visit<'ast> for ErrorSwallowingVisitor<'_> {
 
     fn visit_expr_match(&mut self, node: &'ast ExprMatch) {
         for arm in &node.arms {
-            if is_err_pattern(&arm.pat) && is_only_logging_expr(&arm.body) {
+            if is_err_pattern(&arm.pat) && self.macros.is_only_logging_expr(&arm.body) {
                 self.report_violation_at_arm(arm);
             }
         }
@@ -168,11 +322,34 @@ impl<'ast> Visit<'ast> for ErrorSwallowingVisitor<'_> {
 }
 
 impl ErrorSwallowingVisitor<'_> {
-    fn report_violation(&mut self, span: proc_macro2::Span) {
+    /// Locates `span` in the file, including its byte range when it fits on
+    /// one line.
+    fn location(&self, span: proc_macro2::Span) -> Location {
+        let (start, end) = (span.start(), span.end());
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        if start.line != end.line {
+            return location;
+        }
+        let offset = self.ctx.offset_for(start.line, start.column + 1);
+        location.with_span(offset, end.column.saturating_sub(start.column))
+    }
+
+    /// Labels the logging call that swallows the error.
+    fn logged_here(&self, logging: Option<&syn::Macro>) -> Option<Label> {
+        let mac = logging?;
+        let span = mac
+            .path
+            .span()
+            .join(mac.bang_token.span)
+            .unwrap_or_else(|| mac.path.span());
+        Some(Label::new(self.location(span), "error logged here"))
+    }
+
+    fn report_violation(&mut self, span: proc_macro2::Span, logging: Option<&syn::Macro>) {
         let start = span.start();
 
         // Check for inline allow comment
-        let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
         if allow_check.is_allowed() {
             // If reason is required but not provided, create a separate violation
             if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -194,27 +371,25 @@ impl ErrorSwallowingVisitor<'_> {
             return;
         }
 
-        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-
-        self.violations.push(
-            Violation::new(
-                CODE,
-                NAME,
-                self.rule.severity,
-                location,
-                "Error is caught but only logged, not propagated or handled",
-            )
-            .with_suggestion(Suggestion::new(
-                "Propagate error with `?` or add explicit recovery logic",
-            )),
-        );
+        let mut violation = Violation::new(
+            CODE,
+            NAME,
+            self.rule.severity,
+            self.location(span),
+            "Error is caught but only logged, not propagated or handled",
+        )
+        .with_suggestion(Suggestion::new(
+            "Propagate error with `?` or add explicit recovery logic",
+        ));
+        violation.labels.extend(self.logged_here(logging));
+        self.violations.push(violation);
     }
 
     fn report_violation_at_arm(&mut self, arm: &Arm) {
         let span = arm.pat.span();
         let start = span.start();
 
-        let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
         if allow_check.is_allowed() {
             // If reason is required but not provided, create a separate violation
             if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -236,20 +411,20 @@ impl ErrorSwallowingVisitor<'_> {
             return;
         }
 
-        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-
-        self.violations.push(
-            Violation::new(
-                CODE,
-                NAME,
-                self.rule.severity,
-                location,
-                "Error arm only logs without propagation or recovery",
-            )
-            .with_suggestion(Suggestion::new(
-                "Return the error or provide fallback value",
-            )),
-        );
+        let mut violation = Violation::new(
+            CODE,
+            NAME,
+            self.rule.severity,
+            self.location(span),
+            "Error arm only logs without propagation or recovery",
+        )
+        .with_suggestion(Suggestion::new(
+            "Return the error or provide fallback value",
+        ));
+        violation
+            .labels
+            .extend(self.logged_here(self.macros.first_in_expr(&arm.body)));
+        self.violations.push(violation);
     }
 }
 
@@ -269,60 +444,80 @@ fn is_err_pattern(pat: &Pat) -> bool {
     }
 }
 
-/// Checks if a block contains only logging statements.
-fn is_only_logging_block(stmts: &[Stmt]) -> bool {
-    if stmts.is_empty() {
-        return false;
-    }
+impl LoggingMacros<'_> {
+    /// Checks if a block contains only logging statements.
+    fn is_only_logging_block(&self, stmts: &[Stmt]) -> bool {
+        if stmts.is_empty() {
+            return false;
+        }
 
-    for stmt in stmts {
-        match stmt {
-            Stmt::Expr(expr, _) => {
-                if !is_logging_expr(expr) && !is_return_unit(expr) {
-                    return false;
-                }
-            }
-            Stmt::Local(local) => {
-                // Local bindings are generally OK in error handlers
-                if let Some(init) = &local.init {
-                    if !is_logging_expr(&init.expr) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::Expr(expr, _) => {
+                    if !self.is_logging_expr(expr) && !is_return_unit(expr) {
                         return false;
                     }
                 }
-            }
-            Stmt::Macro(stmt_macro) => {
-                if !is_logging_macro(&stmt_macro.mac) {
-                    return false;
+                Stmt::Local(local) => {
+                    // Local bindings are generally OK in error handlers
+                    if let Some(init) = &local.init {
+                        if !self.is_logging_expr(&init.expr) {
+                            return false;
+                        }
+                    }
+                }
+                Stmt::Macro(stmt_macro) => {
+                    if !self.is_logging_macro(&stmt_macro.mac) {
+                        return false;
+                    }
                 }
+                Stmt::Item(_) => return false,
             }
-            Stmt::Item(_) => return false,
         }
+
+        // Must have at least one logging statement
+        stmts
+            .iter()
+            .any(|s| matches!(s, Stmt::Macro(m) if self.is_logging_macro(&m.mac)))
     }
 
-    // Must have at least one logging statement
-    stmts
-        .iter()
-        .any(|s| matches!(s, Stmt::Macro(m) if is_logging_macro(&m.mac)))
-}
+    /// Returns the first logging macro among `stmts`.
+    pub(crate) fn first_in_block<'s>(&self, stmts: &'s [Stmt]) -> Option<&'s syn::Macro> {
+        stmts.iter().find_map(|stmt| match stmt {
+            Stmt::Macro(m) if self.is_logging_macro(&m.mac) => Some(&m.mac),
+            Stmt::Expr(expr, _) => self.first_in_expr(expr),
+            _ => None,
+        })
+    }
 
-/// Checks if an expression is only logging.
-fn is_only_logging_expr(expr: &Expr) -> bool {
-    match expr {
-        Expr::Block(block) => is_only_logging_block(&block.block.stmts),
-        Expr::Macro(m) => is_logging_macro(&m.mac),
-        _ => false,
+    /// Returns the first logging macro in a logging-only expression.
+    pub(crate) fn first_in_expr<'e>(&self, expr: &'e Expr) -> Option<&'e syn::Macro> {
+        match expr {
+            Expr::Macro(m) if self.is_logging_macro(&m.mac) => Some(&m.mac),
+            Expr::Block(block) => self.first_in_block(&block.block.stmts),
+            _ => None,
+        }
     }
-}
 
-/// Checks if an expression is a logging call.
-fn is_logging_expr(expr: &Expr) -> bool {
-    match expr {
-        Expr::Macro(m) => is_logging_macro(&m.mac),
-        Expr::Block(block) => block.block.stmts.iter().all(|s| {
-            matches!(s, Stmt::Expr(e, _) if is_logging_expr(e))
-                || matches!(s, Stmt::Macro(m) if is_logging_macro(&m.mac))
-        }),
-        _ => false,
+    /// Checks if an expression is only logging.
+    pub(crate) fn is_only_logging_expr(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Block(block) => self.is_only_logging_block(&block.block.stmts),
+            Expr::Macro(m) => self.is_logging_macro(&m.mac),
+            _ => false,
+        }
+    }
+
+    /// Checks if an expression is a logging call.
+    fn is_logging_expr(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Macro(m) => self.is_logging_macro(&m.mac),
+            Expr::Block(block) => block.block.stmts.iter().all(|s| {
+                matches!(s, Stmt::Expr(e, _) if self.is_logging_expr(e))
+                    || matches!(s, Stmt::Macro(m) if self.is_logging_macro(&m.mac))
+            }),
+            _ => false,
+        }
     }
 }
 
@@ -337,40 +532,19 @@ fn is_return_unit(expr: &Expr) -> bool {
     }
 }
 
-/// Checks if a macro is a logging macro.
-fn is_logging_macro(mac: &syn::Macro) -> bool {
-    // Get the path segments
-    let segments: Vec<_> = mac
-        .path
-        .segments
-        .iter()
-        .map(|s| s.ident.to_string())
-        .collect();
-    let path_str = segments.join("::");
-
-    // Check against known logging macros
-    LOGGING_MACROS.iter().any(|&name| {
-        path_str == name
-            || path_str.ends_with(&format!("::{name}"))
-            || segments.last().map(String::as_str) == Some(name)
-    })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::Path;
 
     fn check_code(code: &str) -> Vec<Violation> {
+        check_code_with(code, &NoErrorSwallowing::new())
+    }
+
+    fn check_code_with(code: &str, rule: &NoErrorSwallowing) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
-        NoErrorSwallowing::new().check(&ctx, &ast)
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
     }
 
     #[test]
@@ -388,6 +562,36 @@ fn foo() {
         assert_eq!(violations[0].code, CODE);
     }
 
+    #[test]
+    fn test_labels_logging_call() {
+        let code = r#"
+fn foo() {
+    if let Err(e) = do_something() {
+        tracing::error!("Failed: {}", e);
+    }
+    match run() {
+        Ok(()) => {}
+        Err(e) => tracing::warn!("ignored: {}", e),
+    }
+}
+"#;
+        let violations = check_code(code);
+        assert_eq!(violations.len(), 2);
+
+        let if_let = &violations[0];
+        assert_eq!((if_let.location.line, if_let.location.column), (3, 5));
+        assert_eq!(if_let.labels.len(), 1);
+        let label = &if_let.labels[0];
+        assert_eq!(label.message, "error logged here");
+        assert_eq!((label.location.line, label.location.column), (4, 9));
+        let span = label.location.offset..label.location.offset + label.location.length;
+        assert_eq!(&code[span], "tracing::error!");
+
+        let arm = &violations[1];
+        assert_eq!(arm.labels.len(), 1);
+        assert_eq!(arm.labels[0].location.line, 8);
+    }
+
     #[test]
     fn test_allows_error_propagation() {
         let violations = check_code(
@@ -415,4 +619,99 @@ fn foo() {
         );
         assert!(violations.is_empty());
     }
+
+    #[test]
+    fn test_detects_imported_bare_macro() {
+        let violations = check_code(
+            r#"
+use tracing::{error, warn as w};
+
+fn foo() {
+    if let Err(e) = do_something() {
+        error!("Failed: {}", e);
+    }
+    match do_other() {
+        Ok(v) => v,
+        Err(e) => w!("Failed: {}", e),
+    }
+}
+"#,
+        );
+        assert_eq!(violations.len(), 2);
+    }
+
+    #[test]
+    fn test_detects_glob_imported_macro() {
+        let violations = check_code(
+            r#"
+use log::*;
+
+fn foo() {
+    if let Err(e) = do_something() {
+        error!("Failed: {}", e);
+    }
+}
+"#,
+        );
+        assert_eq!(violations.len(), 1);
+    }
+
+    #[test]
+    fn test_custom_info_macro_is_not_logging() {
+        let violations = check_code(
+            r#"
+macro_rules! info {
+    ($($t:tt)*) => { show_dialog(format!($($t)*)) };
+}
+
+fn foo() {
+    if let Err(e) = do_something() {
+        info!("Could not save: {}", e);
+    }
+    if let Err(e) = do_other() {
+        ui::info!("Could not load: {}", e);
+    }
+}
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_println_is_not_logging_by_default() {
+        let code = r#"
+fn foo() {
+    if let Err(e) = do_something() {
+        eprintln!("error: {}", e);
+    }
+}
+"#;
+        assert!(check_code(code).is_empty());
+
+        let rule = NoErrorSwallowing::new().add_logging_macro("eprintln");
+        assert_eq!(check_code_with(code, &rule).len(), 1);
+    }
+
+    #[test]
+    fn test_logging_config_adds_slog() {
+        let code = r#"
+fn foo() {
+    if let Err(e) = do_something() {
+        slog::error!(logger, "failed: {}", e);
+    }
+}
+"#;
+        assert!(check_code(code).is_empty());
+
+        let config = arch_lint_core::Config::parse(
+            r#"
+[logging]
+macros = ["tracing", "log", "slog"]
+"#,
+        )
+        .expect("Failed to parse");
+        let logging = config.logging.expect("[logging] section");
+        let rule = NoErrorSwallowing::new().logging(&logging);
+        assert_eq!(check_code_with(code, &rule).len(), 1);
+    }
 }
@@ -0,0 +1,382 @@
+//! Rule to keep `pub use` from re-exporting internal modules.
+//!
+//! # Rationale
+//!
+//! A `pub use crate::internal::Parser;` makes `Parser` part of the public
+//! API, along with everything its signature drags in, although the module
+//! it lives in was meant to stay private. Such re-exports grow the API
+//! surface by accident and turn internal refactors into breaking changes.
+//! Public items should be re-exported from a curated module instead.
+//!
+//! # Detected Patterns
+//!
+//! - `pub use` of a path under an internal prefix, including groups,
+//!   renames, and globs: `pub use crate::internal::{Parser, Token as Tok};`
+//!
+//! `pub(crate)` and narrower re-exports are not reported.
+//!
+//! # Configuration
+//!
+//! - `internal`: Path patterns of internal modules; `*` matches one segment
+//!   and `**` any number (default: `["**::internal", "**::private",
+//!   "**::detail"]`). A path matches if it is, or lies under, a match.
+//! - `paths`: Glob patterns of files to check, relative to the analysis
+//!   root (default: every file)
+//! - `allow_in_tests`: Skip test code (default: true)
+
+use arch_lint_core::declarative::model::GlobPattern;
+use arch_lint_core::utils::paths::path_matches;
+use arch_lint_core::utils::{check_arch_lint_allow_for_rule, expand_use_tree, ContextTracker};
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::visit::Visit;
+use syn::{ImplItemFn, ItemFn, ItemImpl, ItemMod, ItemUse, Visibility};
+
+/// Rule code for no-leaky-reexport.
+pub const CODE: &str = "AL033";
+
+/// Rule name for no-leaky-reexport.
+pub const NAME: &str = "no-leaky-reexport";
+
+/// Internal module patterns used by default.
+const DEFAULT_INTERNAL: &[&str] = &["**::internal", "**::private", "**::detail"];
+
+/// Forbids `pub use` re-exports from internal modules.
+#[derive(Debug, Clone)]
+pub struct NoLeakyReexport {
+    /// Custom severity.
+    pub severity: Severity,
+    /// Path patterns of internal modules (e.g. `"crate::internal"`).
+    pub internal: Vec<String>,
+    /// Glob patterns of files to check; empty checks every file.
+    pub paths: Vec<GlobPattern>,
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+}
+
+impl Default for NoLeakyReexport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoLeakyReexport {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+            internal: DEFAULT_INTERNAL.iter().map(ToString::to_string).collect(),
+            paths: Vec::new(),
+            allow_in_tests: true,
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets the path patterns of internal modules, replacing the defaults.
+    #[must_use]
+    pub fn internal(mut self, patterns: &[&str]) -> Self {
+        self.internal = patterns.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Only checks files matching a glob pattern, relative to the analysis
+    /// root. Invalid patterns are ignored.
+    #[must_use]
+    pub fn path(mut self, pattern: &str) -> Self {
+        self.paths.extend(GlobPattern::new(pattern).ok());
+        self
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Returns the internal pattern `path` falls under, if any.
+    fn internal_match(&self, path: &str) -> Option<&str> {
+        self.internal
+            .iter()
+            .find(|pattern| path_matches(path, &format!("{pattern}::**")))
+            .map(String::as_str)
+    }
+}
+
+impl Rule for NoLeakyReexport {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids `pub use` re-exports from internal modules"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A `pub use` of an item in an internal module makes it public API by accident, so \
+         refactoring the internals becomes a breaking change. Re-export public items from a \
+         curated module, or narrow the re-export to `pub(crate) use`."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["internal", "paths", "allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        let invalid = |key: &str, message: String| ConfigError::InvalidOption {
+            rule: NAME.to_string(),
+            key: key.to_string(),
+            message,
+        };
+        if let Some(value) = config.options.get("internal") {
+            self.internal = config.get_option("internal").ok_or_else(|| {
+                invalid(
+                    "internal",
+                    format!("expected an array of paths, got {value}"),
+                )
+            })?;
+        }
+        if let Some(value) = config.options.get("paths") {
+            let patterns: Vec<String> = config.get_option("paths").ok_or_else(|| {
+                invalid("paths", format!("expected an array of globs, got {value}"))
+            })?;
+            self.paths = patterns
+                .iter()
+                .map(|p| GlobPattern::new(p).map_err(|e| invalid("paths", e.to_string())))
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| {
+                invalid("allow_in_tests", format!("expected a boolean, got {value}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        ctx.content.contains("pub use")
+            && (self.paths.is_empty() || self.paths.iter().any(|p| p.matches(&ctx.relative_path)))
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = ReexportVisitor {
+            ctx,
+            rule: self,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct ReexportVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a NoLeakyReexport,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl<'ast> Visit<'ast> for ReexportVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_impl_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_use(&mut self, node: &'ast ItemUse) {
+        if !matches!(node.vis, Visibility::Public(_))
+            || self.context.is_skipped(self.rule.allow_in_tests)
+            || check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed()
+        {
+            return;
+        }
+
+        for resolved in expand_use_tree(&node.tree, "") {
+            let Some(pattern) = self.rule.internal_match(&resolved.path) else {
+                continue;
+            };
+            let start = resolved.span.start();
+            if self
+                .ctx
+                .check_allow_for_rule(start.line, NAME, CODE)
+                .is_allowed()
+            {
+                continue;
+            }
+            let item = resolved.path.rsplit("::").next().unwrap_or_default();
+            let suggestion = if item == "*" {
+                "Re-export the public items one by one from a curated module, or use \
+                 `pub(crate) use`"
+                    .to_string()
+            } else {
+                format!(
+                    "Re-export `{item}` from a curated public module, or use `pub(crate) use` \
+                     if it is only needed inside the crate"
+                )
+            };
+            self.violations.push(
+                Violation::new(
+                    CODE,
+                    NAME,
+                    self.rule.severity,
+                    Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1),
+                    format!(
+                        "`pub use {}` re-exports from an internal module (matches `{pattern}`)",
+                        resolved.path
+                    ),
+                )
+                .with_suggestion(Suggestion::new(suggestion)),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(rule: &NoLeakyReexport, path: &str, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new(path), code, Path::new(""));
+        if !rule.applies_to(&ctx) {
+            return Vec::new();
+        }
+        rule.check(&ctx, &ast)
+    }
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoLeakyReexport::new(), "src/lib.rs", code)
+    }
+
+    #[test]
+    fn test_detects_reexports_from_internal_modules() {
+        let violations = check_code(
+            r"
+pub use crate::internal::Parser;
+pub use self::parse::private::{Token as Tok, Span};
+pub use crate::detail::*;
+pub use crate::api::Client;
+",
+        );
+        let messages: Vec<_> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "`pub use crate::internal::Parser` re-exports from an internal module \
+                 (matches `**::internal`)",
+                "`pub use self::parse::private::Token` re-exports from an internal module \
+                 (matches `**::private`)",
+                "`pub use self::parse::private::Span` re-exports from an internal module \
+                 (matches `**::private`)",
+                "`pub use crate::detail::*` re-exports from an internal module \
+                 (matches `**::detail`)",
+            ]
+        );
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(
+            (violations[0].location.line, violations[0].location.column),
+            (2, 26)
+        );
+        let suggestion = violations[0].suggestion.as_ref().expect("suggestion");
+        assert!(suggestion.message.contains("Re-export `Parser`"));
+    }
+
+    #[test]
+    fn test_restricted_visibility_tests_and_allowed_are_skipped() {
+        let violations = check_code(
+            r#"
+pub(crate) use crate::internal::Parser;
+use crate::internal::Lexer;
+
+#[arch_lint::allow(no_leaky_reexport, reason = "stable since 1.0")]
+pub use crate::internal::Token;
+
+// arch-lint: allow(no-leaky-reexport) reason: documented escape hatch
+pub use crate::internal::Span;
+
+#[cfg(test)]
+mod tests {
+    pub use crate::internal::Parser;
+}
+"#,
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_custom_internal_patterns_and_paths() {
+        let code = "pub use crate::engine::Cache;\npub use crate::internal::Parser;\n";
+        let rule = NoLeakyReexport::new().internal(&["crate::engine"]);
+        let violations = check_with(&rule, "src/lib.rs", code);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("crate::engine::Cache"));
+
+        let rule = NoLeakyReexport::new().path("src/lib.rs");
+        assert_eq!(check_with(&rule, "src/lib.rs", code).len(), 1);
+        assert!(check_with(&rule, "src/api/mod.rs", code).is_empty());
+    }
+
+    fn configured(toml: &str) -> Result<NoLeakyReexport, ConfigError> {
+        let config = arch_lint_core::Config::parse(toml).expect("parse config");
+        let mut rule = NoLeakyReexport::new();
+        rule.configure(&config.rules[NAME])?;
+        Ok(rule)
+    }
+
+    #[test]
+    fn test_configure_options() {
+        let rule = configured(
+            "[rules.no-leaky-reexport]\ninternal = [\"crate::imp\"]\npaths = [\"src/lib.rs\"]\n",
+        )
+        .expect("valid options");
+        assert_eq!(rule.internal, ["crate::imp"]);
+        assert_eq!(rule.paths.len(), 1);
+
+        let err = configured("[rules.no-leaky-reexport]\npaths = [\"src/[\"]\n").expect_err("glob");
+        assert!(err.to_string().contains("paths"), "{err}");
+        let err = configured("[rules.no-leaky-reexport]\ninternal = \"crate::imp\"\n")
+            .expect_err("not an array");
+        assert!(err.to_string().contains("internal"), "{err}");
+    }
+}
@@ -0,0 +1,303 @@
+//! Rule to forbid `.unwrap()` and `.expect()` on lock results.
+//!
+//! # Rationale
+//!
+//! `Mutex::lock`, `RwLock::read` and `RwLock::write` return `Err` when the
+//! lock is poisoned, i.e. another thread panicked while holding it.
+//! Unwrapping that result turns one panic into a cascade across every thread
+//! that touches the lock. Recover the guard with
+//! `.unwrap_or_else(PoisonError::into_inner)` or propagate the error instead.
+//!
+//! This is narrower than `no-unwrap-expect` (AL001) and can stay enabled
+//! where AL001 is relaxed or disabled.
+//!
+//! # Detected Patterns
+//!
+//! - `.lock().unwrap()`, `.read().unwrap()`, `.write().unwrap()`
+//! - the same chains ending in `.expect(..)`
+//!
+//! Only argument-less `lock`/`read`/`write` calls match, so
+//! `io::Read::read(&mut buf)` is not reported.
+//!
+//! # Configuration
+//!
+//! - `allow_in_tests`: Allow in test code (default: true)
+
+use crate::no_unwrap_expect::chained_method_call;
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::visit::Visit;
+use syn::{ExprMethodCall, ItemFn, ItemImpl, ItemMod};
+
+/// Rule code for no-lock-unwrap.
+pub const CODE: &str = "AL034";
+
+/// Rule name for no-lock-unwrap.
+pub const NAME: &str = "no-lock-unwrap";
+
+/// Lock acquisition methods whose result is poisoned after a panic.
+const LOCK_METHODS: &[&str] = &["lock", "read", "write"];
+
+/// Forbids `.unwrap()` and `.expect()` on `lock()`/`read()`/`write()` results.
+#[derive(Debug, Clone)]
+pub struct NoLockUnwrap {
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+    /// Custom severity.
+    pub severity: Severity,
+}
+
+impl Default for NoLockUnwrap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoLockUnwrap {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            allow_in_tests: true,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Rule for NoLockUnwrap {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids .unwrap()/.expect() on lock(), read() and write() results"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A lock is poisoned when a thread panics while holding it. Unwrapping \
+         `.lock()`, `.read()` or `.write()` spreads that panic to every other thread \
+         using the lock. Recover with `.unwrap_or_else(PoisonError::into_inner)` or \
+         propagate the error."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: "allow_in_tests".to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        LOCK_METHODS
+            .iter()
+            .any(|method| ctx.content.contains(&format!(".{method}(")))
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = LockUnwrapVisitor {
+            ctx,
+            rule: self,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct LockUnwrapVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a NoLockUnwrap,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl LockUnwrapVisitor<'_> {
+    fn check_call(&mut self, node: &ExprMethodCall) {
+        if self.context.is_skipped(self.rule.allow_in_tests)
+            || (node.method != "unwrap" && node.method != "expect")
+        {
+            return;
+        }
+        let Some(lock) = chained_method_call(&node.receiver)
+            .filter(|call| call.args.is_empty() && LOCK_METHODS.iter().any(|m| call.method == m))
+        else {
+            return;
+        };
+
+        let start = node.method.span().start();
+        if self
+            .ctx
+            .check_allow_for_rule(start.line, NAME, CODE)
+            .is_allowed()
+        {
+            return;
+        }
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!(
+                    ".{}().{}() panics if the lock is poisoned",
+                    lock.method, node.method
+                ),
+            )
+            .with_suggestion(Suggestion::new(
+                "Handle `PoisonError`: use `.unwrap_or_else(PoisonError::into_inner)` to recover \
+                 the guard, or map the error and propagate it with `?`",
+            )),
+        );
+    }
+}
+
+impl<'ast> Visit<'ast> for LockUnwrapVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        self.check_call(node);
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(rule: &NoLockUnwrap, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoLockUnwrap::new(), code)
+    }
+
+    #[test]
+    fn test_detects_lock_read_write_unwrap() {
+        let violations = check_code(
+            r#"
+fn f(m: &Mutex<u8>, rw: &RwLock<u8>) {
+    let a = m.lock().unwrap();
+    let b = rw.read().expect("poisoned");
+    let c = (rw.write()).unwrap();
+}
+"#,
+        );
+        let messages: Vec<_> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                ".lock().unwrap() panics if the lock is poisoned",
+                ".read().expect() panics if the lock is poisoned",
+                ".write().unwrap() panics if the lock is poisoned",
+            ]
+        );
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(violations[0].location.line, 3);
+    }
+
+    #[test]
+    fn test_ignores_other_chains() {
+        let violations = check_code(
+            r"
+fn f(m: &Mutex<u8>, file: &mut File, buf: &mut [u8]) {
+    let a = m.lock().unwrap_or_else(PoisonError::into_inner);
+    let n = file.read(buf).unwrap();
+    let v = parse().unwrap();
+    let g = m.try_lock().ok();
+}
+",
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_skips_tests_and_allowed() {
+        let code = r"
+fn f(m: &Mutex<u8>) {
+    // arch-lint: allow(no-lock-unwrap) reason: single-threaded init
+    let a = m.lock().unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    fn t(m: &Mutex<u8>) {
+        let a = m.lock().unwrap();
+    }
+}
+";
+        assert!(check_code(code).is_empty());
+        assert_eq!(
+            check_with(&NoLockUnwrap::new().allow_in_tests(false), code).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_configure_options() {
+        let config =
+            arch_lint_core::Config::parse("[rules.no-lock-unwrap]\nallow_in_tests = false\n")
+                .expect("parse config");
+        let mut rule = NoLockUnwrap::new();
+        rule.configure(&config.rules[NAME]).expect("valid options");
+        assert!(!rule.allow_in_tests);
+    }
+}
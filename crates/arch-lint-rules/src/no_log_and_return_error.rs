@@ -0,0 +1,574 @@
+//! Rule to forbid logging an error and then returning it unchanged.
+//!
+//! # Rationale
+//!
+//! An error that is logged and then propagated as-is is reported twice:
+//! once here and again by whichever layer finally handles it. The
+//! duplicated log lines carry no extra information and make incidents
+//! look worse than they are. Either handle the error (and log it), or
+//! return it, adding context if the caller needs more detail.
+//!
+//! This is the counterpart of `no-error-swallowing` (AL003), which flags
+//! errors that are logged but *not* propagated.
+//!
+//! # Detected Patterns
+//!
+//! ```ignore
+//! // BAD: logged, then propagated unchanged
+//! let config = load().map_err(|e| {
+//!     tracing::error!("load failed: {e}");
+//!     e
+//! })?;
+//!
+//! // BAD: same, with `inspect_err`
+//! let config = load().inspect_err(|e| tracing::error!("load failed: {e}"))?;
+//!
+//! // BAD: the arm logs, then returns the same error
+//! match load() {
+//!     Ok(config) => config,
+//!     Err(e) => {
+//!         log::error!("load failed: {e}");
+//!         return Err(e);
+//!     }
+//! }
+//! ```
+//!
+//! # Good Patterns
+//!
+//! ```ignore
+//! // GOOD: propagate and let the handler log
+//! let config = load()?;
+//!
+//! // GOOD: the returned error is a new value carrying more context
+//! let config = load().map_err(|e| {
+//!     tracing::debug!("load failed: {e}");
+//!     ConfigError::Load { path, source: e }
+//! })?;
+//! ```
+//!
+//! Returning `e.into()` counts as returning the same error. Logging macros
+//! are recognized as in AL003, including the `[logging]` config section.
+
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{
+    ConfigError, FileContext, Label, Location, LoggingConfig, Rule, RuleConfig, Severity,
+    Suggestion, Violation,
+};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{
+    Expr, ExprIf, ExprMatch, ExprMethodCall, ExprTry, Ident, ItemFn, ItemImpl, ItemMod, Pat, Stmt,
+};
+
+use crate::no_error_swallowing::LoggingMacros;
+
+/// Rule code for no-log-and-return-error.
+pub const CODE: &str = "AL039";
+
+/// Rule name for no-log-and-return-error.
+pub const NAME: &str = "no-log-and-return-error";
+
+/// Forbids logging an error and then returning it unchanged.
+#[derive(Debug, Clone)]
+pub struct NoLogAndReturnError {
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+    /// Custom severity.
+    pub severity: Severity,
+    /// Macros treated as logging: full paths, or bare names for
+    /// unqualified macros like `eprintln`.
+    pub logging_macros: Vec<String>,
+}
+
+impl Default for NoLogAndReturnError {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoLogAndReturnError {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            allow_in_tests: true,
+            severity: Severity::Warning,
+            logging_macros: LoggingConfig::default().level_macros(),
+        }
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Replaces the set of logging macros with the level macros of the
+    /// crates in `logging`.
+    #[must_use]
+    pub fn logging(mut self, logging: &LoggingConfig) -> Self {
+        self.configure_logging(logging);
+        self
+    }
+
+    /// Adds a logging macro (e.g. `"slog::error"` or `"eprintln"`).
+    #[must_use]
+    pub fn add_logging_macro(mut self, name: impl Into<String>) -> Self {
+        self.logging_macros.push(name.into());
+        self
+    }
+}
+
+impl Rule for NoLogAndReturnError {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids logging an error and then returning it unchanged"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "An error that is logged and then propagated as-is gets logged again by the layer \
+         that handles it, so every failure is reported twice. Either handle the error and \
+         log it, or return it (adding context if needed) and leave logging to the handler."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: "allow_in_tests".to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn configure_logging(&mut self, logging: &LoggingConfig) {
+        self.logging_macros = logging.level_macros();
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        ctx.content.contains("Err(") || ctx.content.contains("_err(")
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = LogAndReturnVisitor {
+            ctx,
+            rule: self,
+            macros: LoggingMacros::new(&self.logging_macros, ast),
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct LogAndReturnVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a NoLogAndReturnError,
+    macros: LoggingMacros<'a>,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl LogAndReturnVisitor<'_> {
+    /// Checks `.map_err(..)?` and `.inspect_err(..)?` for a closure that
+    /// logs and passes the error through.
+    fn check_try_call(&mut self, call: &ExprMethodCall) {
+        let (1, Some(Expr::Closure(closure))) = (call.args.len(), call.args.first()) else {
+            return;
+        };
+        let logging = if call.method == "map_err" {
+            let Some(param) = closure.inputs.first().and_then(binding_of) else {
+                return;
+            };
+            let Expr::Block(body) = &*closure.body else {
+                return;
+            };
+            match body.block.stmts.split_last() {
+                Some((Stmt::Expr(tail, None), init)) if is_same_error(tail, param) => {
+                    self.macros.first_in_block(init)
+                }
+                _ => None,
+            }
+        } else if call.method == "inspect_err" && self.macros.is_only_logging_expr(&closure.body) {
+            self.macros.first_in_expr(&closure.body)
+        } else {
+            None
+        };
+
+        if let Some(logging) = logging {
+            self.report(call.method.span(), logging);
+        }
+    }
+
+    /// Checks an `Err(e)` handler block that logs and then returns `Err(e)`.
+    fn check_err_handler(&mut self, span: proc_macro2::Span, binding: &Ident, stmts: &[Stmt]) {
+        let Some((Stmt::Expr(last, semi), init)) = stmts.split_last() else {
+            return;
+        };
+        let returned = match last {
+            Expr::Return(ret) => ret.expr.as_deref(),
+            _ if semi.is_none() => Some(last),
+            _ => None,
+        };
+        if !returned.is_some_and(|expr| is_err_of(expr, binding)) {
+            return;
+        }
+        if let Some(logging) = self.macros.first_in_block(init) {
+            self.report(span, logging);
+        }
+    }
+
+    /// Locates `span` in the file, including its byte range when it fits on
+    /// one line.
+    fn location(&self, span: proc_macro2::Span) -> Location {
+        let (start, end) = (span.start(), span.end());
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        if start.line != end.line {
+            return location;
+        }
+        let offset = self.ctx.offset_for(start.line, start.column + 1);
+        location.with_span(offset, end.column.saturating_sub(start.column))
+    }
+
+    fn report(&mut self, span: proc_macro2::Span, logging: &syn::Macro) {
+        let start = span.start();
+
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
+        if allow_check.is_allowed() {
+            // If reason is required but not provided, create a separate violation
+            if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
+                let location =
+                    Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+                self.violations.push(
+                    Violation::new(
+                        CODE,
+                        NAME,
+                        Severity::Warning,
+                        location,
+                        format!("Allow directive for '{NAME}' is missing required reason"),
+                    )
+                    .with_suggestion(Suggestion::new(
+                        "Add reason=\"...\" to explain why this exception is necessary",
+                    )),
+                );
+            }
+            return;
+        }
+
+        let logged = logging
+            .path
+            .span()
+            .join(logging.bang_token.span)
+            .unwrap_or_else(|| logging.path.span());
+        let mut violation = Violation::new(
+            CODE,
+            NAME,
+            self.rule.severity,
+            self.location(span),
+            "Error is logged and then returned unchanged, so it will be reported twice",
+        )
+        .with_suggestion(Suggestion::new(
+            "Return the error without logging it (add context if needed), or handle it here",
+        ));
+        violation
+            .labels
+            .push(Label::new(self.location(logged), "error logged here"));
+        self.violations.push(violation);
+    }
+}
+
+impl<'ast> Visit<'ast> for LogAndReturnVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_expr_try(&mut self, node: &'ast ExprTry) {
+        if !self.context.is_skipped(self.rule.allow_in_tests) {
+            if let Expr::MethodCall(call) = &*node.expr {
+                self.check_try_call(call);
+            }
+        }
+        syn::visit::visit_expr_try(self, node);
+    }
+
+    fn visit_expr_if(&mut self, node: &'ast ExprIf) {
+        if !self.context.is_skipped(self.rule.allow_in_tests) {
+            if let Expr::Let(expr_let) = &*node.cond {
+                if let Some(binding) = err_binding(&expr_let.pat) {
+                    self.check_err_handler(node.if_token.span, binding, &node.then_branch.stmts);
+                }
+            }
+        }
+        syn::visit::visit_expr_if(self, node);
+    }
+
+    fn visit_expr_match(&mut self, node: &'ast ExprMatch) {
+        if !self.context.is_skipped(self.rule.allow_in_tests) {
+            for arm in &node.arms {
+                if let (Some(binding), Expr::Block(body)) = (err_binding(&arm.pat), &*arm.body) {
+                    self.check_err_handler(arm.pat.span(), binding, &body.block.stmts);
+                }
+            }
+        }
+        syn::visit::visit_expr_match(self, node);
+    }
+}
+
+/// Returns the identifier bound by a plain (optionally typed) pattern.
+fn binding_of(pat: &Pat) -> Option<&Ident> {
+    match pat {
+        Pat::Ident(ident) if ident.subpat.is_none() => Some(&ident.ident),
+        Pat::Type(typed) => binding_of(&typed.pat),
+        _ => None,
+    }
+}
+
+/// Returns `e` for an `Err(e)` pattern.
+fn err_binding(pat: &Pat) -> Option<&Ident> {
+    let Pat::TupleStruct(ts) = pat else {
+        return None;
+    };
+    if ts.path.segments.last()?.ident != "Err" || ts.elems.len() != 1 {
+        return None;
+    }
+    binding_of(ts.elems.first()?)
+}
+
+/// Checks if `expr` is `Err(e)` (or `Err(e.into())`) for `binding`.
+fn is_err_of(expr: &Expr, binding: &Ident) -> bool {
+    let Expr::Call(call) = expr else {
+        return false;
+    };
+    let Expr::Path(func) = &*call.func else {
+        return false;
+    };
+    func.path.segments.last().is_some_and(|s| s.ident == "Err")
+        && call.args.len() == 1
+        && call
+            .args
+            .first()
+            .is_some_and(|arg| is_same_error(arg, binding))
+}
+
+/// Checks if `expr` is `binding` itself or `binding.into()`.
+fn is_same_error(expr: &Expr, binding: &Ident) -> bool {
+    match expr {
+        Expr::Path(path) => path.qself.is_none() && path.path.is_ident(binding),
+        Expr::MethodCall(call) => {
+            call.method == "into" && call.args.is_empty() && is_same_error(&call.receiver, binding)
+        }
+        Expr::Paren(paren) => is_same_error(&paren.expr, binding),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoLogAndReturnError::new(), code)
+    }
+
+    fn check_with(rule: &NoLogAndReturnError, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    #[test]
+    fn test_detects_map_err_and_inspect_err() {
+        let violations = check_code(
+            r#"
+fn load() -> Result<Config, Error> {
+    let raw = read().map_err(|e| {
+        tracing::error!("read failed: {e}");
+        e
+    })?;
+    let parsed = parse(raw).inspect_err(|e| log::warn!("parse failed: {e}"))?;
+    let checked = validate(parsed).map_err(|e: Error| {
+        tracing::error!("invalid: {e}");
+        e.into()
+    })?;
+    Ok(checked)
+}
+"#,
+        );
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(
+            (violations[0].location.line, violations[0].location.column),
+            (3, 22)
+        );
+        assert_eq!(violations[0].labels.len(), 1);
+        assert_eq!(violations[0].labels[0].message, "error logged here");
+        assert_eq!(violations[0].labels[0].location.line, 4);
+    }
+
+    #[test]
+    fn test_detects_err_arms_that_return_the_error() {
+        let violations = check_code(
+            r#"
+use tracing::error;
+
+fn load() -> Result<Config, Error> {
+    let raw = match read() {
+        Ok(raw) => raw,
+        Err(e) => {
+            error!("read failed: {e}");
+            return Err(e);
+        }
+    };
+    if let Err(err) = validate(&raw) {
+        tracing::warn!(%err, "invalid config");
+        return Err(err.into());
+    }
+    match parse(raw) {
+        Ok(config) => Ok(config),
+        Err(e) => {
+            log::error!("parse failed: {e}");
+            Err(e)
+        }
+    }
+}
+"#,
+        );
+        let lines: Vec<_> = violations.iter().map(|v| v.location.line).collect();
+        assert_eq!(lines, [7, 12, 18], "{violations:?}");
+    }
+
+    #[test]
+    fn test_ignores_context_adding_and_unlogged_propagation() {
+        let violations = check_code(
+            r#"
+fn load(path: &Path) -> Result<Config, ConfigError> {
+    let raw = read(path).map_err(|e| {
+        tracing::debug!("read failed: {e}");
+        ConfigError::Read { path: path.to_owned(), source: e }
+    })?;
+    let parsed = parse(&raw).map_err(|e| e.context("parsing config"))?;
+    let checked = match validate(parsed) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("invalid: {e}");
+            return Err(ConfigError::Invalid(e));
+        }
+    };
+    match finish(checked) {
+        Ok(config) => Ok(config),
+        Err(e) => return Err(e),
+    }
+}
+"#,
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_ignores_handled_errors_and_unknown_macros() {
+        let violations = check_code(
+            r#"
+fn load() -> Result<Config, Error> {
+    let config = match read() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!("using defaults: {e}");
+            Config::default()
+        }
+    };
+    let other = backup().map_err(|e| {
+        error!("not imported, so not a logging macro");
+        e
+    })?;
+    Ok(config.merge(other))
+}
+"#,
+        );
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn test_allowed_in_tests_by_default() {
+        let code = r#"
+#[cfg(test)]
+mod tests {
+    fn helper() -> Result<(), Error> {
+        run().map_err(|e| {
+            tracing::error!("{e}");
+            e
+        })?;
+        Ok(())
+    }
+}
+"#;
+        assert!(check_code(code).is_empty());
+
+        let mut rule = NoLogAndReturnError::new();
+        let mut config = RuleConfig::default();
+        config
+            .options
+            .insert("allow_in_tests".to_string(), toml::Value::Boolean(false));
+        rule.configure(&config).expect("valid option");
+        assert_eq!(check_with(&rule, code).len(), 1);
+    }
+
+    #[test]
+    fn test_custom_logging_macro() {
+        let code = r#"
+fn load() -> Result<(), Error> {
+    run().map_err(|e| {
+        eprintln!("failed: {e}");
+        e
+    })?;
+    Ok(())
+}
+"#;
+        assert!(check_code(code).is_empty());
+        let rule = NoLogAndReturnError::new().add_logging_macro("eprintln");
+        assert_eq!(check_with(&rule, code).len(), 1);
+    }
+}
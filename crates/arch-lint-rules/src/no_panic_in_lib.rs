@@ -13,6 +13,11 @@
 //! - `unimplemented!(...)`
 //! - `unreachable!(...)`
 //!
+//! Each macro can be permitted on its own: `forbid_panic`, `forbid_todo` and
+//! `forbid_unimplemented` (default `true`), and `allow_unreachable` (default
+//! `false`) for exhaustive matches the compiler cannot prove. While
+//! `unreachable!` is forbidden, allow directives for it must give a `reason`.
+//!
 //! # Good Patterns
 //!
 //! ```ignore
@@ -23,11 +28,12 @@
 //! }
 //! ```
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::{check_arch_lint_allow, has_cfg_test, has_test_attr, path_to_string};
-use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use arch_lint_core::utils::{path_to_string, ContextTracker};
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
 use syn::visit::Visit;
-use syn::{ExprMacro, ItemFn, ItemImpl, ItemMod};
+use syn::{ItemFn, ItemImpl, ItemMod};
 
 /// Rule code for no-panic-in-lib.
 pub const CODE: &str = "AL011";
@@ -37,13 +43,63 @@ pub const NAME: &str = "no-panic-in-lib";
 
 /// Forbids panic macros in library code.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct NoPanicInLib {
     /// Allow in test code.
     pub allow_in_tests: bool,
+    /// Report `panic!`.
+    pub forbid_panic: bool,
+    /// Report `todo!`.
+    pub forbid_todo: bool,
+    /// Report `unimplemented!`.
+    pub forbid_unimplemented: bool,
+    /// Permit `unreachable!`; when `false`, allow directives for it need a
+    /// reason.
+    pub allow_unreachable: bool,
     /// Custom severity.
     pub severity: Severity,
 }
 
+/// The panic macros this rule recognizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PanicMacro {
+    Panic,
+    Todo,
+    Unimplemented,
+    Unreachable,
+}
+
+impl PanicMacro {
+    /// Recognizes simple and qualified paths (`panic`, `std::panic`).
+    fn from_path(path: &str) -> Option<Self> {
+        let name = path.rsplit("::").next().unwrap_or(path);
+        match name {
+            "panic" => Some(Self::Panic),
+            "todo" => Some(Self::Todo),
+            "unimplemented" => Some(Self::Unimplemented),
+            "unreachable" => Some(Self::Unreachable),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Panic => "panic!",
+            Self::Todo => "todo!",
+            Self::Unimplemented => "unimplemented!",
+            Self::Unreachable => "unreachable!",
+        }
+    }
+
+    fn suggestion(self) -> &'static str {
+        match self {
+            Self::Panic => "Return Result instead of panicking",
+            Self::Todo | Self::Unimplemented => "Implement the functionality or return Result",
+            Self::Unreachable => "Use Result or proper error handling instead",
+        }
+    }
+}
+
 impl Default for NoPanicInLib {
     fn default() -> Self {
         Self::new()
@@ -56,6 +112,10 @@ impl NoPanicInLib {
     pub fn new() -> Self {
         Self {
             allow_in_tests: true,
+            forbid_panic: true,
+            forbid_todo: true,
+            forbid_unimplemented: true,
+            allow_unreachable: false,
             severity: Severity::Error,
         }
     }
@@ -67,12 +127,49 @@ impl NoPanicInLib {
         self
     }
 
+    /// Sets whether `panic!` is reported.
+    #[must_use]
+    pub fn forbid_panic(mut self, forbid: bool) -> Self {
+        self.forbid_panic = forbid;
+        self
+    }
+
+    /// Sets whether `todo!` is reported.
+    #[must_use]
+    pub fn forbid_todo(mut self, forbid: bool) -> Self {
+        self.forbid_todo = forbid;
+        self
+    }
+
+    /// Sets whether `unimplemented!` is reported.
+    #[must_use]
+    pub fn forbid_unimplemented(mut self, forbid: bool) -> Self {
+        self.forbid_unimplemented = forbid;
+        self
+    }
+
+    /// Sets whether `unreachable!` is permitted.
+    #[must_use]
+    pub fn allow_unreachable(mut self, allow: bool) -> Self {
+        self.allow_unreachable = allow;
+        self
+    }
+
     /// Sets the severity level.
     #[must_use]
     pub fn severity(mut self, severity: Severity) -> Self {
         self.severity = severity;
         self
     }
+
+    fn forbids(&self, panic_macro: PanicMacro) -> bool {
+        match panic_macro {
+            PanicMacro::Panic => self.forbid_panic,
+            PanicMacro::Todo => self.forbid_todo,
+            PanicMacro::Unimplemented => self.forbid_unimplemented,
+            PanicMacro::Unreachable => !self.allow_unreachable,
+        }
+    }
 }
 
 impl Rule for NoPanicInLib {
@@ -88,10 +185,45 @@ impl Rule for NoPanicInLib {
         "Forbids panic macros in library code"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Library code should return `Result` instead of panicking so that callers can \
+         decide how to handle failures. Panics in libraries crash the host application."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
 
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &[
+            "allow_in_tests",
+            "forbid_panic",
+            "forbid_todo",
+            "forbid_unimplemented",
+            "allow_unreachable",
+        ]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        for (key, field) in [
+            ("allow_in_tests", &mut self.allow_in_tests),
+            ("forbid_panic", &mut self.forbid_panic),
+            ("forbid_todo", &mut self.forbid_todo),
+            ("forbid_unimplemented", &mut self.forbid_unimplemented),
+            ("allow_unreachable", &mut self.allow_unreachable),
+        ] {
+            let Some(value) = config.options.get(key) else {
+                continue;
+            };
+            *field = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: key.to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        Ok(())
+    }
+
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
         // Skip test files if configured
         if self.allow_in_tests && ctx.is_test {
@@ -102,8 +234,7 @@ impl Rule for NoPanicInLib {
             ctx,
             rule: self,
             violations: Vec::new(),
-            in_test_context: false,
-            in_allowed_context: false,
+            context: ContextTracker::new(NAME, CODE),
         };
 
         visitor.visit_file(ast);
@@ -115,148 +246,92 @@ struct PanicVisitor<'a> {
     ctx: &'a FileContext<'a>,
     rule: &'a NoPanicInLib,
     violations: Vec<Violation>,
-    in_test_context: bool,
-    in_allowed_context: bool,
+    context: ContextTracker,
 }
 
 impl PanicVisitor<'_> {
     fn check_panic_macro(&mut self, path: &syn::Path) {
-        // Skip if in test context and tests are allowed
-        if self.rule.allow_in_tests && self.in_test_context {
+        if self.context.is_skipped(self.rule.allow_in_tests) {
             return;
         }
 
-        // Skip if in allowed context
-        if self.in_allowed_context {
+        let Some(panic_macro) = PanicMacro::from_path(&path_to_string(path)) else {
+            return;
+        };
+        if !self.rule.forbids(panic_macro) {
             return;
         }
 
-        let path_str = path_to_string(path);
-
-        // Check if this is a panic macro (handle both simple and qualified paths)
-        let panic_macro = if path_str == "panic" || path_str.ends_with("::panic") {
-            Some(("panic!", "Return Result instead of panicking"))
-        } else if path_str == "todo" || path_str.ends_with("::todo") {
-            Some(("todo!", "Implement the functionality or return Result"))
-        } else if path_str == "unimplemented" || path_str.ends_with("::unimplemented") {
-            Some((
-                "unimplemented!",
-                "Implement the functionality or return Result",
-            ))
-        } else if path_str == "unreachable" || path_str.ends_with("::unreachable") {
-            Some((
-                "unreachable!",
-                "Use Result or proper error handling instead",
-            ))
-        } else {
-            None
+        let Some(first_segment) = path.segments.first() else {
+            return;
         };
-
-        if let Some((macro_name, suggestion_text)) = panic_macro {
-            let Some(first_segment) = path.segments.first() else {
-                return;
-            };
-            let span = first_segment.ident.span();
-            let start = span.start();
-
-            // Check for inline allow comment
-            let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
-            if allow_check.is_allowed() {
-                // If reason is required but not provided, create a separate violation
-                if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
-                    let location =
-                        Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-                    self.violations.push(
-                        Violation::new(
-                            CODE,
-                            NAME,
-                            Severity::Warning,
-                            location,
-                            format!("Allow directive for '{NAME}' is missing required reason"),
-                        )
-                        .with_suggestion(Suggestion::new(
-                            "Add reason=\"...\" to explain why this exception is necessary",
-                        )),
-                    );
-                }
-                return;
+        let span = first_segment.ident.span();
+        let start = span.start();
+
+        // Check for inline allow comment
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
+        if allow_check.is_allowed() {
+            // If reason is required but not provided, create a separate violation
+            let reason_required =
+                self.rule.requires_allow_reason() || panic_macro == PanicMacro::Unreachable;
+            if reason_required && allow_check.reason().is_none() {
+                let location =
+                    Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+                self.violations.push(
+                    Violation::new(
+                        CODE,
+                        NAME,
+                        Severity::Warning,
+                        location,
+                        format!("Allow directive for '{NAME}' is missing required reason"),
+                    )
+                    .with_suggestion(Suggestion::new(
+                        "Add reason=\"...\" to explain why this exception is necessary",
+                    )),
+                );
             }
-
-            let location =
-                Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-
-            self.violations.push(
-                Violation::new(
-                    CODE,
-                    NAME,
-                    self.rule.severity,
-                    location,
-                    format!("`{macro_name}` is forbidden in library code"),
-                )
-                .with_suggestion(Suggestion::new(suggestion_text)),
-            );
+            return;
         }
+
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!("`{}` is forbidden in library code", panic_macro.name()),
+            )
+            .with_suggestion(Suggestion::new(panic_macro.suggestion())),
+        );
     }
 }
 
 impl<'ast> Visit<'ast> for PanicVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
-        let was_in_test = self.in_test_context;
-        let was_allowed = self.in_allowed_context;
-
-        if has_cfg_test(&node.attrs) {
-            self.in_test_context = true;
-        }
-
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
-            self.in_allowed_context = true;
-        }
-
+        let saved = self.context.enter_mod(&node.attrs);
         syn::visit::visit_item_mod(self, node);
-
-        self.in_test_context = was_in_test;
-        self.in_allowed_context = was_allowed;
+        self.context.restore(saved);
     }
 
     fn visit_item_fn(&mut self, node: &'ast ItemFn) {
-        let was_in_test = self.in_test_context;
-        let was_allowed = self.in_allowed_context;
-
-        if has_test_attr(&node.attrs) {
-            self.in_test_context = true;
-        }
-
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
-            self.in_allowed_context = true;
-        }
-
+        let saved = self.context.enter_fn(&node.attrs);
         syn::visit::visit_item_fn(self, node);
-
-        self.in_test_context = was_in_test;
-        self.in_allowed_context = was_allowed;
+        self.context.restore(saved);
     }
 
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
-        let was_allowed = self.in_allowed_context;
-
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
-            self.in_allowed_context = true;
-        }
-
+        let saved = self.context.enter_impl(&node.attrs);
         syn::visit::visit_item_impl(self, node);
-
-        self.in_allowed_context = was_allowed;
+        self.context.restore(saved);
     }
 
+    // Also reached from expression-position macros (`_ => unreachable!()`).
     fn visit_macro(&mut self, node: &'ast syn::Macro) {
         self.check_panic_macro(&node.path);
         syn::visit::visit_macro(self, node);
     }
-
-    fn visit_expr_macro(&mut self, node: &'ast ExprMacro) {
-        self.check_panic_macro(&node.mac.path);
-        syn::visit::visit_expr_macro(self, node);
-    }
 }
 
 #[cfg(test)]
@@ -265,15 +340,33 @@ mod tests {
     use std::path::Path;
 
     fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoPanicInLib::new(), code)
+    }
+
+    fn check_with(rule: &NoPanicInLib, code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
-        NoPanicInLib::new().check(&ctx, &ast)
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    const ALL_MACROS: &str = r#"
+pub fn foo() {
+    panic!("error");
+    todo!("implement");
+    unimplemented!();
+    unreachable!();
+}
+"#;
+
+    fn reported(violations: &[Violation]) -> Vec<&str> {
+        violations
+            .iter()
+            .map(|v| {
+                let start = v.message.find('`').map_or(0, |i| i + 1);
+                let end = v.message[start..].find('`').map_or(start, |i| start + i);
+                &v.message[start..end]
+            })
+            .collect()
     }
 
     #[test]
@@ -397,4 +490,101 @@ pub fn foo() {
         );
         assert_eq!(violations.len(), 4);
     }
+
+    #[test]
+    fn test_forbid_toggles_each_macro() {
+        let violations = check_with(&NoPanicInLib::new().forbid_panic(false), ALL_MACROS);
+        assert_eq!(
+            reported(&violations),
+            ["todo!", "unimplemented!", "unreachable!"]
+        );
+
+        let violations = check_with(&NoPanicInLib::new().forbid_todo(false), ALL_MACROS);
+        assert_eq!(
+            reported(&violations),
+            ["panic!", "unimplemented!", "unreachable!"]
+        );
+
+        let violations = check_with(&NoPanicInLib::new().forbid_unimplemented(false), ALL_MACROS);
+        assert_eq!(reported(&violations), ["panic!", "todo!", "unreachable!"]);
+    }
+
+    #[test]
+    fn test_allow_unreachable_keeps_todo_forbidden() {
+        let violations = check_with(&NoPanicInLib::new().allow_unreachable(true), ALL_MACROS);
+        assert_eq!(reported(&violations), ["panic!", "todo!", "unimplemented!"]);
+    }
+
+    #[test]
+    fn test_unreachable_allow_requires_reason() {
+        let rule = NoPanicInLib::new().severity(Severity::Warning);
+        assert!(!rule.requires_allow_reason());
+
+        let violations = check_with(
+            &rule,
+            r"
+pub fn foo(x: u8) -> u8 {
+    match x % 2 {
+        0 | 1 => x,
+        // arch-lint: allow(no-panic-in-lib)
+        _ => unreachable!(),
+    }
+}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("missing required reason"));
+
+        let violations = check_with(
+            &rule,
+            r#"
+pub fn foo(x: u8) -> u8 {
+    match x % 2 {
+        0 | 1 => x,
+        // arch-lint: allow(no-panic-in-lib) reason="x % 2 is 0 or 1"
+        _ => unreachable!(),
+    }
+}
+"#,
+        );
+        assert!(violations.is_empty());
+
+        // Other macros keep the severity-based requirement.
+        let violations = check_with(
+            &rule,
+            r#"
+pub fn foo() {
+    // arch-lint: allow(no-panic-in-lib)
+    todo!("later");
+}
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+
+    fn configured(toml: &str) -> Result<NoPanicInLib, ConfigError> {
+        let config = arch_lint_core::Config::parse(toml).expect("parse config");
+        let mut rule = NoPanicInLib::new();
+        rule.configure(&config.rules[NAME])?;
+        Ok(rule)
+    }
+
+    #[test]
+    fn test_configure_macro_toggles() {
+        let rule =
+            configured("[rules.no-panic-in-lib]\nallow_unreachable = true\nforbid_todo = false\n")
+                .expect("valid options");
+        assert!(rule.allow_unreachable);
+        assert!(!rule.forbid_todo);
+        assert!(rule.forbid_panic);
+        assert!(rule.forbid_unimplemented);
+        assert_eq!(
+            reported(&check_with(&rule, ALL_MACROS)),
+            ["panic!", "unimplemented!"]
+        );
+
+        let err = configured("[rules.no-panic-in-lib]\nforbid_panic = \"no\"\n")
+            .expect_err("non-boolean");
+        assert!(err.to_string().contains("forbid_panic"), "{err}");
+    }
 }
@@ -42,17 +42,27 @@
 //! let v = result.map_err(|e| MyError::from(e))?;
 //! ```
 //!
+//! # Receiver Types
+//!
+//! The rule works on syntax alone, so it cannot generally tell `Option` from
+//! `Result`. As a heuristic, calls on a local binding known to hold an
+//! `Option` are skipped: either declared as `let x: Option<_> = ...` or
+//! initialized with `Some(...)` / `None` in the same function. Any other
+//! receiver is conservatively treated as a `Result`.
+//!
 //! # Configuration
 //!
 //! - `allow_in_tests`: Allow in test code (default: true)
 //! - `allow_ok`: Allow `.ok()` conversion (default: false)
 //! - `allow_let_underscore`: Allow `let _ = ...` (default: false)
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::{check_arch_lint_allow, has_allow_attr, has_cfg_test, has_test_attr};
+use arch_lint_core::utils::{
+    check_arch_lint_allow_for_rule, has_allow_attr, has_cfg_test, has_test_attr,
+};
 use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use std::collections::HashSet;
 use syn::visit::Visit;
-use syn::{ExprMethodCall, ItemFn, ItemImpl, ItemMod, Local, Pat};
+use syn::{Expr, ExprMethodCall, ImplItemFn, ItemFn, ItemImpl, ItemMod, Local, Pat, Type};
 
 /// Rule code for no-silent-result-drop.
 pub const CODE: &str = "AL013";
@@ -136,6 +146,12 @@ impl Rule for NoSilentResultDrop {
         "Forbids silently discarding Result error information"
     }
 
+    fn explanation(&self) -> &'static str {
+        "`.unwrap_or()`, `.unwrap_or_default()`, `.unwrap_or_else()`, and `.ok()` on a \
+         `Result` silently discard the error. Unlike `.unwrap()` they compile without \
+         warning and produce subtle data-loss bugs."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
@@ -151,6 +167,7 @@ impl Rule for NoSilentResultDrop {
             violations: Vec::new(),
             in_test_context: false,
             in_allowed_context: false,
+            option_locals: HashSet::new(),
         };
 
         visitor.visit_file(ast);
@@ -164,6 +181,8 @@ struct SilentResultDropVisitor<'a> {
     violations: Vec<Violation>,
     in_test_context: bool,
     in_allowed_context: bool,
+    /// Locals in the current function known to hold an `Option`.
+    option_locals: HashSet<String>,
 }
 
 impl SilentResultDropVisitor<'_> {
@@ -171,11 +190,47 @@ impl SilentResultDropVisitor<'_> {
         self.in_allowed_context || (self.rule.allow_in_tests && self.in_test_context)
     }
 
+    /// Returns `true` if the receiver is a local known to be an `Option`.
+    fn is_option_receiver(&self, receiver: &Expr) -> bool {
+        match receiver {
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .is_some_and(|ident| self.option_locals.contains(&ident.to_string())),
+            Expr::Paren(paren) => self.is_option_receiver(&paren.expr),
+            _ => false,
+        }
+    }
+
+    /// Records (or forgets, on shadowing) whether a `let` binds an `Option`.
+    fn track_local(&mut self, node: &Local) {
+        let (ident, annotated) = match &node.pat {
+            Pat::Ident(pat) => (&pat.ident, None),
+            Pat::Type(pat) => match &*pat.pat {
+                Pat::Ident(inner) => (&inner.ident, Some(is_option_type(&pat.ty))),
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let is_option = annotated.unwrap_or_else(|| {
+            node.init
+                .as_ref()
+                .is_some_and(|init| is_option_constructor(&init.expr))
+        });
+
+        if is_option {
+            self.option_locals.insert(ident.to_string());
+        } else {
+            self.option_locals.remove(&ident.to_string());
+        }
+    }
+
     fn report_method_violation(&mut self, method_name: &str, span: proc_macro2::Span) {
         let start = span.start();
 
         // Check for inline allow comment
-        let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
         if allow_check.is_allowed() {
             if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
                 let location =
@@ -238,7 +293,7 @@ impl SilentResultDropVisitor<'_> {
     fn report_let_underscore_violation(&mut self, span: proc_macro2::Span) {
         let start = span.start();
 
-        let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
         if allow_check.is_allowed() {
             if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
                 let location =
@@ -276,6 +331,30 @@ impl SilentResultDropVisitor<'_> {
     }
 }
 
+/// Returns `true` for `Option<_>` (optionally path-qualified).
+fn is_option_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        Type::Paren(paren) => is_option_type(&paren.elem),
+        _ => false,
+    }
+}
+
+/// Returns `true` for `Some(...)` and `None` expressions.
+fn is_option_constructor(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call(call) => {
+            matches!(&*call.func, Expr::Path(path) if path.path.is_ident("Some"))
+        }
+        Expr::Path(path) => path.path.is_ident("None"),
+        _ => false,
+    }
+}
+
 impl<'ast> Visit<'ast> for SilentResultDropVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_in_test = self.in_test_context;
@@ -284,7 +363,7 @@ impl<'ast> Visit<'ast> for SilentResultDropVisitor<'_> {
         if has_cfg_test(&node.attrs) {
             self.in_test_context = true;
         }
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -304,20 +383,28 @@ impl<'ast> Visit<'ast> for SilentResultDropVisitor<'_> {
         if has_allow_attr(&node.attrs, &["clippy::let_underscore_must_use"]) {
             self.in_allowed_context = true;
         }
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
+        let outer_locals = std::mem::take(&mut self.option_locals);
 
         syn::visit::visit_item_fn(self, node);
 
+        self.option_locals = outer_locals;
         self.in_test_context = was_in_test;
         self.in_allowed_context = was_allowed;
     }
 
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let outer_locals = std::mem::take(&mut self.option_locals);
+        syn::visit::visit_impl_item_fn(self, node);
+        self.option_locals = outer_locals;
+    }
+
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -341,6 +428,10 @@ impl<'ast> Visit<'ast> for SilentResultDropVisitor<'_> {
                 syn::visit::visit_expr_method_call(self, node);
                 return;
             }
+            if self.is_option_receiver(&node.receiver) {
+                syn::visit::visit_expr_method_call(self, node);
+                return;
+            }
 
             self.report_method_violation(&method_name, node.method.span());
         }
@@ -351,6 +442,7 @@ impl<'ast> Visit<'ast> for SilentResultDropVisitor<'_> {
     fn visit_local(&mut self, node: &'ast Local) {
         if self.is_skipped() || self.rule.allow_let_underscore {
             syn::visit::visit_local(self, node);
+            self.track_local(node);
             return;
         }
 
@@ -362,6 +454,7 @@ impl<'ast> Visit<'ast> for SilentResultDropVisitor<'_> {
         }
 
         syn::visit::visit_local(self, node);
+        self.track_local(node);
     }
 }
 
@@ -372,37 +465,19 @@ mod tests {
 
     fn check_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         NoSilentResultDrop::new().check(&ctx, &ast)
     }
 
     fn check_code_with(code: &str, rule: NoSilentResultDrop) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         rule.check(&ctx, &ast)
     }
 
     fn check_test_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("tests/test.rs"),
-            content: code,
-            is_test: true,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("tests/test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("tests/test.rs"), code, Path::new(""));
         NoSilentResultDrop::new().check(&ctx, &ast)
     }
 
@@ -643,11 +718,56 @@ fn foo() -> String {
 }
 "#,
         );
-        // Note: syn-based analysis cannot distinguish Option from Result at AST level.
-        // This is a known limitation — the rule triggers on the method name regardless
-        // of receiver type. Users should use `allow` directives for Option chains.
-        // This is by design: false positives are preferable to silent data loss.
-        assert_eq!(violations.len(), 1);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn allows_unwrap_or_on_option_constructor_local() {
+        let violations = check_code(
+            r"
+struct S;
+impl S {
+    fn foo(&self) -> u32 {
+        let a = Some(1);
+        let b = None;
+        a.unwrap_or(0) + b.unwrap_or_default()
+    }
+}
+",
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn detects_unwrap_or_on_result_local() {
+        let violations = check_code(
+            r"
+fn foo() -> u32 {
+    let a: Result<u32, Error> = parse();
+    let b = parse();
+    a.unwrap_or(0) + b.unwrap_or_default()
+}
+",
+        );
+        assert_eq!(violations.len(), 2);
+    }
+
+    #[test]
+    fn option_hint_does_not_leak_or_survive_shadowing() {
+        let violations = check_code(
+            r"
+fn first() -> u32 {
+    let v = Some(1);
+    let v: Result<u32, Error> = parse();
+    v.unwrap_or(0)
+}
+
+fn second() -> u32 {
+    v.unwrap_or(0)
+}
+",
+        );
+        assert_eq!(violations.len(), 2);
     }
 
     // ── Multiple violations ──
@@ -11,6 +11,8 @@
 //! - `std::io::*` blocking operations
 //! - `.read()`, `.write()` on std types
 //! - `std::thread::sleep`
+//! - `std::net` connects/binds (`TcpStream::connect`, `TcpListener::bind`, ...)
+//! - `.output()` / `.status()` on a `Command::new(...)` chain (unless awaited)
 //!
 //! # Allowed Patterns
 //!
@@ -20,17 +22,24 @@
 //! # Configuration
 //!
 //! - `allow_patterns`: Additional patterns to allow
+//! - `only_in_async`: Only report inside `async fn`s, async blocks, and async
+//!   closures (default: false; the `recommended` preset enables it). Closures
+//!   passed to `spawn_blocking`, `block_in_place` or `std::thread::spawn`
+//!   run off the async runtime and count as sync context.
 //!
 //! # Suppression
 //!
 //! - `#[allow(sync_io)]` attribute
 //! - `// arch-lint: allow(no-sync-io)` comment
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::{check_arch_lint_allow, has_allow_attr, path_to_string};
+use arch_lint_core::utils::{check_arch_lint_allow_for_rule, has_allow_attr, path_to_string};
 use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use syn::punctuated::Punctuated;
 use syn::visit::Visit;
-use syn::{Expr, ExprCall, ExprMethodCall, ExprPath, ItemFn, ItemImpl, ItemMod};
+use syn::{
+    Expr, ExprAsync, ExprAwait, ExprCall, ExprClosure, ExprMethodCall, ExprPath, ImplItemFn,
+    ItemFn, ItemImpl, ItemMod, Token, TraitItemFn,
+};
 
 /// Rule code for no-sync-io.
 pub const CODE: &str = "AL002";
@@ -38,8 +47,8 @@ pub const CODE: &str = "AL002";
 /// Rule name for no-sync-io.
 pub const NAME: &str = "no-sync-io";
 
-/// Forbidden `std::fs` functions.
-const FORBIDDEN_FS: &[&str] = &[
+/// Forbidden blocking functions.
+const FORBIDDEN_CALLS: &[&str] = &[
     "std::fs::read",
     "std::fs::read_to_string",
     "std::fs::write",
@@ -57,8 +66,16 @@ const FORBIDDEN_FS: &[&str] = &[
     "std::fs::read_dir",
     "std::fs::File::open",
     "std::fs::File::create",
+    "std::thread::sleep",
+    "std::net::TcpStream::connect",
+    "std::net::TcpStream::connect_timeout",
+    "std::net::TcpListener::bind",
+    "std::net::UdpSocket::bind",
 ];
 
+/// Forbidden methods on a `std::process::Command` builder chain.
+const FORBIDDEN_COMMAND_METHODS: &[&str] = &["output", "status"];
+
 /// Forbidden method names on Path-like types.
 const FORBIDDEN_PATH_METHODS: &[&str] = &[
     "exists",
@@ -69,11 +86,16 @@ const FORBIDDEN_PATH_METHODS: &[&str] = &[
     "canonicalize",
 ];
 
+/// Functions and methods whose closure argument runs where blocking is fine.
+const BLOCKING_OFFLOAD_FNS: &[&str] = &["spawn_blocking", "block_in_place"];
+
 /// Forbids synchronous I/O operations.
 #[derive(Debug, Clone)]
 pub struct NoSyncIo {
     /// Additional patterns to allow.
     pub allow_patterns: Vec<String>,
+    /// Only report blocking I/O inside async contexts.
+    pub only_in_async: bool,
     /// Custom severity.
     pub severity: Severity,
 }
@@ -90,6 +112,7 @@ impl NoSyncIo {
     pub fn new() -> Self {
         Self {
             allow_patterns: vec!["tokio::".to_string(), "async_std::".to_string()],
+            only_in_async: false,
             severity: Severity::Error,
         }
     }
@@ -102,6 +125,15 @@ impl NoSyncIo {
         self
     }
 
+    /// Sets whether to report only inside async contexts.
+    ///
+    /// When `false` (strict), blocking I/O is reported everywhere.
+    #[must_use]
+    pub fn only_in_async(mut self, only_in_async: bool) -> Self {
+        self.only_in_async = only_in_async;
+        self
+    }
+
     /// Sets the severity level.
     #[must_use]
     pub fn severity(mut self, severity: Severity) -> Self {
@@ -127,16 +159,28 @@ impl Rule for NoSyncIo {
         "Forbids synchronous I/O in async contexts"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Blocking I/O inside async code stalls the runtime's worker thread and every task \
+         scheduled on it. Use the runtime's async I/O APIs or move the work to a blocking \
+         thread pool."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
 
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        // With `only_in_async`, violations need an async fn, block, or closure.
+        !self.only_in_async || ctx.content.contains("async")
+    }
+
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
         let mut visitor = SyncIoVisitor {
             ctx,
             rule: self,
             violations: Vec::new(),
             in_allowed_context: false,
+            in_async: false,
         };
 
         visitor.visit_file(ast);
@@ -149,13 +193,100 @@ struct SyncIoVisitor<'a> {
     rule: &'a NoSyncIo,
     violations: Vec<Violation>,
     in_allowed_context: bool,
+    in_async: bool,
+}
+
+impl SyncIoVisitor<'_> {
+    fn is_skipped(&self) -> bool {
+        self.in_allowed_context || (self.rule.only_in_async && !self.in_async)
+    }
+
+    fn with_async<F: FnOnce(&mut Self)>(&mut self, is_async: bool, visit: F) {
+        let was_async = self.in_async;
+        self.in_async = is_async;
+        visit(self);
+        self.in_async = was_async;
+    }
+
+    /// Visits call arguments, treating non-async closures as sync context
+    /// because they run on a blocking thread.
+    fn visit_offloaded_args(&mut self, args: &Punctuated<Expr, Token![,]>) {
+        for arg in args {
+            match arg {
+                Expr::Closure(closure) if closure.asyncness.is_none() => {
+                    self.with_async(false, |v| syn::visit::visit_expr_closure(v, closure));
+                }
+                _ => self.visit_expr(arg),
+            }
+        }
+    }
+
+    /// Visits `handle.spawn_blocking(|| ...)`-style calls, returning `false`
+    /// for any other method.
+    fn visit_offloaded_method(&mut self, node: &ExprMethodCall) -> bool {
+        if !BLOCKING_OFFLOAD_FNS.contains(&node.method.to_string().as_str()) {
+            return false;
+        }
+        self.visit_expr(&node.receiver);
+        self.visit_offloaded_args(&node.args);
+        true
+    }
+
+    /// Reports a violation unless an inline allow directive covers the line.
+    fn report(&mut self, span: proc_macro2::Span, message: String, suggestion: String) {
+        let start = span.start();
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+
+        // Check for inline allow comment
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
+        if allow_check.is_allowed() {
+            // If reason is required but not provided, create a separate violation
+            if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
+                self.violations.push(
+                    Violation::new(
+                        CODE,
+                        NAME,
+                        Severity::Warning,
+                        location,
+                        format!("Allow directive for '{NAME}' is missing required reason"),
+                    )
+                    .with_suggestion(Suggestion::new(
+                        "Add reason=\"...\" to explain why this exception is necessary",
+                    )),
+                );
+            }
+            return;
+        }
+
+        self.violations.push(
+            Violation::new(CODE, NAME, self.rule.severity, location, message)
+                .with_suggestion(Suggestion::new(suggestion)),
+        );
+    }
+
+    /// Checks if a method receiver is a builder chain rooted at a
+    /// non-allowed `Command::new(...)` call.
+    fn is_std_command(&self, receiver: &Expr) -> bool {
+        match receiver {
+            Expr::MethodCall(call) => self.is_std_command(&call.receiver),
+            Expr::Paren(paren) => self.is_std_command(&paren.expr),
+            Expr::Call(call) => match &*call.func {
+                Expr::Path(ExprPath { path, .. }) => {
+                    let path_str = path_to_string(path);
+                    path_str.ends_with("Command::new") && !self.rule.is_allowed_path(&path_str)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for SyncIoVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -170,18 +301,41 @@ impl<'ast> Visit<'ast> for SyncIoVisitor<'_> {
             self.in_allowed_context = true;
         }
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
-        syn::visit::visit_item_fn(self, node);
+        self.with_async(node.sig.asyncness.is_some(), |v| {
+            syn::visit::visit_item_fn(v, node);
+        });
         self.in_allowed_context = was_allowed;
     }
 
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        self.with_async(node.sig.asyncness.is_some(), |v| {
+            syn::visit::visit_impl_item_fn(v, node);
+        });
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+        self.with_async(node.sig.asyncness.is_some(), |v| {
+            syn::visit::visit_trait_item_fn(v, node);
+        });
+    }
+
+    fn visit_expr_async(&mut self, node: &'ast ExprAsync) {
+        self.with_async(true, |v| syn::visit::visit_expr_async(v, node));
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast ExprClosure) {
+        let is_async = self.in_async || node.asyncness.is_some();
+        self.with_async(is_async, |v| syn::visit::visit_expr_closure(v, node));
+    }
+
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -190,7 +344,15 @@ impl<'ast> Visit<'ast> for SyncIoVisitor<'_> {
     }
 
     fn visit_expr_call(&mut self, node: &'ast ExprCall) {
-        if self.in_allowed_context {
+        if let Expr::Path(ExprPath { path, .. }) = &*node.func {
+            if is_blocking_offload(&path_to_string(path)) {
+                self.visit_expr(&node.func);
+                self.visit_offloaded_args(&node.args);
+                return;
+            }
+        }
+
+        if self.is_skipped() {
             syn::visit::visit_expr_call(self, node);
             return;
         }
@@ -198,64 +360,15 @@ impl<'ast> Visit<'ast> for SyncIoVisitor<'_> {
         if let Expr::Path(ExprPath { path, .. }) = &*node.func {
             let path_str = path_to_string(path);
 
-            // Check if allowed
-            if self.rule.is_allowed_path(&path_str) {
-                syn::visit::visit_expr_call(self, node);
-                return;
-            }
-
-            // Check if forbidden
-            if FORBIDDEN_FS
-                .iter()
-                .any(|f| path_str.ends_with(f) || path_str == *f)
-            {
+            if !self.rule.is_allowed_path(&path_str) && is_blocking_call(&path_str) {
                 let span = path
                     .segments
                     .last()
                     .map_or_else(proc_macro2::Span::call_site, |s| s.ident.span());
-                let start = span.start();
-
-                // Check for inline allow comment
-                let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
-                if allow_check.is_allowed() {
-                    // If reason is required but not provided, create a separate violation
-                    if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
-                        let location = Location::new(
-                            self.ctx.relative_path.clone(),
-                            start.line,
-                            start.column + 1,
-                        );
-                        self.violations.push(
-                            Violation::new(
-                                CODE,
-                                NAME,
-                                Severity::Warning,
-                                location,
-                                format!("Allow directive for '{NAME}' is missing required reason"),
-                            )
-                            .with_suggestion(Suggestion::new(
-                                "Add reason=\"...\" to explain why this exception is necessary",
-                            )),
-                        );
-                    }
-                    syn::visit::visit_expr_call(self, node);
-                    return;
-                }
-
-                let location =
-                    Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-
-                let suggestion = get_async_alternative(&path_str);
-
-                self.violations.push(
-                    Violation::new(
-                        CODE,
-                        NAME,
-                        self.rule.severity,
-                        location,
-                        format!("Synchronous I/O `{path_str}` may block the async runtime"),
-                    )
-                    .with_suggestion(Suggestion::new(suggestion)),
+                self.report(
+                    span,
+                    format!("Synchronous I/O `{path_str}` may block the async runtime"),
+                    get_async_alternative(&path_str),
                 );
             }
         }
@@ -263,8 +376,23 @@ impl<'ast> Visit<'ast> for SyncIoVisitor<'_> {
         syn::visit::visit_expr_call(self, node);
     }
 
+    fn visit_expr_await(&mut self, node: &'ast ExprAwait) {
+        // An awaited method call is async by definition; only visit its parts.
+        if let Expr::MethodCall(call) = &*node.base {
+            if !self.visit_offloaded_method(call) {
+                syn::visit::visit_expr_method_call(self, call);
+            }
+        } else {
+            syn::visit::visit_expr_await(self, node);
+        }
+    }
+
     fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
-        if self.in_allowed_context {
+        if self.visit_offloaded_method(node) {
+            return;
+        }
+
+        if self.is_skipped() {
             syn::visit::visit_expr_method_call(self, node);
             return;
         }
@@ -272,48 +400,19 @@ impl<'ast> Visit<'ast> for SyncIoVisitor<'_> {
         let method_name = node.method.to_string();
 
         // Check for forbidden Path methods
-        if FORBIDDEN_PATH_METHODS.contains(&method_name.as_str()) {
-            let span = node.method.span();
-            let start = span.start();
-
-            // Check for inline allow comment
-            let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
-            if allow_check.is_allowed() {
-                // If reason is required but not provided, create a separate violation
-                if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
-                    let location =
-                        Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-                    self.violations.push(
-                        Violation::new(
-                            CODE,
-                            NAME,
-                            Severity::Warning,
-                            location,
-                            format!("Allow directive for '{NAME}' is missing required reason"),
-                        )
-                        .with_suggestion(Suggestion::new(
-                            "Add reason=\"...\" to explain why this exception is necessary",
-                        )),
-                    );
-                }
-                syn::visit::visit_expr_method_call(self, node);
-                return;
-            }
-
-            let location =
-                Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-
-            self.violations.push(
-                Violation::new(
-                    CODE,
-                    NAME,
-                    self.rule.severity,
-                    location,
-                    format!("`.{method_name}()` performs synchronous I/O"),
-                )
-                .with_suggestion(Suggestion::new(format!(
-                    "Use `tokio::fs::{method_name}` or async equivalent"
-                ))),
+        if is_blocking_path_method(&method_name) {
+            self.report(
+                node.method.span(),
+                format!("`.{method_name}()` performs synchronous I/O"),
+                format!("Use `tokio::fs::{method_name}` or async equivalent"),
+            );
+        } else if FORBIDDEN_COMMAND_METHODS.contains(&method_name.as_str())
+            && self.is_std_command(&node.receiver)
+        {
+            self.report(
+                node.method.span(),
+                format!("`Command::{method_name}()` blocks until the child process exits"),
+                format!("Use `tokio::process::Command` and `.{method_name}().await` instead"),
             );
         }
 
@@ -321,12 +420,33 @@ impl<'ast> Visit<'ast> for SyncIoVisitor<'_> {
     }
 }
 
+/// Returns `true` if calling the function at `path` is blocking I/O.
+///
+/// Matches by suffix, so `::std::fs::read` counts as `std::fs::read`.
+pub(crate) fn is_blocking_call(path: &str) -> bool {
+    FORBIDDEN_CALLS.iter().any(|f| path.ends_with(f))
+}
+
+/// Returns `true` if the function at `path` runs its closure argument off
+/// the async runtime (`spawn_blocking`, `block_in_place`, `thread::spawn`).
+fn is_blocking_offload(path: &str) -> bool {
+    let last = path.rsplit("::").next().unwrap_or(path);
+    BLOCKING_OFFLOAD_FNS.contains(&last) || path.ends_with("thread::spawn")
+}
+
+/// Returns `true` if `method` is a `Path` method that touches the filesystem.
+pub(crate) fn is_blocking_path_method(method: &str) -> bool {
+    FORBIDDEN_PATH_METHODS.contains(&method)
+}
+
 fn get_async_alternative(path: &str) -> String {
     if path.contains("std::fs::") {
         let fn_name = path.rsplit("::").next().unwrap_or("");
         format!("Use `tokio::fs::{fn_name}` instead")
     } else if path.contains("std::thread::sleep") {
         "Use `tokio::time::sleep` instead".to_string()
+    } else if let Some(rest) = path.split_once("std::net::").map(|(_, rest)| rest) {
+        format!("Use `tokio::net::{rest}` instead")
     } else {
         "Use async I/O operations instead".to_string()
     }
@@ -339,13 +459,7 @@ mod tests {
 
     fn check_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         NoSyncIo::new().check(&ctx, &ast)
     }
 
@@ -425,6 +539,194 @@ fn foo() {
     // arch-lint: allow(no-sync-io) reason="Startup initialization only"
     let content = std::fs::read_to_string("config.toml");
 }
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_allows_next_line_only() {
+        let violations = check_code(
+            r#"
+fn foo() {
+    // arch-lint: allow-next-line(no-sync-io) reason="Startup initialization only"
+    let config = std::fs::read_to_string("config.toml");
+    let other = std::fs::read_to_string("other.toml");
+}
+"#,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location.line, 5);
+    }
+
+    fn check_async_only(code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        NoSyncIo::new().only_in_async(true).check(&ctx, &ast)
+    }
+
+    #[test]
+    fn test_applies_to_skips_sync_files_only_in_async_mode() {
+        let code = "fn load() { let _ = std::fs::read(\"a\"); }";
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        assert!(!NoSyncIo::new().only_in_async(true).applies_to(&ctx));
+        assert!(NoSyncIo::new().applies_to(&ctx));
+
+        let code = "async fn load() { let _ = std::fs::read(\"a\"); }";
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        assert!(NoSyncIo::new().only_in_async(true).applies_to(&ctx));
+    }
+
+    #[test]
+    fn test_only_in_async_allows_sync_fn() {
+        let violations = check_async_only(
+            r#"
+fn load() -> String {
+    std::fs::read_to_string("config.toml").unwrap_or_default()
+}
+
+struct Cli;
+impl Cli {
+    fn run(&self, path: &std::path::Path) -> bool {
+        path.exists()
+    }
+}
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_only_in_async_flags_async_contexts() {
+        let violations = check_async_only(
+            r#"
+async fn load() -> String {
+    std::fs::read_to_string("config.toml").unwrap_or_default()
+}
+
+struct Server;
+impl Server {
+    async fn serve(&self, path: &std::path::Path) -> bool {
+        path.exists()
+    }
+}
+
+fn spawn() {
+    tokio::spawn(async {
+        std::fs::write("out.txt", "data");
+    });
+}
+"#,
+        );
+        let lines: Vec<_> = violations.iter().map(|v| v.location.line).collect();
+        assert_eq!(lines, vec![3, 9, 15]);
+    }
+
+    #[test]
+    fn test_only_in_async_nested_sync_fn_is_allowed() {
+        let violations = check_async_only(
+            r#"
+async fn outer() {
+    fn helper() -> Vec<u8> {
+        std::fs::read("data.bin").unwrap_or_default()
+    }
+    helper();
+}
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_only_in_async_allows_blocking_offload_closures() {
+        let violations = check_async_only(
+            r#"
+async fn load(path: std::path::PathBuf) -> Vec<u8> {
+    let a = tokio::task::spawn_blocking(move || std::fs::read(&path)).await;
+    let b = tokio::task::block_in_place(|| std::fs::read("b.bin"));
+    let c = std::thread::spawn(|| std::fs::read("c.bin"));
+    let d = handle.spawn_blocking(|| std::fs::read("d.bin")).await;
+    let e = tokio::task::spawn_blocking(|| ()).map(|_| std::fs::read("e.bin"));
+    a
+}
+"#,
+        );
+        let lines: Vec<_> = violations.iter().map(|v| v.location.line).collect();
+        assert_eq!(lines, vec![7], "{violations:?}");
+    }
+
+    #[test]
+    fn test_detects_thread_sleep() {
+        let violations = check_code(
+            r"
+fn wait() {
+    std::thread::sleep(std::time::Duration::from_millis(10));
+}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("std::thread::sleep"));
+        let suggestion = violations[0].suggestion.as_ref().expect("suggestion");
+        assert!(suggestion.message.contains("tokio::time::sleep"));
+    }
+
+    #[test]
+    fn test_detects_std_net_connect() {
+        let violations = check_code(
+            r#"
+fn connect() {
+    let stream = std::net::TcpStream::connect("127.0.0.1:80");
+}
+"#,
+        );
+        assert_eq!(violations.len(), 1);
+        let suggestion = violations[0].suggestion.as_ref().expect("suggestion");
+        assert!(suggestion
+            .message
+            .contains("tokio::net::TcpStream::connect"));
+    }
+
+    #[test]
+    fn test_detects_command_output_and_status() {
+        let violations = check_code(
+            r#"
+fn run() {
+    let out = std::process::Command::new("ls").arg("-l").output();
+    let status = Command::new("true").status();
+}
+"#,
+        );
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].message.contains("Command::output()"));
+        assert!(violations[1].message.contains("Command::status()"));
+        let suggestion = violations[0].suggestion.as_ref().expect("suggestion");
+        assert!(suggestion.message.contains("tokio::process::Command"));
+    }
+
+    #[test]
+    fn test_allows_async_command_and_unrelated_status() {
+        let violations = check_code(
+            r#"
+async fn run(response: Response) {
+    let out = tokio::process::Command::new("ls").output().await;
+    let out = Command::new("ls").status().await;
+    let code = response.status();
+}
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_new_patterns_respect_suppression() {
+        let violations = check_code(
+            r#"
+fn run() {
+    // arch-lint: allow(no-sync-io) reason="CLI tool, no runtime"
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    // arch-lint: allow(no-sync-io) reason="CLI tool, no runtime"
+    let out = Command::new("ls").output();
+}
 "#,
         );
         assert!(violations.is_empty());
@@ -8,18 +8,35 @@
 //! # Configuration
 //!
 //! - `allow_in_tests`: Allow in test code (default: true)
-//! - `allow_expect`: Allow `.expect()` but forbid `.unwrap()` (default: false)
+//! - `allow_expect`: Allow `.expect()` and `.expect_err()` but forbid
+//!   `.unwrap()` (default: false)
+//! - `check_unwrap_err`: Also forbid `.unwrap_err()` and `.expect_err()`,
+//!   which panic on `Ok` (default: true)
+//!
+//! # Suggestions
+//!
+//! Inside a function returning `Result` or `Option`, `.unwrap()` is
+//! suggested to become `?`. The autofix is only attached when the receiver
+//! is known to be of the same kind (e.g. `.parse()` for `Result`, `.get(..)`
+//! or `.ok()` for `Option`); otherwise `?` might not compile, so only the
+//! message is given. Closures and async blocks have their own return type,
+//! so calls inside them get the generic suggestion.
 //!
 //! # Suppression
 //!
 //! - `#[allow(clippy::unwrap_used)]` on the item
 //! - `// arch-lint: allow(no-unwrap-expect)` comment
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::{check_arch_lint_allow, has_allow_attr, has_cfg_test, has_test_attr};
-use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Replacement, Rule, RuleConfig, Severity, Suggestion,
+    Violation,
+};
 use syn::visit::Visit;
-use syn::{Expr, ExprMethodCall, ItemFn, ItemImpl, ItemMod};
+use syn::{
+    Expr, ExprAsync, ExprClosure, ExprMethodCall, ImplItemFn, ItemFn, ItemImpl, ItemMod,
+    ReturnType, Type,
+};
 
 /// Rule code for no-unwrap-expect.
 pub const CODE: &str = "AL001";
@@ -32,8 +49,10 @@ pub const NAME: &str = "no-unwrap-expect";
 pub struct NoUnwrapExpect {
     /// Allow in test code.
     pub allow_in_tests: bool,
-    /// Allow `.expect()` (only forbid `.unwrap()`).
+    /// Allow `.expect()` and `.expect_err()` (only forbid the unwrap forms).
     pub allow_expect: bool,
+    /// Also forbid `.unwrap_err()` and `.expect_err()`.
+    pub check_unwrap_err: bool,
     /// Custom severity.
     pub severity: Severity,
 }
@@ -51,6 +70,7 @@ impl NoUnwrapExpect {
         Self {
             allow_in_tests: true,
             allow_expect: false,
+            check_unwrap_err: true,
             severity: Severity::Error,
         }
     }
@@ -62,13 +82,20 @@ impl NoUnwrapExpect {
         self
     }
 
-    /// Sets whether to allow `.expect()`.
+    /// Sets whether to allow `.expect()` and `.expect_err()`.
     #[must_use]
     pub fn allow_expect(mut self, allow: bool) -> Self {
         self.allow_expect = allow;
         self
     }
 
+    /// Sets whether to forbid `.unwrap_err()` and `.expect_err()`.
+    #[must_use]
+    pub fn check_unwrap_err(mut self, check: bool) -> Self {
+        self.check_unwrap_err = check;
+        self
+    }
+
     /// Sets the severity level.
     #[must_use]
     pub fn severity(mut self, severity: Severity) -> Self {
@@ -90,10 +117,37 @@ impl Rule for NoUnwrapExpect {
         "Forbids .unwrap() and .expect() in production code"
     }
 
+    fn explanation(&self) -> &'static str {
+        "`.unwrap()` and `.expect()` panic at runtime when the value is missing. \
+         Production code should propagate errors with `?` or handle them explicitly."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
 
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_in_tests", "allow_expect", "check_unwrap_err"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        for (key, field) in [
+            ("allow_in_tests", &mut self.allow_in_tests),
+            ("allow_expect", &mut self.allow_expect),
+            ("check_unwrap_err", &mut self.check_unwrap_err),
+        ] {
+            let Some(value) = config.options.get(key) else {
+                continue;
+            };
+            *field = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: key.to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        Ok(())
+    }
+
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
         // Skip test files if configured
         if self.allow_in_tests && ctx.is_test {
@@ -104,8 +158,9 @@ impl Rule for NoUnwrapExpect {
             ctx,
             rule: self,
             violations: Vec::new(),
-            in_test_context: false,
-            in_allowed_context: false,
+            context: ContextTracker::new(NAME, CODE)
+                .allow_lints(&["clippy::unwrap_used", "clippy::expect_used"]),
+            try_return: None,
         };
 
         visitor.visit_file(ast);
@@ -117,88 +172,139 @@ struct UnwrapExpectVisitor<'a> {
     ctx: &'a FileContext<'a>,
     rule: &'a NoUnwrapExpect,
     violations: Vec<Violation>,
-    in_test_context: bool,
-    in_allowed_context: bool,
+    context: ContextTracker,
+    /// `Result` or `Option` if the enclosing function returns it, so `?`
+    /// can replace `.unwrap()`.
+    try_return: Option<&'static str>,
 }
 
-impl<'ast> Visit<'ast> for UnwrapExpectVisitor<'_> {
-    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
-        let was_in_test = self.in_test_context;
-        let was_allowed = self.in_allowed_context;
+impl UnwrapExpectVisitor<'_> {
+    /// Visits a body whose `?` returns from a function (or closure) with
+    /// the given return type.
+    fn with_return(&mut self, output: Option<&ReturnType>, visit: impl FnOnce(&mut Self)) {
+        let saved = self.try_return;
+        self.try_return = output.and_then(try_return_kind);
+        visit(self);
+        self.try_return = saved;
+    }
 
-        if has_cfg_test(&node.attrs) {
-            self.in_test_context = true;
+    /// Suggestion replacing `receiver.unwrap()` with `receiver?`, with an
+    /// autofix only if the receiver is known to be a `kind` as well.
+    fn question_mark_fix(&self, node: &ExprMethodCall, kind: &str) -> Suggestion {
+        if receiver_kind(&node.receiver) != Some(kind) {
+            return Suggestion::new(format!(
+                "Use `?`: the enclosing function returns `{kind}` \
+                 (convert the receiver first if needed, e.g. `.ok_or(..)?` or `.map_err(..)?`)"
+            ));
         }
+        let start = node.dot_token.span.start();
+        let end = node.paren_token.span.close().end();
+        let offset = self.ctx.offset_for(start.line, start.column + 1);
+        let length = self.ctx.offset_for(end.line, end.column + 1) - offset;
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1)
+            .with_span(offset, length);
+        Suggestion::with_fix(
+            format!("Replace `.unwrap()` with `?`: the enclosing function returns `{kind}`"),
+            Replacement::new(location, "?"),
+        )
+    }
+}
+
+/// Returns `"Result"` or `"Option"` if `output` names one of them
+/// (including paths such as `io::Result<T>`).
+fn try_return_kind(output: &ReturnType) -> Option<&'static str> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let Type::Path(path) = ty.as_ref() else {
+        return None;
+    };
+    match path.path.segments.last()?.ident.to_string().as_str() {
+        "Result" => Some("Result"),
+        "Option" => Some("Option"),
+        _ => None,
+    }
+}
 
-        // Check for #[arch_lint::allow(no-unwrap-expect)]
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
-            self.in_allowed_context = true;
+/// Returns `"Result"` or `"Option"` if `expr` is known to produce one,
+/// judging by its outermost method call or constructor.
+fn receiver_kind(expr: &Expr) -> Option<&'static str> {
+    match expr {
+        Expr::MethodCall(call) => match call.method.to_string().as_str() {
+            "parse" | "ok_or" | "ok_or_else" => Some("Result"),
+            "ok" | "err" | "get" | "get_mut" | "first" | "last" | "next" | "find" | "position"
+            | "strip_prefix" | "strip_suffix" => Some("Option"),
+            _ => None,
+        },
+        Expr::Call(call) => {
+            let Expr::Path(func) = call.func.as_ref() else {
+                return None;
+            };
+            match func.path.segments.last()?.ident.to_string().as_str() {
+                "Ok" | "Err" => Some("Result"),
+                "Some" => Some("Option"),
+                _ => None,
+            }
         }
+        Expr::Paren(paren) => receiver_kind(&paren.expr),
+        _ => None,
+    }
+}
 
+impl<'ast> Visit<'ast> for UnwrapExpectVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
         syn::visit::visit_item_mod(self, node);
-
-        self.in_test_context = was_in_test;
-        self.in_allowed_context = was_allowed;
+        self.context.restore(saved);
     }
 
     fn visit_item_fn(&mut self, node: &'ast ItemFn) {
-        let was_in_test = self.in_test_context;
-        let was_allowed = self.in_allowed_context;
-
-        if has_test_attr(&node.attrs) {
-            self.in_test_context = true;
-        }
-
-        if has_allow_attr(&node.attrs, &["clippy::unwrap_used", "clippy::expect_used"]) {
-            self.in_allowed_context = true;
-        }
+        let saved = self.context.enter_fn(&node.attrs);
+        self.with_return(Some(&node.sig.output), |v| {
+            syn::visit::visit_item_fn(v, node);
+        });
+        self.context.restore(saved);
+    }
 
-        // Check for #[arch_lint::allow(no-unwrap-expect)]
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
-            self.in_allowed_context = true;
-        }
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        self.with_return(Some(&node.sig.output), |v| {
+            syn::visit::visit_impl_item_fn(v, node);
+        });
+    }
 
-        syn::visit::visit_item_fn(self, node);
+    fn visit_expr_closure(&mut self, node: &'ast ExprClosure) {
+        self.with_return(Some(&node.output), |v| {
+            syn::visit::visit_expr_closure(v, node);
+        });
+    }
 
-        self.in_test_context = was_in_test;
-        self.in_allowed_context = was_allowed;
+    fn visit_expr_async(&mut self, node: &'ast ExprAsync) {
+        self.with_return(None, |v| syn::visit::visit_expr_async(v, node));
     }
 
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
-        let was_allowed = self.in_allowed_context;
-
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
-            self.in_allowed_context = true;
-        }
-
+        let saved = self.context.enter_impl(&node.attrs);
         syn::visit::visit_item_impl(self, node);
-
-        self.in_allowed_context = was_allowed;
+        self.context.restore(saved);
     }
 
     fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
-        // Skip if in test context and tests are allowed
-        if self.rule.allow_in_tests && self.in_test_context {
+        if self.context.is_skipped(self.rule.allow_in_tests) {
             syn::visit::visit_expr_method_call(self, node);
             return;
         }
 
-        // Skip if in allowed context
-        if self.in_allowed_context {
+        let Some(method) = PanickingMethod::from_name(&node.method.to_string()) else {
             syn::visit::visit_expr_method_call(self, node);
             return;
-        }
-
-        let method_name = node.method.to_string();
-        let is_unwrap = method_name == "unwrap";
-        let is_expect = method_name == "expect";
+        };
 
-        if is_unwrap || (is_expect && !self.rule.allow_expect) {
+        if self.rule.forbids(method) {
             let span = node.method.span();
             let start = span.start();
 
             // Check for inline allow comment
-            let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+            let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
             if allow_check.is_allowed() {
                 // If reason is required but not provided, create a separate violation
                 if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -224,20 +330,35 @@ impl<'ast> Visit<'ast> for UnwrapExpectVisitor<'_> {
             let location =
                 Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
 
-            let (message, suggestion) = if is_unwrap {
-                (
+            let (message, suggestion) = match method {
+                PanickingMethod::Unwrap => (
                     ".unwrap() is forbidden in production code".to_string(),
-                    Suggestion::new("Use `?` operator, `.ok_or(Error)?`, or pattern matching"),
-                )
-            } else {
-                (
+                    match self.try_return {
+                        Some(kind) => self.question_mark_fix(node, kind),
+                        None => Suggestion::new(
+                            "Use `?` operator, `.ok_or(Error)?`, or pattern matching",
+                        ),
+                    },
+                ),
+                PanickingMethod::Expect => (
                     ".expect() is forbidden in production code".to_string(),
                     Suggestion::new("Use `?` operator with `.context()` or custom error"),
-                )
+                ),
+                PanickingMethod::UnwrapErr | PanickingMethod::ExpectErr => (
+                    format!(
+                        ".{}() is forbidden in production code (panics when the result is `Ok`)",
+                        node.method
+                    ),
+                    Suggestion::new(
+                        "Match on the result, or use `.err()` / `if let Err(e) = ...` to handle the `Ok` case",
+                    ),
+                ),
             };
 
             // Check for partial_cmp().unwrap() pattern (NaN danger)
-            let is_partial_cmp_unwrap = is_unwrap && is_partial_cmp_chain(&node.receiver);
+            let is_partial_cmp_unwrap = method == PanickingMethod::Unwrap
+                && chained_method_call(&node.receiver)
+                    .is_some_and(|call| call.method == "partial_cmp");
             let message = if is_partial_cmp_unwrap {
                 format!("{message} (NaN comparison danger with partial_cmp)")
             } else {
@@ -254,12 +375,47 @@ impl<'ast> Visit<'ast> for UnwrapExpectVisitor<'_> {
     }
 }
 
-/// Checks if the receiver is a `partial_cmp()` call.
-fn is_partial_cmp_chain(expr: &Expr) -> bool {
-    if let Expr::MethodCall(call) = expr {
-        call.method == "partial_cmp"
-    } else {
-        false
+/// Panicking accessor methods the rule looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PanickingMethod {
+    Unwrap,
+    Expect,
+    UnwrapErr,
+    ExpectErr,
+}
+
+impl PanickingMethod {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "unwrap" => Some(Self::Unwrap),
+            "expect" => Some(Self::Expect),
+            "unwrap_err" => Some(Self::UnwrapErr),
+            "expect_err" => Some(Self::ExpectErr),
+            _ => None,
+        }
+    }
+}
+
+impl NoUnwrapExpect {
+    fn forbids(&self, method: PanickingMethod) -> bool {
+        match method {
+            PanickingMethod::Unwrap => true,
+            PanickingMethod::Expect => !self.allow_expect,
+            PanickingMethod::UnwrapErr => self.check_unwrap_err,
+            PanickingMethod::ExpectErr => self.check_unwrap_err && !self.allow_expect,
+        }
+    }
+}
+
+/// Returns the method call `expr` is, if any, looking through parentheses.
+///
+/// Used on the receiver of `.unwrap()` to inspect the call it is chained on,
+/// e.g. `partial_cmp` in `a.partial_cmp(b).unwrap()`.
+pub(crate) fn chained_method_call(expr: &Expr) -> Option<&ExprMethodCall> {
+    match expr {
+        Expr::MethodCall(call) => Some(call),
+        Expr::Paren(paren) => chained_method_call(&paren.expr),
+        _ => None,
     }
 }
 
@@ -269,15 +425,92 @@ mod tests {
     use std::path::Path;
 
     fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&NoUnwrapExpect::new(), code)
+    }
+
+    fn check_with(rule: &NoUnwrapExpect, code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
-        NoUnwrapExpect::new().check(&ctx, &ast)
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    const ALL_FOUR: &str = r#"
+fn foo(r: Result<u8, u8>) {
+    r.unwrap();
+    r.expect("ok");
+    r.unwrap_err();
+    r.expect_err("err");
+}
+"#;
+
+    fn flagged_methods(rule: &NoUnwrapExpect) -> Vec<String> {
+        check_with(rule, ALL_FOUR)
+            .into_iter()
+            .map(|v| {
+                let method = v.message.split("()").next().unwrap_or_default();
+                method.trim_start_matches('.').to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_each_panicking_method() {
+        assert_eq!(
+            flagged_methods(&NoUnwrapExpect::new()),
+            ["unwrap", "expect", "unwrap_err", "expect_err"]
+        );
+
+        let violations = check_code(ALL_FOUR);
+        assert!(violations[2]
+            .message
+            .contains("panics when the result is `Ok`"));
+        assert!(violations[3]
+            .message
+            .contains("panics when the result is `Ok`"));
+    }
+
+    #[test]
+    fn test_allow_expect_covers_expect_err() {
+        assert_eq!(
+            flagged_methods(&NoUnwrapExpect::new().allow_expect(true)),
+            ["unwrap", "unwrap_err"]
+        );
+    }
+
+    #[test]
+    fn test_check_unwrap_err_disabled() {
+        assert_eq!(
+            flagged_methods(&NoUnwrapExpect::new().check_unwrap_err(false)),
+            ["unwrap", "expect"]
+        );
+        assert_eq!(
+            flagged_methods(
+                &NoUnwrapExpect::new()
+                    .check_unwrap_err(false)
+                    .allow_expect(true)
+            ),
+            ["unwrap"]
+        );
+    }
+
+    #[test]
+    fn test_configure_options() {
+        let config = arch_lint_core::Config::parse(
+            "[rules.no-unwrap-expect]\ncheck_unwrap_err = false\nallow_expect = true\n",
+        )
+        .expect("parse config");
+        let mut rule = NoUnwrapExpect::new();
+        rule.configure(&config.rules[NAME]).expect("valid options");
+        assert!(!rule.check_unwrap_err);
+        assert!(rule.allow_expect);
+
+        let config =
+            arch_lint_core::Config::parse("[rules.no-unwrap-expect]\ncheck_unwrap_err = \"no\"\n")
+                .expect("parse config");
+        assert!(matches!(
+            NoUnwrapExpect::new().configure(&config.rules[NAME]),
+            Err(ConfigError::InvalidOption { .. })
+        ));
     }
 
     #[test]
@@ -293,6 +526,39 @@ fn foo() {
         assert_eq!(violations[0].code, CODE);
     }
 
+    #[test]
+    fn test_allow_by_code_and_name_are_equivalent() {
+        for allow in ["AL001", "no-unwrap-expect"] {
+            let comment = format!(
+                "fn foo() {{\n    // arch-lint: allow({allow}) reason=\"checked\"\n    Some(1).unwrap();\n}}"
+            );
+            assert!(check_code(&comment).is_empty(), "comment allow({allow})");
+
+            let attr = format!(
+                "#[arch_lint::allow({}, reason = \"checked\")]\nfn foo() {{ Some(1).unwrap(); }}",
+                allow.replace('-', "_")
+            );
+            assert!(check_code(&attr).is_empty(), "attribute allow({allow})");
+        }
+    }
+
+    #[test]
+    fn test_allow_block_range() {
+        let violations = check_code(
+            r#"
+fn foo() {
+    // arch-lint: allow-begin(no-unwrap-expect) reason="fixture setup"
+    let a = Some(1).unwrap();
+    let b = Some(2).unwrap();
+    // arch-lint: allow-end(no-unwrap-expect)
+    let c = Some(3).unwrap();
+}
+"#,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location.line, 7);
+    }
+
     #[test]
     fn test_detects_expect() {
         let violations = check_code(
@@ -391,4 +657,107 @@ fn foo() {
         // Should not have any violations when reason is provided
         assert!(violations.is_empty());
     }
+
+    fn apply_fix(code: &str, violation: &Violation) -> String {
+        let replacement = violation
+            .suggestion
+            .as_ref()
+            .and_then(|s| s.replacement.as_ref())
+            .expect("has a fix");
+        let location = &replacement.location;
+        let mut fixed = code.to_string();
+        fixed.replace_range(
+            location.offset..location.offset + location.length,
+            &replacement.new_text,
+        );
+        fixed
+    }
+
+    #[test]
+    fn test_suggests_question_mark_in_result_and_option_fns() {
+        let code = r#"
+fn load(s: &str) -> Result<u8, ParseIntError> {
+    let v = s.parse::<u8>().unwrap();
+    Ok(v)
+}
+
+impl Config {
+    fn port(&self) -> Option<u16> {
+        let p = self.get("port") .unwrap ( );
+        Some(p)
+    }
+}
+"#;
+        let violations = check_code(code);
+        assert_eq!(violations.len(), 2);
+
+        let suggestion = violations[0].suggestion.as_ref().expect("suggestion");
+        assert_eq!(
+            suggestion.message,
+            "Replace `.unwrap()` with `?`: the enclosing function returns `Result`"
+        );
+        let fixed = apply_fix(code, &violations[0]);
+        assert!(fixed.contains("let v = s.parse::<u8>()?;"), "{fixed}");
+
+        assert!(violations[1]
+            .suggestion
+            .as_ref()
+            .is_some_and(|s| s.message.ends_with("returns `Option`")));
+        let fixed = apply_fix(code, &violations[1]);
+        assert!(fixed.contains(r#"let p = self.get("port") ?;"#), "{fixed}");
+    }
+
+    #[test]
+    fn test_no_autofix_when_receiver_kind_differs_or_is_unknown() {
+        let violations = check_code(
+            r#"
+fn load(map: &HashMap<String, u8>) -> Result<u8, Error> {
+    let v = map.get("key").unwrap();
+    let w = read_config().unwrap();
+    Ok(v + w)
+}
+
+fn find(s: &str) -> Option<u8> {
+    s.parse::<u8>().unwrap();
+    None
+}
+"#,
+        );
+        assert_eq!(violations.len(), 3);
+        for (violation, kind) in violations.iter().zip(["Result", "Result", "Option"]) {
+            let suggestion = violation.suggestion.as_ref().expect("suggestion");
+            assert!(suggestion.replacement.is_none(), "{violation:?}");
+            assert!(
+                suggestion
+                    .message
+                    .starts_with(&format!("Use `?`: the enclosing function returns `{kind}`")),
+                "{}",
+                suggestion.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_generic_suggestion_outside_try_contexts() {
+        let violations = check_code(
+            r#"
+fn run() {
+    let v = "1".parse::<u8>().unwrap();
+}
+
+fn load() -> Result<Vec<u8>, Error> {
+    let items: Vec<u8> = raw.iter().map(|s| s.parse().unwrap()).collect();
+    let task = async { fetch().await.unwrap() };
+    let y = Some(1).expect("set");
+    Ok(items)
+}
+"#,
+        );
+        assert_eq!(violations.len(), 4);
+        for violation in &violations {
+            let suggestion = violation.suggestion.as_ref().expect("suggestion");
+            assert!(suggestion.replacement.is_none(), "{violation:?}");
+            assert!(suggestion.message.starts_with("Use `?` operator"));
+        }
+    }
 }
@@ -0,0 +1,370 @@
+//! Project rule to detect source files that no `mod` declaration reaches.
+//!
+//! # Rationale
+//!
+//! A `.rs` file under `src/` that is never declared with `mod` is not part
+//! of the crate: it is not compiled, not tested, and silently rots. Such
+//! files are usually leftovers from a refactoring or a forgotten `mod`.
+//!
+//! # Detection
+//!
+//! Starting from each crate root (`src/lib.rs`, `src/main.rs`,
+//! `src/bin/*.rs`, `src/bin/*/main.rs`), `mod x;` declarations are followed
+//! across files, including inline `mod x { ... }` blocks, `x.rs` and
+//! `x/mod.rs` layouts, and `#[path = "..."]` attributes. Every other file
+//! under the same `src/` directory is reported.
+//!
+//! `cfg` attributes are ignored, so a module compiled only on some targets
+//! counts as reached. Modules declared by macros are not seen. If a reached
+//! file fails to parse, its crate is skipped to avoid false positives.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use arch_lint_core::{Location, ProjectContext, ProjectRule, Severity, Suggestion, Violation};
+use syn::{Item, ItemMod};
+
+/// Rule code for orphan-modules.
+pub const CODE: &str = "AL040";
+
+/// Rule name for orphan-modules.
+pub const NAME: &str = "orphan-modules";
+
+/// Reports `.rs` files under `src/` that no `mod` declaration reaches.
+#[derive(Debug, Clone)]
+pub struct OrphanModules {
+    /// Severity level.
+    pub severity: Severity,
+}
+
+impl Default for OrphanModules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrphanModules {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl ProjectRule for OrphanModules {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects source files not reached by any mod declaration"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A `.rs` file under `src/` that no `mod` declaration reaches is never compiled: \
+         it is not type-checked, its tests never run, and readers mistake it for live code."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Violation> {
+        let sources: HashMap<PathBuf, &PathBuf> = ctx
+            .source_files
+            .iter()
+            .map(|path| (normalize(path), path))
+            .collect();
+
+        let mut crate_src_dirs = HashSet::new();
+        let mut incomplete_src_dirs = HashSet::new();
+        let mut reached = HashSet::new();
+        let mut queue = Vec::new();
+
+        for path in &ctx.source_files {
+            if let Some(src_dir) = crate_root_src_dir(path) {
+                crate_src_dirs.insert(src_dir);
+                reached.insert(normalize(path));
+                queue.push((path.clone(), parent(path)));
+            }
+        }
+
+        while let Some((file, mod_dir)) = queue.pop() {
+            let Some(ast) = ctx.ast(&file) else {
+                incomplete_src_dirs.extend(src_dir_of(&file));
+                continue;
+            };
+            let mut declared = Vec::new();
+            collect_declared(&ast.items, &parent(&file), &mod_dir, false, &mut declared);
+
+            for (child, child_dir) in declared {
+                let normalized = normalize(&child);
+                if let Some(&original) = sources.get(&normalized) {
+                    if reached.insert(normalized) {
+                        queue.push((original.clone(), child_dir));
+                    }
+                }
+            }
+        }
+
+        ctx.source_files
+            .iter()
+            .filter(|path| !reached.contains(&normalize(path)))
+            .filter(|path| {
+                src_dir_of(path).is_some_and(|src| {
+                    crate_src_dirs.contains(&src) && !incomplete_src_dirs.contains(&src)
+                })
+            })
+            .map(|path| self.violation(path))
+            .collect()
+    }
+}
+
+impl OrphanModules {
+    fn violation(&self, path: &Path) -> Violation {
+        let module = module_name(path);
+        Violation::new(
+            CODE,
+            NAME,
+            self.severity,
+            Location::new(path.to_path_buf(), 1, 1),
+            format!(
+                "Module file is not reached by any `mod` declaration: `{module}` is not compiled"
+            ),
+        )
+        .with_suggestion(Suggestion::new(format!(
+            "Add `mod {module};` to the parent module, or delete the file"
+        )))
+    }
+}
+
+/// Collects the candidate files of `mod x;` declarations in `items`, paired
+/// with the directory their own submodules resolve against.
+///
+/// `file_dir` is the directory of the declaring file (the base for
+/// `#[path]` outside inline modules); `mod_dir` is where `x.rs` is looked up.
+fn collect_declared(
+    items: &[Item],
+    file_dir: &Path,
+    mod_dir: &Path,
+    inline: bool,
+    out: &mut Vec<(PathBuf, PathBuf)>,
+) {
+    for item in items {
+        let Item::Mod(module) = item else {
+            continue;
+        };
+        let name = module.ident.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name);
+
+        match (&module.content, path_attr(module)) {
+            (None, Some(path)) => {
+                let base = if inline { mod_dir } else { file_dir };
+                let file = base.join(path);
+                let dir = parent(&file);
+                out.push((file, dir));
+            }
+            (None, None) => {
+                let dir = mod_dir.join(name);
+                out.push((mod_dir.join(format!("{name}.rs")), dir.clone()));
+                out.push((dir.join("mod.rs"), dir));
+            }
+            (Some((_, items)), path) => {
+                let dir = mod_dir.join(path.unwrap_or_else(|| name.to_string()));
+                collect_declared(items, file_dir, &dir, true, out);
+            }
+        }
+    }
+}
+
+/// Returns the value of a `#[path = "..."]` attribute.
+fn path_attr(module: &ItemMod) -> Option<String> {
+    module.attrs.iter().find_map(|attr| {
+        let syn::Meta::NameValue(nv) = &attr.meta else {
+            return None;
+        };
+        if !nv.path.is_ident("path") {
+            return None;
+        }
+        match &nv.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            }) => Some(s.value()),
+            _ => None,
+        }
+    })
+}
+
+/// Returns the `src/` directory of a crate root file, or `None` if `path`
+/// is not a crate root.
+fn crate_root_src_dir(path: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?.to_str()?;
+    let dir = path.parent()?;
+    let dir_name = dir.file_name()?.to_str()?;
+
+    if dir_name == "src" && matches!(file_name, "lib.rs" | "main.rs") {
+        return Some(dir.to_path_buf());
+    }
+    if dir_name == "bin" {
+        return dir
+            .parent()
+            .filter(|src| src.ends_with("src"))
+            .map(Path::to_path_buf);
+    }
+    let bin = dir.parent()?;
+    if file_name == "main.rs" && bin.ends_with("bin") {
+        return bin
+            .parent()
+            .filter(|src| src.ends_with("src"))
+            .map(Path::to_path_buf);
+    }
+    None
+}
+
+/// Returns the nearest ancestor directory named `src`.
+fn src_dir_of(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .find(|dir| dir.ends_with("src"))
+        .map(Path::to_path_buf)
+}
+
+/// Returns the module name a file would be declared as.
+fn module_name(path: &Path) -> String {
+    let target = if path.ends_with("mod.rs") {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    target
+        .file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().into_owned())
+}
+
+fn parent(path: &Path) -> PathBuf {
+    path.parent().map_or_else(PathBuf::new, Path::to_path_buf)
+}
+
+/// Resolves `.` and `..` lexically so `#[path = "../x.rs"]` matches the
+/// discovered path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(files: &[(&str, &str)]) -> Vec<String> {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let mut paths = Vec::new();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+            std::fs::write(&path, content).expect("write file");
+            paths.push(path);
+        }
+        let ctx = ProjectContext::new(dir.path()).with_source_files(paths);
+
+        OrphanModules::new()
+            .check_project(&ctx)
+            .iter()
+            .map(|v| {
+                let relative = v
+                    .location
+                    .file
+                    .strip_prefix(dir.path())
+                    .expect("under root");
+                relative.to_string_lossy().replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reports_undeclared_file() {
+        let orphans = check(&[
+            ("src/lib.rs", "mod used;"),
+            ("src/used.rs", ""),
+            ("src/unused.rs", ""),
+        ]);
+        assert_eq!(orphans, ["src/unused.rs"]);
+    }
+
+    #[test]
+    fn test_follows_nested_and_mod_rs_layouts() {
+        let orphans = check(&[
+            ("src/main.rs", "mod a; mod b;"),
+            ("src/a.rs", "mod child;"),
+            ("src/a/child.rs", ""),
+            ("src/b/mod.rs", "pub mod inner { mod deep; }"),
+            ("src/b/inner/deep.rs", ""),
+            ("src/b/stale.rs", ""),
+        ]);
+        assert_eq!(orphans, ["src/b/stale.rs"]);
+    }
+
+    #[test]
+    fn test_follows_path_attributes() {
+        let orphans = check(&[
+            ("src/lib.rs", "#[path = \"impls/unix.rs\"] mod sys;"),
+            (
+                "src/impls/unix.rs",
+                "#[path = \"../shared.rs\"] mod shared;",
+            ),
+            ("src/shared.rs", ""),
+        ]);
+        assert!(orphans.is_empty(), "{orphans:?}");
+    }
+
+    #[test]
+    fn test_bin_roots_and_files_outside_src() {
+        let orphans = check(&[
+            ("src/main.rs", ""),
+            ("src/bin/tool.rs", "mod helper;"),
+            ("src/bin/helper.rs", ""),
+            ("src/bin/other/main.rs", ""),
+            ("tests/integration.rs", ""),
+            ("build.rs", ""),
+        ]);
+        assert!(orphans.is_empty(), "{orphans:?}");
+    }
+
+    #[test]
+    fn test_skips_crate_with_unparsable_file() {
+        let orphans = check(&[
+            ("src/lib.rs", "mod broken;"),
+            ("src/broken.rs", "fn {"),
+            ("src/broken/child.rs", ""),
+        ]);
+        assert!(orphans.is_empty(), "{orphans:?}");
+    }
+}
@@ -10,6 +10,12 @@
 //!
 //! - `impl Into<T> for U { ... }`
 //!
+//! The suggestion carries a fix rewriting the impl into
+//! `impl From<U> for T { fn from(value: U) -> T { ... } }`, with `self` in
+//! the body renamed to `value` and `Self` replaced by `U`. Generic impls,
+//! `where` clauses, and anything beyond a single `fn into(self)` are left
+//! for a manual rewrite.
+//!
 //! # Good Patterns
 //!
 //! ```ignore
@@ -21,13 +27,13 @@
 //! }
 //! ```
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::check_arch_lint_allow;
-use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
-#[allow(unused_imports)]
+use arch_lint_core::utils::check_arch_lint_allow_for_rule;
+use arch_lint_core::{FileContext, Location, Replacement, Rule, Severity, Suggestion, Violation};
+use proc_macro2::{LineColumn, Span, TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::spanned::Spanned;
 use syn::visit::Visit;
-use syn::{ItemImpl, ItemMod};
+use syn::{FnArg, ImplItem, ItemImpl, ItemMod, PathArguments, ReturnType};
 
 /// Rule code for prefer-from-over-into.
 pub const CODE: &str = "AL010";
@@ -78,6 +84,12 @@ impl Rule for PreferFromOverInto {
         "Prefers From trait implementation over Into"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Implementing `From` provides `Into` for free through the standard library's \
+         blanket impl. Implementing `Into` directly is redundant and goes against Rust \
+         conventions."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
@@ -106,7 +118,7 @@ impl<'ast> Visit<'ast> for FromIntoVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -116,7 +128,9 @@ impl<'ast> Visit<'ast> for FromIntoVisitor<'_> {
 
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
         // Check if this impl is explicitly allowed
-        if self.in_allowed_context || check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if self.in_allowed_context
+            || check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed()
+        {
             syn::visit::visit_item_impl(self, node);
             return;
         }
@@ -130,7 +144,7 @@ impl<'ast> Visit<'ast> for FromIntoVisitor<'_> {
                     let start = span.start();
 
                     // Check for inline allow comment
-                    let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+                    let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
                     if allow_check.is_allowed() {
                         // If reason is required but not provided, create a separate violation
                         if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -158,25 +172,44 @@ impl<'ast> Visit<'ast> for FromIntoVisitor<'_> {
                         return;
                     }
 
-                    // Extract type information for better error message
-                    let self_type = quote::quote!(#node.self_ty).to_string();
-
                     let location =
                         Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
-
-                    self.violations.push(
-                        Violation::new(
+                    let violation = match rewrite_as_from(self.ctx, node) {
+                        Ok(rewrite) => Violation::new(
                             CODE,
                             NAME,
                             self.rule.severity,
                             location,
-                            format!("Implement `From` instead of `Into` for type `{self_type}`"),
+                            format!(
+                                "Implement `From` instead of `Into` for type `{}`: `{}`",
+                                rewrite.source_type, rewrite.header
+                            ),
                         )
-                        .with_suggestion(Suggestion::new(
-                            "Replace `impl Into<Target> for Source` with `impl From<Source> for Target`. \
-                            The Into implementation will be provided automatically."
+                        .with_suggestion(Suggestion::with_fix(
+                            format!(
+                                "Replace with `{}`; `Into` is then provided automatically",
+                                rewrite.header
+                            ),
+                            rewrite.replacement,
                         )),
-                    );
+                        Err(reason) => Violation::new(
+                            CODE,
+                            NAME,
+                            self.rule.severity,
+                            location,
+                            format!(
+                                "Implement `From` instead of `Into` for type `{}`",
+                                source_text(self.ctx, node.self_ty.span())
+                                    .unwrap_or("Self")
+                            ),
+                        )
+                        .with_suggestion(Suggestion::new(format!(
+                            "Replace `impl Into<Target> for Source` with `impl From<Source> for Target`. \
+                            The Into implementation will be provided automatically. \
+                            (Not rewritten automatically: {reason}.)"
+                        ))),
+                    };
+                    self.violations.push(violation);
                 }
             }
         }
@@ -185,6 +218,138 @@ impl<'ast> Visit<'ast> for FromIntoVisitor<'_> {
     }
 }
 
+/// A mechanical `impl Into<T> for U` to `impl From<U> for T` rewrite.
+struct FromRewrite<'a> {
+    /// `U`, as written.
+    source_type: &'a str,
+    /// `impl From<U> for T`.
+    header: String,
+    /// Replaces the impl from `impl` to its closing brace.
+    replacement: Replacement,
+}
+
+/// Builds the `From` rewrite, or explains why the impl is left alone.
+fn rewrite_as_from<'a>(
+    ctx: &'a FileContext,
+    node: &ItemImpl,
+) -> Result<FromRewrite<'a>, &'static str> {
+    if !node.generics.params.is_empty() || node.generics.where_clause.is_some() {
+        return Err("generic parameters and where-clauses need a manual rewrite");
+    }
+    let target = match node
+        .trait_
+        .as_ref()
+        .and_then(|(_, path, _)| path.segments.last())
+    {
+        Some(segment) => match &segment.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => &args.args[0],
+            _ => return Err("`Into` has no single type argument"),
+        },
+        None => return Err("not a trait impl"),
+    };
+    let [ImplItem::Fn(method)] = node.items.as_slice() else {
+        return Err("the impl has items besides `fn into`");
+    };
+    let mut inputs = method.sig.inputs.iter();
+    let (Some(FnArg::Receiver(receiver)), None) = (inputs.next(), inputs.next()) else {
+        return Err("`into` does not take `self` by value");
+    };
+    if receiver.reference.is_some() || receiver.colon_token.is_some() {
+        return Err("`into` does not take `self` by value");
+    }
+    let ReturnType::Type(_, output) = &method.sig.output else {
+        return Err("`into` has no return type");
+    };
+
+    let text = |span: Span| source_text(ctx, span).ok_or("source text is unavailable");
+    let source_type = text(node.self_ty.span())?;
+    let target_type = text(target.span())?;
+    let return_type = text(output.span())?;
+
+    let mut self_tokens = Vec::new();
+    collect_self_tokens(method.block.to_token_stream(), &mut self_tokens);
+    let simple_source = matches!(
+        node.self_ty.as_ref(),
+        syn::Type::Path(path) if path.qself.is_none()
+            && path.path.segments.iter().all(|s| s.arguments.is_empty())
+    );
+    if !simple_source && self_tokens.iter().any(|(_, is_type)| *is_type) {
+        return Err("the body uses `Self` and the source type is not a plain path");
+    }
+
+    let header = format!("impl From<{source_type}> for {target_type}");
+    let mutability = if receiver.mutability.is_some() {
+        "mut "
+    } else {
+        ""
+    };
+    let mut edits = vec![
+        (node.impl_token.span, node.self_ty.span(), header.clone()),
+        (
+            method.sig.fn_token.span,
+            output.span(),
+            format!("fn from({mutability}value: {source_type}) -> {return_type}"),
+        ),
+    ];
+    for (span, is_type) in self_tokens {
+        let new_text = if is_type { source_type } else { "value" };
+        edits.push((span, span, new_text.to_string()));
+    }
+
+    let start = offset(ctx, node.impl_token.span.start());
+    let end = offset(ctx, node.brace_token.span.close().end());
+    let mut new_text = ctx
+        .content
+        .get(start..end)
+        .ok_or("source text is unavailable")?
+        .to_string();
+    edits.sort_by_key(|(from, _, _)| std::cmp::Reverse(offset(ctx, from.start())));
+    for (from, to, replacement) in edits {
+        let range = offset(ctx, from.start()) - start..offset(ctx, to.end()) - start;
+        if new_text.get(range.clone()).is_none() {
+            return Err("source text is unavailable");
+        }
+        new_text.replace_range(range, &replacement);
+    }
+
+    let impl_start = node.impl_token.span.start();
+    let location = Location::new(
+        ctx.relative_path.clone(),
+        impl_start.line,
+        impl_start.column + 1,
+    )
+    .with_span(start, end - start);
+    Ok(FromRewrite {
+        source_type,
+        header,
+        replacement: Replacement::new(location, new_text),
+    })
+}
+
+/// Collects `self` (`false`) and `Self` (`true`) tokens, including inside
+/// macro invocations.
+fn collect_self_tokens(tokens: TokenStream, out: &mut Vec<(Span, bool)>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) if ident == "self" => out.push((ident.span(), false)),
+            TokenTree::Ident(ident) if ident == "Self" => out.push((ident.span(), true)),
+            TokenTree::Group(group) => collect_self_tokens(group.stream(), out),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the source text covered by `span`.
+fn source_text<'a>(ctx: &'a FileContext, span: Span) -> Option<&'a str> {
+    ctx.content
+        .get(offset(ctx, span.start())..offset(ctx, span.end()))
+        .filter(|text| !text.is_empty())
+}
+
+fn offset(ctx: &FileContext, position: LineColumn) -> usize {
+    ctx.offset_for(position.line, position.column + 1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,13 +357,7 @@ mod tests {
 
     fn check_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         PreferFromOverInto::new().check(&ctx, &ast)
     }
 
@@ -292,6 +451,123 @@ impl Into<i32> for Type2 {
         assert_eq!(violations.len(), 2);
     }
 
+    /// Applies the violation's fix to `code`.
+    fn apply_fix(code: &str, violation: &Violation) -> String {
+        let replacement = violation
+            .suggestion
+            .as_ref()
+            .and_then(|s| s.replacement.as_ref())
+            .expect("violation has a fix");
+        let start = replacement.location.offset;
+        let end = start + replacement.location.length;
+        format!("{}{}{}", &code[..start], replacement.new_text, &code[end..])
+    }
+
+    #[test]
+    fn test_fix_rewrites_into_as_from() {
+        let code = r#"
+struct Meters(f64);
+
+/// Converts to a raw value.
+impl Into<f64> for Meters {
+    #[inline]
+    fn into(self) -> f64 {
+        // Strip the unit.
+        let Self(raw) = self;
+        assert!(self.0 >= 0.0, "{}", self.0);
+        raw
+    }
+}
+"#;
+        let violations = check_code(code);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0]
+            .message
+            .contains("`impl From<Meters> for f64`"));
+
+        let fixed = apply_fix(code, &violations[0]);
+        assert_eq!(
+            fixed,
+            r#"
+struct Meters(f64);
+
+/// Converts to a raw value.
+impl From<Meters> for f64 {
+    #[inline]
+    fn from(value: Meters) -> f64 {
+        // Strip the unit.
+        let Meters(raw) = value;
+        assert!(value.0 >= 0.0, "{}", value.0);
+        raw
+    }
+}
+"#
+        );
+        syn::parse_file(&fixed).expect("fixed code parses");
+        assert!(check_code(&fixed).is_empty());
+    }
+
+    #[test]
+    fn test_fix_handles_qualified_paths_and_mut_self() {
+        let code = r"
+struct Wrapper(Vec<u8>);
+
+impl std::convert::Into<Vec<u8>> for Wrapper {
+    fn into(mut self) -> Vec<u8> {
+        self.0.push(0);
+        self.0
+    }
+}
+";
+        let violations = check_code(code);
+        let fixed = apply_fix(code, &violations[0]);
+        assert!(
+            fixed.contains("impl From<Wrapper> for Vec<u8> {"),
+            "{fixed}"
+        );
+        assert!(
+            fixed.contains("fn from(mut value: Wrapper) -> Vec<u8> {"),
+            "{fixed}"
+        );
+        assert!(fixed.contains("value.0.push(0);"), "{fixed}");
+    }
+
+    #[test]
+    fn test_generic_impls_are_not_rewritten() {
+        let violations = check_code(
+            r"
+struct Wrapper<T>(T);
+
+impl<T> Into<Option<T>> for Wrapper<T> where T: Clone {
+    fn into(self) -> Option<T> {
+        Some(self.0)
+    }
+}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("`Wrapper<T>`"));
+        let suggestion = violations[0].suggestion.as_ref().expect("suggestion");
+        assert!(suggestion.replacement.is_none());
+        assert!(suggestion.message.contains("generic parameters"));
+    }
+
+    #[test]
+    fn test_self_in_body_with_generic_source_is_not_rewritten() {
+        let violations = check_code(
+            r"
+impl Into<usize> for Vec<u8> {
+    fn into(self) -> usize {
+        Self::len(&self)
+    }
+}
+",
+        );
+        let suggestion = violations[0].suggestion.as_ref().expect("suggestion");
+        assert!(suggestion.replacement.is_none());
+        assert!(suggestion.message.contains("uses `Self`"));
+    }
+
     #[test]
     fn test_allows_with_reason() {
         let violations = check_code(
@@ -44,13 +44,7 @@ mod tests {
     fn check_code(code: &str) -> Vec<arch_lint_core::Violation> {
         let rule = new_prefer_utoipa();
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         rule.check(&ctx, &ast)
     }
 
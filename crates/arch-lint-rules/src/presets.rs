@@ -1,10 +1,14 @@
 //! Rule presets for common configurations.
 
 use crate::{
-    HandlerComplexity, NoErrorSwallowing, NoSilentResultDrop, NoSyncIo, NoUnwrapExpect,
-    RequireThiserror, RequireTracing, TracingEnvInit,
+    AsyncFnWithoutAwait, AsyncTraitSendCheck, HandlerComplexity, MaxFnParams, NoAnyhowInPublicApi,
+    NoBlockingInIteratorClosure, NoCatchUnwindInLib, NoErrorSwallowing, NoLeakyReexport,
+    NoLockUnwrap, NoLogAndReturnError, NoPanicInLib, NoSilentResultDrop, NoSyncIo, NoUnwrapExpect,
+    OrphanModules, PreferFromOverInto, RequireAllowReason, RequireCfgTestOnTestMods,
+    RequireConstructor, RequireDocComments, RequireModuleDocs, RequireThiserror, RequireTracing,
+    TracingEnvInit, TrackedTodos,
 };
-use arch_lint_core::RuleBox;
+use arch_lint_core::{Config, ConfigError, PresetConfig, ProjectRuleBox, RuleBox, RuleRegistry};
 
 /// Preset configurations for arch-lint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,6 +22,41 @@ pub enum Preset {
 }
 
 impl Preset {
+    /// All presets, in documentation order.
+    pub const ALL: [Self; 3] = [Self::Recommended, Self::Strict, Self::Minimal];
+
+    /// Returns the preset name as used in config files (e.g., `"recommended"`).
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Recommended => "recommended",
+            Self::Strict => "strict",
+            Self::Minimal => "minimal",
+        }
+    }
+
+    /// Looks up a preset by its config name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    /// Returns a one-line description of the preset.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Recommended => "Sensible defaults (default)",
+            Self::Strict => "Stricter settings plus handler complexity",
+            Self::Minimal => "AL001 only, for gradual adoption",
+        }
+    }
+
+    /// Returns `true` if the preset includes a rule with the given code.
+    #[must_use]
+    pub fn contains(self, code: &str) -> bool {
+        self.registry().by_code(code).is_some()
+    }
+
     /// Returns the rules for this preset.
     #[must_use]
     pub fn rules(self) -> Vec<RuleBox> {
@@ -27,13 +66,141 @@ impl Preset {
             Self::Minimal => minimal_rules(),
         }
     }
+
+    /// Returns the configuration this preset implies, with an entry for
+    /// each of its rules at the severity the preset gives it.
+    ///
+    /// See [`Config::default_for_preset`].
+    #[must_use]
+    pub fn default_config(self) -> Config {
+        Config::default_for_preset(self.name(), &self.rules())
+    }
+
+    /// Returns the rules for this preset as a [`RuleRegistry`].
+    #[must_use]
+    pub fn registry(self) -> RuleRegistry {
+        self.rules().into()
+    }
+}
+
+/// Resolves a preset name to its rules: a built-in [`Preset`], or a custom
+/// preset defined under `[presets.<name>]` in `config`.
+///
+/// A custom preset starts from its `rules` list, or from the rules of the
+/// preset it `extends` (keeping that preset's rule settings); `enable` then
+/// adds rules with default settings and `disable` removes rules.
+///
+/// # Errors
+///
+/// Returns [`ConfigError::InvalidValue`] for an unknown preset or rule
+/// name, a custom preset that redefines a built-in one, sets both or
+/// neither of `rules` and `extends`, or `extends` itself in a cycle.
+pub fn preset_rules(name: &str, config: &Config) -> Result<Vec<RuleBox>, ConfigError> {
+    if let Some(builtin) = Preset::ALL
+        .into_iter()
+        .find(|preset| config.presets.contains_key(preset.name()))
+    {
+        return Err(ConfigError::InvalidValue {
+            key: format!("presets.{}", builtin.name()),
+            message: "a custom preset cannot redefine a built-in preset".to_string(),
+        });
+    }
+    resolve_preset(name, "preset", config, &mut Vec::new())
+}
+
+fn resolve_preset(
+    name: &str,
+    key: &str,
+    config: &Config,
+    chain: &mut Vec<String>,
+) -> Result<Vec<RuleBox>, ConfigError> {
+    if let Some(preset) = Preset::from_name(name) {
+        return Ok(preset.rules());
+    }
+    let Some(custom) = config.presets.get(name) else {
+        let mut valid: Vec<&str> = Preset::ALL.iter().map(|p| p.name()).collect();
+        let mut custom: Vec<&str> = config.presets.keys().map(String::as_str).collect();
+        custom.sort_unstable();
+        valid.extend(custom);
+        return Err(ConfigError::InvalidValue {
+            key: key.to_string(),
+            message: format!(
+                "unknown preset `{name}`. Valid presets: {}",
+                valid.join(", ")
+            ),
+        });
+    };
+    if chain.iter().any(|seen| seen == name) {
+        chain.push(name.to_string());
+        return Err(ConfigError::InvalidValue {
+            key: format!("presets.{}.extends", chain[0]),
+            message: format!(
+                "presets extend each other in a cycle: {}",
+                chain.join(" -> ")
+            ),
+        });
+    }
+    chain.push(name.to_string());
+    let rules = custom_preset_rules(name, custom, config, chain)?;
+    chain.pop();
+    Ok(rules)
+}
+
+fn custom_preset_rules(
+    name: &str,
+    custom: &PresetConfig,
+    config: &Config,
+    chain: &mut Vec<String>,
+) -> Result<Vec<RuleBox>, ConfigError> {
+    let key = |field: &str| format!("presets.{name}.{field}");
+    let unknown_rule = |field: &str, rule: &str| ConfigError::InvalidValue {
+        key: key(field),
+        message: format!("unknown rule `{rule}`"),
+    };
+    let lookup = |field: &str, rule: &str| {
+        all_rules()
+            .into_iter()
+            .find(|r| r.name() == rule || r.code() == rule)
+            .ok_or_else(|| unknown_rule(field, rule))
+    };
+
+    let mut rules = match (&custom.rules, &custom.extends) {
+        (Some(list), None) => list
+            .iter()
+            .map(|rule| lookup("rules", rule))
+            .collect::<Result<Vec<_>, _>>()?,
+        (None, Some(base)) => resolve_preset(base, &key("extends"), config, chain)?,
+        (Some(_), Some(_)) => {
+            return Err(ConfigError::InvalidValue {
+                key: key("rules"),
+                message: "set either `rules` or `extends`, not both".to_string(),
+            })
+        }
+        (None, None) => {
+            return Err(ConfigError::InvalidValue {
+                key: format!("presets.{name}"),
+                message: "a custom preset needs `rules` or `extends`".to_string(),
+            })
+        }
+    };
+    for rule in &custom.enable {
+        let rule = lookup("enable", rule)?;
+        if !rules.iter().any(|r| r.name() == rule.name()) {
+            rules.push(rule);
+        }
+    }
+    for rule in &custom.disable {
+        let rule = lookup("disable", rule)?;
+        rules.retain(|r| r.name() != rule.name());
+    }
+    Ok(rules)
 }
 
 /// Returns the recommended set of rules.
 ///
 /// Includes:
 /// - `no-unwrap-expect` (AL001) - Forbids `.unwrap()/.expect()`
-/// - `no-sync-io` (AL002) - Forbids blocking I/O
+/// - `no-sync-io` (AL002) - Forbids blocking I/O inside async code
 /// - `no-error-swallowing` (AL003) - Forbids silent error handling
 /// - `no-silent-result-drop` (AL013) - Forbids silently discarding Result errors
 /// - `require-thiserror` (AL005) - Requires thiserror for error types
@@ -43,7 +210,7 @@ impl Preset {
 pub fn recommended_rules() -> Vec<RuleBox> {
     vec![
         Box::new(NoUnwrapExpect::new()),
-        Box::new(NoSyncIo::new()),
+        Box::new(NoSyncIo::new().only_in_async(true)),
         Box::new(NoErrorSwallowing::new()),
         Box::new(NoSilentResultDrop::new()),
         Box::new(RequireThiserror::new()),
@@ -56,7 +223,9 @@ pub fn recommended_rules() -> Vec<RuleBox> {
 ///
 /// Includes all recommended rules plus:
 /// - Stricter `no-unwrap-expect` (no exceptions in tests)
+/// - Stricter `no-sync-io` (blocking I/O reported outside async code too)
 /// - Stricter `no-silent-result-drop` (no exceptions in tests, `.ok()` forbidden)
+/// - Stricter `require-thiserror` (manual `impl std::error::Error` not accepted)
 /// - `handler-complexity` (AL004) - Limits handler complexity
 #[must_use]
 pub fn strict_rules() -> Vec<RuleBox> {
@@ -73,7 +242,7 @@ pub fn strict_rules() -> Vec<RuleBox> {
                 .allow_in_tests(false)
                 .allow_ok(false),
         ),
-        Box::new(RequireThiserror::new()),
+        Box::new(RequireThiserror::new().allow_manual_impl(false)),
         Box::new(RequireTracing::new()),
         Box::new(TracingEnvInit::new()),
         Box::new(HandlerComplexity::new()),
@@ -89,6 +258,22 @@ pub fn minimal_rules() -> Vec<RuleBox> {
     vec![Box::new(NoUnwrapExpect::new().allow_expect(true))]
 }
 
+/// Returns the built-in project rules.
+///
+/// They run in every preset; opt out of one with
+/// `[rules.<name>] enabled = false`.
+/// - `orphan-modules` (AL040) - Flags files no `mod` declaration reaches
+#[must_use]
+pub fn project_rules() -> Vec<ProjectRuleBox> {
+    vec![Box::new(OrphanModules::new())]
+}
+
+/// Returns every available rule, with default settings, as a [`RuleRegistry`].
+#[must_use]
+pub fn rule_registry() -> RuleRegistry {
+    all_rules().into()
+}
+
 /// Returns all available rules.
 #[must_use]
 pub fn all_rules() -> Vec<RuleBox> {
@@ -101,12 +286,30 @@ pub fn all_rules() -> Vec<RuleBox> {
         Box::new(RequireThiserror::new()),
         Box::new(RequireTracing::new()),
         Box::new(TracingEnvInit::new()),
+        Box::new(AsyncTraitSendCheck::new()),
+        Box::new(PreferFromOverInto::new()),
+        Box::new(NoPanicInLib::new()),
+        Box::new(RequireDocComments::new()),
+        Box::new(NoAnyhowInPublicApi::new()),
+        Box::new(AsyncFnWithoutAwait::new()),
+        Box::new(RequireAllowReason::new()),
+        Box::new(RequireModuleDocs::new()),
+        Box::new(RequireConstructor::new()),
+        Box::new(MaxFnParams::new()),
+        Box::new(NoLeakyReexport::new()),
+        Box::new(NoLockUnwrap::new()),
+        Box::new(NoBlockingInIteratorClosure::new()),
+        Box::new(TrackedTodos::new()),
+        Box::new(RequireCfgTestOnTestMods::new()),
+        Box::new(NoCatchUnwindInLib::new()),
+        Box::new(NoLogAndReturnError::new()),
     ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arch_lint_core::Rule;
 
     #[test]
     fn test_preset_rules() {
@@ -114,4 +317,172 @@ mod tests {
         assert!(!Preset::Strict.rules().is_empty());
         assert!(!Preset::Minimal.rules().is_empty());
     }
+
+    #[test]
+    fn test_preset_names_roundtrip() {
+        for preset in Preset::ALL {
+            assert_eq!(Preset::from_name(preset.name()), Some(preset));
+        }
+        assert_eq!(Preset::from_name("nonexistent"), None);
+    }
+
+    #[test]
+    fn test_preset_rules_are_listed_in_all_rules() {
+        let all: Vec<_> = all_rules().iter().map(|r| r.code()).collect();
+        for preset in Preset::ALL {
+            for rule in preset.rules() {
+                assert!(all.contains(&rule.code()), "{} missing", rule.code());
+            }
+        }
+        assert!(Preset::Minimal.contains("AL001"));
+        assert!(!Preset::Minimal.contains("AL002"));
+    }
+
+    #[test]
+    fn test_rule_registry_lookup() {
+        let registry = rule_registry();
+        assert_eq!(registry.len(), all_rules().len());
+        assert_eq!(
+            registry.by_name("no-unwrap-expect").map(Rule::code),
+            Some("AL001")
+        );
+        assert_eq!(
+            registry.by_code("AL028").map(Rule::name),
+            Some("async-fn-without-await")
+        );
+        assert!(Preset::Strict.registry().contains("handler-complexity"));
+        assert!(!Preset::Recommended.registry().contains("AL004"));
+    }
+
+    fn names(rules: &[RuleBox]) -> Vec<&'static str> {
+        rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    #[test]
+    fn test_preset_rules_builtin_and_custom() {
+        let config = Config::parse(
+            r#"
+[presets.team]
+extends = "strict"
+enable = ["AL036", "no-lock-unwrap"]
+disable = ["handler-complexity", "AL006"]
+
+[presets.tiny]
+rules = ["no-unwrap-expect", "AL002"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            names(&preset_rules("minimal", &config).unwrap()),
+            names(&Preset::Minimal.rules())
+        );
+        assert_eq!(
+            names(&preset_rules("tiny", &config).unwrap()),
+            ["no-unwrap-expect", "no-sync-io"]
+        );
+
+        let team = preset_rules("team", &config).unwrap();
+        let mut expected = names(&Preset::Strict.rules());
+        expected.extend(["tracked-todos", "no-lock-unwrap"]);
+        expected.retain(|name| *name != "handler-complexity" && *name != "require-tracing");
+        assert_eq!(names(&team), expected);
+    }
+
+    #[test]
+    fn test_extend_then_disable() {
+        // `disable` applies after `enable`, and to rules inherited through
+        // a chain of presets.
+        let config = Config::parse(
+            r#"
+[presets.base]
+extends = "recommended"
+enable = ["AL004"]
+
+[presets.derived]
+extends = "base"
+enable = ["AL011"]
+disable = ["AL004", "AL011", "no-sync-io"]
+"#,
+        )
+        .unwrap();
+        let base = names(&preset_rules("base", &config).unwrap());
+        assert!(base.contains(&"handler-complexity"));
+
+        let derived = names(&preset_rules("derived", &config).unwrap());
+        let mut expected = names(&Preset::Recommended.rules());
+        expected.retain(|name| *name != "no-sync-io");
+        assert_eq!(derived, expected);
+    }
+
+    #[test]
+    fn test_preset_rules_keep_inherited_settings() {
+        // Strict's `no-unwrap-expect` also checks tests; an extending preset
+        // keeps that instance, while `enable` adds default-configured rules.
+        let config = Config::parse(
+            "[presets.team]\nextends = \"strict\"\n\n[presets.loose]\nrules = [\"AL001\"]\n",
+        )
+        .unwrap();
+        let code = "#[test]\nfn t() { let _ = Some(1).unwrap(); }\n";
+        let ast = syn::parse_file(code).unwrap();
+        let ctx = arch_lint_core::FileContext::new(
+            std::path::Path::new("src/lib.rs"),
+            code,
+            std::path::Path::new(""),
+        );
+        let violations = |preset: &str| -> usize {
+            preset_rules(preset, &config)
+                .unwrap()
+                .iter()
+                .filter(|rule| rule.name() == "no-unwrap-expect")
+                .map(|rule| rule.check(&ctx, &ast).len())
+                .sum()
+        };
+        assert_eq!(violations("team"), 1);
+        assert_eq!(violations("loose"), 0);
+    }
+
+    #[test]
+    fn test_preset_rules_errors() {
+        let error = |toml: &str, name: &str| {
+            let config = Config::parse(toml).unwrap();
+            match preset_rules(name, &config) {
+                Ok(rules) => panic!("expected an error, got {:?}", names(&rules)),
+                Err(e) => e.to_string(),
+            }
+        };
+        assert!(error("", "lenient").contains("unknown preset `lenient`"));
+        assert!(error("[presets.a]\nextends = \"nope\"\n", "a").contains("`presets.a.extends`"));
+        assert!(error(
+            "[presets.a]\nenable = [\"AL999\"]\nextends = \"minimal\"\n",
+            "a"
+        )
+        .contains("unknown rule `AL999`"));
+        assert!(error("[presets.a]\nenable = [\"AL001\"]\n", "a")
+            .contains("needs `rules` or `extends`"));
+        assert!(error(
+            "[presets.a]\nextends = \"b\"\n[presets.b]\nextends = \"a\"\n",
+            "a"
+        )
+        .contains("a -> b -> a"));
+        assert!(
+            error("[presets.strict]\nextends = \"minimal\"\n", "minimal")
+                .contains("cannot redefine a built-in preset")
+        );
+    }
+
+    #[test]
+    fn test_default_config_lists_preset_rules() {
+        let config = Preset::Minimal.default_config();
+        assert_eq!(config.preset.as_deref(), Some("minimal"));
+        assert_eq!(config.rules.len(), Preset::Minimal.rules().len());
+        assert!(config.rules["no-unwrap-expect"].enabled == Some(true));
+
+        let strict = Preset::Strict.default_config();
+        for rule in Preset::Strict.rules() {
+            assert_eq!(
+                strict.rule_severity(rule.name()),
+                Some(rule.default_severity())
+            );
+        }
+    }
 }
@@ -0,0 +1,411 @@
+//! Rule to require a justification on `#[allow(...)]` lint suppressions.
+//!
+//! # Rationale
+//!
+//! `#[allow(clippy::...)]` and `#[allow(dead_code)]` silence the compiler
+//! without saying why. Months later nobody knows whether the suppression is
+//! still needed, so it stays forever. Requiring a reason makes every
+//! suppression reviewable.
+//!
+//! # Detected Patterns
+//!
+//! - `#[allow(...)]` and `#![allow(...)]` without a `reason = "..."` argument
+//!   and without a `// reason: ...` comment on the lines directly above (or
+//!   trailing the attribute)
+//!
+//! Lints listed in `exempt_lints` (e.g. `dead_code`) never need a reason; an
+//! attribute is reported only for its non-exempt lints.
+//!
+//! # Good Patterns
+//!
+//! ```ignore
+//! // reason: the index is checked against `len` above
+//! #[allow(clippy::indexing_slicing)]
+//! fn get(v: &[u8], i: usize) -> u8 { v[i] }
+//!
+//! #[allow(clippy::too_many_arguments, reason = "mirrors the C API")]
+//! fn ffi_call(/* ... */) {}
+//! ```
+
+use arch_lint_core::utils::{path_to_string, ContextTracker};
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{Attribute, ItemFn, ItemImpl, ItemMod, Meta, Token};
+
+/// Rule code for require-allow-reason.
+pub const CODE: &str = "AL029";
+
+/// Rule name for require-allow-reason.
+pub const NAME: &str = "require-allow-reason";
+
+/// Requires a reason on `#[allow(...)]` attributes.
+#[derive(Debug, Clone)]
+pub struct RequireAllowReason {
+    /// Custom severity.
+    pub severity: Severity,
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+    /// Lints that may be allowed without a reason (e.g. `dead_code`).
+    pub exempt_lints: Vec<String>,
+}
+
+impl Default for RequireAllowReason {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequireAllowReason {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+            allow_in_tests: true,
+            exempt_lints: Vec::new(),
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Exempts a lint (e.g. `clippy::module_name_repetitions`) from the
+    /// reason requirement.
+    #[must_use]
+    pub fn exempt_lint(mut self, lint: impl Into<String>) -> Self {
+        self.exempt_lints.push(lint.into());
+        self
+    }
+
+    fn is_exempt(&self, lint: &str) -> bool {
+        self.exempt_lints.iter().any(|l| l == lint)
+    }
+}
+
+impl Rule for RequireAllowReason {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires a reason on #[allow(...)] lint suppressions"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A lint suppression without a justification cannot be reviewed and tends to \
+         outlive its purpose. Add `reason = \"...\"` to the attribute or a `// reason: ...` \
+         comment above it."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_in_tests", "exempt_lints"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: "allow_in_tests".to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        if let Some(value) = config.options.get("exempt_lints") {
+            self.exempt_lints =
+                config
+                    .get_option("exempt_lints")
+                    .ok_or_else(|| ConfigError::InvalidOption {
+                        rule: NAME.to_string(),
+                        key: "exempt_lints".to_string(),
+                        message: format!("expected an array of lint names, got {value}"),
+                    })?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = AllowVisitor {
+            ctx,
+            rule: self,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+struct AllowVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a RequireAllowReason,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl AllowVisitor<'_> {
+    fn check_attribute(&mut self, attr: &Attribute) {
+        if !attr.path().is_ident("allow") || self.context.is_skipped(self.rule.allow_in_tests) {
+            return;
+        }
+        let Ok(args) = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) else {
+            return;
+        };
+
+        let mut lints = Vec::new();
+        for arg in &args {
+            match arg {
+                Meta::NameValue(nv) if nv.path.is_ident("reason") => return,
+                Meta::Path(path) => lints.push(path_to_string(path)),
+                _ => {}
+            }
+        }
+        lints.retain(|lint| !self.rule.is_exempt(lint));
+        if lints.is_empty() {
+            return;
+        }
+
+        let start = attr.span().start();
+        if has_reason_comment(self.ctx.content, start.line)
+            || self
+                .ctx
+                .check_allow_for_rule(start.line, NAME, CODE)
+                .is_allowed()
+        {
+            return;
+        }
+
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!("`#[allow({})]` has no reason", lints.join(", ")),
+            )
+            .with_suggestion(Suggestion::new(
+                "Add `reason = \"...\"` to the attribute or a `// reason: ...` comment above it",
+            )),
+        );
+    }
+}
+
+/// Returns `true` if the attribute starting on `line` (1-based) carries a
+/// trailing `// reason:` comment, or one in the comment and attribute lines
+/// directly above it.
+fn has_reason_comment(content: &str, line: usize) -> bool {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines
+        .get(line.wrapping_sub(1))
+        .and_then(|l| l.split_once("//"))
+        .is_some_and(|(_, comment)| is_reason(comment))
+    {
+        return true;
+    }
+
+    lines[..line.saturating_sub(1).min(lines.len())]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with("//") || l.starts_with("#["))
+        .any(|l| l.strip_prefix("//").is_some_and(is_reason))
+}
+
+fn is_reason(comment: &str) -> bool {
+    comment
+        .trim_start_matches('/')
+        .trim_start()
+        .get(..7)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("reason:"))
+}
+
+impl<'ast> Visit<'ast> for AllowVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_attribute(&mut self, node: &'ast Attribute) {
+        self.check_attribute(node);
+        syn::visit::visit_attribute(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(rule: &RequireAllowReason, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        check_with(&RequireAllowReason::new(), code)
+    }
+
+    #[test]
+    fn test_detects_allow_without_reason() {
+        let violations = check_code(
+            r"
+#![allow(dead_code)]
+
+#[allow(clippy::too_many_arguments)]
+fn f() {}
+
+struct S {
+    #[allow(unused)]
+    field: u8,
+}
+",
+        );
+        assert_eq!(violations.len(), 3);
+        assert_eq!(violations[0].code, CODE);
+        assert!(violations[0].message.contains("dead_code"));
+        assert!(violations[1]
+            .message
+            .contains("#[allow(clippy::too_many_arguments)]"));
+        assert_eq!(violations[1].location.line, 4);
+        assert_eq!(violations[2].location.line, 8);
+    }
+
+    #[test]
+    fn test_accepts_reason_argument() {
+        let violations = check_code(
+            r#"
+#[allow(clippy::too_many_arguments, reason = "mirrors the C API")]
+fn f() {}
+"#,
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_accepts_reason_comment() {
+        let violations = check_code(
+            r"
+// reason: indices are checked above
+#[inline]
+#[allow(clippy::indexing_slicing)]
+fn f() {}
+
+#[allow(dead_code)] // Reason: kept for the FFI layout
+struct S;
+",
+        );
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_unrelated_comment_is_not_a_reason() {
+        let violations = check_code(
+            r"
+// reason: applies to g
+
+fn g() {}
+// just a note
+#[allow(dead_code)]
+fn f() {}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].location.line, 6);
+    }
+
+    #[test]
+    fn test_exempt_lints() {
+        let rule = RequireAllowReason::new().exempt_lint("dead_code");
+        let violations = check_with(
+            &rule,
+            r"
+#[allow(dead_code)]
+fn f() {}
+
+#[allow(dead_code, clippy::similar_names)]
+fn g() {}
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0]
+            .message
+            .contains("#[allow(clippy::similar_names)]"));
+    }
+
+    #[test]
+    fn test_ignores_other_attributes_and_test_code() {
+        let violations = check_code(
+            r"
+#[derive(Debug)]
+#[deny(warnings)]
+struct S;
+
+#[cfg(test)]
+mod tests {
+    #[allow(clippy::unwrap_used)]
+    fn helper() {}
+}
+",
+        );
+        assert!(violations.is_empty());
+    }
+
+    fn configured(toml: &str) -> Result<RequireAllowReason, ConfigError> {
+        let config = arch_lint_core::Config::parse(toml).expect("parse config");
+        let mut rule = RequireAllowReason::new();
+        rule.configure(&config.rules[NAME])?;
+        Ok(rule)
+    }
+
+    #[test]
+    fn test_configure_exempt_lints() {
+        let rule = configured("[rules.require-allow-reason]\nexempt_lints = [\"dead_code\"]\n")
+            .expect("valid options");
+        assert_eq!(rule.exempt_lints, ["dead_code"]);
+
+        let err = configured("[rules.require-allow-reason]\nexempt_lints = \"dead_code\"\n")
+            .expect_err("not an array");
+        assert!(err.to_string().contains("exempt_lints"), "{err}");
+    }
+}
@@ -0,0 +1,320 @@
+//! Rule to require `#[cfg(test)]` on test modules.
+//!
+//! # Rationale
+//!
+//! A `mod tests` without `#[cfg(test)]` is compiled into every build: its
+//! helpers, fixtures and `#[test]` functions end up in release binaries, and
+//! test-only dependencies leak into the normal dependency graph. The
+//! compiler only warns about the resulting dead code, if at all.
+//!
+//! # Detected Patterns
+//!
+//! - `mod tests { ... }` / `mod test { ... }` without `#[cfg(test)]`
+//! - any inline module containing `#[test]` (or `#[tokio::test]`, ...)
+//!   functions without `#[cfg(test)]`
+//! - `mod tests;` declarations without `#[cfg(test)]`
+//!
+//! Modules nested in a gated module are not reported, and only the
+//! outermost ungated test module is. Integration tests under `tests/` are
+//! skipped.
+
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{FileContext, Location, Replacement, Rule, Severity, Suggestion, Violation};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{Attribute, Item, ItemMod};
+
+/// Rule code for require-cfg-test-on-test-mods.
+pub const CODE: &str = "AL037";
+
+/// Rule name for require-cfg-test-on-test-mods.
+pub const NAME: &str = "require-cfg-test-on-test-mods";
+
+/// Module names treated as test modules regardless of their contents.
+const TEST_MOD_NAMES: &[&str] = &["tests", "test"];
+
+/// Requires `#[cfg(test)]` on modules that hold tests.
+#[derive(Debug, Clone)]
+pub struct RequireCfgTestOnTestMods {
+    /// Custom severity.
+    pub severity: Severity,
+}
+
+impl Default for RequireCfgTestOnTestMods {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequireCfgTestOnTestMods {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Rule for RequireCfgTestOnTestMods {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires #[cfg(test)] on test modules"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A test module without `#[cfg(test)]` is compiled into release builds, together \
+         with its helpers and test-only dependencies. Gate every `mod tests` (and any \
+         module holding `#[test]` functions) with `#[cfg(test)]`."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        !ctx.is_test && ctx.content.contains("mod ")
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut visitor = TestModVisitor {
+            ctx,
+            rule: self,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+        let saved = visitor.context.enter_mod(&ast.attrs);
+        visitor.visit_file(ast);
+        visitor.context.restore(saved);
+        visitor.violations
+    }
+}
+
+struct TestModVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a RequireCfgTestOnTestMods,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl TestModVisitor<'_> {
+    fn report(&mut self, node: &ItemMod, reason: &str) {
+        let start = node.span().start();
+        let mod_line = node.mod_token.span.start().line;
+        if self
+            .ctx
+            .check_allow_for_rule(mod_line, NAME, CODE)
+            .is_allowed()
+        {
+            return;
+        }
+
+        let ident = node.ident.span().start();
+        let location = Location::new(self.ctx.relative_path.clone(), ident.line, ident.column + 1);
+        self.violations.push(
+            Violation::new(
+                CODE,
+                NAME,
+                self.rule.severity,
+                location,
+                format!(
+                    "Module `{}` {reason} but is not gated with #[cfg(test)]",
+                    node.ident
+                ),
+            )
+            .with_suggestion(Suggestion::with_fix(
+                "Add `#[cfg(test)]` so the module is only compiled for tests",
+                self.cfg_test_insertion(start.line, start.column),
+            )),
+        );
+    }
+
+    /// Inserts `#[cfg(test)]` on its own line before the item starting at
+    /// `line`/`column`, keeping the item's indentation.
+    fn cfg_test_insertion(&self, line: usize, column: usize) -> Replacement {
+        let indent: String = self
+            .ctx
+            .content
+            .lines()
+            .nth(line - 1)
+            .unwrap_or_default()
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        let offset = self.ctx.offset_for(line, column + 1);
+        let location =
+            Location::new(self.ctx.relative_path.clone(), line, column + 1).with_span(offset, 0);
+        Replacement::new(location, format!("#[cfg(test)]\n{indent}"))
+    }
+}
+
+impl<'ast> Visit<'ast> for TestModVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        if self.context.is_skipped(true) {
+            self.context.restore(saved);
+            return;
+        }
+
+        let named_test = TEST_MOD_NAMES.iter().any(|name| node.ident == name);
+        let has_tests = node
+            .content
+            .as_ref()
+            .is_some_and(|(_, items)| items.iter().any(is_test_fn));
+        if named_test || has_tests {
+            let reason = if has_tests {
+                "contains #[test] functions"
+            } else {
+                "is a test module"
+            };
+            self.report(node, reason);
+        } else {
+            syn::visit::visit_item_mod(self, node);
+        }
+        self.context.restore(saved);
+    }
+}
+
+/// Returns `true` for a function with `#[test]` or a `#[<runtime>::test]`
+/// attribute.
+fn is_test_fn(item: &Item) -> bool {
+    let Item::Fn(func) = item else {
+        return false;
+    };
+    func.attrs.iter().any(is_test_attr)
+}
+
+fn is_test_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "test")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        RequireCfgTestOnTestMods::new().check(&ctx, &ast)
+    }
+
+    fn apply_fix(code: &str, violation: &Violation) -> String {
+        let replacement = violation
+            .suggestion
+            .as_ref()
+            .and_then(|s| s.replacement.as_ref())
+            .expect("has a fix");
+        let mut fixed = code.to_string();
+        fixed.insert_str(replacement.location.offset, &replacement.new_text);
+        fixed
+    }
+
+    #[test]
+    fn test_flags_ungated_test_modules() {
+        let code = r"
+pub fn add(a: u8, b: u8) -> u8 { a + b }
+
+mod tests {
+    use super::*;
+}
+
+mod checks {
+    /// Adds.
+    #[tokio::test]
+    async fn adds() {}
+}
+
+mod tests_support;
+";
+        let violations = check_code(code);
+        let messages: Vec<_> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Module `tests` is a test module but is not gated with #[cfg(test)]",
+                "Module `checks` contains #[test] functions but is not gated with #[cfg(test)]",
+            ]
+        );
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(violations[0].location.line, 4);
+    }
+
+    #[test]
+    fn test_fix_inserts_cfg_test() {
+        let code = "pub mod outer {\n    /// Unit tests.\n    mod tests {\n        #[test]\n        fn t() {}\n    }\n}\n";
+        let violations = check_code(code);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            apply_fix(code, &violations[0]),
+            "pub mod outer {\n    #[cfg(test)]\n    /// Unit tests.\n    mod tests {\n        #[test]\n        fn t() {}\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_ignores_gated_and_allowed_modules() {
+        let code = r#"
+#[cfg(test)]
+mod tests {
+    mod nested {
+        #[test]
+        fn t() {}
+    }
+}
+
+#[cfg(all(test, feature = "slow"))]
+mod slow_tests {
+    #[test]
+    fn t() {}
+}
+
+#[cfg(test)]
+mod test;
+
+#[arch_lint::allow(require_cfg_test_on_test_mods, reason = "doc examples")]
+mod tests_in_docs {
+    #[test]
+    fn t() {}
+}
+
+mod helpers {
+    fn not_a_test() {}
+}
+"#;
+        assert!(check_code(code).is_empty(), "{:?}", check_code(code));
+    }
+
+    #[test]
+    fn test_reports_only_outermost_module() {
+        let code = r"
+mod tests {
+    mod inner {
+        #[test]
+        fn t() {}
+    }
+}
+";
+        assert_eq!(check_code(code).len(), 1);
+    }
+}
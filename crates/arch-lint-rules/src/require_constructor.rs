@@ -0,0 +1,500 @@
+//! Rule to forbid struct literals of types that must go through a constructor.
+//!
+//! # Rationale
+//!
+//! A type whose constructor enforces invariants (validation, normalization,
+//! derived fields) loses them when code builds it with a struct literal.
+//! Public fields or a module that re-exports the type make this easy to do
+//! by accident.
+//!
+//! # Detected Patterns
+//!
+//! - `Config { .. }` struct literals of a configured type outside the module
+//!   that defines it
+//!
+//! A literal is allowed in the defining module and its submodules when
+//! `module` is configured, and otherwise in any file that defines a struct
+//! of that name. Types are matched by the last segment of the literal's path.
+//!
+//! # Good Patterns
+//!
+//! ```ignore
+//! let config = Config::new(root, preset)?;
+//! let config = Config::builder().root(root).build()?;
+//! ```
+
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{ExprStruct, ItemFn, ItemImpl, ItemMod, ItemStruct};
+
+/// Rule code for require-constructor.
+pub const CODE: &str = "AL031";
+
+/// Rule name for require-constructor.
+pub const NAME: &str = "require-constructor";
+
+/// A type that must be built through a designated function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstructorRequirement {
+    /// Type name (e.g. `"Config"`).
+    pub name: String,
+    /// Function to use instead (e.g. `"Config::new"`); defaults to
+    /// `<name>::new`.
+    pub constructor: Option<String>,
+    /// Module path defining the type (e.g. `"crate::config"`). Struct
+    /// literals are allowed in it and its submodules.
+    pub module: Option<String>,
+    /// Custom violation message.
+    pub message: Option<String>,
+    /// Design document reference attached to violations.
+    pub doc_ref: Option<String>,
+}
+
+impl ConstructorRequirement {
+    /// Creates a requirement for the named type.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            constructor: None,
+            module: None,
+            message: None,
+            doc_ref: None,
+        }
+    }
+
+    /// Sets the function to use instead of a struct literal.
+    #[must_use]
+    pub fn constructor(mut self, constructor: impl Into<String>) -> Self {
+        self.constructor = Some(constructor.into());
+        self
+    }
+
+    /// Sets the module that defines the type.
+    #[must_use]
+    pub fn module(mut self, module: impl Into<String>) -> Self {
+        self.module = Some(module.into());
+        self
+    }
+
+    /// Sets a custom violation message.
+    #[must_use]
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Sets the design document reference attached to violations.
+    #[must_use]
+    pub fn doc_ref(mut self, doc_ref: impl Into<String>) -> Self {
+        self.doc_ref = Some(doc_ref.into());
+        self
+    }
+
+    fn constructor_name(&self) -> String {
+        self.constructor
+            .clone()
+            .unwrap_or_else(|| format!("{}::new", self.name))
+    }
+
+    /// Returns `true` if `module_path` is the defining module or one of
+    /// its submodules.
+    fn allows_module(&self, module_path: &[String]) -> bool {
+        let Some(module) = &self.module else {
+            return false;
+        };
+        let wanted: Vec<&str> = module
+            .trim_start_matches("crate::")
+            .split("::")
+            .filter(|s| !s.is_empty() && *s != "crate")
+            .collect();
+        let actual = crate_relative(module_path);
+        actual.len() >= wanted.len() && actual.iter().zip(&wanted).all(|(a, w)| a == w)
+    }
+}
+
+/// Strips the `crate` and `src` prefix of [`FileContext::module_path`].
+fn crate_relative(module_path: &[String]) -> Vec<&str> {
+    let mut parts: Vec<&str> = module_path.iter().map(String::as_str).collect();
+    if parts.first() == Some(&"crate") {
+        parts.remove(0);
+    }
+    if parts.first() == Some(&"src") {
+        parts.remove(0);
+    }
+    parts
+}
+
+/// Forbids struct literals of configured types outside their module.
+#[derive(Debug, Clone)]
+pub struct RequireConstructor {
+    /// Custom severity.
+    pub severity: Severity,
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+    /// Types that must be built through a constructor.
+    pub types: Vec<ConstructorRequirement>,
+}
+
+impl Default for RequireConstructor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequireConstructor {
+    /// Creates a new rule with no types configured.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+            allow_in_tests: true,
+            types: Vec::new(),
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Adds a type that must be built through a constructor.
+    #[must_use]
+    pub fn require(mut self, requirement: ConstructorRequirement) -> Self {
+        self.types.push(requirement);
+        self
+    }
+}
+
+/// Parses one `types` entry: a type name or a table with `name` and the
+/// optional `constructor`, `module`, `message` and `doc_ref` keys.
+fn parse_requirement(value: &toml::Value) -> Result<ConstructorRequirement, String> {
+    if let Some(name) = value.as_str() {
+        return Ok(ConstructorRequirement::new(name));
+    }
+    let table = value
+        .as_table()
+        .ok_or_else(|| format!("expected a type name or table, got {value}"))?;
+    let text = |key: &str| -> Result<Option<String>, String> {
+        table
+            .get(key)
+            .map(|v| {
+                v.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("`{key}` must be a string, got {v}"))
+            })
+            .transpose()
+    };
+    let name = text("name")?.ok_or_else(|| "entry is missing `name`".to_string())?;
+    if let Some(key) = table.keys().find(|key| {
+        !matches!(
+            key.as_str(),
+            "name" | "constructor" | "module" | "message" | "doc_ref"
+        )
+    }) {
+        return Err(format!("unknown key `{key}` in entry for `{name}`"));
+    }
+    Ok(ConstructorRequirement {
+        name,
+        constructor: text("constructor")?,
+        module: text("module")?,
+        message: text("message")?,
+        doc_ref: text("doc_ref")?,
+    })
+}
+
+impl Rule for RequireConstructor {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Forbids struct literals of types that must be built via a constructor"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Constructors enforce invariants that a struct literal skips. Configured types may only \
+         be built with a struct literal in the module that defines them; elsewhere, call the \
+         designated constructor."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_in_tests", "types"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        let invalid = |key: &str, message: String| ConfigError::InvalidOption {
+            rule: NAME.to_string(),
+            key: key.to_string(),
+            message,
+        };
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| {
+                invalid("allow_in_tests", format!("expected a boolean, got {value}"))
+            })?;
+        }
+        if let Some(value) = config.options.get("types") {
+            let entries = value
+                .as_array()
+                .ok_or_else(|| invalid("types", format!("expected an array, got {value}")))?;
+            self.types = entries
+                .iter()
+                .map(|entry| parse_requirement(entry).map_err(|m| invalid("types", m)))
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.types.is_empty() || (self.allow_in_tests && ctx.is_test) {
+            return Vec::new();
+        }
+
+        let mut defined = DefinedStructs::default();
+        defined.visit_file(ast);
+
+        let mut visitor = StructLiteralVisitor {
+            ctx,
+            rule: self,
+            defined: defined.names,
+            violations: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+
+        visitor.visit_file(ast);
+        visitor.violations
+    }
+}
+
+/// Collects the names of structs defined anywhere in a file.
+#[derive(Default)]
+struct DefinedStructs {
+    names: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for DefinedStructs {
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        self.names.push(node.ident.to_string());
+        syn::visit::visit_item_struct(self, node);
+    }
+}
+
+struct StructLiteralVisitor<'a> {
+    ctx: &'a FileContext<'a>,
+    rule: &'a RequireConstructor,
+    defined: Vec<String>,
+    violations: Vec<Violation>,
+    context: ContextTracker,
+}
+
+impl StructLiteralVisitor<'_> {
+    fn check_literal(&mut self, node: &ExprStruct) {
+        if self.context.is_skipped(self.rule.allow_in_tests) {
+            return;
+        }
+        let Some(last) = node.path.segments.last() else {
+            return;
+        };
+        let name = last.ident.to_string();
+        let Some(requirement) = self
+            .rule
+            .types
+            .iter()
+            .find(|t| t.name.rsplit("::").next() == Some(name.as_str()))
+        else {
+            return;
+        };
+        let allowed = if requirement.module.is_some() {
+            requirement.allows_module(&self.ctx.module_path)
+        } else {
+            self.defined.contains(&name)
+        };
+        if allowed {
+            return;
+        }
+
+        let start = node.path.span().start();
+        if self
+            .ctx
+            .check_allow_for_rule(start.line, NAME, CODE)
+            .is_allowed()
+        {
+            return;
+        }
+
+        let constructor = requirement.constructor_name();
+        let message = requirement.message.clone().unwrap_or_else(|| {
+            format!("`{name}` is built with a struct literal; use `{constructor}` instead")
+        });
+        let location = Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
+        let mut violation = Violation::new(CODE, NAME, self.rule.severity, location, message)
+            .with_suggestion(Suggestion::new(format!(
+                "Call `{constructor}` so the type's invariants are enforced"
+            )));
+        if let Some(doc_ref) = &requirement.doc_ref {
+            violation = violation.with_doc_ref(doc_ref.clone());
+        }
+        self.violations.push(violation);
+    }
+}
+
+impl<'ast> Visit<'ast> for StructLiteralVisitor<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        syn::visit::visit_item_mod(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        syn::visit::visit_item_fn(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        syn::visit::visit_item_impl(self, node);
+        self.context.restore(saved);
+    }
+
+    fn visit_expr_struct(&mut self, node: &'ast ExprStruct) {
+        self.check_literal(node);
+        syn::visit::visit_expr_struct(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_in(rule: &RequireConstructor, path: &str, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new(path), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    fn config_rule() -> RequireConstructor {
+        RequireConstructor::new().require(
+            ConstructorRequirement::new("Config")
+                .module("crate::config")
+                .doc_ref("docs/adr/0003.md"),
+        )
+    }
+
+    #[test]
+    fn test_detects_struct_literal_outside_module() {
+        let violations = check_in(
+            &config_rule(),
+            "src/app.rs",
+            "fn f() { let c = crate::config::Config { root: p }; }",
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, CODE);
+        assert!(violations[0].message.contains("`Config::new`"));
+        assert_eq!(violations[0].doc_ref.as_deref(), Some("docs/adr/0003.md"));
+    }
+
+    #[test]
+    fn test_allows_defining_module_and_submodules() {
+        let code = "fn f() -> Config { Config { root: p } }";
+        assert!(check_in(&config_rule(), "src/config.rs", code).is_empty());
+        assert!(check_in(&config_rule(), "src/config/mod.rs", code).is_empty());
+        assert!(check_in(&config_rule(), "src/config/load.rs", code).is_empty());
+        assert_eq!(check_in(&config_rule(), "src/configs.rs", code).len(), 1);
+    }
+
+    #[test]
+    fn test_without_module_allows_defining_file() {
+        let rule = RequireConstructor::new().require(
+            ConstructorRequirement::new("Port")
+                .constructor("Port::parse")
+                .message("Ports must be validated"),
+        );
+        let defining = "pub struct Port { n: u16 }\nfn p() -> Port { Port { n: 1 } }";
+        assert!(check_in(&rule, "src/port.rs", defining).is_empty());
+
+        let violations = check_in(&rule, "src/main.rs", "fn f() { let p = Port { n: 1 }; }");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message, "Ports must be validated");
+        assert!(violations[0]
+            .suggestion
+            .as_ref()
+            .is_some_and(|s| s.message.contains("Port::parse")));
+    }
+
+    #[test]
+    fn test_ignores_other_types_tests_and_allowed() {
+        let code = r"
+fn f() {
+    let o = Other { x: 1 };
+    // arch-lint: allow(require-constructor) reason: migration shim
+    let c = Config { root: p };
+}
+
+#[cfg(test)]
+mod tests {
+    fn t() { let c = Config { root: p }; }
+}
+";
+        assert!(check_in(&config_rule(), "src/app.rs", code).is_empty());
+    }
+
+    fn configured(toml: &str) -> Result<RequireConstructor, ConfigError> {
+        let config = arch_lint_core::Config::parse(toml).expect("parse config");
+        let mut rule = RequireConstructor::new();
+        rule.configure(&config.rules[NAME])?;
+        Ok(rule)
+    }
+
+    #[test]
+    fn test_configure_types() {
+        let rule = configured(
+            r#"
+[rules.require-constructor]
+types = [
+    "Port",
+    { name = "Config", constructor = "Config::load", module = "config", doc_ref = "ADR-3" },
+]
+"#,
+        )
+        .expect("valid options");
+        assert_eq!(rule.types[0], ConstructorRequirement::new("Port"));
+        assert_eq!(
+            rule.types[1],
+            ConstructorRequirement::new("Config")
+                .constructor("Config::load")
+                .module("config")
+                .doc_ref("ADR-3")
+        );
+
+        let err = configured("[rules.require-constructor]\ntypes = [{ constructor = \"x\" }]\n")
+            .expect_err("missing name");
+        assert!(err.to_string().contains("missing `name`"), "{err}");
+        let err =
+            configured("[rules.require-constructor]\ntypes = [{ name = \"A\", modul = \"a\" }]\n")
+                .expect_err("typo");
+        assert!(err.to_string().contains("unknown key `modul`"), "{err}");
+    }
+}
@@ -23,8 +23,7 @@
 //! }
 //! ```
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::check_arch_lint_allow;
+use arch_lint_core::utils::check_arch_lint_allow_for_rule;
 use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
 #[allow(unused_imports)]
 use syn::spanned::Spanned;
@@ -110,6 +109,11 @@ impl Rule for RequireDocComments {
         "Requires documentation comments on public items"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Public APIs should be documented so that users know how to use them. \
+         Documentation keeps the code maintainable and makes `cargo doc` output useful."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
@@ -163,7 +167,7 @@ impl DocCommentsVisitor<'_> {
         let start = span.start();
 
         // Check for inline allow comment
-        let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
         if allow_check.is_allowed() {
             // If reason is required but not provided, create a separate violation
             if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -186,7 +190,7 @@ impl DocCommentsVisitor<'_> {
         }
 
         // Check for attribute-level allow
-        if check_arch_lint_allow(attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(attrs, NAME, CODE).is_allowed() {
             return;
         }
 
@@ -211,7 +215,7 @@ impl<'ast> Visit<'ast> for DocCommentsVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -263,13 +267,7 @@ mod tests {
 
     fn check_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         RequireDocComments::new().check(&ctx, &ast)
     }
 
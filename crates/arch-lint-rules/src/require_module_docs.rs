@@ -0,0 +1,283 @@
+//! Rule to require a module-level doc comment in every source file.
+//!
+//! # Rationale
+//!
+//! `require-doc-comments` covers public items, but nothing says what a
+//! module is for. A `//!` comment at the top of each file explains the
+//! module's purpose to readers and in `cargo doc`.
+//!
+//! # Detected Patterns
+//!
+//! - Source files with no inner doc attribute (`//!` or `#![doc = ...]`),
+//!   reported at line 1
+//!
+//! `main.rs` and test files are skipped by default (`allow_main`,
+//! `allow_in_tests`), build scripts always; `exclude` takes glob patterns
+//! for generated files. A file-level `#![arch_lint::allow(require_module_docs)]`
+//! suppresses the rule.
+//!
+//! # Good Patterns
+//!
+//! ```ignore
+//! //! Parsing of `arch-lint.toml` into [`Config`].
+//!
+//! use serde::Deserialize;
+//! ```
+
+use arch_lint_core::declarative::model::GlobPattern;
+use arch_lint_core::utils::check_arch_lint_allow_for_rule;
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::AttrStyle;
+
+/// Rule code for require-module-docs.
+pub const CODE: &str = "AL030";
+
+/// Rule name for require-module-docs.
+pub const NAME: &str = "require-module-docs";
+
+/// Requires a `//!` module doc comment at the top of each source file.
+#[derive(Debug, Clone)]
+pub struct RequireModuleDocs {
+    /// Custom severity.
+    pub severity: Severity,
+    /// Skip `main.rs` files.
+    pub allow_main: bool,
+    /// Skip test files.
+    pub allow_in_tests: bool,
+    /// Glob patterns of files to skip (e.g. `"src/generated/**"`).
+    pub exclude: Vec<GlobPattern>,
+}
+
+impl Default for RequireModuleDocs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequireModuleDocs {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Warning,
+            allow_main: true,
+            allow_in_tests: true,
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets whether to skip `main.rs` files.
+    #[must_use]
+    pub fn allow_main(mut self, allow: bool) -> Self {
+        self.allow_main = allow;
+        self
+    }
+
+    /// Sets whether to skip test files.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Skips files matching a glob pattern, relative to the analysis root.
+    /// Invalid patterns are ignored.
+    #[must_use]
+    pub fn exclude(mut self, pattern: &str) -> Self {
+        self.exclude.extend(GlobPattern::new(pattern).ok());
+        self
+    }
+
+    fn skips(&self, ctx: &FileContext) -> bool {
+        ctx.is_build_script
+            || (self.allow_in_tests && ctx.is_test)
+            || (self.allow_main && ctx.relative_path.ends_with("main.rs"))
+            || self.exclude.iter().any(|p| p.matches(&ctx.relative_path))
+    }
+}
+
+impl Rule for RequireModuleDocs {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires a //! module doc comment in each source file"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A `//!` comment at the top of a file states what the module is for, which item \
+         docs alone do not. It shows up in `cargo doc` and orients readers of the source."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["allow_main", "allow_in_tests", "exclude"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        for (key, field) in [
+            ("allow_main", &mut self.allow_main),
+            ("allow_in_tests", &mut self.allow_in_tests),
+        ] {
+            let Some(value) = config.options.get(key) else {
+                continue;
+            };
+            *field = value.as_bool().ok_or_else(|| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: key.to_string(),
+                message: format!("expected a boolean, got {value}"),
+            })?;
+        }
+        if let Some(value) = config.options.get("exclude") {
+            let invalid = |message: String| ConfigError::InvalidOption {
+                rule: NAME.to_string(),
+                key: "exclude".to_string(),
+                message,
+            };
+            let patterns: Vec<String> = config
+                .get_option("exclude")
+                .ok_or_else(|| invalid(format!("expected an array of globs, got {value}")))?;
+            self.exclude = patterns
+                .iter()
+                .map(|p| GlobPattern::new(p).map_err(|e| invalid(e.to_string())))
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.skips(ctx) {
+            return Vec::new();
+        }
+
+        let inner: Vec<_> = ast
+            .attrs
+            .iter()
+            .filter(|attr| matches!(attr.style, AttrStyle::Inner(_)))
+            .cloned()
+            .collect();
+        if inner.iter().any(|attr| attr.path().is_ident("doc"))
+            || check_arch_lint_allow_for_rule(&inner, NAME, CODE).is_allowed()
+            || ctx.check_allow_for_rule(1, NAME, CODE).is_allowed()
+        {
+            return Vec::new();
+        }
+
+        vec![Violation::new(
+            CODE,
+            NAME,
+            self.severity,
+            Location::new(ctx.relative_path.clone(), 1, 1),
+            "Module has no `//!` doc comment",
+        )
+        .with_suggestion(Suggestion::new(
+            "Add a `//!` comment at the top of the file describing the module's purpose",
+        ))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_file(rule: &RequireModuleDocs, path: &str, code: &str, is_test: bool) -> usize {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let mut ctx = FileContext::new(Path::new(path), code, Path::new(""));
+        ctx.is_test = is_test;
+        rule.check(&ctx, &ast).len()
+    }
+
+    fn check_code(code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        RequireModuleDocs::new().check(&ctx, &ast)
+    }
+
+    #[test]
+    fn test_detects_missing_module_docs() {
+        let violations = check_code("/// Item docs do not count.\npub fn f() {}\n");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(
+            (violations[0].location.line, violations[0].location.column),
+            (1, 1)
+        );
+    }
+
+    #[test]
+    fn test_accepts_inner_doc_comment_and_attribute() {
+        assert!(check_code("//! Parses configs.\n\npub fn f() {}\n").is_empty());
+        assert!(check_code("#![doc = \"Parses configs.\"]\npub fn f() {}\n").is_empty());
+        assert!(check_code("#![forbid(unsafe_code)]\n//! Parses configs.\n").is_empty());
+    }
+
+    #[test]
+    fn test_respects_file_level_allow() {
+        assert!(check_code(
+            "#![arch_lint::allow(require_module_docs, reason = \"re-exports only\")]\npub use a::b;\n"
+        )
+        .is_empty());
+        assert!(check_code("// arch-lint: allow(require-module-docs)\npub use a::b;\n").is_empty());
+    }
+
+    #[test]
+    fn test_skips_main_tests_and_build_scripts() {
+        let rule = RequireModuleDocs::new();
+        assert_eq!(check_file(&rule, "src/main.rs", "fn main() {}", false), 0);
+        assert_eq!(check_file(&rule, "tests/it.rs", "fn t() {}", true), 0);
+        assert_eq!(check_file(&rule, "build.rs", "fn main() {}", false), 0);
+
+        let rule = RequireModuleDocs::new()
+            .allow_main(false)
+            .allow_in_tests(false);
+        assert_eq!(check_file(&rule, "src/main.rs", "fn main() {}", false), 1);
+        assert_eq!(check_file(&rule, "tests/it.rs", "fn t() {}", true), 1);
+    }
+
+    #[test]
+    fn test_exclude_patterns() {
+        let rule = RequireModuleDocs::new().exclude("src/generated/**");
+        assert_eq!(check_file(&rule, "src/generated/api.rs", "", false), 0);
+        assert_eq!(check_file(&rule, "src/api.rs", "", false), 1);
+    }
+
+    fn configured(toml: &str) -> Result<RequireModuleDocs, ConfigError> {
+        let config = arch_lint_core::Config::parse(toml).expect("parse config");
+        let mut rule = RequireModuleDocs::new();
+        rule.configure(&config.rules[NAME])?;
+        Ok(rule)
+    }
+
+    #[test]
+    fn test_configure_options() {
+        let rule = configured(
+            "[rules.require-module-docs]\nallow_main = false\nexclude = [\"**/*_pb.rs\"]\n",
+        )
+        .expect("valid options");
+        assert!(!rule.allow_main);
+        assert_eq!(check_file(&rule, "src/api_pb.rs", "", false), 0);
+        assert_eq!(check_file(&rule, "src/main.rs", "", false), 1);
+
+        let err = configured("[rules.require-module-docs]\nexclude = \"src/gen/**\"\n")
+            .expect_err("not an array");
+        assert!(err.to_string().contains("exclude"), "{err}");
+    }
+}
@@ -10,7 +10,16 @@
 //! # Detected Patterns
 //!
 //! - Structs/enums ending with `Error` without `#[derive(thiserror::Error)]`
-//! - Custom `impl std::error::Error` without thiserror
+//! - Custom `impl std::error::Error` without thiserror (when
+//!   `allow_manual_impl` is disabled)
+//!
+//! # Manual Implementations
+//!
+//! By default a type is accepted if the same file contains
+//! `impl std::error::Error for T`. [`RequireThiserror::project_wide`] turns
+//! the rule into a [`ProjectRule`] that accepts impls found anywhere in the
+//! project. Types are matched by name only, so two error types with the same
+//! name in different modules are treated as one.
 //!
 //! # Good Patterns
 //!
@@ -25,11 +34,13 @@
 //! }
 //! ```
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
 use arch_lint_core::utils::has_allow_attr;
-use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use arch_lint_core::{
+    FileContext, Location, ProjectContext, ProjectRule, Rule, Severity, Suggestion, Violation,
+};
+use std::collections::HashSet;
 use syn::visit::Visit;
-use syn::{ItemEnum, ItemStruct};
+use syn::{ItemEnum, ItemImpl, ItemStruct};
 
 /// Rule code for require-thiserror.
 pub const CODE: &str = "AL005";
@@ -44,6 +55,8 @@ pub struct RequireThiserror {
     pub severity: Severity,
     /// Patterns to match error type names.
     pub patterns: Vec<String>,
+    /// Accept types with a hand-written `impl std::error::Error`.
+    pub allow_manual_impl: bool,
 }
 
 impl Default for RequireThiserror {
@@ -59,6 +72,7 @@ impl RequireThiserror {
         Self {
             severity: Severity::Warning,
             patterns: vec!["Error".to_string()],
+            allow_manual_impl: true,
         }
     }
 
@@ -76,9 +90,44 @@ impl RequireThiserror {
         self
     }
 
+    /// Sets whether a hand-written `impl std::error::Error` satisfies the
+    /// rule (default: true).
+    #[must_use]
+    pub fn allow_manual_impl(mut self, allow: bool) -> Self {
+        self.allow_manual_impl = allow;
+        self
+    }
+
+    /// Converts the rule into a project rule that accepts manual
+    /// `impl std::error::Error` blocks from any file in the project.
+    ///
+    /// Register the result with `AnalyzerBuilder::project_rule` instead of
+    /// the per-file rule.
+    #[must_use]
+    pub fn project_wide(self) -> ProjectWideRequireThiserror {
+        ProjectWideRequireThiserror { rule: self }
+    }
+
     fn is_error_type(&self, name: &str) -> bool {
         self.patterns.iter().any(|p| name.ends_with(p))
     }
+
+    fn check_with(
+        &self,
+        ctx: &FileContext,
+        ast: &syn::File,
+        manual_impls: &HashSet<String>,
+    ) -> Vec<Violation> {
+        let mut visitor = ThiserrorVisitor {
+            ctx,
+            rule: self,
+            manual_impls,
+            violations: Vec::new(),
+        };
+
+        visitor.visit_file(ast);
+        visitor.violations
+    }
 }
 
 impl Rule for RequireThiserror {
@@ -94,25 +143,73 @@ impl Rule for RequireThiserror {
         "Requires thiserror::Error derive for error types"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Deriving `thiserror::Error` gives error types a consistent `std::error::Error` \
+         implementation, structured messages via `#[error(...)]`, and source chaining via \
+         `#[from]` and `#[source]`."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
 
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
-        let mut visitor = ThiserrorVisitor {
-            ctx,
-            rule: self,
-            violations: Vec::new(),
-        };
+        let mut manual_impls = HashSet::new();
+        if self.allow_manual_impl {
+            collect_manual_impls(ast, &mut manual_impls);
+        }
+        self.check_with(ctx, ast, &manual_impls)
+    }
+}
 
-        visitor.visit_file(ast);
-        visitor.violations
+/// [`RequireThiserror`] as a project rule, see
+/// [`RequireThiserror::project_wide`].
+#[derive(Debug, Clone)]
+pub struct ProjectWideRequireThiserror {
+    rule: RequireThiserror,
+}
+
+impl ProjectRule for ProjectWideRequireThiserror {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires thiserror::Error derive for error types"
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.rule.severity
+    }
+
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Violation> {
+        let mut manual_impls = HashSet::new();
+        if self.rule.allow_manual_impl {
+            for (_, ast) in ctx.asts() {
+                collect_manual_impls(&ast, &mut manual_impls);
+            }
+        }
+
+        let mut violations = Vec::new();
+        for (path, ast) in ctx.asts() {
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let file_ctx = FileContext::new(path, &content, ctx.root);
+            violations.extend(self.rule.check_with(&file_ctx, &ast, &manual_impls));
+        }
+        violations
     }
 }
 
 struct ThiserrorVisitor<'a> {
     ctx: &'a FileContext<'a>,
     rule: &'a RequireThiserror,
+    manual_impls: &'a HashSet<String>,
     violations: Vec<Violation>,
 }
 
@@ -120,7 +217,7 @@ impl<'ast> Visit<'ast> for ThiserrorVisitor<'_> {
     fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
         let name = node.ident.to_string();
 
-        if self.rule.is_error_type(&name) && !has_thiserror_derive(&node.attrs) {
+        if self.is_missing_derive(&name, &node.attrs) {
             self.report_violation(&name, node.ident.span(), &node.attrs);
         }
 
@@ -130,7 +227,7 @@ impl<'ast> Visit<'ast> for ThiserrorVisitor<'_> {
     fn visit_item_enum(&mut self, node: &'ast ItemEnum) {
         let name = node.ident.to_string();
 
-        if self.rule.is_error_type(&name) && !has_thiserror_derive(&node.attrs) {
+        if self.is_missing_derive(&name, &node.attrs) {
             self.report_violation(&name, node.ident.span(), &node.attrs);
         }
 
@@ -139,6 +236,12 @@ impl<'ast> Visit<'ast> for ThiserrorVisitor<'_> {
 }
 
 impl ThiserrorVisitor<'_> {
+    fn is_missing_derive(&self, name: &str, attrs: &[syn::Attribute]) -> bool {
+        self.rule.is_error_type(name)
+            && !has_thiserror_derive(attrs)
+            && !self.manual_impls.contains(name)
+    }
+
     fn report_violation(&mut self, name: &str, span: proc_macro2::Span, attrs: &[syn::Attribute]) {
         let start = span.start();
 
@@ -155,7 +258,7 @@ impl ThiserrorVisitor<'_> {
             .unwrap_or(start.line);
 
         // Check for inline allow comment (check from attributes start to item start)
-        let allow_check = check_allow_with_reason(self.ctx.content, earliest_line, NAME);
+        let allow_check = self.ctx.check_allow_for_rule(earliest_line, NAME, CODE);
         if allow_check.is_allowed() {
             // If reason is required but not provided, create a separate violation
             if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -194,6 +297,42 @@ impl ThiserrorVisitor<'_> {
     }
 }
 
+/// Collects the names of types with an `impl std::error::Error for T` block.
+///
+/// Accepts `std::error::Error`, `core::error::Error`, `error::Error`, and a
+/// bare `Error` (as written after `use std::error::Error;`).
+fn collect_manual_impls(ast: &syn::File, names: &mut HashSet<String>) {
+    struct ImplCollector<'a> {
+        names: &'a mut HashSet<String>,
+    }
+
+    impl<'ast> Visit<'ast> for ImplCollector<'_> {
+        fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+            if let (Some((None, trait_path, _)), syn::Type::Path(self_ty)) =
+                (&node.trait_, node.self_ty.as_ref())
+            {
+                if is_std_error_path(trait_path) {
+                    if let Some(segment) = self_ty.path.segments.last() {
+                        self.names.insert(segment.ident.to_string());
+                    }
+                }
+            }
+            syn::visit::visit_item_impl(self, node);
+        }
+    }
+
+    ImplCollector { names }.visit_file(ast);
+}
+
+fn is_std_error_path(path: &syn::Path) -> bool {
+    let idents: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+    let idents: Vec<&str> = idents.iter().map(String::as_str).collect();
+    matches!(
+        idents.as_slice(),
+        ["std" | "core", "error", "Error"] | ["error", "Error"] | ["Error"]
+    )
+}
+
 /// Checks if attributes contain `#[derive(thiserror::Error)]` or `#[derive(Error)]`.
 ///
 /// This handles both patterns:
@@ -233,15 +372,13 @@ mod tests {
     use std::path::Path;
 
     fn check_code(code: &str) -> Vec<Violation> {
+        check_with_rule(&RequireThiserror::new(), code)
+    }
+
+    fn check_with_rule(rule: &RequireThiserror, code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
-        RequireThiserror::new().check(&ctx, &ast)
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
     }
 
     #[test]
@@ -365,4 +502,88 @@ pub struct ParseError {
             "Should respect allow comment on previous line"
         );
     }
+
+    const MANUAL_IMPL: &str = r#"
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parse error at line {}", self.line)
+    }
+}
+
+impl std::error::Error for ParseError {}
+"#;
+
+    #[test]
+    fn test_allows_manual_error_impl() {
+        assert!(check_code(MANUAL_IMPL).is_empty());
+
+        let with_use = "use std::error::Error;\n#[derive(Debug)]\npub enum MyError { A }\nimpl Error for MyError {}\n";
+        assert!(check_code(with_use).is_empty());
+    }
+
+    #[test]
+    fn test_manual_impl_disallowed() {
+        let rule = RequireThiserror::new().allow_manual_impl(false);
+        assert_eq!(check_with_rule(&rule, MANUAL_IMPL).len(), 1);
+    }
+
+    #[test]
+    fn test_display_impl_alone_is_not_enough() {
+        let violations = check_code(
+            r#"
+#[derive(Debug)]
+pub struct ParseError;
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("parse error")
+    }
+}
+"#,
+        );
+        assert_eq!(violations.len(), 1);
+    }
+
+    #[test]
+    fn test_project_wide_sees_impls_in_other_files() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let types = dir.path().join("types.rs");
+        let impls = dir.path().join("impls.rs");
+        std::fs::write(
+            &types,
+            "#[derive(Debug)]\npub struct ParseError;\n#[derive(Debug)]\npub struct IoError;\n",
+        )
+        .expect("write types.rs");
+        std::fs::write(
+            &impls,
+            "impl std::error::Error for crate::types::ParseError {}\n",
+        )
+        .expect("write impls.rs");
+
+        let ctx =
+            ProjectContext::new(dir.path()).with_source_files(vec![types.clone(), impls.clone()]);
+
+        // The per-file heuristic cannot see the impl in another file.
+        assert_eq!(
+            check_with_rule(
+                &RequireThiserror::new(),
+                &std::fs::read_to_string(&types).expect("read types.rs")
+            )
+            .len(),
+            2
+        );
+
+        let violations = RequireThiserror::new().project_wide().check_project(&ctx);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("IoError"));
+        assert_eq!(
+            violations[0].location.file,
+            std::path::PathBuf::from("types.rs")
+        );
+    }
 }
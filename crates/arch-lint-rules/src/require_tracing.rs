@@ -10,6 +10,10 @@
 //! - `log::info!`, `log::error!`, `log::warn!`, `log::debug!`, `log::trace!`
 //! - Any macro from `log::` crate
 //!
+//! With a `[logging]` section in the config, macros from every listed crate
+//! other than `tracing` are reported instead (e.g. `slog::info!` for
+//! `macros = ["tracing", "slog"]`).
+//!
 //! # Good Patterns
 //!
 //! ```ignore
@@ -18,9 +22,8 @@
 //! tracing::error!("error occurred");
 //! ```
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::{check_arch_lint_allow, path_to_string};
-use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
+use arch_lint_core::utils::{check_arch_lint_allow_for_rule, path_to_string};
+use arch_lint_core::{FileContext, Location, LoggingConfig, Rule, Severity, Suggestion, Violation};
 use syn::visit::Visit;
 use syn::{ExprMacro, ItemFn, ItemImpl, ItemMod};
 
@@ -35,6 +38,8 @@ pub const NAME: &str = "require-tracing";
 pub struct RequireTracing {
     /// Severity level.
     pub severity: Severity,
+    /// Crates whose macros should be replaced with `tracing`.
+    pub discouraged_crates: Vec<String>,
 }
 
 impl Default for RequireTracing {
@@ -49,6 +54,7 @@ impl RequireTracing {
     pub fn new() -> Self {
         Self {
             severity: Severity::Warning,
+            discouraged_crates: vec!["log".to_string()],
         }
     }
 
@@ -58,6 +64,22 @@ impl RequireTracing {
         self.severity = severity;
         self
     }
+
+    /// Reports macros from every crate in `logging` except `tracing`.
+    #[must_use]
+    pub fn logging(mut self, logging: &LoggingConfig) -> Self {
+        self.configure_logging(logging);
+        self
+    }
+
+    /// Splits `path` into a discouraged crate and the macro name.
+    fn discouraged<'p>(&self, path: &'p str) -> Option<(&'p str, &'p str)> {
+        let (krate, name) = path.split_once("::")?;
+        self.discouraged_crates
+            .iter()
+            .any(|c| c == krate)
+            .then_some((krate, name))
+    }
 }
 
 impl Rule for RequireTracing {
@@ -73,10 +95,24 @@ impl Rule for RequireTracing {
         "Requires tracing crate instead of log crate"
     }
 
+    fn explanation(&self) -> &'static str {
+        "`tracing` provides structured, async-aware diagnostics with spans and fields. \
+         Mixing it with the `log` crate loses context and splits configuration."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
 
+    fn configure_logging(&mut self, logging: &LoggingConfig) {
+        self.discouraged_crates = logging
+            .macros
+            .iter()
+            .filter(|krate| *krate != "tracing")
+            .cloned()
+            .collect();
+    }
+
     fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
         let mut visitor = TracingVisitor {
             ctx,
@@ -101,7 +137,7 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -112,7 +148,7 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
     fn visit_item_fn(&mut self, node: &'ast ItemFn) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -123,7 +159,7 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -139,8 +175,8 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
 
         let path_str = path_to_string(&node.path);
 
-        // Check if this is a log:: macro
-        if path_str.starts_with("log::") {
+        // Check if this is a macro from a discouraged logging crate
+        if let Some((krate, macro_name)) = self.rule.discouraged(&path_str) {
             let Some(first_segment) = node.path.segments.first() else {
                 syn::visit::visit_macro(self, node);
                 return;
@@ -149,7 +185,7 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
             let start = span.start();
 
             // Check for inline allow comment
-            let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+            let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
             if allow_check.is_allowed() {
                 // If reason is required but not provided, create a separate violation
                 if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -175,16 +211,13 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
             let location =
                 Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
 
-            // Extract macro name (e.g., "info" from "log::info")
-            let macro_name = path_str.strip_prefix("log::").unwrap_or(&path_str);
-
             self.violations.push(
                 Violation::new(
                     CODE,
                     NAME,
                     self.rule.severity,
                     location,
-                    format!("Use `tracing::{macro_name}!` instead of `log::{macro_name}!`"),
+                    format!("Use `tracing::{macro_name}!` instead of `{krate}::{macro_name}!`"),
                 )
                 .with_suggestion(Suggestion::new(format!(
                     "Replace with `tracing::{macro_name}!` for structured logging"
@@ -203,8 +236,8 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
 
         let path_str = path_to_string(&node.mac.path);
 
-        // Check if this is a log:: macro
-        if path_str.starts_with("log::") {
+        // Check if this is a macro from a discouraged logging crate
+        if let Some((krate, macro_name)) = self.rule.discouraged(&path_str) {
             let Some(first_segment) = node.mac.path.segments.first() else {
                 syn::visit::visit_expr_macro(self, node);
                 return;
@@ -213,7 +246,7 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
             let start = span.start();
 
             // Check for inline allow comment
-            let allow_check = check_allow_with_reason(self.ctx.content, start.line, NAME);
+            let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
             if allow_check.is_allowed() {
                 // If reason is required but not provided, create a separate violation
                 if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -239,16 +272,13 @@ impl<'ast> Visit<'ast> for TracingVisitor<'_> {
             let location =
                 Location::new(self.ctx.relative_path.clone(), start.line, start.column + 1);
 
-            // Extract macro name (e.g., "info" from "log::info")
-            let macro_name = path_str.strip_prefix("log::").unwrap_or(&path_str);
-
             self.violations.push(
                 Violation::new(
                     CODE,
                     NAME,
                     self.rule.severity,
                     location,
-                    format!("Use `tracing::{macro_name}!` instead of `log::{macro_name}!`"),
+                    format!("Use `tracing::{macro_name}!` instead of `{krate}::{macro_name}!`"),
                 )
                 .with_suggestion(Suggestion::new(format!(
                     "Replace with `tracing::{macro_name}!` for structured logging"
@@ -266,15 +296,13 @@ mod tests {
     use std::path::Path;
 
     fn check_code(code: &str) -> Vec<Violation> {
+        check_code_with(code, &RequireTracing::new())
+    }
+
+    fn check_code_with(code: &str, rule: &RequireTracing) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
-        RequireTracing::new().check(&ctx, &ast)
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
     }
 
     #[test]
@@ -358,4 +386,23 @@ fn foo() {
         );
         assert_eq!(violations.len(), 5);
     }
+
+    #[test]
+    fn test_logging_config_discourages_other_crates() {
+        let code = r#"
+fn foo() {
+    slog::info!(logger, "info");
+    log::warn!("warn");
+    tracing::error!("error");
+}
+"#;
+        assert_eq!(check_code(code).len(), 1);
+
+        let logging = LoggingConfig {
+            macros: vec!["tracing".into(), "slog".into()],
+        };
+        let violations = check_code_with(code, &RequireTracing::new().logging(&logging));
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("`slog::info!`"));
+    }
 }
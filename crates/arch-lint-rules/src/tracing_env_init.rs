@@ -25,8 +25,7 @@
 //!     .unwrap_or_else(|_| EnvFilter::new("info"));
 //! ```
 
-use arch_lint_core::utils::allowance::check_allow_with_reason;
-use arch_lint_core::utils::{check_arch_lint_allow, path_to_string};
+use arch_lint_core::utils::{check_arch_lint_allow_for_rule, path_to_string};
 use arch_lint_core::{FileContext, Location, Rule, Severity, Suggestion, Violation};
 use syn::visit::Visit;
 use syn::{Expr, ExprCall, ExprLit, ExprMethodCall, ExprPath, ItemFn, ItemImpl, ItemMod, Lit};
@@ -87,6 +86,12 @@ impl Rule for TracingEnvInit {
         "Prevents hardcoded log levels in tracing initialization"
     }
 
+    fn explanation(&self) -> &'static str {
+        "Hardcoded log levels cannot be changed without a rebuild. Initializing with \
+         `EnvFilter::from_default_env()` lets operators control verbosity through \
+         `RUST_LOG`."
+    }
+
     fn default_severity(&self) -> Severity {
         self.severity
     }
@@ -131,7 +136,7 @@ impl<'ast> Visit<'ast> for EnvInitVisitor<'_> {
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -142,7 +147,7 @@ impl<'ast> Visit<'ast> for EnvInitVisitor<'_> {
     fn visit_item_fn(&mut self, node: &'ast ItemFn) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -153,7 +158,7 @@ impl<'ast> Visit<'ast> for EnvInitVisitor<'_> {
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
         let was_allowed = self.in_allowed_context;
 
-        if check_arch_lint_allow(&node.attrs, NAME).is_allowed() {
+        if check_arch_lint_allow_for_rule(&node.attrs, NAME, CODE).is_allowed() {
             self.in_allowed_context = true;
         }
 
@@ -204,8 +209,7 @@ impl<'ast> Visit<'ast> for EnvInitVisitor<'_> {
                         let start = span.start();
 
                         // Check for inline allow comment
-                        let allow_check =
-                            check_allow_with_reason(self.ctx.content, start.line, NAME);
+                        let allow_check = self.ctx.check_allow_for_rule(start.line, NAME, CODE);
                         if allow_check.is_allowed() {
                             // If reason is required but not provided, create a separate violation
                             if self.rule.requires_allow_reason() && allow_check.reason().is_none() {
@@ -267,13 +271,7 @@ mod tests {
 
     fn check_code(code: &str) -> Vec<Violation> {
         let ast = syn::parse_file(code).expect("Failed to parse");
-        let ctx = FileContext {
-            path: Path::new("test.rs"),
-            content: code,
-            is_test: false,
-            module_path: vec![],
-            relative_path: std::path::PathBuf::from("test.rs"),
-        };
+        let ctx = FileContext::new(Path::new("test.rs"), code, Path::new(""));
         TracingEnvInit::new().check(&ctx, &ast)
     }
 
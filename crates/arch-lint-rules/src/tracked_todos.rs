@@ -0,0 +1,445 @@
+//! Rule to surface `TODO`/`FIXME`/`XXX` comments.
+//!
+//! # Rationale
+//!
+//! Work-in-progress markers are easy to write and easy to forget. Listing
+//! them keeps them visible; requiring a tracking reference such as
+//! `TODO(#123)` or `TODO(JIRA-45)` ties each one to an issue that will
+//! outlive the comment's author.
+//!
+//! # Detected Patterns
+//!
+//! - `// TODO ...`, `// FIXME ...`, `// XXX ...` (also in `///` and `//!`)
+//!
+//! Comments are not part of the `syn` AST, so the rule scans source lines.
+//! A marker must be the first word of the comment; `//` inside a string
+//! literal on the same line is skipped.
+//!
+//! # Configuration
+//!
+//! - `require_reference`: Report unlinked markers as warnings (default: false)
+//! - `markers`: Words to look for (default: `TODO`, `FIXME`, `XXX`)
+//! - `allow_in_tests`: Allow in test code (default: true)
+
+use arch_lint_core::utils::ContextTracker;
+use arch_lint_core::{
+    ConfigError, FileContext, Location, Rule, RuleConfig, Severity, Suggestion, Violation,
+};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{ImplItemFn, ItemFn, ItemImpl, ItemMod};
+
+/// Rule code for tracked-todos.
+pub const CODE: &str = "AL036";
+
+/// Rule name for tracked-todos.
+pub const NAME: &str = "tracked-todos";
+
+/// Markers reported by default.
+const DEFAULT_MARKERS: &[&str] = &["TODO", "FIXME", "XXX"];
+
+/// Reports `TODO`/`FIXME`/`XXX` comments, optionally requiring an issue
+/// reference.
+#[derive(Debug, Clone)]
+pub struct TrackedTodos {
+    /// Severity of markers (and of linked markers with `require_reference`).
+    pub severity: Severity,
+    /// Report markers without a `(#123)`/`(JIRA-45)` reference as warnings.
+    pub require_reference: bool,
+    /// Words reported when they start a comment.
+    pub markers: Vec<String>,
+    /// Allow in test code.
+    pub allow_in_tests: bool,
+}
+
+impl Default for TrackedTodos {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrackedTodos {
+    /// Creates a new rule with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            severity: Severity::Info,
+            require_reference: false,
+            markers: DEFAULT_MARKERS.iter().map(ToString::to_string).collect(),
+            allow_in_tests: true,
+        }
+    }
+
+    /// Sets the severity level.
+    #[must_use]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets whether markers without a tracking reference are escalated to
+    /// warnings.
+    #[must_use]
+    pub fn require_reference(mut self, require: bool) -> Self {
+        self.require_reference = require;
+        self
+    }
+
+    /// Sets the marker words, replacing the defaults.
+    #[must_use]
+    pub fn markers(mut self, markers: &[&str]) -> Self {
+        self.markers = markers.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Sets whether to allow in test code.
+    #[must_use]
+    pub fn allow_in_tests(mut self, allow: bool) -> Self {
+        self.allow_in_tests = allow;
+        self
+    }
+
+    /// Finds a marker comment on `line`, returning the marker, its column
+    /// (0-based byte offset) and the text after it.
+    fn find_marker<'l>(&self, line: &'l str) -> Option<(&str, usize, &'l str)> {
+        let start = comment_start(line)?;
+        let body = line[start..]
+            .trim_start_matches('/')
+            .trim_start_matches('!');
+        let text = body.trim_start();
+        let offset = line.len() - text.len();
+        self.markers.iter().find_map(|marker| {
+            let rest = text.strip_prefix(marker.as_str())?;
+            let ends_word = rest
+                .chars()
+                .next()
+                .map_or(true, |c| !c.is_alphanumeric() && c != '_');
+            ends_word.then_some((marker.as_str(), offset, rest))
+        })
+    }
+}
+
+impl Rule for TrackedTodos {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn code(&self) -> &'static str {
+        CODE
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports TODO/FIXME/XXX comments, optionally requiring an issue reference"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "TODO, FIXME and XXX comments mark unfinished work that is easily forgotten. \
+         Listing them keeps them visible; with `require_reference`, each marker must \
+         name a tracking issue such as `TODO(#123)` or `TODO(JIRA-45)`."
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn configurable_keys(&self) -> &'static [&'static str] {
+        &["require_reference", "markers", "allow_in_tests"]
+    }
+
+    fn configure(&mut self, config: &RuleConfig) -> Result<(), ConfigError> {
+        let invalid = |key: &str, message: String| ConfigError::InvalidOption {
+            rule: NAME.to_string(),
+            key: key.to_string(),
+            message,
+        };
+        if let Some(value) = config.options.get("require_reference") {
+            self.require_reference = value.as_bool().ok_or_else(|| {
+                invalid(
+                    "require_reference",
+                    format!("expected a boolean, got {value}"),
+                )
+            })?;
+        }
+        if let Some(value) = config.options.get("markers") {
+            self.markers = config.get_option("markers").ok_or_else(|| {
+                invalid(
+                    "markers",
+                    format!("expected an array of words, got {value}"),
+                )
+            })?;
+        }
+        if let Some(value) = config.options.get("allow_in_tests") {
+            self.allow_in_tests = value.as_bool().ok_or_else(|| {
+                invalid("allow_in_tests", format!("expected a boolean, got {value}"))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, ctx: &FileContext) -> bool {
+        self.markers
+            .iter()
+            .any(|marker| ctx.content.contains(marker.as_str()))
+    }
+
+    fn check(&self, ctx: &FileContext, ast: &syn::File) -> Vec<Violation> {
+        if self.allow_in_tests && ctx.is_test {
+            return Vec::new();
+        }
+
+        let mut skipped = SkippedLines {
+            rule: self,
+            ranges: Vec::new(),
+            context: ContextTracker::new(NAME, CODE),
+        };
+        let saved = skipped.context.enter_mod(&ast.attrs);
+        if skipped.context.is_skipped(self.allow_in_tests) {
+            return Vec::new();
+        }
+        skipped.visit_file(ast);
+        skipped.context.restore(saved);
+
+        let mut violations = Vec::new();
+        for (idx, line) in ctx.content.lines().enumerate() {
+            let line_no = idx + 1;
+            let Some((marker, column, rest)) = self.find_marker(line) else {
+                continue;
+            };
+            if skipped.contains(line_no)
+                || ctx.check_allow_for_rule(line_no, NAME, CODE).is_allowed()
+            {
+                continue;
+            }
+
+            let location = Location::new(ctx.relative_path.clone(), line_no, column + 1);
+            let comment = line[column..].trim_end();
+            let violation = if self.require_reference && !has_reference(rest) {
+                Violation::new(
+                    CODE,
+                    NAME,
+                    Severity::Warning,
+                    location,
+                    format!("{comment} (no tracking reference)"),
+                )
+                .with_suggestion(Suggestion::new(format!(
+                    "Link an issue, e.g. `{marker}(#123)` or `{marker}(PROJ-45)`"
+                )))
+            } else {
+                Violation::new(CODE, NAME, self.severity, location, comment)
+            };
+            violations.push(violation);
+        }
+        violations
+    }
+}
+
+/// Returns the byte offset of the first `//` outside a string literal.
+fn comment_start(line: &str) -> Option<usize> {
+    let mut in_string = false;
+    let mut escaped = false;
+    let bytes = line.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            _ if escaped => escaped = false,
+            b'\\' if in_string => escaped = true,
+            b'"' => in_string = !in_string,
+            b'/' if !in_string && bytes.get(i + 1) == Some(&b'/') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns `true` if the text after a marker starts with `(#123)` or
+/// `(PROJ-45)`.
+fn has_reference(rest: &str) -> bool {
+    let Some(reference) = rest
+        .strip_prefix('(')
+        .and_then(|r| r.split_once(')'))
+        .map(|(reference, _)| reference.trim())
+    else {
+        return false;
+    };
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if let Some(number) = reference.strip_prefix('#') {
+        return is_number(number);
+    }
+    reference.split_once('-').is_some_and(|(project, number)| {
+        project.starts_with(|c: char| c.is_ascii_uppercase())
+            && project
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            && is_number(number)
+    })
+}
+
+/// Collects the line ranges of items where the rule is skipped (tests and
+/// `#[arch_lint::allow(tracked_todos)]`).
+struct SkippedLines<'a> {
+    rule: &'a TrackedTodos,
+    ranges: Vec<(usize, usize)>,
+    context: ContextTracker,
+}
+
+impl SkippedLines<'_> {
+    fn contains(&self, line: usize) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&line))
+    }
+
+    /// Records `span` if the current context is skipped; returns whether it
+    /// was, so the caller need not descend.
+    fn record(&mut self, span: proc_macro2::Span) -> bool {
+        let skipped = self.context.is_skipped(self.rule.allow_in_tests);
+        if skipped {
+            self.ranges.push((span.start().line, span.end().line));
+        }
+        skipped
+    }
+}
+
+impl<'ast> Visit<'ast> for SkippedLines<'_> {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        let saved = self.context.enter_mod(&node.attrs);
+        if !self.record(node.span()) {
+            syn::visit::visit_item_mod(self, node);
+        }
+        self.context.restore(saved);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        if !self.record(node.span()) {
+            syn::visit::visit_item_fn(self, node);
+        }
+        self.context.restore(saved);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let saved = self.context.enter_impl(&node.attrs);
+        if !self.record(node.span()) {
+            syn::visit::visit_item_impl(self, node);
+        }
+        self.context.restore(saved);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let saved = self.context.enter_fn(&node.attrs);
+        if !self.record(node.span()) {
+            syn::visit::visit_impl_item_fn(self, node);
+        }
+        self.context.restore(saved);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(rule: &TrackedTodos, code: &str) -> Vec<Violation> {
+        let ast = syn::parse_file(code).expect("Failed to parse");
+        let ctx = FileContext::new(Path::new("src/lib.rs"), code, Path::new(""));
+        rule.check(&ctx, &ast)
+    }
+
+    fn summary(violations: &[Violation]) -> Vec<(usize, Severity, &str)> {
+        violations
+            .iter()
+            .map(|v| (v.location.line, v.severity, v.message.as_str()))
+            .collect()
+    }
+
+    const CODE_WITH_TODOS: &str = r#"
+// TODO(#123): retry on timeout
+fn f() {
+    let url = "http://example.com // TODO not a comment";
+    // FIXME: handle empty input
+    let x = 1; // XXX(JIRA-45) remove after migration
+    // TODOS are not markers, nor is a todo
+}
+"#;
+
+    #[test]
+    fn test_reports_markers_as_info() {
+        let violations = check_with(&TrackedTodos::new(), CODE_WITH_TODOS);
+        assert_eq!(
+            summary(&violations),
+            [
+                (2, Severity::Info, "TODO(#123): retry on timeout"),
+                (5, Severity::Info, "FIXME: handle empty input"),
+                (6, Severity::Info, "XXX(JIRA-45) remove after migration"),
+            ]
+        );
+        assert_eq!(violations[0].code, CODE);
+        assert_eq!(violations[1].location.column, 8);
+    }
+
+    #[test]
+    fn test_require_reference_escalates_unlinked() {
+        let rule = TrackedTodos::new().require_reference(true);
+        let violations = check_with(&rule, CODE_WITH_TODOS);
+        assert_eq!(
+            summary(&violations),
+            [
+                (2, Severity::Info, "TODO(#123): retry on timeout"),
+                (
+                    5,
+                    Severity::Warning,
+                    "FIXME: handle empty input (no tracking reference)"
+                ),
+                (6, Severity::Info, "XXX(JIRA-45) remove after migration"),
+            ]
+        );
+        assert!(violations[1].suggestion.is_some());
+    }
+
+    #[test]
+    fn test_reference_forms() {
+        assert!(has_reference("(#1)"));
+        assert!(has_reference("(PROJ2-45): text"));
+        assert!(!has_reference(": (#123)"));
+        assert!(!has_reference("(alice)"));
+        assert!(!has_reference("(proj-45)"));
+        assert!(!has_reference("(#)"));
+    }
+
+    #[test]
+    fn test_respects_allow_and_tests() {
+        let code = r#"
+// TODO: kept
+// arch-lint: allow(tracked-todos) reason: tracked on the board
+// TODO: directive above
+#[arch_lint::allow(tracked_todos, reason = "legacy")]
+fn legacy() {
+    // FIXME: inside allowed fn
+}
+
+#[cfg(test)]
+mod tests {
+    // TODO: test helper
+}
+"#;
+        let violations = check_with(&TrackedTodos::new(), code);
+        assert_eq!(summary(&violations), [(2, Severity::Info, "TODO: kept")]);
+        let all = check_with(&TrackedTodos::new().allow_in_tests(false), code);
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_configure_options() {
+        let config = arch_lint_core::Config::parse(
+            "[rules.tracked-todos]\nrequire_reference = true\nmarkers = [\"HACK\"]\n",
+        )
+        .expect("parse config");
+        let mut rule = TrackedTodos::new();
+        rule.configure(&config.rules[NAME]).expect("valid options");
+        assert!(rule.require_reference);
+        let violations = check_with(&rule, "// HACK: x\n// TODO: y\nfn f() {}\n");
+        assert_eq!(
+            summary(&violations),
+            [(1, Severity::Warning, "HACK: x (no tracking reference)")]
+        );
+    }
+}
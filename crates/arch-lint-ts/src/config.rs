@@ -7,7 +7,7 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use arch_lint_core::Severity;
+use arch_lint_core::{ConfigFormat, Severity};
 
 /// Top-level architecture lint configuration.
 #[derive(Debug, Clone, Deserialize)]
@@ -31,6 +31,12 @@ pub struct ArchConfig {
     /// Custom constraints.
     #[serde(default)]
     pub constraints: Vec<Constraint>,
+
+    /// Also check the layer graph as a whole: the declared `dependencies`
+    /// must be acyclic, and imports must not close a layer cycle
+    /// (`[analyzer] transitive_layers = true`).
+    #[serde(default)]
+    pub transitive_layers: bool,
 }
 
 /// A named architecture layer.
@@ -38,7 +44,7 @@ pub struct ArchConfig {
 pub struct LayerDef {
     /// Layer name (e.g., `"domain"`, `"infrastructure"`).
     pub name: String,
-    /// Package prefixes belonging to this layer.
+    /// Package prefixes belonging to this layer; `*` matches one segment.
     pub packages: Vec<String>,
 }
 
@@ -53,6 +59,10 @@ pub struct Constraint {
     #[serde(default)]
     pub pattern: String,
 
+    /// Treat `pattern` as a regular expression instead of a substring.
+    #[serde(default)]
+    pub regex: bool,
+
     /// Layers this constraint applies to.
     #[serde(default)]
     pub in_layers: Vec<String>,
@@ -69,15 +79,62 @@ pub struct Constraint {
     #[serde(default)]
     pub import_matches: String,
 
-    /// Source file must have a declaration matching this substring (used by `naming-rule`).
+    /// Source file must have a declaration matching this [`NamePattern`] (used by `naming-rule`).
     #[serde(default)]
     pub source_must_match: String,
 
-    /// Source file must NOT have a declaration matching this substring (used by `naming-rule`).
+    /// Source file must NOT have a declaration matching this [`NamePattern`] (used by `naming-rule`).
     #[serde(default)]
     pub source_must_not_match: String,
 }
 
+/// A declaration-name matcher parsed from `source_must_match` /
+/// `source_must_not_match`.
+///
+/// `startsWith:Foo`, `endsWith:Foo` and `regex:^Foo$` select the match kind;
+/// a value without prefix matches as a substring.
+#[derive(Debug, Clone)]
+pub enum NamePattern {
+    /// Name contains the substring.
+    Contains(String),
+    /// Name starts with the prefix.
+    StartsWith(String),
+    /// Name ends with the suffix.
+    EndsWith(String),
+    /// Name matches the regular expression.
+    Regex(regex::Regex),
+}
+
+impl NamePattern {
+    /// Parse a pattern string.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a `regex:` pattern does not compile.
+    pub fn parse(pattern: &str) -> Result<Self, regex::Error> {
+        if let Some(rest) = pattern.strip_prefix("startsWith:") {
+            Ok(Self::StartsWith(rest.to_owned()))
+        } else if let Some(rest) = pattern.strip_prefix("endsWith:") {
+            Ok(Self::EndsWith(rest.to_owned()))
+        } else if let Some(rest) = pattern.strip_prefix("regex:") {
+            regex::Regex::new(rest).map(Self::Regex)
+        } else {
+            Ok(Self::Contains(pattern.to_owned()))
+        }
+    }
+
+    /// Whether `name` matches this pattern.
+    #[must_use]
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Contains(s) => name.contains(s.as_str()),
+            Self::StartsWith(s) => name.starts_with(s.as_str()),
+            Self::EndsWith(s) => name.ends_with(s.as_str()),
+            Self::Regex(r) => r.is_match(name),
+        }
+    }
+}
+
 fn default_root() -> PathBuf {
     PathBuf::from(".")
 }
@@ -97,7 +154,7 @@ pub enum ConfigError {
         /// IO error.
         source: std::io::Error,
     },
-    /// Failed to parse TOML.
+    /// Failed to parse the config (TOML, YAML, or JSON).
     #[error("invalid config: {message}")]
     Parse {
         /// Parse error detail.
@@ -106,10 +163,94 @@ pub enum ConfigError {
     /// Config is structurally invalid.
     #[error("config validation: {0}")]
     Validation(String),
+    /// Every consistency problem found by [`ArchConfig::validate`].
+    #[error("config validation errors:\n{}", format_problems(.0))]
+    Invalid(Vec<ConfigProblem>),
+}
+
+/// A single consistency problem in an [`ArchConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigProblem {
+    /// Two `[[layers]]` share a name.
+    #[error("layer '{name}' is defined more than once")]
+    DuplicateLayer {
+        /// The repeated layer name.
+        name: String,
+    },
+    /// A `[dependencies]` key is not a defined layer.
+    #[error("dependencies.{layer}: unknown layer")]
+    UnknownLayer {
+        /// The undefined layer.
+        layer: String,
+    },
+    /// A `[dependencies]` value is not a defined layer.
+    #[error("dependencies.{layer}: unknown dep '{dep}'")]
+    UnknownDependency {
+        /// The layer declaring the dependency.
+        layer: String,
+        /// The undefined dependency.
+        dep: String,
+    },
+    /// A layer lists itself as a dependency.
+    #[error("dependencies.{layer}: self-dependency")]
+    SelfDependency {
+        /// The layer.
+        layer: String,
+    },
+    /// A layer has no `[dependencies]` entry.
+    #[error("layer '{layer}' has no entry in [dependencies]")]
+    MissingDependencies {
+        /// The layer.
+        layer: String,
+    },
+    /// A constraint `type` is not recognized.
+    #[error(
+        "constraints[{index}]: unknown type '{kind}', expected one of: {}",
+        CONSTRAINT_KINDS.join(", ")
+    )]
+    UnknownConstraintKind {
+        /// Constraint index.
+        index: usize,
+        /// The unrecognized type.
+        kind: String,
+    },
+    /// A constraint's `in_layers` names an undefined layer.
+    #[error("constraints[{index}]: unknown layer '{layer}'")]
+    UnknownConstraintLayer {
+        /// Constraint index.
+        index: usize,
+        /// The undefined layer.
+        layer: String,
+    },
+    /// A constraint pattern does not compile.
+    #[error("constraints[{index}] ({message}): invalid {key} '{pattern}': {reason}")]
+    InvalidPattern {
+        /// Constraint index.
+        index: usize,
+        /// The constraint's message, for context.
+        message: String,
+        /// The offending key (`regex`, `source_must_match`, ...).
+        key: &'static str,
+        /// The pattern as written.
+        pattern: String,
+        /// Why it is invalid.
+        reason: String,
+    },
+}
+
+/// Recognized constraint `type` values.
+pub const CONSTRAINT_KINDS: &[&str] = &["no-import-pattern", "naming-rule"];
+
+fn format_problems(problems: &[ConfigProblem]) -> String {
+    problems
+        .iter()
+        .map(|p| format!("  - {p}"))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 impl ArchConfig {
-    /// Load from a TOML file.
+    /// Load from a TOML, YAML, or JSON file, picked by extension.
     ///
     /// # Errors
     ///
@@ -119,7 +260,7 @@ impl ArchConfig {
             path: path.to_path_buf(),
             source: e,
         })?;
-        Self::parse(&content)
+        Self::parse_as(&content, ConfigFormat::from_path(path))
     }
 
     /// Parse from a TOML string.
@@ -128,6 +269,15 @@ impl ArchConfig {
     ///
     /// Returns error if TOML is invalid.
     pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        Self::parse_as(content, ConfigFormat::Toml)
+    }
+
+    /// Parse from a string in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the content is invalid for `format`.
+    pub fn parse_as(content: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
         /// Wrapper to handle `[analyzer]` section in the TOML.
         #[derive(Deserialize)]
         struct RawConfig {
@@ -147,11 +297,16 @@ impl ArchConfig {
             root: PathBuf,
             #[serde(default)]
             exclude: Vec<String>,
+            #[serde(default)]
+            transitive_layers: bool,
         }
 
-        let raw: RawConfig = toml::from_str(content).map_err(|e| ConfigError::Parse {
-            message: e.to_string(),
-        })?;
+        let parsed = match format {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        };
+        let raw: RawConfig = parsed.map_err(|message| ConfigError::Parse { message })?;
 
         Ok(Self {
             root: raw.analyzer.root,
@@ -159,58 +314,136 @@ impl ArchConfig {
             layers: raw.layers,
             dependencies: raw.dependencies,
             constraints: raw.constraints,
+            transitive_layers: raw.analyzer.transitive_layers,
         })
     }
 
+    /// Parse and validate from a TOML string.
+    ///
+    /// ```
+    /// use arch_lint_ts::ArchConfig;
+    ///
+    /// let config = ArchConfig::from_toml(
+    ///     r#"
+    /// [[layers]]
+    /// name = "domain"
+    /// packages = ["com.example.domain"]
+    ///
+    /// [dependencies]
+    /// domain = []
+    /// "#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(config.layers[0].name, "domain");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Parse`] if the TOML is invalid, or
+    /// [`ConfigError::Invalid`] listing every problem found by
+    /// [`validate`](Self::validate).
+    pub fn from_toml(content: &str) -> Result<Self, ConfigError> {
+        let config = Self::parse(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
     /// Validate config consistency.
     ///
     /// # Errors
     ///
-    /// Returns error describing the first problem found.
+    /// Returns [`ConfigError::Invalid`] listing every problem found.
     pub fn validate(&self) -> Result<(), ConfigError> {
-        let layer_names: std::collections::HashSet<&str> =
-            self.layers.iter().map(|l| l.name.as_str()).collect();
+        let problems = self.problems();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
 
-        for (layer, deps) in &self.dependencies {
+    /// Collects every consistency problem, in config order.
+    fn problems(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+        let mut layer_names = std::collections::HashSet::new();
+        for l in &self.layers {
+            if !layer_names.insert(l.name.as_str()) {
+                problems.push(ConfigProblem::DuplicateLayer {
+                    name: l.name.clone(),
+                });
+            }
+        }
+
+        let mut dependencies: Vec<_> = self.dependencies.iter().collect();
+        dependencies.sort_by_key(|(layer, _)| layer.as_str());
+        for (layer, deps) in dependencies {
             if !layer_names.contains(layer.as_str()) {
-                return Err(ConfigError::Validation(format!(
-                    "dependencies.{layer}: unknown layer"
-                )));
+                problems.push(ConfigProblem::UnknownLayer {
+                    layer: layer.clone(),
+                });
             }
             for dep in deps {
                 if !layer_names.contains(dep.as_str()) {
-                    return Err(ConfigError::Validation(format!(
-                        "dependencies.{layer}: unknown dep '{dep}'"
-                    )));
+                    problems.push(ConfigProblem::UnknownDependency {
+                        layer: layer.clone(),
+                        dep: dep.clone(),
+                    });
                 }
             }
             if deps.contains(layer) {
-                return Err(ConfigError::Validation(format!(
-                    "dependencies.{layer}: self-dependency"
-                )));
+                problems.push(ConfigProblem::SelfDependency {
+                    layer: layer.clone(),
+                });
             }
         }
 
         for (i, c) in self.constraints.iter().enumerate() {
+            if !CONSTRAINT_KINDS.contains(&c.kind.as_str()) {
+                problems.push(ConfigProblem::UnknownConstraintKind {
+                    index: i,
+                    kind: c.kind.clone(),
+                });
+            }
             for l in &c.in_layers {
                 if !layer_names.contains(l.as_str()) {
-                    return Err(ConfigError::Validation(format!(
-                        "constraints[{i}]: unknown layer '{l}'"
-                    )));
+                    problems.push(ConfigProblem::UnknownConstraintLayer {
+                        index: i,
+                        layer: l.clone(),
+                    });
+                }
+            }
+            let invalid =
+                |key, pattern: &str, reason: regex::Error| ConfigProblem::InvalidPattern {
+                    index: i,
+                    message: c.message.clone(),
+                    key,
+                    pattern: pattern.to_owned(),
+                    reason: reason.to_string(),
+                };
+            if c.regex {
+                if let Err(e) = regex::Regex::new(&c.pattern) {
+                    problems.push(invalid("regex", &c.pattern, e));
+                }
+            }
+            for (key, value) in [
+                ("source_must_match", &c.source_must_match),
+                ("source_must_not_match", &c.source_must_not_match),
+            ] {
+                if let Err(e) = NamePattern::parse(value) {
+                    problems.push(invalid(key, value, e));
                 }
             }
         }
 
         for l in &self.layers {
             if !self.dependencies.contains_key(&l.name) {
-                return Err(ConfigError::Validation(format!(
-                    "layer '{}' has no entry in [dependencies]",
-                    l.name
-                )));
+                problems.push(ConfigProblem::MissingDependencies {
+                    layer: l.name.clone(),
+                });
             }
         }
 
-        Ok(())
+        problems
     }
 }
 
@@ -218,6 +451,18 @@ impl ArchConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_yaml_and_json_configs() {
+        let yaml = "analyzer:\n  root: kotlin\nlayers:\n  - name: domain\n    packages: [com.example.domain]\ndependencies:\n  domain: []\n";
+        let config = ArchConfig::parse_as(yaml, ConfigFormat::Yaml).expect("parse yaml");
+        assert_eq!(config.layers[0].name, "domain");
+        assert_eq!(config.root, PathBuf::from("kotlin"));
+
+        let json = r#"{"layers": [{"name": "domain", "packages": ["com.example.domain"]}], "dependencies": {"domain": []}}"#;
+        let config = ArchConfig::parse_as(json, ConfigFormat::Json).expect("parse json");
+        assert_eq!(config.layers[0].name, "domain");
+    }
+
     #[test]
     fn parse_minimal_config() {
         let toml = r#"
@@ -313,4 +558,140 @@ domain = ["domain"]
         let config = ArchConfig::parse(toml).expect("parse failed");
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn validate_catches_invalid_regex_pattern() {
+        let toml = r#"
+[[layers]]
+name = "domain"
+packages = ["com.example.domain"]
+
+[dependencies]
+domain = []
+
+[[constraints]]
+type = "no-import-pattern"
+pattern = "^java\\.sql\\.(?!Date)"
+regex = true
+in_layers = ["domain"]
+message = "No JDBC in domain"
+"#;
+        let config = ArchConfig::parse(toml).expect("parse failed");
+        assert!(config.constraints[0].regex);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("constraints[0] (No JDBC in domain)"), "{err}");
+        assert!(err.contains("invalid regex"), "{err}");
+    }
+
+    #[test]
+    fn name_pattern_prefixes() {
+        let ends = NamePattern::parse("endsWith:Service").expect("parse");
+        assert!(ends.matches("UserService"));
+        assert!(!ends.matches("ServiceLocator"));
+        assert!(!ends.matches("MyServiceHelper"));
+
+        let starts = NamePattern::parse("startsWith:Service").expect("parse");
+        assert!(starts.matches("ServiceLocator"));
+        assert!(!starts.matches("UserService"));
+
+        let regex = NamePattern::parse("regex:^[A-Z]\\w*Service$").expect("parse");
+        assert!(regex.matches("UserService"));
+        assert!(!regex.matches("ServiceLocator"));
+
+        let contains = NamePattern::parse("Service").expect("parse");
+        assert!(contains.matches("MyServiceHelper"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_name_regex() {
+        let toml = r#"
+[[layers]]
+name = "domain"
+packages = ["com.example.domain"]
+
+[dependencies]
+domain = []
+
+[[constraints]]
+type = "naming-rule"
+import_matches = "Repository"
+source_must_match = "regex:(Service"
+in_layers = ["domain"]
+message = "Only services"
+"#;
+        let config = ArchConfig::parse(toml).expect("parse failed");
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("invalid source_must_match"), "{err}");
+    }
+
+    #[test]
+    fn from_toml_reports_every_problem() {
+        let toml = r#"
+[[layers]]
+name = "domain"
+packages = ["com.example.domain"]
+
+[[layers]]
+name = "domain"
+packages = ["com.example.core"]
+
+[dependencies]
+domain = []
+infra = ["domain"]
+
+[[constraints]]
+type = "no-import-patern"
+pattern = "java.sql"
+in_layers = ["domain"]
+"#;
+        let Err(ConfigError::Invalid(problems)) = ArchConfig::from_toml(toml) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(
+            problems,
+            vec![
+                ConfigProblem::DuplicateLayer {
+                    name: "domain".into()
+                },
+                ConfigProblem::UnknownLayer {
+                    layer: "infra".into()
+                },
+                ConfigProblem::UnknownConstraintKind {
+                    index: 0,
+                    kind: "no-import-patern".into()
+                },
+            ]
+        );
+
+        let message = ArchConfig::from_toml(toml).unwrap_err().to_string();
+        assert!(message.contains("  - layer 'domain' is defined more than once"));
+        assert!(message.contains("expected one of: no-import-pattern, naming-rule"));
+    }
+
+    #[test]
+    fn from_toml_accepts_valid_config() {
+        let toml = r#"
+[[layers]]
+name = "domain"
+packages = ["com.example.domain"]
+
+[dependencies]
+domain = []
+
+[[constraints]]
+type = "naming-rule"
+import_matches = "Repository"
+source_must_match = "endsWith:Service"
+in_layers = ["domain"]
+"#;
+        assert!(ArchConfig::from_toml(toml).is_ok());
+    }
+
+    #[test]
+    fn from_toml_reports_parse_errors() {
+        assert!(matches!(
+            ArchConfig::from_toml("[[layers]]\nname = 1"),
+            Err(ConfigError::Parse { .. })
+        ));
+    }
 }
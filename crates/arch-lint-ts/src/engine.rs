@@ -2,25 +2,65 @@
 //!
 //! Evaluates layer dependency rules and pattern constraints
 //! against a [`FileAnalysis`], producing [`Violation`]s from arch-lint-core.
+//!
+//! Import violations can be suppressed with the same comment directives as
+//! Rust code (`// arch-lint: allow(layer-dependency) reason="..."`) on or
+//! directly above the import. Error-severity suppressions need a `reason`.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
-use arch_lint_core::{Location, Severity, Violation};
+use arch_lint_core::{Location, Severity, Suggestion, Violation};
+use regex::Regex;
 
-use crate::config::ArchConfig;
-use crate::extractor::FileAnalysis;
+use crate::config::{ArchConfig, NamePattern};
+use crate::extractor::{FileAnalysis, ImportInfo};
 use crate::layer::LayerResolver;
 
 /// Evaluates architecture rules against extracted file analysis.
 pub struct ArchRuleEngine {
     config: ArchConfig,
     resolver: LayerResolver,
+    /// Compiled `regex = true` constraint patterns, indexed like `config.constraints`.
+    patterns: Vec<Option<Regex>>,
+    /// Parsed `source_must_match` / `source_must_not_match`, indexed like `config.constraints`.
+    name_patterns: Vec<(Option<NamePattern>, Option<NamePattern>)>,
 }
 
 impl ArchRuleEngine {
     /// Create a new engine from config.
+    ///
+    /// Regex and naming patterns are compiled once here; an invalid pattern
+    /// (rejected by [`ArchConfig::validate`]) never matches.
     #[must_use]
     pub fn new(config: ArchConfig) -> Self {
         let resolver = LayerResolver::new(&config);
-        Self { config, resolver }
+        let patterns = config
+            .constraints
+            .iter()
+            .map(|c| {
+                if c.regex {
+                    Regex::new(&c.pattern).ok()
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let name_patterns = config
+            .constraints
+            .iter()
+            .map(|c| {
+                (
+                    parse_name_pattern(&c.source_must_match),
+                    parse_name_pattern(&c.source_must_not_match),
+                )
+            })
+            .collect();
+        Self {
+            config,
+            resolver,
+            patterns,
+            name_patterns,
+        }
     }
 
     /// Check a single file analysis for architecture violations.
@@ -30,9 +70,140 @@ impl ArchRuleEngine {
         violations.extend(self.check_layer_deps(analysis));
         violations.extend(self.check_constraints(analysis));
         violations.extend(self.check_naming_rules(analysis));
+        Self::apply_allows(analysis, violations)
+    }
+
+    /// Check the layer graph across all files (`transitive_layers = true`).
+    ///
+    /// A cycle in the declared `dependencies` is reported once, located at
+    /// the config root. Otherwise every import whose layer edge is outside
+    /// the transitive closure of the declared dependencies and closes a
+    /// cycle among the imports of all files is reported with the cycle path.
+    /// Returns nothing unless the flag is set.
+    #[must_use]
+    pub fn check_project(&self, analyses: &[FileAnalysis]) -> Vec<Violation> {
+        if !self.config.transitive_layers {
+            return Vec::new();
+        }
+
+        let declared: LayerGraph<'_> = self
+            .config
+            .dependencies
+            .iter()
+            .map(|(layer, deps)| {
+                let deps = deps.iter().map(String::as_str).filter(|d| d != layer);
+                (layer.as_str(), deps.collect())
+            })
+            .collect();
+        if let Some(cycle) = find_cycle(&declared) {
+            return vec![Violation::new(
+                "LAYER002",
+                "layer-cycle",
+                Severity::Error,
+                Location::new(self.config.root.clone(), 0, 0),
+                format!(
+                    "Declared layer dependencies form a cycle: {}",
+                    cycle.join(" -> ")
+                ),
+            )];
+        }
+
+        let mut observed = LayerGraph::new();
+        for analysis in analyses {
+            for (_, from, to) in self.layer_edges(analysis) {
+                observed.entry(from).or_default().insert(to);
+            }
+        }
+
+        let mut violations = Vec::new();
+        for analysis in analyses {
+            let mut file_violations = Vec::new();
+            for (imp, from, to) in self.layer_edges(analysis) {
+                if find_path(&declared, from, to).is_some() {
+                    continue;
+                }
+                let Some(back) = find_path(&observed, to, from) else {
+                    continue;
+                };
+                let mut cycle = vec![from];
+                cycle.extend(back);
+                file_violations.push(Violation::new(
+                    "LAYER002",
+                    "layer-cycle",
+                    Severity::Error,
+                    Location::new(analysis.file_path.clone(), imp.line, imp.column + 1),
+                    format!(
+                        "{from} -> {to} closes a layer cycle: {}",
+                        cycle.join(" -> ")
+                    ),
+                ));
+            }
+            violations.extend(Self::apply_allows(analysis, file_violations));
+        }
         violations
     }
 
+    /// Cross-layer import edges of a file: (import, from layer, to layer).
+    fn layer_edges<'a>(
+        &'a self,
+        analysis: &'a FileAnalysis,
+    ) -> Vec<(&'a ImportInfo, &'a str, &'a str)> {
+        let Some(from) = analysis
+            .package
+            .as_ref()
+            .and_then(|p| self.resolver.resolve(&p.path))
+        else {
+            return Vec::new();
+        };
+        analysis
+            .imports
+            .iter()
+            .filter_map(|imp| {
+                let to = self.resolver.resolve(&imp.path)?;
+                (to != from).then_some((imp, from, to))
+            })
+            .collect()
+    }
+
+    /// Drops violations suppressed by an allow directive on their import.
+    ///
+    /// A suppressed error without a `reason` is replaced by a warning about
+    /// the missing reason, as for Rust rules.
+    fn apply_allows(analysis: &FileAnalysis, violations: Vec<Violation>) -> Vec<Violation> {
+        let mut kept = Vec::new();
+        for violation in violations {
+            let allow = analysis
+                .imports
+                .iter()
+                .filter(|imp| imp.line == violation.location.line)
+                .flat_map(|imp| &imp.allows)
+                .find(|d| d.covers(&violation.rule) || d.covers(&violation.code));
+
+            match allow {
+                None => kept.push(violation),
+                Some(d) if violation.severity == Severity::Error && d.reason.is_none() => {
+                    kept.push(
+                        Violation::new(
+                            violation.code.clone(),
+                            violation.rule.clone(),
+                            Severity::Warning,
+                            violation.location.clone(),
+                            format!(
+                                "Allow directive for '{}' is missing required reason",
+                                violation.rule
+                            ),
+                        )
+                        .with_suggestion(Suggestion::new(
+                            "Add reason=\"...\" to explain why this exception is necessary",
+                        )),
+                    );
+                }
+                Some(_) => {}
+            }
+        }
+        kept
+    }
+
     fn check_layer_deps(&self, analysis: &FileAnalysis) -> Vec<Violation> {
         let package = match &analysis.package {
             Some(p) => &p.path,
@@ -95,7 +266,9 @@ impl ArchRuleEngine {
 
         let mut violations = Vec::new();
 
-        for constraint in &self.config.constraints {
+        for (constraint, (must_match, must_not_match)) in
+            self.config.constraints.iter().zip(&self.name_patterns)
+        {
             if constraint.kind != "naming-rule" {
                 continue;
             }
@@ -111,11 +284,10 @@ impl ArchRuleEngine {
                     continue;
                 }
 
-                // source_must_match: at least one declaration must contain the substring
-                if !constraint.source_must_match.is_empty()
-                    && !decl_names
-                        .iter()
-                        .any(|n| n.contains(&constraint.source_must_match))
+                // source_must_match: at least one declaration must match
+                if must_match
+                    .as_ref()
+                    .is_some_and(|p| !decl_names.iter().any(|n| p.matches(n)))
                 {
                     violations.push(Violation::new(
                         "NAMING001",
@@ -126,11 +298,10 @@ impl ArchRuleEngine {
                     ));
                 }
 
-                // source_must_not_match: no declaration should contain the substring
-                if !constraint.source_must_not_match.is_empty()
-                    && decl_names
-                        .iter()
-                        .any(|n| n.contains(&constraint.source_must_not_match))
+                // source_must_not_match: no declaration should match
+                if must_not_match
+                    .as_ref()
+                    .is_some_and(|p| decl_names.iter().any(|n| p.matches(n)))
                 {
                     violations.push(Violation::new(
                         "NAMING001",
@@ -159,7 +330,7 @@ impl ArchRuleEngine {
 
         let mut violations = Vec::new();
 
-        for constraint in &self.config.constraints {
+        for (constraint, regex) in self.config.constraints.iter().zip(&self.patterns) {
             if constraint.kind != "no-import-pattern" {
                 continue;
             }
@@ -168,7 +339,12 @@ impl ArchRuleEngine {
             }
 
             for imp in &analysis.imports {
-                if imp.path.contains(&constraint.pattern) {
+                let matched = if constraint.regex {
+                    regex.as_ref().is_some_and(|r| r.is_match(&imp.path))
+                } else {
+                    imp.path.contains(&constraint.pattern)
+                };
+                if matched {
                     violations.push(Violation::new(
                         "PATTERN001",
                         "import-pattern",
@@ -184,6 +360,55 @@ impl ArchRuleEngine {
     }
 }
 
+/// Layer dependency graph, ordered for deterministic reports.
+type LayerGraph<'a> = BTreeMap<&'a str, BTreeSet<&'a str>>;
+
+/// Shortest path from `from` to `to` (both included), if any.
+fn find_path<'a>(graph: &LayerGraph<'a>, from: &'a str, to: &'a str) -> Option<Vec<&'a str>> {
+    let mut previous: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(layer) = queue.pop_front() {
+        for &next in graph.get(layer).into_iter().flatten() {
+            if next == from || previous.contains_key(next) {
+                continue;
+            }
+            previous.insert(next, layer);
+            if next == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while let Some(&prev) = previous.get(current) {
+                    path.push(prev);
+                    current = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+/// A cycle in `graph` as `a -> b -> ... -> a`, if any.
+fn find_cycle<'a>(graph: &LayerGraph<'a>) -> Option<Vec<&'a str>> {
+    graph.iter().find_map(|(&layer, deps)| {
+        deps.iter().find_map(|&dep| {
+            let back = find_path(graph, dep, layer)?;
+            let mut cycle = vec![layer];
+            cycle.extend(back);
+            Some(cycle)
+        })
+    })
+}
+
+/// Parses a naming pattern; empty or invalid patterns yield `None`.
+fn parse_name_pattern(pattern: &str) -> Option<NamePattern> {
+    if pattern.is_empty() {
+        return None;
+    }
+    NamePattern::parse(pattern).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,6 +442,7 @@ mod tests {
             .into_iter()
             .collect(),
             constraints: vec![],
+            transitive_layers: false,
         }
     }
 
@@ -234,6 +460,7 @@ mod tests {
                     line: i + 2,
                     column: 0,
                     path: (*p).into(),
+                    allows: vec![],
                 })
                 .collect(),
             declarations: vec![],
@@ -284,6 +511,7 @@ mod tests {
                 line: 1,
                 column: 0,
                 path: "com.example.infra.Foo".into(),
+                allows: vec![],
             }],
             declarations: vec![],
         };
@@ -294,6 +522,7 @@ mod tests {
         Constraint {
             kind: "no-import-pattern".into(),
             pattern: pattern.into(),
+            regex: false,
             in_layers: in_layers.iter().map(|s| (*s).into()).collect(),
             severity: Severity::Warning,
             message: message.into(),
@@ -313,6 +542,7 @@ mod tests {
         Constraint {
             kind: "naming-rule".into(),
             pattern: String::new(),
+            regex: false,
             in_layers: in_layers.iter().map(|s| (*s).into()).collect(),
             severity: Severity::Error,
             message: message.into(),
@@ -337,6 +567,7 @@ mod tests {
                     line: i + 2,
                     column: 0,
                     path: (*p).into(),
+                    allows: vec![],
                 })
                 .collect(),
             declarations: decl_names
@@ -384,6 +615,43 @@ mod tests {
         assert!(engine.check(&a).is_empty());
     }
 
+    #[test]
+    fn regex_pattern_constraint_matches_anchored_pattern() {
+        let mut config = test_config();
+        let mut constraint = make_pattern_constraint(
+            r"^java\.sql\.(Connection|Statement)$",
+            &["domain"],
+            "No JDBC",
+        );
+        constraint.regex = true;
+        config.constraints.push(constraint);
+
+        let engine = ArchRuleEngine::new(config);
+        let a = make_analysis(
+            "com.example.domain.model",
+            &[
+                "java.sql.Connection",
+                "java.sql.Date",
+                "com.acme.java.sql.Statement",
+            ],
+        );
+        let v = engine.check(&a);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].location.line, 2);
+    }
+
+    #[test]
+    fn non_regex_pattern_is_literal_substring() {
+        let mut config = test_config();
+        config
+            .constraints
+            .push(make_pattern_constraint("java.sql$", &["domain"], "No JDBC"));
+
+        let engine = ArchRuleEngine::new(config);
+        let a = make_analysis("com.example.domain.model", &["java.sql"]);
+        assert!(engine.check(&a).is_empty());
+    }
+
     // --- naming-rule tests ---
 
     /// Config that allows app → infra (for testing naming rules in isolation)
@@ -506,4 +774,148 @@ mod tests {
         );
         assert!(engine.check(&a).is_empty());
     }
+
+    #[test]
+    fn naming_rule_ends_with_and_starts_with() {
+        let naming_violations = |source_must_match: &str, decl: &str| {
+            let mut config = test_config_with_infra();
+            config.constraints.push(make_naming_constraint(
+                "RepositoryImpl",
+                source_must_match,
+                "",
+                &["app"],
+                "Only Service can import RepositoryImpl",
+            ));
+            let a = make_analysis_with_decls(
+                "com.example.app.service",
+                &["com.example.infra.db.UserRepositoryImpl"],
+                &[decl],
+            );
+            ArchRuleEngine::new(config).check(&a).len()
+        };
+
+        assert_eq!(naming_violations("endsWith:Service", "UserService"), 0);
+        assert_eq!(naming_violations("endsWith:Service", "ServiceLocator"), 1);
+        assert_eq!(naming_violations("endsWith:Service", "MyServiceHelper"), 1);
+        assert_eq!(naming_violations("startsWith:Service", "ServiceLocator"), 0);
+        assert_eq!(naming_violations("startsWith:Service", "UserService"), 1);
+        assert_eq!(naming_violations("regex:^User\\w+$", "UserService"), 0);
+        // No prefix keeps substring matching
+        assert_eq!(naming_violations("Service", "MyServiceHelper"), 0);
+    }
+
+    // --- transitive layer tests ---
+
+    fn transitive_config() -> ArchConfig {
+        let mut config = test_config();
+        config.transitive_layers = true;
+        config
+    }
+
+    #[test]
+    fn reports_declared_dependency_cycle() {
+        let mut config = transitive_config();
+        config
+            .dependencies
+            .insert("domain".into(), vec!["infra".into()]);
+
+        let v = ArchRuleEngine::new(config).check_project(&[]);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].code, "LAYER002");
+        assert_eq!(
+            v[0].message,
+            "Declared layer dependencies form a cycle: app -> domain -> infra -> app"
+        );
+    }
+
+    #[test]
+    fn reports_import_closing_layer_cycle_with_path() {
+        let engine = ArchRuleEngine::new(transitive_config());
+        let analyses = [
+            make_analysis("com.example.app", &["com.example.domain.User"]),
+            make_analysis("com.example.domain", &["com.example.infra.Db"]),
+            make_analysis("com.example.infra", &["com.example.app.Service"]),
+        ];
+
+        let v = engine.check_project(&analyses);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].code, "LAYER002");
+        assert_eq!(v[0].location.line, 2);
+        assert_eq!(
+            v[0].message,
+            "domain -> infra closes a layer cycle: domain -> infra -> app -> domain"
+        );
+    }
+
+    #[test]
+    fn forbidden_import_without_cycle_is_left_to_layer001() {
+        let engine = ArchRuleEngine::new(transitive_config());
+        let analyses = [make_analysis(
+            "com.example.domain",
+            &["com.example.infra.Db"],
+        )];
+        assert!(engine.check_project(&analyses).is_empty());
+        assert_eq!(engine.check(&analyses[0])[0].code, "LAYER001");
+    }
+
+    #[test]
+    fn transitive_check_is_opt_in() {
+        let mut config = test_config();
+        config
+            .dependencies
+            .insert("domain".into(), vec!["infra".into()]);
+        let analyses = [
+            make_analysis("com.example.domain", &["com.example.infra.Db"]),
+            make_analysis("com.example.infra", &["com.example.domain.User"]),
+        ];
+        assert!(ArchRuleEngine::new(config)
+            .check_project(&analyses)
+            .is_empty());
+    }
+
+    // --- suppression tests ---
+
+    fn check_kotlin(config: ArchConfig, source: &str) -> Vec<Violation> {
+        use crate::extractor::LanguageExtractor;
+        let analysis = crate::kotlin::KotlinExtractor::new().analyze(source);
+        ArchRuleEngine::new(config).check(&analysis)
+    }
+
+    #[test]
+    fn allow_comment_with_reason_suppresses_import_violation() {
+        let source = "package com.example.domain.model\n\
+                      // arch-lint: allow(layer-dependency) reason=\"migration in progress\"\n\
+                      import com.example.infra.db.Repo\n\
+                      import com.example.infra.db.Other // arch-lint: allow(LAYER001) reason=\"legacy\"\n\
+                      import com.example.infra.db.Third\n";
+        let v = check_kotlin(test_config(), source);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].location.line, 5);
+        assert_eq!(v[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn allow_comment_without_reason_on_error_warns() {
+        let source = "package com.example.domain.model\n\
+                      // arch-lint: allow(layer-dependency)\n\
+                      import com.example.infra.db.Repo\n";
+        let v = check_kotlin(test_config(), source);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].severity, Severity::Warning);
+        assert!(v[0].message.contains("missing required reason"));
+    }
+
+    #[test]
+    fn allow_comment_without_reason_suppresses_warning() {
+        let mut config = test_config();
+        config.constraints.push(make_pattern_constraint(
+            "java.sql",
+            &["domain"],
+            "No JDBC in domain",
+        ));
+        let source = "package com.example.domain.model\n\
+                      // arch-lint: allow(import-pattern)\n\
+                      import java.sql.Connection\n";
+        assert!(check_kotlin(config, source).is_empty());
+    }
 }
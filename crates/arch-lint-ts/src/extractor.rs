@@ -6,6 +6,8 @@
 
 use std::path::PathBuf;
 
+use arch_lint_core::utils::allowance::AllowDirective;
+
 /// Package/module declaration extracted from source.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PackageInfo {
@@ -24,6 +26,8 @@ pub struct ImportInfo {
     pub column: usize,
     /// Fully qualified import path (e.g., `com.example.infra.db.UserRepository`).
     pub path: String,
+    /// `// arch-lint: allow(...)` directives on or directly above the import.
+    pub allows: Vec<AllowDirective>,
 }
 
 /// Kind of declaration.
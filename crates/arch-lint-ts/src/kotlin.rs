@@ -1,6 +1,8 @@
 //! Kotlin language extractor using Tree-sitter.
 
 use std::path::PathBuf;
+
+use arch_lint_core::utils::allowance::line_directives;
 use tree_sitter::{Language, Node, Parser};
 
 use crate::extractor::{
@@ -58,6 +60,7 @@ impl KotlinExtractor {
                     line: node.start_position().row + 1,
                     column: node.start_position().column,
                     path: Self::qualified_id(&child, src),
+                    allows: Vec::new(),
                 });
             }
         }
@@ -168,7 +171,8 @@ impl LanguageExtractor for KotlinExtractor {
                     result.package = Self::extract_package(&node, src);
                 }
                 "import" => {
-                    if let Some(imp) = Self::extract_import(&node, src) {
+                    if let Some(mut imp) = Self::extract_import(&node, src) {
+                        imp.allows = line_directives(source, imp.line);
                         result.imports.push(imp);
                     }
                 }
@@ -252,4 +256,18 @@ mod tests {
         let a = analyze("package com.example.infra.db\nclass RepoImpl { }\n");
         assert_eq!(a.declarations[0].package, "com.example.infra.db");
     }
+
+    #[test]
+    fn captures_allow_directives_on_imports() {
+        let a = analyze(
+            "package com.example.app\n\
+             // arch-lint: allow(layer-dependency) reason=\"wip\"\n\
+             import com.example.infra.Repo\n\
+             import com.example.domain.User\n",
+        );
+        assert_eq!(a.imports[0].allows.len(), 1);
+        assert!(a.imports[0].allows[0].covers("layer-dependency"));
+        assert_eq!(a.imports[0].allows[0].reason.as_deref(), Some("wip"));
+        assert!(a.imports[1].allows.is_empty());
+    }
 }
@@ -4,40 +4,55 @@ use crate::config::ArchConfig;
 
 /// Resolves fully-qualified package names to architecture layer names.
 ///
-/// Resolution uses longest-prefix-match so that more specific package
-/// prefixes take priority over broader ones.
+/// Package patterns are matched segment-wise as prefixes: `*` matches exactly
+/// one segment, so `com.example.*.domain` covers `com.example.billing.domain`
+/// and `com.example.users.domain.model`. When several patterns match, the one
+/// with more segments wins, and among equally long patterns the one with
+/// fewer wildcards wins.
 pub struct LayerResolver {
-    /// (package_prefix, layer_name) sorted by prefix length descending.
-    map: Vec<(String, String)>,
+    /// (package_pattern, layer_name) sorted most specific first.
+    map: Vec<(Vec<String>, String)>,
 }
 
 impl LayerResolver {
     /// Build a resolver from config.
     #[must_use]
     pub fn new(config: &ArchConfig) -> Self {
-        let mut map: Vec<(String, String)> = Vec::new();
+        let mut map: Vec<(Vec<String>, String)> = Vec::new();
         for layer in &config.layers {
             for pkg in &layer.packages {
-                map.push((pkg.clone(), layer.name.clone()));
+                let segments = pkg.split('.').map(str::to_string).collect();
+                map.push((segments, layer.name.clone()));
             }
         }
-        // Longest prefix first for correct matching
-        map.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        // Most specific pattern first for correct matching
+        map.sort_by_key(|(segments, _)| {
+            let wildcards = segments.iter().filter(|s| *s == "*").count();
+            (std::cmp::Reverse(segments.len()), wildcards)
+        });
         Self { map }
     }
 
     /// Which layer does this package belong to?
     #[must_use]
     pub fn resolve(&self, qualified_name: &str) -> Option<&str> {
-        for (prefix, layer_name) in &self.map {
-            if qualified_name == prefix || qualified_name.starts_with(&format!("{prefix}.")) {
-                return Some(layer_name);
-            }
-        }
-        None
+        let segments: Vec<&str> = qualified_name.split('.').collect();
+        self.map
+            .iter()
+            .find(|(pattern, _)| matches_prefix(pattern, &segments))
+            .map(|(_, layer_name)| layer_name.as_str())
     }
 }
 
+/// Whether `pattern` matches the leading segments of `segments`.
+fn matches_prefix(pattern: &[String], segments: &[&str]) -> bool {
+    pattern.len() <= segments.len()
+        && pattern
+            .iter()
+            .zip(segments)
+            .all(|(p, s)| p == "*" || p == s)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,6 +87,7 @@ mod tests {
             .into_iter()
             .collect(),
             constraints: vec![],
+            transitive_layers: false,
         }
     }
 
@@ -106,4 +122,47 @@ mod tests {
         // "com.example.domains" should NOT match "com.example.domain"
         assert_eq!(r.resolve("com.example.domains.Foo"), None);
     }
+
+    fn wildcard_config() -> ArchConfig {
+        let mut config = make_config();
+        config.layers.push(LayerDef {
+            name: "feature-domain".into(),
+            packages: vec!["com.example.*.domain".into()],
+        });
+        config.layers.push(LayerDef {
+            name: "billing".into(),
+            packages: vec!["com.example.billing.domain".into()],
+        });
+        config
+    }
+
+    #[test]
+    fn wildcard_matches_multiple_packages() {
+        let r = LayerResolver::new(&wildcard_config());
+        assert_eq!(
+            r.resolve("com.example.users.domain"),
+            Some("feature-domain")
+        );
+        assert_eq!(
+            r.resolve("com.example.orders.domain.model.Order"),
+            Some("feature-domain")
+        );
+        // A wildcard spans exactly one segment
+        assert_eq!(r.resolve("com.example.a.b.domain.Foo"), None);
+        assert_eq!(r.resolve("com.example.users.domains.Foo"), None);
+    }
+
+    #[test]
+    fn literal_wins_over_wildcard() {
+        let r = LayerResolver::new(&wildcard_config());
+        assert_eq!(
+            r.resolve("com.example.billing.domain.Invoice"),
+            Some("billing")
+        );
+        // Longer wildcard pattern still beats a shorter literal prefix
+        assert_eq!(
+            r.resolve("com.example.infra.domain.Foo"),
+            Some("feature-domain")
+        );
+    }
 }
@@ -0,0 +1,171 @@
+//! Analyzer benchmarks.
+//!
+//! Run with `cargo bench -p arch-lint`. Inputs come from [`fixture`], so
+//! results are comparable across runs and machines.
+
+#![allow(clippy::expect_used, missing_docs)]
+
+mod fixture;
+
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use arch_lint::declarative::{load_rules_from_toml, model::GlobPattern};
+use arch_lint::rules::{all_rules, recommended_rules, OrphanModules};
+use arch_lint::{Analyzer, FileContext};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+fn parse_large_file(c: &mut Criterion) {
+    let source = fixture::module_source(500);
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function("large_file", |b| {
+        b.iter(|| syn::parse_file(black_box(&source)).expect("parse fixture"));
+    });
+    group.finish();
+}
+
+fn recommended_over_crate(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    fixture::write_crate(dir.path(), 64, 20);
+
+    let mut group = c.benchmark_group("analyzer/recommended");
+    group.sample_size(20);
+    for parallel in [false, true] {
+        let name = if parallel { "parallel" } else { "sequential" };
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                recommended_rules()
+                    .into_iter()
+                    .fold(Analyzer::builder().root(dir.path()), |builder, rule| {
+                        builder.rule_box(rule)
+                    })
+                    .parallel(parallel)
+                    .build()
+                    .expect("build analyzer")
+                    .analyze()
+                    .expect("analyze fixture")
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Number of rules in the per-file benchmark.
+const RULES_PER_FILE: usize = 50;
+
+fn rules_per_file(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let path = dir.path().join("src/lib.rs");
+    let source = fixture::module_source(500);
+
+    let mut group = c.benchmark_group("analyzer/50_rules_one_file");
+    group.sample_size(20);
+    for parallel in [false, true] {
+        let name = if parallel { "parallel" } else { "sequential" };
+        let analyzer = all_rules()
+            .into_iter()
+            .chain(all_rules())
+            .take(RULES_PER_FILE)
+            .fold(Analyzer::builder().root(dir.path()), |builder, rule| {
+                builder.rule_box(rule)
+            })
+            .parallel(parallel)
+            .split_rules(parallel)
+            .build()
+            .expect("build analyzer");
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                analyzer
+                    .analyze_source(&path, black_box(&source))
+                    .expect("analyze fixture")
+            });
+        });
+    }
+    group.finish();
+}
+
+fn project_rule_asts(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    fixture::write_crate(dir.path(), 64, 20);
+
+    let mut group = c.benchmark_group("analyzer/project_asts");
+    group.sample_size(20);
+    for parallel in [false, true] {
+        let name = if parallel {
+            "parallel_reparse"
+        } else {
+            "sequential_shared"
+        };
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                recommended_rules()
+                    .into_iter()
+                    .fold(Analyzer::builder().root(dir.path()), |builder, rule| {
+                        builder.rule_box(rule)
+                    })
+                    .project_rule(OrphanModules::new())
+                    .parallel(parallel)
+                    .build()
+                    .expect("build analyzer")
+                    .analyze()
+                    .expect("analyze fixture")
+            });
+        });
+    }
+    group.finish();
+}
+
+fn declarative_scopes(c: &mut Criterion) {
+    let root = Path::new("/fixture");
+    let source = fixture::module_source(20);
+    let ast = syn::parse_file(&source).expect("parse fixture");
+    let paths: Vec<PathBuf> = (0..32)
+        .map(|i| root.join(fixture::module_path(i)))
+        .collect();
+    let contexts: Vec<FileContext<'_>> = paths
+        .iter()
+        .map(|path| FileContext::new(path, &source, root))
+        .collect();
+
+    let mut group = c.benchmark_group("declarative");
+    for scopes in [8, 64, 256] {
+        let rules = load_rules_from_toml(&fixture::declarative_toml(scopes)).expect("load rules");
+        group.bench_with_input(BenchmarkId::new("scopes", scopes), &rules, |b, rules| {
+            b.iter(|| {
+                contexts
+                    .iter()
+                    .flat_map(|ctx| rules.iter().flat_map(|rule| rule.check(ctx, &ast)))
+                    .count()
+            });
+        });
+    }
+    group.finish();
+}
+
+fn glob_matches(c: &mut Criterion) {
+    let pattern = GlobPattern::new("src/layer3/**").expect("valid glob");
+    let paths: Vec<PathBuf> = (0..256)
+        .map(|i| PathBuf::from(fixture::module_path(i)))
+        .collect();
+
+    c.bench_function("glob/matches", |b| {
+        b.iter(|| {
+            paths
+                .iter()
+                .filter(|path| pattern.matches(black_box(path)))
+                .count()
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    parse_large_file,
+    recommended_over_crate,
+    rules_per_file,
+    project_rule_asts,
+    declarative_scopes,
+    glob_matches
+);
+criterion_main!(benches);
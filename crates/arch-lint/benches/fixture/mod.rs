@@ -0,0 +1,107 @@
+//! Deterministic fixtures for the analyzer benchmarks.
+//!
+//! Everything here is generated from the given sizes alone, so two runs (or
+//! two machines) benchmark byte-identical input.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Number of layer directories the fixture crate is spread over.
+pub const LAYERS: usize = 8;
+
+/// Generates one module with `functions` functions and a type per function.
+///
+/// The bodies mix the constructs built-in rules look at: `unwrap`, `?`,
+/// `match`, `async fn`, `std::fs`, and `use` items.
+pub fn module_source(functions: usize) -> String {
+    let mut src =
+        String::from("use std::collections::HashMap;\nuse std::io::Read;\n\n/// Module docs.\n");
+    for i in 0..functions {
+        let _ = write!(
+            src,
+            r#"
+/// Item {i}.
+#[derive(Debug, Clone)]
+pub struct Item{i} {{
+    pub id: u64,
+    pub name: String,
+}}
+
+/// Loads item {i}.
+pub async fn load_{i}(map: &HashMap<u64, String>, key: Option<u64>) -> Result<Item{i}, String> {{
+    let id = key.unwrap_or({i});
+    let name = match map.get(&id) {{
+        Some(name) if name.is_empty() => return Err("empty".to_string()),
+        Some(name) => name.clone(),
+        None => std::fs::read_to_string("fallback").map_err(|e| e.to_string())?,
+    }};
+    let parsed: u64 = name.len().to_string().parse().unwrap();
+    if parsed > 10 && id % 2 == 0 {{
+        Ok(Item{i} {{ id, name }})
+    }} else {{
+        Err(format!("item {{id}} rejected"))
+    }}
+}}
+"#
+        );
+    }
+    src
+}
+
+/// Relative path of module `index` in the fixture crate.
+pub fn module_path(index: usize) -> String {
+    format!("src/layer{}/module{index}.rs", index % LAYERS)
+}
+
+/// Writes a crate with `modules` modules of `functions` functions each.
+pub fn write_crate(root: &Path, modules: usize, functions: usize) {
+    let source = module_source(functions);
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\n",
+    )
+    .expect("write manifest");
+    for index in 0..modules {
+        let path = root.join(module_path(index));
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create layer dir");
+        std::fs::write(path, &source).expect("write module");
+    }
+}
+
+/// Declarative config with `scopes` scopes and one rule of each per-file
+/// kind per scope.
+pub fn declarative_toml(scopes: usize) -> String {
+    let mut toml = String::new();
+    for i in 0..scopes {
+        let layer = i % LAYERS;
+        let _ = write!(
+            toml,
+            r#"
+[[scopes]]
+name = "scope{i}"
+paths = ["src/layer{layer}/**", "src/other{i}/**/*.rs"]
+
+[[restrict-use]]
+name = "restrict{i}"
+scope = "scope{i}"
+deny = ["sqlx::*", "diesel::*", "std::io::*"]
+message = "denied"
+
+[[forbid-pattern]]
+name = "pattern{i}"
+scope = "scope{i}"
+kind = "method-call"
+patterns = ["unwrap", "expect"]
+message = "forbidden"
+"#
+        );
+        if i > 0 {
+            let _ = write!(
+                toml,
+                "\n[[deny-scope-dep]]\nfrom = \"scope{i}\"\nto = [\"scope{}\"]\nmessage = \"layering\"\n",
+                i - 1
+            );
+        }
+    }
+    toml
+}
@@ -3,38 +3,48 @@
 //! This module is `#[doc(hidden)]` and not part of the public API.
 //! It is called by the generated test function from `arch_lint::check!()`.
 
-use arch_lint_core::{Analyzer, Config, Severity};
-use arch_lint_rules::Preset;
+use arch_lint_core::declarative::{self, DeclarativeRules};
+use arch_lint_core::{Analyzer, Config, ConfigFormat, GateConfig, LintResult, RuleBox, Severity};
 use std::path::{Path, PathBuf};
 
-/// Config file names to search for, in priority order.
-const CONFIG_CANDIDATES: &[&str] = &["arch-lint.toml", ".arch-lint.toml"];
-
 /// Runs arch-lint analysis as part of `cargo test`.
 ///
 /// Called by the `check!()` macro-generated test function.
 /// Panics with a formatted report if violations are found.
 ///
+/// `custom_rules` (from `check!(rules = [...])`) run alongside the preset
+/// and declarative rules; see [`rule_set`].
+///
 /// # Panics
 ///
-/// Panics if violations at or above `fail_on` severity are found,
-/// or if the analyzer cannot be built.
-pub fn run_check(preset: Option<&str>, config_path: Option<&str>, fail_on: Option<&str>) {
-    let root = find_project_root();
-    let content = read_config_content(&root, config_path);
-    let config = parse_config(&content);
-
-    let effective_preset = resolve_preset(preset, &config);
+/// Panics if violations at or above `fail_on` severity are found, if a
+/// `[gate]` count limit is exceeded, or if the analyzer cannot be built.
+pub fn run_check(
+    preset: Option<&str>,
+    config_path: Option<&str>,
+    fail_on: Option<&str>,
+    custom_rules: Vec<RuleBox>,
+) {
+    let start = discovery_start();
+    let root = arch_lint_core::find_project_root(&start);
+    let (content, format, config_file) = read_config_content(&root, &start, config_path);
+    let config = parse_config(&content, format);
+
+    let preset_rules = resolve_preset(preset, &config);
     let effective_fail_on = resolve_fail_on(fail_on, &config);
-    let preset_rules = effective_preset.rules();
-    let declarative_rules = load_declarative_rules(&content);
+    let gate = config.gate;
+    let rules = rule_set(preset_rules, custom_rules);
+    let declarative = load_declarative_rules(&content, format, config_file.as_deref());
 
     let mut builder = Analyzer::builder().root(&root).config(config);
-    for rule in preset_rules {
+    for rule in rules.into_iter().chain(declarative.rules) {
         builder = builder.rule_box(rule);
     }
-    for rule in declarative_rules {
-        builder = builder.rule_box(rule);
+    for rule in arch_lint_rules::project_rules()
+        .into_iter()
+        .chain(declarative.project_rules)
+    {
+        builder = builder.project_rule_box(rule);
     }
 
     let analyzer = builder.build().unwrap_or_else(|e| {
@@ -45,146 +55,171 @@ pub fn run_check(preset: Option<&str>, config_path: Option<&str>, fail_on: Optio
         panic!("arch-lint: analysis failed: {e}");
     });
 
-    if result.has_violations_at(effective_fail_on) {
-        let report = result.format_test_report(effective_fail_on);
+    if let Some(report) = failure_report(&result, effective_fail_on, &gate) {
         panic!("{report}");
     }
 }
 
-/// Reads the raw TOML content from the config file.
+/// Returns the preset's rules followed by the custom rules.
+///
+/// A custom rule replaces the preset rule of the same name, so a built-in
+/// rule can be swapped for a differently configured instance. Like preset
+/// rules, custom rules are configured by `[rules.<name>]` and run before
+/// the config's declarative rules.
+fn rule_set(preset_rules: Vec<RuleBox>, custom_rules: Vec<RuleBox>) -> Vec<RuleBox> {
+    let mut rules = preset_rules;
+    rules.retain(|rule| !custom_rules.iter().any(|c| c.name() == rule.name()));
+    rules.extend(custom_rules);
+    rules
+}
+
+/// Returns the test failure report if `result` fails the `fail_on` severity
+/// gate or exceeds a `[gate]` count limit.
+///
+/// When only a count limit fails, the report lists violations down to the
+/// least severe breached level and names each breached limit.
+fn failure_report(result: &LintResult, fail_on: Severity, gate: &GateConfig) -> Option<String> {
+    use std::fmt::Write;
+
+    let breaches = gate.breaches(result);
+    if !result.has_violations_at(fail_on) && breaches.is_empty() {
+        return None;
+    }
+
+    let shown = breaches
+        .iter()
+        .map(|b| b.severity)
+        .fold(fail_on, Severity::min);
+    let mut report = result.format_test_report(shown);
+    for breach in &breaches {
+        let _ = writeln!(report, "Gate failed: {breach}");
+    }
+    Some(report)
+}
+
+/// Reads the raw content of the config file, with its format.
 ///
-/// Returns an empty string if no config file is found.
-fn read_config_content(root: &Path, explicit_path: Option<&str>) -> String {
+/// An explicit path is resolved against `root`; otherwise the config is
+/// discovered from `start` the same way the CLI does, falling back to a
+/// `[package.metadata.arch-lint]` table. Returns an empty string if no
+/// config is found.
+fn read_config_content(
+    root: &Path,
+    start: &Path,
+    explicit_path: Option<&str>,
+) -> (String, ConfigFormat, Option<PathBuf>) {
     if let Some(path) = explicit_path {
         let full_path = if Path::new(path).is_absolute() {
             PathBuf::from(path)
         } else {
             root.join(path)
         };
-        return std::fs::read_to_string(&full_path).unwrap_or_else(|e| {
+        let content = std::fs::read_to_string(&full_path).unwrap_or_else(|e| {
             panic!(
                 "arch-lint: failed to read config from {}: {e}",
                 full_path.display()
             );
         });
+        let format = ConfigFormat::from_path(&full_path);
+        return (content, format, Some(full_path));
     }
 
-    for candidate in CONFIG_CANDIDATES {
-        let path = root.join(candidate);
-        if path.exists() {
-            return std::fs::read_to_string(&path).unwrap_or_else(|e| {
-                panic!(
-                    "arch-lint: failed to read config from {}: {e}",
-                    path.display()
-                );
-            });
+    if let Some(path) = arch_lint_core::find_config(start) {
+        let content = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "arch-lint: failed to read config from {}: {e}",
+                path.display()
+            );
+        });
+        let format = ConfigFormat::from_path(&path);
+        return (content, format, Some(path));
+    }
+
+    if let Some(manifest) = arch_lint_core::find_cargo_metadata(start) {
+        if let Some(content) = arch_lint_core::cargo_metadata_config(&manifest) {
+            return (content, ConfigFormat::Toml, Some(manifest));
         }
     }
 
-    String::new()
+    (String::new(), ConfigFormat::Toml, None)
 }
 
-/// Parses a `Config` from TOML content.
-fn parse_config(content: &str) -> Config {
+/// Parses a `Config` from config file content.
+fn parse_config(content: &str, format: ConfigFormat) -> Config {
     if content.is_empty() {
         return Config::default();
     }
-    Config::parse(content).unwrap_or_else(|e| {
+    Config::parse_as(content, format).unwrap_or_else(|e| {
         panic!("arch-lint: failed to parse config: {e}");
     })
 }
 
-/// Loads declarative rules from TOML content.
-///
-/// Returns an empty vec if no declarative sections are present.
-fn load_declarative_rules(content: &str) -> Vec<arch_lint_core::RuleBox> {
-    if content.is_empty() {
-        return vec![];
+/// Loads the per-file and project-wide declarative rules from config file
+/// content, read from `config_file` when there is one.
+fn load_declarative_rules(
+    content: &str,
+    format: ConfigFormat,
+    config_file: Option<&Path>,
+) -> DeclarativeRules {
+    match config_file {
+        Some(path) => declarative::load_file(path, content, format),
+        None => declarative::load_all(content, format),
     }
-    arch_lint_core::declarative::load_rules_from_toml(content)
-        .unwrap_or_else(|e| panic!("arch-lint: declarative config error: {e}"))
+    .unwrap_or_else(|e| panic!("arch-lint: declarative config error: {e}"))
 }
 
-/// Checks whether a `Cargo.toml` file defines a `[workspace]` section
-/// by parsing as TOML, avoiding false positives from comments or strings.
-fn has_workspace_section(cargo_toml: &Path) -> bool {
-    let Ok(content) = std::fs::read_to_string(cargo_toml) else {
-        return false;
-    };
-    let Ok(table) = content.parse::<toml::Table>() else {
-        return false;
-    };
-    table.contains_key("workspace")
+/// Returns the directory config discovery starts from: the crate
+/// containing the test (`CARGO_MANIFEST_DIR`), or the current directory.
+fn discovery_start() -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."))
 }
 
-/// Finds the project root by looking for `Cargo.toml` from `CARGO_MANIFEST_DIR`.
-fn find_project_root() -> PathBuf {
-    // CARGO_MANIFEST_DIR points to the crate containing the test,
-    // which may be a workspace member. Walk up to find workspace root.
-    if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
-        let manifest_path = PathBuf::from(&manifest_dir);
-
-        // Check if there's a workspace Cargo.toml above
-        let mut candidate = manifest_path.as_path();
-        loop {
-            let cargo_toml = candidate.join("Cargo.toml");
-            if cargo_toml.exists() && has_workspace_section(&cargo_toml) {
-                return candidate.to_path_buf();
-            }
-            match candidate.parent() {
-                Some(parent) => candidate = parent,
-                None => break,
-            }
-        }
-
-        // No workspace root found — use manifest dir itself
-        return manifest_path;
-    }
-
-    // Fallback: current directory
-    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
-}
-
-/// Resolves the effective preset from macro arg > config > default.
-fn resolve_preset(macro_arg: Option<&str>, config: &Config) -> Preset {
+/// Resolves the effective preset from macro arg > config > default to its
+/// rules; the name may refer to a custom `[presets.<name>]` table.
+fn resolve_preset(macro_arg: Option<&str>, config: &Config) -> Vec<RuleBox> {
     let name = macro_arg
         .or(config.preset.as_deref())
         .unwrap_or("recommended");
 
-    match name {
-        "recommended" => Preset::Recommended,
-        "strict" => Preset::Strict,
-        "minimal" => Preset::Minimal,
-        other => panic!(
-            "arch-lint: unknown preset `{other}`. Valid presets: recommended, strict, minimal"
-        ),
-    }
+    arch_lint_rules::preset_rules(name, config).unwrap_or_else(|e| panic!("arch-lint: {e}"))
 }
 
-/// Resolves the effective `fail_on` severity from macro arg > config > default.
+/// Resolves the effective `fail_on` severity from macro arg > config > default,
+/// lowered to `warning` when `deny_warnings` is set.
 ///
 /// Priority: explicit macro arg > config file > default ("error").
 fn resolve_fail_on(macro_arg: Option<&str>, config: &Config) -> Severity {
     let name = macro_arg.or(config.fail_on.as_deref()).unwrap_or("error");
 
-    match name {
-        "error" => Severity::Error,
-        "warning" => Severity::Warning,
-        "info" => Severity::Info,
-        other => {
-            panic!("arch-lint: unknown severity `{other}`. Valid values: error, warning, info")
-        }
+    let fail_on: Severity = name
+        .parse()
+        .unwrap_or_else(|e| panic!("arch-lint: invalid `fail_on`: {e}"));
+    if config.deny_warnings {
+        fail_on.min(Severity::Warning)
+    } else {
+        fail_on
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arch_lint_rules::Preset;
+
+    fn names(rules: &[RuleBox]) -> Vec<&'static str> {
+        rules.iter().map(|rule| rule.name()).collect()
+    }
 
     #[test]
     fn resolve_preset_defaults_to_recommended() {
         let config = Config::default();
-        assert_eq!(resolve_preset(None, &config), Preset::Recommended);
+        assert_eq!(
+            names(&resolve_preset(None, &config)),
+            names(&Preset::Recommended.rules())
+        );
     }
 
     #[test]
@@ -192,14 +227,32 @@ mod tests {
         let mut config = Config::default();
         config.preset = Some("minimal".to_string());
         // macro arg "strict" overrides config "minimal"
-        assert_eq!(resolve_preset(Some("strict"), &config), Preset::Strict);
+        assert_eq!(
+            names(&resolve_preset(Some("strict"), &config)),
+            names(&Preset::Strict.rules())
+        );
     }
 
     #[test]
     fn resolve_preset_from_config() {
         let mut config = Config::default();
         config.preset = Some("strict".to_string());
-        assert_eq!(resolve_preset(None, &config), Preset::Strict);
+        assert_eq!(
+            names(&resolve_preset(None, &config)),
+            names(&Preset::Strict.rules())
+        );
+    }
+
+    #[test]
+    fn resolve_preset_custom_from_config() {
+        let config = Config::parse(
+            "preset = \"team\"\n\n[presets.team]\nextends = \"minimal\"\nenable = [\"AL002\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            names(&resolve_preset(None, &config)),
+            ["no-unwrap-expect", "no-sync-io"]
+        );
     }
 
     #[test]
@@ -238,6 +291,26 @@ mod tests {
         assert_eq!(resolve_fail_on(Some("error"), &config), Severity::Error);
     }
 
+    #[test]
+    fn resolve_fail_on_deny_warnings_lowers_to_warning() {
+        let config = Config {
+            deny_warnings: true,
+            ..Config::default()
+        };
+        assert_eq!(resolve_fail_on(None, &config), Severity::Warning);
+        assert_eq!(resolve_fail_on(Some("error"), &config), Severity::Warning);
+        // A looser threshold is kept.
+        assert_eq!(resolve_fail_on(Some("info"), &config), Severity::Info);
+
+        let report = failure_report(
+            &result_with(&[Severity::Warning]),
+            resolve_fail_on(None, &config),
+            &GateConfig::default(),
+        )
+        .expect("warning fails");
+        assert!(report.contains("warning"), "{report}");
+    }
+
     #[test]
     #[should_panic(expected = "unknown severity")]
     fn resolve_fail_on_invalid_panics() {
@@ -245,12 +318,91 @@ mod tests {
         resolve_fail_on(Some("critical"), &config);
     }
 
+    #[test]
+    fn rule_set_appends_custom_rules_replacing_same_name() {
+        use arch_lint_rules::{MaxFnParams, NoUnwrapExpect};
+
+        let custom: Vec<RuleBox> = vec![
+            Box::new(NoUnwrapExpect::new().allow_expect(false)),
+            Box::new(MaxFnParams::new()),
+        ];
+        let rules = rule_set(Preset::Minimal.rules(), custom);
+        let names: Vec<&str> = rules.iter().map(|r| r.name()).collect();
+        assert_eq!(names, ["no-unwrap-expect", "max-fn-params"]);
+
+        assert_eq!(
+            rule_set(Preset::Recommended.rules(), Vec::new()).len(),
+            Preset::Recommended.rules().len()
+        );
+    }
+
     // ── Declarative rules loading ──
 
+    fn result_with(severities: &[Severity]) -> LintResult {
+        LintResult {
+            violations: severities
+                .iter()
+                .map(|&severity| {
+                    arch_lint_core::Violation::new(
+                        "AL001",
+                        "rule",
+                        severity,
+                        arch_lint_core::Location::new(PathBuf::from("src/lib.rs"), 1, 1),
+                        "msg",
+                    )
+                })
+                .collect(),
+            files_checked: 1,
+            files_skipped: 0,
+        }
+    }
+
+    #[test]
+    fn failure_report_applies_gate_thresholds() {
+        let config = parse_config(
+            "[gate]\nmax_errors = 1\nmax_warnings = 2\n",
+            ConfigFormat::Toml,
+        );
+
+        // At the limit.
+        let warnings = result_with(&[Severity::Warning; 2]);
+        assert!(failure_report(&warnings, Severity::Error, &config.gate).is_none());
+
+        // Above max_warnings.
+        let warnings = result_with(&[Severity::Warning; 3]);
+        let report = failure_report(&warnings, Severity::Error, &config.gate)
+            .expect("max_warnings exceeded");
+        assert!(report.contains("3 violation(s)"), "{report}");
+        assert!(report.contains("Gate failed: 3 warning(s) exceed max_warnings = 2"));
+
+        // fail_on still applies below max_errors.
+        let errors = result_with(&[Severity::Error]);
+        let report = failure_report(&errors, Severity::Error, &config.gate).expect("fail_on");
+        assert!(!report.contains("Gate failed"), "{report}");
+    }
+
+    #[test]
+    fn load_declarative_rules_from_yaml() {
+        let yaml = r#"
+scopes:
+  - name: domain
+    paths: ["src/domain/**"]
+restrict-use:
+  - name: no-sqlx
+    scope: domain
+    deny: ["sqlx::*"]
+    message: No DB in domain.
+"#;
+        let rules = load_declarative_rules(yaml, ConfigFormat::Yaml, None).rules;
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name(), "restrict-use");
+    }
+
     #[test]
     fn load_declarative_rules_empty_content() {
-        let rules = load_declarative_rules("");
-        assert!(rules.is_empty());
+        let declarative = load_declarative_rules("", ConfigFormat::Toml, None);
+        assert!(declarative.rules.is_empty());
+        assert!(declarative.project_rules.is_empty());
     }
 
     #[test]
@@ -259,7 +411,7 @@ mod tests {
 preset = "recommended"
 fail_on = "error"
 "#;
-        let rules = load_declarative_rules(toml);
+        let rules = load_declarative_rules(toml, ConfigFormat::Toml, None).rules;
         assert!(rules.is_empty());
     }
 
@@ -276,7 +428,7 @@ scope = "domain"
 deny = ["sqlx::*"]
 message = "No DB in domain."
 "#;
-        let rules = load_declarative_rules(toml);
+        let rules = load_declarative_rules(toml, ConfigFormat::Toml, None).rules;
         assert_eq!(rules.len(), 1);
         assert_eq!(rules[0].name(), "restrict-use");
     }
@@ -310,8 +462,12 @@ from = "domain"
 to = ["infra"]
 message = "Domain must not depend on infra."
 "#;
-        let rules = load_declarative_rules(toml);
+        let declarative = load_declarative_rules(toml, ConfigFormat::Toml, None);
+        let rules = declarative.rules;
         assert_eq!(rules.len(), 3);
+        // Scopes also enable the project-wide empty-scope check.
+        let project: Vec<&str> = declarative.project_rules.iter().map(|r| r.name()).collect();
+        assert_eq!(project, ["empty-scope"]);
 
         let names: Vec<&str> = rules.iter().map(|r| r.name()).collect();
         assert!(names.contains(&"restrict-use"));
@@ -335,7 +491,51 @@ scope = "domain"
 deny = ["sqlx::*"]
 message = "No DB."
 "#;
-        let config = parse_config(toml);
+        let config = parse_config(toml, ConfigFormat::Toml);
         assert_eq!(config.preset.as_deref(), Some("minimal"));
     }
+
+    #[test]
+    fn read_config_content_discovers_workspace_config_from_nested_crate() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("arch-lint.yaml"), "preset: strict\n").unwrap();
+        let member = tmp.path().join("crates/app");
+        std::fs::create_dir_all(&member).unwrap();
+        std::fs::write(member.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+
+        let root = arch_lint_core::find_project_root(&member);
+        assert_eq!(root, tmp.path());
+        let (content, format, _) = read_config_content(&root, &member, None);
+        assert_eq!(content, "preset: strict\n");
+        assert_eq!(format, ConfigFormat::Yaml);
+    }
+
+    #[test]
+    fn read_config_content_falls_back_to_cargo_metadata() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[package.metadata.arch-lint]\npreset = \"minimal\"\n",
+        )
+        .unwrap();
+
+        let (content, format, _) = read_config_content(tmp.path(), tmp.path(), None);
+        assert_eq!(format, ConfigFormat::Toml);
+        assert_eq!(
+            parse_config(&content, format).preset.as_deref(),
+            Some("minimal")
+        );
+
+        std::fs::write(tmp.path().join("arch-lint.toml"), "preset = \"strict\"\n").unwrap();
+        let (content, format, _) = read_config_content(tmp.path(), tmp.path(), None);
+        assert_eq!(
+            parse_config(&content, format).preset.as_deref(),
+            Some("strict")
+        );
+    }
 }
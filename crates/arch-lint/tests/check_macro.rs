@@ -9,3 +9,35 @@ arch_lint::check!(
     preset = "minimal",
     config = "crates/arch-lint/tests/test-config.toml",
 );
+
+/// `check!(rules = [...])` runs custom rules alongside the preset.
+mod custom_rules {
+    use arch_lint::{FileContext, Rule, Violation};
+
+    /// Reports nothing; exercises registration of a user-defined rule.
+    #[derive(Debug)]
+    struct NoOp;
+
+    impl Rule for NoOp {
+        fn name(&self) -> &'static str {
+            "no-op"
+        }
+
+        fn code(&self) -> &'static str {
+            "CUSTOM001"
+        }
+
+        fn check(&self, _ctx: &FileContext<'_>, _ast: &syn::File) -> Vec<Violation> {
+            Vec::new()
+        }
+    }
+
+    arch_lint::check!(
+        preset = "minimal",
+        config = "crates/arch-lint/tests/test-config.toml",
+        rules = [
+            NoOp,
+            arch_lint::rules::NoUnwrapExpect::new().allow_expect(true)
+        ],
+    );
+}